@@ -1,3 +1,11 @@
 fn main() {
-    tauri_build::build()
+    #[cfg(feature = "desktop")]
+    tauri_build::build();
+
+    // tauri-build declares these platform cfgs; headless builds set neither.
+    #[cfg(not(feature = "desktop"))]
+    {
+        println!("cargo::rustc-check-cfg=cfg(desktop)");
+        println!("cargo::rustc-check-cfg=cfg(mobile)");
+    }
 }
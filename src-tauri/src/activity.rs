@@ -5,6 +5,8 @@ pub enum AppActivity {
     Recording,
     Updating,
     Configuring,
+    /// Typing text sent through the `type_text` command.
+    Typing,
 }
 
 #[derive(thiserror::Error, Debug)]
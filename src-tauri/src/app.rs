@@ -6,16 +6,31 @@ use crate::commands;
 #[cfg(desktop)]
 use crate::desktop;
 use crate::engine::SpeechEngine;
+use crate::extensions::Extensions;
+use crate::post_process::PostProcessor;
 
 #[cfg(not(debug_assertions))]
 const AUTOMATIC_UPDATE_INTERVAL: std::time::Duration = std::time::Duration::from_secs(6 * 60 * 60);
 
 #[cfg_attr(mobile, tauri::mobile_entry_point)]
 pub fn run() {
+    start(Extensions::new(), PostProcessor::builtin());
+}
+
+/// Runs the app with a downstream build's extensions and post-processing
+/// stages added. `post_processor` usually starts from
+/// [`PostProcessor::builtin`] so the built-in stages stay available.
+#[cfg(feature = "extensions")]
+pub fn run_with(extensions: Extensions, post_processor: PostProcessor) {
+    start(extensions, post_processor);
+}
+
+fn start(extensions: Extensions, post_processor: PostProcessor) {
     let context = tauri::generate_context!();
 
     let app = Builder::default()
         .plugin(tauri_plugin_single_instance::init(on_second_instance))
+        .plugin(tauri_plugin_deep_link::init())
         .plugin(
             tauri_plugin_log::Builder::new()
                 .targets([
@@ -23,6 +38,7 @@ pub fn run() {
                     tauri_plugin_log::Target::new(tauri_plugin_log::TargetKind::LogDir {
                         file_name: None,
                     }),
+                    recent_logs_target(),
                 ])
                 .rotation_strategy(tauri_plugin_log::RotationStrategy::KeepAll)
                 .max_file_size(2_000_000)
@@ -32,26 +48,188 @@ pub fn run() {
         )
         .plugin(tauri_plugin_store::Builder::default().build())
         .plugin(tauri_plugin_dialog::init())
+        .plugin(tauri_plugin_clipboard_manager::init())
         .plugin(tauri_plugin_updater::Builder::new().build())
-        .setup(|app| {
+        .setup(move |app| {
             let handle = app.handle().clone();
             app.manage(SpeechEngine::new(handle));
+            app.manage(post_processor);
+            if !extensions.names().is_empty() {
+                log::info!("Extensions: {}", extensions.names().join(", "));
+            }
+            app.manage(extensions);
             setup(app)
         })
         .on_window_event(handle_window_event)
         .invoke_handler(tauri::generate_handler![
             commands::retry_model_download,
+            commands::skip_model_load,
+            commands::pause_download,
+            commands::resume_download,
+            commands::cancel_download,
             commands::get_model_path,
             commands::set_model_path,
             commands::pick_model_folder,
+            commands::pick_model_archive,
+            commands::pick_audio_file,
+            commands::transcribe_audio_file,
+            commands::cancel_file_transcription,
+            commands::import_model_archive,
             commands::start_recording,
             commands::stop_recording,
             commands::engine_state,
+            commands::audio_health,
+            commands::capture_quality,
+            commands::list_speech_stats,
+            commands::subscribe_vad_events,
+            commands::unsubscribe_vad_events,
+            commands::get_recent_logs,
+            commands::is_recording,
             commands::update_record_shortcut,
+            commands::begin_shortcut_capture,
+            commands::get_shortcut_conflict,
+            commands::get_onboarding,
+            commands::check_onboarding_microphone,
+            commands::test_onboarding_microphone,
+            commands::confirm_onboarding_shortcut,
+            commands::skip_onboarding_step,
+            commands::finish_onboarding,
+            commands::restart_onboarding,
             commands::get_record_shortcut,
             commands::default_record_shortcut,
             commands::get_use_streaming,
             commands::set_use_streaming,
+            commands::get_streaming_strategy,
+            commands::set_streaming_strategy,
+            commands::get_resampler_quality,
+            commands::set_resampler_quality,
+            commands::list_input_devices,
+            commands::get_input_device,
+            commands::set_input_device,
+            commands::get_vad_settings,
+            commands::set_vad_settings,
+            commands::calibrate_microphone,
+            commands::test_microphone,
+            commands::play_microphone_test,
+            commands::get_noise_suppression,
+            commands::set_noise_selection,
+            commands::capture_noise_profile,
+            commands::delete_noise_profile,
+            commands::get_speaker_adaptation,
+            commands::set_speaker_adaptation,
+            commands::reset_voice_profile,
+            commands::get_shortcut_gesture,
+            commands::set_shortcut_gesture,
+            commands::get_triggers,
+            commands::set_triggers,
+            commands::get_suppress_record_key,
+            commands::set_suppress_record_key,
+            commands::detect_mouse_button,
+            commands::list_hid_devices,
+            commands::type_text,
+            commands::open_document,
+            commands::get_document,
+            commands::set_document_text,
+            commands::pick_document_file,
+            commands::finish_document,
+            commands::get_patch_routing,
+            commands::set_patch_routing,
+            commands::get_live_waveform,
+            commands::set_live_waveform,
+            commands::get_buffer_until_model_ready,
+            commands::set_buffer_until_model_ready,
+            commands::get_model_prewarm,
+            commands::set_model_prewarm,
+            commands::get_power_saver,
+            commands::set_power_saver,
+            commands::get_recording_indicator,
+            commands::set_recording_indicator,
+            commands::get_hardware_detection,
+            commands::detect_hardware,
+            commands::get_dictation_enabled,
+            commands::set_dictation_enabled,
+            commands::get_quiet_hours,
+            commands::set_quiet_hours,
+            commands::get_quiet_hours_active,
+            commands::get_webhook,
+            commands::set_webhook,
+            commands::get_llm_handoff,
+            commands::set_llm_handoff,
+            commands::get_dictation_toggle_shortcut,
+            commands::set_dictation_toggle_shortcut,
+            commands::get_numbers_mode,
+            commands::set_numbers_mode,
+            commands::get_output_templates,
+            commands::set_output_templates,
+            commands::get_capture_file,
+            commands::set_capture_file,
+            commands::get_decode_cache,
+            commands::set_decode_cache,
+            commands::clear_decode_cache,
+            commands::get_lookback,
+            commands::set_lookback,
+            commands::get_encrypt_at_rest,
+            commands::set_encrypt_at_rest,
+            commands::get_deep_links_enabled,
+            commands::set_deep_links_enabled,
+            commands::pick_capture_file,
+            commands::list_history_entries,
+            commands::rename_history_entry,
+            commands::retranscribe_history_utterance,
+            commands::retranscribe_history_entry,
+            commands::set_history_utterances,
+            commands::pick_digest_folder,
+            commands::export_digest,
+            commands::get_caret_spacing,
+            commands::set_caret_spacing,
+            commands::get_continuation,
+            commands::set_continuation,
+            commands::negotiate_patch_protocol,
+            commands::get_inactivity,
+            commands::set_inactivity,
+            commands::keep_recording,
+            commands::get_announce_transcripts,
+            commands::set_announce_transcripts,
+            commands::get_speak_transcripts,
+            commands::set_speak_transcripts,
+            commands::get_network_input,
+            commands::set_network_input,
+            commands::get_typed_metadata,
+            commands::set_typed_metadata,
+            commands::get_autostart,
+            commands::set_autostart,
+            commands::pick_config_export_file,
+            commands::pick_config_import_file,
+            commands::export_config,
+            commands::import_config,
+            commands::get_settings_lock,
+            commands::set_settings_lock,
+            commands::remove_settings_lock,
+            commands::unlock_settings,
+            commands::lock_settings,
+            commands::get_profiles,
+            commands::create_profile,
+            commands::delete_profile,
+            commands::switch_profile,
+            commands::get_companion,
+            commands::set_companion,
+            commands::start_companion_pairing,
+            commands::revoke_companion_device,
+            commands::get_typing_pace,
+            commands::set_typing_pace,
+            commands::get_ort_threads,
+            commands::set_ort_threads,
+            commands::tune_ort_threads,
+            commands::model_signature_report,
+            commands::get_pause_markers,
+            commands::set_pause_markers,
+            commands::get_time_stretch,
+            commands::set_time_stretch,
+            commands::get_text_commands,
+            commands::set_text_commands,
+            commands::list_post_process_stages,
+            commands::get_post_processing,
+            commands::set_post_processing,
             commands::get_asr_language,
             commands::get_asr_languages,
             commands::set_asr_language,
@@ -72,6 +250,14 @@ pub fn run() {
     app.run(handle_run_event);
 }
 
+/// Feeds the in-app log viewer alongside stdout and the log files.
+fn recent_logs_target() -> tauri_plugin_log::Target {
+    let sink: Box<dyn log::Log> = Box::new(crate::recent_logs::RecentLogsSink);
+    tauri_plugin_log::Target::new(tauri_plugin_log::TargetKind::Dispatch(
+        tauri_plugin_log::fern::Dispatch::new().chain(sink),
+    ))
+}
+
 fn handle_window_event(window: &tauri::Window, event: &WindowEvent) {
     if let WindowEvent::CloseRequested { api, .. } = event {
         if let Err(err) = window.hide() {
@@ -89,10 +275,7 @@ fn handle_run_event(app_handle: &AppHandle, event: RunEvent) {
     } = event
     {
         if !has_visible_windows {
-            if let Some(window) = app_handle.get_webview_window("main") {
-                let _ = window.show();
-                let _ = window.set_focus();
-            }
+            desktop::show_main_window(app_handle);
         }
     }
 }
@@ -100,8 +283,16 @@ fn handle_run_event(app_handle: &AppHandle, event: RunEvent) {
 #[cfg(not(target_os = "macos"))]
 fn handle_run_event(_app_handle: &AppHandle, _event: RunEvent) {}
 
+/// A second launch with commands on its command line runs them here instead
+/// of opening the window, so scripts and OS shortcuts can drive the app.
 fn on_second_instance(app: &AppHandle, argv: Vec<String>, cwd: String) {
     log::info!("Second instance detected (args={argv:?}, cwd={cwd})");
+    #[cfg(desktop)]
+    if desktop::run_command_line(app, &argv, std::path::Path::new(&cwd)) {
+        return;
+    }
+    #[cfg(desktop)]
+    desktop::show_main_window(app);
     if let Err(err) = app.emit("single-instance", ()) {
         log::error!("Failed to emit single-instance event: {err}");
     }
@@ -109,16 +300,83 @@ fn on_second_instance(app: &AppHandle, argv: Vec<String>, cwd: String) {
 
 fn setup(app: &mut App) -> Result<(), Box<dyn std::error::Error>> {
     schedule_automatic_updates(app.handle().clone());
+    publish_vad_events(app.handle().clone());
+    publish_waveform(app.handle().clone());
 
     #[cfg(desktop)]
     {
         desktop::setup_desktop(app)?;
-        prewarm_model(app.handle().clone());
+        detect_hardware_on_first_run(app.handle());
+        let prewarm = crate::settings::get_settings(app.handle()).effective_model_prewarm();
+        if prewarm == crate::settings::ModelPrewarm::OnLaunch {
+            prewarm_model(app.handle().clone());
+        } else {
+            log::info!("Deferring speech model load ({prewarm:?})");
+        }
+        register_deep_links(app.handle());
+        // The first launch runs its own command line too, so a script works
+        // whether or not the app was already open.
+        let args = std::env::args().collect::<Vec<_>>();
+        match std::env::current_dir() {
+            Ok(cwd) => {
+                desktop::run_command_line(app.handle(), &args, &cwd);
+            }
+            Err(error) => log::warn!("Could not read the working directory: {error}"),
+        }
     }
 
     Ok(())
 }
 
+/// Windows and Linux open a `silentkeys:` link by launching the app with it as
+/// an argument, which the command line path runs; macOS hands it to the
+/// running app instead. Installers register the scheme, so only an unbundled
+/// Windows or Linux build, or an AppImage, which nothing installs, registers
+/// it itself. Links are run only while the deep links setting is on.
+#[cfg(desktop)]
+fn register_deep_links(app: &AppHandle) {
+    use tauri_plugin_deep_link::DeepLinkExt;
+
+    #[cfg(any(windows, target_os = "linux"))]
+    {
+        use tauri::utils::{config::BundleType, platform::bundle_type};
+
+        if matches!(bundle_type(), None | Some(BundleType::AppImage)) {
+            if let Err(error) = app.deep_link().register_all() {
+                log::warn!(
+                    "Could not register {}: links: {error}",
+                    crate::cli::DEEP_LINK_SCHEME
+                );
+            }
+        }
+    }
+    #[cfg(target_os = "macos")]
+    {
+        let link_app = app.clone();
+        app.deep_link().on_open_url(move |event| {
+            for url in event.urls() {
+                desktop::run_deep_link(&link_app, url.as_str());
+            }
+        });
+    }
+}
+
+fn publish_vad_events(app: AppHandle) {
+    crate::recording::vad_monitor().set_sink(move |events| {
+        if let Err(error) = app.emit(crate::recording::VAD_EVENTS_EVENT, events) {
+            log::warn!("Failed to emit voice activity: {error}");
+        }
+    });
+}
+
+fn publish_waveform(app: AppHandle) {
+    crate::recording::set_waveform_sink(move |frame| {
+        if let Err(error) = app.emit(crate::recording::WAVEFORM_EVENT, frame) {
+            log::warn!("Failed to emit waveform: {error}");
+        }
+    });
+}
+
 #[cfg(not(debug_assertions))]
 fn schedule_automatic_updates(app: AppHandle) {
     let result = std::thread::Builder::new()
@@ -142,6 +400,20 @@ fn schedule_automatic_updates(app: AppHandle) {
 #[cfg(debug_assertions)]
 fn schedule_automatic_updates(_app: AppHandle) {}
 
+/// Probes the hardware once per install. Its defaults are applied only when
+/// nothing has been saved yet, so upgrading keeps existing choices.
+#[cfg(desktop)]
+fn detect_hardware_on_first_run(app: &AppHandle) {
+    let settings = crate::settings::get_settings(app);
+    if settings.hardware_detection.is_some() {
+        return;
+    }
+    let first_run = !crate::settings::has_saved_settings(app);
+    if let Err(error) = crate::settings::record_hardware_detection(app, first_run) {
+        log::warn!("Could not save hardware detection: {error}");
+    }
+}
+
 #[cfg(desktop)]
 fn prewarm_model(app_handle: AppHandle) {
     std::thread::spawn(move || {
@@ -1,15 +1,38 @@
 mod model_store;
+mod pool;
 mod recognizer;
+mod signature;
+mod threads;
 
+#[cfg(feature = "desktop")]
+pub use model_store::default_model_root;
+pub(crate) use model_store::MODEL_SPEC;
 pub use model_store::{
-    default_model_root, fallback_model_root, invalid_model_files_for_tests,
-    model_file_matches_for_tests, resolve_model_dir, verification_receipt_matches_for_tests,
-    write_verification_receipt_for_tests,
+    cancel_model_download, discard_model_download, fallback_model_root, import_model_archive,
+    invalid_model_files_for_tests, model_file_matches_for_tests, pause_model_download,
+    resolve_model_dir, resume_model_download, verification_receipt_matches_for_tests,
+    write_verification_receipt_for_tests, ModelStoreLock,
 };
-pub(crate) use model_store::{invalidate_model_verification, resolve_model_dir_with_progress};
-pub(crate) use recognizer::STREAM_CHUNK_SAMPLES;
+#[cfg(feature = "desktop")]
+pub(crate) use model_store::{
+    current_download_progress, invalidate_model_verification, model_download_cancelled,
+    reset_model_download_cancel, resolve_model_dir_with_progress, DownloadProgress,
+};
+#[cfg(feature = "desktop")]
+pub(crate) use pool::decode_in_order;
+pub use pool::{default_pool_size, SessionPool};
 pub use recognizer::{
-    language_candidates_for_tests, language_options_for_tests, AsrError, AsrModel,
+    check_model_files_for_tests, language_candidates_for_tests, language_options_for_tests,
+    parse_model_config_for_tests, AsrError, AsrModel, ModelLoadStage, DEFAULT_ASR_LANGUAGE,
+};
+pub(crate) use recognizer::{write_model, STREAM_CHUNK_SAMPLES};
+pub use signature::{
+    is_shape_error, read_signature, signature_report, GraphSignature, ModelSignature,
+    TensorSignature,
+};
+pub use threads::{
+    available_cores, tune_threads, tuning_candidates, OrtThreads, ThreadTiming, ThreadTuning,
+    MAX_ORT_THREADS,
 };
 
 pub(crate) const TARGET_SAMPLE_RATE: u32 = 16_000;
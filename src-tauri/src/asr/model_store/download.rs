@@ -1,15 +1,15 @@
 use std::fs;
-use std::io::{Read, Write};
-use std::path::Path;
+use std::io::{self, Read, Write};
+use std::path::{Path, PathBuf};
 use std::time::Duration;
 
 use crate::asr::recognizer::AsrError;
 
 use super::paths::model_file_matches;
 use super::{
-    mark_finished, model_base_url, notify_progress, set_file_index, start_tracking,
-    update_download_bytes, DownloadProgress, ModelAsset, MAX_RETRIES, MODEL_SPEC,
-    RETRY_BACKOFF_SECS,
+    mark_finished, model_base_url, model_download_cancelled, model_download_discarded,
+    model_download_paused, notify_progress, set_file_index, start_tracking, update_download_bytes,
+    DownloadProgress, ModelAsset, MAX_RETRIES, MODEL_SPEC, RETRY_BACKOFF_SECS,
 };
 
 const CONNECT_TIMEOUT: Duration = Duration::from_secs(30);
@@ -17,6 +17,14 @@ const RESPONSE_TIMEOUT: Duration = Duration::from_secs(30);
 const TRANSFER_TIMEOUT: Duration = Duration::from_secs(4 * 60 * 60);
 const DOWNLOAD_BUFFER_BYTES: usize = 64 * 1024;
 const PROGRESS_REPORT_BYTES: u64 = 1024 * 1024;
+const PAUSE_POLL_INTERVAL: Duration = Duration::from_millis(250);
+
+/// How a request ended when it did not fail.
+enum Transfer {
+    Complete,
+    /// The user paused; the partial file holds everything received.
+    Paused,
+}
 
 pub(crate) fn download_assets(
     snapshot_dir: &Path,
@@ -28,7 +36,10 @@ pub(crate) fn download_assets(
     }
     start_tracking(assets.len(), on_progress);
 
-    assets.iter().enumerate().try_for_each(|(index, asset)| {
+    let result = assets.iter().enumerate().try_for_each(|(index, asset)| {
+        if model_download_cancelled() {
+            return Err(AsrError::Cancelled);
+        }
         set_file_index(index + 1, on_progress);
         download_asset(
             &model_base_url(),
@@ -36,11 +47,52 @@ pub(crate) fn download_assets(
             &snapshot_dir.join(asset.name),
             on_progress,
         )
-    })?;
+    });
+    if matches!(result, Err(AsrError::Cancelled)) && model_download_discarded() {
+        discard_partial_downloads(snapshot_dir, assets);
+    }
+    result?;
     mark_finished(on_progress);
     Ok(())
 }
 
+fn partial_path(dest: &Path) -> PathBuf {
+    dest.with_extension("download")
+}
+
+fn discard_partial_downloads(snapshot_dir: &Path, assets: &[ModelAsset]) {
+    for asset in assets {
+        let partial = partial_path(&snapshot_dir.join(asset.name));
+        match fs::remove_file(&partial) {
+            Ok(()) => log::info!("Discarded partial download {}", partial.display()),
+            Err(error) if error.kind() == io::ErrorKind::NotFound => {}
+            Err(error) => log::warn!(
+                "Could not discard partial download {}: {error}",
+                partial.display()
+            ),
+        }
+    }
+}
+
+/// Blocks while the download is paused; cancelling or skipping it ends the
+/// wait.
+fn wait_while_paused(on_progress: &dyn Fn(DownloadProgress)) -> Result<(), AsrError> {
+    if !model_download_paused() {
+        return Ok(());
+    }
+    log::info!("Model download paused");
+    notify_progress(on_progress);
+    while model_download_paused() {
+        if model_download_cancelled() {
+            return Err(AsrError::Cancelled);
+        }
+        std::thread::sleep(PAUSE_POLL_INTERVAL);
+    }
+    log::info!("Model download resumed");
+    notify_progress(on_progress);
+    Ok(())
+}
+
 pub(crate) fn write_revision_ref(root: &Path) -> Result<(), AsrError> {
     let refs_dir = root.join("refs");
     fs::create_dir_all(&refs_dir).map_err(|error| {
@@ -76,7 +128,7 @@ fn download_asset(
         })?;
     }
 
-    let tmp = dest.with_extension("download");
+    let tmp = partial_path(dest);
     if fs::metadata(&tmp)
         .map(|metadata| metadata.len() >= asset.size)
         .unwrap_or(false)
@@ -98,13 +150,17 @@ fn download_asset(
     let url = format!("{base_url}/{}", asset.name);
     let mut last_error = None;
 
-    for attempt in 1..=MAX_RETRIES {
+    // A pause ends a request without using up an attempt.
+    let mut attempt = 1;
+    while attempt <= MAX_RETRIES {
+        wait_while_paused(on_progress)?;
         log::info!(
             "Downloading model asset {} (attempt {attempt}/{MAX_RETRIES})",
             asset.name
         );
         match try_download_resumable(&agent, &url, &tmp, asset.size, on_progress) {
-            Ok(()) if model_file_matches(&tmp, asset)? => {
+            Ok(Transfer::Paused) => continue,
+            Ok(Transfer::Complete) if model_file_matches(&tmp, asset)? => {
                 fs::rename(&tmp, dest).map_err(|error| {
                     AsrError::io(
                         format!(
@@ -117,15 +173,17 @@ fn download_asset(
                 })?;
                 return Ok(());
             }
-            Ok(()) => {
+            Ok(Transfer::Complete) => {
                 let _ = fs::remove_file(&tmp);
                 last_error = Some(AsrError::Integrity(asset.name.to_string()));
             }
+            Err(AsrError::Cancelled) => return Err(AsrError::Cancelled),
             Err(error) => last_error = Some(error),
         }
         if attempt < MAX_RETRIES {
             std::thread::sleep(Duration::from_secs(RETRY_BACKOFF_SECS * attempt as u64));
         }
+        attempt += 1;
     }
 
     Err(last_error.unwrap_or_else(|| AsrError::Download(url)))
@@ -137,7 +195,7 @@ fn try_download_resumable(
     tmp: &Path,
     expected_size: u64,
     on_progress: &dyn Fn(DownloadProgress),
-) -> Result<(), AsrError> {
+) -> Result<Transfer, AsrError> {
     let current_len = fs::metadata(tmp)
         .map(|metadata| metadata.len())
         .unwrap_or(0);
@@ -171,6 +229,14 @@ fn try_download_resumable(
     let mut reader = response.into_body().into_reader();
     let mut buffer = [0_u8; DOWNLOAD_BUFFER_BYTES];
     loop {
+        if model_download_cancelled() {
+            log::info!("Model download of {url} skipped at {downloaded} bytes");
+            return Err(AsrError::Cancelled);
+        }
+        if model_download_paused() {
+            log::info!("Model download of {url} paused at {downloaded} bytes");
+            return Ok(Transfer::Paused);
+        }
         let count = reader
             .read(&mut buffer)
             .map_err(|error| AsrError::Download(format!("{url}: read failed: {error}")))?;
@@ -202,5 +268,5 @@ fn try_download_resumable(
             error,
         )
     })?;
-    Ok(())
+    Ok(Transfer::Complete)
 }
@@ -0,0 +1,138 @@
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use crate::asr::recognizer::AsrError;
+
+use super::lock::ModelStoreLock;
+use super::paths::{invalid_model_files, model_file_matches, persist_verification_receipt};
+use super::{download, ModelAsset, MODEL_SPEC};
+
+/// Installs assets from a directory of loose model files, such as the
+/// installer's bundled resources. Only files that pass the pinned size and
+/// SHA-256 checks are copied; anything else is left for the download path.
+pub(super) fn install_from_dir(
+    source_dir: &Path,
+    snapshot_dir: &Path,
+    assets: &[ModelAsset],
+) -> Result<usize, AsrError> {
+    let mut installed = 0;
+    for asset in assets {
+        let source = source_dir.join(asset.name);
+        if !model_file_matches(&source, *asset)? {
+            continue;
+        }
+        let dest = snapshot_dir.join(asset.name);
+        let tmp = dest.with_extension("import");
+        fs::copy(&source, &tmp).map_err(|error| {
+            AsrError::io(
+                format!("copy bundled model asset {}", source.display()),
+                error,
+            )
+        })?;
+        install_asset(&tmp, &dest)?;
+        installed += 1;
+    }
+    if installed > 0 {
+        log::info!(
+            "Installed {installed} model assets from {}",
+            source_dir.display()
+        );
+    }
+    Ok(installed)
+}
+
+/// Unpacks a `.tar` archive of the model files into the snapshot layout under
+/// `root`. Entries are matched by file name, so archives may keep the files
+/// at the top level or inside any folder.
+pub fn import_model_archive(
+    archive_path: impl AsRef<Path>,
+    root: impl AsRef<Path>,
+) -> Result<PathBuf, AsrError> {
+    let archive_path = archive_path.as_ref();
+    let root = root.as_ref();
+    let snapshot = root.join("snapshots").join(MODEL_SPEC.revision);
+    let _lock = ModelStoreLock::acquire(root)?;
+    fs::create_dir_all(&snapshot).map_err(|error| {
+        AsrError::io(
+            format!("create model snapshot directory {}", snapshot.display()),
+            error,
+        )
+    })?;
+
+    let file = fs::File::open(archive_path).map_err(|error| {
+        AsrError::io(
+            format!("open model archive {}", archive_path.display()),
+            error,
+        )
+    })?;
+    let mut archive = tar::Archive::new(file);
+    let entries = archive
+        .entries()
+        .map_err(|error| AsrError::io("read model archive", error))?;
+    for entry in entries {
+        let mut entry = entry.map_err(|error| AsrError::io("read model archive entry", error))?;
+        if !entry.header().entry_type().is_file() {
+            continue;
+        }
+        let path = entry
+            .path()
+            .map_err(|error| AsrError::io("read model archive entry path", error))?
+            .into_owned();
+        let Some(asset) = archive_asset(&path) else {
+            continue;
+        };
+
+        let dest = snapshot.join(asset.name);
+        let tmp = dest.with_extension("import");
+        entry
+            .unpack(&tmp)
+            .map_err(|error| AsrError::io(format!("extract model asset {}", asset.name), error))?;
+        if !model_file_matches(&tmp, asset)? {
+            let _ = fs::remove_file(&tmp);
+            return Err(AsrError::Integrity(format!(
+                "{} in {} does not match the pinned model",
+                asset.name,
+                archive_path.display()
+            )));
+        }
+        install_asset(&tmp, &dest)?;
+    }
+
+    let missing = invalid_model_files(&snapshot)?;
+    if !missing.is_empty() {
+        return Err(AsrError::Integrity(format!(
+            "model archive is missing: {}",
+            missing
+                .iter()
+                .map(|asset| asset.name)
+                .collect::<Vec<_>>()
+                .join(", ")
+        )));
+    }
+    download::write_revision_ref(root)?;
+    persist_verification_receipt(&snapshot);
+    log::info!("Imported model archive {}", archive_path.display());
+    Ok(snapshot)
+}
+
+fn archive_asset(path: &Path) -> Option<ModelAsset> {
+    let name = path.file_name()?;
+    MODEL_SPEC
+        .assets
+        .iter()
+        .find(|asset| name == asset.name)
+        .copied()
+}
+
+fn install_asset(tmp: &Path, dest: &Path) -> Result<(), AsrError> {
+    fs::rename(tmp, dest).map_err(|error| {
+        AsrError::io(
+            format!(
+                "install model asset {} as {}",
+                tmp.display(),
+                dest.display()
+            ),
+            error,
+        )
+    })
+}
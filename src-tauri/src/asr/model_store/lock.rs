@@ -0,0 +1,51 @@
+use std::fs::{self, File, OpenOptions, TryLockError};
+use std::path::Path;
+
+use crate::asr::recognizer::AsrError;
+
+const LOCK_FILE: &str = ".silentkeys.lock";
+
+/// Exclusive advisory lock on a model root, shared by every process that
+/// verifies, downloads, or imports into it. Released when dropped.
+pub struct ModelStoreLock {
+    _file: File,
+}
+
+impl ModelStoreLock {
+    /// Blocks until no other holder, in this or another process, has the lock.
+    pub fn acquire(root: impl AsRef<Path>) -> Result<Self, AsrError> {
+        let root = root.as_ref();
+        fs::create_dir_all(root).map_err(|error| {
+            AsrError::io(format!("create model directory {}", root.display()), error)
+        })?;
+        let path = root.join(LOCK_FILE);
+        let file = OpenOptions::new()
+            .create(true)
+            .truncate(false)
+            .write(true)
+            .open(&path)
+            .map_err(|error| {
+                AsrError::io(format!("open model store lock {}", path.display()), error)
+            })?;
+
+        match file.try_lock() {
+            Ok(()) => {}
+            Err(TryLockError::WouldBlock) => {
+                log::info!(
+                    "Waiting for another process to finish with the model store at {}",
+                    root.display()
+                );
+                file.lock().map_err(|error| {
+                    AsrError::io(format!("lock model store {}", path.display()), error)
+                })?;
+            }
+            Err(TryLockError::Error(error)) => {
+                return Err(AsrError::io(
+                    format!("lock model store {}", path.display()),
+                    error,
+                ))
+            }
+        }
+        Ok(Self { _file: file })
+    }
+}
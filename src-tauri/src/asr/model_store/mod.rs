@@ -1,15 +1,23 @@
+use std::sync::atomic::{AtomicBool, Ordering};
 use std::sync::{Mutex, OnceLock};
 
 use serde::Serialize;
 
 pub mod download;
+mod import;
+mod lock;
 mod paths;
 mod verification;
 
+pub use import::import_model_archive;
+pub use lock::ModelStoreLock;
+#[cfg(feature = "desktop")]
+pub use paths::default_model_root;
 pub use paths::{
-    default_model_root, fallback_model_root, invalid_model_files_for_tests,
-    model_file_matches_for_tests, resolve_model_dir,
+    fallback_model_root, invalid_model_files_for_tests, model_file_matches_for_tests,
+    resolve_model_dir,
 };
+#[cfg(feature = "desktop")]
 pub(crate) use paths::{invalidate_model_verification, resolve_model_dir_with_progress};
 pub use verification::{
     receipt_matches_for_tests as verification_receipt_matches_for_tests,
@@ -77,9 +85,19 @@ pub struct DownloadProgress {
     pub downloaded_bytes: u64,
     pub total_bytes: u64,
     pub done: bool,
+    /// Set while the user holds the download; its connection is closed and
+    /// the partial file waits to be resumed.
+    pub paused: bool,
 }
 
 static DOWNLOAD_PROGRESS: OnceLock<Mutex<DownloadProgress>> = OnceLock::new();
+/// Set when the user skips a model load; the download stops at its next read
+/// and keeps the partial file so a later load resumes it.
+static DOWNLOAD_CANCELLED: AtomicBool = AtomicBool::new(false);
+/// Set with `DOWNLOAD_CANCELLED` when the user cancels rather than skips, so
+/// the partial files are deleted instead of kept.
+static DOWNLOAD_DISCARDED: AtomicBool = AtomicBool::new(false);
+static DOWNLOAD_PAUSED: AtomicBool = AtomicBool::new(false);
 
 const MAX_RETRIES: usize = 3;
 const RETRY_BACKOFF_SECS: u64 = 2;
@@ -95,6 +113,7 @@ fn empty_progress() -> DownloadProgress {
         downloaded_bytes: 0,
         total_bytes: 0,
         done: false,
+        paused: false,
     }
 }
 
@@ -108,6 +127,7 @@ fn start_tracking(file_count: usize, on_progress: &dyn Fn(DownloadProgress)) {
     if let Ok(mut progress) = progress_state().lock() {
         *progress = DownloadProgress {
             file_count,
+            paused: model_download_paused(),
             ..empty_progress()
         };
     }
@@ -138,7 +158,55 @@ fn mark_finished(on_progress: &dyn Fn(DownloadProgress)) {
     notify_progress(on_progress);
 }
 
-fn current_download_progress() -> Option<DownloadProgress> {
+fn set_paused(paused: bool) {
+    DOWNLOAD_PAUSED.store(paused, Ordering::Release);
+    if let Ok(mut progress) = progress_state().lock() {
+        progress.paused = paused;
+    }
+}
+
+pub fn cancel_model_download() {
+    DOWNLOAD_CANCELLED.store(true, Ordering::Release);
+}
+
+/// Stops the download like [`cancel_model_download`], but deletes the
+/// partial files; assets already downloaded in full are kept.
+pub fn discard_model_download() {
+    DOWNLOAD_DISCARDED.store(true, Ordering::Release);
+    cancel_model_download();
+}
+
+/// Holds the download at its next read and closes the connection until
+/// [`resume_model_download`], keeping what was fetched.
+pub fn pause_model_download() {
+    set_paused(true);
+}
+
+/// Continues a paused download with a range request from where it stopped.
+pub fn resume_model_download() {
+    set_paused(false);
+}
+
+#[cfg(feature = "desktop")]
+pub(crate) fn reset_model_download_cancel() {
+    DOWNLOAD_CANCELLED.store(false, Ordering::Release);
+    DOWNLOAD_DISCARDED.store(false, Ordering::Release);
+    set_paused(false);
+}
+
+pub(crate) fn model_download_cancelled() -> bool {
+    DOWNLOAD_CANCELLED.load(Ordering::Acquire)
+}
+
+fn model_download_discarded() -> bool {
+    DOWNLOAD_DISCARDED.load(Ordering::Acquire)
+}
+
+fn model_download_paused() -> bool {
+    DOWNLOAD_PAUSED.load(Ordering::Acquire)
+}
+
+pub(crate) fn current_download_progress() -> Option<DownloadProgress> {
     DOWNLOAD_PROGRESS
         .get()
         .and_then(|mutex| mutex.lock().ok().map(|progress| progress.clone()))
@@ -3,11 +3,11 @@ use std::io::Read;
 use std::path::{Path, PathBuf};
 
 use sha2::{Digest, Sha256};
-use tauri::AppHandle;
 
 use crate::asr::recognizer::AsrError;
 
-use super::{download, verification, DownloadProgress, ModelAsset, MODEL_SPEC};
+use super::lock::ModelStoreLock;
+use super::{download, import, verification, DownloadProgress, ModelAsset, MODEL_SPEC};
 
 pub(crate) fn model_file_matches(path: &Path, asset: ModelAsset) -> Result<bool, AsrError> {
     match fs::metadata(path) {
@@ -38,7 +38,7 @@ pub(crate) fn model_file_matches(path: &Path, asset: ModelAsset) -> Result<bool,
     Ok(format!("{:x}", hasher.finalize()) == asset.sha256)
 }
 
-fn invalid_model_files(snapshot_dir: &Path) -> Result<Vec<ModelAsset>, AsrError> {
+pub(super) fn invalid_model_files(snapshot_dir: &Path) -> Result<Vec<ModelAsset>, AsrError> {
     MODEL_SPEC
         .assets
         .iter()
@@ -76,7 +76,8 @@ pub fn model_file_matches_for_tests(path: &Path, size: u64, sha256: &'static str
     .unwrap_or(false)
 }
 
-pub fn default_model_root(app: &AppHandle) -> PathBuf {
+#[cfg(feature = "desktop")]
+pub fn default_model_root(app: &tauri::AppHandle) -> PathBuf {
     crate::settings::get_custom_model_path(app).unwrap_or_else(fallback_model_root)
 }
 
@@ -90,7 +91,7 @@ pub fn fallback_model_root() -> PathBuf {
 }
 
 pub fn resolve_model_dir<P: AsRef<Path>>(root: P) -> Result<PathBuf, AsrError> {
-    resolve_model_dir_with_progress(root, |_| {})
+    resolve_model_dir_with_progress(root, None, |_| {})
 }
 
 pub(crate) fn invalidate_model_verification(snapshot_dir: &Path) {
@@ -102,8 +103,11 @@ pub(crate) fn invalidate_model_verification(snapshot_dir: &Path) {
     }
 }
 
+/// Returns the verified snapshot under `root`, repairing missing or invalid
+/// assets from `bundled_dir` first and downloading only what is still absent.
 pub(crate) fn resolve_model_dir_with_progress<P, F>(
     root: P,
+    bundled_dir: Option<&Path>,
     on_progress: F,
 ) -> Result<PathBuf, AsrError>
 where
@@ -112,13 +116,15 @@ where
 {
     let root = root.as_ref();
     let snapshot = root.join("snapshots").join(MODEL_SPEC.revision);
-    match verification::receipt_matches(&snapshot, MODEL_SPEC.revision, MODEL_SPEC.assets) {
-        Ok(true) => return Ok(snapshot),
-        Ok(false) => {}
-        Err(error) => log::warn!(
-            "Could not inspect model verification receipt at {}: {error}",
-            snapshot.display()
-        ),
+    if receipt_is_current(&snapshot) {
+        return Ok(snapshot);
+    }
+
+    // Another process may be repairing the same snapshot; once it releases the
+    // lock its receipt lets this caller reuse the result without re-hashing.
+    let _lock = ModelStoreLock::acquire(root)?;
+    if receipt_is_current(&snapshot) {
+        return Ok(snapshot);
     }
 
     let invalid = invalid_model_files(&snapshot)?;
@@ -134,6 +140,12 @@ where
             error,
         )
     })?;
+    let invalid = match bundled_dir {
+        Some(bundled_dir) if import::install_from_dir(bundled_dir, &snapshot, &invalid)? > 0 => {
+            invalid_model_files(&snapshot)?
+        }
+        _ => invalid,
+    };
     download::download_assets(&snapshot, &invalid, &on_progress)?;
     download::write_revision_ref(root)?;
 
@@ -152,7 +164,18 @@ where
     )))
 }
 
-fn persist_verification_receipt(snapshot_dir: &Path) {
+fn receipt_is_current(snapshot_dir: &Path) -> bool {
+    verification::receipt_matches(snapshot_dir, MODEL_SPEC.revision, MODEL_SPEC.assets)
+        .unwrap_or_else(|error| {
+            log::warn!(
+                "Could not inspect model verification receipt at {}: {error}",
+                snapshot_dir.display()
+            );
+            false
+        })
+}
+
+pub(super) fn persist_verification_receipt(snapshot_dir: &Path) {
     if let Err(error) =
         verification::write_receipt(snapshot_dir, MODEL_SPEC.revision, MODEL_SPEC.assets)
     {
@@ -0,0 +1,200 @@
+use std::ops::{ControlFlow, Range};
+use std::path::Path;
+use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
+use std::sync::{mpsc, Mutex};
+use std::thread;
+
+use crate::sync::MutexRecover;
+use crate::transcript::Transcript;
+use crate::vad::{speech_segments, VadConfig};
+
+use super::recognizer::{AsrError, AsrModel};
+use super::threads::{available_cores, OrtThreads};
+
+/// Each session holds a full encoder/decoder pair, so the default stays small
+/// to bound memory on machines with many cores.
+const MAX_DEFAULT_SESSIONS: usize = 4;
+const CORES_PER_SESSION: usize = 4;
+
+pub fn default_pool_size() -> usize {
+    (available_cores() / CORES_PER_SESSION).clamp(1, MAX_DEFAULT_SESSIONS)
+}
+
+/// Independent model sessions for decoding VAD-separated segments of long
+/// audio in parallel. Segments are claimed in order by whichever session is
+/// free and reassembled by index, so output order never depends on timing.
+pub struct SessionPool {
+    sessions: Vec<Mutex<AsrModel>>,
+}
+
+impl SessionPool {
+    /// Loads `size` sessions, each given its share of the cores so the pool
+    /// does not oversubscribe them.
+    pub fn new(
+        model_dir: impl AsRef<Path>,
+        language_preference: &str,
+        size: usize,
+    ) -> Result<Self, AsrError> {
+        let model_dir = model_dir.as_ref();
+        let threads = OrtThreads {
+            intra_threads: CORES_PER_SESSION,
+            inter_threads: 1,
+        };
+        let sessions = (0..size.max(1))
+            .map(|_| {
+                AsrModel::with_progress(model_dir, language_preference, threads, |_| {})
+                    .map(Mutex::new)
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+        log::info!("ASR session pool ready with {} sessions", sessions.len());
+        Ok(Self { sessions })
+    }
+
+    pub fn size(&self) -> usize {
+        self.sessions.len()
+    }
+
+    pub fn transcribe(&self, samples: &[f32], vad: &VadConfig) -> Result<Transcript, AsrError> {
+        let segments = speech_segments(samples, vad);
+        let texts = self.transcribe_segments(samples, &segments)?;
+        Ok(Transcript::from_segments(self.language(), &segments, texts))
+    }
+
+    /// Every session is created with the same preference, so the first one
+    /// speaks for the pool.
+    fn language(&self) -> Option<String> {
+        self.sessions
+            .first()?
+            .lock_recover()
+            .language()
+            .map(str::to_string)
+    }
+
+    /// Prompts every session with `language`, as the model the pool decodes
+    /// alongside is prompted. English-only models have no prompt.
+    pub fn match_language(&self, language: Option<&str>) -> Result<(), AsrError> {
+        let Some(language) = language else {
+            return Ok(());
+        };
+        for session in &self.sessions {
+            let mut session = session.lock_recover();
+            if session.language() != Some(language) {
+                session.set_language(language)?;
+            }
+        }
+        Ok(())
+    }
+
+    /// Returns one transcript per segment, in segment order.
+    pub fn transcribe_segments(
+        &self,
+        samples: &[f32],
+        segments: &[Range<usize>],
+    ) -> Result<Vec<String>, AsrError> {
+        let mut sessions: Vec<_> = self
+            .sessions
+            .iter()
+            .map(|session| session.lock_recover())
+            .collect();
+        let models = sessions.iter_mut().map(|session| &mut **session).collect();
+        let decoded = decode_in_order(
+            models,
+            segments,
+            |model, range| model.transcribe_segment(&samples[range]),
+            |_, _| ControlFlow::Continue(()),
+        )?;
+        match decoded {
+            ControlFlow::Continue(texts) => Ok(texts),
+            ControlFlow::Break(()) => Err(AsrError::Cancelled),
+        }
+    }
+
+    /// Like [`decode_in_order`] on `primary` and every pooled session.
+    #[cfg(feature = "desktop")]
+    pub(crate) fn decode_with(
+        &self,
+        primary: &mut AsrModel,
+        segments: &[Range<usize>],
+        decode: impl Fn(&mut AsrModel, Range<usize>) -> Result<String, AsrError> + Sync,
+        on_decoded: impl FnMut(usize, &str) -> ControlFlow<()>,
+    ) -> Result<ControlFlow<(), Vec<String>>, AsrError> {
+        let mut sessions: Vec<_> = self
+            .sessions
+            .iter()
+            .map(|session| session.lock_recover())
+            .collect();
+        let models = std::iter::once(primary)
+            .chain(sessions.iter_mut().map(|session| &mut **session))
+            .collect();
+        decode_in_order(models, segments, decode, on_decoded)
+    }
+}
+
+/// Decodes `segments` with `decode` on every model in `models` at once and
+/// hands each text to `on_decoded` in segment order, as soon as it and every
+/// segment before it are done. Breaking from `on_decoded`, or a failed
+/// segment, stops each model after the segment it is on.
+pub(crate) fn decode_in_order(
+    mut models: Vec<&mut AsrModel>,
+    segments: &[Range<usize>],
+    decode: impl Fn(&mut AsrModel, Range<usize>) -> Result<String, AsrError> + Sync,
+    mut on_decoded: impl FnMut(usize, &str) -> ControlFlow<()>,
+) -> Result<ControlFlow<(), Vec<String>>, AsrError> {
+    let next = AtomicUsize::new(0);
+    let stop = AtomicBool::new(false);
+    let mut texts: Vec<Option<String>> = vec![None; segments.len()];
+    let mut stopped = None;
+    models.truncate(segments.len());
+    let work = |model: &mut AsrModel, tx: mpsc::Sender<(usize, Result<String, AsrError>)>| {
+        // Each segment resets the decoder, so a session whose last decode
+        // panicked is safe to reuse.
+        while !stop.load(Ordering::Relaxed) {
+            let index = next.fetch_add(1, Ordering::Relaxed);
+            let Some(range) = segments.get(index) else {
+                break;
+            };
+            if tx.send((index, decode(model, range.clone()))).is_err() {
+                break;
+            }
+        }
+    };
+
+    thread::scope(|scope| {
+        let (tx, rx) = mpsc::channel();
+        let work = &work;
+        for model in models {
+            let tx = tx.clone();
+            scope.spawn(move || work(model, tx));
+        }
+        drop(tx);
+
+        let mut emitted = 0;
+        'received: for (index, result) in rx {
+            match result {
+                Ok(text) => texts[index] = Some(text),
+                Err(error) => {
+                    stopped = Some(Err(error));
+                    break;
+                }
+            }
+            while let Some(text) = texts.get(emitted).and_then(Option::as_deref) {
+                if on_decoded(emitted, text).is_break() {
+                    log::info!("Transcription cancelled after {} segments", emitted + 1);
+                    stopped = Some(Ok(ControlFlow::Break(())));
+                    break 'received;
+                }
+                emitted += 1;
+            }
+        }
+        stop.store(true, Ordering::Relaxed);
+    });
+
+    if let Some(stopped) = stopped {
+        return stopped;
+    }
+    texts
+        .into_iter()
+        .collect::<Option<Vec<_>>>()
+        .map(ControlFlow::Continue)
+        .ok_or_else(|| AsrError::Pool("segment was not decoded".to_string()))
+}
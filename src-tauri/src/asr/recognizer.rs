@@ -1,14 +1,28 @@
-use std::{collections::HashSet, fmt, path::Path, time::Instant};
+use std::path::PathBuf;
+use std::sync::{RwLock, RwLockWriteGuard};
+use std::time::{Duration, Instant};
+use std::{collections::HashSet, fmt, ops::Range, path::Path};
 
-use parakeet_rs::{Nemotron, NemotronMode};
+use parakeet_rs::{ExecutionConfig, Nemotron, NemotronMode};
 use serde::de::{MapAccess, Visitor};
-use serde::{Deserialize, Deserializer};
+use serde::{Deserialize, Deserializer, Serialize};
 
 use crate::errors::UserFacing;
-use crate::settings::DEFAULT_ASR_LANGUAGE;
+use crate::sync::RwLockRecover;
+use crate::transcript::Transcript;
 
+use super::model_store::MODEL_SPEC;
+use super::signature::{is_shape_error, report_shape_error_once};
+use super::threads::{available_cores, OrtThreads};
+
+pub const DEFAULT_ASR_LANGUAGE: &str = "en-US";
 pub(crate) const STREAM_CHUNK_SAMPLES: usize = 8_960;
 const STREAM_FLUSH_CHUNKS: usize = 3;
+const WARM_UP_SAMPLES: usize = 16_000;
+/// Decodes timed per thread split; the fastest counts, so a stray scheduling
+/// hiccup does not decide the result.
+const BENCHMARK_RUNS: usize = 2;
+const MAX_LANGUAGE_TAG_LEN: usize = 35;
 pub const AUTOMATIC_LANGUAGE: &str = "auto";
 pub const SYSTEM_LANGUAGE: &str = "system";
 
@@ -67,6 +81,10 @@ pub enum AsrError {
         #[source]
         source: serde_json::Error,
     },
+    #[error("model file {file} is corrupted: {reason}")]
+    Corrupted { file: String, reason: String },
+    #[error("load speech model: {0}")]
+    ModelLoad(#[source] parakeet_rs::Error),
     #[error("Unsupported speech language: {0}")]
     UnsupportedLanguage(String),
     #[error("{context}: {source}")]
@@ -75,6 +93,10 @@ pub enum AsrError {
         #[source]
         source: parakeet_rs::Error,
     },
+    #[error("ASR session pool failed: {0}")]
+    Pool(String),
+    #[error("speech model load was skipped")]
+    Cancelled,
 }
 
 impl AsrError {
@@ -99,33 +121,99 @@ impl UserFacing for AsrError {
             Self::Integrity(_) | Self::Config { .. } => {
                 "The speech model files are invalid. Download the model again."
             }
+            Self::Corrupted { .. } | Self::ModelLoad(_) => {
+                "The speech model is corrupted. Download the model again."
+            }
             Self::UnsupportedLanguage(_) => {
                 "That language is not supported by the installed speech model."
             }
-            Self::Nemotron { .. } => {
+            Self::Nemotron { .. } | Self::Pool(_) => {
                 "The speech engine failed to run. Try restarting the app or downloading the model again."
             }
             Self::Io { .. } => {
                 "The app could not read or write its local files. Check disk space and permissions."
             }
+            Self::Cancelled => {
+                "The speech model was not loaded. Load it from the recorder when you are ready."
+            }
         }
     }
 }
 
+/// Steps of a model load, in the order they run. parakeet-rs builds the
+/// encoder, decoder and preprocessor ONNX sessions in a single call, so they
+/// are reported together as `LoadingSessions`. `Downloading` only appears when
+/// files are missing; a load can be skipped until sessions start loading.
+/// A reload after the model path changes first reports `WaitingForDictation`
+/// while a recording is still running and `Unloading` while the old sessions
+/// are released, or `Failed` when the load it replaces stalled and the reload
+/// gave up.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ModelLoadStage {
+    WaitingForDictation,
+    Unloading,
+    VerifyingFiles,
+    Downloading,
+    ReadingConfig,
+    LoadingSessions,
+    ConfiguringLanguage,
+    WarmingUp,
+    Ready,
+    Failed,
+}
+
 pub struct AsrModel {
     model: Box<Nemotron>,
     languages: Vec<String>,
     accepted_languages: Vec<String>,
+    /// The language prompt in use; `None` for English-only models.
+    language: Option<String>,
+    /// Where the graphs were loaded from, for the signature report.
+    model_dir: PathBuf,
 }
 
 impl AsrModel {
     pub fn new(model_dir: impl AsRef<Path>, language_preference: &str) -> Result<Self, AsrError> {
+        Self::with_progress(
+            model_dir,
+            language_preference,
+            OrtThreads::default(),
+            |_| {},
+        )
+    }
+
+    /// Loads the model like [`AsrModel::new`], reporting each stage before it
+    /// starts and running one silent decode so the first dictation is not the
+    /// one paying for lazy ONNX initialization.
+    pub fn with_progress(
+        model_dir: impl AsRef<Path>,
+        language_preference: &str,
+        threads: OrtThreads,
+        mut on_stage: impl FnMut(ModelLoadStage),
+    ) -> Result<Self, AsrError> {
         let start = Instant::now();
         let model_dir = model_dir.as_ref();
+        on_stage(ModelLoadStage::ReadingConfig);
+        check_model_files(model_dir)?;
         let catalog = load_languages(model_dir)?;
-        let mut model = Nemotron::from_pretrained(model_dir, None)
-            .map_err(|error| AsrError::nemotron("load Nemotron model", error))?;
+        on_stage(ModelLoadStage::LoadingSessions);
+        // The ONNX graphs and tokenizer are parsed inside parakeet-rs; a file
+        // that passed the size check but cannot be loaded is still corrupt.
+        let threads = threads.resolved(available_cores());
+        log::info!(
+            "Nemotron ASR threads: {} intra, {} inter",
+            threads.intra_threads,
+            threads.inter_threads
+        );
+        let config = ExecutionConfig::new()
+            .with_intra_threads(threads.intra_threads)
+            .with_inter_threads(threads.inter_threads);
+        let mut model =
+            Nemotron::from_pretrained(model_dir, Some(config)).map_err(AsrError::ModelLoad)?;
+        let mut selected_language = None;
         if model.mode() == NemotronMode::Multilingual {
+            on_stage(ModelLoadStage::ConfiguringLanguage);
             let language = match apply_language(&mut model, &catalog.accepted, language_preference)
             {
                 Ok(language) => language,
@@ -138,15 +226,48 @@ impl AsrModel {
                 Err(error) => return Err(error),
             };
             log::info!("Nemotron ASR language hint: {language}");
+            selected_language = Some(language);
         }
+        on_stage(ModelLoadStage::WarmingUp);
+        // Missing outputs and mismatched tensor shapes only surface on the
+        // first decode.
+        model
+            .transcribe_audio(&[0.0; WARM_UP_SAMPLES])
+            .map_err(|error| {
+                if is_shape_error(&error) {
+                    report_shape_error_once(model_dir, &error);
+                }
+                AsrError::ModelLoad(error)
+            })?;
+        model.reset();
         log::info!("Nemotron ASR model initialized in {:?}", start.elapsed());
         Ok(Self {
             model: Box::new(model),
             languages: catalog.options,
             accepted_languages: catalog.accepted,
+            language: selected_language,
+            model_dir: model_dir.to_path_buf(),
         })
     }
 
+    /// Loads a separate copy of the model with `threads` and times the fastest
+    /// of a few decodes of `samples`.
+    pub fn time_decode(
+        model_dir: impl AsRef<Path>,
+        language_preference: &str,
+        threads: OrtThreads,
+        samples: &[f32],
+    ) -> Result<Duration, AsrError> {
+        let mut model = Self::with_progress(model_dir, language_preference, threads, |_| {})?;
+        let mut fastest = Duration::MAX;
+        for _ in 0..BENCHMARK_RUNS {
+            let start = Instant::now();
+            model.transcribe_samples(samples)?;
+            fastest = fastest.min(start.elapsed());
+        }
+        Ok(fastest)
+    }
+
     pub fn languages(&self) -> &[String] {
         &self.languages
     }
@@ -160,6 +281,7 @@ impl AsrModel {
     pub fn set_language(&mut self, language: &str) -> Result<String, AsrError> {
         let selected = apply_language(&mut self.model, &self.accepted_languages, language)?;
         self.model.reset();
+        self.language = Some(selected.clone());
         Ok(selected)
     }
 
@@ -170,6 +292,48 @@ impl AsrModel {
             .map_err(|error| AsrError::nemotron("run offline transcription", error))
     }
 
+    /// Decodes one speech segment like [`AsrModel::transcribe_samples`],
+    /// but after a tensor shape mismatch the segment is decoded again padded
+    /// to whole streaming chunks, which gives the decoder the frame count it
+    /// was exported for.
+    pub fn transcribe_segment(&mut self, samples: &[f32]) -> Result<String, AsrError> {
+        let error = match self.transcribe_samples(samples) {
+            Err(AsrError::Nemotron { source, .. }) if is_shape_error(&source) => source,
+            result => return result,
+        };
+        report_shape_error_once(&self.model_dir, &error);
+        let text = self.transcribe_samples(&pad_to_chunks(samples))?;
+        log::info!("Decoded the segment again after padding it to whole chunks");
+        Ok(text)
+    }
+
+    pub fn language(&self) -> Option<&str> {
+        self.language.as_deref()
+    }
+
+    /// The directory the graphs were loaded from.
+    pub fn model_dir(&self) -> &Path {
+        &self.model_dir
+    }
+
+    /// Decodes each range of `samples` on its own, one transcript segment per
+    /// range.
+    pub fn transcribe_ranges(
+        &mut self,
+        samples: &[f32],
+        ranges: &[Range<usize>],
+    ) -> Result<Transcript, AsrError> {
+        let texts = ranges
+            .iter()
+            .map(|range| self.transcribe_segment(&samples[range.clone()]))
+            .collect::<Result<Vec<_>, _>>()?;
+        Ok(Transcript::from_segments(
+            self.language.clone(),
+            ranges,
+            texts,
+        ))
+    }
+
     pub fn reset_state(&mut self) {
         self.model.reset();
     }
@@ -177,12 +341,17 @@ impl AsrModel {
     pub(crate) fn advance_streaming(&mut self, samples: &[f32]) -> Result<String, AsrError> {
         let mut text = String::new();
         for chunk in samples.chunks(STREAM_CHUNK_SAMPLES) {
-            text.push_str(
-                &self
-                    .model
-                    .transcribe_chunk(chunk)
-                    .map_err(|error| AsrError::nemotron("run streaming transcription", error))?,
-            );
+            match self.model.transcribe_chunk(chunk) {
+                Ok(chunk_text) => text.push_str(&chunk_text),
+                // One misshapen chunk drops its words, not the dictation;
+                // the final offline pass decodes the audio again.
+                Err(error) if is_shape_error(&error) => {
+                    report_shape_error_once(&self.model_dir, &error);
+                }
+                Err(error) => {
+                    return Err(AsrError::nemotron("run streaming transcription", error));
+                }
+            }
         }
         Ok(text)
     }
@@ -202,18 +371,90 @@ impl AsrModel {
     }
 }
 
+/// `samples` followed by silence up to a whole number of streaming chunks.
+fn pad_to_chunks(samples: &[f32]) -> Vec<f32> {
+    let mut padded = samples.to_vec();
+    padded.resize(
+        samples.len().div_ceil(STREAM_CHUNK_SAMPLES).max(1) * STREAM_CHUNK_SAMPLES,
+        0.0,
+    );
+    padded
+}
+
+/// Fails with [`AsrError::Corrupted`] when a model file is missing or empty,
+/// so parakeet-rs is never handed a partial snapshot.
+fn check_model_files(model_dir: &Path) -> Result<(), AsrError> {
+    for asset in MODEL_SPEC.assets {
+        let path = model_dir.join(asset.name);
+        let reason = match std::fs::metadata(&path) {
+            Ok(metadata) if metadata.is_file() && metadata.len() > 0 => continue,
+            Ok(metadata) if metadata.is_file() => "file is empty".to_string(),
+            Ok(_) => "not a regular file".to_string(),
+            Err(error) if error.kind() == std::io::ErrorKind::NotFound => {
+                "file is missing".to_string()
+            }
+            Err(error) => {
+                return Err(AsrError::io(
+                    format!("read model file metadata {}", path.display()),
+                    error,
+                ))
+            }
+        };
+        return Err(AsrError::Corrupted {
+            file: asset.name.to_string(),
+            reason,
+        });
+    }
+    Ok(())
+}
+
+/// Locks the shared model for decoding. A decode that panicked leaves the
+/// decoder state half-advanced, so a recovered model is reset first.
+pub(crate) fn write_model(
+    model: &RwLock<Option<AsrModel>>,
+) -> RwLockWriteGuard<'_, Option<AsrModel>> {
+    model.write_repair(|model| {
+        if let Some(model) = model.as_mut() {
+            model.reset_state();
+        }
+    })
+}
+
 fn load_languages(model_dir: &Path) -> Result<LanguageCatalog, AsrError> {
     let path = model_dir.join("config.json");
     let config = std::fs::read(&path)
         .map_err(|error| AsrError::io(format!("read model config {}", path.display()), error))?;
-    let config: ModelConfig =
-        serde_json::from_slice(&config).map_err(|source| AsrError::Config {
-            path: path.display().to_string(),
-            source,
-        })?;
+    parse_model_config(&config, &path.display().to_string())
+}
+
+fn parse_model_config(data: &[u8], path: &str) -> Result<LanguageCatalog, AsrError> {
+    let config: ModelConfig = serde_json::from_slice(data).map_err(|source| AsrError::Config {
+        path: path.to_string(),
+        source,
+    })?;
+    if let Some((language, _)) = config
+        .prompt_dictionary
+        .0
+        .iter()
+        .find(|(language, _)| !is_language_tag(language))
+    {
+        return Err(AsrError::Corrupted {
+            file: "config.json".to_string(),
+            reason: format!("invalid language tag {language:?} in prompt dictionary"),
+        });
+    }
     Ok(language_catalog(config.prompt_dictionary.0))
 }
 
+/// Prompt dictionary keys are BCP 47 style tags such as `en-US`.
+fn is_language_tag(language: &str) -> bool {
+    !language.is_empty()
+        && language.len() <= MAX_LANGUAGE_TAG_LEN
+        && language
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_')
+}
+
 fn apply_language(
     model: &mut Nemotron,
     languages: &[String],
@@ -285,6 +526,16 @@ fn language_catalog(entries: Vec<(String, u16)>) -> LanguageCatalog {
     LanguageCatalog { accepted, options }
 }
 
+#[doc(hidden)]
+pub fn parse_model_config_for_tests(data: &[u8]) -> Result<Vec<String>, AsrError> {
+    parse_model_config(data, "config.json").map(|catalog| catalog.options)
+}
+
+#[doc(hidden)]
+pub fn check_model_files_for_tests(model_dir: &Path) -> Result<(), AsrError> {
+    check_model_files(model_dir)
+}
+
 #[doc(hidden)]
 pub fn language_options_for_tests(entries: &[(&str, u16)]) -> Vec<String> {
     language_catalog(
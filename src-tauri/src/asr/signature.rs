@@ -0,0 +1,141 @@
+use std::fmt;
+use std::path::Path;
+use std::sync::atomic::{AtomicBool, Ordering};
+
+use serde::Serialize;
+
+use super::model_store::MODEL_SPEC;
+
+/// The ONNX graphs whose inputs and outputs the report lists; decoder_joint
+/// is the one whose output shapes have varied between exports.
+const GRAPHS: &[&str] = &["decoder_joint.onnx", "encoder.onnx"];
+
+static REPORTED: AtomicBool = AtomicBool::new(false);
+
+#[derive(Clone, Debug, PartialEq, Serialize)]
+pub struct TensorSignature {
+    pub name: String,
+    /// Element type and dimensions, `-1` or a symbol for dynamic ones.
+    pub dtype: String,
+}
+
+#[derive(Clone, Debug, PartialEq, Serialize)]
+pub struct GraphSignature {
+    pub file: String,
+    pub inputs: Vec<TensorSignature>,
+    pub outputs: Vec<TensorSignature>,
+    /// Why the graph could not be opened, instead of its tensors.
+    pub error: Option<String>,
+}
+
+/// The inputs and outputs of the model's ONNX graphs, for bug reports about
+/// exports the decoder does not expect.
+#[derive(Clone, Debug, Default, PartialEq, Serialize)]
+pub struct ModelSignature {
+    pub graphs: Vec<GraphSignature>,
+}
+
+/// Whether ONNX Runtime rejected the decoder's tensors, which a different
+/// export of the model causes, rather than the model being missing or broken.
+/// Runtime reports a wrong rank or dimension only as `InvalidArgument`.
+/// parakeet-rs keeps reshape failures as `Error::Model` text, which is not
+/// told apart.
+pub fn is_shape_error(error: &parakeet_rs::Error) -> bool {
+    match error {
+        parakeet_rs::Error::Ort(error) => error.code() == ort::ErrorCode::InvalidArgument,
+        parakeet_rs::Error::Model(_)
+        | parakeet_rs::Error::Io(_)
+        | parakeet_rs::Error::Audio(_)
+        | parakeet_rs::Error::Tokenizer(_)
+        | parakeet_rs::Error::Config(_) => false,
+    }
+}
+
+/// Opens each graph in `model_dir` in a session of its own to read its
+/// signature. Slow for the encoder, so only for reports.
+pub fn read_signature(model_dir: &Path) -> ModelSignature {
+    ModelSignature {
+        graphs: GRAPHS
+            .iter()
+            .map(|file| read_graph(&model_dir.join(file), file))
+            .collect(),
+    }
+}
+
+fn read_graph(path: &Path, file: &str) -> GraphSignature {
+    let tensors = |outlets: &[ort::value::Outlet]| {
+        outlets
+            .iter()
+            .map(|outlet| TensorSignature {
+                name: outlet.name().to_string(),
+                dtype: outlet.dtype().to_string(),
+            })
+            .collect()
+    };
+    match ort::session::Session::builder().and_then(|mut builder| builder.commit_from_file(path)) {
+        Ok(session) => GraphSignature {
+            file: file.to_string(),
+            inputs: tensors(session.inputs()),
+            outputs: tensors(session.outputs()),
+            error: None,
+        },
+        Err(error) => GraphSignature {
+            file: file.to_string(),
+            inputs: Vec::new(),
+            outputs: Vec::new(),
+            error: Some(error.to_string()),
+        },
+    }
+}
+
+impl fmt::Display for ModelSignature {
+    fn fmt(&self, formatter: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(
+            formatter,
+            "Model {} at {}",
+            MODEL_SPEC.repository, MODEL_SPEC.revision
+        )?;
+        for graph in &self.graphs {
+            writeln!(formatter, "{}:", graph.file)?;
+            if let Some(error) = &graph.error {
+                writeln!(formatter, "  could not open: {error}")?;
+            }
+            for (direction, tensors) in [("in", &graph.inputs), ("out", &graph.outputs)] {
+                for tensor in tensors {
+                    writeln!(
+                        formatter,
+                        "  {direction:<3} {}: {}",
+                        tensor.name, tensor.dtype
+                    )?;
+                }
+            }
+        }
+        Ok(())
+    }
+}
+
+/// The signature as text to paste into a bug report.
+pub fn signature_report(model_dir: &Path) -> String {
+    read_signature(model_dir).to_string()
+}
+
+/// Logs the model's signature the first time a decode fails on tensor
+/// shapes, on a thread of its own since opening the graphs takes a while.
+pub(crate) fn report_shape_error_once(model_dir: &Path, error: &dyn fmt::Display) {
+    log::warn!("Speech decoder hit a tensor shape mismatch: {error}");
+    if REPORTED.swap(true, Ordering::AcqRel) {
+        return;
+    }
+    let model_dir = model_dir.to_path_buf();
+    let spawned = std::thread::Builder::new()
+        .name("model-signature".to_string())
+        .spawn(move || {
+            log::warn!(
+                "Speech model signature, for bug reports:\n{}",
+                signature_report(&model_dir)
+            );
+        });
+    if let Err(error) = spawned {
+        log::warn!("Could not start the model signature report: {error}");
+    }
+}
@@ -0,0 +1,109 @@
+use std::thread;
+use std::time::Duration;
+
+use serde::{Deserialize, Serialize};
+
+/// Upper bound for either count. The encoder and decoder are too small to
+/// split across more threads than this.
+pub const MAX_ORT_THREADS: usize = 16;
+const MAX_DEFAULT_INTRA_THREADS: usize = 8;
+
+/// ONNX Runtime thread counts for the encoder and decoder sessions. Intra
+/// threads split one operator's work; inter threads run independent operators
+/// side by side. `0` picks a default from the core count.
+#[derive(Clone, Copy, Debug, Default, Deserialize, Eq, PartialEq, Serialize)]
+#[serde(default)]
+pub struct OrtThreads {
+    pub intra_threads: usize,
+    pub inter_threads: usize,
+}
+
+impl OrtThreads {
+    pub fn clamped(self) -> Self {
+        Self {
+            intra_threads: self.intra_threads.min(MAX_ORT_THREADS),
+            inter_threads: self.inter_threads.min(MAX_ORT_THREADS),
+        }
+    }
+
+    /// Fills in automatic counts for a machine with `cores` cores. Intra
+    /// threads default to half the cores, leaving the rest for capture and the
+    /// app being typed into; a single inter thread keeps the two pools from
+    /// competing for the same cores.
+    pub fn resolved(self, cores: usize) -> Self {
+        let threads = self.clamped();
+        Self {
+            intra_threads: match threads.intra_threads {
+                0 => (cores / 2).clamp(1, MAX_DEFAULT_INTRA_THREADS),
+                intra => intra,
+            },
+            inter_threads: threads.inter_threads.max(1),
+        }
+    }
+}
+
+pub fn available_cores() -> usize {
+    thread::available_parallelism()
+        .map(|cores| cores.get())
+        .unwrap_or(1)
+}
+
+/// Splits worth timing on a machine with `cores` cores: a quarter, half, and
+/// all of the cores as intra threads with one inter thread, then half with two
+/// inter threads.
+pub fn tuning_candidates(cores: usize) -> Vec<OrtThreads> {
+    let half = OrtThreads::default().resolved(cores).intra_threads;
+    let mut intra_counts = vec![cores / 4, half, cores];
+    intra_counts.iter_mut().for_each(|count| {
+        *count = (*count).clamp(1, MAX_ORT_THREADS);
+    });
+    intra_counts.dedup();
+    let mut candidates: Vec<OrtThreads> = intra_counts
+        .into_iter()
+        .map(|intra_threads| OrtThreads {
+            intra_threads,
+            inter_threads: 1,
+        })
+        .collect();
+    candidates.push(OrtThreads {
+        intra_threads: half,
+        inter_threads: 2,
+    });
+    candidates
+}
+
+#[derive(Clone, Copy, Debug, Eq, PartialEq, Serialize)]
+pub struct ThreadTiming {
+    pub threads: OrtThreads,
+    pub decode_ms: u64,
+}
+
+/// The timings of every candidate, in the order they ran, and the fastest.
+#[derive(Clone, Debug, Eq, PartialEq, Serialize)]
+pub struct ThreadTuning {
+    pub fastest: OrtThreads,
+    pub timings: Vec<ThreadTiming>,
+}
+
+/// Times each candidate with `measure` and keeps the fastest. Ties go to the
+/// earlier candidate, which uses fewer threads. The first failure stops the
+/// run, since every candidate loads the same model.
+pub fn tune_threads<E>(
+    candidates: &[OrtThreads],
+    mut measure: impl FnMut(OrtThreads) -> Result<Duration, E>,
+) -> Result<ThreadTuning, E> {
+    let mut timings = Vec::with_capacity(candidates.len());
+    for &threads in candidates {
+        let elapsed = measure(threads)?;
+        timings.push(ThreadTiming {
+            threads,
+            decode_ms: elapsed.as_millis() as u64,
+        });
+    }
+    let fastest = timings
+        .iter()
+        .min_by_key(|timing| timing.decode_ms)
+        .map(|timing| timing.threads)
+        .unwrap_or_default();
+    Ok(ThreadTuning { fastest, timings })
+}
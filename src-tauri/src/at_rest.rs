@@ -0,0 +1,215 @@
+use std::fs::File;
+use std::io::{self, Write};
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::RwLock;
+
+use chacha20poly1305::aead::{Aead, KeyInit};
+use chacha20poly1305::{XChaCha20Poly1305, XNonce};
+use hkdf::Hkdf;
+use sha2::Sha256;
+
+use crate::errors::UserFacing;
+use crate::sync::RwLockRecover;
+
+/// Starts every encrypted file, so plain files written before encryption was
+/// turned on, or after it was turned off, are still read as they are.
+pub const SEALED_MAGIC: &[u8; 8] = b"SKSEAL01";
+const NONCE_BYTES: usize = 24;
+/// HKDF info separating this key from anything else derived from the same
+/// secret.
+const KEY_INFO: &[u8] = b"silentkeys at-rest key v1";
+
+/// The key for the last secret loaded from the keychain. Reads need it even
+/// with encryption off, for files sealed while it was on.
+static KEY: RwLock<Option<AtRestKey>> = RwLock::new(None);
+static SEAL_WRITES: AtomicBool = AtomicBool::new(false);
+
+#[derive(thiserror::Error, Debug, Eq, PartialEq)]
+pub enum AtRestError {
+    #[error("file is encrypted but no key is loaded")]
+    NoKey,
+    #[error("encrypted file is truncated")]
+    Truncated,
+    #[error("encrypted file does not match the key or was modified")]
+    Tampered,
+    #[error("could not generate a nonce: {0}")]
+    Random(String),
+    #[error("keychain: {0}")]
+    Keychain(String),
+}
+
+impl UserFacing for AtRestError {
+    fn user_message(&self) -> &'static str {
+        match self {
+            Self::NoKey => "This file is encrypted and its key is not in the system keychain.",
+            Self::Truncated | Self::Tampered => {
+                "An encrypted file could not be decrypted. It may be damaged."
+            }
+            Self::Random(_) => "Could not encrypt the file. Please try again.",
+            Self::Keychain(_) => {
+                "Could not reach the system keychain, which holds the encryption key."
+            }
+        }
+    }
+}
+
+/// A 256-bit XChaCha20-Poly1305 key.
+#[derive(Clone)]
+pub struct AtRestKey([u8; 32]);
+
+impl AtRestKey {
+    /// Derives the key from a random secret kept in the OS keychain.
+    pub fn derive(secret: &[u8]) -> Self {
+        let mut key = [0_u8; 32];
+        // A 32-byte output is always within HKDF-SHA256's limit.
+        let _ = Hkdf::<Sha256>::new(None, secret).expand(KEY_INFO, &mut key);
+        Self(key)
+    }
+
+    fn cipher(&self) -> XChaCha20Poly1305 {
+        XChaCha20Poly1305::new(&self.0.into())
+    }
+}
+
+impl std::fmt::Debug for AtRestKey {
+    fn fmt(&self, formatter: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        formatter.write_str("AtRestKey(..)")
+    }
+}
+
+pub fn is_sealed(bytes: &[u8]) -> bool {
+    bytes.starts_with(SEALED_MAGIC)
+}
+
+/// Encrypts `plaintext` as [`SEALED_MAGIC`], a random nonce, and the
+/// authenticated ciphertext.
+pub fn seal(key: &AtRestKey, plaintext: &[u8]) -> Result<Vec<u8>, AtRestError> {
+    let mut nonce = [0_u8; NONCE_BYTES];
+    getrandom::fill(&mut nonce).map_err(|error| AtRestError::Random(error.to_string()))?;
+    let ciphertext = key
+        .cipher()
+        .encrypt(XNonce::from_slice(&nonce), plaintext)
+        .map_err(|_| AtRestError::Tampered)?;
+    let mut sealed = Vec::with_capacity(SEALED_MAGIC.len() + NONCE_BYTES + ciphertext.len());
+    sealed.extend_from_slice(SEALED_MAGIC);
+    sealed.extend_from_slice(&nonce);
+    sealed.extend_from_slice(&ciphertext);
+    Ok(sealed)
+}
+
+/// Decrypts bytes from [`seal`]. Anything else is returned unchanged.
+pub fn open(key: Option<&AtRestKey>, bytes: Vec<u8>) -> Result<Vec<u8>, AtRestError> {
+    if !is_sealed(&bytes) {
+        return Ok(bytes);
+    }
+    let key = key.ok_or(AtRestError::NoKey)?;
+    let body = &bytes[SEALED_MAGIC.len()..];
+    if body.len() < NONCE_BYTES {
+        return Err(AtRestError::Truncated);
+    }
+    let (nonce, ciphertext) = body.split_at(NONCE_BYTES);
+    key.cipher()
+        .decrypt(XNonce::from_slice(nonce), ciphertext)
+        .map_err(|_| AtRestError::Tampered)
+}
+
+/// Loads the key used to read sealed files and, with `seal_writes`, to seal
+/// what is written from now on. `None` forgets it.
+pub fn configure(key: Option<AtRestKey>, seal_writes: bool) {
+    let seal_writes = seal_writes && key.is_some();
+    *KEY.write_recover() = key;
+    SEAL_WRITES.store(seal_writes, Ordering::Release);
+}
+
+/// Whether utterance clips and the decode cache are written encrypted.
+pub fn seals_writes() -> bool {
+    SEAL_WRITES.load(Ordering::Acquire)
+}
+
+/// `bytes` as they should be stored: sealed while encryption is on.
+pub fn for_storage(bytes: Vec<u8>) -> io::Result<Vec<u8>> {
+    if !seals_writes() {
+        return Ok(bytes);
+    }
+    seal_with_loaded_key(&bytes)
+}
+
+fn seal_with_loaded_key(bytes: &[u8]) -> io::Result<Vec<u8>> {
+    let key = KEY.read_recover();
+    let key = key.as_ref().ok_or_else(|| io_error(AtRestError::NoKey))?;
+    seal(key, bytes).map_err(io_error)
+}
+
+/// Reads a file, decrypting it when it was sealed.
+pub fn read(path: &Path) -> io::Result<Vec<u8>> {
+    let bytes = std::fs::read(path)?;
+    open(KEY.read_recover().as_ref(), bytes).map_err(io_error)
+}
+
+pub fn read_to_string(path: &Path) -> io::Result<String> {
+    String::from_utf8(read(path)?)
+        .map_err(|error| io::Error::new(io::ErrorKind::InvalidData, error))
+}
+
+/// Replaces a file's contents, sealed while encryption is on. The contents
+/// go to a temporary file beside it that is then renamed over it, so a crash
+/// or full disk partway through leaves the previous file whole.
+pub fn write(path: &Path, bytes: impl Into<Vec<u8>>) -> io::Result<()> {
+    replace(path, &for_storage(bytes.into())?)
+}
+
+/// Like [`write`], but sealed only when the file already is, whatever the
+/// setting. For files the user chose, such as the capture file, so turning
+/// encryption on never makes one unreadable in the editor it was chosen for.
+pub fn write_as_found(path: &Path, bytes: impl Into<Vec<u8>>) -> io::Result<()> {
+    let bytes = bytes.into();
+    if file_is_sealed(path)? {
+        replace(path, &seal_with_loaded_key(&bytes)?)
+    } else {
+        replace(path, &bytes)
+    }
+}
+
+fn replace(path: &Path, bytes: &[u8]) -> io::Result<()> {
+    let temporary = temporary_path(path);
+    let written = File::create(&temporary)
+        .and_then(|mut file| {
+            file.write_all(bytes)?;
+            file.sync_all()
+        })
+        .and_then(|()| std::fs::rename(&temporary, path));
+    if written.is_err() {
+        let _ = std::fs::remove_file(&temporary);
+    }
+    written
+}
+
+fn temporary_path(path: &Path) -> PathBuf {
+    let mut name = path.file_name().unwrap_or_default().to_os_string();
+    name.push(".tmp");
+    path.with_file_name(name)
+}
+
+/// Whether the file at `path` starts with [`SEALED_MAGIC`]. A missing file is
+/// not sealed.
+pub fn file_is_sealed(path: &Path) -> io::Result<bool> {
+    use std::io::Read;
+    let mut magic = [0_u8; SEALED_MAGIC.len()];
+    match std::fs::File::open(path).and_then(|mut file| file.read_exact(&mut magic)) {
+        Ok(()) => Ok(&magic == SEALED_MAGIC),
+        Err(error)
+            if matches!(
+                error.kind(),
+                io::ErrorKind::NotFound | io::ErrorKind::UnexpectedEof
+            ) =>
+        {
+            Ok(false)
+        }
+        Err(error) => Err(error),
+    }
+}
+
+fn io_error(error: AtRestError) -> io::Error {
+    io::Error::new(io::ErrorKind::InvalidData, error)
+}
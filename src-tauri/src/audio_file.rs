@@ -0,0 +1,301 @@
+use std::io;
+use std::path::{Path, PathBuf};
+
+use serde::{Deserialize, Serialize};
+
+use crate::asr::TARGET_SAMPLE_RATE;
+use crate::audio_processing::{AudioError, AudioProcessor, ResamplerQuality};
+use crate::errors::UserFacing;
+use crate::vad::{speech_segments, VadConfig};
+
+/// About an hour of 48 kHz stereo 16-bit audio.
+pub const MAX_AUDIO_FILE_BYTES: u64 = 700 * 1024 * 1024;
+
+const WAVE_FORMAT_PCM: u16 = 1;
+const WAVE_FORMAT_IEEE_FLOAT: u16 = 3;
+const WAVE_FORMAT_EXTENSIBLE: u16 = 0xFFFE;
+/// Where [`WavExport::normalize`] puts a clip's loudest sample: -1 dBFS,
+/// leaving headroom for players that resample.
+pub const NORMALIZE_PEAK: f32 = 0.891;
+
+#[derive(thiserror::Error, Debug)]
+pub enum AudioFileError {
+    #[error("read audio file {path:?}: {source}")]
+    Read {
+        path: PathBuf,
+        #[source]
+        source: io::Error,
+    },
+    #[error("audio file {path:?} is larger than {MAX_AUDIO_FILE_BYTES} bytes")]
+    TooLarge { path: PathBuf },
+    #[error("not a WAV file: {0}")]
+    NotWav(&'static str),
+    #[error("unsupported WAV encoding: format {format}, {bits} bits per sample")]
+    Unsupported { format: u16, bits: u16 },
+    #[error("WAV file has no audio")]
+    Empty,
+    #[error(transparent)]
+    Resample(#[from] AudioError),
+}
+
+impl UserFacing for AudioFileError {
+    fn user_message(&self) -> &'static str {
+        match self {
+            Self::Read { .. } => "Could not read the audio file.",
+            Self::TooLarge { .. } => "The audio file is too large to transcribe.",
+            Self::NotWav(_) | Self::Unsupported { .. } => {
+                "Only PCM or floating-point WAV files can be transcribed."
+            }
+            Self::Empty => "The audio file has no audio.",
+            Self::Resample(_) => "Could not convert the audio file for transcription.",
+        }
+    }
+}
+
+/// Interleaved samples as stored in a WAV file, mixed down to mono.
+#[derive(Clone, Debug, PartialEq)]
+pub struct WavAudio {
+    pub sample_rate: u32,
+    pub samples: Vec<f32>,
+}
+
+/// Decodes 8-, 16-, 24-, or 32-bit PCM and 32- or 64-bit float WAV data,
+/// averaging the channels.
+pub fn decode_wav(bytes: &[u8]) -> Result<WavAudio, AudioFileError> {
+    if bytes.len() < 12 || &bytes[..4] != b"RIFF" || &bytes[8..12] != b"WAVE" {
+        return Err(AudioFileError::NotWav("missing RIFF/WAVE header"));
+    }
+    let mut format = None;
+    let mut data = None;
+    let mut offset = 12;
+    while offset + 8 <= bytes.len() {
+        let id = &bytes[offset..offset + 4];
+        let len = le_u32(&bytes[offset + 4..]) as usize;
+        let body = &bytes[offset + 8..(offset + 8).saturating_add(len).min(bytes.len())];
+        match id {
+            b"fmt " => format = Some(parse_format(body)?),
+            b"data" => data = Some(body),
+            _ => {}
+        }
+        // Chunks are padded to an even length.
+        offset = offset.saturating_add(8 + len + len % 2);
+    }
+    let format = format.ok_or(AudioFileError::NotWav("missing fmt chunk"))?;
+    let data = data.ok_or(AudioFileError::NotWav("missing data chunk"))?;
+
+    let channels = usize::from(format.channels);
+    let frame_bytes = channels * usize::from(format.bits / 8);
+    let samples: Vec<f32> = data
+        .chunks_exact(frame_bytes)
+        .map(|frame| {
+            let sum: f32 = frame
+                .chunks_exact(usize::from(format.bits / 8))
+                .map(|sample| decode_sample(sample, format.encoding))
+                .sum();
+            sum / channels as f32
+        })
+        .collect();
+    if samples.is_empty() {
+        return Err(AudioFileError::Empty);
+    }
+    Ok(WavAudio {
+        sample_rate: format.sample_rate,
+        samples,
+    })
+}
+
+/// Sample format of written WAV files.
+#[derive(Clone, Copy, Debug, Default, Deserialize, Eq, PartialEq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum WavEncoding {
+    /// 16-bit PCM: half the size, and plenty for speech.
+    #[default]
+    Pcm16,
+    /// 32-bit float: the samples exactly as the model heard them.
+    Float32,
+}
+
+/// How kept recordings are cleaned up before they are written.
+#[derive(Clone, Copy, Debug, Default, Deserialize, Eq, PartialEq, Serialize)]
+#[serde(default)]
+pub struct WavExport {
+    /// Drops the silence before the first and after the last speech that
+    /// voice activity detection hears, keeping its padding.
+    pub trim_silence: bool,
+    /// Scales the audio so its peak reaches [`NORMALIZE_PEAK`].
+    pub normalize: bool,
+    pub encoding: WavEncoding,
+}
+
+impl WavExport {
+    /// The samples to write and how many were trimmed from the start.
+    /// Audio without detected speech is kept whole.
+    pub fn prepare(&self, samples: &[f32], vad: &VadConfig) -> (usize, Vec<f32>) {
+        let range = if self.trim_silence {
+            let segments = speech_segments(samples, vad);
+            match (segments.first(), segments.last()) {
+                (Some(first), Some(last)) => first.start..last.end,
+                _ => 0..samples.len(),
+            }
+        } else {
+            0..samples.len()
+        };
+        let start = range.start;
+        let mut kept = samples[range].to_vec();
+        if self.normalize {
+            let peak = kept
+                .iter()
+                .fold(0.0_f32, |peak, sample| peak.max(sample.abs()));
+            if peak > 0.0 {
+                let gain = NORMALIZE_PEAK / peak;
+                kept.iter_mut().for_each(|sample| *sample *= gain);
+            }
+        }
+        (start, kept)
+    }
+
+    pub fn encode(&self, samples: &[f32], sample_rate: u32) -> Vec<u8> {
+        match self.encoding {
+            WavEncoding::Pcm16 => encode_wav(samples, sample_rate),
+            WavEncoding::Float32 => encode_float_wav(samples, sample_rate),
+        }
+    }
+}
+
+/// Encodes mono samples as a 16-bit PCM WAV file.
+pub fn encode_wav(samples: &[f32], sample_rate: u32) -> Vec<u8> {
+    let mut bytes = wav_header(samples.len(), sample_rate, WAVE_FORMAT_PCM, 16);
+    for sample in samples {
+        let value = (sample.clamp(-1.0, 1.0) * f32::from(i16::MAX)) as i16;
+        bytes.extend_from_slice(&value.to_le_bytes());
+    }
+    bytes
+}
+
+/// Encodes mono samples as a 32-bit float WAV file, unclipped.
+pub fn encode_float_wav(samples: &[f32], sample_rate: u32) -> Vec<u8> {
+    let mut bytes = wav_header(samples.len(), sample_rate, WAVE_FORMAT_IEEE_FLOAT, 32);
+    for sample in samples {
+        bytes.extend_from_slice(&sample.to_le_bytes());
+    }
+    bytes
+}
+
+/// The RIFF, `fmt `, and `data` headers of a mono WAV file, with room for
+/// its samples.
+fn wav_header(samples: usize, sample_rate: u32, format: u16, bits: u16) -> Vec<u8> {
+    let sample_bytes = u32::from(bits / 8);
+    let data_len = u32::try_from(samples * sample_bytes as usize).unwrap_or(u32::MAX - 36);
+    let mut bytes = Vec::with_capacity(44 + data_len as usize);
+    bytes.extend_from_slice(b"RIFF");
+    bytes.extend_from_slice(&(36 + data_len).to_le_bytes());
+    bytes.extend_from_slice(b"WAVEfmt ");
+    bytes.extend_from_slice(&16_u32.to_le_bytes());
+    bytes.extend_from_slice(&format.to_le_bytes());
+    bytes.extend_from_slice(&1_u16.to_le_bytes());
+    bytes.extend_from_slice(&sample_rate.to_le_bytes());
+    bytes.extend_from_slice(&(sample_rate * sample_bytes).to_le_bytes());
+    bytes.extend_from_slice(&(sample_bytes as u16).to_le_bytes());
+    bytes.extend_from_slice(&bits.to_le_bytes());
+    bytes.extend_from_slice(b"data");
+    bytes.extend_from_slice(&data_len.to_le_bytes());
+    bytes
+}
+
+/// Reads a WAV file and converts it to the model's 16 kHz mono.
+pub fn read_wav(path: &Path) -> Result<Vec<f32>, AudioFileError> {
+    let read_error = |source| AudioFileError::Read {
+        path: path.to_path_buf(),
+        source,
+    };
+    let size = std::fs::metadata(path).map_err(read_error)?.len();
+    if size > MAX_AUDIO_FILE_BYTES {
+        return Err(AudioFileError::TooLarge {
+            path: path.to_path_buf(),
+        });
+    }
+    let wav = decode_wav(&crate::at_rest::read(path).map_err(read_error)?)?;
+    resample(wav)
+}
+
+fn resample(wav: WavAudio) -> Result<Vec<f32>, AudioFileError> {
+    if wav.sample_rate == TARGET_SAMPLE_RATE {
+        return Ok(wav.samples);
+    }
+    let mut processor = AudioProcessor::with_quality(
+        wav.sample_rate as usize,
+        TARGET_SAMPLE_RATE as usize,
+        ResamplerQuality::High,
+    )?;
+    let mut samples = Vec::with_capacity(
+        wav.samples.len() * TARGET_SAMPLE_RATE as usize / wav.sample_rate as usize,
+    );
+    processor.process(&wav.samples, |frame| samples.extend(frame.samples))?;
+    processor.flush(|frame| samples.extend(frame.samples))?;
+    Ok(samples)
+}
+
+#[derive(Clone, Copy, PartialEq)]
+enum Encoding {
+    Pcm,
+    Float,
+}
+
+struct Format {
+    encoding: Encoding,
+    channels: u16,
+    sample_rate: u32,
+    bits: u16,
+}
+
+fn parse_format(body: &[u8]) -> Result<Format, AudioFileError> {
+    if body.len() < 16 {
+        return Err(AudioFileError::NotWav("short fmt chunk"));
+    }
+    let read_u16 = |at: usize| u16::from_le_bytes([body[at], body[at + 1]]);
+    let mut tag = read_u16(0);
+    if tag == WAVE_FORMAT_EXTENSIBLE && body.len() >= 26 {
+        // The sub-format GUID starts with the plain format tag.
+        tag = read_u16(24);
+    }
+    let channels = read_u16(2);
+    let sample_rate = le_u32(&body[4..]);
+    let bits = read_u16(14);
+    let encoding = match (tag, bits) {
+        (WAVE_FORMAT_PCM, 8 | 16 | 24 | 32) => Encoding::Pcm,
+        (WAVE_FORMAT_IEEE_FLOAT, 32 | 64) => Encoding::Float,
+        (format, bits) => return Err(AudioFileError::Unsupported { format, bits }),
+    };
+    if channels == 0 || sample_rate == 0 {
+        return Err(AudioFileError::NotWav("no channels or sample rate"));
+    }
+    Ok(Format {
+        encoding,
+        channels,
+        sample_rate,
+        bits,
+    })
+}
+
+fn decode_sample(bytes: &[u8], encoding: Encoding) -> f32 {
+    match (encoding, bytes.len()) {
+        // 8-bit WAV is unsigned around 128.
+        (Encoding::Pcm, 1) => (f32::from(bytes[0]) - 128.0) / 128.0,
+        (Encoding::Pcm, 2) => f32::from(i16::from_le_bytes([bytes[0], bytes[1]])) / 32_768.0,
+        (Encoding::Pcm, 3) => {
+            let value = i32::from_le_bytes([0, bytes[0], bytes[1], bytes[2]]) >> 8;
+            value as f32 / 8_388_608.0
+        }
+        (Encoding::Pcm, _) => le_u32(bytes) as i32 as f32 / 2_147_483_648.0,
+        (Encoding::Float, 4) => f32::from_bits(le_u32(bytes)),
+        (Encoding::Float, _) => {
+            let low = u64::from(le_u32(bytes));
+            let high = u64::from(le_u32(&bytes[4..]));
+            f64::from_bits(high << 32 | low) as f32
+        }
+    }
+}
+
+fn le_u32(bytes: &[u8]) -> u32 {
+    u32::from_le_bytes([bytes[0], bytes[1], bytes[2], bytes[3]])
+}
@@ -1,11 +1,42 @@
 use rubato::{
-    audioadapter_buffers::direct::InterleavedSlice, Async, FixedAsync, Resampler,
+    audioadapter_buffers::direct::InterleavedSlice, Async, FixedAsync, PolynomialDegree, Resampler,
     SincInterpolationParameters, SincInterpolationType, WindowFunction,
 };
+use serde::{Deserialize, Serialize};
 use std::collections::VecDeque;
 
 pub const PROCESS_CHUNK_SIZE: usize = 480;
 const RESAMPLER_CHUNK_OUT: usize = PROCESS_CHUNK_SIZE;
+/// Machines with fewer cores than this default to the polynomial resampler.
+const HIGH_QUALITY_MIN_CORES: usize = 4;
+
+#[derive(Clone, Copy, Debug, Default, Deserialize, Eq, PartialEq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ResamplerQuality {
+    #[default]
+    Auto,
+    Fast,
+    High,
+}
+
+impl ResamplerQuality {
+    /// Resolves `Auto` by CPU class; explicit choices are returned unchanged.
+    pub fn resolve(self) -> Self {
+        match self {
+            Self::Auto => {
+                let cores = std::thread::available_parallelism()
+                    .map(|cores| cores.get())
+                    .unwrap_or(1);
+                if cores < HIGH_QUALITY_MIN_CORES {
+                    Self::Fast
+                } else {
+                    Self::High
+                }
+            }
+            quality => quality,
+        }
+    }
+}
 
 #[derive(thiserror::Error, Debug)]
 pub enum AudioError {
@@ -15,6 +46,20 @@ pub enum AudioError {
     ResamplerProcessing(String),
 }
 
+/// One step between the input device and the model, in the order applied,
+/// as reported in capture diagnostics.
+#[derive(Clone, Copy, Debug, Eq, PartialEq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ProcessingStage {
+    Downmix,
+    Resample,
+    /// Regrouping samples into fixed frames, when the rate already matches.
+    Rechunk,
+    Gain,
+    NoiseReduction,
+    SpeakerNormalization,
+}
+
 #[derive(Debug)]
 pub struct AudioFrame {
     pub samples: Vec<f32>,
@@ -22,6 +67,8 @@ pub struct AudioFrame {
 
 pub struct AudioProcessor {
     resampler: Option<Async<f32>>,
+    /// Emits input as it arrives instead of regrouping it.
+    bypass: bool,
     buffer: VecDeque<f32>,
     scratch_in: Vec<f32>,
     scratch_out: Vec<f32>,
@@ -29,32 +76,55 @@ pub struct AudioProcessor {
 
 impl AudioProcessor {
     pub fn new(in_sample_rate: usize, out_sample_rate: usize) -> Result<Self, AudioError> {
-        let resampler = if in_sample_rate != out_sample_rate {
-            let params = SincInterpolationParameters {
-                sinc_len: 256,
-                f_cutoff: Some(0.95),
-                interpolation: SincInterpolationType::Linear,
-                oversampling_factor: 256,
-                window: WindowFunction::BlackmanHarris2,
-            };
+        Self::with_quality(in_sample_rate, out_sample_rate, ResamplerQuality::High)
+    }
+
+    /// Passes audio straight through, for sources that are already mono at
+    /// the model's rate. Nothing is held back, so frames add no latency.
+    pub fn bypass() -> Self {
+        Self {
+            resampler: None,
+            bypass: true,
+            buffer: VecDeque::new(),
+            scratch_in: Vec::new(),
+            scratch_out: Vec::new(),
+        }
+    }
+
+    /// A bypass for a mono source at `out_sample_rate`, otherwise a processor
+    /// that resamples or rechunks. `channels` is the device's count before
+    /// downmixing.
+    pub fn for_source(
+        in_sample_rate: usize,
+        channels: usize,
+        out_sample_rate: usize,
+        quality: ResamplerQuality,
+    ) -> Result<Self, AudioError> {
+        if channels == 1 && in_sample_rate == out_sample_rate {
+            log::info!("Input is mono at {in_sample_rate} Hz; bypassing audio preprocessing");
+            return Ok(Self::bypass());
+        }
+        Self::with_quality(in_sample_rate, out_sample_rate, quality)
+    }
 
+    pub fn with_quality(
+        in_sample_rate: usize,
+        out_sample_rate: usize,
+        quality: ResamplerQuality,
+    ) -> Result<Self, AudioError> {
+        let resampler = if in_sample_rate != out_sample_rate {
+            let quality = quality.resolve();
             log::info!(
-                "Configuring resampler: {} Hz -> {} Hz",
+                "Configuring {:?} resampler: {} Hz -> {} Hz",
+                quality,
                 in_sample_rate,
                 out_sample_rate
             );
 
-            Some(
-                Async::<f32>::new_sinc(
-                    out_sample_rate as f64 / in_sample_rate as f64,
-                    2.0,
-                    &params,
-                    RESAMPLER_CHUNK_OUT,
-                    1,
-                    FixedAsync::Output,
-                )
-                .map_err(|e| AudioError::ResamplerCreation(e.to_string()))?,
-            )
+            Some(build_resampler(
+                out_sample_rate as f64 / in_sample_rate as f64,
+                quality,
+            )?)
         } else {
             log::debug!(
                 "Resampler not needed ({} Hz input matches target)",
@@ -65,17 +135,45 @@ impl AudioProcessor {
 
         Ok(Self {
             resampler,
+            bypass: false,
             buffer: VecDeque::with_capacity(4096),
             scratch_in: Vec::with_capacity(2048),
             scratch_out: Vec::with_capacity(PROCESS_CHUNK_SIZE),
         })
     }
 
+    pub fn is_resampling(&self) -> bool {
+        self.resampler.is_some()
+    }
+
+    pub fn is_bypassed(&self) -> bool {
+        self.bypass
+    }
+
+    /// What this processor does to the audio, `None` when bypassed.
+    pub fn stage(&self) -> Option<ProcessingStage> {
+        if self.bypass {
+            None
+        } else if self.resampler.is_some() {
+            Some(ProcessingStage::Resample)
+        } else {
+            Some(ProcessingStage::Rechunk)
+        }
+    }
+
     pub fn process(
         &mut self,
         data: &[f32],
         mut emit: impl FnMut(AudioFrame),
     ) -> Result<(), AudioError> {
+        if self.bypass {
+            for frame in data.chunks(PROCESS_CHUNK_SIZE) {
+                emit(AudioFrame {
+                    samples: frame.to_vec(),
+                });
+            }
+            return Ok(());
+        }
         self.buffer.extend(data.iter());
 
         loop {
@@ -164,3 +262,34 @@ impl AudioProcessor {
         Ok(())
     }
 }
+
+fn build_resampler(ratio: f64, quality: ResamplerQuality) -> Result<Async<f32>, AudioError> {
+    let resampler = match quality {
+        ResamplerQuality::Fast => Async::<f32>::new_poly(
+            ratio,
+            2.0,
+            PolynomialDegree::Cubic,
+            RESAMPLER_CHUNK_OUT,
+            1,
+            FixedAsync::Output,
+        ),
+        ResamplerQuality::Auto | ResamplerQuality::High => {
+            let params = SincInterpolationParameters {
+                sinc_len: 256,
+                f_cutoff: Some(0.95),
+                interpolation: SincInterpolationType::Linear,
+                oversampling_factor: 256,
+                window: WindowFunction::BlackmanHarris2,
+            };
+            Async::<f32>::new_sinc(
+                ratio,
+                2.0,
+                &params,
+                RESAMPLER_CHUNK_OUT,
+                1,
+                FixedAsync::Output,
+            )
+        }
+    };
+    resampler.map_err(|e| AudioError::ResamplerCreation(e.to_string()))
+}
@@ -0,0 +1,202 @@
+use std::io;
+use std::path::Path;
+
+use serde::{Deserialize, Serialize};
+
+use crate::errors::UserFacing;
+
+/// Passed by the login item so a launch at login can be told apart from one
+/// started by the user.
+pub const AUTOSTART_ARG: &str = "--autostart";
+
+/// Registration as a per-user login item. `start_in_tray` keeps the window
+/// hidden when the app is launched at login; launching it by hand still opens
+/// the window.
+#[derive(Clone, Copy, Debug, Default, Deserialize, Eq, PartialEq, Serialize)]
+pub struct AutostartSettings {
+    pub enabled: bool,
+    pub start_in_tray: bool,
+}
+
+impl AutostartSettings {
+    pub fn starts_hidden<S: AsRef<str>>(&self, args: &[S]) -> bool {
+        self.enabled
+            && self.start_in_tray
+            && args.iter().skip(1).any(|arg| arg.as_ref() == AUTOSTART_ARG)
+    }
+}
+
+#[derive(thiserror::Error, Debug)]
+pub enum AutostartError {
+    #[error("locate app executable: {0}")]
+    Executable(#[source] io::Error),
+    #[error("write login item: {0}")]
+    Write(#[source] io::Error),
+    #[error("remove login item: {0}")]
+    Remove(#[source] io::Error),
+    #[error("update login item registry value: {0}")]
+    Registry(String),
+    #[error("start at login is not supported on this platform")]
+    Unsupported,
+}
+
+impl UserFacing for AutostartError {
+    fn user_message(&self) -> &'static str {
+        match self {
+            Self::Executable(_) | Self::Write(_) | Self::Remove(_) | Self::Registry(_) => {
+                "Could not update the login item. Check the logs for details."
+            }
+            Self::Unsupported => "Start at login is not supported on this platform.",
+        }
+    }
+}
+
+fn escape_xml(value: &str) -> String {
+    value
+        .replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+}
+
+/// A per-user LaunchAgent that runs the app once at login.
+pub fn launch_agent_plist(label: &str, executable: &Path) -> String {
+    format!(
+        r#"<?xml version="1.0" encoding="UTF-8"?>
+<!DOCTYPE plist PUBLIC "-//Apple//DTD PLIST 1.0//EN" "http://www.apple.com/DTDs/PropertyList-1.0.dtd">
+<plist version="1.0">
+<dict>
+    <key>Label</key>
+    <string>{}</string>
+    <key>ProgramArguments</key>
+    <array>
+        <string>{}</string>
+        <string>{AUTOSTART_ARG}</string>
+    </array>
+    <key>RunAtLoad</key>
+    <true/>
+    <key>ProcessType</key>
+    <string>Interactive</string>
+</dict>
+</plist>
+"#,
+        escape_xml(label),
+        escape_xml(&executable.to_string_lossy()),
+    )
+}
+
+/// An XDG autostart entry. The executable is quoted as the desktop entry
+/// specification requires for paths with spaces or reserved characters.
+pub fn desktop_entry(name: &str, executable: &Path) -> String {
+    let mut quoted = String::from("\"");
+    for ch in executable.to_string_lossy().chars() {
+        if matches!(ch, '"' | '`' | '$' | '\\') {
+            quoted.push('\\');
+        }
+        quoted.push(ch);
+    }
+    quoted.push('"');
+    // The Exec key unescapes backslashes once more before the quoting rules.
+    let exec = quoted.replace('\\', "\\\\");
+    format!(
+        "[Desktop Entry]\nType=Application\nName={name}\nExec={exec} {AUTOSTART_ARG}\nTerminal=false\nX-GNOME-Autostart-enabled=true\n"
+    )
+}
+
+/// The command line stored under the Windows `Run` registry key.
+pub fn run_key_value(executable: &Path) -> String {
+    format!("\"{}\" {AUTOSTART_ARG}", executable.to_string_lossy())
+}
+
+#[cfg(any(target_os = "macos", target_os = "linux"))]
+fn write_login_item(path: &Path, contents: &str) -> Result<(), AutostartError> {
+    if let Some(parent) = path.parent() {
+        std::fs::create_dir_all(parent).map_err(AutostartError::Write)?;
+    }
+    std::fs::write(path, contents).map_err(AutostartError::Write)
+}
+
+#[cfg(any(target_os = "macos", target_os = "linux"))]
+fn remove_login_item(path: &Path) -> Result<(), AutostartError> {
+    match std::fs::remove_file(path) {
+        Err(error) if error.kind() != io::ErrorKind::NotFound => Err(AutostartError::Remove(error)),
+        _ => Ok(()),
+    }
+}
+
+/// Adds or removes the login item for `executable`. Registering again
+/// rewrites the item, so a moved or updated app keeps launching.
+#[cfg(target_os = "macos")]
+pub fn register(identifier: &str, executable: &Path, enabled: bool) -> Result<(), AutostartError> {
+    let home = dirs_next::home_dir().ok_or_else(|| {
+        AutostartError::Write(io::Error::new(
+            io::ErrorKind::NotFound,
+            "home directory not found",
+        ))
+    })?;
+    let path = home
+        .join("Library/LaunchAgents")
+        .join(format!("{identifier}.plist"));
+    if enabled {
+        write_login_item(&path, &launch_agent_plist(identifier, executable))
+    } else {
+        remove_login_item(&path)
+    }
+}
+
+/// Adds or removes the login item for `executable`. Registering again
+/// rewrites the item, so a moved or updated app keeps launching.
+#[cfg(target_os = "linux")]
+pub fn register(identifier: &str, executable: &Path, enabled: bool) -> Result<(), AutostartError> {
+    let config = dirs_next::config_dir().ok_or_else(|| {
+        AutostartError::Write(io::Error::new(
+            io::ErrorKind::NotFound,
+            "config directory not found",
+        ))
+    })?;
+    let path = config
+        .join("autostart")
+        .join(format!("{identifier}.desktop"));
+    if enabled {
+        write_login_item(&path, &desktop_entry("SilentKeys", executable))
+    } else {
+        remove_login_item(&path)
+    }
+}
+
+/// Adds or removes the login item for `executable`. Registering again
+/// rewrites the item, so a moved or updated app keeps launching.
+#[cfg(target_os = "windows")]
+pub fn register(identifier: &str, executable: &Path, enabled: bool) -> Result<(), AutostartError> {
+    const RUN_KEY: &str = r"HKCU\Software\Microsoft\Windows\CurrentVersion\Run";
+    let mut command = std::process::Command::new("reg");
+    if enabled {
+        command.args(["add", RUN_KEY, "/v", identifier, "/t", "REG_SZ", "/d"]);
+        command.arg(run_key_value(executable));
+        command.arg("/f");
+    } else {
+        command.args(["delete", RUN_KEY, "/v", identifier, "/f"]);
+    }
+    let output = command
+        .output()
+        .map_err(|error| AutostartError::Registry(error.to_string()))?;
+    // Deleting a value that was never added fails; that is already the goal.
+    if output.status.success() || !enabled {
+        return Ok(());
+    }
+    Err(AutostartError::Registry(
+        String::from_utf8_lossy(&output.stderr).trim().to_string(),
+    ))
+}
+
+#[cfg(not(any(target_os = "macos", target_os = "linux", target_os = "windows")))]
+pub fn register(
+    _identifier: &str,
+    _executable: &Path,
+    enabled: bool,
+) -> Result<(), AutostartError> {
+    if enabled {
+        return Err(AutostartError::Unsupported);
+    }
+    Ok(())
+}
@@ -0,0 +1,362 @@
+use std::fs::{File, OpenOptions};
+use std::io::{self, Read, Seek, SeekFrom, Write};
+use std::path::{Path, PathBuf};
+
+use chrono::NaiveDateTime;
+use serde::{Deserialize, Serialize};
+
+use crate::asr::TARGET_SAMPLE_RATE;
+use crate::at_rest;
+use crate::audio_file::WavExport;
+use crate::errors::UserFacing;
+use crate::transcript::Transcript;
+use crate::vad::VadConfig;
+
+/// Entry headings are `## YYYY-MM-DD HH:MM`, followed by a title when the
+/// entry is named.
+const HEADING_FORMAT: &str = "%Y-%m-%d %H:%M";
+/// Length of a [`HEADING_FORMAT`] timestamp.
+const HEADING_LEN: usize = 16;
+const MAX_TITLE_WORDS: usize = 8;
+/// Spoken before a title, as in "title: weekly sync".
+const TITLE_TRIGGER: &str = "title";
+/// Utterance lines are `- [MM:SS](<clip>) text`, the link pointing at the
+/// utterance's audio relative to the capture file.
+const UTTERANCE_PREFIX: &str = "- [";
+const CLIP_TIME_FORMAT: &str = "%Y%m%d-%H%M%S%3f";
+
+/// Appends finished shortcut dictations to a Markdown file instead of typing
+/// them.
+#[derive(Clone, Debug, Default, Deserialize, Eq, PartialEq, Serialize)]
+#[serde(default)]
+pub struct CaptureFileSettings {
+    pub enabled: bool,
+    pub path: String,
+    /// Adds a title to each entry's heading, from a spoken "title: ..."
+    /// phrase or else the entry's first sentence.
+    pub name_entries: bool,
+    /// Writes each utterance on its own line, linked to its audio, so one
+    /// utterance can be transcribed again from the history.
+    pub split_utterances: bool,
+    /// How the utterance clips are written.
+    pub clip_export: WavExport,
+}
+
+#[derive(thiserror::Error, Debug)]
+pub enum CaptureFileError {
+    #[error("capture file path must be absolute: {0:?}")]
+    InvalidPath(String),
+    #[error("write capture file {path:?}: {source}")]
+    Write {
+        path: PathBuf,
+        #[source]
+        source: io::Error,
+    },
+}
+
+impl UserFacing for CaptureFileError {
+    fn user_message(&self) -> &'static str {
+        match self {
+            Self::InvalidPath(_) => "Choose a full path for the capture file.",
+            Self::Write { .. } => {
+                "Could not write to the capture file. Check that its folder exists and is writable."
+            }
+        }
+    }
+}
+
+pub fn capture_path(path: &str) -> Result<PathBuf, CaptureFileError> {
+    let path = Path::new(path.trim());
+    if path.is_absolute() {
+        Ok(path.to_path_buf())
+    } else {
+        Err(CaptureFileError::InvalidPath(path.display().to_string()))
+    }
+}
+
+pub fn format_entry(text: &str, now: NaiveDateTime) -> String {
+    format_titled_entry(None, text, now)
+}
+
+pub fn format_titled_entry(title: Option<&str>, text: &str, now: NaiveDateTime) -> String {
+    format!("## {}\n\n{}\n", format_heading(now, title), text.trim())
+}
+
+/// An entry heading's text, without the `## ` marker.
+pub fn format_heading(at: NaiveDateTime, title: Option<&str>) -> String {
+    let at = at.format(HEADING_FORMAT);
+    let title = title
+        .map(|title| title.split_whitespace().collect::<Vec<_>>().join(" "))
+        .unwrap_or_default();
+    if title.is_empty() {
+        at.to_string()
+    } else {
+        format!("{at} {title}")
+    }
+}
+
+/// Reads an entry heading's text, without the `## ` marker, into its
+/// timestamp and title. Headings that do not start with a timestamp are not
+/// entries.
+pub fn parse_heading(heading: &str) -> Option<(NaiveDateTime, Option<&str>)> {
+    let heading = heading.trim();
+    let stamp = heading.get(..HEADING_LEN)?;
+    let rest = &heading[HEADING_LEN..];
+    if !rest.is_empty() && !rest.starts_with(' ') {
+        return None;
+    }
+    let at = NaiveDateTime::parse_from_str(stamp, HEADING_FORMAT).ok()?;
+    let title = rest.trim();
+    Some((at, (!title.is_empty()).then_some(title)))
+}
+
+/// Where an utterance starts in its recording, as `MM:SS`, or `H:MM:SS`
+/// from the first hour on.
+pub fn format_offset(ms: u64) -> String {
+    let seconds = ms / 1_000;
+    let (hours, minutes, seconds) = (seconds / 3_600, seconds / 60 % 60, seconds % 60);
+    if hours > 0 {
+        format!("{hours}:{minutes:02}:{seconds:02}")
+    } else {
+        format!("{minutes:02}:{seconds:02}")
+    }
+}
+
+pub fn format_utterance(start_ms: u64, clip: &str, text: &str) -> String {
+    UtteranceLine {
+        at: &format_offset(start_ms),
+        clip,
+        text,
+    }
+    .to_string()
+}
+
+/// A line of a split entry.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct UtteranceLine<'a> {
+    /// The offset as written, such as `01:05`.
+    pub at: &'a str,
+    pub clip: &'a str,
+    pub text: &'a str,
+}
+
+impl std::fmt::Display for UtteranceLine<'_> {
+    /// Writes the line with the text on one line.
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let text = self.text.split_whitespace().collect::<Vec<_>>().join(" ");
+        write!(f, "{UTTERANCE_PREFIX}{}](<{}>) {text}", self.at, self.clip)
+    }
+}
+
+pub fn parse_utterance(line: &str) -> Option<UtteranceLine<'_>> {
+    let rest = line.trim_end().strip_prefix(UTTERANCE_PREFIX)?;
+    let (at, rest) = rest.split_once("](<")?;
+    let (clip, text) = rest.split_once(">)")?;
+    let is_offset = !at.is_empty() && at.chars().all(|c| c.is_ascii_digit() || c == ':');
+    (is_offset && !clip.is_empty()).then_some(UtteranceLine {
+        at,
+        clip,
+        text: text.trim(),
+    })
+}
+
+/// The folder beside the capture file that keeps the audio of its split
+/// entries: `notes.md` keeps it in `notes audio`.
+pub fn clip_folder(capture: &Path) -> String {
+    let stem = capture
+        .file_stem()
+        .map(|stem| stem.to_string_lossy().into_owned())
+        .unwrap_or_default();
+    format!("{stem} audio")
+}
+
+/// Names an entry. A leading "title: ..." phrase, up to the end of its
+/// sentence, becomes the title and is left out of the returned text unless
+/// nothing else was said; otherwise the first sentence, cut to a few words,
+/// is the title.
+pub fn entry_title(text: &str) -> (String, &str) {
+    let text = text.trim();
+    let first_word = text.split_whitespace().next().unwrap_or_default();
+    if first_word
+        .trim_end_matches([':', ','])
+        .eq_ignore_ascii_case(TITLE_TRIGGER)
+    {
+        let (title, body) = split_sentence(text[first_word.len()..].trim_start());
+        if !title.is_empty() {
+            return (capitalize(title), if body.is_empty() { text } else { body });
+        }
+    }
+    let (sentence, _) = split_sentence(text);
+    let words: Vec<&str> = sentence.split_whitespace().collect();
+    let mut title = words[..words.len().min(MAX_TITLE_WORDS)].join(" ");
+    if words.len() > MAX_TITLE_WORDS {
+        title.push('…');
+    }
+    (title, text)
+}
+
+/// Splits off the first sentence, without its closing period, from the rest.
+fn split_sentence(text: &str) -> (&str, &str) {
+    match text.find(['.', '!', '?', '\n']) {
+        Some(end) => {
+            let closing = &text[end..end + 1];
+            let sentence = if closing == "." {
+                &text[..end]
+            } else {
+                &text[..end + closing.len()]
+            };
+            (sentence.trim(), text[end + 1..].trim())
+        }
+        None => (text.trim(), ""),
+    }
+}
+
+fn capitalize(text: &str) -> String {
+    let mut chars = text.chars();
+    match chars.next() {
+        Some(first) => first.to_uppercase().chain(chars).collect(),
+        None => String::new(),
+    }
+}
+
+fn open_for_append(path: &Path) -> Result<File, CaptureFileError> {
+    OpenOptions::new()
+        .create(true)
+        .append(true)
+        .open(path)
+        .map_err(|source| CaptureFileError::Write {
+            path: path.to_path_buf(),
+            source,
+        })
+}
+
+/// Opens the file as a capture would, creating it if needed, so an unwritable
+/// path is reported when it is configured rather than after a dictation.
+pub fn check_writable(path: &str) -> Result<(), CaptureFileError> {
+    open_for_append(&capture_path(path)?).map(drop)
+}
+
+/// Blank-line separator needed before a new entry so its heading starts on
+/// its own line.
+fn separator(path: &Path) -> io::Result<&'static str> {
+    let mut file = File::open(path)?;
+    if file.metadata()?.len() == 0 {
+        return Ok("");
+    }
+    file.seek(SeekFrom::End(-1))?;
+    let mut last = [0_u8; 1];
+    file.read_exact(&mut last)?;
+    Ok(if last[0] == b'\n' { "\n" } else { "\n\n" })
+}
+
+/// Appends `text` under a timestamp heading, creating the file if needed.
+pub fn append_entry(path: &str, text: &str, now: NaiveDateTime) -> Result<(), CaptureFileError> {
+    append_titled_entry(path, None, text, now)
+}
+
+/// Appends `text` under a timestamp heading followed by `title`.
+pub fn append_titled_entry(
+    path: &str,
+    title: Option<&str>,
+    text: &str,
+    now: NaiveDateTime,
+) -> Result<(), CaptureFileError> {
+    append(&capture_path(path)?, &format_titled_entry(title, text, now))
+}
+
+/// Appends an entry with one line per segment of `transcript`, saving each
+/// segment's part of the 16 kHz `samples` as a WAV file in the capture file's
+/// [`clip_folder`].
+pub fn append_utterance_entry(
+    path: &str,
+    title: Option<&str>,
+    transcript: &Transcript,
+    samples: &[f32],
+    now: NaiveDateTime,
+) -> Result<(), CaptureFileError> {
+    append_exported_utterance_entry(
+        path,
+        title,
+        transcript,
+        samples,
+        &WavExport::default(),
+        &VadConfig::default(),
+        now,
+    )
+}
+
+/// Like [`append_utterance_entry`], writing each clip as `export` asks,
+/// with `vad` finding the silence to trim. A line's offset moves with the
+/// start of its trimmed clip.
+pub fn append_exported_utterance_entry(
+    path: &str,
+    title: Option<&str>,
+    transcript: &Transcript,
+    samples: &[f32],
+    export: &WavExport,
+    vad: &VadConfig,
+    now: NaiveDateTime,
+) -> Result<(), CaptureFileError> {
+    let path = capture_path(path)?;
+    let folder = clip_folder(&path);
+    let dir = path.with_file_name(&folder);
+    std::fs::create_dir_all(&dir).map_err(|source| CaptureFileError::Write {
+        path: dir.clone(),
+        source,
+    })?;
+    let stamp = now.format(CLIP_TIME_FORMAT);
+    let mut lines = Vec::with_capacity(transcript.segments.len());
+    for (number, segment) in transcript.segments.iter().enumerate() {
+        let name = format!("{stamp}-{}.wav", number + 1);
+        let clip = dir.join(&name);
+        let (trimmed, audio) = export.prepare(segment.audio(samples), vad);
+        let wav = export.encode(&audio, TARGET_SAMPLE_RATE);
+        at_rest::for_storage(wav)
+            .and_then(|wav| {
+                OpenOptions::new()
+                    .write(true)
+                    .create_new(true)
+                    .open(&clip)
+                    .and_then(|mut file| file.write_all(&wav))
+            })
+            .map_err(|source| CaptureFileError::Write { path: clip, source })?;
+        lines.push(format_utterance(
+            segment.start_ms + trimmed as u64 * 1_000 / u64::from(TARGET_SAMPLE_RATE),
+            &format!("{folder}/{name}"),
+            &segment.text,
+        ));
+    }
+    append(&path, &format_titled_entry(title, &lines.join("\n"), now))
+}
+
+fn append(path: &Path, entry: &str) -> Result<(), CaptureFileError> {
+    let write_error = |source| CaptureFileError::Write {
+        path: path.to_path_buf(),
+        source,
+    };
+    // The capture file is the user's note, so encryption never converts it.
+    // Only one sealed by an earlier version is rewritten whole, since it is
+    // one ciphertext that no editor has open.
+    if at_rest::file_is_sealed(path).map_err(write_error)? {
+        return rewrite_with(path, entry).map_err(write_error);
+    }
+    let mut file = open_for_append(path)?;
+    let separator = separator(path).map_err(write_error)?;
+    file.write_all(format!("{separator}{entry}").as_bytes())
+        .map_err(write_error)
+}
+
+fn rewrite_with(path: &Path, entry: &str) -> io::Result<()> {
+    let mut contents = match at_rest::read_to_string(path) {
+        Ok(contents) => contents,
+        Err(error) if error.kind() == io::ErrorKind::NotFound => String::new(),
+        Err(error) => return Err(error),
+    };
+    contents.push_str(match contents.chars().last() {
+        None => "",
+        Some('\n') => "\n",
+        Some(_) => "\n\n",
+    });
+    contents.push_str(entry);
+    at_rest::write_as_found(path, contents)
+}
@@ -0,0 +1,248 @@
+use std::path::{Path, PathBuf};
+
+use url::Url;
+
+use crate::autostart::AUTOSTART_ARG;
+use crate::errors::UserFacing;
+use crate::streaming::RecordingMode;
+
+pub const START_RECORDING_ARG: &str = "--start-recording";
+pub const STOP_RECORDING_ARG: &str = "--stop-recording";
+pub const TOGGLE_RECORDING_ARG: &str = "--toggle-recording";
+pub const TRANSCRIBE_FILE_ARG: &str = "--transcribe-file";
+pub const EXPORT_WORD_TIMINGS_ARG: &str = "--export-word-timings";
+/// The URL scheme other apps open to drive recordings, as in
+/// `silentkeys://record?mode=streaming&profile=code`.
+pub const DEEP_LINK_SCHEME: &str = "silentkeys";
+
+/// Any process can launch the executable, so a forwarded command line is
+/// bounded before it is looked at.
+pub const MAX_CLI_ARGS: usize = 16;
+pub const MAX_CLI_ARG_LEN: usize = 4096;
+
+/// A command passed on the command line, run by the instance that is already
+/// open when another is started.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum CliCommand {
+    StartRecording,
+    StopRecording,
+    ToggleRecording,
+    /// Starts a recording transcribed in the given mode rather than as the
+    /// streaming settings say.
+    StartRecordingIn(RecordingMode),
+    ToggleRecordingIn(RecordingMode),
+    /// Switches to a saved profile before the commands after it run.
+    SwitchProfile(String),
+    TranscribeFile(PathBuf),
+    /// Transcribes a file and writes its word timings rather than its text.
+    ExportWordTimings(PathBuf),
+}
+
+#[derive(thiserror::Error, Debug, Eq, PartialEq)]
+pub enum CliError {
+    #[error("more than {MAX_CLI_ARGS} arguments")]
+    TooManyArguments,
+    #[error("argument longer than {MAX_CLI_ARG_LEN} bytes")]
+    ArgumentTooLong,
+    #[error("unknown argument {0:?}")]
+    UnknownArgument(String),
+    #[error("{0} needs a file path")]
+    MissingPath(&'static str),
+    #[error("{0:?} is not a .wav file")]
+    NotWav(PathBuf),
+    #[error("relative path {0:?} without an absolute working directory")]
+    RelativePath(PathBuf),
+    #[error("{0:?} is not a SilentKeys link")]
+    UnknownLink(String),
+    #[error("unknown link parameter {0:?}")]
+    UnknownParameter(String),
+    #[error("unknown recording mode {0:?}")]
+    UnknownMode(String),
+    #[error("links from other apps are turned off")]
+    DeepLinksDisabled,
+}
+
+impl UserFacing for CliError {
+    fn user_message(&self) -> &'static str {
+        match self {
+            Self::TooManyArguments | Self::ArgumentTooLong | Self::UnknownArgument(_) => {
+                "The command line was not understood."
+            }
+            Self::MissingPath(_) | Self::RelativePath(_) => {
+                "Give the full path of the file to transcribe."
+            }
+            Self::NotWav(_) => "Only WAV files can be transcribed.",
+            Self::UnknownLink(_) | Self::UnknownParameter(_) => "The link was not understood.",
+            Self::UnknownMode(_) => "Use mode=streaming, mode=phrase, or mode=batch.",
+            Self::DeepLinksDisabled => "Turn on links from other apps in Settings to use them.",
+        }
+    }
+}
+
+/// Parses a full command line, program name first. Arguments the platform
+/// adds itself are skipped; anything else unrecognised rejects the whole
+/// line, so a typo never runs half of what was asked. Relative paths are
+/// resolved against `cwd`, the directory the command was run from. Windows
+/// and Linux open deep links by launching the app with the link as an
+/// argument, so links are parsed here too, and reject the line unless
+/// `deep_links` is on.
+pub fn parse_cli<S: AsRef<str>>(
+    args: &[S],
+    cwd: &Path,
+    deep_links: bool,
+) -> Result<Vec<CliCommand>, CliError> {
+    if args.len() > MAX_CLI_ARGS + 1 {
+        return Err(CliError::TooManyArguments);
+    }
+    if args.iter().any(|arg| arg.as_ref().len() > MAX_CLI_ARG_LEN) {
+        return Err(CliError::ArgumentTooLong);
+    }
+
+    let mut commands = Vec::new();
+    let mut args = args.iter().skip(1).map(AsRef::as_ref);
+    while let Some(arg) = args.next() {
+        let command = match arg {
+            START_RECORDING_ARG => CliCommand::StartRecording,
+            STOP_RECORDING_ARG => CliCommand::StopRecording,
+            TOGGLE_RECORDING_ARG => CliCommand::ToggleRecording,
+            _ if is_deep_link(arg) => {
+                if !deep_links {
+                    return Err(CliError::DeepLinksDisabled);
+                }
+                commands.extend(parse_deep_link(arg)?);
+                continue;
+            }
+            _ => {
+                let (flag, inline_path) = match arg.split_once('=') {
+                    Some((flag, path)) => (flag, Some(path)),
+                    None => (arg, None),
+                };
+                match file_flag(flag) {
+                    Some((flag, command)) => {
+                        let path = match inline_path {
+                            Some(path) => Some(path),
+                            None => args.next().filter(|path| !path.starts_with("--")),
+                        };
+                        let path = path
+                            .filter(|path| !path.is_empty())
+                            .ok_or(CliError::MissingPath(flag))?;
+                        command(wav_path(path, cwd)?)
+                    }
+                    // macOS passes a process serial number to apps opened
+                    // from Finder.
+                    None if arg == AUTOSTART_ARG || arg.starts_with("-psn_") => continue,
+                    None => return Err(CliError::UnknownArgument(arg.to_string())),
+                }
+            }
+        };
+        commands.push(command);
+    }
+    Ok(commands)
+}
+
+fn is_deep_link(arg: &str) -> bool {
+    arg.split_once(':')
+        .is_some_and(|(scheme, _)| scheme.eq_ignore_ascii_case(DEEP_LINK_SCHEME))
+}
+
+/// Parses a `silentkeys:` link. Its action is `record`, `toggle`, or `stop`;
+/// `mode` picks `streaming`, `phrase`, or `batch` transcription for the
+/// recording it starts, and `profile` names a saved profile to switch to
+/// first. As with arguments, anything unrecognised rejects the whole link.
+pub fn parse_deep_link(link: &str) -> Result<Vec<CliCommand>, CliError> {
+    if link.len() > MAX_CLI_ARG_LEN {
+        return Err(CliError::ArgumentTooLong);
+    }
+    let unknown = || CliError::UnknownLink(link.to_string());
+    let url = Url::parse(link).map_err(|_| unknown())?;
+    if url.scheme() != DEEP_LINK_SCHEME {
+        return Err(unknown());
+    }
+    // `silentkeys://record` names the action as the host and
+    // `silentkeys:record` as the path.
+    let action = match url.host_str() {
+        Some(host) if matches!(url.path(), "" | "/") => host,
+        Some(_) => return Err(unknown()),
+        None => url.path(),
+    }
+    .to_ascii_lowercase();
+
+    let mut mode = None;
+    let mut profile = None;
+    for (key, value) in url.query_pairs() {
+        match key.as_ref() {
+            "mode" => {
+                let parsed = RecordingMode::parse(&value);
+                mode = Some(parsed.ok_or_else(|| CliError::UnknownMode(value.to_string()))?);
+            }
+            "profile" => profile = Some(value.trim().to_string()).filter(|name| !name.is_empty()),
+            _ => return Err(CliError::UnknownParameter(key.into_owned())),
+        }
+    }
+
+    let recording = match (action.as_str(), mode) {
+        ("record", None) => CliCommand::StartRecording,
+        ("record", Some(mode)) => CliCommand::StartRecordingIn(mode),
+        ("toggle", None) => CliCommand::ToggleRecording,
+        ("toggle", Some(mode)) => CliCommand::ToggleRecordingIn(mode),
+        ("stop", None) => CliCommand::StopRecording,
+        ("stop", Some(_)) => return Err(CliError::UnknownParameter("mode".to_string())),
+        _ => return Err(unknown()),
+    };
+    Ok(profile
+        .map(CliCommand::SwitchProfile)
+        .into_iter()
+        .chain([recording])
+        .collect())
+}
+
+type FileCommand = fn(PathBuf) -> CliCommand;
+
+/// The flags that take a WAV path, given as the next argument or after `=`.
+fn file_flag(flag: &str) -> Option<(&'static str, FileCommand)> {
+    match flag {
+        TRANSCRIBE_FILE_ARG => Some((TRANSCRIBE_FILE_ARG, CliCommand::TranscribeFile)),
+        EXPORT_WORD_TIMINGS_ARG => Some((EXPORT_WORD_TIMINGS_ARG, CliCommand::ExportWordTimings)),
+        _ => None,
+    }
+}
+
+fn wav_path(path: &str, cwd: &Path) -> Result<PathBuf, CliError> {
+    let path = PathBuf::from(path);
+    let is_wav = path
+        .extension()
+        .and_then(|extension| extension.to_str())
+        .is_some_and(|extension| extension.eq_ignore_ascii_case("wav"));
+    if !is_wav {
+        return Err(CliError::NotWav(path));
+    }
+    if path.is_absolute() {
+        return Ok(path);
+    }
+    if !cwd.is_absolute() {
+        return Err(CliError::RelativePath(path));
+    }
+    Ok(cwd.join(path))
+}
+
+/// Where the transcript of `audio` is written: beside it with a `.txt`
+/// extension, numbered from the second attempt on so an existing transcript
+/// is never overwritten.
+pub fn transcript_path(audio: &Path, attempt: u32) -> PathBuf {
+    output_path(audio, "txt", attempt)
+}
+
+/// Like [`transcript_path`] for another output, such as `words.json` for
+/// word timings.
+pub fn output_path(audio: &Path, extension: &str, attempt: u32) -> PathBuf {
+    let stem = audio
+        .file_stem()
+        .map(|stem| stem.to_string_lossy().into_owned())
+        .unwrap_or_default();
+    let name = if attempt <= 1 {
+        format!("{stem}.{extension}")
+    } else {
+        format!("{stem} ({attempt}).{extension}")
+    };
+    audio.with_file_name(name)
+}
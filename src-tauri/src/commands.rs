@@ -1,65 +1,1479 @@
 use tauri::{AppHandle, Emitter, State};
 
+use crate::asr::{available_cores, tuning_candidates, OrtThreads, ThreadTuning};
+use crate::audio_file::decode_wav;
+use crate::audio_processing::ResamplerQuality;
+use crate::autostart::AutostartSettings;
+use crate::capture_file::CaptureFileSettings;
+use crate::companion::{CompanionSettings, CompanionStatus, PairingInfo};
+use crate::continuation::ContinuationSettings;
+use crate::decode_cache::DecodeCacheSettings;
 #[cfg(desktop)]
 use crate::desktop;
+use crate::dictation::PatchProtocol;
+use crate::digest::DigestExport;
 use crate::engine::{EngineState, SpeechEngine};
-use crate::errors::UserFacing;
+use crate::errors::AppError;
+use crate::hardware::HardwareDetection;
+use crate::history::HistoryEntry;
+use crate::inactivity::InactivityTimeout;
+use crate::llm_handoff::LlmHandoffConfig;
+use crate::network_input::NetworkInputSettings;
+use crate::noise_profile::{
+    NoiseProfileError, NoiseSelection, NoiseSuppression, NoiseSuppressionInfo,
+};
+use crate::onboarding::{Onboarding, OnboardingEvent};
+use crate::output_template::OutputTemplates;
+use crate::patch_routing::{PatchRouter, PatchRouting, PatchSource};
+use crate::post_process::{PostProcessing, PostProcessor, StageInfo};
+use crate::power::PowerSaverMode;
+use crate::profiles::{ProfileList, PROFILE_CHANGED_EVENT};
+use crate::quiet_hours::QuietHours;
+use crate::recent_logs::LogEntry;
+use crate::recording::{
+    AudioHealth, CaptureQuality, InputDeviceSettings, LookbackSettings, MicrophoneTest,
+};
+use crate::retranscribe::{RetranscribeConfig, Retranscription};
+use crate::settings::{ModelPrewarm, RecordingIndicator, TypingPace};
+use crate::settings_lock::SettingsLockStatus;
+use crate::shortcut_gesture::ShortcutGestureSettings;
+use crate::speaker_adaptation::SpeakerAdaptationInfo;
+use crate::streaming::StreamingStrategy;
+use crate::text_commands::TextCommands;
+use crate::time_stretch::TimeStretch;
+use crate::transcript::PauseMarkers;
+use crate::trigger::TriggerSettings;
+use crate::typed_metadata::TypedMetadataSettings;
 use crate::updater::AppUpdateInfo;
+use crate::vad::{NoiseCalibration, VadSettings};
+use crate::webhook::WebhookConfig;
 
-fn user_error(err: impl UserFacing) -> String {
-    err.user_message().to_string()
+/// Speech decoded to time each thread split when tuning.
+const THREAD_TUNING_SAMPLE: &[u8] = include_bytes!("../tests/samples/jfk.wav");
+
+fn command_error(context: &str, err: impl Into<AppError>) -> AppError {
+    let err = err.into();
+    log::error!("{context}: {err}");
+    err
+}
+
+async fn run_blocking<T, F>(task: &'static str, work: F) -> Result<T, AppError>
+where
+    T: Send + 'static,
+    F: FnOnce() -> Result<T, AppError> + Send + 'static,
+{
+    tauri::async_runtime::spawn_blocking(work)
+        .await
+        .map_err(|error| {
+            log::error!("{task} worker failed: {error}");
+            AppError::Worker(task)
+        })?
+}
+
+#[tauri::command]
+pub fn engine_state(state: State<'_, SpeechEngine>) -> EngineState {
+    state.state()
+}
+
+#[tauri::command]
+pub fn is_recording(state: State<'_, SpeechEngine>) -> bool {
+    state.is_dictating()
+}
+
+#[tauri::command]
+pub fn audio_health(state: State<'_, SpeechEngine>) -> Option<AudioHealth> {
+    state.recorder().last_health()
+}
+
+/// Clipping, DC offset, and bandwidth checks on the last recording.
+#[tauri::command]
+pub fn capture_quality(state: State<'_, SpeechEngine>) -> Option<CaptureQuality> {
+    state.recorder().last_quality()
+}
+
+/// Speech rate and talk time of recent dictations, oldest first.
+#[tauri::command]
+pub fn list_speech_stats(app: AppHandle) -> Vec<crate::speech_stats::SpeechStats> {
+    crate::speech_stats::load_sessions(&app)
+}
+
+/// Starts `vad_events` delivery for the caller; pair with
+/// `unsubscribe_vad_events`.
+#[tauri::command]
+pub fn subscribe_vad_events() {
+    crate::recording::vad_monitor().subscribe();
+}
+
+#[tauri::command]
+pub fn unsubscribe_vad_events() {
+    crate::recording::vad_monitor().unsubscribe();
+}
+
+#[tauri::command]
+pub fn get_model_path(app: AppHandle) -> String {
+    crate::asr::default_model_root(&app)
+        .to_string_lossy()
+        .to_string()
+}
+
+#[tauri::command]
+pub fn set_model_path(
+    app: AppHandle,
+    state: State<'_, SpeechEngine>,
+    path: String,
+) -> Result<(), AppError> {
+    let p = std::path::PathBuf::from(&path);
+    if !p.exists() || !p.is_dir() {
+        return Err(AppError::InvalidModelPath);
+    }
+
+    if crate::settings::get_custom_model_path(&app) == Some(p) {
+        return Ok(());
+    }
+    crate::settings::set_model_path(&app, path)
+        .map_err(|error| command_error("Could not set model path", error))?;
+    state.reload_model();
+    Ok(())
+}
+
+#[tauri::command]
+pub fn get_use_streaming(app: AppHandle) -> bool {
+    crate::settings::get_settings(&app).streaming_enabled
+}
+
+#[tauri::command]
+pub fn set_use_streaming(app: AppHandle, enabled: bool) -> Result<(), AppError> {
+    crate::settings::set_streaming_enabled(&app, enabled)
+        .map_err(|error| command_error("Could not set streaming preference", error))
+}
+
+#[tauri::command]
+pub fn get_streaming_strategy(app: AppHandle) -> StreamingStrategy {
+    crate::settings::get_settings(&app).streaming_strategy
+}
+
+#[tauri::command]
+pub fn set_streaming_strategy(app: AppHandle, strategy: StreamingStrategy) -> Result<(), AppError> {
+    crate::settings::set_streaming_strategy(&app, strategy)
+        .map_err(|error| command_error("Could not set streaming strategy", error))
+}
+
+#[tauri::command]
+pub fn get_resampler_quality(app: AppHandle) -> ResamplerQuality {
+    crate::settings::get_settings(&app).resampler_quality
+}
+
+#[tauri::command]
+pub fn set_resampler_quality(app: AppHandle, quality: ResamplerQuality) -> Result<(), AppError> {
+    crate::settings::set_resampler_quality(&app, quality)
+        .map_err(|error| command_error("Could not set resampler quality", error))?;
+    #[cfg(desktop)]
+    desktop::sync_lookback(&app);
+    Ok(())
+}
+
+#[tauri::command]
+pub fn get_model_prewarm(app: AppHandle) -> ModelPrewarm {
+    crate::settings::get_settings(&app).model_prewarm
+}
+
+#[tauri::command]
+pub fn set_model_prewarm(app: AppHandle, prewarm: ModelPrewarm) -> Result<(), AppError> {
+    crate::settings::set_model_prewarm(&app, prewarm)
+        .map_err(|error| command_error("Could not set model prewarm preference", error))
+}
+
+#[tauri::command]
+pub fn get_power_saver(app: AppHandle) -> PowerSaverMode {
+    crate::settings::get_settings(&app).power_saver
+}
+
+#[tauri::command]
+pub fn set_power_saver(app: AppHandle, mode: PowerSaverMode) -> Result<(), AppError> {
+    crate::settings::set_power_saver(&app, mode)
+        .map_err(|error| command_error("Could not set battery saver mode", error))
+}
+
+#[tauri::command]
+pub fn get_recording_indicator(app: AppHandle) -> RecordingIndicator {
+    crate::settings::get_settings(&app).recording_indicator
+}
+
+#[tauri::command]
+pub fn set_recording_indicator(
+    app: AppHandle,
+    indicator: RecordingIndicator,
+) -> Result<(), AppError> {
+    crate::settings::set_recording_indicator(&app, indicator)
+        .map_err(|error| command_error("Could not set recording indicator", error))
+}
+
+#[tauri::command]
+pub fn get_hardware_detection(app: AppHandle) -> Option<HardwareDetection> {
+    crate::settings::get_settings(&app).hardware_detection
+}
+
+/// Probes the hardware again and applies its recommended defaults.
+#[tauri::command]
+pub async fn detect_hardware(app: AppHandle) -> Result<HardwareDetection, AppError> {
+    run_blocking("Hardware detection", move || {
+        crate::settings::record_hardware_detection(&app, true)
+            .map_err(|error| command_error("Could not save hardware detection", error))
+    })
+    .await
+}
+
+#[tauri::command]
+pub fn get_buffer_until_model_ready(app: AppHandle) -> bool {
+    crate::settings::get_settings(&app).buffer_until_model_ready
+}
+
+#[tauri::command]
+pub fn set_buffer_until_model_ready(app: AppHandle, enabled: bool) -> Result<(), AppError> {
+    crate::settings::set_buffer_until_model_ready(&app, enabled)
+        .map_err(|error| command_error("Could not set model-loading buffer preference", error))
+}
+
+#[tauri::command]
+pub fn get_dictation_enabled(app: AppHandle) -> bool {
+    crate::settings::get_settings(&app).dictation_enabled
+}
+
+#[tauri::command]
+pub fn set_dictation_enabled(app: AppHandle, enabled: bool) -> Result<(), AppError> {
+    #[cfg(desktop)]
+    let result = desktop::set_dictation_enabled(&app, enabled);
+    #[cfg(not(desktop))]
+    let result = crate::settings::set_dictation_enabled(&app, enabled);
+    result.map_err(|error| command_error("Could not change dictation state", error))
+}
+
+#[tauri::command]
+pub fn get_quiet_hours(app: AppHandle) -> QuietHours {
+    crate::settings::get_settings(&app).quiet_hours
+}
+
+#[tauri::command]
+pub fn set_quiet_hours(app: AppHandle, quiet_hours: QuietHours) -> Result<(), AppError> {
+    crate::settings::set_quiet_hours(&app, quiet_hours)
+        .map_err(|error| command_error("Could not set quiet hours", error))?;
+    #[cfg(desktop)]
+    desktop::refresh_quiet_hours(&app);
+    Ok(())
+}
+
+#[tauri::command]
+pub fn get_webhook(app: AppHandle) -> Result<WebhookConfig, AppError> {
+    let settings = crate::settings::get_settings(&app).webhook;
+    #[cfg(desktop)]
+    let secret = desktop::webhook_secret()
+        .map_err(|error| command_error("Could not read webhook", error))?;
+    #[cfg(not(desktop))]
+    let secret = String::new();
+    Ok(WebhookConfig { settings, secret })
+}
+
+/// The secret is stored before the rest is saved, so a secret the keychain
+/// refuses fails the whole change.
+#[tauri::command]
+pub fn set_webhook(app: AppHandle, webhook: WebhookConfig) -> Result<(), AppError> {
+    let WebhookConfig { settings, secret } = webhook;
+    if settings.enabled {
+        crate::webhook::validate_url(&settings.url)
+            .map_err(|error| command_error("Could not set webhook", error))?;
+    }
+    crate::settings::ensure_settings_unlocked(&app)
+        .map_err(|error| command_error("Could not set webhook", error))?;
+    #[cfg(desktop)]
+    desktop::store_webhook_secret(&secret)
+        .map_err(|error| command_error("Could not set webhook", error))?;
+    #[cfg(not(desktop))]
+    if !secret.is_empty() {
+        let error = crate::webhook::WebhookError::Keychain("no keychain".to_string());
+        return Err(command_error("Could not set webhook", error));
+    }
+    crate::settings::set_webhook(&app, settings)
+        .map_err(|error| command_error("Could not set webhook", error))
+}
+
+#[tauri::command]
+pub fn get_llm_handoff(app: AppHandle) -> Result<LlmHandoffConfig, AppError> {
+    let settings = crate::settings::get_settings(&app).llm_handoff;
+    #[cfg(desktop)]
+    let api_key = desktop::llm_api_key()
+        .map_err(|error| command_error("Could not read LLM handoff", error))?;
+    #[cfg(not(desktop))]
+    let api_key = String::new();
+    Ok(LlmHandoffConfig { settings, api_key })
+}
+
+/// Like [`set_webhook`], the API key is stored before the rest is saved.
+#[tauri::command]
+pub fn set_llm_handoff(app: AppHandle, llm_handoff: LlmHandoffConfig) -> Result<(), AppError> {
+    let LlmHandoffConfig { settings, api_key } = llm_handoff;
+    if settings.enabled {
+        crate::llm_handoff::chat_completions_url(&settings.endpoint)
+            .map_err(|error| command_error("Could not set LLM handoff", error))?;
+    }
+    crate::settings::ensure_settings_unlocked(&app)
+        .map_err(|error| command_error("Could not set LLM handoff", error))?;
+    #[cfg(desktop)]
+    desktop::sync_handoff_shortcut(&app, settings.enabled)
+        .map_err(|error| command_error("Could not set LLM handoff", error))?;
+    let saved = save_llm_handoff(&app, settings, &api_key);
+    #[cfg(desktop)]
+    if saved.is_err() {
+        desktop::sync_mode_shortcuts(&app);
+    }
+    saved
+}
+
+fn save_llm_handoff(
+    app: &AppHandle,
+    settings: crate::llm_handoff::LlmHandoffSettings,
+    api_key: &str,
+) -> Result<(), AppError> {
+    #[cfg(desktop)]
+    desktop::store_llm_api_key(api_key)
+        .map_err(|error| command_error("Could not set LLM handoff", error))?;
+    #[cfg(not(desktop))]
+    if !api_key.is_empty() {
+        let error = crate::llm_handoff::LlmHandoffError::Keychain("no keychain".to_string());
+        return Err(command_error("Could not set LLM handoff", error));
+    }
+    crate::settings::set_llm_handoff(app, settings)
+        .map_err(|error| command_error("Could not set LLM handoff", error))
+}
+
+#[tauri::command]
+pub fn get_dictation_toggle_shortcut(app: AppHandle) -> bool {
+    crate::settings::get_settings(&app).dictation_toggle_shortcut
+}
+
+#[tauri::command]
+pub fn set_dictation_toggle_shortcut(app: AppHandle, enabled: bool) -> Result<(), AppError> {
+    #[cfg(desktop)]
+    desktop::sync_dictation_toggle_shortcut(&app, enabled)
+        .map_err(|error| command_error("Could not set dictation toggle shortcut", error))?;
+    let saved = crate::settings::set_dictation_toggle_shortcut(&app, enabled)
+        .map_err(|error| command_error("Could not set dictation toggle shortcut", error));
+    #[cfg(desktop)]
+    if saved.is_err() {
+        desktop::sync_mode_shortcuts(&app);
+    }
+    saved
+}
+
+#[tauri::command]
+pub fn get_numbers_mode(app: AppHandle) -> bool {
+    crate::settings::get_settings(&app).numbers_mode
+}
+
+#[tauri::command]
+pub fn set_numbers_mode(app: AppHandle, enabled: bool) -> Result<(), AppError> {
+    #[cfg(desktop)]
+    desktop::sync_numbers_mode_shortcut(&app, enabled)
+        .map_err(|error| command_error("Could not set numbers mode", error))?;
+    let saved = crate::settings::set_numbers_mode(&app, enabled)
+        .map_err(|error| command_error("Could not set numbers mode", error));
+    #[cfg(desktop)]
+    if saved.is_err() {
+        desktop::sync_mode_shortcuts(&app);
+    }
+    saved
+}
+
+#[tauri::command]
+pub fn get_output_templates(app: AppHandle) -> OutputTemplates {
+    crate::settings::get_settings(&app).output_templates
+}
+
+#[tauri::command]
+pub fn set_output_templates(
+    app: AppHandle,
+    output_templates: OutputTemplates,
+) -> Result<(), AppError> {
+    output_templates
+        .validate()
+        .map_err(|error| command_error("Could not set output templates", error))?;
+    crate::settings::set_output_templates(&app, output_templates)
+        .map_err(|error| command_error("Could not set output templates", error))
+}
+
+#[tauri::command]
+pub fn get_capture_file(app: AppHandle) -> CaptureFileSettings {
+    crate::settings::get_settings(&app).capture_file
+}
+
+#[tauri::command]
+pub fn set_capture_file(app: AppHandle, capture_file: CaptureFileSettings) -> Result<(), AppError> {
+    if capture_file.enabled {
+        crate::capture_file::check_writable(&capture_file.path)
+            .map_err(|error| command_error("Could not set capture file", error))?;
+    }
+    crate::settings::set_capture_file(&app, capture_file)
+        .map_err(|error| command_error("Could not set capture file", error))
+}
+
+/// Lists the capture file's entries, oldest first. With the decode cache on,
+/// entries with identical recordings are marked as duplicates.
+#[tauri::command]
+pub async fn list_history_entries(app: AppHandle) -> Result<Vec<HistoryEntry>, AppError> {
+    run_blocking("History", move || {
+        let settings = crate::settings::get_settings(&app);
+        let entries = if settings.decode_cache.enabled {
+            crate::history::read_history_deduplicated(&settings.capture_file.path)
+        } else {
+            crate::history::read_history(&settings.capture_file.path)
+        };
+        entries.map_err(|error| command_error("Could not read history", error))
+    })
+    .await
+}
+
+/// Retitles capture file entry `index`; an empty title leaves only the
+/// timestamp.
+#[tauri::command]
+pub async fn rename_history_entry(
+    app: AppHandle,
+    index: usize,
+    title: String,
+) -> Result<(), AppError> {
+    run_blocking("History", move || {
+        let capture_file = crate::settings::get_settings(&app).capture_file;
+        crate::history::rename_history_entry(&capture_file.path, index, &title)
+            .map_err(|error| command_error("Could not rename history entry", error))
+    })
+    .await
+}
+
+/// Transcribes one utterance of a split capture file entry again from its
+/// audio, with the current model and settings, and saves the new text.
+#[tauri::command]
+pub async fn retranscribe_history_utterance(
+    app: AppHandle,
+    state: State<'_, SpeechEngine>,
+    index: usize,
+    utterance: usize,
+) -> Result<String, AppError> {
+    let engine = state.inner().clone();
+    run_blocking("History", move || {
+        let capture_file = crate::settings::get_settings(&app).capture_file;
+        let clip = crate::history::utterance_clip(&capture_file.path, index, utterance)
+            .map_err(|error| command_error("Could not find utterance audio", error))?;
+        let text = engine
+            .transcribe_file(&clip)
+            .map_err(|error| command_error("Could not transcribe utterance", error))?;
+        crate::history::set_history_utterance(&capture_file.path, index, utterance, &text)
+            .map_err(|error| command_error("Could not update history entry", error))?;
+        Ok(text)
+    })
+    .await
+}
+
+/// Transcribes a split capture file entry again from its recording with
+/// `config`, without saving, and compares it with the entry's text.
+#[tauri::command]
+pub async fn retranscribe_history_entry(
+    app: AppHandle,
+    state: State<'_, SpeechEngine>,
+    index: usize,
+    config: RetranscribeConfig,
+) -> Result<Retranscription, AppError> {
+    let engine = state.inner().clone();
+    run_blocking("History", move || {
+        let capture_file = crate::settings::get_settings(&app).capture_file;
+        let (original, clips): (Vec<String>, Vec<std::path::PathBuf>) =
+            crate::history::entry_clips(&capture_file.path, index)
+                .map_err(|error| command_error("Could not find the entry's recording", error))?
+                .into_iter()
+                .unzip();
+        let retranscribed = engine
+            .retranscribe_clips(&clips, &config)
+            .map_err(|error| command_error("Could not transcribe the entry again", error))?;
+        Ok(Retranscription::new(original, retranscribed))
+    })
+    .await
+}
+
+/// Replaces the text of a split capture file entry's utterances, as when
+/// keeping a re-transcription.
+#[tauri::command]
+pub async fn set_history_utterances(
+    app: AppHandle,
+    index: usize,
+    texts: Vec<String>,
+) -> Result<(), AppError> {
+    run_blocking("History", move || {
+        let capture_file = crate::settings::get_settings(&app).capture_file;
+        crate::history::set_history_utterances(&capture_file.path, index, &texts)
+            .map_err(|error| command_error("Could not update history entry", error))
+    })
+    .await
+}
+
+#[tauri::command]
+pub async fn pick_capture_file(app: AppHandle) -> Result<Option<String>, AppError> {
+    use tauri_plugin_dialog::DialogExt;
+
+    let result = run_blocking("Dialog", move || {
+        Ok(app
+            .dialog()
+            .file()
+            .add_filter("Markdown", &["md"])
+            .blocking_save_file())
+    })
+    .await?;
+
+    Ok(result.map(|p| p.to_string()))
+}
+
+#[tauri::command]
+pub async fn pick_digest_folder(app: AppHandle) -> Result<Option<String>, AppError> {
+    use tauri_plugin_dialog::DialogExt;
+
+    let result = run_blocking("Dialog", move || {
+        Ok(app.dialog().file().blocking_pick_folder())
+    })
+    .await?;
+
+    Ok(result.map(|p| p.to_string()))
+}
+
+/// Compiles capture file entries dated `from` through `to` (YYYY-MM-DD) into a
+/// Markdown digest, also written into `notes_dir` when one is given.
+#[tauri::command]
+pub async fn export_digest(
+    app: AppHandle,
+    from: String,
+    to: String,
+    notes_dir: Option<String>,
+) -> Result<DigestExport, AppError> {
+    run_blocking("Digest export", move || {
+        let capture_file = crate::settings::get_settings(&app).capture_file;
+        crate::digest::export_digest(&capture_file.path, &from, &to, notes_dir.as_deref())
+            .map_err(|error| command_error("Could not export digest", error))
+    })
+    .await
+}
+
+#[tauri::command]
+pub async fn pick_config_export_file(app: AppHandle) -> Result<Option<String>, AppError> {
+    use tauri_plugin_dialog::DialogExt;
+
+    let result = run_blocking("Dialog", move || {
+        Ok(app
+            .dialog()
+            .file()
+            .add_filter("SilentKeys configuration", &["json"])
+            .set_file_name("silentkeys-config.json")
+            .blocking_save_file())
+    })
+    .await?;
+
+    Ok(result.map(|p| p.to_string()))
+}
+
+#[tauri::command]
+pub async fn pick_config_import_file(app: AppHandle) -> Result<Option<String>, AppError> {
+    use tauri_plugin_dialog::DialogExt;
+
+    let result = run_blocking("Dialog", move || {
+        Ok(app
+            .dialog()
+            .file()
+            .add_filter("SilentKeys configuration", &["json"])
+            .blocking_pick_file())
+    })
+    .await?;
+
+    Ok(result.map(|p| p.to_string()))
+}
+
+#[tauri::command]
+pub async fn export_config(app: AppHandle, path: String) -> Result<(), AppError> {
+    run_blocking("Configuration export", move || {
+        #[cfg(desktop)]
+        let record_shortcut = desktop::get_record_shortcut(app.clone());
+        #[cfg(not(desktop))]
+        let record_shortcut = None;
+        let bundle = crate::config_bundle::export_bundle(
+            &crate::settings::get_settings(&app),
+            record_shortcut,
+            &chrono::Local::now().to_rfc3339(),
+        );
+        crate::config_bundle::write_bundle(std::path::Path::new(&path), &bundle)
+            .map_err(|error| command_error("Could not export configuration", error))
+    })
+    .await
+}
+
+#[tauri::command]
+pub async fn import_config(
+    app: AppHandle,
+    state: State<'_, SpeechEngine>,
+    path: String,
+) -> Result<(), AppError> {
+    let engine = state.inner().clone();
+    run_blocking("Configuration import", move || {
+        let imported = crate::config_bundle::read_bundle(
+            std::path::Path::new(&path),
+            &crate::settings::get_settings(&app),
+        )
+        .map_err(|error| command_error("Could not import configuration", error))?;
+        crate::settings::import_settings(&app, &engine, &imported)
+            .map_err(|error| command_error("Could not import configuration", error))?;
+        #[cfg(desktop)]
+        {
+            desktop::sync_dictation_enabled(&app);
+            desktop::refresh_quiet_hours(&app);
+            desktop::sync_triggers(&app);
+            if let Err(error) = desktop::sync_network_input(&app) {
+                log::warn!("Could not apply network audio input after import: {error}");
+            }
+            if let Err(error) = desktop::sync_typed_metadata(&app) {
+                log::warn!("Could not apply editor metadata after import: {error}");
+            }
+            desktop::sync_profiles_menu(&app);
+        }
+        Ok(())
+    })
+    .await
+}
+
+/// Recent log records for the in-app viewer, newest last.
+#[tauri::command]
+pub fn get_recent_logs(level: String, limit: usize) -> Result<Vec<LogEntry>, AppError> {
+    let level = crate::recent_logs::parse_level(&level)
+        .map_err(|error| command_error("Could not read recent logs", error))?;
+    Ok(crate::recent_logs::recent_logs().recent(level, limit))
+}
+
+#[tauri::command]
+pub fn list_input_devices() -> Result<Vec<String>, AppError> {
+    crate::recording::input_device_names()
+        .map_err(|error| command_error("Could not list input devices", error))
+}
+
+#[tauri::command]
+pub fn get_input_device(app: AppHandle) -> InputDeviceSettings {
+    crate::settings::get_settings(&app).input_device
+}
+
+#[tauri::command]
+pub fn set_input_device(app: AppHandle, input_device: InputDeviceSettings) -> Result<(), AppError> {
+    crate::settings::set_input_device(&app, input_device)
+        .map_err(|error| command_error("Could not set input device", error))?;
+    #[cfg(desktop)]
+    desktop::sync_lookback(&app);
+    Ok(())
+}
+
+#[tauri::command]
+pub fn get_vad_settings(app: AppHandle) -> VadSettings {
+    crate::settings::get_settings(&app).vad
+}
+
+#[tauri::command]
+pub fn set_vad_settings(app: AppHandle, vad: VadSettings) -> Result<(), AppError> {
+    crate::settings::set_vad_settings(&app, vad)
+        .map_err(|error| command_error("Could not set voice detection settings", error))?;
+    #[cfg(desktop)]
+    desktop::sync_lookback(&app);
+    Ok(())
+}
+
+#[tauri::command]
+pub fn get_noise_suppression(app: AppHandle) -> NoiseSuppressionInfo {
+    crate::settings::get_settings(&app).noise_suppression.info()
+}
+
+#[tauri::command]
+pub fn set_noise_selection(
+    app: AppHandle,
+    selection: NoiseSelection,
+) -> Result<NoiseSuppressionInfo, AppError> {
+    let mut noise_suppression = crate::settings::get_settings(&app).noise_suppression;
+    if let NoiseSelection::Profile(name) = &selection {
+        if noise_suppression.find(name).is_none() {
+            return Err(command_error(
+                "Could not select noise profile",
+                NoiseProfileError::NotFound(name.clone()),
+            ));
+        }
+    }
+    noise_suppression.select(selection);
+    save_noise_suppression(&app, noise_suppression)
+}
+
+/// Records three seconds of the room, which should be quiet apart from its
+/// usual noise, as a named noise profile.
+#[tauri::command]
+pub async fn capture_noise_profile(
+    app: AppHandle,
+    state: State<'_, SpeechEngine>,
+    name: String,
+) -> Result<NoiseSuppressionInfo, AppError> {
+    crate::settings::ensure_settings_unlocked(&app)
+        .map_err(|error| command_error("Could not capture noise profile", error))?;
+    let engine = state.inner().clone();
+    run_blocking("Noise profile", move || {
+        let noise_suppression = engine
+            .capture_noise_profile(&name)
+            .map_err(|error| command_error("Could not capture noise profile", error))?;
+        save_noise_suppression(&app, noise_suppression)
+    })
+    .await
+}
+
+#[tauri::command]
+pub fn delete_noise_profile(
+    app: AppHandle,
+    name: String,
+) -> Result<NoiseSuppressionInfo, AppError> {
+    let mut noise_suppression = crate::settings::get_settings(&app).noise_suppression;
+    noise_suppression
+        .delete_profile(&name)
+        .map_err(|error| command_error("Could not delete noise profile", error))?;
+    save_noise_suppression(&app, noise_suppression)
+}
+
+fn save_noise_suppression(
+    app: &AppHandle,
+    noise_suppression: NoiseSuppression,
+) -> Result<NoiseSuppressionInfo, AppError> {
+    let info = noise_suppression.info();
+    crate::settings::set_noise_suppression(app, noise_suppression)
+        .map_err(|error| command_error("Could not save noise profiles", error))?;
+    Ok(info)
+}
+
+#[tauri::command]
+pub fn get_speaker_adaptation(app: AppHandle) -> SpeakerAdaptationInfo {
+    crate::settings::get_settings(&app)
+        .speaker_adaptation
+        .info()
+}
+
+#[tauri::command]
+pub fn set_speaker_adaptation(
+    app: AppHandle,
+    enabled: bool,
+) -> Result<SpeakerAdaptationInfo, AppError> {
+    crate::settings::set_speaker_adaptation(&app, enabled)
+        .map_err(|error| command_error("Could not set speaker adaptation", error))?;
+    Ok(get_speaker_adaptation(app))
+}
+
+/// Forgets the voice learned for the active profile; adaptation starts
+/// learning again from the next dictation.
+#[tauri::command]
+pub fn reset_voice_profile(app: AppHandle) -> Result<SpeakerAdaptationInfo, AppError> {
+    crate::settings::reset_voice_profile(&app)
+        .map_err(|error| command_error("Could not reset the learned voice", error))?;
+    Ok(get_speaker_adaptation(app))
+}
+
+#[tauri::command]
+pub async fn calibrate_microphone(
+    state: State<'_, SpeechEngine>,
+) -> Result<NoiseCalibration, AppError> {
+    let engine = state.inner().clone();
+    run_blocking("Microphone calibration", move || {
+        engine
+            .calibrate_microphone()
+            .map_err(|error| command_error("Could not calibrate microphone", error))
+    })
+    .await
+}
+
+/// Records three seconds through the dictation input chain and reports its
+/// levels; [`play_microphone_test`] plays the take back.
+#[tauri::command]
+pub async fn test_microphone(state: State<'_, SpeechEngine>) -> Result<MicrophoneTest, AppError> {
+    let engine = state.inner().clone();
+    run_blocking("Microphone test", move || {
+        engine
+            .test_microphone()
+            .map_err(|error| command_error("Could not test microphone", error))
+    })
+    .await
+}
+
+#[tauri::command]
+pub async fn play_microphone_test(state: State<'_, SpeechEngine>) -> Result<(), AppError> {
+    let engine = state.inner().clone();
+    run_blocking("Microphone test playback", move || {
+        engine
+            .play_microphone_test()
+            .map_err(|error| command_error("Could not play microphone test", error))
+    })
+    .await
+}
+
+#[cfg(desktop)]
+#[tauri::command]
+pub fn get_onboarding(app: AppHandle) -> Onboarding {
+    desktop::current_onboarding(&app)
+}
+
+/// Opens the microphone for the setup's permission step; the outcome arrives
+/// as `onboarding_state`.
+#[cfg(desktop)]
+#[tauri::command]
+pub async fn check_onboarding_microphone(app: AppHandle) -> Result<(), AppError> {
+    run_blocking("Onboarding", move || {
+        desktop::check_onboarding_microphone(&app);
+        Ok(())
+    })
+    .await
+}
+
+/// Runs the setup's microphone test, returning the take's levels when one
+/// was recorded.
+#[cfg(desktop)]
+#[tauri::command]
+pub async fn test_onboarding_microphone(
+    app: AppHandle,
+) -> Result<Option<MicrophoneTest>, AppError> {
+    run_blocking("Onboarding", move || {
+        Ok(desktop::test_onboarding_microphone(&app))
+    })
+    .await
+}
+
+#[cfg(desktop)]
+#[tauri::command]
+pub fn confirm_onboarding_shortcut(app: AppHandle) {
+    desktop::observe_onboarding(&app, OnboardingEvent::ShortcutConfirmed);
+}
+
+#[cfg(desktop)]
+#[tauri::command]
+pub fn skip_onboarding_step(app: AppHandle) {
+    desktop::observe_onboarding(&app, OnboardingEvent::SkipStep);
+}
+
+#[cfg(desktop)]
+#[tauri::command]
+pub fn finish_onboarding(app: AppHandle) {
+    desktop::observe_onboarding(&app, OnboardingEvent::Finish);
+}
+
+#[cfg(desktop)]
+#[tauri::command]
+pub fn restart_onboarding(app: AppHandle) -> Result<Onboarding, AppError> {
+    desktop::restart_onboarding(&app)
+        .map_err(|error| command_error("Could not restart setup", error))
+}
+
+#[tauri::command]
+pub fn get_shortcut_gesture(app: AppHandle) -> ShortcutGestureSettings {
+    crate::settings::get_settings(&app).shortcut_gesture
+}
+
+#[tauri::command]
+pub fn set_shortcut_gesture(
+    app: AppHandle,
+    gesture: ShortcutGestureSettings,
+) -> Result<(), AppError> {
+    #[cfg(desktop)]
+    if gesture.mode == crate::shortcut_gesture::GestureMode::Chord {
+        desktop::parse_shortcut_str(gesture.follow_up.trim())?;
+    }
+    crate::settings::set_shortcut_gesture(&app, gesture)
+        .map_err(|error| command_error("Could not set shortcut gesture", error))?;
+    #[cfg(desktop)]
+    desktop::reset_shortcut_gesture(&app);
+    Ok(())
+}
+
+#[tauri::command]
+pub fn get_triggers(app: AppHandle) -> TriggerSettings {
+    crate::settings::get_settings(&app).triggers
+}
+
+#[tauri::command]
+pub fn set_triggers(app: AppHandle, triggers: TriggerSettings) -> Result<(), AppError> {
+    crate::settings::set_triggers(&app, triggers)
+        .map_err(|error| command_error("Could not set record triggers", error))?;
+    #[cfg(desktop)]
+    desktop::sync_triggers(&app);
+    Ok(())
+}
+
+#[tauri::command]
+pub fn get_suppress_record_key(app: AppHandle) -> bool {
+    crate::settings::get_settings(&app).suppress_record_key
+}
+
+#[tauri::command]
+pub fn set_suppress_record_key(app: AppHandle, enabled: bool) -> Result<(), AppError> {
+    crate::settings::set_suppress_record_key(&app, enabled)
+        .map_err(|error| command_error("Could not set record key suppression", error))?;
+    #[cfg(desktop)]
+    desktop::sync_record_key_suppression(&app);
+    Ok(())
+}
+
+#[cfg(desktop)]
+#[tauri::command]
+pub async fn detect_mouse_button(app: AppHandle) -> Result<u8, AppError> {
+    run_blocking("Mouse button detection", move || {
+        desktop::detect_mouse_button(&app)
+            .map_err(|error| command_error("Could not detect mouse button", error))
+    })
+    .await
+}
+
+/// Types `text` into the focused app as a dictation would be typed, for
+/// tools that want SilentKeys' keyboard handling without a recording.
+#[cfg(desktop)]
+#[tauri::command]
+pub async fn type_text(
+    app: AppHandle,
+    text: String,
+    options: Option<desktop::TypeTextOptions>,
+) -> Result<(), AppError> {
+    run_blocking("Typing", move || {
+        let settings = crate::settings::get_settings(&app);
+        desktop::inject_text(&settings, text, &options.unwrap_or_default())
+            .map_err(|error| command_error("Could not type text", error))
+    })
+    .await
+}
+
+#[cfg(desktop)]
+#[tauri::command]
+pub fn open_document(app: AppHandle) -> Result<(), AppError> {
+    desktop::open_document(&app).map_err(|error| command_error("Could not open document", error))
+}
+
+#[cfg(desktop)]
+#[tauri::command]
+pub fn get_document() -> Option<String> {
+    desktop::document_text()
+}
+
+#[cfg(desktop)]
+#[tauri::command]
+pub fn set_document_text(text: String) -> Result<(), AppError> {
+    desktop::set_document_text(text)
+        .map_err(|error| command_error("Could not update document", error))
+}
+
+#[cfg(desktop)]
+#[tauri::command]
+pub async fn pick_document_file(app: AppHandle) -> Result<Option<String>, AppError> {
+    use tauri_plugin_dialog::DialogExt;
+
+    let result = run_blocking("Dialog", move || {
+        Ok(app
+            .dialog()
+            .file()
+            .add_filter("Markdown", &["md"])
+            .set_file_name("document.md")
+            .blocking_save_file())
+    })
+    .await?;
+
+    Ok(result.map(|p| p.to_string()))
+}
+
+/// Types, saves, or discards the open document and closes it.
+#[cfg(desktop)]
+#[tauri::command]
+pub async fn finish_document(
+    app: AppHandle,
+    finish: desktop::DocumentFinish,
+) -> Result<(), AppError> {
+    run_blocking("Document", move || {
+        desktop::finish_document(&app, finish)
+            .map_err(|error| command_error("Could not finish document", error))
+    })
+    .await
+}
+
+#[cfg(desktop)]
+#[tauri::command]
+pub fn list_hid_devices() -> Result<Vec<desktop::HidDeviceInfo>, AppError> {
+    desktop::list_hid_devices().map_err(|error| command_error("Could not list USB devices", error))
+}
+
+#[tauri::command]
+pub fn get_patch_routing(app: AppHandle) -> PatchRouting {
+    crate::settings::get_settings(&app).patch_routing
+}
+
+#[tauri::command]
+pub fn set_patch_routing(app: AppHandle, routing: PatchRouting) -> Result<(), AppError> {
+    crate::settings::set_patch_routing(&app, routing)
+        .map_err(|error| command_error("Could not set transcript routing", error))
+}
+
+#[tauri::command]
+pub fn get_live_waveform(app: AppHandle) -> bool {
+    crate::settings::get_settings(&app).live_waveform
+}
+
+#[tauri::command]
+pub fn set_live_waveform(app: AppHandle, enabled: bool) -> Result<(), AppError> {
+    crate::settings::set_live_waveform(&app, enabled)
+        .map_err(|error| command_error("Could not set live waveform", error))
+}
+
+#[tauri::command]
+pub fn get_caret_spacing(app: AppHandle) -> bool {
+    crate::settings::get_settings(&app).caret_spacing
+}
+
+#[tauri::command]
+pub fn set_caret_spacing(app: AppHandle, enabled: bool) -> Result<(), AppError> {
+    crate::settings::set_caret_spacing(&app, enabled)
+        .map_err(|error| command_error("Could not set caret-aware spacing", error))
+}
+
+/// Called by the window before it listens for `transcription_update`, to
+/// say which patch forms it can apply.
+#[tauri::command]
+pub fn negotiate_patch_protocol(windowed_drafts: bool) -> PatchProtocol {
+    crate::dictation::negotiate_patch_protocol(windowed_drafts)
+}
+
+#[tauri::command]
+pub fn get_inactivity(app: AppHandle) -> InactivityTimeout {
+    crate::settings::get_settings(&app).inactivity
 }
 
-fn command_error(context: &str, err: impl UserFacing + std::fmt::Display) -> String {
-    log::error!("{context}: {err}");
-    user_error(err)
+#[tauri::command]
+pub fn set_inactivity(app: AppHandle, inactivity: InactivityTimeout) -> Result<(), AppError> {
+    crate::settings::set_inactivity(&app, inactivity)
+        .map_err(|error| command_error("Could not set inactivity timeout", error))
 }
 
-async fn run_blocking<T, F>(task: &'static str, work: F) -> Result<T, String>
-where
-    T: Send + 'static,
-    F: FnOnce() -> Result<T, String> + Send + 'static,
-{
-    tauri::async_runtime::spawn_blocking(work)
-        .await
-        .map_err(|error| {
-            log::error!("{task} worker failed: {error}");
-            format!("{task} could not complete. Please try again.")
-        })?
+/// Answers an inactivity warning; false when no recording is being watched.
+#[tauri::command]
+pub fn keep_recording() -> bool {
+    #[cfg(desktop)]
+    {
+        desktop::keep_recording()
+    }
+    #[cfg(not(desktop))]
+    {
+        false
+    }
 }
 
 #[tauri::command]
-pub fn engine_state(state: State<'_, SpeechEngine>) -> EngineState {
-    state.state()
+pub fn get_continuation(app: AppHandle) -> ContinuationSettings {
+    crate::settings::get_settings(&app).continuation
 }
 
 #[tauri::command]
-pub fn get_model_path(app: AppHandle) -> String {
-    crate::asr::default_model_root(&app)
-        .to_string_lossy()
-        .to_string()
+pub fn set_continuation(
+    app: AppHandle,
+    continuation: ContinuationSettings,
+) -> Result<(), AppError> {
+    crate::settings::set_continuation(&app, continuation)
+        .map_err(|error| command_error("Could not set sentence continuation", error))
 }
 
 #[tauri::command]
-pub fn set_model_path(app: AppHandle, path: String) -> Result<(), String> {
-    let p = std::path::PathBuf::from(&path);
-    if !p.exists() || !p.is_dir() {
-        return Err("Path does not exist or is not a directory".to_string());
+pub fn get_decode_cache(app: AppHandle) -> DecodeCacheSettings {
+    crate::settings::get_settings(&app).decode_cache
+}
+
+#[tauri::command]
+pub fn set_decode_cache(app: AppHandle, decode_cache: DecodeCacheSettings) -> Result<(), AppError> {
+    crate::settings::set_decode_cache(&app, decode_cache)
+        .map_err(|error| command_error("Could not set decode cache", error))
+}
+
+/// Forgets every cached decode; the next transcription of each file decodes
+/// it again.
+#[tauri::command]
+pub fn clear_decode_cache(app: AppHandle) -> Result<(), AppError> {
+    crate::decode_cache::clear(&app)
+        .map_err(|error| command_error("Could not clear decode cache", error))
+}
+
+#[tauri::command]
+pub fn get_lookback(app: AppHandle) -> LookbackSettings {
+    crate::settings::get_settings(&app).lookback
+}
+
+/// Saves the lookback settings, then opens or releases the microphone and the
+/// lookback shortcut to match.
+#[tauri::command]
+pub fn set_lookback(app: AppHandle, lookback: LookbackSettings) -> Result<(), AppError> {
+    crate::settings::set_lookback(&app, lookback)
+        .map_err(|error| command_error("Could not set lookback listening", error))?;
+    #[cfg(desktop)]
+    desktop::sync_lookback(&app);
+    Ok(())
+}
+
+#[tauri::command]
+pub fn get_encrypt_at_rest(app: AppHandle) -> bool {
+    crate::settings::get_settings(&app).encrypt_at_rest
+}
+
+#[tauri::command]
+pub fn set_encrypt_at_rest(app: AppHandle, enabled: bool) -> Result<(), AppError> {
+    // The key is loaded first, so the setting is never saved as on while
+    // writes would still go out in plain text.
+    #[cfg(desktop)]
+    desktop::load_at_rest(enabled)
+        .map_err(|error| command_error("Could not load the encryption key", error))?;
+    if let Err(error) = crate::settings::set_encrypt_at_rest(&app, enabled) {
+        #[cfg(desktop)]
+        if let Err(error) = desktop::sync_at_rest(&app) {
+            log::warn!("Could not restore the saved encryption state: {error}");
+        }
+        return Err(command_error("Could not set history encryption", error));
     }
+    Ok(())
+}
 
-    crate::settings::set_model_path(&app, path)
-        .map_err(|error| command_error("Could not set model path", error))
+#[tauri::command]
+pub fn get_deep_links_enabled(app: AppHandle) -> bool {
+    crate::settings::get_settings(&app).deep_links_enabled
 }
 
 #[tauri::command]
-pub fn get_use_streaming(app: AppHandle) -> bool {
-    crate::settings::get_settings(&app).streaming_enabled
+pub fn set_deep_links_enabled(app: AppHandle, enabled: bool) -> Result<(), AppError> {
+    crate::settings::set_deep_links_enabled(&app, enabled)
+        .map_err(|error| command_error("Could not set links from other apps", error))
 }
 
 #[tauri::command]
-pub fn set_use_streaming(app: AppHandle, enabled: bool) -> Result<(), String> {
-    crate::settings::set_streaming_enabled(&app, enabled)
-        .map_err(|error| command_error("Could not set streaming preference", error))
+pub fn get_announce_transcripts(app: AppHandle) -> bool {
+    crate::settings::get_settings(&app).announce_transcripts
+}
+
+#[tauri::command]
+pub fn set_announce_transcripts(app: AppHandle, enabled: bool) -> Result<(), AppError> {
+    crate::settings::set_announce_transcripts(&app, enabled)
+        .map_err(|error| command_error("Could not set transcript announcement preference", error))
+}
+
+#[tauri::command]
+pub fn get_speak_transcripts(app: AppHandle) -> bool {
+    crate::settings::get_settings(&app).speak_transcripts
+}
+
+#[tauri::command]
+pub fn set_speak_transcripts(app: AppHandle, enabled: bool) -> Result<(), AppError> {
+    crate::settings::set_speak_transcripts(&app, enabled)
+        .map_err(|error| command_error("Could not set spoken read-back preference", error))
+}
+
+#[tauri::command]
+pub fn get_network_input(app: AppHandle) -> NetworkInputSettings {
+    crate::settings::get_settings(&app).network_input
+}
+
+#[tauri::command]
+pub fn set_network_input(
+    app: AppHandle,
+    network_input: NetworkInputSettings,
+) -> Result<(), AppError> {
+    crate::network_input::validate(&network_input)
+        .map_err(|error| command_error("Could not set network audio input", error))?;
+    crate::settings::set_network_input(&app, network_input)
+        .map_err(|error| command_error("Could not set network audio input", error))?;
+    #[cfg(desktop)]
+    desktop::sync_network_input(&app)
+        .map_err(|error| command_error("Could not start network audio input", error))?;
+    Ok(())
+}
+
+#[tauri::command]
+pub fn get_typed_metadata(app: AppHandle) -> TypedMetadataSettings {
+    crate::settings::get_settings(&app).typed_metadata
+}
+
+#[tauri::command]
+pub fn set_typed_metadata(
+    app: AppHandle,
+    typed_metadata: TypedMetadataSettings,
+) -> Result<(), AppError> {
+    crate::typed_metadata::validate(&typed_metadata)
+        .map_err(|error| command_error("Could not set editor metadata", error))?;
+    crate::settings::set_typed_metadata(&app, typed_metadata)
+        .map_err(|error| command_error("Could not set editor metadata", error))?;
+    #[cfg(desktop)]
+    desktop::sync_typed_metadata(&app)
+        .map_err(|error| command_error("Could not publish editor metadata", error))?;
+    Ok(())
+}
+
+#[tauri::command]
+pub fn get_autostart(app: AppHandle) -> AutostartSettings {
+    crate::settings::get_settings(&app).autostart
+}
+
+#[tauri::command]
+pub fn set_autostart(app: AppHandle, autostart: AutostartSettings) -> Result<(), AppError> {
+    crate::settings::set_autostart(&app, autostart)
+        .map_err(|error| command_error("Could not set start at login", error))?;
+    #[cfg(desktop)]
+    desktop::sync_autostart(&app)
+        .map_err(|error| command_error("Could not update the login item", error))?;
+    Ok(())
+}
+
+#[tauri::command]
+pub fn get_companion(app: AppHandle) -> CompanionStatus {
+    CompanionStatus::from(&crate::settings::get_settings(&app).companion)
+}
+
+#[tauri::command]
+pub fn set_companion(app: AppHandle, enabled: bool, port: u16) -> Result<(), AppError> {
+    crate::companion::validate(&CompanionSettings {
+        enabled,
+        port,
+        devices: Vec::new(),
+    })
+    .map_err(|error| command_error("Could not set companion listener", error))?;
+    crate::settings::set_companion(&app, enabled, port)
+        .map_err(|error| command_error("Could not set companion listener", error))?;
+    #[cfg(desktop)]
+    desktop::sync_companion(&app)
+        .map_err(|error| command_error("Could not start companion listener", error))?;
+    Ok(())
+}
+
+#[tauri::command]
+pub fn start_companion_pairing(app: AppHandle) -> Result<PairingInfo, AppError> {
+    crate::settings::ensure_settings_unlocked(&app)
+        .map_err(|error| command_error("Could not start companion pairing", error))?;
+    crate::companion::begin_pairing()
+        .map_err(|error| command_error("Could not start companion pairing", error))
+}
+
+#[tauri::command]
+pub fn revoke_companion_device(app: AppHandle, device_id: String) -> Result<(), AppError> {
+    crate::settings::remove_companion_device(&app, &device_id)
+        .map_err(|error| command_error("Could not revoke companion device", error))?;
+    #[cfg(desktop)]
+    if let Err(error) = desktop::forget_companion_key(&device_id) {
+        log::warn!("Could not remove companion device key: {error}");
+    }
+    Ok(())
+}
+
+#[tauri::command]
+pub fn get_settings_lock(app: AppHandle) -> SettingsLockStatus {
+    crate::settings::settings_lock_status(&app)
+}
+
+#[tauri::command]
+pub fn set_settings_lock(app: AppHandle, passphrase: String) -> Result<(), AppError> {
+    crate::settings::set_settings_lock(&app, &passphrase)
+        .map_err(|error| command_error("Could not lock settings", error))
+}
+
+#[tauri::command]
+pub fn remove_settings_lock(app: AppHandle, passphrase: String) -> Result<(), AppError> {
+    crate::settings::remove_settings_lock(&app, &passphrase)
+        .map_err(|error| command_error("Could not remove the settings lock", error))
+}
+
+#[tauri::command]
+pub fn unlock_settings(app: AppHandle, passphrase: String) -> Result<(), AppError> {
+    crate::settings::unlock_settings(&app, &passphrase)
+        .map_err(|error| command_error("Could not unlock settings", error))
+}
+
+#[tauri::command]
+pub fn lock_settings() {
+    crate::settings::lock_settings();
+}
+
+#[tauri::command]
+pub fn get_profiles(app: AppHandle) -> ProfileList {
+    ProfileList::from(&crate::settings::get_settings(&app).profiles)
+}
+
+#[tauri::command]
+pub fn create_profile(app: AppHandle, name: String) -> Result<(), AppError> {
+    crate::settings::create_profile(&app, &name)
+        .map_err(|error| command_error("Could not create profile", error))?;
+    #[cfg(desktop)]
+    desktop::sync_profiles_menu(&app);
+    Ok(())
+}
+
+#[tauri::command]
+pub fn delete_profile(app: AppHandle, name: String) -> Result<(), AppError> {
+    crate::settings::delete_profile(&app, &name)
+        .map_err(|error| command_error("Could not delete profile", error))?;
+    #[cfg(desktop)]
+    desktop::sync_profiles_menu(&app);
+    Ok(())
+}
+
+#[tauri::command]
+pub async fn switch_profile(
+    app: AppHandle,
+    state: State<'_, SpeechEngine>,
+    name: String,
+) -> Result<(), AppError> {
+    let engine = state.inner().clone();
+    run_blocking("Profile switch", move || {
+        crate::settings::switch_profile(&app, &engine, &name)
+            .map_err(|error| command_error("Could not switch profile", error))?;
+        #[cfg(desktop)]
+        {
+            desktop::sync_profiles_menu(&app);
+            desktop::sync_triggers(&app);
+        }
+        if let Err(error) = app.emit(PROFILE_CHANGED_EVENT, &name) {
+            log::warn!("Could not announce profile switch: {error}");
+        }
+        Ok(())
+    })
+    .await
+}
+
+#[tauri::command]
+pub fn get_quiet_hours_active() -> bool {
+    #[cfg(desktop)]
+    {
+        desktop::quiet_hours_active()
+    }
+    #[cfg(not(desktop))]
+    {
+        false
+    }
+}
+
+#[tauri::command]
+pub fn get_pause_markers(app: AppHandle) -> PauseMarkers {
+    crate::settings::get_settings(&app).pause_markers
+}
+
+#[tauri::command]
+pub fn set_pause_markers(app: AppHandle, pause_markers: PauseMarkers) -> Result<(), AppError> {
+    crate::settings::set_pause_markers(&app, pause_markers)
+        .map_err(|error| command_error("Could not set pause markers", error))
+}
+
+#[tauri::command]
+pub fn get_time_stretch(app: AppHandle) -> TimeStretch {
+    crate::settings::get_settings(&app).time_stretch
+}
+
+#[tauri::command]
+pub fn set_time_stretch(app: AppHandle, time_stretch: TimeStretch) -> Result<(), AppError> {
+    crate::settings::set_time_stretch(&app, time_stretch)
+        .map_err(|error| command_error("Could not set time stretch", error))
+}
+
+#[tauri::command]
+pub fn get_text_commands(app: AppHandle) -> TextCommands {
+    crate::settings::get_settings(&app).text_commands
+}
+
+#[tauri::command]
+pub fn set_text_commands(app: AppHandle, text_commands: TextCommands) -> Result<(), AppError> {
+    crate::settings::set_text_commands(&app, text_commands)
+        .map_err(|error| command_error("Could not set text commands", error))
+}
+
+#[tauri::command]
+pub fn list_post_process_stages(processor: State<'_, PostProcessor>) -> Vec<StageInfo> {
+    processor.stages()
+}
+
+#[tauri::command]
+pub fn get_post_processing(app: AppHandle) -> PostProcessing {
+    crate::settings::get_settings(&app).post_processing
+}
+
+#[tauri::command]
+pub fn set_post_processing(
+    app: AppHandle,
+    processor: State<'_, PostProcessor>,
+    post_processing: PostProcessing,
+) -> Result<(), AppError> {
+    let post_processing = post_processing.normalized();
+    processor
+        .validate(&post_processing)
+        .map_err(|error| command_error("Could not set post-processing stages", error))?;
+    crate::settings::set_post_processing(&app, post_processing)
+        .map_err(|error| command_error("Could not set post-processing stages", error))
+}
+
+#[tauri::command]
+pub fn get_typing_pace(app: AppHandle) -> TypingPace {
+    crate::settings::get_settings(&app).typing_pace
+}
+
+#[tauri::command]
+pub fn set_typing_pace(app: AppHandle, pace: TypingPace) -> Result<(), AppError> {
+    crate::settings::set_typing_pace(&app, pace)
+        .map_err(|error| command_error("Could not set typing pace", error))?;
+    #[cfg(desktop)]
+    desktop::sync_triggers(&app);
+    Ok(())
+}
+
+#[tauri::command]
+pub fn get_ort_threads(app: AppHandle) -> OrtThreads {
+    crate::settings::get_settings(&app).ort_threads
+}
+
+#[tauri::command]
+pub fn set_ort_threads(
+    app: AppHandle,
+    state: State<'_, SpeechEngine>,
+    threads: OrtThreads,
+) -> Result<(), AppError> {
+    if crate::settings::set_ort_threads(&app, threads)
+        .map_err(|error| command_error("Could not set speech model threads", error))?
+    {
+        state.reload_model();
+    }
+    Ok(())
+}
+
+/// Times a few thread splits on the bundled sample, keeps the fastest, and
+/// reloads the model with it.
+#[tauri::command]
+pub async fn tune_ort_threads(
+    app: AppHandle,
+    state: State<'_, SpeechEngine>,
+) -> Result<ThreadTuning, AppError> {
+    crate::settings::ensure_settings_unlocked(&app)
+        .map_err(|error| command_error("Could not tune speech model threads", error))?;
+    let engine = state.inner().clone();
+    run_blocking("Thread tuning", move || {
+        let sample = decode_wav(THREAD_TUNING_SAMPLE)
+            .map_err(|error| command_error("Could not read the tuning sample", error))?;
+        let tuning = engine
+            .tune_threads(&sample.samples, &tuning_candidates(available_cores()))
+            .map_err(|error| command_error("Could not tune speech model threads", error))?;
+        if crate::settings::set_ort_threads(&app, tuning.fastest)
+            .map_err(|error| command_error("Could not set speech model threads", error))?
+        {
+            engine.reload_model();
+        }
+        Ok(tuning)
+    })
+    .await
+}
+
+#[tauri::command]
+pub async fn model_signature_report(state: State<'_, SpeechEngine>) -> Result<String, AppError> {
+    let engine = state.inner().clone();
+    run_blocking("Model report", move || {
+        engine
+            .model_signature_report()
+            .map_err(|error| command_error("Could not read the speech model", error))
+    })
+    .await
 }
 
 #[tauri::command]
@@ -68,7 +1482,7 @@ pub fn get_asr_language(app: AppHandle) -> String {
 }
 
 #[tauri::command]
-pub fn get_asr_languages(state: State<'_, SpeechEngine>) -> Result<Vec<String>, String> {
+pub fn get_asr_languages(state: State<'_, SpeechEngine>) -> Result<Vec<String>, AppError> {
     state
         .languages()
         .map_err(|error| command_error("Could not list speech languages", error))
@@ -79,7 +1493,7 @@ pub async fn set_asr_language(
     app: AppHandle,
     state: State<'_, SpeechEngine>,
     language: String,
-) -> Result<(), String> {
+) -> Result<(), AppError> {
     let engine = state.inner().clone();
     run_blocking("Speech language", move || {
         crate::settings::set_asr_language(&app, &engine, language)
@@ -89,7 +1503,7 @@ pub async fn set_asr_language(
 }
 
 #[tauri::command]
-pub async fn pick_model_folder(app: AppHandle) -> Result<Option<String>, String> {
+pub async fn pick_model_folder(app: AppHandle) -> Result<Option<String>, AppError> {
     use tauri_plugin_dialog::DialogExt;
 
     let result = run_blocking("Dialog", move || {
@@ -101,7 +1515,77 @@ pub async fn pick_model_folder(app: AppHandle) -> Result<Option<String>, String>
 }
 
 #[tauri::command]
-pub async fn retry_model_download(state: State<'_, SpeechEngine>) -> Result<(), String> {
+pub async fn pick_model_archive(app: AppHandle) -> Result<Option<String>, AppError> {
+    use tauri_plugin_dialog::DialogExt;
+
+    let result = run_blocking("Dialog", move || {
+        Ok(app
+            .dialog()
+            .file()
+            .add_filter("Model archive", &["tar"])
+            .blocking_pick_file())
+    })
+    .await?;
+
+    Ok(result.map(|p| p.to_string()))
+}
+
+#[tauri::command]
+pub async fn pick_audio_file(app: AppHandle) -> Result<Option<String>, AppError> {
+    use tauri_plugin_dialog::DialogExt;
+
+    let result = run_blocking("Dialog", move || {
+        Ok(app
+            .dialog()
+            .file()
+            .add_filter("WAV audio", &["wav"])
+            .blocking_pick_file())
+    })
+    .await?;
+
+    Ok(result.map(|p| p.to_string()))
+}
+
+/// Transcribes a WAV file with the dictation settings, emitting each segment
+/// as `file_transcription_progress` as it is decoded.
+#[tauri::command]
+pub async fn transcribe_audio_file(
+    state: State<'_, SpeechEngine>,
+    path: String,
+) -> Result<String, AppError> {
+    let engine = state.inner().clone();
+    run_blocking("File transcription", move || {
+        engine
+            .transcribe_file(std::path::Path::new(&path))
+            .map_err(|error| command_error("Could not transcribe file", error))
+    })
+    .await
+}
+
+#[tauri::command]
+pub fn cancel_file_transcription() {
+    crate::dictation::cancel_file_transcription();
+}
+
+#[tauri::command]
+pub async fn import_model_archive(
+    app: AppHandle,
+    state: State<'_, SpeechEngine>,
+    path: String,
+) -> Result<(), AppError> {
+    crate::settings::ensure_settings_unlocked(&app)
+        .map_err(|error| command_error("Model import failed", error))?;
+    let engine = state.inner().clone();
+    run_blocking("Model import", move || {
+        engine
+            .import_model_archive(std::path::Path::new(&path))
+            .map_err(|error| command_error("Model import failed", error))
+    })
+    .await
+}
+
+#[tauri::command]
+pub async fn retry_model_download(state: State<'_, SpeechEngine>) -> Result<(), AppError> {
     let engine = state.inner().clone();
     run_blocking("Model download", move || {
         engine
@@ -112,45 +1596,110 @@ pub async fn retry_model_download(state: State<'_, SpeechEngine>) -> Result<(),
 }
 
 #[tauri::command]
-pub async fn start_recording(app: AppHandle, state: State<'_, SpeechEngine>) -> Result<(), String> {
+pub fn skip_model_load(state: State<'_, SpeechEngine>) {
+    state.cancel_model_load();
+}
+
+#[tauri::command]
+pub fn pause_download(state: State<'_, SpeechEngine>) {
+    state.set_model_download_paused(true);
+}
+
+#[tauri::command]
+pub fn resume_download(state: State<'_, SpeechEngine>) {
+    state.set_model_download_paused(false);
+}
+
+#[tauri::command]
+pub fn cancel_download(state: State<'_, SpeechEngine>) {
+    state.discard_model_download();
+}
+
+#[tauri::command]
+pub async fn start_recording(
+    app: AppHandle,
+    state: State<'_, SpeechEngine>,
+) -> Result<(), AppError> {
     let engine = state.inner().clone();
     run_blocking("Recording", move || start_recording_blocking(app, engine)).await
 }
 
-fn start_recording_blocking(app: AppHandle, state: SpeechEngine) -> Result<(), String> {
+fn start_recording_blocking(app: AppHandle, state: SpeechEngine) -> Result<(), AppError> {
     let reservation = state
         .reserve_dictation()
         .map_err(|error| command_error("Could not reserve dictation", error))?;
+    let router = state.patch_router(reservation.session_id(), PatchSource::App);
+    #[cfg(desktop)]
+    let router = with_app_typing(&app, router)?;
     state
-        .start_dictation(reservation, move |update| {
-            app.emit("transcription_update", update)
-                .map_err(|error| error.to_string())
-        })
+        .start_dictation(reservation, router)
         .map_err(|error| command_error("Could not start dictation", error))
 }
 
+/// Lets recorder-button dictations type into the focused app, or the open
+/// document, when the window's routing includes typing.
+#[cfg(desktop)]
+fn with_app_typing(app: &AppHandle, router: PatchRouter) -> Result<PatchRouter, AppError> {
+    let pace = crate::settings::get_settings(app).typing_pace;
+    let document_app = app.clone();
+    let router = router.with_typing(move |patch, transcript| {
+        if desktop::capture_patch(&document_app, patch, transcript) {
+            return Ok(());
+        }
+        desktop::type_patch(patch, transcript, pace).map_err(|error| error.to_string())
+    });
+    if router.types() {
+        desktop::reset_typing().map_err(|error| command_error("Could not start typing", error))?;
+    }
+    Ok(router)
+}
+
 #[tauri::command]
-pub async fn stop_recording(app: AppHandle, state: State<'_, SpeechEngine>) -> Result<(), String> {
+pub async fn stop_recording(state: State<'_, SpeechEngine>) -> Result<(), AppError> {
     let engine = state.inner().clone();
-    run_blocking("Recording", move || stop_recording_blocking(app, engine)).await
+    run_blocking("Recording", move || stop_recording_blocking(engine)).await
 }
 
-fn stop_recording_blocking(app: AppHandle, state: SpeechEngine) -> Result<(), String> {
+fn stop_recording_blocking(state: SpeechEngine) -> Result<(), AppError> {
+    let router = state.take_session_router();
+    #[cfg(desktop)]
+    let app = state.app().clone();
     state
         .finish_dictation(move |text| {
-            app.emit(
-                "transcription_update",
-                crate::streaming::TranscriptionUpdate::Replace(text),
-            )
-            .map_err(|error| error.to_string())
+            #[cfg(desktop)]
+            desktop::observe_onboarding(&app, OnboardingEvent::SampleDictated(text.clone()));
+            router.finish(text)
         })
         .map_err(|error| command_error("Could not finish dictation", error))
 }
 
 #[cfg(desktop)]
 #[tauri::command]
-pub fn update_record_shortcut(app: AppHandle, shortcut: String) -> Result<String, String> {
+pub fn update_record_shortcut(app: AppHandle, shortcut: String) -> Result<String, AppError> {
+    crate::settings::ensure_settings_unlocked(&app)
+        .map_err(|error| command_error("Could not update the record shortcut", error))?;
     desktop::update_record_shortcut(app, shortcut)
+        .map_err(|error| command_error("Could not update the record shortcut", error))
+}
+
+/// Why the record shortcut last failed to register, with free chords to try,
+/// until one registers.
+#[cfg(desktop)]
+#[tauri::command]
+pub fn get_shortcut_conflict() -> Option<desktop::ShortcutConflict> {
+    desktop::shortcut_conflict()
+}
+
+/// Waits for the next key chord and returns it as a shortcut string, or
+/// `None` when Escape is pressed. Saving it is left to the settings.
+#[cfg(desktop)]
+#[tauri::command]
+pub async fn begin_shortcut_capture(app: AppHandle) -> Result<Option<String>, AppError> {
+    run_blocking("Shortcut capture", move || {
+        desktop::begin_shortcut_capture(&app)
+            .map_err(|error| command_error("Could not capture a shortcut", error))
+    })
+    .await
 }
 
 #[cfg(desktop)]
@@ -166,27 +1715,61 @@ pub fn default_record_shortcut() -> String {
 }
 
 #[tauri::command]
-pub async fn reset_settings(app: AppHandle, state: State<'_, SpeechEngine>) -> Result<(), String> {
+pub async fn reset_settings(
+    app: AppHandle,
+    state: State<'_, SpeechEngine>,
+) -> Result<(), AppError> {
     let engine = state.inner().clone();
     run_blocking("Settings reset", move || {
+        #[cfg(desktop)]
+        let paired = crate::settings::get_settings(&app).companion.devices;
         crate::settings::reset_settings(&app, &engine)
-            .map_err(|error| command_error("Could not reset settings", error))
+            .map_err(|error| command_error("Could not reset settings", error))?;
+        #[cfg(desktop)]
+        {
+            for device in &paired {
+                if let Err(error) = desktop::forget_companion_key(&device.id) {
+                    log::warn!("Could not remove companion device key: {error}");
+                }
+            }
+            desktop::forget_secrets();
+            desktop::sync_dictation_enabled(&app);
+            desktop::refresh_quiet_hours(&app);
+            desktop::sync_triggers(&app);
+            if let Err(error) = desktop::sync_network_input(&app) {
+                log::warn!("Could not stop network audio input after reset: {error}");
+            }
+            if let Err(error) = desktop::sync_typed_metadata(&app) {
+                log::warn!("Could not stop editor metadata after reset: {error}");
+            }
+            if let Err(error) = desktop::sync_companion(&app) {
+                log::warn!("Could not stop companion listener after reset: {error}");
+            }
+            if let Err(error) = desktop::sync_autostart(&app) {
+                log::warn!("Could not remove login item after reset: {error}");
+            }
+            if let Err(error) = desktop::sync_at_rest(&app) {
+                log::warn!("Could not turn off encryption after reset: {error}");
+            }
+            desktop::sync_profiles_menu(&app);
+        }
+        Ok(())
     })
     .await
 }
 
 #[tauri::command]
-pub async fn check_for_app_update(app: AppHandle) -> Result<Option<AppUpdateInfo>, String> {
+pub async fn check_for_app_update(app: AppHandle) -> Result<Option<AppUpdateInfo>, AppError> {
     crate::updater::check_for_update(app).await.map_err(|err| {
         log::warn!("Update check failed: {err}");
-        user_error(err)
+        err.into()
     })
 }
 
 #[tauri::command]
-pub async fn install_app_update(app: AppHandle) -> Result<bool, String> {
+pub async fn install_app_update(app: AppHandle) -> Result<bool, AppError> {
     crate::updater::install_update(app).await.map_err(|err| {
         log::warn!("Update installation failed: {err}");
-        user_error(err)
+        err.into()
     })
 }
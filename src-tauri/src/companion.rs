@@ -0,0 +1,737 @@
+use std::io::{self, Read, Write};
+use std::net::{Ipv4Addr, TcpListener};
+#[cfg(feature = "desktop")]
+use std::sync::{Mutex, OnceLock};
+use std::time::{Duration, Instant};
+
+use chacha20poly1305::aead::{Aead, KeyInit};
+use chacha20poly1305::{ChaCha20Poly1305, Nonce};
+use hkdf::Hkdf;
+use hmac::{Hmac, Mac};
+use serde::{Deserialize, Serialize};
+use sha2::Sha256;
+use spake2::{Ed25519Group, Identity, Password, Spake2};
+
+use crate::errors::UserFacing;
+use crate::streaming::TranscriptPatch;
+
+pub const DEFAULT_COMPANION_PORT: u16 = 47_614;
+/// DNS-SD service type advertised for companion apps to discover.
+pub const SERVICE_TYPE: &str = "_silentkeys._tcp";
+/// Wire protocol version, advertised in the `proto` TXT record.
+pub const PROTOCOL_VERSION: u32 = 4;
+/// Longest accepted handshake line; anything longer is not a valid request.
+pub const MAX_MESSAGE_BYTES: usize = 4_096;
+/// Longest plaintext one sealed record carries.
+pub const MAX_RECORD_BYTES: usize = 65_536;
+/// Device keys and every key derived for them are 32 bytes.
+pub const KEY_BYTES: usize = 32;
+
+const PAIRING_CODE_TTL: Duration = Duration::from_secs(120);
+const PAIRING_ATTEMPTS: u8 = 5;
+const NONCE_BYTES: usize = 32;
+const SEAL_NONCE_BYTES: usize = 12;
+const TAG_BYTES: usize = 16;
+const DEVICE_ID_BYTES: usize = 8;
+/// HKDF labels, one per key derived from the same secret.
+const PAIRING_INFO: &[u8] = b"silentkeys companion pairing v4";
+const PAIRING_PROOF_INFO: &[u8] = b"silentkeys companion pairing proof v4";
+const CLIENT_SESSION_INFO: &[u8] = b"silentkeys companion client v4";
+const SERVER_SESSION_INFO: &[u8] = b"silentkeys companion server v4";
+/// SPAKE2 identities, binding each message to the side that sent it.
+const CLIENT_IDENTITY: &[u8] = b"silentkeys companion";
+const DESKTOP_IDENTITY: &[u8] = b"silentkeys desktop";
+
+type HmacSha256 = Hmac<Sha256>;
+
+/// A LAN listener that paired companion devices stream microphone audio to,
+/// receiving the live transcript back. Unlike network audio input it accepts
+/// connections from other machines, so every session is authenticated and
+/// encrypted.
+#[derive(Clone, Debug, Deserialize, Eq, PartialEq, Serialize)]
+pub struct CompanionSettings {
+    pub enabled: bool,
+    pub port: u16,
+    #[serde(default)]
+    pub devices: Vec<PairedDevice>,
+}
+
+/// What the settings UI sees: paired devices and when they were paired.
+#[derive(Clone, Debug, Serialize)]
+pub struct CompanionStatus {
+    pub enabled: bool,
+    pub port: u16,
+    pub devices: Vec<CompanionDeviceInfo>,
+}
+
+#[derive(Clone, Debug, Serialize)]
+pub struct CompanionDeviceInfo {
+    pub id: String,
+    pub name: String,
+    pub paired_at: String,
+}
+
+impl From<&CompanionSettings> for CompanionStatus {
+    fn from(settings: &CompanionSettings) -> Self {
+        Self {
+            enabled: settings.enabled,
+            port: settings.port,
+            devices: settings
+                .devices
+                .iter()
+                .map(|device| CompanionDeviceInfo {
+                    id: device.id.clone(),
+                    name: device.name.clone(),
+                    paired_at: device.paired_at.clone(),
+                })
+                .collect(),
+        }
+    }
+}
+
+/// A pairing code to show the user, valid for `expires_in_secs`.
+#[derive(Clone, Debug, Serialize)]
+pub struct PairingInfo {
+    pub code: String,
+    pub expires_in_secs: u64,
+}
+
+impl Default for CompanionSettings {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            port: DEFAULT_COMPANION_PORT,
+            devices: Vec::new(),
+        }
+    }
+}
+
+/// A device allowed to connect. Its key is kept in the system keychain.
+#[derive(Clone, Debug, Deserialize, Eq, PartialEq, Serialize)]
+pub struct PairedDevice {
+    pub id: String,
+    pub name: String,
+    /// RFC 3339 time the device was paired.
+    pub paired_at: String,
+}
+
+/// The line a client sends after the server's `challenge`. Neither carries a
+/// secret: each proves one with an HMAC keyed by what the secret derives.
+#[derive(Clone, Debug, Deserialize, Eq, PartialEq)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum ClientMessage {
+    Pair {
+        device_name: String,
+        pake: String,
+        proof: String,
+    },
+    Hello {
+        device_id: String,
+        proof: String,
+    },
+}
+
+/// Messages the desktop sends back. A connection opens with a plain JSON
+/// `challenge` line. After a `hello`, everything is sealed, starting with
+/// `ready`: the client streams network audio frames and receives `transcript`
+/// updates until the final `replace`, then the connection closes.
+#[derive(Clone, Debug, Serialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum ServerMessage {
+    Challenge {
+        nonce: String,
+        #[serde(skip_serializing_if = "Option::is_none")]
+        pake: Option<String>,
+    },
+    Paired {
+        device_id: String,
+        sealed_key: String,
+    },
+    Ready,
+    Transcript {
+        update: TranscriptPatch,
+    },
+    Error {
+        message: String,
+    },
+}
+
+#[derive(thiserror::Error, Debug)]
+pub enum CompanionError {
+    #[error("companion port must not be 0")]
+    InvalidPort,
+    #[error("listen on port {port}: {source}")]
+    Bind {
+        port: u16,
+        #[source]
+        source: io::Error,
+    },
+    #[error("generate companion secret: {0}")]
+    Random(String),
+    #[error("invalid companion message: {0}")]
+    InvalidMessage(String),
+    #[error("companion connection failed: {0}")]
+    Connection(#[source] io::Error),
+    #[error("companion pairing code was rejected")]
+    PairingRejected,
+    #[error("companion device is not paired")]
+    Unauthorized,
+    #[error("save paired device: {0}")]
+    Store(String),
+    #[error("companion pairing state is unavailable")]
+    LockFailed,
+    #[error("companion key exchange failed: {0}")]
+    KeyExchange(String),
+    #[error("companion device key: {0}")]
+    Keychain(String),
+}
+
+impl UserFacing for CompanionError {
+    fn user_message(&self) -> &'static str {
+        match self {
+            Self::InvalidPort => "Choose a companion port between 1 and 65535.",
+            Self::Bind { .. } => {
+                "Could not listen for companion devices. Check that the port is not already in use."
+            }
+            Self::Random(_) => "Could not create a secure pairing secret. Please try again.",
+            Self::InvalidMessage(_) | Self::Connection(_) | Self::KeyExchange(_) => {
+                "The companion device connection failed. Please reconnect."
+            }
+            Self::PairingRejected => {
+                "The pairing code was wrong or expired. Start pairing again on the desktop."
+            }
+            Self::Unauthorized => "This device is not paired. Pair it again from the desktop.",
+            Self::Store(_) | Self::LockFailed => {
+                "Could not pair the companion device. Please try again."
+            }
+            Self::Keychain(_) => {
+                "Could not reach the system keychain, which holds companion device keys."
+            }
+        }
+    }
+}
+
+pub fn validate(settings: &CompanionSettings) -> Result<(), CompanionError> {
+    if settings.port == 0 {
+        return Err(CompanionError::InvalidPort);
+    }
+    Ok(())
+}
+
+/// Binds every interface, since companions connect from other devices.
+pub fn listen(port: u16) -> Result<TcpListener, CompanionError> {
+    TcpListener::bind((Ipv4Addr::UNSPECIFIED, port))
+        .map_err(|source| CompanionError::Bind { port, source })
+}
+
+pub fn parse_client_message(line: &str) -> Result<ClientMessage, CompanionError> {
+    serde_json::from_str(line.trim())
+        .map_err(|error| CompanionError::InvalidMessage(error.to_string()))
+}
+
+pub fn encode_server_message(message: &ServerMessage) -> String {
+    let mut line = serde_json::to_string(message).unwrap_or_else(|_| "{}".to_string());
+    line.push('\n');
+    line
+}
+
+fn random_bytes<const N: usize>() -> Result<[u8; N], CompanionError> {
+    let mut bytes = [0_u8; N];
+    getrandom::fill(&mut bytes).map_err(|error| CompanionError::Random(error.to_string()))?;
+    Ok(bytes)
+}
+
+pub fn to_hex(bytes: &[u8]) -> String {
+    bytes.iter().map(|byte| format!("{byte:02x}")).collect()
+}
+
+pub fn from_hex(text: &str) -> Option<Vec<u8>> {
+    if !text.len().is_multiple_of(2) {
+        return None;
+    }
+    (0..text.len())
+        .step_by(2)
+        .map(|index| u8::from_str_radix(text.get(index..index + 2)?, 16).ok())
+        .collect()
+}
+
+fn mac(key: &[u8], parts: &[&[u8]]) -> Result<HmacSha256, CompanionError> {
+    let mut mac = <HmacSha256 as Mac>::new_from_slice(key)
+        .map_err(|error| CompanionError::KeyExchange(error.to_string()))?;
+    for part in parts {
+        mac.update(part);
+    }
+    Ok(mac)
+}
+
+fn mac_hex(key: &[u8], parts: &[&[u8]]) -> Result<String, CompanionError> {
+    mac(key, parts).map(|mac| to_hex(&mac.finalize().into_bytes()))
+}
+
+/// Checks `proof` in constant time, so response timing does not reveal how
+/// much of it matched.
+fn verify_mac(key: &[u8], parts: &[&[u8]], proof: &str) -> bool {
+    from_hex(proof)
+        .is_some_and(|proof| mac(key, parts).is_ok_and(|mac| mac.verify_slice(&proof).is_ok()))
+}
+
+fn derive_key(ikm: &[u8], salt: &[u8], info: &[u8]) -> [u8; KEY_BYTES] {
+    let mut key = [0_u8; KEY_BYTES];
+    // A 32-byte output is always within HKDF-SHA256's limit.
+    let _ = Hkdf::<Sha256>::new(Some(salt), ikm).expand(info, &mut key);
+    key
+}
+
+/// What a paired client sends to prove it holds `device_key`.
+pub fn hello_proof(
+    device_key: &[u8],
+    nonce: &[u8],
+    device_id: &str,
+) -> Result<String, CompanionError> {
+    mac_hex(device_key, &[nonce, device_id.as_bytes()])
+}
+
+enum PairingSide {
+    Client,
+    Desktop,
+}
+
+fn start_pairing(code: &str, side: PairingSide) -> (Spake2<Ed25519Group>, Vec<u8>) {
+    let (password, client, desktop) = (
+        Password::new(code.as_bytes()),
+        Identity::new(CLIENT_IDENTITY),
+        Identity::new(DESKTOP_IDENTITY),
+    );
+    match side {
+        PairingSide::Client => Spake2::start_a(&password, &client, &desktop),
+        PairingSide::Desktop => Spake2::start_b(&password, &client, &desktop),
+    }
+}
+
+/// The companion's half of pairing: its SPAKE2 message for the `pair` line,
+/// and the key it shares with the desktop if both used `code`.
+pub fn client_pairing(
+    code: &str,
+    nonce: &[u8],
+    desktop_message: &str,
+) -> Result<(String, PairingKey), CompanionError> {
+    let desktop_message = from_hex(desktop_message)
+        .ok_or_else(|| CompanionError::InvalidMessage("pake is not hex".to_string()))?;
+    let (state, message) = start_pairing(code, PairingSide::Client);
+    let shared = state
+        .finish(&desktop_message)
+        .map_err(|error| CompanionError::KeyExchange(format!("{error:?}")))?;
+    Ok((to_hex(&message), PairingKey::new(&shared, nonce)))
+}
+
+/// The key a SPAKE2 exchange over the pairing code agreed on. Each guess at
+/// the code costs an attacker one exchange with the desktop, even one that
+/// can intercept and alter the traffic.
+pub struct PairingKey {
+    proof: [u8; KEY_BYTES],
+    seal: [u8; KEY_BYTES],
+}
+
+impl PairingKey {
+    fn new(shared: &[u8], nonce: &[u8]) -> Self {
+        Self {
+            proof: derive_key(shared, nonce, PAIRING_PROOF_INFO),
+            seal: derive_key(shared, nonce, PAIRING_INFO),
+        }
+    }
+
+    /// What a pairing client sends to show it ran the exchange with the
+    /// right code.
+    pub fn proof(&self, device_name: &str) -> Result<String, CompanionError> {
+        mac_hex(&self.proof, &[device_name.as_bytes()])
+    }
+
+    pub fn proves(&self, device_name: &str, proof: &str) -> bool {
+        verify_mac(&self.proof, &[device_name.as_bytes()], proof)
+    }
+
+    /// Seals under a fresh random nonce, sent ahead of the ciphertext.
+    pub fn seal_device_key(&self, device_key: &[u8; KEY_BYTES]) -> Result<String, CompanionError> {
+        let nonce = random_bytes::<SEAL_NONCE_BYTES>()?;
+        let sealed = ChaCha20Poly1305::new(&self.seal.into())
+            .encrypt(&Nonce::from(nonce), device_key.as_slice())
+            .map_err(|_| {
+                CompanionError::KeyExchange("could not seal the device key".to_string())
+            })?;
+        Ok(to_hex(&[nonce.as_slice(), &sealed].concat()))
+    }
+
+    pub fn open_device_key(&self, sealed_key: &str) -> Result<[u8; KEY_BYTES], CompanionError> {
+        let sealed = from_hex(sealed_key)
+            .filter(|sealed| sealed.len() > SEAL_NONCE_BYTES)
+            .ok_or_else(|| CompanionError::InvalidMessage("sealed key is malformed".to_string()))?;
+        let (nonce, ciphertext) = sealed.split_at(SEAL_NONCE_BYTES);
+        ChaCha20Poly1305::new(&self.seal.into())
+            .decrypt(Nonce::from_slice(nonce), ciphertext)
+            .ok()
+            .and_then(|key| key.try_into().ok())
+            .ok_or_else(|| CompanionError::KeyExchange("could not open the device key".to_string()))
+    }
+}
+
+/// The fresh nonce a connection opens with and, while a pairing code is
+/// outstanding, the desktop's SPAKE2 message for it.
+pub struct Challenge {
+    nonce: [u8; NONCE_BYTES],
+    pairing: Option<DesktopPairing>,
+}
+
+struct DesktopPairing {
+    code: String,
+    state: Spake2<Ed25519Group>,
+    message: Vec<u8>,
+}
+
+impl Challenge {
+    pub fn generate(pairing_code: Option<&str>) -> Result<Self, CompanionError> {
+        Ok(Self {
+            nonce: random_bytes()?,
+            pairing: pairing_code.map(|code| {
+                let (state, message) = start_pairing(code, PairingSide::Desktop);
+                DesktopPairing {
+                    code: code.to_string(),
+                    state,
+                    message,
+                }
+            }),
+        })
+    }
+
+    pub fn nonce(&self) -> &[u8] {
+        &self.nonce
+    }
+
+    pub fn message(&self) -> ServerMessage {
+        ServerMessage::Challenge {
+            nonce: to_hex(&self.nonce),
+            pake: self
+                .pairing
+                .as_ref()
+                .map(|pairing| to_hex(&pairing.message)),
+        }
+    }
+
+    /// Finishes the exchange with a `pair` message, returning the code this
+    /// challenge ran it with and the key, which only matches the client's if
+    /// it used that code too.
+    pub fn pairing_key(&mut self, pake: &str) -> Result<(String, PairingKey), CompanionError> {
+        let pairing = self.pairing.take().ok_or(CompanionError::PairingRejected)?;
+        let pake = from_hex(pake)
+            .ok_or_else(|| CompanionError::InvalidMessage("pake is not hex".to_string()))?;
+        let shared = pairing
+            .state
+            .finish(&pake)
+            .map_err(|error| CompanionError::KeyExchange(format!("{error:?}")))?;
+        Ok((pairing.code, PairingKey::new(&shared, &self.nonce)))
+    }
+
+    /// Finds the device a `hello` names and checks that its proof was made
+    /// with `device_key`, the key stored for that device.
+    pub fn authenticate<'a>(
+        &self,
+        devices: &'a [PairedDevice],
+        device_id: &str,
+        device_key: &[u8],
+        proof: &str,
+    ) -> Result<&'a PairedDevice, CompanionError> {
+        devices
+            .iter()
+            .find(|device| device.id == device_id)
+            .filter(|_| verify_mac(device_key, &[&self.nonce, device_id.as_bytes()], proof))
+            .ok_or(CompanionError::Unauthorized)
+    }
+
+    /// The desktop's half of an authenticated session.
+    pub fn server_session(&self, device_key: &[u8]) -> (RecordOpener, RecordSealer) {
+        let (client, server) = session_keys(device_key, &self.nonce);
+        (RecordOpener::new(&client), RecordSealer::new(&server))
+    }
+}
+
+/// Separate keys for each direction, so the two never share a nonce.
+fn session_keys(device_key: &[u8], nonce: &[u8]) -> ([u8; KEY_BYTES], [u8; KEY_BYTES]) {
+    (
+        derive_key(device_key, nonce, CLIENT_SESSION_INFO),
+        derive_key(device_key, nonce, SERVER_SESSION_INFO),
+    )
+}
+
+/// The companion's half of an authenticated session.
+pub fn client_session(device_key: &[u8], nonce: &[u8]) -> (RecordOpener, RecordSealer) {
+    let (client, server) = session_keys(device_key, nonce);
+    (RecordOpener::new(&server), RecordSealer::new(&client))
+}
+
+/// Record `n` is sealed with nonce `n`; a dropped, replayed, or reordered
+/// record fails to open.
+fn record_nonce(counter: u64) -> Nonce {
+    let mut nonce = Nonce::default();
+    nonce[..8].copy_from_slice(&counter.to_le_bytes());
+    nonce
+}
+
+/// Seals outgoing records: a little-endian `u32` length, then the
+/// ChaCha20-Poly1305 ciphertext and tag.
+pub struct RecordSealer {
+    cipher: ChaCha20Poly1305,
+    counter: u64,
+}
+
+impl RecordSealer {
+    fn new(key: &[u8; KEY_BYTES]) -> Self {
+        Self {
+            cipher: ChaCha20Poly1305::new(key.into()),
+            counter: 0,
+        }
+    }
+
+    pub fn seal(&mut self, plaintext: &[u8]) -> io::Result<Vec<u8>> {
+        if plaintext.len() > MAX_RECORD_BYTES {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidInput,
+                "companion record is too long",
+            ));
+        }
+        let sealed = self
+            .cipher
+            .encrypt(&record_nonce(self.counter), plaintext)
+            .map_err(|_| io::Error::other("could not seal a companion record"))?;
+        self.counter += 1;
+        let mut record = Vec::with_capacity(4 + sealed.len());
+        record.extend_from_slice(&(sealed.len() as u32).to_le_bytes());
+        record.extend_from_slice(&sealed);
+        Ok(record)
+    }
+}
+
+pub struct RecordOpener {
+    cipher: ChaCha20Poly1305,
+    counter: u64,
+}
+
+impl RecordOpener {
+    fn new(key: &[u8; KEY_BYTES]) -> Self {
+        Self {
+            cipher: ChaCha20Poly1305::new(key.into()),
+            counter: 0,
+        }
+    }
+
+    /// Reads and opens the next record, or `None` once the stream ends.
+    pub fn open(&mut self, reader: &mut impl Read) -> io::Result<Option<Vec<u8>>> {
+        let mut header = [0_u8; 4];
+        match reader.read_exact(&mut header) {
+            Ok(()) => {}
+            Err(error) if error.kind() == io::ErrorKind::UnexpectedEof => return Ok(None),
+            Err(error) => return Err(error),
+        }
+        let len = u32::from_le_bytes(header) as usize;
+        if len > MAX_RECORD_BYTES + TAG_BYTES {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidData,
+                "companion record is too long",
+            ));
+        }
+        let mut sealed = vec![0; len];
+        reader.read_exact(&mut sealed)?;
+        let plaintext = self
+            .cipher
+            .decrypt(&record_nonce(self.counter), sealed.as_slice())
+            .map_err(|_| {
+                io::Error::new(
+                    io::ErrorKind::InvalidData,
+                    "companion record failed authentication",
+                )
+            })?;
+        self.counter += 1;
+        Ok(Some(plaintext))
+    }
+}
+
+/// Presents the plaintext of sealed records as one byte stream, so network
+/// audio frames read the same as from a plain connection.
+pub struct SealedReader<R> {
+    inner: R,
+    opener: RecordOpener,
+    buffer: Vec<u8>,
+    position: usize,
+}
+
+impl<R: Read> SealedReader<R> {
+    pub fn new(inner: R, opener: RecordOpener) -> Self {
+        Self {
+            inner,
+            opener,
+            buffer: Vec::new(),
+            position: 0,
+        }
+    }
+}
+
+impl<R: Read> Read for SealedReader<R> {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        while self.position == self.buffer.len() {
+            match self.opener.open(&mut self.inner)? {
+                Some(plaintext) => {
+                    self.buffer = plaintext;
+                    self.position = 0;
+                }
+                None => return Ok(0),
+            }
+        }
+        let len = buf.len().min(self.buffer.len() - self.position);
+        buf[..len].copy_from_slice(&self.buffer[self.position..self.position + len]);
+        self.position += len;
+        Ok(len)
+    }
+}
+
+/// Seals each write as one or more records.
+pub struct SealedWriter<W> {
+    inner: W,
+    sealer: RecordSealer,
+}
+
+impl<W: Write> SealedWriter<W> {
+    pub fn new(inner: W, sealer: RecordSealer) -> Self {
+        Self { inner, sealer }
+    }
+}
+
+impl<W: Write> Write for SealedWriter<W> {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        let len = buf.len().min(MAX_RECORD_BYTES);
+        self.inner.write_all(&self.sealer.seal(&buf[..len])?)?;
+        Ok(len)
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        self.inner.flush()
+    }
+}
+
+/// Creates a device record and the key handed to it once; the desktop keeps
+/// the key in the system keychain.
+pub fn new_device(
+    name: &str,
+    paired_at: &str,
+) -> Result<(PairedDevice, [u8; KEY_BYTES]), CompanionError> {
+    let name = name.trim();
+    let device = PairedDevice {
+        id: to_hex(&random_bytes::<DEVICE_ID_BYTES>()?),
+        name: if name.is_empty() {
+            "Companion device".to_string()
+        } else {
+            name.chars().take(64).collect()
+        },
+        paired_at: paired_at.to_string(),
+    };
+    Ok((device, random_bytes()?))
+}
+
+/// A short code shown on the desktop that a new device must echo to pair.
+/// It expires quickly and after a few wrong guesses.
+#[derive(Clone, Debug)]
+pub struct PairingCode {
+    code: String,
+    expires_at: Instant,
+    attempts_left: u8,
+}
+
+impl PairingCode {
+    pub fn generate(now: Instant) -> Result<Self, CompanionError> {
+        let value = u32::from_le_bytes(random_bytes::<4>()?) % 1_000_000;
+        Ok(Self::new(format!("{value:06}"), now))
+    }
+
+    pub fn new(code: String, now: Instant) -> Self {
+        Self {
+            code,
+            expires_at: now + PAIRING_CODE_TTL,
+            attempts_left: PAIRING_ATTEMPTS,
+        }
+    }
+
+    pub fn code(&self) -> &str {
+        &self.code
+    }
+
+    pub fn remaining(&self, now: Instant) -> Duration {
+        self.expires_at.saturating_duration_since(now)
+    }
+
+    /// Whether the code can still be tried.
+    pub fn is_usable(&self, now: Instant) -> bool {
+        self.attempts_left > 0 && now < self.expires_at
+    }
+
+    /// Checks one attempt, spending it when `proves` rejects the code.
+    pub fn check(
+        &mut self,
+        proves: impl FnOnce(&str) -> bool,
+        now: Instant,
+    ) -> Result<(), CompanionError> {
+        if !self.is_usable(now) {
+            return Err(CompanionError::PairingRejected);
+        }
+        if proves(&self.code) {
+            return Ok(());
+        }
+        self.attempts_left -= 1;
+        Err(CompanionError::PairingRejected)
+    }
+}
+
+#[cfg(feature = "desktop")]
+fn pairing_code() -> &'static Mutex<Option<PairingCode>> {
+    static PAIRING_CODE: OnceLock<Mutex<Option<PairingCode>>> = OnceLock::new();
+    PAIRING_CODE.get_or_init(|| Mutex::new(None))
+}
+
+/// Replaces any outstanding pairing code with a fresh one.
+#[cfg(feature = "desktop")]
+pub(crate) fn begin_pairing() -> Result<PairingInfo, CompanionError> {
+    let now = Instant::now();
+    let code = PairingCode::generate(now)?;
+    let info = PairingInfo {
+        code: code.code().to_string(),
+        expires_in_secs: code.remaining(now).as_secs(),
+    };
+    *pairing_code()
+        .lock()
+        .map_err(|_| CompanionError::LockFailed)? = Some(code);
+    Ok(info)
+}
+
+/// The outstanding code while it can still be tried.
+#[cfg(feature = "desktop")]
+pub(crate) fn pending_pairing_code() -> Option<String> {
+    let pending = pairing_code().lock().ok()?;
+    pending
+        .as_ref()
+        .filter(|code| code.is_usable(Instant::now()))
+        .map(|code| code.code().to_string())
+}
+
+/// Consumes and returns the outstanding code when `proves` accepts it, so
+/// each code pairs at most one device.
+#[cfg(feature = "desktop")]
+pub(crate) fn redeem_pairing_code(
+    proves: impl FnOnce(&str) -> bool,
+) -> Result<String, CompanionError> {
+    let mut pending = pairing_code()
+        .lock()
+        .map_err(|_| CompanionError::LockFailed)?;
+    let code = pending.as_mut().ok_or(CompanionError::PairingRejected)?;
+    let result = code.check(proves, Instant::now());
+    let redeemed = code.code().to_string();
+    if result.is_ok() || !code.is_usable(Instant::now()) {
+        *pending = None;
+    }
+    result.map(|()| redeemed)
+}
@@ -0,0 +1,232 @@
+use std::io;
+use std::path::Path;
+
+use serde::{Deserialize, Serialize};
+use serde_json::{Map, Value};
+
+use crate::errors::UserFacing;
+use crate::settings::Settings;
+
+/// Identifies a SilentKeys configuration file among other JSON documents.
+pub const CONFIG_FORMAT: &str = "silentkeys-config";
+/// Bumped whenever exported settings change shape, together with a new entry
+/// in [`MIGRATIONS`].
+pub const CONFIG_VERSION: u64 = 1;
+
+/// Upgrades the settings of a version `index + 1` bundle to the next version.
+const MIGRATIONS: &[fn(&mut Map<String, Value>)] = &[];
+
+/// Settings that describe this machine rather than the user's setup. They are
+/// left out of exports and kept as they are on import.
+const LOCAL_KEYS: &[&str] = &[
+    "model_path",
+    "hardware_detection",
+    "companion",
+    "autostart",
+    "settings_lock",
+    "ort_threads",
+    "encrypt_at_rest",
+    "deep_links_enabled",
+    "speaker_adaptation",
+    "lookback",
+    "onboarding_completed",
+];
+
+/// Everything needed to reproduce a setup on another machine.
+#[derive(Clone, Debug, Deserialize, PartialEq, Serialize)]
+pub struct ConfigBundle {
+    pub format: String,
+    pub version: u64,
+    pub exported_at: String,
+    pub settings: Map<String, Value>,
+    #[serde(default)]
+    pub record_shortcut: Option<String>,
+}
+
+/// A validated bundle, ready to replace the current configuration.
+#[derive(Clone, Debug, PartialEq)]
+pub struct ImportedConfig {
+    pub settings: Settings,
+    pub record_shortcut: Option<String>,
+}
+
+#[derive(thiserror::Error, Debug)]
+pub enum ConfigBundleError {
+    #[error("read configuration file: {0}")]
+    Read(#[source] io::Error),
+    #[error("write configuration file: {0}")]
+    Write(#[source] io::Error),
+    #[error("parse configuration file: {0}")]
+    Parse(#[source] serde_json::Error),
+    #[error("file is not a SilentKeys configuration")]
+    NotABundle,
+    #[error("configuration version {0} is newer than this app supports")]
+    UnsupportedVersion(u64),
+    #[error("invalid {key} setting: {message}")]
+    InvalidSetting { key: String, message: String },
+}
+
+impl UserFacing for ConfigBundleError {
+    fn user_message(&self) -> &'static str {
+        match self {
+            Self::Read(_) => "Could not read the configuration file.",
+            Self::Write(_) => "Could not write the configuration file. Choose another location.",
+            Self::Parse(_) | Self::NotABundle => {
+                "The file is not a SilentKeys configuration export."
+            }
+            Self::UnsupportedVersion(_) => {
+                "The configuration was exported by a newer SilentKeys. Update the app to import it."
+            }
+            Self::InvalidSetting { .. } => {
+                "The configuration contains an invalid setting and was not imported."
+            }
+        }
+    }
+}
+
+fn settings_map(settings: &Settings) -> Map<String, Value> {
+    match serde_json::to_value(settings) {
+        Ok(Value::Object(map)) => map,
+        _ => Map::new(),
+    }
+}
+
+pub fn export_bundle(
+    settings: &Settings,
+    record_shortcut: Option<String>,
+    exported_at: &str,
+) -> ConfigBundle {
+    let mut map = settings_map(settings);
+    for key in LOCAL_KEYS {
+        map.remove(*key);
+    }
+    ConfigBundle {
+        format: CONFIG_FORMAT.to_string(),
+        version: CONFIG_VERSION,
+        exported_at: exported_at.to_string(),
+        settings: map,
+        record_shortcut,
+    }
+}
+
+/// Checks the format and version before the rest of the document, so a file
+/// from a newer app is reported as such rather than as malformed.
+pub fn parse_bundle(json: &str) -> Result<ConfigBundle, ConfigBundleError> {
+    let value: Value = serde_json::from_str(json).map_err(ConfigBundleError::Parse)?;
+    if value.get("format").and_then(Value::as_str) != Some(CONFIG_FORMAT) {
+        return Err(ConfigBundleError::NotABundle);
+    }
+    match value.get("version").and_then(Value::as_u64) {
+        Some(version) if version > CONFIG_VERSION => {
+            return Err(ConfigBundleError::UnsupportedVersion(version))
+        }
+        Some(version) if version > 0 => {}
+        _ => return Err(ConfigBundleError::NotABundle),
+    }
+    serde_json::from_value(value).map_err(ConfigBundleError::Parse)
+}
+
+fn migrate(bundle: &mut ConfigBundle) {
+    let first = usize::try_from(bundle.version.saturating_sub(1)).unwrap_or(usize::MAX);
+    for upgrade in MIGRATIONS.iter().skip(first) {
+        upgrade(&mut bundle.settings);
+    }
+    bundle.version = CONFIG_VERSION;
+}
+
+fn invalid(key: &str, error: impl std::fmt::Display) -> ConfigBundleError {
+    ConfigBundleError::InvalidSetting {
+        key: key.to_string(),
+        message: error.to_string(),
+    }
+}
+
+/// Builds the settings a bundle describes on top of the defaults, keeping this
+/// machine's local settings and any secrets the bundle leaves blank. Unknown
+/// keys are ignored; any invalid value rejects the whole bundle.
+pub fn resolve_bundle(
+    mut bundle: ConfigBundle,
+    current: &Settings,
+) -> Result<ImportedConfig, ConfigBundleError> {
+    migrate(&mut bundle);
+    let current = settings_map(current);
+    let mut merged = settings_map(&Settings::default());
+
+    for (key, value) in bundle.settings {
+        if LOCAL_KEYS.contains(&key.as_str()) {
+            continue;
+        }
+        if !merged.contains_key(&key) {
+            log::warn!("Ignoring unknown setting {key} in configuration import");
+            continue;
+        }
+        let mut candidate = merged.clone();
+        candidate.insert(key.clone(), value);
+        serde_json::from_value::<Settings>(Value::Object(candidate.clone()))
+            .map_err(|error| invalid(&key, error))?;
+        merged = candidate;
+    }
+    for key in LOCAL_KEYS {
+        if let Some(value) = current.get(*key) {
+            merged.insert(key.to_string(), value.clone());
+        }
+    }
+
+    let mut settings: Settings = serde_json::from_value(Value::Object(merged))
+        .map_err(|error| invalid("settings", error))?;
+    settings.input_device = settings.input_device.normalized();
+    settings.typing_pace = settings.typing_pace.clamped();
+    settings.continuation = settings.continuation.clamped();
+    settings.inactivity = settings.inactivity.clamped();
+    settings.pause_markers = settings.pause_markers.clamped();
+    settings.vad = settings.vad.clamped();
+    settings.noise_suppression = settings.noise_suppression.normalized();
+    settings.time_stretch = settings.time_stretch.clamped();
+    settings.decode_cache = settings.decode_cache.clamped();
+    settings.shortcut_gesture = settings.shortcut_gesture.clamped();
+    settings.triggers = settings.triggers.normalized();
+    settings.post_processing = settings.post_processing.normalized();
+    settings.quiet_hours = settings.quiet_hours.normalized();
+    validate(&settings)?;
+
+    Ok(ImportedConfig {
+        settings,
+        record_shortcut: bundle
+            .record_shortcut
+            .map(|shortcut| shortcut.trim().to_string())
+            .filter(|shortcut| !shortcut.is_empty()),
+    })
+}
+
+/// Applies the same checks as the individual settings commands.
+fn validate(settings: &Settings) -> Result<(), ConfigBundleError> {
+    if settings.webhook.enabled {
+        crate::webhook::validate_url(&settings.webhook.url)
+            .map_err(|error| invalid("webhook", error))?;
+    }
+    if settings.llm_handoff.enabled {
+        crate::llm_handoff::chat_completions_url(&settings.llm_handoff.endpoint)
+            .map_err(|error| invalid("llm_handoff", error))?;
+    }
+    settings
+        .output_templates
+        .validate()
+        .map_err(|error| invalid("output_templates", error))?;
+    crate::network_input::validate(&settings.network_input)
+        .map_err(|error| invalid("network_input", error))?;
+    crate::typed_metadata::validate(&settings.typed_metadata)
+        .map_err(|error| invalid("typed_metadata", error))?;
+    Ok(())
+}
+
+pub fn write_bundle(path: &Path, bundle: &ConfigBundle) -> Result<(), ConfigBundleError> {
+    let mut json = serde_json::to_string_pretty(bundle)
+        .map_err(|error| ConfigBundleError::Write(io::Error::other(error)))?;
+    json.push('\n');
+    std::fs::write(path, json).map_err(ConfigBundleError::Write)
+}
+
+pub fn read_bundle(path: &Path, current: &Settings) -> Result<ImportedConfig, ConfigBundleError> {
+    let json = std::fs::read_to_string(path).map_err(ConfigBundleError::Read)?;
+    resolve_bundle(parse_bundle(&json)?, current)
+}
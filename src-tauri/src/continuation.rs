@@ -0,0 +1,107 @@
+use std::collections::HashMap;
+use std::time::{Duration, Instant};
+
+use serde::{Deserialize, Serialize};
+
+pub const MIN_CONTINUATION_WINDOW_SECS: u64 = 5;
+pub const MAX_CONTINUATION_WINDOW_SECS: u64 = 600;
+/// How many typed characters are kept per app; enough to see how the last
+/// sentence ended.
+pub const CONTEXT_CHARS: usize = 32;
+
+/// Carries the end of the last dictation into the next one typed into the
+/// same app, so a sentence split across two recordings reads as one.
+#[derive(Clone, Copy, Debug, Deserialize, Eq, PartialEq, Serialize)]
+#[serde(default)]
+pub struct ContinuationSettings {
+    pub enabled: bool,
+    /// Dictations further apart than this start fresh.
+    pub window_secs: u64,
+}
+
+impl Default for ContinuationSettings {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            window_secs: 30,
+        }
+    }
+}
+
+impl ContinuationSettings {
+    pub fn clamped(self) -> Self {
+        Self {
+            window_secs: self
+                .window_secs
+                .clamp(MIN_CONTINUATION_WINDOW_SECS, MAX_CONTINUATION_WINDOW_SECS),
+            ..self
+        }
+    }
+
+    pub fn window(&self) -> Option<Duration> {
+        self.enabled.then(|| Duration::from_secs(self.window_secs))
+    }
+}
+
+/// The last characters typed into each app, keyed by the app's name.
+#[derive(Debug, Default)]
+pub struct RecentTyping {
+    apps: HashMap<String, (String, Instant)>,
+}
+
+impl RecentTyping {
+    pub fn record(&mut self, app: &str, typed: &str, at: Instant) {
+        if typed.is_empty() {
+            return;
+        }
+        let skip = typed.chars().count().saturating_sub(CONTEXT_CHARS);
+        let tail = typed.chars().skip(skip).collect();
+        self.apps.insert(app.to_string(), (tail, at));
+    }
+
+    /// The text last typed into `app`, if that was no longer than `window`
+    /// before `now`.
+    pub fn context(&self, app: &str, now: Instant, window: Duration) -> Option<&str> {
+        self.apps
+            .get(app)
+            .filter(|(_, at)| now.saturating_duration_since(*at) <= window)
+            .map(|(tail, _)| tail.as_str())
+    }
+}
+
+/// Whether text typed after `before` continues its sentence: `before` ends
+/// in a word or mid-sentence punctuation rather than a sentence end or a
+/// line break.
+pub fn continues_sentence(before: &str) -> bool {
+    let before = before.trim_end_matches([' ', '\t']);
+    let before = before.trim_end_matches(['"', '\'', ')', ']', '\u{201D}', '\u{2019}']);
+    match before.chars().last() {
+        Some(last) => !matches!(last, '.' | '!' | '?' | '\u{2026}' | '\n' | '\r'),
+        None => false,
+    }
+}
+
+/// Lowercases the first word the model capitalized as a sentence start. All
+/// caps words and "I" forms are left alone; a proper noun at the start of a
+/// continuation is lowercased too.
+pub fn lowercase_sentence_start(text: &str) -> String {
+    let leading = text.len() - text.trim_start().len();
+    let (space, rest) = text.split_at(leading);
+    let word = rest.split_whitespace().next().unwrap_or_default();
+    let letters = word
+        .trim_end_matches(|c: char| !c.is_alphanumeric())
+        .chars()
+        .collect::<Vec<_>>();
+    let is_pronoun_i = letters.first() == Some(&'I')
+        && (letters.len() == 1 || matches!(letters.get(1), Some('\'' | '\u{2019}')));
+    let capitalized = letters.first().is_some_and(|first| first.is_uppercase())
+        && !letters[1..].iter().any(|letter| letter.is_uppercase());
+    if !capitalized || is_pronoun_i {
+        return text.to_string();
+    }
+    let mut chars = rest.chars();
+    let first = chars
+        .next()
+        .map(|first| first.to_lowercase().collect::<String>());
+    format!("{space}{}{}", first.unwrap_or_default(), chars.as_str())
+}
@@ -0,0 +1,205 @@
+use std::io;
+use std::path::{Path, PathBuf};
+
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+#[cfg(feature = "desktop")]
+use tauri::AppHandle;
+
+use crate::asr::MODEL_SPEC;
+use crate::at_rest;
+use crate::errors::UserFacing;
+use crate::transcript::Transcript;
+use crate::vad::VadConfig;
+
+pub const DEFAULT_MAX_ENTRIES: usize = 256;
+pub const MIN_MAX_ENTRIES: usize = 16;
+pub const MAX_MAX_ENTRIES: usize = 10_000;
+
+#[cfg(feature = "desktop")]
+const CACHE_FILE: &str = "decode_cache.json";
+
+#[derive(thiserror::Error, Debug)]
+pub enum DecodeCacheError {
+    #[error("clear decode cache {path:?}: {source}")]
+    Clear {
+        path: PathBuf,
+        #[source]
+        source: io::Error,
+    },
+}
+
+impl UserFacing for DecodeCacheError {
+    fn user_message(&self) -> &'static str {
+        match self {
+            Self::Clear { .. } => {
+                "Could not delete the decode cache. Check the app's cache folder."
+            }
+        }
+    }
+}
+
+/// Whether file transcriptions are looked up by audio content before the
+/// model decodes them, and how many decodes are kept.
+#[derive(Clone, Copy, Debug, Deserialize, Eq, PartialEq, Serialize)]
+#[serde(default)]
+pub struct DecodeCacheSettings {
+    pub enabled: bool,
+    pub max_entries: usize,
+}
+
+impl Default for DecodeCacheSettings {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            max_entries: DEFAULT_MAX_ENTRIES,
+        }
+    }
+}
+
+impl DecodeCacheSettings {
+    pub fn clamped(self) -> Self {
+        Self {
+            max_entries: self.max_entries.clamp(MIN_MAX_ENTRIES, MAX_MAX_ENTRIES),
+            ..self
+        }
+    }
+}
+
+/// SHA-256 of the samples' bits, as hex. Identical audio always matches;
+/// the same speech recorded twice never does.
+pub fn audio_fingerprint(samples: &[f32]) -> String {
+    let mut hasher = Sha256::new();
+    for sample in samples {
+        hasher.update(sample.to_bits().to_le_bytes());
+    }
+    hex(&hasher.finalize())
+}
+
+/// What besides the audio decides a transcript: the model, its language
+/// prompt, and how the audio is segmented and stretched before decoding.
+/// Post-processing is not part of it, as it runs on the cached transcript.
+pub fn decode_context(
+    model_root: &Path,
+    language: &str,
+    vad: &VadConfig,
+    speed: Option<f32>,
+) -> String {
+    format!(
+        "{}@{} in {}; language {language}; {vad:?}; speed {speed:?}",
+        MODEL_SPEC.repository,
+        MODEL_SPEC.revision,
+        model_root.display()
+    )
+}
+
+/// The cache key for decoding audio with `fingerprint` under `context`.
+pub fn decode_key(fingerprint: &str, context: &str) -> String {
+    let mut hasher = Sha256::new();
+    hasher.update(context.as_bytes());
+    hasher.update([0]);
+    hasher.update(fingerprint.as_bytes());
+    hex(&hasher.finalize())
+}
+
+#[derive(Clone, Debug, Deserialize, PartialEq, Serialize)]
+pub struct CachedDecode {
+    pub key: String,
+    pub transcript: Transcript,
+}
+
+/// Raw transcripts by [`decode_key`], least recently used first.
+#[derive(Clone, Debug, Default, Deserialize, PartialEq, Serialize)]
+#[serde(default)]
+pub struct DecodeCache {
+    pub entries: Vec<CachedDecode>,
+}
+
+impl DecodeCache {
+    /// Reads the cache at `path`. A missing file is an empty cache, and so is
+    /// one that cannot be read, since anything in it can be decoded again.
+    pub fn load(path: &Path) -> Self {
+        match at_rest::read(path) {
+            Ok(bytes) => serde_json::from_slice(&bytes).unwrap_or_else(|error| {
+                log::warn!(
+                    "Ignoring unreadable decode cache {}: {error}",
+                    path.display()
+                );
+                Self::default()
+            }),
+            Err(error) if error.kind() == io::ErrorKind::NotFound => Self::default(),
+            Err(error) => {
+                log::warn!("Could not read decode cache {}: {error}", path.display());
+                Self::default()
+            }
+        }
+    }
+
+    /// Writes the cache to `path`, sealed while encryption at rest is on.
+    pub fn save(&self, path: &Path) -> io::Result<()> {
+        if let Some(parent) = path.parent() {
+            std::fs::create_dir_all(parent)?;
+        }
+        let json = serde_json::to_vec(self)
+            .map_err(|error| io::Error::new(io::ErrorKind::InvalidData, error))?;
+        at_rest::write(path, json)
+    }
+
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    /// The transcript stored under `key`, which becomes the most recently
+    /// used.
+    pub fn get(&mut self, key: &str) -> Option<Transcript> {
+        let position = self.entries.iter().position(|entry| entry.key == key)?;
+        let entry = self.entries.remove(position);
+        let transcript = entry.transcript.clone();
+        self.entries.push(entry);
+        Some(transcript)
+    }
+
+    /// Stores `transcript` as the most recently used, dropping the least
+    /// recently used beyond `max_entries`.
+    pub fn insert(&mut self, key: String, transcript: Transcript, max_entries: usize) {
+        self.entries.retain(|entry| entry.key != key);
+        self.entries.push(CachedDecode { key, transcript });
+        let excess = self.entries.len().saturating_sub(max_entries);
+        self.entries.drain(..excess);
+    }
+}
+
+fn hex(bytes: &[u8]) -> String {
+    bytes.iter().map(|byte| format!("{byte:02x}")).collect()
+}
+
+/// Where the cache lives: the app's cache folder, which the OS may clear.
+#[cfg(feature = "desktop")]
+pub fn cache_path(app: &AppHandle) -> Option<PathBuf> {
+    use tauri::Manager;
+    match app.path().app_cache_dir() {
+        Ok(dir) => Some(dir.join(CACHE_FILE)),
+        Err(error) => {
+            log::warn!("No cache folder for the decode cache: {error}");
+            None
+        }
+    }
+}
+
+/// Deletes every cached decode.
+#[cfg(feature = "desktop")]
+pub fn clear(app: &AppHandle) -> Result<(), DecodeCacheError> {
+    let Some(path) = cache_path(app) else {
+        return Ok(());
+    };
+    match std::fs::remove_file(&path) {
+        Err(source) if source.kind() != io::ErrorKind::NotFound => {
+            Err(DecodeCacheError::Clear { path, source })
+        }
+        _ => Ok(()),
+    }
+}
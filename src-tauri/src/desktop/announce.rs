@@ -0,0 +1,225 @@
+use std::io::Write;
+use std::process::{Command, Stdio};
+
+use tauri::{AppHandle, Emitter};
+
+use crate::errors::UserFacing;
+
+/// Read by the main window's live region on platforms whose screen readers are
+/// reached through the webview's accessibility tree rather than a command.
+const ANNOUNCEMENT_EVENT: &str = "screen_reader_announcement";
+
+#[derive(thiserror::Error, Debug)]
+pub enum AnnounceError {
+    #[error("no screen reader accepted the announcement: {0}")]
+    Unavailable(String),
+    #[error("emit announcement to the window: {0}")]
+    Emit(String),
+    #[error("no system voice could speak the transcript: {0}")]
+    NoVoice(String),
+}
+
+impl UserFacing for AnnounceError {
+    fn user_message(&self) -> &'static str {
+        match self {
+            Self::Unavailable(_) | Self::Emit(_) => {
+                "Could not announce the transcript. Check that your screen reader is running."
+            }
+            Self::NoVoice(_) => {
+                "Could not read the transcript aloud. Check that a system voice is installed."
+            }
+        }
+    }
+}
+
+/// A platform command that hands text to the screen reader or system voice.
+/// The text is either the last argument or written to stdin, so it is never
+/// parsed as script or markup.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct AnnounceCommand {
+    pub program: &'static str,
+    pub args: Vec<String>,
+    pub input: Option<String>,
+}
+
+impl AnnounceCommand {
+    #[cfg_attr(not(any(target_os = "macos", target_os = "linux")), allow(dead_code))]
+    fn new(program: &'static str, args: &[&str], text: &str) -> Self {
+        let mut args: Vec<String> = args.iter().map(|arg| arg.to_string()).collect();
+        args.push(text.to_string());
+        Self {
+            program,
+            args,
+            input: None,
+        }
+    }
+
+    #[cfg_attr(
+        not(any(target_os = "macos", target_os = "linux", target_os = "windows")),
+        allow(dead_code)
+    )]
+    fn piped(program: &'static str, args: &[&str], text: &str) -> Self {
+        Self {
+            program,
+            args: args.iter().map(|arg| arg.to_string()).collect(),
+            input: Some(text.to_string()),
+        }
+    }
+
+    fn run(&self) -> Result<(), String> {
+        let mut command = Command::new(self.program);
+        command
+            .args(&self.args)
+            .stdin(if self.input.is_some() {
+                Stdio::piped()
+            } else {
+                Stdio::null()
+            })
+            .stdout(Stdio::null())
+            .stderr(Stdio::null());
+        #[cfg(windows)]
+        {
+            use std::os::windows::process::CommandExt;
+            const CREATE_NO_WINDOW: u32 = 0x0800_0000;
+            command.creation_flags(CREATE_NO_WINDOW);
+        }
+        let mut child = command
+            .spawn()
+            .map_err(|error| format!("run {}: {error}", self.program))?;
+        // Dropping stdin after the write closes it, so the program sees EOF.
+        let written = match (&self.input, child.stdin.take()) {
+            (Some(input), Some(mut stdin)) => stdin.write_all(input.as_bytes()),
+            _ => Ok(()),
+        };
+        let status = child
+            .wait()
+            .map_err(|error| format!("wait for {}: {error}", self.program))?;
+        written.map_err(|error| format!("write to {}: {error}", self.program))?;
+        if status.success() {
+            Ok(())
+        } else {
+            Err(format!("{} exited with {status}", self.program))
+        }
+    }
+}
+
+/// Commands tried in order until one succeeds. VoiceOver is scripted through
+/// its AppleScript `output` verb; on Linux, Orca's D-Bus service presents the
+/// message, with speech-dispatcher (which Orca speaks through) as a fallback.
+/// Windows has no such command and uses the window's live region instead.
+pub fn announce_commands(text: &str) -> Vec<AnnounceCommand> {
+    #[cfg(target_os = "macos")]
+    {
+        vec![AnnounceCommand::new(
+            "osascript",
+            &[
+                "-e",
+                "on run argv",
+                "-e",
+                r#"if application "VoiceOver" is not running then error "VoiceOver is not running""#,
+                "-e",
+                r#"tell application "VoiceOver" to output (item 1 of argv)"#,
+                "-e",
+                "end run",
+            ],
+            text,
+        )]
+    }
+    #[cfg(target_os = "linux")]
+    {
+        vec![
+            AnnounceCommand::new(
+                "busctl",
+                &[
+                    "--user",
+                    "call",
+                    "org.gnome.Orca.Service",
+                    "/org/gnome/Orca/Service",
+                    "org.gnome.Orca.Service",
+                    "PresentMessage",
+                    "s",
+                ],
+                text,
+            ),
+            AnnounceCommand::new("spd-say", &["--"], text),
+        ]
+    }
+    #[cfg(not(any(target_os = "macos", target_os = "linux")))]
+    {
+        let _ = text;
+        Vec::new()
+    }
+}
+
+/// Commands that read text aloud with the system voice, independent of any
+/// screen reader, tried in order until one succeeds.
+pub fn speech_commands(text: &str) -> Vec<AnnounceCommand> {
+    #[cfg(target_os = "macos")]
+    {
+        vec![AnnounceCommand::piped("say", &["-f", "-"], text)]
+    }
+    #[cfg(target_os = "linux")]
+    {
+        vec![
+            AnnounceCommand::piped("spd-say", &["-e", "-w"], text),
+            AnnounceCommand::piped("espeak-ng", &["--stdin"], text),
+        ]
+    }
+    #[cfg(target_os = "windows")]
+    {
+        vec![AnnounceCommand::piped(
+            "powershell",
+            &[
+                "-NoProfile",
+                "-NonInteractive",
+                "-Command",
+                "[Console]::InputEncoding = [Text.Encoding]::UTF8; \
+                 Add-Type -AssemblyName System.Speech; \
+                 (New-Object System.Speech.Synthesis.SpeechSynthesizer).Speak([Console]::In.ReadToEnd())",
+            ],
+            text,
+        )]
+    }
+    #[cfg(not(any(target_os = "macos", target_os = "linux", target_os = "windows")))]
+    {
+        let _ = text;
+        Vec::new()
+    }
+}
+
+fn run_first(commands: &[AnnounceCommand]) -> Result<(), String> {
+    let mut failures = Vec::new();
+    for command in commands {
+        match command.run() {
+            Ok(()) => return Ok(()),
+            Err(failure) => failures.push(failure),
+        }
+    }
+    Err(failures.join("; "))
+}
+
+/// Sends `text` to the platform screen reader. On Windows the window's live
+/// region raises it as a UI Automation notification through WebView2.
+pub(crate) fn announce(app: &AppHandle, text: &str) -> Result<(), AnnounceError> {
+    let commands = announce_commands(text);
+    if commands.is_empty() {
+        return app
+            .emit(ANNOUNCEMENT_EVENT, text)
+            .map_err(|error| AnnounceError::Emit(error.to_string()));
+    }
+    run_first(&commands).map_err(AnnounceError::Unavailable)
+}
+
+/// Reads `text` aloud with the system voice, waiting until it has been spoken.
+/// The microphone is muted meanwhile so a recording started during read-back
+/// does not pick up the voice.
+pub(crate) fn speak(text: &str) -> Result<(), AnnounceError> {
+    let commands = speech_commands(text);
+    if commands.is_empty() {
+        return Err(AnnounceError::NoVoice(
+            "no speech command on this platform".to_string(),
+        ));
+    }
+    let _playback = crate::recording::app_playback().begin();
+    run_first(&commands).map_err(AnnounceError::NoVoice)
+}
@@ -0,0 +1,43 @@
+use tauri::{AppHandle, Manager};
+
+use crate::autostart::AutostartError;
+
+/// Brings the settings window forward, e.g. from the tray or when the app is
+/// launched while already running.
+pub(crate) fn show_main_window(app: &AppHandle) {
+    let Some(window) = app.get_webview_window("main") else {
+        log::warn!("Main window is missing");
+        return;
+    };
+    if let Err(error) = window.show() {
+        log::warn!("Could not show main window: {error}");
+    }
+    if let Err(error) = window.set_focus() {
+        log::debug!("Could not focus main window: {error}");
+    }
+}
+
+/// Registers or removes the login item to match the saved setting.
+pub(crate) fn sync_autostart(app: &AppHandle) -> Result<(), AutostartError> {
+    let enabled = crate::settings::get_settings(app).autostart.enabled;
+    let executable = std::env::current_exe().map_err(AutostartError::Executable)?;
+    crate::autostart::register(&app.config().identifier, &executable, enabled)
+}
+
+/// The window starts hidden so a launch at login never flashes it; every
+/// other launch shows it here. An enabled login item is rewritten so it keeps
+/// pointing at this executable after the app moves or updates.
+pub(super) fn init_autostart(app: &AppHandle) {
+    let settings = crate::settings::get_settings(app).autostart;
+    if settings.enabled {
+        if let Err(error) = sync_autostart(app) {
+            log::warn!("Could not refresh login item: {error}");
+        }
+    }
+    let args = std::env::args().collect::<Vec<_>>();
+    if settings.starts_hidden(&args) {
+        log::info!("Launched at login; staying in the tray");
+        return;
+    }
+    show_main_window(app);
+}
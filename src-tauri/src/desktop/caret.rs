@@ -0,0 +1,120 @@
+use std::io::Read;
+use std::process::{Command, Stdio};
+use std::thread;
+use std::time::{Duration, Instant};
+
+/// Longest an accessibility query may run. Focus is queried before each
+/// typed chunk, so a stalled System Events or X server must not hold up
+/// typing.
+const QUERY_TIMEOUT: Duration = Duration::from_secs(2);
+const QUERY_POLL: Duration = Duration::from_millis(10);
+
+/// Asks the accessibility API for the character left of the caret in the
+/// focused field, through System Events on macOS and UI Automation on
+/// Windows. Returns `None` at the start of a field, when the app exposes no
+/// caret, or where no query exists (Linux).
+pub(crate) fn char_before_caret() -> Option<char> {
+    query_output(caret_query()?)?.chars().last()
+}
+
+/// Runs an accessibility query without a console window and returns its
+/// output without the trailing line break, or `None` when it fails or takes
+/// longer than [`QUERY_TIMEOUT`].
+pub(super) fn query_output(mut command: Command) -> Option<String> {
+    command
+        .stdin(Stdio::null())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::null());
+    #[cfg(windows)]
+    {
+        use std::os::windows::process::CommandExt;
+        const CREATE_NO_WINDOW: u32 = 0x0800_0000;
+        command.creation_flags(CREATE_NO_WINDOW);
+    }
+    let mut child = match command.spawn() {
+        Ok(child) => child,
+        Err(error) => {
+            log::debug!("Accessibility query failed to run: {error}");
+            return None;
+        }
+    };
+    // Answers are a name, an id, or a character, well within the pipe's
+    // buffer, so the query never blocks on output before it exits.
+    let started = Instant::now();
+    let status = loop {
+        match child.try_wait() {
+            Ok(Some(status)) => break status,
+            Ok(None) if started.elapsed() < QUERY_TIMEOUT => thread::sleep(QUERY_POLL),
+            Ok(None) => {
+                log::debug!("Accessibility query timed out");
+                let _ = child.kill();
+                let _ = child.wait();
+                return None;
+            }
+            Err(error) => {
+                log::debug!("Accessibility query could not be waited on: {error}");
+                let _ = child.kill();
+                return None;
+            }
+        }
+    };
+    if !status.success() {
+        log::debug!("Accessibility query exited with {status}");
+        return None;
+    }
+    let mut output = Vec::new();
+    child.stdout.take()?.read_to_end(&mut output).ok()?;
+    Some(
+        String::from_utf8_lossy(&output)
+            .trim_end_matches(['\r', '\n'])
+            .to_string(),
+    )
+}
+
+#[cfg(target_os = "macos")]
+fn caret_query() -> Option<Command> {
+    // System Events reports an empty selection at offset n as {n + 1, n}.
+    let mut command = Command::new("osascript");
+    command.args([
+        "-e",
+        "tell application \"System Events\"",
+        "-e",
+        "set focusedField to value of attribute \"AXFocusedUIElement\" of (first application process whose frontmost is true)",
+        "-e",
+        "set caretStart to item 1 of (value of attribute \"AXSelectedTextRange\" of focusedField)",
+        "-e",
+        "if caretStart > 1 then return character (caretStart - 1) of (value of attribute \"AXValue\" of focusedField)",
+        "-e",
+        "end tell",
+    ]);
+    Some(command)
+}
+
+#[cfg(target_os = "windows")]
+fn caret_query() -> Option<Command> {
+    let mut command = Command::new("powershell");
+    command.args([
+        "-NoProfile",
+        "-NonInteractive",
+        "-Command",
+        "[Console]::OutputEncoding = [Text.Encoding]::UTF8; \
+         Add-Type -AssemblyName UIAutomationClient, UIAutomationTypes; \
+         $field = [Windows.Automation.AutomationElement]::FocusedElement; \
+         $pattern = $null; \
+         if ($field.TryGetCurrentPattern([Windows.Automation.TextPattern]::Pattern, [ref]$pattern)) { \
+           $caret = $pattern.GetSelection()[0]; \
+           $before = $caret.Clone(); \
+           $start = [Windows.Automation.Text.TextPatternRangeEndpoint]::Start; \
+           $end = [Windows.Automation.Text.TextPatternRangeEndpoint]::End; \
+           [void]$before.MoveEndpointByRange($end, $caret, $start); \
+           [void]$before.MoveEndpointByUnit($start, [Windows.Automation.Text.TextUnit]::Character, -1); \
+           [Console]::Out.Write($before.GetText(1)) \
+         }",
+    ]);
+    Some(command)
+}
+
+#[cfg(not(any(target_os = "macos", target_os = "windows")))]
+fn caret_query() -> Option<Command> {
+    None
+}
@@ -0,0 +1,164 @@
+use std::fs::OpenOptions;
+use std::io::{self, Write};
+use std::path::{Path, PathBuf};
+
+use tauri::{AppHandle, Manager};
+
+use super::shortcuts::{report_failure, start_command_recording, stop_command_recording};
+use super::tray::apply_profile;
+#[cfg(target_os = "macos")]
+use crate::cli::parse_deep_link;
+use crate::cli::{output_path, parse_cli, CliCommand};
+use crate::engine::SpeechEngine;
+use crate::recording::RecordingError;
+use crate::word_timings::{estimated_word_timings, to_audacity_labels, to_json};
+
+/// Numbered output names tried before giving up on a crowded folder.
+const MAX_TRANSCRIPT_ATTEMPTS: u32 = 100;
+
+/// Runs the commands on a command line, from this launch or forwarded by a
+/// second one. Returns false when there were none to run, so the caller can
+/// fall back to showing the window.
+pub(crate) fn run_command_line<S: AsRef<str>>(app: &AppHandle, args: &[S], cwd: &Path) -> bool {
+    let deep_links = crate::settings::get_settings(app).deep_links_enabled;
+    let commands = match parse_cli(args, cwd, deep_links) {
+        Ok(commands) => commands,
+        Err(error) => {
+            log::warn!("Ignoring command line: {error}");
+            return false;
+        }
+    };
+    let ran = !commands.is_empty();
+    run_commands(app, commands);
+    ran
+}
+
+/// Runs a link the running app was handed directly rather than on a command
+/// line, as macOS does.
+#[cfg(target_os = "macos")]
+pub(crate) fn run_deep_link(app: &AppHandle, link: &str) {
+    log::info!("Deep link opened ({link})");
+    if !crate::settings::get_settings(app).deep_links_enabled {
+        log::warn!("Ignoring deep link: links from other apps are turned off");
+        return;
+    }
+    match parse_deep_link(link) {
+        Ok(commands) => run_commands(app, commands),
+        Err(error) => log::warn!("Ignoring deep link: {error}"),
+    }
+}
+
+/// Switching profiles may reconfigure the speech engine, so commands run in
+/// order on a worker. A failed switch drops the commands after it, so a
+/// recording asked for under one profile never starts under another.
+fn run_commands(app: &AppHandle, commands: Vec<CliCommand>) {
+    if commands.is_empty() {
+        return;
+    }
+    let worker_app = app.clone();
+    let result = std::thread::Builder::new()
+        .name("cli-commands".to_string())
+        .spawn(move || {
+            for command in &commands {
+                if !run_command(&worker_app, command) {
+                    break;
+                }
+            }
+        });
+    if let Err(error) = result {
+        report_failure(
+            app,
+            "Failed to start command line worker",
+            &RecordingError::ThreadStart(error),
+        );
+    }
+}
+
+/// Returns false when the commands after this one should not run.
+fn run_command(app: &AppHandle, command: &CliCommand) -> bool {
+    let dictating = app.state::<SpeechEngine>().is_dictating();
+    match command {
+        CliCommand::StartRecording => start_command_recording(app, None),
+        CliCommand::StartRecordingIn(mode) => start_command_recording(app, Some(*mode)),
+        CliCommand::StopRecording => stop_command_recording(app),
+        CliCommand::ToggleRecording | CliCommand::ToggleRecordingIn(_) if dictating => {
+            stop_command_recording(app)
+        }
+        CliCommand::ToggleRecording => start_command_recording(app, None),
+        CliCommand::ToggleRecordingIn(mode) => start_command_recording(app, Some(*mode)),
+        CliCommand::SwitchProfile(name) => return apply_profile(app, name),
+        CliCommand::TranscribeFile(path) => transcribe_file_async(app, path.clone(), false),
+        CliCommand::ExportWordTimings(path) => transcribe_file_async(app, path.clone(), true),
+    }
+    true
+}
+
+/// Transcribes `path` on a worker and writes its text, or with
+/// `export_word_timings` its words' estimated times as JSON and an Audacity
+/// label track.
+fn transcribe_file_async(app: &AppHandle, path: PathBuf, export_word_timings: bool) {
+    let report_app = app.clone();
+    let worker_app = app.clone();
+    let result = std::thread::Builder::new()
+        .name("cli-transcribe".to_string())
+        .spawn(move || {
+            log::info!("Command line -> Transcribing {path:?}");
+            let engine = worker_app.state::<SpeechEngine>();
+            let (text, transcript) = match engine.transcribe_file_segments(&path) {
+                Ok(transcribed) => transcribed,
+                Err(error) => {
+                    report_failure(&worker_app, "Failed to transcribe file", &error);
+                    return;
+                }
+            };
+            let outputs = if export_word_timings {
+                let words = estimated_word_timings(&transcript);
+                vec![
+                    (
+                        "words.json",
+                        to_json(transcript.language.as_deref(), &words),
+                    ),
+                    ("labels.txt", to_audacity_labels(&words)),
+                ]
+            } else {
+                vec![("txt", format!("{text}\n"))]
+            };
+            for (extension, contents) in outputs {
+                match write_output(&path, extension, &contents) {
+                    Ok(output) => log::info!("Wrote {extension} output of {path:?} to {output:?}"),
+                    Err(error) => {
+                        log::error!("Could not write {extension} output of {path:?}: {error}")
+                    }
+                }
+            }
+        });
+    if let Err(error) = result {
+        report_failure(
+            &report_app,
+            "Failed to start file transcription worker",
+            &RecordingError::ThreadStart(error),
+        );
+    }
+}
+
+fn write_output(audio: &Path, extension: &str, contents: &str) -> io::Result<PathBuf> {
+    for attempt in 1..=MAX_TRANSCRIPT_ATTEMPTS {
+        let output = output_path(audio, extension, attempt);
+        match OpenOptions::new()
+            .write(true)
+            .create_new(true)
+            .open(&output)
+        {
+            Ok(mut file) => {
+                file.write_all(contents.as_bytes())?;
+                return Ok(output);
+            }
+            Err(error) if error.kind() == io::ErrorKind::AlreadyExists => continue,
+            Err(error) => return Err(error),
+        }
+    }
+    Err(io::Error::new(
+        io::ErrorKind::AlreadyExists,
+        "every output name is taken",
+    ))
+}
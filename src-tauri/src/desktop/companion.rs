@@ -0,0 +1,280 @@
+use std::io::{self, BufRead, BufReader, Read, Write};
+use std::net::TcpStream;
+use std::process::{Child, Command, Stdio};
+use std::sync::{mpsc, Mutex, OnceLock};
+use std::thread;
+
+use tauri::{AppHandle, Emitter};
+
+use super::network_input::{configure_connection, stream_dictation, Listener};
+use crate::companion::{
+    encode_server_message, new_device, parse_client_message, pending_pairing_code,
+    redeem_pairing_code, Challenge, ClientMessage, CompanionError, SealedReader, SealedWriter,
+    ServerMessage, MAX_MESSAGE_BYTES, PROTOCOL_VERSION, SERVICE_TYPE,
+};
+use crate::errors::UserFacing;
+
+/// Tells the settings window to refresh its device list.
+const PAIRED_EVENT: &str = "companion_paired";
+
+static SERVICE: OnceLock<Mutex<Option<CompanionService>>> = OnceLock::new();
+
+struct CompanionService {
+    listener: Listener,
+    advertisement: Option<Advertisement>,
+}
+
+impl CompanionService {
+    fn shut_down(self) {
+        drop(self.advertisement);
+        self.listener.shut_down();
+    }
+}
+
+/// A DNS-SD registration held by the system responder for as long as the
+/// child process runs.
+struct Advertisement(Child);
+
+impl Advertisement {
+    /// Registers through the platform responder: Bonjour's `dns-sd` on macOS
+    /// and Avahi on Linux. Windows has no bundled command-line responder, so
+    /// companions there connect by address and port.
+    fn publish(port: u16) -> Option<Self> {
+        let instance = format!("SilentKeys on {}", host_name());
+        let port = port.to_string();
+        let txt = format!("proto={PROTOCOL_VERSION}");
+        let mut command = if cfg!(target_os = "macos") {
+            let mut command = Command::new("dns-sd");
+            command.args(["-R", &instance, SERVICE_TYPE, "local", &port, &txt]);
+            command
+        } else if cfg!(target_os = "linux") {
+            let mut command = Command::new("avahi-publish");
+            command.args(["-s", &instance, SERVICE_TYPE, &port, &txt]);
+            command
+        } else {
+            return None;
+        };
+        let spawned = command
+            .stdin(Stdio::null())
+            .stdout(Stdio::null())
+            .stderr(Stdio::null())
+            .spawn();
+        match spawned {
+            Ok(child) => Some(Self(child)),
+            Err(error) => {
+                log::warn!("Could not advertise companion service: {error}");
+                None
+            }
+        }
+    }
+}
+
+impl Drop for Advertisement {
+    fn drop(&mut self) {
+        let _ = self.0.kill();
+        let _ = self.0.wait();
+    }
+}
+
+fn host_name() -> String {
+    ["HOSTNAME", "COMPUTERNAME"]
+        .iter()
+        .find_map(|key| std::env::var(key).ok())
+        .filter(|name| !name.trim().is_empty())
+        .unwrap_or_else(|| "this computer".to_string())
+}
+
+fn service() -> &'static Mutex<Option<CompanionService>> {
+    SERVICE.get_or_init(|| Mutex::new(None))
+}
+
+/// Starts, moves, or stops the companion listener and its advertisement to
+/// match the saved settings.
+pub(crate) fn sync_companion(app: &AppHandle) -> Result<(), CompanionError> {
+    let settings = crate::settings::get_settings(app).companion;
+    let wanted = settings.enabled.then_some(settings.port);
+    let Ok(mut active) = service().lock() else {
+        log::warn!("Companion listener state is unavailable");
+        return Ok(());
+    };
+    if active.as_ref().map(|service| service.listener.port()) == wanted {
+        return Ok(());
+    }
+    if let Some(previous) = active.take() {
+        previous.shut_down();
+    }
+    let Some(port) = wanted else {
+        return Ok(());
+    };
+
+    let socket = crate::companion::listen(port)?;
+    let listener = Listener::spawn(app, socket, port, "companion", serve_companion)
+        .map_err(|source| CompanionError::Bind { port, source })?;
+    log::info!("Listening for companion devices on port {port}");
+    *active = Some(CompanionService {
+        listener,
+        advertisement: Advertisement::publish(port),
+    });
+    Ok(())
+}
+
+pub(super) fn init_companion(app: &AppHandle) {
+    if let Err(error) = sync_companion(app) {
+        log::warn!("Companion devices unavailable: {error}");
+    }
+}
+
+fn send(writer: &mut impl Write, message: &ServerMessage) -> io::Result<()> {
+    writer.write_all(encode_server_message(message).as_bytes())
+}
+
+/// Reads the handshake line. Audio frames that follow may already be
+/// buffered, so the same reader carries on with the dictation.
+fn read_handshake(reader: &mut BufReader<TcpStream>) -> Result<ClientMessage, CompanionError> {
+    let mut line = Vec::new();
+    reader
+        .take(MAX_MESSAGE_BYTES as u64)
+        .read_until(b'\n', &mut line)
+        .map_err(CompanionError::Connection)?;
+    if line.last() != Some(&b'\n') {
+        return Err(CompanionError::InvalidMessage(
+            "handshake line is missing or too long".to_string(),
+        ));
+    }
+    let line = String::from_utf8(line)
+        .map_err(|error| CompanionError::InvalidMessage(error.to_string()))?;
+    parse_client_message(&line)
+}
+
+fn pair(
+    app: &AppHandle,
+    challenge: &mut Challenge,
+    device_name: &str,
+    pake: &str,
+    proof: &str,
+) -> Result<ServerMessage, CompanionError> {
+    let (challenge_code, pairing_key) = challenge.pairing_key(pake)?;
+    // A code replaced since the challenge was sent cannot be proven with it.
+    redeem_pairing_code(|code| code == challenge_code && pairing_key.proves(device_name, proof))?;
+    let paired_at = chrono::Local::now().to_rfc3339();
+    let (device, device_key) = new_device(device_name, &paired_at)?;
+    let sealed_key = pairing_key.seal_device_key(&device_key)?;
+    let device_id = device.id.clone();
+    super::keychain::store_companion_key(&device_id, &device_key)?;
+    log::info!("Paired companion device {} ({device_id})", device.name);
+    if let Err(error) = crate::settings::add_companion_device(app, device) {
+        let _ = super::keychain::forget_companion_key(&device_id);
+        return Err(CompanionError::Store(error.to_string()));
+    }
+    if let Err(error) = app.emit(PAIRED_EVENT, &device_id) {
+        log::warn!("Could not notify the window about a paired device: {error}");
+    }
+    Ok(ServerMessage::Paired {
+        device_id,
+        sealed_key,
+    })
+}
+
+/// Checks a `hello` against the key stored when the device paired.
+fn authenticate(
+    app: &AppHandle,
+    challenge: &Challenge,
+    device_id: &str,
+    proof: &str,
+) -> Result<Vec<u8>, CompanionError> {
+    let devices = crate::settings::get_settings(app).companion.devices;
+    let key = super::keychain::companion_key(device_id)?.ok_or(CompanionError::Unauthorized)?;
+    let device = challenge.authenticate(&devices, device_id, &key, proof)?;
+    log::info!("Companion device {} connected", device.name);
+    Ok(key)
+}
+
+/// Streams one dictation for an authenticated device, sending transcript
+/// updates back on `writer` as they arrive.
+fn dictate(
+    app: &AppHandle,
+    reader: &mut impl Read,
+    mut writer: impl Write + Send + 'static,
+) -> io::Result<()> {
+    send(&mut writer, &ServerMessage::Ready)?;
+    let (updates_tx, updates_rx) = mpsc::channel();
+    let forwarder = thread::Builder::new()
+        .name("companion-transcript".to_string())
+        .spawn(move || {
+            for update in updates_rx {
+                if let Err(error) = send(&mut writer, &ServerMessage::Transcript { update }) {
+                    log::warn!("Could not send transcript to companion: {error}");
+                    break;
+                }
+            }
+            writer
+        })?;
+    let started = stream_dictation(app, reader, Some(updates_tx));
+    // The recorder drops its observer when the dictation ends, which ends
+    // the forwarder; a dictation that never started drops it immediately.
+    let mut writer = forwarder
+        .join()
+        .map_err(|_| io::Error::other("transcript forwarder panicked"))?;
+    if !started {
+        send(
+            &mut writer,
+            &ServerMessage::Error {
+                message: "Dictation is busy or unavailable on the desktop.".to_string(),
+            },
+        )?;
+    }
+    Ok(())
+}
+
+fn serve_companion(app: &AppHandle, stream: TcpStream) {
+    let result = configure_connection(&stream)
+        .and_then(|()| Ok((stream.try_clone()?, BufReader::new(stream))));
+    let (mut writer, mut reader) = match result {
+        Ok(halves) => halves,
+        Err(error) => {
+            log::warn!("Could not configure companion connection: {error}");
+            return;
+        }
+    };
+
+    let pairing_code = pending_pairing_code();
+    let outcome = Challenge::generate(pairing_code.as_deref()).and_then(|mut challenge| {
+        send(&mut writer, &challenge.message()).map_err(CompanionError::Connection)?;
+        match read_handshake(&mut reader)? {
+            ClientMessage::Pair {
+                device_name,
+                pake,
+                proof,
+            } => {
+                let reply = pair(app, &mut challenge, &device_name, &pake, &proof)?;
+                send(&mut writer, &reply).map_err(CompanionError::Connection)
+            }
+            ClientMessage::Hello { device_id, proof } => {
+                let key = authenticate(app, &challenge, &device_id, &proof)?;
+                let (opener, sealer) = challenge.server_session(&key);
+                let writer = writer.try_clone().map_err(CompanionError::Connection)?;
+                dictate(
+                    app,
+                    &mut SealedReader::new(&mut reader, opener),
+                    SealedWriter::new(writer, sealer),
+                )
+                .map_err(CompanionError::Connection)
+            }
+        }
+    });
+    match outcome {
+        Ok(()) => {}
+        // The session is sealed or the connection is gone, so there is no
+        // one to send a plain error to.
+        Err(error @ CompanionError::Connection(_)) => {
+            log::warn!("Companion connection failed: {error}");
+        }
+        Err(error) => {
+            log::warn!("Companion connection failed: {error}");
+            let reply = ServerMessage::Error {
+                message: error.user_message().to_string(),
+            };
+            let _ = send(&mut writer, &reply);
+        }
+    }
+}
@@ -0,0 +1,162 @@
+use std::io;
+use std::path::PathBuf;
+use std::sync::Mutex;
+use std::thread;
+use std::time::Duration;
+
+use serde::Deserialize;
+use tauri::{AppHandle, Emitter, Manager, WebviewUrl, WebviewWindowBuilder};
+
+use super::typing::{inject_text, TypeTextError, TypeTextOptions};
+use crate::errors::UserFacing;
+use crate::streaming::{Transcript, TranscriptPatch};
+use crate::sync::MutexRecover;
+
+const DOCUMENT_WINDOW: &str = "document";
+const DOCUMENT_UPDATED_EVENT: &str = "document_updated";
+/// Lets the app behind the editor take focus back before typing starts.
+const FOCUS_SETTLE: Duration = Duration::from_millis(200);
+
+/// The open document, if any. While one is open, dictations that would be
+/// typed are added to it instead.
+static DOCUMENT: Mutex<Option<String>> = Mutex::new(None);
+
+#[derive(thiserror::Error, Debug)]
+pub enum DocumentError {
+    #[error("no document is open")]
+    NotOpen,
+    #[error("open document window: {0}")]
+    Window(String),
+    #[error("write document {path:?}: {source}")]
+    Write {
+        path: PathBuf,
+        #[source]
+        source: io::Error,
+    },
+    #[error(transparent)]
+    Typing(#[from] TypeTextError),
+}
+
+impl UserFacing for DocumentError {
+    fn user_message(&self) -> &'static str {
+        match self {
+            Self::NotOpen => "No document is open. Start one from the settings.",
+            Self::Window(_) => "Could not open the document window.",
+            Self::Write { .. } => {
+                "Could not save the document. Check that the folder exists and is writable."
+            }
+            Self::Typing(error) => error.user_message(),
+        }
+    }
+}
+
+/// What to do with a document when the user is done with it.
+#[derive(Clone, Debug, Deserialize, Eq, PartialEq)]
+#[serde(tag = "action", rename_all = "snake_case")]
+pub enum DocumentFinish {
+    /// Type it into the app focused behind the editor, without heading
+    /// markers.
+    Type,
+    /// Write it to `path` as Markdown.
+    Save {
+        path: String,
+    },
+    Discard,
+}
+
+/// Starts a document, or keeps the open one, and shows the editor.
+pub(crate) fn open_document(app: &AppHandle) -> Result<(), DocumentError> {
+    DOCUMENT.lock_recover().get_or_insert_with(String::new);
+    if let Some(window) = app.get_webview_window(DOCUMENT_WINDOW) {
+        window
+            .show()
+            .map_err(|error| DocumentError::Window(error.to_string()))?;
+        if let Err(error) = window.set_focus() {
+            log::debug!("Could not focus document window: {error}");
+        }
+        return Ok(());
+    }
+    WebviewWindowBuilder::new(app, DOCUMENT_WINDOW, WebviewUrl::default())
+        .title("SilentKeys Document")
+        .inner_size(640.0, 720.0)
+        .build()
+        .map_err(|error| DocumentError::Window(error.to_string()))?;
+    Ok(())
+}
+
+pub(crate) fn document_text() -> Option<String> {
+    DOCUMENT.lock_recover().clone()
+}
+
+/// Replaces the document with the editor's text.
+pub(crate) fn set_document_text(text: String) -> Result<(), DocumentError> {
+    let mut document = DOCUMENT.lock_recover();
+    let document = document.as_mut().ok_or(DocumentError::NotOpen)?;
+    *document = text;
+    Ok(())
+}
+
+/// Takes a dictation's patches while a document is open: the final text is
+/// added to the document and nothing is typed. Returns false when no document
+/// is open, leaving the patch to be typed.
+pub(crate) fn capture_patch(
+    app: &AppHandle,
+    patch: &TranscriptPatch,
+    transcript: &Transcript,
+) -> bool {
+    let mut document = DOCUMENT.lock_recover();
+    let Some(document) = document.as_mut() else {
+        return false;
+    };
+    if let TranscriptPatch::Replace { .. } = patch {
+        crate::document::append_dictation(document, transcript.committed());
+        if let Err(error) = app.emit(DOCUMENT_UPDATED_EVENT, document.as_str()) {
+            log::warn!("Could not emit {DOCUMENT_UPDATED_EVENT}: {error}");
+        }
+    }
+    true
+}
+
+/// Types, saves, or discards the document, then closes it. The document stays
+/// open if typing or saving fails.
+pub(crate) fn finish_document(
+    app: &AppHandle,
+    finish: DocumentFinish,
+) -> Result<(), DocumentError> {
+    let text = document_text().ok_or(DocumentError::NotOpen)?;
+    let window = app.get_webview_window(DOCUMENT_WINDOW);
+    match finish {
+        DocumentFinish::Type => {
+            if let Some(window) = &window {
+                if let Err(error) = window.hide() {
+                    log::warn!("Could not hide document window: {error}");
+                }
+            }
+            thread::sleep(FOCUS_SETTLE);
+            let settings = crate::settings::get_settings(app);
+            let typed = inject_text(
+                &settings,
+                crate::document::plain_text(&text),
+                &TypeTextOptions::default(),
+            );
+            if let Err(error) = typed {
+                if let Some(window) = &window {
+                    let _ = window.show();
+                }
+                return Err(error.into());
+            }
+        }
+        DocumentFinish::Save { path } => {
+            let path = PathBuf::from(path);
+            std::fs::write(&path, &text).map_err(|source| DocumentError::Write { path, source })?;
+        }
+        DocumentFinish::Discard => {}
+    }
+    DOCUMENT.lock_recover().take();
+    if let Some(window) = window {
+        if let Err(error) = window.destroy() {
+            log::warn!("Could not close document window: {error}");
+        }
+    }
+    Ok(())
+}
@@ -0,0 +1,187 @@
+#[cfg(not(target_os = "windows"))]
+use std::process::Command;
+
+#[cfg(not(target_os = "windows"))]
+use super::caret::query_output;
+
+/// The name of the app that owns the focused window, used to keep typing
+/// context apart per app. `None` when the platform offers no query.
+pub(crate) fn focused_app() -> Option<String> {
+    #[cfg(target_os = "windows")]
+    let name = win32::focused_app();
+    #[cfg(not(target_os = "windows"))]
+    let name = query_output(focus_query()?);
+    name.filter(|name| !name.trim().is_empty())
+}
+
+/// An id for the focused window, for keeping typed output in the window a
+/// recording started in: the frontmost app on macOS, the window handle on
+/// Windows, and the X11 window id on Linux.
+pub(crate) fn focused_window() -> Option<String> {
+    #[cfg(target_os = "windows")]
+    let id = win32::focused_window();
+    #[cfg(not(target_os = "windows"))]
+    let id = query_output(window_query()?);
+    id.filter(|id| !id.trim().is_empty())
+}
+
+/// Brings the window from [`focused_window`] back to the front. Returns
+/// whether the platform accepted the request.
+pub(crate) fn activate_window(id: &str) -> bool {
+    #[cfg(target_os = "windows")]
+    {
+        win32::activate_window(id)
+    }
+    #[cfg(not(target_os = "windows"))]
+    {
+        activate_command(id).and_then(query_output).is_some()
+    }
+}
+
+/// Focus on Windows is read and set in process. A helper process would cost
+/// a PowerShell start per typed chunk, and Windows refuses foreground changes
+/// asked for by a process that did not receive the last input.
+#[cfg(target_os = "windows")]
+mod win32 {
+    use std::ffi::OsString;
+    use std::os::windows::ffi::OsStringExt;
+    use std::path::Path;
+
+    use windows_sys::Win32::Foundation::{CloseHandle, HWND};
+    use windows_sys::Win32::System::Threading::{
+        OpenProcess, QueryFullProcessImageNameW, PROCESS_NAME_WIN32,
+        PROCESS_QUERY_LIMITED_INFORMATION,
+    };
+    use windows_sys::Win32::UI::WindowsAndMessaging::{
+        GetForegroundWindow, GetWindowThreadProcessId, IsIconic, SetForegroundWindow, ShowWindow,
+        SW_RESTORE,
+    };
+
+    /// The focused window's process name without its extension, as
+    /// `Get-Process` reports it.
+    pub(super) fn focused_app() -> Option<String> {
+        let mut process_id = 0_u32;
+        // SAFETY: both calls accept any window handle, null included, and
+        // `process_id` outlives the call that writes it.
+        let thread_id = unsafe { GetWindowThreadProcessId(GetForegroundWindow(), &mut process_id) };
+        if thread_id == 0 || process_id == 0 {
+            return None;
+        }
+        // SAFETY: a null handle is checked for before use, and the handle
+        // is closed exactly once below.
+        let process = unsafe { OpenProcess(PROCESS_QUERY_LIMITED_INFORMATION, 0, process_id) };
+        if process.is_null() {
+            return None;
+        }
+        let mut path = [0_u16; 1024];
+        let mut len = path.len() as u32;
+        // SAFETY: `len` holds the buffer's length in UTF-16 units, and the
+        // call writes at most that many.
+        let queried = unsafe {
+            QueryFullProcessImageNameW(process, PROCESS_NAME_WIN32, path.as_mut_ptr(), &mut len)
+        };
+        // SAFETY: `process` was opened above and is not used after this.
+        unsafe { CloseHandle(process) };
+        if queried == 0 {
+            return None;
+        }
+        let path = OsString::from_wide(&path[..len as usize]);
+        Path::new(&path)
+            .file_stem()
+            .map(|name| name.to_string_lossy().into_owned())
+    }
+
+    pub(super) fn focused_window() -> Option<String> {
+        // SAFETY: takes no arguments and returns null when nothing has focus.
+        let window = unsafe { GetForegroundWindow() };
+        (!window.is_null()).then(|| (window as isize).to_string())
+    }
+
+    /// Restores the window first when minimized, since a minimized window
+    /// takes focus without showing its text field.
+    pub(super) fn activate_window(id: &str) -> bool {
+        let Ok(handle) = id.parse::<isize>() else {
+            return false;
+        };
+        let window = handle as HWND;
+        // SAFETY: these calls validate the handle and fail on a window that
+        // has since closed.
+        unsafe {
+            if IsIconic(window) != 0 {
+                ShowWindow(window, SW_RESTORE);
+            }
+            SetForegroundWindow(window) != 0
+        }
+    }
+}
+
+#[cfg(target_os = "macos")]
+fn focus_query() -> Option<Command> {
+    let mut command = Command::new("osascript");
+    command.args([
+        "-e",
+        "tell application \"System Events\" to get name of first application process whose frontmost is true",
+    ]);
+    Some(command)
+}
+
+#[cfg(all(unix, not(target_os = "macos")))]
+fn focus_query() -> Option<Command> {
+    // X11 only; Wayland compositors do not expose the focused window.
+    let mut command = Command::new("xdotool");
+    command.args(["getactivewindow", "getwindowclassname"]);
+    Some(command)
+}
+
+#[cfg(not(any(unix, target_os = "windows")))]
+fn focus_query() -> Option<Command> {
+    None
+}
+
+#[cfg(target_os = "macos")]
+fn window_query() -> Option<Command> {
+    // Apps can only be raised as a whole, so the app stands for its windows.
+    focus_query()
+}
+
+#[cfg(all(unix, not(target_os = "macos")))]
+fn window_query() -> Option<Command> {
+    let mut command = Command::new("xdotool");
+    command.arg("getactivewindow");
+    Some(command)
+}
+
+#[cfg(not(any(unix, target_os = "windows")))]
+fn window_query() -> Option<Command> {
+    None
+}
+
+#[cfg(target_os = "macos")]
+fn activate_command(id: &str) -> Option<Command> {
+    // The name is passed as an argument rather than spliced into the script.
+    let mut command = Command::new("osascript");
+    command.args([
+        "-e",
+        "on run argv",
+        "-e",
+        "tell application \"System Events\" to set frontmost of first application process whose name is (item 1 of argv) to true",
+        "-e",
+        "end run",
+        id,
+    ]);
+    Some(command)
+}
+
+#[cfg(all(unix, not(target_os = "macos")))]
+fn activate_command(id: &str) -> Option<Command> {
+    // Ids come from `getactivewindow`; anything else is not passed on.
+    id.parse::<u64>().ok()?;
+    let mut command = Command::new("xdotool");
+    command.args(["windowactivate", "--sync", id]);
+    Some(command)
+}
+
+#[cfg(not(any(unix, target_os = "windows")))]
+fn activate_command(_id: &str) -> Option<Command> {
+    None
+}
@@ -0,0 +1,121 @@
+use std::sync::Mutex;
+use std::thread;
+use std::time::{Duration, Instant};
+
+use serde::Serialize;
+use tauri::{AppHandle, Emitter};
+
+use super::announce::announce;
+use crate::inactivity::{InactivityAction, InactivityWatch};
+use crate::recording::{Recorder, SessionId};
+use crate::sync::MutexRecover;
+
+/// Sent with the seconds left when a latched recording has been silent for
+/// the configured time.
+pub const INACTIVITY_WARNING_EVENT: &str = "inactivity_warning";
+/// Sent when speech or the user keeps a warned recording going.
+pub const INACTIVITY_RESUMED_EVENT: &str = "inactivity_resumed";
+
+const POLL_INTERVAL: Duration = Duration::from_secs(1);
+
+/// The latched recording being watched. A newer recording replaces it, so a
+/// watcher thread that finds another session here stops.
+static WATCHED: Mutex<Option<(SessionId, InactivityWatch)>> = Mutex::new(None);
+
+#[derive(Clone, Copy, Serialize)]
+struct InactivityWarning {
+    remaining_secs: u64,
+}
+
+/// Watches a latched recording for silence while the inactivity rule is on,
+/// calling `finalize` once the warning's grace runs out.
+pub(super) fn watch_recording(
+    app: &AppHandle,
+    session_id: SessionId,
+    finalize: impl FnOnce(&AppHandle) + Send + 'static,
+) {
+    let timeout = crate::settings::get_settings(app).inactivity;
+    let Some(watch) = timeout.watch(Instant::now()) else {
+        return;
+    };
+    *WATCHED.lock_recover() = Some((session_id, watch));
+    let app = app.clone();
+    let result = thread::Builder::new()
+        .name("inactivity-watch".to_string())
+        .spawn(move || {
+            let mut speech_frames = crate::recording::vad_monitor().speech_frames();
+            loop {
+                thread::sleep(POLL_INTERVAL);
+                let recorder = Recorder::global();
+                let recording =
+                    recorder.is_recording() && recorder.session_id() == Some(session_id);
+                let heard = crate::recording::vad_monitor().speech_frames();
+                let action = {
+                    let mut watched = WATCHED.lock_recover();
+                    let watch = match watched.as_mut() {
+                        Some((id, watch)) if *id == session_id => watch,
+                        _ => return,
+                    };
+                    if !recording {
+                        *watched = None;
+                        return;
+                    }
+                    let now = Instant::now();
+                    if heard != speech_frames {
+                        speech_frames = heard;
+                        watch.heard_speech(now);
+                    }
+                    watch.poll(now)
+                };
+                match action {
+                    InactivityAction::None => {}
+                    InactivityAction::Warn { remaining_secs } => warn(&app, remaining_secs),
+                    InactivityAction::Resume => {
+                        log::info!("Inactive recording resumed");
+                        if let Err(error) = app.emit(INACTIVITY_RESUMED_EVENT, ()) {
+                            log::warn!("Could not emit inactivity resume: {error}");
+                        }
+                    }
+                    InactivityAction::Finalize => {
+                        log::info!("No speech before the inactivity timeout; finalizing recording");
+                        *WATCHED.lock_recover() = None;
+                        finalize(&app);
+                        return;
+                    }
+                }
+            }
+        });
+    if let Err(error) = result {
+        log::warn!("Could not start the inactivity watch: {error}");
+    }
+}
+
+/// Answers a warning by restarting the silence count. Returns whether a
+/// recording is being watched.
+pub(crate) fn keep_recording() -> bool {
+    match WATCHED.lock_recover().as_mut() {
+        Some((_, watch)) => {
+            watch.keep_going(Instant::now());
+            true
+        }
+        None => false,
+    }
+}
+
+fn warn(app: &AppHandle, remaining_secs: u64) {
+    log::info!("Recording inactive; finalizing in {remaining_secs}s unless kept going");
+    if let Err(error) = app.emit(
+        INACTIVITY_WARNING_EVENT,
+        InactivityWarning { remaining_secs },
+    ) {
+        log::warn!("Could not emit inactivity warning: {error}");
+    }
+    if crate::settings::get_settings(app).announce_transcripts {
+        let text = format!(
+            "No speech heard. Recording stops in {remaining_secs} seconds unless you keep going."
+        );
+        if let Err(error) = announce(app, &text) {
+            log::warn!("Could not announce the inactivity warning: {error}");
+        }
+    }
+}
@@ -0,0 +1,100 @@
+use std::sync::{Mutex, OnceLock};
+use std::thread;
+use std::time::Duration;
+
+use tauri::image::Image;
+use tauri::{AppHandle, Listener};
+
+use crate::dictation::{RECORDING_STARTED_EVENT, RECORDING_STOPPED_EVENT};
+use crate::settings::RecordingIndicator;
+use crate::sync::MutexRecover;
+
+const PULSE_INTERVAL: Duration = Duration::from_millis(600);
+const BADGE_RGB: [u8; 3] = [0xE5, 0x39, 0x35];
+
+/// Bumped on every start and stop, so a pulse thread from an earlier
+/// recording stops at its next tick. Icon changes happen under this lock so a
+/// late pulse cannot relight the icon after a stop.
+static GENERATION: Mutex<u64> = Mutex::new(0);
+static BADGED_ICON: OnceLock<Option<Image<'static>>> = OnceLock::new();
+
+/// Follows the recorder's start and stop events, so every way of recording
+/// shows in the tray.
+pub(super) fn init_recording_indicator(app: &AppHandle) {
+    let handle = app.clone();
+    app.listen(RECORDING_STARTED_EVENT, move |_| show_recording(&handle));
+    let handle = app.clone();
+    app.listen(RECORDING_STOPPED_EVENT, move |_| clear_recording(&handle));
+}
+
+fn show_recording(app: &AppHandle) {
+    let indicator = crate::settings::get_settings(app).recording_indicator;
+    let generation = {
+        let mut current = GENERATION.lock_recover();
+        *current += 1;
+        if indicator != RecordingIndicator::Off {
+            super::tray::show_recording(app, true, badged_icon(app));
+        }
+        *current
+    };
+    if indicator != RecordingIndicator::Pulse {
+        return;
+    }
+    let app = app.clone();
+    let result = thread::Builder::new()
+        .name("recording-indicator".to_string())
+        .spawn(move || {
+            let mut lit = true;
+            loop {
+                thread::sleep(PULSE_INTERVAL);
+                let current = GENERATION.lock_recover();
+                if *current != generation {
+                    return;
+                }
+                lit = !lit;
+                super::tray::show_recording(&app, true, badged_icon(&app).filter(|_| lit));
+            }
+        });
+    if let Err(error) = result {
+        log::warn!("Could not pulse the recording indicator: {error}");
+    }
+}
+
+fn clear_recording(app: &AppHandle) {
+    let mut current = GENERATION.lock_recover();
+    *current += 1;
+    super::tray::show_recording(app, false, None);
+}
+
+fn badged_icon(app: &AppHandle) -> Option<&'static Image<'static>> {
+    BADGED_ICON
+        .get_or_init(|| {
+            let icon = app.default_window_icon()?;
+            let rgba = badge_rgba(icon.rgba(), icon.width(), icon.height());
+            Some(Image::new_owned(rgba, icon.width(), icon.height()))
+        })
+        .as_ref()
+}
+
+/// Draws a red recording dot over the lower-right quarter of an RGBA icon.
+pub fn badge_rgba(rgba: &[u8], width: u32, height: u32) -> Vec<u8> {
+    let mut badged = rgba.to_vec();
+    let radius = f64::from(width.min(height)) / 4.0;
+    let center_x = f64::from(width) - radius;
+    let center_y = f64::from(height) - radius;
+    for y in 0..height {
+        for x in 0..width {
+            let dx = f64::from(x) + 0.5 - center_x;
+            let dy = f64::from(y) + 0.5 - center_y;
+            if dx * dx + dy * dy > radius * radius {
+                continue;
+            }
+            let offset = ((y * width + x) * 4) as usize;
+            if let Some(pixel) = badged.get_mut(offset..offset + 4) {
+                pixel[..3].copy_from_slice(&BADGE_RGB);
+                pixel[3] = u8::MAX;
+            }
+        }
+    }
+    badged
+}
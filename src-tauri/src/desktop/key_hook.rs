@@ -0,0 +1,283 @@
+use rdev::Key;
+use tauri::AppHandle;
+use tauri_plugin_global_shortcut::{Code, Modifiers, Shortcut};
+
+use crate::key_suppression::{MODIFIER_ALT, MODIFIER_CONTROL, MODIFIER_META, MODIFIER_SHIFT};
+
+/// Points the key suppression hook at the record shortcut while it is
+/// registered and the setting is on, installing the hook the first time.
+/// Only Windows needs it: elsewhere a registered shortcut never reaches the
+/// focused app.
+pub(super) fn sync_key_suppression(app: &AppHandle, shortcut: Option<Shortcut>) {
+    let chord = shortcut
+        .filter(|_| crate::settings::get_settings(app).suppress_record_key)
+        .and_then(|shortcut| {
+            let chord = hook_chord(&shortcut);
+            if chord.is_none() {
+                log::warn!(
+                    "Record shortcut {} cannot be suppressed; its key is not supported",
+                    shortcut.into_string()
+                );
+            }
+            chord
+        });
+    #[cfg(target_os = "windows")]
+    windows::bind(app, chord);
+    #[cfg(not(target_os = "windows"))]
+    if chord.is_some() {
+        log::debug!("Record key suppression is only needed on Windows");
+    }
+}
+
+/// Each modifier with the kind the hook tracks it as.
+pub(super) const MODIFIER_KINDS: [(Modifiers, u8); 4] = [
+    (Modifiers::SHIFT, MODIFIER_SHIFT),
+    (Modifiers::CONTROL, MODIFIER_CONTROL),
+    (Modifiers::ALT, MODIFIER_ALT),
+    (Modifiers::SUPER, MODIFIER_META),
+];
+
+/// Shortcut keys and the hook's names for them. Keys rdev has no name for,
+/// such as F13 to F24 that dedicated push-to-talk buttons often send, arrive
+/// as their Windows virtual-key code.
+const HOOK_KEYS: &[(Code, Key)] = &[
+    (Code::KeyA, Key::KeyA),
+    (Code::KeyB, Key::KeyB),
+    (Code::KeyC, Key::KeyC),
+    (Code::KeyD, Key::KeyD),
+    (Code::KeyE, Key::KeyE),
+    (Code::KeyF, Key::KeyF),
+    (Code::KeyG, Key::KeyG),
+    (Code::KeyH, Key::KeyH),
+    (Code::KeyI, Key::KeyI),
+    (Code::KeyJ, Key::KeyJ),
+    (Code::KeyK, Key::KeyK),
+    (Code::KeyL, Key::KeyL),
+    (Code::KeyM, Key::KeyM),
+    (Code::KeyN, Key::KeyN),
+    (Code::KeyO, Key::KeyO),
+    (Code::KeyP, Key::KeyP),
+    (Code::KeyQ, Key::KeyQ),
+    (Code::KeyR, Key::KeyR),
+    (Code::KeyS, Key::KeyS),
+    (Code::KeyT, Key::KeyT),
+    (Code::KeyU, Key::KeyU),
+    (Code::KeyV, Key::KeyV),
+    (Code::KeyW, Key::KeyW),
+    (Code::KeyX, Key::KeyX),
+    (Code::KeyY, Key::KeyY),
+    (Code::KeyZ, Key::KeyZ),
+    (Code::Digit0, Key::Num0),
+    (Code::Digit1, Key::Num1),
+    (Code::Digit2, Key::Num2),
+    (Code::Digit3, Key::Num3),
+    (Code::Digit4, Key::Num4),
+    (Code::Digit5, Key::Num5),
+    (Code::Digit6, Key::Num6),
+    (Code::Digit7, Key::Num7),
+    (Code::Digit8, Key::Num8),
+    (Code::Digit9, Key::Num9),
+    (Code::F1, Key::F1),
+    (Code::F2, Key::F2),
+    (Code::F3, Key::F3),
+    (Code::F4, Key::F4),
+    (Code::F5, Key::F5),
+    (Code::F6, Key::F6),
+    (Code::F7, Key::F7),
+    (Code::F8, Key::F8),
+    (Code::F9, Key::F9),
+    (Code::F10, Key::F10),
+    (Code::F11, Key::F11),
+    (Code::F12, Key::F12),
+    (Code::F13, Key::Unknown(0x7C)),
+    (Code::F14, Key::Unknown(0x7D)),
+    (Code::F15, Key::Unknown(0x7E)),
+    (Code::F16, Key::Unknown(0x7F)),
+    (Code::F17, Key::Unknown(0x80)),
+    (Code::F18, Key::Unknown(0x81)),
+    (Code::F19, Key::Unknown(0x82)),
+    (Code::F20, Key::Unknown(0x83)),
+    (Code::F21, Key::Unknown(0x84)),
+    (Code::F22, Key::Unknown(0x85)),
+    (Code::F23, Key::Unknown(0x86)),
+    (Code::F24, Key::Unknown(0x87)),
+    (Code::Space, Key::Space),
+    (Code::Tab, Key::Tab),
+    (Code::Enter, Key::Return),
+    (Code::Escape, Key::Escape),
+    (Code::Backspace, Key::Backspace),
+    (Code::Insert, Key::Insert),
+    (Code::Delete, Key::Delete),
+    (Code::Home, Key::Home),
+    (Code::End, Key::End),
+    (Code::PageUp, Key::PageUp),
+    (Code::PageDown, Key::PageDown),
+    (Code::ArrowUp, Key::UpArrow),
+    (Code::ArrowDown, Key::DownArrow),
+    (Code::ArrowLeft, Key::LeftArrow),
+    (Code::ArrowRight, Key::RightArrow),
+    (Code::CapsLock, Key::CapsLock),
+    (Code::ScrollLock, Key::ScrollLock),
+    (Code::Pause, Key::Pause),
+    (Code::PrintScreen, Key::PrintScreen),
+    (Code::Backquote, Key::BackQuote),
+    (Code::Minus, Key::Minus),
+    (Code::Equal, Key::Equal),
+    (Code::BracketLeft, Key::LeftBracket),
+    (Code::BracketRight, Key::RightBracket),
+    (Code::Semicolon, Key::SemiColon),
+    (Code::Quote, Key::Quote),
+    (Code::Backslash, Key::BackSlash),
+    (Code::Comma, Key::Comma),
+    (Code::Period, Key::Dot),
+    (Code::Slash, Key::Slash),
+];
+
+fn hook_chord(shortcut: &Shortcut) -> Option<(Key, u8)> {
+    let modifiers = MODIFIER_KINDS
+        .into_iter()
+        .filter(|(modifier, _)| shortcut.mods.contains(*modifier))
+        .fold(0, |kinds, (_, kind)| kinds | kind);
+    Some((hook_key(shortcut.key)?, modifiers))
+}
+
+/// The hook's name for a shortcut key.
+fn hook_key(code: Code) -> Option<Key> {
+    HOOK_KEYS
+        .iter()
+        .find(|(candidate, _)| *candidate == code)
+        .map(|(_, key)| *key)
+}
+
+/// The shortcut key the hook reports as `key`, if it can be a shortcut.
+pub(super) fn shortcut_code(key: Key) -> Option<Code> {
+    HOOK_KEYS
+        .iter()
+        .find(|(_, candidate)| *candidate == key)
+        .map(|(code, _)| *code)
+}
+
+#[cfg(target_os = "windows")]
+mod windows {
+    use std::sync::atomic::{AtomicBool, Ordering};
+    use std::sync::{mpsc, Mutex};
+    use std::thread;
+
+    use tauri::AppHandle;
+    use tauri_plugin_global_shortcut::ShortcutState;
+
+    use super::super::modifiers;
+    use super::super::shortcuts::record_trigger;
+    use crate::key_suppression::{HookDecision, KeySuppressor};
+
+    /// An unassigned virtual-key code. Sent after a swallowed key so the
+    /// release of Alt or the Windows key that follows is not taken as a tap
+    /// that opens a menu or the Start menu.
+    const MASK_KEY: u32 = 0xE8;
+
+    static SUPPRESSOR: Mutex<KeySuppressor<rdev::Key>> = Mutex::new(KeySuppressor::new());
+    static HOOKED: AtomicBool = AtomicBool::new(false);
+
+    pub(super) fn bind(app: &AppHandle, chord: Option<(rdev::Key, u8)>) {
+        let was_down = match SUPPRESSOR.lock() {
+            Ok(mut suppressor) => {
+                let was_down = suppressor.is_down();
+                suppressor.bind(chord);
+                was_down && !suppressor.is_down()
+            }
+            Err(_) => {
+                log::warn!("Record key suppression state is unavailable");
+                return;
+            }
+        };
+        if was_down {
+            record_trigger(app, ShortcutState::Released);
+        }
+        if chord.is_some() {
+            start_hook(app);
+        }
+    }
+
+    #[derive(Clone, Copy)]
+    enum HookAction {
+        Trigger(ShortcutState),
+        Mask,
+    }
+
+    /// The hook is removed only when the app exits, so unbinding leaves it
+    /// passing every event through. Shortcut handling runs on a separate
+    /// thread: Windows drops a low-level hook that answers slowly.
+    fn start_hook(app: &AppHandle) {
+        if HOOKED.swap(true, Ordering::AcqRel) {
+            return;
+        }
+        let (tx, rx) = mpsc::channel::<HookAction>();
+        let app = app.clone();
+        let worker = thread::Builder::new()
+            .name("key-suppression".to_string())
+            .spawn(move || {
+                for action in rx {
+                    match action {
+                        HookAction::Trigger(state) => record_trigger(&app, state),
+                        HookAction::Mask => mask_modifier_release(),
+                    }
+                }
+            });
+        if let Err(error) = worker {
+            log::warn!("Could not start record key suppression: {error}");
+            HOOKED.store(false, Ordering::Release);
+            return;
+        }
+        let hook = thread::Builder::new()
+            .name("key-suppression-hook".to_string())
+            .spawn(move || {
+                let result = rdev::grab(move |event| {
+                    modifiers::observe(&event.event_type);
+                    let (key, pressed) = match event.event_type {
+                        rdev::EventType::KeyPress(key) => (key, true),
+                        rdev::EventType::KeyRelease(key) => (key, false),
+                        _ => return Some(event),
+                    };
+                    let decision = match SUPPRESSOR.lock() {
+                        Ok(mut suppressor) => suppressor.key(key, pressed, modifiers::held_kinds()),
+                        Err(_) => HookDecision::Pass,
+                    };
+                    let actions: &[HookAction] = match decision {
+                        HookDecision::Pass => return Some(event),
+                        HookDecision::Swallow => &[],
+                        HookDecision::Press { mask: false } => {
+                            &[HookAction::Trigger(ShortcutState::Pressed)]
+                        }
+                        HookDecision::Press { mask: true } => &[
+                            HookAction::Mask,
+                            HookAction::Trigger(ShortcutState::Pressed),
+                        ],
+                        HookDecision::Release => &[HookAction::Trigger(ShortcutState::Released)],
+                    };
+                    for action in actions {
+                        let _ = tx.send(*action);
+                    }
+                    None
+                });
+                if let Err(error) = result {
+                    log::warn!("Record key suppression stopped: {error:?}");
+                    HOOKED.store(false, Ordering::Release);
+                }
+            });
+        if let Err(error) = hook {
+            log::warn!("Could not install record key suppression: {error}");
+            HOOKED.store(false, Ordering::Release);
+        }
+    }
+
+    fn mask_modifier_release() {
+        for event in [
+            rdev::EventType::KeyPress(rdev::Key::Unknown(MASK_KEY)),
+            rdev::EventType::KeyRelease(rdev::Key::Unknown(MASK_KEY)),
+        ] {
+            if let Err(error) = rdev::simulate(&event) {
+                log::debug!("Could not send the modifier mask key: {error:?}");
+            }
+        }
+    }
+}
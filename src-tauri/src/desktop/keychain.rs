@@ -0,0 +1,149 @@
+use keyring::Entry;
+use tauri::AppHandle;
+
+use crate::at_rest::{AtRestError, AtRestKey};
+use crate::companion::{from_hex, to_hex, CompanionError, KEY_BYTES};
+use crate::llm_handoff::LlmHandoffError;
+use crate::webhook::WebhookError;
+
+const SERVICE: &str = "SilentKeys";
+const ACCOUNT: &str = "at-rest-secret";
+/// Followed by the device id, one entry per paired companion device.
+const COMPANION_ACCOUNT_PREFIX: &str = "companion-device-";
+const WEBHOOK_SECRET_ACCOUNT: &str = "webhook-secret";
+const LLM_API_KEY_ACCOUNT: &str = "llm-api-key";
+const SECRET_BYTES: usize = 32;
+
+/// Loads the at-rest key from the keychain to match the saved settings. With
+/// encryption on, a secret is created the first time; with it off, an
+/// existing secret is still loaded so earlier encrypted files stay readable.
+pub(crate) fn sync_at_rest(app: &AppHandle) -> Result<(), AtRestError> {
+    load_at_rest(crate::settings::get_settings(app).encrypt_at_rest)
+}
+
+/// Like [`sync_at_rest`] for `enabled` before it is saved, so turning
+/// encryption on fails without changing anything when the key is missing.
+pub(crate) fn load_at_rest(enabled: bool) -> Result<(), AtRestError> {
+    let secret = at_rest_secret(enabled)?;
+    crate::at_rest::configure(
+        secret.map(|secret| AtRestKey::derive(secret.as_bytes())),
+        enabled,
+    );
+    Ok(())
+}
+
+pub(super) fn init_at_rest(app: &AppHandle) {
+    if let Err(error) = sync_at_rest(app) {
+        log::warn!("Encrypted history unavailable: {error}");
+    }
+}
+
+fn at_rest_secret(create: bool) -> Result<Option<String>, AtRestError> {
+    let entry = Entry::new(SERVICE, ACCOUNT).map_err(keychain_error)?;
+    match entry.get_password() {
+        Ok(secret) => Ok(Some(secret)),
+        Err(keyring::Error::NoEntry) if create => {
+            let mut bytes = [0_u8; SECRET_BYTES];
+            getrandom::fill(&mut bytes).map_err(|error| AtRestError::Random(error.to_string()))?;
+            let secret: String = bytes.iter().map(|byte| format!("{byte:02x}")).collect();
+            entry.set_password(&secret).map_err(keychain_error)?;
+            log::info!("Created the at-rest encryption secret in the keychain");
+            Ok(Some(secret))
+        }
+        Err(keyring::Error::NoEntry) => Ok(None),
+        Err(error) => Err(keychain_error(error)),
+    }
+}
+
+fn keychain_error(error: keyring::Error) -> AtRestError {
+    AtRestError::Keychain(error.to_string())
+}
+
+fn companion_entry(device_id: &str) -> Result<Entry, CompanionError> {
+    Entry::new(SERVICE, &format!("{COMPANION_ACCOUNT_PREFIX}{device_id}"))
+        .map_err(companion_keychain_error)
+}
+
+pub(crate) fn store_companion_key(
+    device_id: &str,
+    key: &[u8; KEY_BYTES],
+) -> Result<(), CompanionError> {
+    companion_entry(device_id)?
+        .set_password(&to_hex(key))
+        .map_err(companion_keychain_error)
+}
+
+/// The key stored when `device_id` paired, or `None` for a device paired
+/// before keys were kept in the keychain.
+pub(crate) fn companion_key(device_id: &str) -> Result<Option<Vec<u8>>, CompanionError> {
+    match companion_entry(device_id)?.get_password() {
+        Ok(key) => Ok(from_hex(&key)),
+        Err(keyring::Error::NoEntry) => Ok(None),
+        Err(error) => Err(companion_keychain_error(error)),
+    }
+}
+
+pub(crate) fn forget_companion_key(device_id: &str) -> Result<(), CompanionError> {
+    match companion_entry(device_id)?.delete_credential() {
+        Ok(()) | Err(keyring::Error::NoEntry) => Ok(()),
+        Err(error) => Err(companion_keychain_error(error)),
+    }
+}
+
+fn companion_keychain_error(error: keyring::Error) -> CompanionError {
+    CompanionError::Keychain(error.to_string())
+}
+
+/// Reads a secret setting; one never set reads as empty.
+fn secret(account: &str) -> keyring::Result<String> {
+    match Entry::new(SERVICE, account)?.get_password() {
+        Err(keyring::Error::NoEntry) => Ok(String::new()),
+        result => result,
+    }
+}
+
+/// Stores a secret setting; an empty one removes it.
+fn store_secret(account: &str, secret: &str) -> keyring::Result<()> {
+    let entry = Entry::new(SERVICE, account)?;
+    if !secret.is_empty() {
+        return entry.set_password(secret);
+    }
+    match entry.delete_credential() {
+        Ok(()) | Err(keyring::Error::NoEntry) => Ok(()),
+        Err(error) => Err(error),
+    }
+}
+
+pub(crate) fn webhook_secret() -> Result<String, WebhookError> {
+    secret(WEBHOOK_SECRET_ACCOUNT).map_err(webhook_keychain_error)
+}
+
+pub(crate) fn store_webhook_secret(webhook_secret: &str) -> Result<(), WebhookError> {
+    store_secret(WEBHOOK_SECRET_ACCOUNT, webhook_secret).map_err(webhook_keychain_error)
+}
+
+fn webhook_keychain_error(error: keyring::Error) -> WebhookError {
+    WebhookError::Keychain(error.to_string())
+}
+
+pub(crate) fn llm_api_key() -> Result<String, LlmHandoffError> {
+    secret(LLM_API_KEY_ACCOUNT).map_err(llm_keychain_error)
+}
+
+pub(crate) fn store_llm_api_key(api_key: &str) -> Result<(), LlmHandoffError> {
+    store_secret(LLM_API_KEY_ACCOUNT, api_key).map_err(llm_keychain_error)
+}
+
+fn llm_keychain_error(error: keyring::Error) -> LlmHandoffError {
+    LlmHandoffError::Keychain(error.to_string())
+}
+
+/// Removes every secret setting, for a settings reset.
+pub(crate) fn forget_secrets() {
+    if let Err(error) = store_webhook_secret("") {
+        log::warn!("Could not remove the webhook secret: {error}");
+    }
+    if let Err(error) = store_llm_api_key("") {
+        log::warn!("Could not remove the LLM API key: {error}");
+    }
+}
@@ -0,0 +1,87 @@
+use tauri::{AppHandle, Manager};
+use tauri_plugin_global_shortcut::{
+    Code, GlobalShortcutExt, Modifiers, Shortcut, ShortcutEvent, ShortcutState,
+};
+
+use super::shortcuts::{capture_text, record_shortcut_allowed, report_failure};
+use super::typing::{inject_text, TypeTextOptions};
+use crate::engine::SpeechEngine;
+use crate::recording::{ListenConfig, Lookback, RecordingError};
+
+/// Fixed shortcut that transcribes the last seconds the lookback listener
+/// heard. Registered only while lookback is enabled.
+pub fn lookback_shortcut() -> Shortcut {
+    Shortcut::new(Some(Modifiers::ALT | Modifiers::SHIFT), Code::KeyX)
+}
+
+/// Starts, restarts, or stops the listener and its shortcut to match the
+/// saved settings. Like the record shortcut, both are off while dictation is
+/// disabled or quiet hours are on, so the microphone is not kept open then.
+pub(crate) fn sync_lookback(app: &AppHandle) {
+    let settings = crate::settings::get_settings(app);
+    let lookback = settings.lookback.clamped();
+    let wanted = lookback.enabled && record_shortcut_allowed(app);
+    let config = wanted.then(|| ListenConfig {
+        capacity: lookback.buffer_samples(),
+        input_device: settings.input_device.clone(),
+        gain: settings.vad.gain,
+        resampler_quality: settings.resampler_quality,
+    });
+    if let Err(error) = Lookback::global().sync(config) {
+        report_failure(app, "Failed to start lookback listening", &error);
+    }
+
+    let shortcut = lookback_shortcut();
+    let listening = Lookback::global().is_listening();
+    let result = match (listening, app.global_shortcut().is_registered(shortcut)) {
+        (true, false) => app
+            .global_shortcut()
+            .on_shortcut(shortcut, handle_lookback_shortcut),
+        (false, true) => app.global_shortcut().unregister(shortcut),
+        _ => Ok(()),
+    };
+    if let Err(error) = result {
+        log::warn!("Could not update lookback shortcut: {error}");
+    }
+}
+
+fn handle_lookback_shortcut(app: &AppHandle, _: &Shortcut, event: ShortcutEvent) {
+    if !matches!(event.state(), ShortcutState::Pressed) {
+        return;
+    }
+    log::info!("Lookback shortcut PRESSED -> Transcribing buffered audio");
+    let worker_app = app.clone();
+    let result = std::thread::Builder::new()
+        .name("lookback-transcribe".to_string())
+        .spawn(move || transcribe_lookback(&worker_app));
+    if let Err(error) = result {
+        report_failure(
+            app,
+            "Failed to start lookback worker",
+            &RecordingError::ThreadStart(error),
+        );
+    }
+}
+
+/// Delivers the buffered audio's transcript like a dictation's final text:
+/// to the capture file when it is on, otherwise typed into the focused app.
+fn transcribe_lookback(app: &AppHandle) {
+    let text = match app.state::<SpeechEngine>().transcribe_lookback() {
+        Ok(text) => text,
+        Err(error) => {
+            report_failure(app, "Failed to transcribe lookback audio", &error);
+            return;
+        }
+    };
+    if text.is_empty() {
+        log::info!("Lookback audio held no speech");
+        return;
+    }
+    let settings = crate::settings::get_settings(app);
+    if settings.capture_file.enabled && capture_text(app, &settings, &text) {
+        return;
+    }
+    if let Err(error) = inject_text(&settings, text, &TypeTextOptions::default()) {
+        report_failure(app, "Failed to type lookback transcript", &error);
+    }
+}
@@ -1,18 +1,96 @@
+mod announce;
+mod autostart;
+mod caret;
+mod cli;
+mod companion;
+mod document;
+mod focus;
+mod inactivity;
+mod indicator;
+mod key_hook;
+mod keychain;
+mod lookback;
+mod modifiers;
+mod network_input;
+mod onboarding;
+mod quiet_hours;
+mod shortcut_capture;
+mod shortcut_conflicts;
 mod shortcuts;
 mod tray;
+mod triggers;
+mod typed_metadata;
 mod typing;
 
+pub use announce::AnnounceError;
+#[doc(hidden)]
+pub use announce::{announce_commands, speech_commands, AnnounceCommand};
+pub(crate) use autostart::{show_main_window, sync_autostart};
+pub(crate) use cli::run_command_line;
+#[cfg(target_os = "macos")]
+pub(crate) use cli::run_deep_link;
+pub(crate) use companion::sync_companion;
+pub(crate) use document::{
+    capture_patch, document_text, finish_document, open_document, set_document_text,
+};
+pub use document::{DocumentError, DocumentFinish};
+pub(crate) use inactivity::keep_recording;
+#[doc(hidden)]
+pub use indicator::badge_rgba;
+pub(crate) use keychain::{
+    forget_companion_key, forget_secrets, llm_api_key, load_at_rest, store_llm_api_key,
+    store_webhook_secret, sync_at_rest, webhook_secret,
+};
+pub use lookback::lookback_shortcut;
+pub(crate) use lookback::sync_lookback;
+pub(crate) use network_input::sync_network_input;
+pub(crate) use onboarding::{
+    check_onboarding_microphone, current_onboarding, observe_onboarding, restart_onboarding,
+    test_onboarding_microphone,
+};
+pub use quiet_hours::is_active as quiet_hours_active;
+pub(crate) use quiet_hours::refresh_quiet_hours;
+pub(crate) use shortcut_capture::begin_shortcut_capture;
+pub use shortcut_capture::chord_shortcut;
+pub(crate) use shortcut_conflicts::shortcut_conflict;
+pub use shortcut_conflicts::{
+    fallback_candidates, likely_conflicts, ShortcutConflict, SHORTCUT_CONFLICT_EVENT,
+};
 pub use shortcuts::{
-    default_record_shortcut, default_shortcut, get_record_shortcut, parse_shortcut_str,
-    update_record_shortcut,
+    default_record_shortcut, default_shortcut, dictation_toggle_shortcut, get_record_shortcut,
+    llm_handoff_shortcut, numbers_mode_shortcut, parse_shortcut_str, update_record_shortcut,
+    ShortcutError,
 };
+pub(crate) use shortcuts::{
+    reset_shortcut_gesture, set_dictation_enabled, sync_dictation_enabled, sync_handoff_shortcut,
+    sync_dictation_toggle_shortcut, sync_mode_shortcuts, sync_numbers_mode_shortcut,
+    sync_record_key_suppression,
+};
+pub(crate) use tray::sync_profiles_menu;
+pub use triggers::HidDeviceInfo;
+pub(crate) use triggers::{detect_mouse_button, list_hid_devices, sync_triggers};
+pub(crate) use typed_metadata::{publish_typed_commit, sync_typed_metadata};
 #[doc(hidden)]
-pub use typing::{append_for_tests, deliver_for_tests, plan_final_delivery, FinalDelivery};
+pub use typing::{
+    append_for_tests, deliver_for_tests, needs_leading_space, plan_final_delivery,
+    rate_limit_delay, reached_window, typing_chunks, untyped_remainder, FinalDelivery,
+};
+pub(crate) use typing::{inject_text, reset_buffer as reset_typing, type_patch};
+pub use typing::{DeliveryProgress, TypeTextError, TypeTextOptions, TypeTextPlan, TypingError};
 
 #[cfg(desktop)]
 pub fn setup_desktop(app: &mut tauri::App) -> tauri::Result<()> {
     let handle = app.handle();
     tray::init_tray(handle)?;
+    indicator::init_recording_indicator(handle);
+    quiet_hours::init_quiet_hours(handle);
     shortcuts::init_shortcuts(handle)?;
+    triggers::sync_triggers(handle);
+    network_input::init_network_input(handle);
+    typed_metadata::init_typed_metadata(handle);
+    companion::init_companion(handle);
+    autostart::init_autostart(handle);
+    keychain::init_at_rest(handle);
+    onboarding::init_onboarding(handle);
     Ok(())
 }
@@ -0,0 +1,63 @@
+use std::sync::atomic::{AtomicU16, Ordering};
+
+/// One bit per modifier key currently held, as seen by the input listener.
+static HELD: AtomicU16 = AtomicU16::new(0);
+
+fn modifier_bit(key: rdev::Key) -> Option<u16> {
+    let index = match key {
+        rdev::Key::ShiftLeft => 0,
+        rdev::Key::ShiftRight => 1,
+        rdev::Key::ControlLeft => 2,
+        rdev::Key::ControlRight => 3,
+        rdev::Key::Alt => 4,
+        rdev::Key::AltGr => 5,
+        rdev::Key::MetaLeft => 6,
+        rdev::Key::MetaRight => 7,
+        _ => return None,
+    };
+    Some(1 << index)
+}
+
+/// Tracks modifier presses and releases from the global input listener.
+pub(super) fn observe(event: &rdev::EventType) {
+    match event {
+        rdev::EventType::KeyPress(key) => {
+            if let Some(bit) = modifier_bit(*key) {
+                HELD.fetch_or(bit, Ordering::AcqRel);
+            }
+        }
+        rdev::EventType::KeyRelease(key) => {
+            if let Some(bit) = modifier_bit(*key) {
+                HELD.fetch_and(!bit, Ordering::AcqRel);
+            }
+        }
+        _ => {}
+    }
+}
+
+pub(super) fn is_modifier(key: rdev::Key) -> bool {
+    modifier_bit(key).is_some()
+}
+
+/// The modifier kinds held, left and right merged, as
+/// [`crate::key_suppression::KeySuppressor`] takes them.
+pub(super) fn held_kinds() -> u8 {
+    use crate::key_suppression::{MODIFIER_ALT, MODIFIER_CONTROL, MODIFIER_META, MODIFIER_SHIFT};
+
+    let held = HELD.load(Ordering::Acquire);
+    [
+        (0b0000_0011, MODIFIER_SHIFT),
+        (0b0000_1100, MODIFIER_CONTROL),
+        (0b0011_0000, MODIFIER_ALT),
+        (0b1100_0000, MODIFIER_META),
+    ]
+    .into_iter()
+    .filter(|(bits, _)| held & bits != 0)
+    .fold(0, |kinds, (_, kind)| kinds | kind)
+}
+
+/// Whether a modifier is physically held. Always `false` while the input
+/// listener is not running.
+pub(super) fn held() -> bool {
+    HELD.load(Ordering::Acquire) != 0
+}
@@ -0,0 +1,174 @@
+use std::io::{self, Read};
+use std::net::{TcpListener, TcpStream};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{mpsc, Arc, Mutex, OnceLock};
+use std::thread::{self, JoinHandle};
+use std::time::Duration;
+
+use tauri::AppHandle;
+
+use super::shortcuts::{
+    finish_network_dictation, report_failure, start_network_dictation, TranscriptObserver,
+};
+use crate::network_input::{read_frame, NetworkInputError};
+use crate::recording::AudioInput;
+
+const ACCEPT_POLL_INTERVAL: Duration = Duration::from_millis(100);
+/// A client that sends nothing for this long is treated as gone, so a dropped
+/// phone connection cannot hold the recorder open.
+const READ_TIMEOUT: Duration = Duration::from_secs(5);
+
+static LISTENER: OnceLock<Mutex<Option<Listener>>> = OnceLock::new();
+
+/// A polling accept loop that hands each connection to its own thread.
+pub(super) struct Listener {
+    port: u16,
+    stop: Arc<AtomicBool>,
+    handle: JoinHandle<()>,
+}
+
+impl Listener {
+    pub(super) fn spawn(
+        app: &AppHandle,
+        socket: TcpListener,
+        port: u16,
+        name: &'static str,
+        serve: fn(&AppHandle, TcpStream),
+    ) -> io::Result<Self> {
+        socket.set_nonblocking(true)?;
+        let stop = Arc::new(AtomicBool::new(false));
+        let worker_stop = stop.clone();
+        let worker_app = app.clone();
+        let handle = thread::Builder::new()
+            .name(format!("{name}-listener"))
+            .spawn(move || accept_loop(&worker_app, &socket, &worker_stop, name, serve))?;
+        Ok(Self { port, stop, handle })
+    }
+
+    pub(super) fn port(&self) -> u16 {
+        self.port
+    }
+
+    /// Joining waits at most one poll interval, so the port is free again
+    /// before a replacement binds it.
+    pub(super) fn shut_down(self) {
+        self.stop.store(true, Ordering::Release);
+        if self.handle.join().is_err() {
+            log::warn!("Listener on port {} panicked", self.port);
+        }
+        log::info!("Stopped listening on port {}", self.port);
+    }
+}
+
+fn listener() -> &'static Mutex<Option<Listener>> {
+    LISTENER.get_or_init(|| Mutex::new(None))
+}
+
+/// Starts, moves, or stops the network audio listener to match the saved
+/// settings.
+pub(crate) fn sync_network_input(app: &AppHandle) -> Result<(), NetworkInputError> {
+    let settings = crate::settings::get_settings(app).network_input;
+    let wanted = settings.enabled.then_some(settings.port);
+    let Ok(mut active) = listener().lock() else {
+        log::warn!("Network audio listener state is unavailable");
+        return Ok(());
+    };
+    if active.as_ref().map(Listener::port) == wanted {
+        return Ok(());
+    }
+    if let Some(previous) = active.take() {
+        previous.shut_down();
+    }
+    let Some(port) = wanted else {
+        return Ok(());
+    };
+
+    let socket = crate::network_input::listen(port)?;
+    let listener = Listener::spawn(app, socket, port, "network-audio", serve_connection)
+        .map_err(|source| NetworkInputError::Bind { port, source })?;
+    log::info!("Listening for network audio on 127.0.0.1:{port}");
+    *active = Some(listener);
+    Ok(())
+}
+
+pub(super) fn init_network_input(app: &AppHandle) {
+    if let Err(error) = sync_network_input(app) {
+        log::warn!("Network audio input unavailable: {error}");
+    }
+}
+
+fn accept_loop(
+    app: &AppHandle,
+    socket: &TcpListener,
+    stop: &AtomicBool,
+    name: &'static str,
+    serve: fn(&AppHandle, TcpStream),
+) {
+    while !stop.load(Ordering::Acquire) {
+        match socket.accept() {
+            Ok((stream, peer)) => {
+                log::info!("Accepted {name} connection from {peer}");
+                let app = app.clone();
+                let result = thread::Builder::new()
+                    .name(format!("{name}-connection"))
+                    .spawn(move || serve(&app, stream));
+                if let Err(error) = result {
+                    log::warn!("Could not start {name} connection thread: {error}");
+                }
+            }
+            Err(error) if error.kind() == io::ErrorKind::WouldBlock => {
+                thread::sleep(ACCEPT_POLL_INTERVAL);
+            }
+            Err(error) => {
+                log::warn!("Accepting {name} connection failed: {error}");
+                thread::sleep(ACCEPT_POLL_INTERVAL);
+            }
+        }
+    }
+}
+
+/// Accepted sockets inherit the listener's non-blocking mode on some
+/// platforms, so each connection is switched back to blocking reads.
+pub(super) fn configure_connection(stream: &TcpStream) -> io::Result<()> {
+    stream.set_nonblocking(false)?;
+    stream.set_read_timeout(Some(READ_TIMEOUT))
+}
+
+/// Feeds network audio frames from `reader` to a dictation until the client
+/// ends the stream. Audio received before a read failure is still
+/// transcribed. Returns false when no dictation could be started.
+pub(super) fn stream_dictation(
+    app: &AppHandle,
+    reader: &mut impl Read,
+    observer: Option<TranscriptObserver>,
+) -> bool {
+    let (frames_tx, frames_rx) = mpsc::channel();
+    if !start_network_dictation(app, AudioInput::Network(frames_rx), observer) {
+        return false;
+    }
+    let result = loop {
+        match read_frame(reader) {
+            Ok(Some(samples)) => {
+                if frames_tx.send(samples).is_err() {
+                    break Ok(());
+                }
+            }
+            Ok(None) => break Ok(()),
+            Err(error) => break Err(error),
+        }
+    };
+    drop(frames_tx);
+    if let Err(error) = result {
+        report_failure(app, "Network audio stream ended early", &error);
+    }
+    finish_network_dictation(app);
+    true
+}
+
+fn serve_connection(app: &AppHandle, mut stream: TcpStream) {
+    if let Err(error) = configure_connection(&stream) {
+        log::warn!("Could not configure network audio connection: {error}");
+        return;
+    }
+    stream_dictation(app, &mut stream, None);
+}
@@ -0,0 +1,108 @@
+use std::sync::Mutex;
+
+use tauri::{AppHandle, Emitter, Listener, Manager};
+
+use crate::engine::{EngineState, SpeechEngine};
+use crate::errors::UserFacing;
+use crate::onboarding::{Onboarding, OnboardingEvent, ONBOARDING_EVENT};
+use crate::recording::MicrophoneTest;
+use crate::settings::SettingsServiceError;
+use crate::sync::MutexRecover;
+
+/// This run through first-run setup, started from the settings on first use.
+static ONBOARDING: Mutex<Option<Onboarding>> = Mutex::new(None);
+
+/// Follows the speech engine, so the download step passes on its own once
+/// the model has loaded.
+pub(super) fn init_onboarding(app: &AppHandle) {
+    let handle = app.clone();
+    app.listen("engine_state", move |event| {
+        match serde_json::from_str::<EngineState>(event.payload()) {
+            Ok(EngineState::Loaded) => observe_onboarding(&handle, OnboardingEvent::ModelReady),
+            Ok(EngineState::Failed(message)) => {
+                observe_onboarding(&handle, OnboardingEvent::ModelFailed(message))
+            }
+            Ok(_) => {}
+            Err(error) => log::warn!("Could not read speech engine state: {error}"),
+        }
+    });
+}
+
+pub(crate) fn current_onboarding(app: &AppHandle) -> Onboarding {
+    ONBOARDING
+        .lock_recover()
+        .get_or_insert_with(|| start(app))
+        .clone()
+}
+
+/// Feeds `event` to setup, emitting the new state and saving it once setup
+/// is done.
+pub(crate) fn observe_onboarding(app: &AppHandle, event: OnboardingEvent) {
+    let mut onboarding = ONBOARDING.lock_recover();
+    let onboarding = onboarding.get_or_insert_with(|| start(app));
+    if !onboarding.apply(event) {
+        return;
+    }
+    log::info!("Onboarding is at {:?}", onboarding.step);
+    if onboarding.is_done() {
+        if let Err(error) = crate::settings::set_onboarding_completed(app, true) {
+            log::warn!("Could not save finished onboarding: {error}");
+        }
+    }
+    emit_onboarding(app, onboarding);
+}
+
+/// Walks through setup again from the start, as on a first run.
+pub(crate) fn restart_onboarding(app: &AppHandle) -> Result<Onboarding, SettingsServiceError> {
+    crate::settings::set_onboarding_completed(app, false)?;
+    let restarted = start(app);
+    *ONBOARDING.lock_recover() = Some(restarted.clone());
+    emit_onboarding(app, &restarted);
+    Ok(restarted)
+}
+
+/// The microphone step: opening the microphone asks the OS for access.
+pub(crate) fn check_onboarding_microphone(app: &AppHandle) {
+    let event = match app.state::<SpeechEngine>().check_microphone_access() {
+        Ok(()) => OnboardingEvent::MicrophoneOpened,
+        Err(error) => {
+            log::warn!("Onboarding could not open the microphone: {error}");
+            OnboardingEvent::MicrophoneBlocked(error.user_message().to_string())
+        }
+    };
+    observe_onboarding(app, event);
+}
+
+/// The test step passes once a take is loud enough to transcribe.
+pub(crate) fn test_onboarding_microphone(app: &AppHandle) -> Option<MicrophoneTest> {
+    let (event, test) = match app.state::<SpeechEngine>().test_microphone() {
+        Ok(test) if test.too_quiet => (
+            OnboardingEvent::MicTestFailed(
+                "The microphone is very quiet. Move closer or raise its input level, then test again."
+                    .to_string(),
+            ),
+            Some(test),
+        ),
+        Ok(test) => (OnboardingEvent::MicTestPassed, Some(test)),
+        Err(error) => {
+            log::warn!("Onboarding microphone test failed: {error}");
+            (
+                OnboardingEvent::MicTestFailed(error.user_message().to_string()),
+                None,
+            )
+        }
+    };
+    observe_onboarding(app, event);
+    test
+}
+
+fn start(app: &AppHandle) -> Onboarding {
+    let completed = crate::settings::get_settings(app).onboarding_completed;
+    Onboarding::new(completed, app.state::<SpeechEngine>().is_ready())
+}
+
+fn emit_onboarding(app: &AppHandle, onboarding: &Onboarding) {
+    if let Err(error) = app.emit(ONBOARDING_EVENT, onboarding) {
+        log::warn!("Could not emit {ONBOARDING_EVENT}: {error}");
+    }
+}
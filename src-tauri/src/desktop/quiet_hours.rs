@@ -0,0 +1,55 @@
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::thread;
+use std::time::Duration;
+
+use tauri::{AppHandle, Emitter};
+
+const CHECK_INTERVAL: Duration = Duration::from_secs(30);
+
+static QUIET_HOURS_ACTIVE: AtomicBool = AtomicBool::new(false);
+
+/// Whether a quiet-hours window is in effect. Anything that starts
+/// listening on its own must stay suspended while this is true.
+pub fn is_active() -> bool {
+    QUIET_HOURS_ACTIVE.load(Ordering::Acquire)
+}
+
+/// Records the state at launch, before the record shortcut is registered, then
+/// re-evaluates the schedule in the background.
+pub(super) fn init_quiet_hours(app: &AppHandle) {
+    let active = crate::settings::get_settings(app)
+        .quiet_hours
+        .is_quiet_now();
+    QUIET_HOURS_ACTIVE.store(active, Ordering::Release);
+    super::tray::sync_quiet_hours(app, active);
+    if active {
+        log::info!("Quiet hours active at launch");
+    }
+
+    let app = app.clone();
+    let result = thread::Builder::new()
+        .name("quiet-hours".to_string())
+        .spawn(move || loop {
+            thread::sleep(CHECK_INTERVAL);
+            refresh_quiet_hours(&app);
+        });
+    if let Err(error) = result {
+        log::error!("Failed to start quiet-hours scheduler: {error}");
+    }
+}
+
+/// Applies the current schedule immediately, e.g. after it is edited.
+pub(crate) fn refresh_quiet_hours(app: &AppHandle) {
+    let active = crate::settings::get_settings(app)
+        .quiet_hours
+        .is_quiet_now();
+    if QUIET_HOURS_ACTIVE.swap(active, Ordering::AcqRel) == active {
+        return;
+    }
+    log::info!("Quiet hours {}", if active { "started" } else { "ended" });
+    super::shortcuts::refresh_record_shortcut(app);
+    super::tray::sync_quiet_hours(app, active);
+    if let Err(error) = app.emit("quiet_hours_active", active) {
+        log::warn!("Could not emit quiet hours state: {error}");
+    }
+}
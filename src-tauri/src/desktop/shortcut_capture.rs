@@ -0,0 +1,116 @@
+use std::sync::{mpsc, Mutex};
+use std::time::{Duration, Instant};
+
+use rdev::Key;
+use tauri::AppHandle;
+use tauri_plugin_global_shortcut::{Code, Modifiers, Shortcut};
+
+use super::key_hook::{shortcut_code, MODIFIER_KINDS};
+use super::modifiers;
+use super::shortcuts::ShortcutError;
+
+/// How long capture waits for a chord.
+const CAPTURE_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// Receives each key press, with the modifier kinds held, while a shortcut
+/// is being captured.
+static CAPTURE: Mutex<Option<mpsc::Sender<(Key, u8)>>> = Mutex::new(None);
+
+/// Keys that make a shortcut alone, being unused while typing.
+const BARE_KEYS: &[Code] = &[
+    Code::F1,
+    Code::F2,
+    Code::F3,
+    Code::F4,
+    Code::F5,
+    Code::F6,
+    Code::F7,
+    Code::F8,
+    Code::F9,
+    Code::F10,
+    Code::F11,
+    Code::F12,
+    Code::F13,
+    Code::F14,
+    Code::F15,
+    Code::F16,
+    Code::F17,
+    Code::F18,
+    Code::F19,
+    Code::F20,
+    Code::F21,
+    Code::F22,
+    Code::F23,
+    Code::F24,
+    Code::Pause,
+    Code::ScrollLock,
+    Code::PrintScreen,
+    Code::Insert,
+];
+
+/// Waits for the next key chord and returns it as a shortcut string the
+/// settings can save, or `None` when Escape is pressed alone. Registered
+/// shortcuts are ignored meanwhile, so the current one can be pressed again.
+pub(crate) fn begin_shortcut_capture(app: &AppHandle) -> Result<Option<String>, ShortcutError> {
+    super::triggers::start_input_listener(app)
+        .map_err(|error| ShortcutError::Capture(error.to_string()))?;
+    let (tx, rx) = mpsc::channel();
+    if let Ok(mut capture) = CAPTURE.lock() {
+        *capture = Some(tx);
+    }
+    let result = next_chord(&rx);
+    if let Ok(mut capture) = CAPTURE.lock() {
+        capture.take();
+    }
+    let shortcut = result?.map(|shortcut| shortcut.into_string());
+    log::info!("Captured shortcut {shortcut:?}");
+    Ok(shortcut)
+}
+
+fn next_chord(rx: &mpsc::Receiver<(Key, u8)>) -> Result<Option<Shortcut>, ShortcutError> {
+    let deadline = Instant::now() + CAPTURE_TIMEOUT;
+    loop {
+        let remaining = deadline.saturating_duration_since(Instant::now());
+        let (key, held) = rx
+            .recv_timeout(remaining)
+            .map_err(|_| ShortcutError::NoChordPressed)?;
+        if modifiers::is_modifier(key) {
+            continue;
+        }
+        if key == Key::Escape && held == 0 {
+            return Ok(None);
+        }
+        let code = shortcut_code(key).ok_or(ShortcutError::UnsupportedKey)?;
+        return chord_shortcut(code, held).map(Some);
+    }
+}
+
+/// Passes a key press from the input listener to a running capture.
+pub(super) fn observe_key(key: Key) {
+    if let Some(capture) = CAPTURE.lock().ok().and_then(|capture| capture.clone()) {
+        let _ = capture.send((key, modifiers::held_kinds()));
+    }
+}
+
+pub(super) fn is_capturing() -> bool {
+    CAPTURE
+        .lock()
+        .map(|capture| capture.is_some())
+        .unwrap_or(false)
+}
+
+/// The shortcut for `code` pressed with the modifier kinds in `held`. A key
+/// that types text needs a modifier, or holding it to dictate would type it.
+pub fn chord_shortcut(code: Code, held: u8) -> Result<Shortcut, ShortcutError> {
+    let mods = MODIFIER_KINDS
+        .iter()
+        .filter(|(_, kind)| held & kind != 0)
+        .fold(Modifiers::empty(), |mods, (modifier, _)| mods | *modifier);
+    if mods.is_empty() && !BARE_KEYS.contains(&code) {
+        return Err(ShortcutError::NeedsModifier);
+    }
+    Ok(Shortcut::new(
+        Some(mods).filter(|mods| !mods.is_empty()),
+        code,
+    ))
+}
@@ -0,0 +1,313 @@
+use std::sync::Mutex;
+
+use serde::Serialize;
+use tauri::{AppHandle, Emitter};
+use tauri_plugin_global_shortcut::{Code, GlobalShortcutExt, Modifiers, Shortcut};
+
+use super::lookback::lookback_shortcut;
+use super::shortcuts::{dictation_toggle_shortcut, llm_handoff_shortcut, numbers_mode_shortcut};
+use crate::sync::MutexRecover;
+
+pub const SHORTCUT_CONFLICT_EVENT: &str = "shortcut_conflict";
+
+/// How many free chords a conflict suggests.
+const SUGGESTION_LIMIT: usize = 3;
+
+/// The record shortcut's last failed registration, until one succeeds.
+static LAST_CONFLICT: Mutex<Option<ShortcutConflict>> = Mutex::new(None);
+
+/// Chords other software is known to take, by the OS they are taken on.
+const KNOWN_CHORDS: &[(&str, Modifiers, Code, &str)] = &[
+    (
+        "windows",
+        Modifiers::ALT,
+        Code::KeyZ,
+        "NVIDIA GeForce overlay",
+    ),
+    (
+        "windows",
+        Modifiers::ALT,
+        Code::KeyR,
+        "AMD Radeon Software overlay",
+    ),
+    ("windows", Modifiers::ALT, Code::Space, "PowerToys Run"),
+    (
+        "windows",
+        Modifiers::SUPER,
+        Code::KeyH,
+        "Windows voice typing",
+    ),
+    (
+        "windows",
+        Modifiers::SUPER,
+        Code::KeyV,
+        "Windows clipboard history",
+    ),
+    (
+        "windows",
+        Modifiers::SUPER,
+        Code::Period,
+        "Windows emoji panel",
+    ),
+    (
+        "windows",
+        Modifiers::SUPER.union(Modifiers::SHIFT),
+        Code::KeyS,
+        "Snipping Tool",
+    ),
+    (
+        "windows",
+        Modifiers::CONTROL.union(Modifiers::SHIFT),
+        Code::Escape,
+        "Task Manager",
+    ),
+    ("macos", Modifiers::SUPER, Code::Space, "Spotlight"),
+    (
+        "macos",
+        Modifiers::CONTROL,
+        Code::Space,
+        "macOS input source switching",
+    ),
+    ("macos", Modifiers::ALT, Code::Space, "Alfred"),
+    (
+        "macos",
+        Modifiers::SUPER.union(Modifiers::SHIFT),
+        Code::Digit3,
+        "macOS screenshots",
+    ),
+    (
+        "macos",
+        Modifiers::SUPER.union(Modifiers::SHIFT),
+        Code::Digit4,
+        "macOS screenshots",
+    ),
+    (
+        "macos",
+        Modifiers::SUPER.union(Modifiers::SHIFT),
+        Code::Digit5,
+        "macOS screenshots",
+    ),
+    (
+        "macos",
+        Modifiers::SUPER.union(Modifiers::ALT),
+        Code::Escape,
+        "Force Quit",
+    ),
+    (
+        "linux",
+        Modifiers::CONTROL.union(Modifiers::ALT),
+        Code::KeyT,
+        "the desktop's terminal shortcut",
+    ),
+    (
+        "linux",
+        Modifiers::ALT,
+        Code::F2,
+        "the desktop's run dialog",
+    ),
+    (
+        "linux",
+        Modifiers::SUPER,
+        Code::Space,
+        "desktop input source switching",
+    ),
+    (
+        "linux",
+        Modifiers::SUPER,
+        Code::KeyL,
+        "the desktop's screen lock",
+    ),
+];
+
+/// Keys next to each other on a US layout, for suggesting a chord a finger
+/// away from the one that is taken.
+const KEY_ROWS: &[&[Code]] = &[
+    &[
+        Code::Digit1,
+        Code::Digit2,
+        Code::Digit3,
+        Code::Digit4,
+        Code::Digit5,
+        Code::Digit6,
+        Code::Digit7,
+        Code::Digit8,
+        Code::Digit9,
+        Code::Digit0,
+    ],
+    &[
+        Code::KeyQ,
+        Code::KeyW,
+        Code::KeyE,
+        Code::KeyR,
+        Code::KeyT,
+        Code::KeyY,
+        Code::KeyU,
+        Code::KeyI,
+        Code::KeyO,
+        Code::KeyP,
+    ],
+    &[
+        Code::KeyA,
+        Code::KeyS,
+        Code::KeyD,
+        Code::KeyF,
+        Code::KeyG,
+        Code::KeyH,
+        Code::KeyJ,
+        Code::KeyK,
+        Code::KeyL,
+    ],
+    &[
+        Code::KeyZ,
+        Code::KeyX,
+        Code::KeyC,
+        Code::KeyV,
+        Code::KeyB,
+        Code::KeyN,
+        Code::KeyM,
+    ],
+    &[
+        Code::F1,
+        Code::F2,
+        Code::F3,
+        Code::F4,
+        Code::F5,
+        Code::F6,
+        Code::F7,
+        Code::F8,
+        Code::F9,
+        Code::F10,
+        Code::F11,
+        Code::F12,
+    ],
+];
+
+/// A record shortcut the OS would not register, usually because another app
+/// owns it, with who likely does and free chords to use instead.
+#[derive(Clone, Debug, PartialEq, Serialize)]
+pub struct ShortcutConflict {
+    pub shortcut: String,
+    /// Apps or OS features known to use the chord; empty when it is unknown
+    /// which one does.
+    pub likely_owners: Vec<&'static str>,
+    pub suggestions: Vec<String>,
+}
+
+/// Who is known to use `shortcut` on this OS, SilentKeys' own fixed
+/// shortcuts included.
+pub fn likely_conflicts(shortcut: &Shortcut) -> Vec<&'static str> {
+    let own = [
+        (dictation_toggle_shortcut(), "SilentKeys dictation toggle"),
+        (llm_handoff_shortcut(), "SilentKeys LLM handoff"),
+        (lookback_shortcut(), "SilentKeys lookback"),
+        (numbers_mode_shortcut(), "SilentKeys numbers mode"),
+    ];
+    let known = KNOWN_CHORDS
+        .iter()
+        .filter(|(os, ..)| *os == std::env::consts::OS)
+        .map(|(_, mods, code, owner)| (Shortcut::new(Some(*mods), *code), *owner));
+    own.into_iter()
+        .chain(known)
+        .filter(|(chord, _)| chord == shortcut)
+        .map(|(_, owner)| owner)
+        .collect()
+}
+
+/// Chords to try instead of `shortcut`, closest first: the same key with
+/// one more modifier, then the keys beside it with the same modifiers.
+/// Chords with a known owner are left out.
+pub fn fallback_candidates(shortcut: &Shortcut) -> Vec<Shortcut> {
+    let mods = shortcut.mods;
+    let code = shortcut.key;
+    let more_modifiers = [
+        Modifiers::SHIFT,
+        Modifiers::CONTROL,
+        Modifiers::ALT,
+        Modifiers::SUPER,
+    ]
+    .into_iter()
+    .filter(|extra| !mods.contains(*extra))
+    .map(|extra| Shortcut::new(Some(mods | extra), code));
+    let neighbours = KEY_ROWS.iter().flat_map(|row| {
+        let index = row.iter().position(|key| *key == code);
+        let before = index.and_then(|index| index.checked_sub(1));
+        let after = index.map(|index| index + 1);
+        [before, after]
+            .into_iter()
+            .filter_map(|near| row.get(near?))
+            .map(|near| Shortcut::new(Some(mods), *near))
+            .collect::<Vec<_>>()
+    });
+
+    let mut candidates: Vec<Shortcut> = Vec::new();
+    for candidate in more_modifiers.chain(neighbours) {
+        if candidate != *shortcut
+            && likely_conflicts(&candidate).is_empty()
+            && !candidates.contains(&candidate)
+        {
+            candidates.push(candidate);
+        }
+    }
+    candidates
+}
+
+/// Describes why `shortcut` could not be registered, offering candidates the
+/// OS lets this app register right now. Records and emits it so the settings
+/// can show it even when it happened at startup.
+pub(super) fn report_conflict(app: &AppHandle, shortcut: &Shortcut) -> ShortcutConflict {
+    let suggestions = fallback_candidates(shortcut)
+        .into_iter()
+        .filter(|candidate| is_free(app, candidate))
+        .take(SUGGESTION_LIMIT)
+        .map(|candidate| candidate.into_string())
+        .collect();
+    let conflict = ShortcutConflict {
+        shortcut: shortcut.into_string(),
+        likely_owners: likely_conflicts(shortcut),
+        suggestions,
+    };
+    log::warn!(
+        "Record shortcut {} is taken (likely by {:?}); free alternatives: {:?}",
+        conflict.shortcut,
+        conflict.likely_owners,
+        conflict.suggestions
+    );
+    *LAST_CONFLICT.lock_recover() = Some(conflict.clone());
+    emit_conflict(app, Some(&conflict));
+    conflict
+}
+
+/// Forgets the last conflict once the record shortcut registers.
+pub(super) fn clear_conflict(app: &AppHandle) {
+    if LAST_CONFLICT.lock_recover().take().is_some() {
+        emit_conflict(app, None);
+    }
+}
+
+pub(crate) fn shortcut_conflict() -> Option<ShortcutConflict> {
+    LAST_CONFLICT.lock_recover().clone()
+}
+
+/// Emits the conflict, or `null` once it is resolved.
+fn emit_conflict(app: &AppHandle, conflict: Option<&ShortcutConflict>) {
+    if let Err(error) = app.emit(SHORTCUT_CONFLICT_EVENT, conflict) {
+        log::warn!("Could not emit {SHORTCUT_CONFLICT_EVENT}: {error}");
+    }
+}
+
+/// Whether the OS lets this app take `shortcut`, checked by registering it
+/// and releasing it at once.
+fn is_free(app: &AppHandle, shortcut: &Shortcut) -> bool {
+    if app.global_shortcut().is_registered(*shortcut) {
+        return false;
+    }
+    match app.global_shortcut().register(*shortcut) {
+        Ok(()) => {
+            if let Err(error) = app.global_shortcut().unregister(*shortcut) {
+                log::warn!("Could not release probed shortcut: {error}");
+            }
+            true
+        }
+        Err(_) => false,
+    }
+}
@@ -1,19 +1,158 @@
-use std::sync::{Mutex, OnceLock};
+use std::sync::{mpsc, Mutex, OnceLock};
+use std::time::{Duration, Instant};
 
+use serde::Serialize;
 use tauri::{async_runtime, AppHandle, Emitter, Manager};
+use tauri_plugin_clipboard_manager::ClipboardExt;
 use tauri_plugin_global_shortcut::{
     Code, GlobalShortcutExt, Modifiers, Shortcut, ShortcutEvent, ShortcutState,
 };
 
-use super::typing::{append_streaming_text, deliver_final_text, reset_buffer};
+use super::announce::{announce, speak};
+use super::caret::char_before_caret;
+use super::document::capture_patch;
+use super::focus::{focused_app, focused_window};
+use super::inactivity::watch_recording;
+use super::key_hook::sync_key_suppression;
+use super::onboarding::observe_onboarding;
+use super::shortcut_conflicts::{clear_conflict, report_conflict, ShortcutConflict};
+use super::typing::{
+    append_streaming_text, deliver_final_text, reset_buffer, set_lead_in, set_target_window,
+    untyped_final_text, TypingError,
+};
 use crate::engine::SpeechEngine;
 use crate::errors::UserFacing;
-use crate::recording::{RecordingError, RecordingReservation};
+use crate::onboarding::OnboardingEvent;
+use crate::output_template::OutputTemplates;
+use crate::patch_routing::PatchSource;
+use crate::recording::{AudioInput, RecordingError, RecordingReservation, SessionId};
+use crate::settings::{Settings, SettingsServiceError, TypingPace};
+use crate::shortcut_gesture::{GestureAction, GestureInput, GestureMachine, GestureMode};
+use crate::streaming::{RecordingMode, Transcript, TranscriptPatch};
+use crate::sync::MutexRecover;
+use crate::target_lock::TargetLock;
+use crate::typed_metadata::COMMIT_DELIMITER;
 
 const SHORTCUT_STORE: &str = "settings.json";
 const SHORTCUT_STORE_KEY: &str = "record_shortcut";
 
 static ACTIVE_SHORTCUT: OnceLock<Mutex<Option<Shortcut>>> = OnceLock::new();
+/// Which shortcut started or queued the last recording; read once when that
+/// recording is stopped.
+static REQUESTED_OUTPUT: Mutex<DictationOutput> = Mutex::new(DictationOutput::Type);
+/// How the queued recording, if any, starts once the last one finishes.
+static QUEUED_START: Mutex<QueuedStart> = Mutex::new(QueuedStart::PUSH_TO_TALK);
+/// Double-tap and chord state of the record shortcut.
+static GESTURE: Mutex<GestureMachine> =
+    Mutex::new(GestureMachine::new(GestureMode::Hold, Duration::ZERO));
+/// The chord's follow-up shortcut while it is registered.
+static FOLLOW_UP: Mutex<Option<Shortcut>> = Mutex::new(None);
+
+/// A push-to-talk start ends on release; a gesture or command start is
+/// latched and may override the recording mode.
+#[derive(Clone, Copy)]
+struct QueuedStart {
+    latched: bool,
+    mode: Option<RecordingMode>,
+}
+
+impl QueuedStart {
+    const PUSH_TO_TALK: Self = Self {
+        latched: false,
+        mode: None,
+    };
+}
+
+#[derive(thiserror::Error, Debug)]
+pub enum ShortcutError {
+    #[error("Invalid shortcut: {0}")]
+    Invalid(String),
+    #[error("store record shortcut: {0}")]
+    Store(#[source] tauri_plugin_store::Error),
+    #[error("register record shortcut: {0}")]
+    Register(#[source] tauri_plugin_global_shortcut::Error),
+    #[error("register record shortcut {}: {source}", conflict.shortcut)]
+    Conflict {
+        conflict: Box<ShortcutConflict>,
+        #[source]
+        source: tauri_plugin_global_shortcut::Error,
+    },
+    #[error("{primary}; shortcut rollback failed: {rollback}")]
+    Rollback {
+        primary: Box<ShortcutError>,
+        rollback: tauri_plugin_global_shortcut::Error,
+    },
+    #[error("record shortcut state lock poisoned")]
+    LockFailed,
+    #[error("listen for a shortcut: {0}")]
+    Capture(String),
+    #[error("no shortcut was pressed")]
+    NoChordPressed,
+    #[error("key cannot be a shortcut")]
+    UnsupportedKey,
+    #[error("shortcut key needs a modifier")]
+    NeedsModifier,
+    #[error("mode shortcut {0} is already in use")]
+    InUse(String),
+}
+
+impl UserFacing for ShortcutError {
+    fn user_message(&self) -> &'static str {
+        match self {
+            Self::Invalid(_) => "That is not a valid shortcut.",
+            Self::Store(_) => "Could not save the record shortcut. Please try again.",
+            Self::Register(_) | Self::Rollback { .. } => {
+                "Could not register the record shortcut. Another app may already use it."
+            }
+            Self::Conflict { .. } => {
+                "Another app already uses that shortcut. Pick one of the suggested shortcuts or another chord."
+            }
+            Self::LockFailed => "The record shortcut is temporarily unavailable.",
+            Self::Capture(_) => {
+                "Could not listen for a shortcut. On macOS, allow SilentKeys under Accessibility."
+            }
+            Self::NoChordPressed => "No shortcut was pressed. Press the keys while recording.",
+            Self::UnsupportedKey => "That key cannot be used in a shortcut. Try another key.",
+            Self::NeedsModifier => {
+                "Hold Ctrl, Alt, Shift, or the Windows or Command key with that key, or use a function key."
+            }
+            Self::InUse(_) => {
+                "The record shortcut or another app already uses this mode's shortcut. Change the record shortcut or close the other app first."
+            }
+        }
+    }
+}
+
+/// Receives a copy of a dictation's live patches and then its final text as a
+/// `Replace`, for clients that show the transcript on another device.
+pub(super) type TranscriptObserver = mpsc::Sender<TranscriptPatch>;
+
+/// What a shortcut does with its finished transcript.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+enum DictationOutput {
+    Type,
+    LlmHandoff,
+    Numbers,
+}
+
+impl DictationOutput {
+    /// Numbers mode types the digits alone, without the record template.
+    fn template(self, templates: &OutputTemplates) -> &str {
+        match self {
+            Self::Type => &templates.record,
+            Self::LlmHandoff => &templates.llm_handoff,
+            Self::Numbers => "",
+        }
+    }
+
+    /// Partial text is typed only when the final text will be typed and
+    /// extend it.
+    fn types_partials(self, settings: &Settings) -> bool {
+        self == Self::Type
+            && !settings.capture_file.enabled
+            && self.template(&settings.output_templates).trim().is_empty()
+    }
+}
 
 fn active_shortcut() -> &'static Mutex<Option<Shortcut>> {
     ACTIVE_SHORTCUT.get_or_init(|| Mutex::new(None))
@@ -24,24 +163,38 @@ pub fn default_shortcut() -> Shortcut {
     Shortcut::new(Some(Modifiers::ALT), Code::KeyZ)
 }
 
+/// Fixed shortcut that switches shortcut dictation on and off. Registered only
+/// while the dictation toggle shortcut setting is on.
+pub fn dictation_toggle_shortcut() -> Shortcut {
+    Shortcut::new(Some(Modifiers::ALT | Modifiers::SHIFT), Code::KeyZ)
+}
+
+/// Fixed shortcut that records like the record shortcut but types the LLM's
+/// rewrite of the transcript. Registered only while the handoff is enabled.
+pub fn llm_handoff_shortcut() -> Shortcut {
+    Shortcut::new(Some(Modifiers::ALT), Code::KeyX)
+}
+
+/// Fixed shortcut that records like the record shortcut but types only the
+/// digits and separators heard. Registered only while numbers mode is on.
+pub fn numbers_mode_shortcut() -> Shortcut {
+    Shortcut::new(Some(Modifiers::ALT), Code::KeyN)
+}
+
 #[doc(hidden)]
-pub fn parse_shortcut_str(s: &str) -> Result<Shortcut, String> {
+pub fn parse_shortcut_str(s: &str) -> Result<Shortcut, ShortcutError> {
     s.parse::<Shortcut>()
-        .map_err(|e| format!("Invalid shortcut: {e}"))
+        .map_err(|e| ShortcutError::Invalid(e.to_string()))
 }
 
-fn persist_shortcut(app: &AppHandle, shortcut: &Shortcut) -> Result<(), String> {
+fn persist_shortcut(app: &AppHandle, shortcut: &Shortcut) -> Result<(), ShortcutError> {
     use tauri_plugin_store::StoreExt;
-    let store = app
-        .store(SHORTCUT_STORE)
-        .map_err(|e| format!("Could not open settings store: {e}"))?;
+    let store = app.store(SHORTCUT_STORE).map_err(ShortcutError::Store)?;
     store.set(
         SHORTCUT_STORE_KEY.to_string(),
         serde_json::json!(shortcut.into_string()),
     );
-    store
-        .save()
-        .map_err(|e| format!("Could not persist shortcut: {e}"))
+    store.save().map_err(ShortcutError::Store)
 }
 
 fn load_persisted_shortcut(app: &AppHandle) -> Option<String> {
@@ -52,52 +205,345 @@ fn load_persisted_shortcut(app: &AppHandle) -> Option<String> {
         .and_then(|value| value.as_str().map(ToString::to_string))
 }
 
+/// The `dictation_error` event payload.
+#[derive(Clone, Debug, Serialize)]
+struct DictationErrorEvent {
+    session_id: Option<SessionId>,
+    message: &'static str,
+}
+
 /// Logs the full error and shows the user-facing message in the app window,
 /// since shortcut-driven dictation has no other visible failure surface.
-fn report_failure(app: &AppHandle, context: &str, error: &(impl UserFacing + std::fmt::Display)) {
-    log::error!("{context}: {error}");
-    if let Err(emit_error) = app.emit("dictation_error", error.user_message()) {
+pub(super) fn report_failure(
+    app: &AppHandle,
+    context: &str,
+    error: &(impl UserFacing + std::fmt::Display),
+) {
+    let session_id = app.state::<SpeechEngine>().recorder().session_id();
+    match session_id {
+        Some(session_id) => log::error!("{context} (session {session_id}): {error}"),
+        None => log::error!("{context}: {error}"),
+    }
+    let event = DictationErrorEvent {
+        session_id,
+        message: error.user_message(),
+    };
+    if let Err(emit_error) = app.emit("dictation_error", event) {
         log::warn!("Could not report dictation error to UI: {emit_error}");
     }
 }
 
-fn make_handler() -> impl Fn(&AppHandle, &Shortcut, ShortcutEvent) + Send + Sync {
+fn make_handler(
+    output: DictationOutput,
+) -> impl Fn(&AppHandle, &Shortcut, ShortcutEvent) + Send + Sync {
     move |app: &AppHandle, _, event| {
-        let engine = app.state::<SpeechEngine>();
-        match event.state() {
-            ShortcutState::Pressed if !engine.is_dictating() => {
-                log::info!("Shortcut PRESSED -> Starting recording");
-                match engine.reserve_dictation() {
-                    Ok(reservation) => start_recording_async(app, reservation),
-                    Err(err) => report_failure(app, "Failed to reserve recording", &err),
+        // The chord being recorded as a new shortcut may be this one.
+        if super::shortcut_capture::is_capturing() {
+            return;
+        }
+        handle_record_trigger(app, output, event.state())
+    }
+}
+
+/// Mouse buttons and pedals bound to recording go through here too, so they
+/// follow the record shortcut's gesture.
+pub(super) fn record_trigger(app: &AppHandle, state: ShortcutState) {
+    handle_record_trigger(app, DictationOutput::Type, state);
+}
+
+fn handle_record_trigger(app: &AppHandle, output: DictationOutput, state: ShortcutState) {
+    if output == DictationOutput::Type {
+        let gesture = crate::settings::get_settings(app).shortcut_gesture;
+        if gesture.mode != GestureMode::Hold {
+            let input = match state {
+                ShortcutState::Pressed => GestureInput::Pressed,
+                ShortcutState::Released => GestureInput::Released,
+            };
+            handle_gesture(app, input);
+            return;
+        }
+    }
+    let engine = app.state::<SpeechEngine>();
+    match state {
+        ShortcutState::Pressed if !record_shortcut_allowed(app) => {
+            log::info!("Shortcut PRESSED while dictation is disabled or quiet; ignoring");
+        }
+        ShortcutState::Pressed if !engine.is_dictating() => {
+            if !engine.is_ready() {
+                let buffering = crate::settings::get_settings(app).buffer_until_model_ready;
+                engine.notify_model_not_ready(buffering);
+                if !buffering {
+                    return;
+                }
+            }
+            log::info!("Shortcut PRESSED -> Starting recording ({output:?})");
+            *REQUESTED_OUTPUT.lock_recover() = output;
+            *QUEUED_START.lock_recover() = QueuedStart::PUSH_TO_TALK;
+            // Held during the last utterance's transcription, the recording
+            // starts once that finishes; releasing first cancels it.
+            match engine.reserve_or_queue_dictation() {
+                Ok(Some(reservation)) => {
+                    start_recording_async(app, reservation, output, None, false, None)
+                }
+                Ok(None) => {
+                    log::info!("Previous dictation still finishing; queueing recording start");
                 }
+                Err(err) => report_failure(app, "Failed to reserve recording", &err),
             }
-            ShortcutState::Released if engine.is_dictating() => {
-                log::info!("Shortcut RELEASED -> Stopping recording");
+        }
+        ShortcutState::Released if engine.cancel_queued_dictation() => {
+            log::info!("Shortcut RELEASED before queued recording started; cancelled");
+        }
+        ShortcutState::Released if engine.is_dictating() => {
+            log::info!("Shortcut RELEASED -> Stopping recording");
+            stop_recording_async(app);
+        }
+        _ => {}
+    }
+}
+
+/// Feeds the record shortcut's gesture machine and carries out what it asks.
+fn handle_gesture(app: &AppHandle, input: GestureInput) {
+    let gesture = crate::settings::get_settings(app).shortcut_gesture;
+    let engine = app.state::<SpeechEngine>();
+    let (action, deadline) = {
+        let mut machine = GESTURE.lock_recover();
+        machine.configure(gesture.mode, gesture.window());
+        // A queued start counts as recording, so the gesture can latch or
+        // cancel it before it begins.
+        machine.sync(engine.is_dictating() || engine.has_queued_dictation());
+        let action = machine.handle(input, Instant::now());
+        (action, machine.deadline())
+    };
+    if let Some(deadline) = deadline {
+        schedule_gesture_expiry(app, deadline);
+    }
+    match action {
+        GestureAction::None => {}
+        GestureAction::Start => start_gesture_recording(app, None),
+        GestureAction::Stop if engine.cancel_queued_dictation() => {
+            log::info!("Shortcut gesture before queued recording started; cancelled");
+        }
+        GestureAction::Stop if engine.is_dictating() => {
+            log::info!("Shortcut gesture -> Stopping recording");
+            stop_recording_async(app);
+        }
+        GestureAction::Stop => {}
+        GestureAction::Toggle => {
+            disarm_follow_up(app);
+            if engine.cancel_queued_dictation() {
+                log::info!("Shortcut chord before queued recording started; cancelled");
+            } else if engine.is_dictating() {
+                log::info!("Shortcut chord -> Stopping recording");
                 stop_recording_async(app);
+            } else {
+                start_gesture_recording(app, None);
+            }
+        }
+        GestureAction::ArmFollowUp => arm_follow_up(app, &gesture.follow_up),
+        GestureAction::DisarmFollowUp => disarm_follow_up(app),
+    }
+}
+
+/// Feeds [`GestureInput::Expired`] once `deadline` passes. One thread waits
+/// for the latest deadline only; an earlier one it replaces never fires, and
+/// one the machine has moved past by the time it passes is skipped.
+fn schedule_gesture_expiry(app: &AppHandle, deadline: Instant) {
+    static TIMER: OnceLock<Option<mpsc::Sender<Instant>>> = OnceLock::new();
+    let timer = TIMER.get_or_init(|| {
+        let (deadline_tx, deadline_rx) = mpsc::channel();
+        let app = app.clone();
+        let spawned = std::thread::Builder::new()
+            .name("shortcut-gesture-timer".to_string())
+            .spawn(move || run_gesture_timer(&app, &deadline_rx));
+        match spawned {
+            Ok(_) => Some(deadline_tx),
+            Err(error) => {
+                log::warn!("Could not start the shortcut gesture timer: {error}");
+                None
+            }
+        }
+    });
+    if let Some(deadline_tx) = timer {
+        let _ = deadline_tx.send(deadline);
+    }
+}
+
+fn run_gesture_timer(app: &AppHandle, deadlines: &mpsc::Receiver<Instant>) {
+    let mut pending = None;
+    loop {
+        let received = match pending {
+            Some(deadline) => {
+                deadlines.recv_timeout(deadline.saturating_duration_since(Instant::now()))
+            }
+            None => deadlines.recv().map_err(mpsc::RecvTimeoutError::from),
+        };
+        match received {
+            Ok(deadline) => pending = Some(deadline),
+            Err(mpsc::RecvTimeoutError::Timeout) => {
+                let deadline = pending.take();
+                if GESTURE.lock_recover().deadline() == deadline {
+                    handle_gesture(app, GestureInput::Expired);
+                }
+            }
+            Err(mpsc::RecvTimeoutError::Disconnected) => return,
+        }
+    }
+}
+
+fn start_gesture_recording(app: &AppHandle, mode: Option<RecordingMode>) {
+    let engine = app.state::<SpeechEngine>();
+    let buffering = crate::settings::get_settings(app).buffer_until_model_ready;
+    if !record_shortcut_allowed(app) {
+        log::info!("Shortcut gesture while dictation is disabled or quiet; ignoring");
+    } else if engine.is_dictating() {
+        log::info!("Shortcut gesture while recording; ignoring");
+    } else if !engine.is_ready() && !buffering {
+        engine.notify_model_not_ready(false);
+    } else {
+        if !engine.is_ready() {
+            engine.notify_model_not_ready(true);
+        }
+        log::info!("Shortcut gesture -> Starting recording");
+        *REQUESTED_OUTPUT.lock_recover() = DictationOutput::Type;
+        *QUEUED_START.lock_recover() = QueuedStart {
+            latched: true,
+            mode,
+        };
+        // During the last utterance's transcription, the recording starts
+        // once that finishes.
+        match engine.reserve_or_queue_dictation() {
+            Ok(Some(reservation)) => {
+                start_recording_async(app, reservation, DictationOutput::Type, None, true, mode);
+                return;
             }
-            _ => {}
+            Ok(None) => {
+                log::info!("Previous dictation still finishing; queueing recording start");
+                return;
+            }
+            Err(err) => report_failure(app, "Failed to reserve recording", &err),
         }
     }
+    // Nothing is recording, so the gesture has nothing to latch or stop.
+    GESTURE.lock_recover().reset();
+}
+
+/// Starts a dictation asked for from outside the app, such as a second
+/// launch's command line or a deep link, typed like a shortcut's. `mode`
+/// overrides how this one recording is transcribed.
+pub(super) fn start_command_recording(app: &AppHandle, mode: Option<RecordingMode>) {
+    log::info!("Command line -> Starting recording ({mode:?})");
+    start_gesture_recording(app, mode);
+}
+
+pub(super) fn stop_command_recording(app: &AppHandle) {
+    if app.state::<SpeechEngine>().is_dictating() {
+        log::info!("Command line -> Stopping recording");
+        stop_recording_async(app);
+    }
+}
+
+/// Drops any gesture in progress after its settings change.
+pub(crate) fn reset_shortcut_gesture(app: &AppHandle) {
+    GESTURE.lock_recover().reset();
+    disarm_follow_up(app);
+}
+
+/// Registration changes run off the shortcut callback, which holds the
+/// plugin's handler table.
+fn arm_follow_up(app: &AppHandle, follow_up: &str) {
+    let shortcut = match parse_shortcut_str(follow_up) {
+        Ok(shortcut) => shortcut,
+        Err(error) => {
+            log::warn!("Chord follow-up {follow_up:?} is not a shortcut: {error}");
+            return;
+        }
+    };
+    let app = app.clone();
+    async_runtime::spawn_blocking(move || {
+        let mut registered = FOLLOW_UP.lock_recover();
+        if registered.is_some() {
+            return;
+        }
+        let handler = |app: &AppHandle, _: &Shortcut, event: ShortcutEvent| {
+            if matches!(event.state(), ShortcutState::Pressed) {
+                handle_gesture(app, GestureInput::FollowUp);
+            }
+        };
+        match app.global_shortcut().on_shortcut(shortcut, handler) {
+            Ok(()) => *registered = Some(shortcut),
+            Err(error) => log::warn!("Could not listen for chord follow-up: {error}"),
+        }
+    });
+}
+
+fn disarm_follow_up(app: &AppHandle) {
+    let app = app.clone();
+    async_runtime::spawn_blocking(move || {
+        let mut registered = FOLLOW_UP.lock_recover();
+        if let Some(shortcut) = registered.take() {
+            if let Err(error) = app.global_shortcut().unregister(shortcut) {
+                log::warn!("Could not release chord follow-up: {error}");
+            }
+        }
+    });
 }
 
-fn start_recording_async(app: &AppHandle, reservation: RecordingReservation) {
+/// A `latched` recording keeps going until something stops it, so the
+/// inactivity rule watches it for silence. `mode` overrides the saved
+/// streaming settings for this recording.
+fn start_recording_async(
+    app: &AppHandle,
+    reservation: RecordingReservation,
+    output: DictationOutput,
+    observer: Option<TranscriptObserver>,
+    latched: bool,
+    mode: Option<RecordingMode>,
+) {
     let app = app.clone();
+    let session_id = reservation.session_id();
 
     async_runtime::spawn_blocking(move || {
         if let Err(error) = reset_buffer() {
             report_failure(&app, "Failed to reset typing state", &error);
             return;
         }
+        let settings = crate::settings::get_settings(&app);
+        let pace = settings.typing_pace;
+        let target_lock = pace.target_lock;
+        let types_partials = output.types_partials(&settings);
+        let caret_spacing = settings.caret_spacing;
+        let continuation_window = settings.continuation.window();
         let engine = app.state::<SpeechEngine>();
-        let result = engine.start_dictation(reservation, |update| {
-            if let crate::streaming::TranscriptionUpdate::Append(text) = update {
-                append_streaming_text(text).map_err(|error| error.to_string())?;
-            }
-            Ok(())
-        });
+        let mut router = engine
+            .patch_router(reservation.session_id(), PatchSource::Shortcut)
+            .with_typing(shortcut_typing(&app, pace))
+            .with_live_typing(types_partials);
+        if let Some(observer) = observer {
+            router = router.with_mirror(move |update| {
+                let _ = observer.send(update);
+            });
+        }
+        let result = engine.start_dictation_in(reservation, router, mode);
         if let Err(err) = result {
             report_failure(&app, "Failed to start recording", &err);
+            return;
+        }
+        if latched {
+            watch_recording(&app, session_id, stop_recording_async);
+        }
+        // Queried once capture is running so the accessibility round trips
+        // do not delay the start of the recording.
+        let caret = caret_spacing.then(char_before_caret).flatten();
+        let target = continuation_window.and_then(|_| focused_app());
+        if let Err(error) = set_lead_in(caret, target, continuation_window) {
+            log::warn!("Could not record the typing context: {error}");
+        }
+        let window = (target_lock != TargetLock::Off)
+            .then(focused_window)
+            .flatten();
+        if let Err(error) = set_target_window(window) {
+            log::warn!("Could not lock typing to the focused window: {error}");
         }
     });
 }
@@ -105,12 +551,26 @@ fn start_recording_async(app: &AppHandle, reservation: RecordingReservation) {
 fn stop_recording_async(app: &AppHandle) {
     let report_app = app.clone();
     let worker_app = app.clone();
+    let output = std::mem::replace(&mut *REQUESTED_OUTPUT.lock_recover(), DictationOutput::Type);
     let result = std::thread::Builder::new()
         .name("shortcut-stop".to_string())
         .spawn(move || {
             let engine = worker_app.state::<SpeechEngine>();
-            let result =
-                engine.finish_dictation(|text| deliver_final_text(text).map_err(|e| e.to_string()));
+            let router = engine.take_session_router();
+            let result = engine.finish_dictation(|text| {
+                observe_onboarding(&worker_app, OnboardingEvent::SampleDictated(text.clone()));
+                if output != DictationOutput::Type {
+                    // Rewritten text has no utterances to split.
+                    engine.take_held();
+                }
+                let settings = crate::settings::get_settings(&worker_app);
+                let text = final_output_text(&worker_app, &settings, output, text);
+                let delivered = router.finish(text.clone());
+                if delivered.is_ok() {
+                    read_back_text(&worker_app, &settings, text);
+                }
+                delivered
+            });
             if let Err(err) = result {
                 report_failure(&worker_app, "Failed to finish dictation", &err);
             }
@@ -124,42 +584,458 @@ fn stop_recording_async(app: &AppHandle) {
     }
 }
 
-fn register_record_shortcut(app: &AppHandle, shortcut: Shortcut) -> Result<String, String> {
-    let mut active = active_shortcut().lock().map_err(|e| e.to_string())?;
+/// The shortcut's text output: streamed text is typed as it commits, and the
+/// final text goes to the open document or the capture file when there is
+/// one, otherwise it is typed over the streamed text.
+fn shortcut_typing(
+    app: &AppHandle,
+    pace: TypingPace,
+) -> impl Fn(&TranscriptPatch, &Transcript) -> Result<(), String> + Send + Sync + 'static {
+    let app = app.clone();
+    move |patch, transcript| match patch {
+        _ if capture_patch(&app, patch, transcript) => Ok(()),
+        TranscriptPatch::Draft { .. } | TranscriptPatch::DraftTail { .. } => Ok(()),
+        TranscriptPatch::Commit { append } => {
+            append_streaming_text(append.clone(), pace).map_err(|error| error.to_string())
+        }
+        TranscriptPatch::Replace { .. } => {
+            let text = transcript.committed().to_string();
+            let settings = crate::settings::get_settings(&app);
+            if settings.capture_file.enabled && capture_text(&app, &settings, &text) {
+                return Ok(());
+            }
+            let typed = settings.typed_metadata.delimit(text);
+            match deliver_final_text(typed, settings.typing_pace) {
+                Err(error @ TypingError::TargetChanged(_)) => copy_untyped_text(&app, &error),
+                result => result.map_err(|error| error.to_string()),
+            }
+        }
+    }
+}
+
+/// Puts the part of a transcript the target lock kept from typing on the
+/// clipboard, so it can be pasted where it belongs.
+fn copy_untyped_text(app: &AppHandle, error: &TypingError) -> Result<(), String> {
+    let untyped = untyped_final_text().map_err(|error| error.to_string())?;
+    app.clipboard()
+        .write_text(untyped.trim_end_matches(COMMIT_DELIMITER))
+        .map_err(|error| format!("copy the untyped transcript: {error}"))?;
+    report_failure(app, "Typing target changed", error);
+    Ok(())
+}
+
+/// Runs the transcript through the shortcut's output stages: the LLM rewrite
+/// for handoff recordings or the digit filter for numbers mode, then the
+/// shortcut's output template.
+fn final_output_text(
+    app: &AppHandle,
+    settings: &Settings,
+    output: DictationOutput,
+    text: String,
+) -> String {
+    if text.is_empty() {
+        return text;
+    }
+    let text = match output {
+        DictationOutput::Type => text,
+        DictationOutput::LlmHandoff => handoff_text(app, settings, text),
+        DictationOutput::Numbers => crate::numbers_mode::constrain_to_digits(&text),
+    };
+    let template = output.template(&settings.output_templates);
+    crate::output_template::render(template, &text, chrono::Local::now().naive_local())
+}
+
+/// Appends the dictation to the capture file in place of typing it. Returns
+/// false after reporting a failed write so the caller types the text instead
+/// of losing it.
+pub(super) fn capture_text(app: &AppHandle, settings: &Settings, text: &str) -> bool {
+    if text.is_empty() {
+        return true;
+    }
+    let now = chrono::Local::now().naive_local();
+    let (title, text) = if settings.capture_file.name_entries {
+        let (title, body) = crate::capture_file::entry_title(text);
+        (Some(title), body)
+    } else {
+        (None, text)
+    };
+    let path = &settings.capture_file.path;
+    let appended = match app.state::<SpeechEngine>().take_held() {
+        Some(take) => crate::capture_file::append_exported_utterance_entry(
+            path,
+            title.as_deref(),
+            &take.transcript,
+            &take.samples,
+            &settings.capture_file.clip_export,
+            &settings.vad.config(),
+            now,
+        ),
+        None => crate::capture_file::append_titled_entry(path, title.as_deref(), text, now),
+    };
+    match appended {
+        Ok(()) => {
+            log::info!("Dictation appended to capture file");
+            true
+        }
+        Err(error) => {
+            report_failure(app, "Failed to append to capture file", &error);
+            false
+        }
+    }
+}
+
+/// Reads the delivered transcript back through the screen reader and the
+/// system voice, as enabled, on its own thread so slow speech does not hold up
+/// a queued recording.
+fn read_back_text(app: &AppHandle, settings: &Settings, text: String) {
+    let announce_enabled = settings.announce_transcripts;
+    let speak_enabled = settings.speak_transcripts;
+    if text.is_empty() || !(announce_enabled || speak_enabled) {
+        return;
+    }
+    let app = app.clone();
+    let result = std::thread::Builder::new()
+        .name("transcript-read-back".to_string())
+        .spawn(move || {
+            if announce_enabled {
+                if let Err(error) = announce(&app, &text) {
+                    report_failure(&app, "Failed to announce transcript", &error);
+                }
+            }
+            if speak_enabled {
+                if let Err(error) = speak(&text) {
+                    report_failure(&app, "Failed to read transcript aloud", &error);
+                }
+            }
+        });
+    if let Err(error) = result {
+        log::warn!("Could not start transcript read-back thread: {error}");
+    }
+}
+
+/// Returns the LLM's rewrite of `text`, or `text` itself when the request
+/// fails so the dictation is never lost.
+fn handoff_text(app: &AppHandle, settings: &Settings, text: String) -> String {
+    let rewritten = super::keychain::llm_api_key()
+        .and_then(|api_key| crate::llm_handoff::rewrite(&settings.llm_handoff, &api_key, &text));
+    match rewritten {
+        Ok(reply) => reply,
+        Err(error) => {
+            report_failure(app, "LLM handoff failed", &error);
+            text
+        }
+    }
+}
+
+/// Starts a dictation fed by a network audio connection. It is delivered like
+/// the record shortcut once [`finish_network_dictation`] is called. Returns
+/// false when no recording was started.
+pub(super) fn start_network_dictation(
+    app: &AppHandle,
+    input: AudioInput,
+    observer: Option<TranscriptObserver>,
+) -> bool {
+    let engine = app.state::<SpeechEngine>();
+    if !engine.is_ready() {
+        let buffering = crate::settings::get_settings(app).buffer_until_model_ready;
+        engine.notify_model_not_ready(buffering);
+        if !buffering {
+            return false;
+        }
+    }
+    match engine.reserve_dictation() {
+        Ok(reservation) => {
+            log::info!("Network audio connected -> Starting recording");
+            *REQUESTED_OUTPUT.lock_recover() = DictationOutput::Type;
+            start_recording_async(
+                app,
+                reservation.with_input(input),
+                DictationOutput::Type,
+                observer,
+                false,
+                None,
+            );
+            true
+        }
+        Err(err) => {
+            report_failure(app, "Failed to reserve network dictation", &err);
+            false
+        }
+    }
+}
+
+pub(super) fn finish_network_dictation(app: &AppHandle) {
+    if app.state::<SpeechEngine>().is_dictating() {
+        log::info!("Network audio ended -> Stopping recording");
+        stop_recording_async(app);
+    }
+}
+
+/// Starts a recording queued by the record shortcut, handed over by whichever
+/// dictation held the recorder when it finishes.
+fn start_queued_recording(app: &AppHandle, reservation: RecordingReservation) {
+    if !record_shortcut_allowed(app) {
+        log::info!("Dropping queued recording: dictation is disabled or quiet");
+        return;
+    }
+    let output = *REQUESTED_OUTPUT.lock_recover();
+    let QueuedStart { latched, mode } = *QUEUED_START.lock_recover();
+    start_recording_async(app, reservation, output, None, latched, mode);
+}
+
+fn register_record_shortcut(app: &AppHandle, shortcut: Shortcut) -> Result<String, ShortcutError> {
+    let mut active = active_shortcut()
+        .lock()
+        .map_err(|_| ShortcutError::LockFailed)?;
     if *active == Some(shortcut) {
         persist_shortcut(app, &shortcut)?;
+        drop(active);
+        sync_key_suppression(app, Some(shortcut));
         return Ok(shortcut.into_string());
     }
 
     app.global_shortcut()
-        .on_shortcut(shortcut, make_handler())
-        .map_err(|e| e.to_string())?;
+        .on_shortcut(shortcut, make_handler(DictationOutput::Type))
+        .map_err(|source| ShortcutError::Conflict {
+            conflict: Box::new(report_conflict(app, &shortcut)),
+            source,
+        })?;
+    clear_conflict(app);
     let previous = *active;
     if let Some(previous) = previous {
         if let Err(error) = app.global_shortcut().unregister(previous) {
             let _ = app.global_shortcut().unregister(shortcut);
-            return Err(error.to_string());
+            return Err(ShortcutError::Register(error));
         }
     }
     if let Err(error) = persist_shortcut(app, &shortcut) {
         let _ = app.global_shortcut().unregister(shortcut);
         if let Some(previous) = previous {
-            if let Err(rollback_error) = app.global_shortcut().on_shortcut(previous, make_handler())
+            if let Err(rollback_error) = app
+                .global_shortcut()
+                .on_shortcut(previous, make_handler(DictationOutput::Type))
             {
-                return Err(format!(
-                    "{error}; shortcut rollback failed: {rollback_error}"
-                ));
+                return Err(ShortcutError::Rollback {
+                    primary: Box::new(error),
+                    rollback: rollback_error,
+                });
             }
         }
         return Err(error);
     }
     let s = shortcut.into_string();
     *active = Some(shortcut);
+    drop(active);
+    sync_key_suppression(app, Some(shortcut));
     Ok(s)
 }
 
-pub fn update_record_shortcut(app: AppHandle, s: String) -> Result<String, String> {
-    register_record_shortcut(&app, parse_shortcut_str(&s)?)
+pub fn update_record_shortcut(app: AppHandle, s: String) -> Result<String, ShortcutError> {
+    let shortcut = parse_shortcut_str(&s)?;
+    if !record_shortcut_allowed(&app) {
+        persist_shortcut(&app, &shortcut)?;
+        return Ok(shortcut.into_string());
+    }
+    register_record_shortcut(&app, shortcut)
+}
+
+/// Persists the enabled state, then registers or releases the record
+/// shortcut and updates the tray switch and UI to match.
+pub(crate) fn set_dictation_enabled(
+    app: &AppHandle,
+    enabled: bool,
+) -> Result<(), SettingsServiceError> {
+    crate::settings::set_dictation_enabled(app, enabled)?;
+    apply_dictation_enabled(app, enabled);
+    Ok(())
+}
+
+/// Re-applies the stored enabled state, e.g. after settings are reset.
+pub(crate) fn sync_dictation_enabled(app: &AppHandle) {
+    apply_dictation_enabled(app, crate::settings::get_settings(app).dictation_enabled);
+}
+
+fn apply_dictation_enabled(app: &AppHandle, enabled: bool) {
+    refresh_record_shortcut(app);
+    log::info!("Dictation {}", if enabled { "enabled" } else { "disabled" });
+    super::tray::sync_dictation_item(app, enabled);
+    if let Err(error) = app.emit("dictation_enabled", enabled) {
+        log::warn!("Could not emit dictation enabled state: {error}");
+    }
+}
+
+/// The record shortcut listens only while dictation is enabled and outside
+/// quiet hours.
+pub(super) fn record_shortcut_allowed(app: &AppHandle) -> bool {
+    crate::settings::get_settings(app).dictation_enabled && !super::quiet_hours::is_active()
+}
+
+/// Registers or releases the record shortcut to match
+/// [`record_shortcut_allowed`]. Releasing stops any recording in progress,
+/// since its release event can no longer arrive.
+pub(super) fn refresh_record_shortcut(app: &AppHandle) {
+    sync_mode_shortcuts(app);
+    super::lookback::sync_lookback(app);
+    if record_shortcut_allowed(app) {
+        if let Err(error) = register_record_shortcut(app, resolve_shortcut(app)) {
+            log::warn!("Could not restore record shortcut: {error}");
+        }
+        return;
+    }
+    app.state::<SpeechEngine>().cancel_queued_dictation();
+    if app.state::<SpeechEngine>().is_dictating() {
+        stop_recording_async(app);
+    }
+    release_record_shortcut(app);
+}
+
+/// Registers the LLM handoff shortcut only while the handoff is `enabled` and
+/// the record shortcut is listening.
+pub(crate) fn sync_handoff_shortcut(app: &AppHandle, enabled: bool) -> Result<(), ShortcutError> {
+    sync_mode_shortcut(
+        app,
+        llm_handoff_shortcut(),
+        DictationOutput::LlmHandoff,
+        enabled,
+    )
+}
+
+/// Registers the numbers mode shortcut only while numbers mode is `enabled`
+/// and the record shortcut is listening.
+pub(crate) fn sync_numbers_mode_shortcut(
+    app: &AppHandle,
+    enabled: bool,
+) -> Result<(), ShortcutError> {
+    sync_mode_shortcut(
+        app,
+        numbers_mode_shortcut(),
+        DictationOutput::Numbers,
+        enabled,
+    )
+}
+
+/// Brings both mode shortcuts and the dictation toggle shortcut in line with
+/// the saved settings.
+pub(crate) fn sync_mode_shortcuts(app: &AppHandle) {
+    let settings = crate::settings::get_settings(app);
+    if let Err(error) = sync_dictation_toggle_shortcut(app, settings.dictation_toggle_shortcut) {
+        log::warn!("Could not update dictation toggle shortcut: {error}");
+    }
+    if let Err(error) = sync_handoff_shortcut(app, settings.llm_handoff.enabled) {
+        log::warn!("Could not update LLM handoff shortcut: {error}");
+    }
+    if let Err(error) = sync_numbers_mode_shortcut(app, settings.numbers_mode) {
+        log::warn!("Could not update numbers mode shortcut: {error}");
+    }
+}
+
+/// Registers the dictation toggle shortcut only while it is `enabled`. It
+/// stays registered while dictation is off, since it is how dictation comes
+/// back on.
+pub(crate) fn sync_dictation_toggle_shortcut(
+    app: &AppHandle,
+    enabled: bool,
+) -> Result<(), ShortcutError> {
+    let shortcut = dictation_toggle_shortcut();
+    let is_record = active_shortcut()
+        .lock()
+        .map_err(|_| ShortcutError::LockFailed)?
+        .is_some_and(|record| record == shortcut);
+    if enabled && is_record {
+        return Err(ShortcutError::InUse(shortcut.into_string()));
+    }
+    let registered = !is_record && app.global_shortcut().is_registered(shortcut);
+    match (enabled, registered) {
+        (true, false) => app
+            .global_shortcut()
+            .on_shortcut(shortcut, make_toggle_handler())
+            .map_err(|error| {
+                log::warn!("Could not register {}: {error}", shortcut.into_string());
+                ShortcutError::InUse(shortcut.into_string())
+            }),
+        (false, true) => {
+            if let Err(error) = app.global_shortcut().unregister(shortcut) {
+                log::warn!("Could not release {}: {error}", shortcut.into_string());
+            }
+            Ok(())
+        }
+        _ => Ok(()),
+    }
+}
+
+/// The mode shortcuts are fixed chords, so turning one on fails rather than
+/// taking over a record shortcut set to the same chord or one another app
+/// holds.
+fn sync_mode_shortcut(
+    app: &AppHandle,
+    shortcut: Shortcut,
+    output: DictationOutput,
+    enabled: bool,
+) -> Result<(), ShortcutError> {
+    let wanted = enabled && record_shortcut_allowed(app);
+    let is_record = active_shortcut()
+        .lock()
+        .map_err(|_| ShortcutError::LockFailed)?
+        .is_some_and(|record| record == shortcut);
+    if wanted && is_record {
+        return Err(ShortcutError::InUse(shortcut.into_string()));
+    }
+    let registered = !is_record && app.global_shortcut().is_registered(shortcut);
+    match (wanted, registered) {
+        (true, false) => app
+            .global_shortcut()
+            .on_shortcut(shortcut, make_handler(output))
+            .map_err(|error| {
+                log::warn!("Could not register {}: {error}", shortcut.into_string());
+                ShortcutError::InUse(shortcut.into_string())
+            }),
+        (false, true) => {
+            if let Err(error) = app.global_shortcut().unregister(shortcut) {
+                log::warn!("Could not release {}: {error}", shortcut.into_string());
+            }
+            Ok(())
+        }
+        _ => Ok(()),
+    }
+}
+
+fn release_record_shortcut(app: &AppHandle) {
+    let Ok(mut active) = active_shortcut().lock() else {
+        log::warn!("Record shortcut state is unavailable");
+        return;
+    };
+    if let Some(shortcut) = active.take() {
+        if let Err(error) = app.global_shortcut().unregister(shortcut) {
+            log::warn!("Could not release record shortcut: {error}");
+            *active = Some(shortcut);
+        }
+    }
+    let remaining = *active;
+    drop(active);
+    sync_key_suppression(app, remaining);
+}
+
+/// Re-applies the key suppression setting to the registered record shortcut.
+pub(crate) fn sync_record_key_suppression(app: &AppHandle) {
+    let shortcut = active_shortcut().lock().ok().and_then(|active| *active);
+    sync_key_suppression(app, shortcut);
+}
+
+fn make_toggle_handler() -> impl Fn(&AppHandle, &Shortcut, ShortcutEvent) + Send + Sync {
+    move |app: &AppHandle, _, event| {
+        if !matches!(event.state(), ShortcutState::Pressed) {
+            return;
+        }
+        // Registration changes run off the shortcut callback, which holds the
+        // plugin's handler table.
+        let app = app.clone();
+        async_runtime::spawn_blocking(move || {
+            let enabled = !crate::settings::get_settings(&app).dictation_enabled;
+            if let Err(error) = set_dictation_enabled(&app, enabled) {
+                report_failure(&app, "Failed to toggle dictation", &error);
+            }
+        });
+    }
 }
 
 pub fn get_record_shortcut(app: AppHandle) -> Option<String> {
@@ -184,12 +1060,23 @@ fn resolve_shortcut(app: &AppHandle) -> Shortcut {
 #[cfg(desktop)]
 pub(super) fn init_shortcuts(app: &AppHandle) -> tauri::Result<()> {
     app.plugin(tauri_plugin_global_shortcut::Builder::new().build())?;
-    let shortcut = resolve_shortcut(app);
-    if let Err(e) = register_record_shortcut(app, shortcut) {
-        log::warn!("Global shortcut failed: {e}");
+    let queued_app = app.clone();
+    crate::recording::set_queued_start_sink(move |reservation| {
+        start_queued_recording(&queued_app, reservation)
+    });
+    if record_shortcut_allowed(app) {
+        let shortcut = resolve_shortcut(app);
+        if let Err(e) = register_record_shortcut(app, shortcut) {
+            log::warn!("Global shortcut failed: {e}");
+        }
+    } else {
+        log::info!("Dictation is disabled or quiet; record shortcut not registered");
     }
-    let _ = app
-        .global_shortcut()
-        .on_shortcut(Shortcut::new(Some(Modifiers::FN), Code::Fn), make_handler());
+    sync_mode_shortcuts(app);
+    super::lookback::sync_lookback(app);
+    let _ = app.global_shortcut().on_shortcut(
+        Shortcut::new(Some(Modifiers::FN), Code::Fn),
+        make_handler(DictationOutput::Type),
+    );
     Ok(())
 }
@@ -1,15 +1,35 @@
 use std::path::PathBuf;
 
-use tauri::menu::{Menu, MenuItem};
+use tauri::image::Image;
+use tauri::menu::{CheckMenuItem, Menu, MenuItem, Submenu};
 use tauri::tray::TrayIconBuilder;
-use tauri::{AppHandle, Manager};
+use tauri::{AppHandle, Emitter, Manager, Wry};
 use tauri_plugin_dialog::{DialogExt, MessageDialogKind};
 
 const MENU_ITEM_QUIT: &str = "quit";
+const MENU_ITEM_OPEN: &str = "open_window";
 const MENU_ITEM_VIEW_LOGS: &str = "view_logs";
+const MENU_ITEM_DISABLE: &str = "disable_dictation";
+const MENU_ITEM_PROFILES: &str = "profiles";
+const MENU_ITEM_NO_PROFILES: &str = "no_profiles";
+/// Profile items are identified by this prefix followed by the profile name.
+const PROFILE_ITEM_PREFIX: &str = "profile:";
+const TRAY_ID: &str = "main";
+const TOOLTIP: &str = "SilentKeys";
+const QUIET_TOOLTIP: &str = "SilentKeys (quiet hours)";
+const RECORDING_TOOLTIP: &str = "SilentKeys (recording)";
+
+/// Checked while dictation is disabled, kept in step with the toggle shortcut
+/// and settings UI.
+struct DisableMenuItem(CheckMenuItem<Wry>);
+
+/// Lists saved profiles with the active one checked; rebuilt whenever the
+/// profiles change.
+struct ProfilesMenu(Submenu<Wry>);
 
 pub(super) fn init_tray(app: &AppHandle) -> tauri::Result<()> {
     let quit = MenuItem::with_id(app, MENU_ITEM_QUIT, "Quit", true, None::<&str>)?;
+    let open = MenuItem::with_id(app, MENU_ITEM_OPEN, "Open SilentKeys", true, None::<&str>)?;
     let view_logs = MenuItem::with_id(
         app,
         MENU_ITEM_VIEW_LOGS,
@@ -17,9 +37,22 @@ pub(super) fn init_tray(app: &AppHandle) -> tauri::Result<()> {
         true,
         None::<&str>,
     )?;
-    let menu = Menu::with_items(app, &[&view_logs, &quit])?;
+    let disable = CheckMenuItem::with_id(
+        app,
+        MENU_ITEM_DISABLE,
+        "Disable SilentKeys",
+        true,
+        !crate::settings::get_settings(app).dictation_enabled,
+        None::<&str>,
+    )?;
+    app.manage(DisableMenuItem(disable.clone()));
+    let profiles = Submenu::with_id(app, MENU_ITEM_PROFILES, "Profiles", true)?;
+    fill_profiles_menu(app, &profiles)?;
+    app.manage(ProfilesMenu(profiles.clone()));
+    let menu = Menu::with_items(app, &[&open, &disable, &profiles, &view_logs, &quit])?;
 
-    let mut tray = TrayIconBuilder::new()
+    let mut tray = TrayIconBuilder::with_id(TRAY_ID)
+        .tooltip(TOOLTIP)
         .menu(&menu)
         .show_menu_on_left_click(true)
         .on_menu_event(|app, event| match event.id.as_ref() {
@@ -27,6 +60,15 @@ pub(super) fn init_tray(app: &AppHandle) -> tauri::Result<()> {
                 log::info!("Quit menu item clicked");
                 app.exit(0);
             }
+            MENU_ITEM_OPEN => super::show_main_window(app),
+            MENU_ITEM_DISABLE => {
+                let enabled = !crate::settings::get_settings(app).dictation_enabled;
+                log::info!("Disable menu item clicked (enabled={enabled})");
+                if let Err(error) = super::shortcuts::set_dictation_enabled(app, enabled) {
+                    log::error!("Failed to toggle dictation: {error}");
+                    sync_dictation_item(app, !enabled);
+                }
+            }
             MENU_ITEM_VIEW_LOGS => {
                 log::info!("View Log File menu item clicked");
                 if let Err(e) = open_log_file(app) {
@@ -39,7 +81,10 @@ pub(super) fn init_tray(app: &AppHandle) -> tauri::Result<()> {
                         .blocking_show();
                 }
             }
-            _ => log::debug!("Unhandled menu item: {:?}", event.id),
+            id => match id.strip_prefix(PROFILE_ITEM_PREFIX) {
+                Some(name) => switch_profile(app, name.to_string()),
+                None => log::debug!("Unhandled menu item: {:?}", event.id),
+            },
         });
 
     if let Some(icon) = app.default_window_icon() {
@@ -52,6 +97,134 @@ pub(super) fn init_tray(app: &AppHandle) -> tauri::Result<()> {
     Ok(())
 }
 
+pub(super) fn sync_dictation_item(app: &AppHandle, enabled: bool) {
+    if let Some(item) = app.try_state::<DisableMenuItem>() {
+        if let Err(error) = item.0.set_checked(!enabled) {
+            log::warn!("Could not update tray dictation switch: {error}");
+        }
+    }
+}
+
+fn fill_profiles_menu(app: &AppHandle, menu: &Submenu<Wry>) -> tauri::Result<()> {
+    let profiles = crate::settings::get_settings(app).profiles;
+    if profiles.saved.is_empty() {
+        let empty = MenuItem::with_id(
+            app,
+            MENU_ITEM_NO_PROFILES,
+            "No Profiles",
+            false,
+            None::<&str>,
+        )?;
+        return menu.append(&empty);
+    }
+    for profile in &profiles.saved {
+        let item = CheckMenuItem::with_id(
+            app,
+            format!("{PROFILE_ITEM_PREFIX}{}", profile.name),
+            &profile.name,
+            true,
+            profiles.active.as_deref() == Some(profile.name.as_str()),
+            None::<&str>,
+        )?;
+        menu.append(&item)?;
+    }
+    Ok(())
+}
+
+/// Rebuilds the profile list after profiles are added, removed, or switched.
+pub(crate) fn sync_profiles_menu(app: &AppHandle) {
+    let Some(menu) = app.try_state::<ProfilesMenu>() else {
+        return;
+    };
+    let result = menu.0.items().and_then(|items| {
+        for item in &items {
+            menu.0.remove(item)?;
+        }
+        fill_profiles_menu(app, &menu.0)
+    });
+    if let Err(error) = result {
+        log::warn!("Could not update tray profiles: {error}");
+    }
+}
+
+/// Switching may reconfigure the speech engine, so it runs off the menu
+/// event thread.
+fn switch_profile(app: &AppHandle, name: String) {
+    log::info!("Profile menu item clicked ({name})");
+    let app = app.clone();
+    let result = std::thread::Builder::new()
+        .name("profile-switch".to_string())
+        .spawn(move || {
+            apply_profile(&app, &name);
+        });
+    if let Err(error) = result {
+        log::error!("Could not start profile switch: {error}");
+    }
+}
+
+/// Switches to the saved profile `name` on the calling thread and brings the
+/// triggers and tray in line. Returns whether it switched.
+pub(super) fn apply_profile(app: &AppHandle, name: &str) -> bool {
+    let engine = app.state::<crate::engine::SpeechEngine>();
+    let switched = match crate::settings::switch_profile(app, &engine, name) {
+        Ok(()) => {
+            super::sync_triggers(app);
+            if let Err(error) = app.emit(crate::profiles::PROFILE_CHANGED_EVENT, name) {
+                log::warn!("Could not announce profile switch: {error}");
+            }
+            true
+        }
+        Err(error) => {
+            log::error!("Failed to switch profile: {error}");
+            false
+        }
+    };
+    // Restores the check marks when the switch failed.
+    sync_profiles_menu(app);
+    switched
+}
+
+pub(super) fn sync_quiet_hours(app: &AppHandle, active: bool) {
+    if let Some(tray) = app.tray_by_id(TRAY_ID) {
+        let tooltip = if active { QUIET_TOOLTIP } else { TOOLTIP };
+        if let Err(error) = tray.set_tooltip(Some(tooltip)) {
+            log::warn!("Could not update tray quiet-hours tooltip: {error}");
+        }
+    }
+    if let Some(item) = app.try_state::<DisableMenuItem>() {
+        let text = if active {
+            "Disable SilentKeys (quiet hours active)"
+        } else {
+            "Disable SilentKeys"
+        };
+        if let Err(error) = item.0.set_text(text) {
+            log::warn!("Could not update tray quiet-hours label: {error}");
+        }
+    }
+}
+
+/// Shows `icon` in place of the app icon, or restores it when `None`, and
+/// names the recording in the tooltip while `recording`.
+pub(super) fn show_recording(app: &AppHandle, recording: bool, icon: Option<&Image<'_>>) {
+    let Some(tray) = app.tray_by_id(TRAY_ID) else {
+        return;
+    };
+    let icon = icon.or_else(|| app.default_window_icon()).cloned();
+    if let Err(error) = tray.set_icon(icon) {
+        log::warn!("Could not update tray recording icon: {error}");
+    }
+    let tooltip = if recording {
+        RECORDING_TOOLTIP
+    } else if super::quiet_hours::is_active() {
+        QUIET_TOOLTIP
+    } else {
+        TOOLTIP
+    };
+    if let Err(error) = tray.set_tooltip(Some(tooltip)) {
+        log::warn!("Could not update tray recording tooltip: {error}");
+    }
+}
+
 fn open_log_file(app: &AppHandle) -> Result<(), String> {
     let log_dir = app.path().app_log_dir().map_err(|e| e.to_string())?;
     if !log_dir.exists() {
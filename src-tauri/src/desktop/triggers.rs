@@ -0,0 +1,224 @@
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{mpsc, Mutex};
+use std::thread;
+use std::time::Duration;
+
+use serde::Serialize;
+use tauri::AppHandle;
+use tauri_plugin_global_shortcut::ShortcutState;
+
+use super::modifiers;
+use super::shortcuts::record_trigger;
+use crate::trigger::{PedalBinding, PedalState, TriggerError, TriggerSettings};
+
+/// How long detection waits for a side button press.
+const DETECT_TIMEOUT: Duration = Duration::from_secs(10);
+const PEDAL_READ_TIMEOUT_MS: i32 = 200;
+/// Between attempts to open a pedal that is unplugged, and between checks
+/// while none is bound.
+const PEDAL_RETRY_INTERVAL: Duration = Duration::from_secs(2);
+const PEDAL_REPORT_BYTES: usize = 64;
+
+static TRIGGERS: Mutex<TriggerSettings> = Mutex::new(TriggerSettings {
+    mouse_button: None,
+    pedal: None,
+});
+/// Receives the next side button press while the settings window detects one.
+static DETECT: Mutex<Option<mpsc::Sender<u8>>> = Mutex::new(None);
+static INPUT_LISTENING: AtomicBool = AtomicBool::new(false);
+static PEDAL_WATCHING: AtomicBool = AtomicBool::new(false);
+
+/// A HID device as the pedal picker lists it.
+#[derive(Clone, Debug, Serialize)]
+pub struct HidDeviceInfo {
+    pub vendor_id: u16,
+    pub product_id: u16,
+    pub name: String,
+}
+
+fn current() -> TriggerSettings {
+    TRIGGERS
+        .lock()
+        .map(|triggers| *triggers)
+        .unwrap_or_default()
+}
+
+/// Applies the saved triggers, starting the input and pedal watchers the
+/// first time each is needed. The input hook serves the mouse button
+/// trigger, typing's modifier pause, and shortcut capture; it cannot be
+/// removed once installed, so unbinding a button only stops acting on it.
+pub(crate) fn sync_triggers(app: &AppHandle) {
+    let settings = crate::settings::get_settings(app);
+    let triggers = settings.triggers;
+    if let Ok(mut active) = TRIGGERS.lock() {
+        *active = triggers;
+    }
+    if triggers.mouse_button.is_some() || settings.typing_pace.pause_for_modifiers {
+        if let Err(error) = start_input_listener(app) {
+            log::warn!("Mouse button trigger and modifier pause unavailable: {error}");
+        }
+    }
+    if triggers.pedal.is_some() {
+        start_pedal_watcher(app);
+    }
+    super::shortcuts::sync_record_key_suppression(app);
+}
+
+/// Waits for the next press of a mouse button other than the primary,
+/// secondary, and wheel buttons and returns its code.
+pub(crate) fn detect_mouse_button(app: &AppHandle) -> Result<u8, TriggerError> {
+    start_input_listener(app)?;
+    let (tx, rx) = mpsc::channel();
+    if let Ok(mut detect) = DETECT.lock() {
+        *detect = Some(tx);
+    }
+    let result = rx.recv_timeout(DETECT_TIMEOUT);
+    if let Ok(mut detect) = DETECT.lock() {
+        detect.take();
+    }
+    result.map_err(|_| TriggerError::NoButtonPressed)
+}
+
+pub(crate) fn list_hid_devices() -> Result<Vec<HidDeviceInfo>, TriggerError> {
+    let api = hidapi::HidApi::new().map_err(|error| TriggerError::Hid(error.to_string()))?;
+    let mut devices = api
+        .device_list()
+        .map(|device| HidDeviceInfo {
+            vendor_id: device.vendor_id(),
+            product_id: device.product_id(),
+            name: device
+                .product_string()
+                .filter(|name| !name.trim().is_empty())
+                .map(str::to_string)
+                .unwrap_or_else(|| {
+                    format!("{:04x}:{:04x}", device.vendor_id(), device.product_id())
+                }),
+        })
+        .collect::<Vec<_>>();
+    devices.sort_by(|a, b| a.name.cmp(&b.name));
+    devices.dedup_by_key(|device| (device.vendor_id, device.product_id));
+    Ok(devices)
+}
+
+pub(super) fn start_input_listener(app: &AppHandle) -> Result<(), TriggerError> {
+    if INPUT_LISTENING.swap(true, Ordering::AcqRel) {
+        return Ok(());
+    }
+    let app = app.clone();
+    let spawned = thread::Builder::new()
+        .name("input-listener".to_string())
+        .spawn(move || {
+            let result = rdev::listen(move |event| {
+                modifiers::observe(&event.event_type);
+                let (code, state) = match event.event_type {
+                    rdev::EventType::KeyPress(key) => {
+                        super::shortcut_capture::observe_key(key);
+                        return;
+                    }
+                    rdev::EventType::ButtonPress(rdev::Button::Unknown(code)) => {
+                        (code, ShortcutState::Pressed)
+                    }
+                    rdev::EventType::ButtonRelease(rdev::Button::Unknown(code)) => {
+                        (code, ShortcutState::Released)
+                    }
+                    _ => return,
+                };
+                if matches!(state, ShortcutState::Pressed) {
+                    if let Some(detect) = DETECT.lock().ok().and_then(|mut detect| detect.take()) {
+                        let _ = detect.send(code);
+                        return;
+                    }
+                }
+                if current().mouse_button == Some(code) {
+                    record_trigger(&app, state);
+                }
+            });
+            if let Err(error) = result {
+                log::warn!("Input listener stopped: {error:?}");
+                INPUT_LISTENING.store(false, Ordering::Release);
+            }
+        });
+    spawned.map(|_| ()).map_err(|error| {
+        INPUT_LISTENING.store(false, Ordering::Release);
+        TriggerError::MouseListener(error.to_string())
+    })
+}
+
+fn start_pedal_watcher(app: &AppHandle) {
+    if PEDAL_WATCHING.swap(true, Ordering::AcqRel) {
+        return;
+    }
+    let app = app.clone();
+    if let Err(error) = thread::Builder::new()
+        .name("pedal-trigger".to_string())
+        .spawn(move || watch_pedal(&app))
+    {
+        log::warn!("Could not start pedal watcher: {error}");
+        PEDAL_WATCHING.store(false, Ordering::Release);
+    }
+}
+
+fn watch_pedal(app: &AppHandle) {
+    let mut api = None;
+    loop {
+        let Some(binding) = current().pedal else {
+            thread::sleep(PEDAL_RETRY_INTERVAL);
+            continue;
+        };
+        if api.is_none() {
+            match hidapi::HidApi::new() {
+                Ok(opened) => api = Some(opened),
+                Err(error) => {
+                    log::warn!("HID access failed: {error}");
+                    thread::sleep(PEDAL_RETRY_INTERVAL);
+                    continue;
+                }
+            }
+        }
+        let Some(api) = api.as_ref() else {
+            continue;
+        };
+        match api.open(binding.vendor_id, binding.product_id) {
+            Ok(device) => {
+                log::info!(
+                    "Watching pedal {:04x}:{:04x}",
+                    binding.vendor_id,
+                    binding.product_id
+                );
+                read_pedal(app, &device, binding);
+            }
+            Err(error) => {
+                log::debug!("Pedal not available: {error}");
+                thread::sleep(PEDAL_RETRY_INTERVAL);
+            }
+        }
+    }
+}
+
+/// Reads until the pedal is unplugged or rebound, releasing the trigger if
+/// it was down at that point.
+fn read_pedal(app: &AppHandle, device: &hidapi::HidDevice, binding: PedalBinding) {
+    let mut state = PedalState::default();
+    let mut report = [0u8; PEDAL_REPORT_BYTES];
+    while current().pedal == Some(binding) {
+        match device.read_timeout(&mut report, PEDAL_READ_TIMEOUT_MS) {
+            Ok(len) => {
+                if let Some(down) = state.update(&binding, &report[..len]) {
+                    let trigger = if down {
+                        ShortcutState::Pressed
+                    } else {
+                        ShortcutState::Released
+                    };
+                    record_trigger(app, trigger);
+                }
+            }
+            Err(error) => {
+                log::warn!("Pedal disconnected: {error}");
+                break;
+            }
+        }
+    }
+    if state.is_down() {
+        record_trigger(app, ShortcutState::Released);
+    }
+}
@@ -0,0 +1,97 @@
+use std::io::Write;
+use std::net::TcpStream;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Mutex, OnceLock};
+use std::time::Duration;
+
+use tauri::AppHandle;
+
+use super::network_input::Listener;
+use crate::recording::SessionId;
+use crate::sync::MutexRecover;
+use crate::transcript::Transcript;
+use crate::typed_metadata::{TypedCommit, TypedMetadataError};
+
+/// A plugin that stops reading is dropped rather than holding up dictation.
+const WRITE_TIMEOUT: Duration = Duration::from_millis(200);
+
+static LISTENER: OnceLock<Mutex<Option<Listener>>> = OnceLock::new();
+/// Connected plugins, each sent every commit from the time it connects.
+static SUBSCRIBERS: Mutex<Vec<TcpStream>> = Mutex::new(Vec::new());
+static SEQUENCE: AtomicU64 = AtomicU64::new(1);
+
+fn listener() -> &'static Mutex<Option<Listener>> {
+    LISTENER.get_or_init(|| Mutex::new(None))
+}
+
+/// Starts, moves, or stops the editor metadata listener to match the saved
+/// settings. Stopping it disconnects every plugin.
+pub(crate) fn sync_typed_metadata(app: &AppHandle) -> Result<(), TypedMetadataError> {
+    let settings = crate::settings::get_settings(app).typed_metadata;
+    let wanted = settings.enabled.then_some(settings.port);
+    let Ok(mut active) = listener().lock() else {
+        log::warn!("Editor metadata listener state is unavailable");
+        return Ok(());
+    };
+    if active.as_ref().map(Listener::port) == wanted {
+        return Ok(());
+    }
+    if let Some(previous) = active.take() {
+        previous.shut_down();
+        SUBSCRIBERS.lock_recover().clear();
+    }
+    let Some(port) = wanted else {
+        return Ok(());
+    };
+
+    let socket = crate::typed_metadata::listen(port)?;
+    let listener = Listener::spawn(app, socket, port, "typed-metadata", subscribe)
+        .map_err(|source| TypedMetadataError::Bind { port, source })?;
+    log::info!("Publishing editor metadata on 127.0.0.1:{port}");
+    *active = Some(listener);
+    Ok(())
+}
+
+pub(super) fn init_typed_metadata(app: &AppHandle) {
+    if let Err(error) = sync_typed_metadata(app) {
+        log::warn!("Editor metadata unavailable: {error}");
+    }
+}
+
+/// Sends a typed dictation to every connected plugin, dropping those that
+/// have gone away.
+pub(crate) fn publish_typed_commit(
+    app: &AppHandle,
+    session_id: Option<SessionId>,
+    text: &str,
+    transcript: &Transcript,
+) {
+    let settings = crate::settings::get_settings(app).typed_metadata;
+    if !settings.enabled || text.is_empty() {
+        return;
+    }
+    let mut subscribers = SUBSCRIBERS.lock_recover();
+    if subscribers.is_empty() {
+        return;
+    }
+    let sequence = SEQUENCE.fetch_add(1, Ordering::Relaxed);
+    let line = TypedCommit::new(sequence, session_id, text, transcript, &settings).to_line();
+    subscribers.retain_mut(|stream| match stream.write_all(line.as_bytes()) {
+        Ok(()) => true,
+        Err(error) => {
+            log::info!("Dropping editor metadata subscriber: {error}");
+            false
+        }
+    });
+}
+
+fn subscribe(_app: &AppHandle, stream: TcpStream) {
+    let configured = stream
+        .set_nonblocking(false)
+        .and_then(|()| stream.set_write_timeout(Some(WRITE_TIMEOUT)));
+    if let Err(error) = configured {
+        log::warn!("Could not configure editor metadata connection: {error}");
+        return;
+    }
+    SUBSCRIBERS.lock_recover().push(stream);
+}
@@ -1,12 +1,57 @@
+use std::collections::VecDeque;
 use std::sync::{mpsc, Mutex, OnceLock};
 use std::thread;
+use std::time::{Duration, Instant};
 
-use enigo::{Direction, Enigo, Key, Keyboard, Settings};
+use enigo::{Direction, Enigo, Key, Keyboard};
+use serde::Deserialize;
 
+use super::caret::char_before_caret;
+use super::focus::{activate_window, focused_app, focused_window};
+use super::modifiers;
+use crate::activity::{self, ActivityError, AppActivity};
+use crate::continuation::{
+    continues_sentence, lowercase_sentence_start, ContinuationSettings, RecentTyping,
+};
 use crate::errors::UserFacing;
+use crate::profiles::ProfileError;
+use crate::settings::{Settings, TypingPace};
+use crate::streaming::{Transcript, TranscriptPatch};
+use crate::target_lock::{check_target, TargetCheck, TargetLock};
 
-static TRANSCRIPTION_BUFFER: OnceLock<Mutex<String>> = OnceLock::new();
+/// Appends queued within this long of each other are typed as one string.
+const MERGE_WINDOW: Duration = Duration::from_millis(40);
+/// How often held modifiers are rechecked, and how long output waits for them
+/// before typing anyway in case a release was missed.
+const MODIFIER_POLL: Duration = Duration::from_millis(20);
+const MAX_MODIFIER_WAIT: Duration = Duration::from_secs(10);
+/// Output idle for this long starts a fresh characters-per-second budget.
+const RATE_IDLE_RESET: Duration = Duration::from_secs(1);
+/// Time for a refocused window to take focus before it is checked again.
+const REFOCUS_SETTLE: Duration = Duration::from_millis(50);
+
+static TRANSCRIPTION_BUFFER: OnceLock<Mutex<DictationTyping>> = OnceLock::new();
 static TYPING_SENDER: OnceLock<Result<mpsc::Sender<TypingRequest>, String>> = OnceLock::new();
+static RECENT_TYPING: OnceLock<Mutex<RecentTyping>> = OnceLock::new();
+/// What the current recording is typed after. Cleared with the buffer.
+static LEAD_IN: Mutex<LeadIn> = Mutex::new(LeadIn::new());
+
+struct LeadIn {
+    /// The app whose context the dictation is remembered under.
+    target: Option<String>,
+    before: Option<char>,
+    continues_sentence: bool,
+}
+
+impl LeadIn {
+    const fn new() -> Self {
+        Self {
+            target: None,
+            before: None,
+            continues_sentence: false,
+        }
+    }
+}
 
 #[derive(Clone, Debug, PartialEq, Eq)]
 pub enum FinalDelivery {
@@ -15,7 +60,7 @@ pub enum FinalDelivery {
     Replace { previous_chars: usize, text: String },
 }
 
-#[derive(thiserror::Error, Debug)]
+#[derive(thiserror::Error, Clone, Debug)]
 pub enum TypingError {
     #[error("typing state is unavailable")]
     State,
@@ -23,6 +68,16 @@ pub enum TypingError {
     Worker(String),
     #[error("virtual keyboard failed: {0}")]
     Keyboard(String),
+    #[error("focus left the window the recording started in")]
+    TargetChanged(DeliveryProgress),
+}
+
+/// How much of a delivery reached the window before the target lock stopped
+/// it: backspaces sent, then characters of its text typed.
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+pub struct DeliveryProgress {
+    pub deleted: usize,
+    pub typed: usize,
 }
 
 impl UserFacing for TypingError {
@@ -30,17 +85,176 @@ impl UserFacing for TypingError {
         match self {
             Self::Keyboard(_) => "Could not type into the focused app. Check input permissions.",
             Self::State | Self::Worker(_) => "Text output is unavailable. Please restart the app.",
+            Self::TargetChanged(_) => {
+                "Typing stopped because another window was focused. The rest of the text is on the clipboard."
+            }
+        }
+    }
+}
+
+/// How the `type_text` command types text sent by another tool. Unset
+/// fields follow the settings dictations are typed with.
+#[derive(Clone, Debug, Default, Deserialize, Eq, PartialEq)]
+#[serde(default)]
+pub struct TypeTextOptions {
+    /// Put a space first when the caret follows a word.
+    pub spacing: Option<bool>,
+    /// Lowercase the first word when it continues a sentence recently typed
+    /// into the same app.
+    pub continuation: Option<bool>,
+    /// Type at this saved profile's pace rather than the active one.
+    pub profile: Option<String>,
+}
+
+/// [`TypeTextOptions`] resolved against the current settings.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct TypeTextPlan {
+    pub pace: TypingPace,
+    pub caret_spacing: bool,
+    pub continuation_window: Option<Duration>,
+}
+
+impl TypeTextOptions {
+    pub fn plan(&self, settings: &Settings) -> Result<TypeTextPlan, TypeTextError> {
+        let pace = match &self.profile {
+            None => settings.typing_pace,
+            Some(name) => {
+                let profile = settings
+                    .profiles
+                    .find(name)
+                    .ok_or_else(|| ProfileError::NotFound(name.clone()))?;
+                // The active profile's values live in the settings until
+                // another profile is chosen.
+                if settings.profiles.active.as_ref() == Some(&profile.name) {
+                    settings.typing_pace
+                } else {
+                    profile.settings.typing_pace
+                }
+            }
+        };
+        let continuation = match self.continuation {
+            Some(enabled) => ContinuationSettings {
+                enabled,
+                ..settings.continuation
+            },
+            None => settings.continuation,
+        };
+        Ok(TypeTextPlan {
+            pace,
+            caret_spacing: self.spacing.unwrap_or(settings.caret_spacing),
+            continuation_window: continuation.window(),
+        })
+    }
+}
+
+#[derive(thiserror::Error, Debug)]
+pub enum TypeTextError {
+    #[error(transparent)]
+    Activity(#[from] ActivityError),
+    #[error(transparent)]
+    Profile(#[from] ProfileError),
+    #[error(transparent)]
+    Typing(#[from] TypingError),
+}
+
+impl UserFacing for TypeTextError {
+    fn user_message(&self) -> &'static str {
+        match self {
+            Self::Activity(ActivityError::Busy(AppActivity::Recording)) => {
+                "Finish the current recording before typing text."
+            }
+            Self::Activity(ActivityError::Busy(AppActivity::Typing)) => {
+                "Other text is still being typed. Please try again."
+            }
+            Self::Activity(ActivityError::Busy(AppActivity::Updating)) => {
+                "An app update is being installed. Please try again after restart."
+            }
+            Self::Activity(ActivityError::Busy(AppActivity::Configuring)) => {
+                "Settings are being changed. Please try again."
+            }
+            Self::Activity(ActivityError::LockFailed) => {
+                "Text output is unavailable. Please try again."
+            }
+            Self::Profile(error) => error.user_message(),
+            Self::Typing(error) => error.user_message(),
         }
     }
 }
 
 struct TypingRequest {
     delivery: FinalDelivery,
-    completion: mpsc::Sender<Result<(), String>>,
+    pace: TypingPace,
+    /// The window the text must go to, under `pace.target_lock`.
+    target: Option<String>,
+    completion: mpsc::Sender<Result<(), TypingError>>,
+}
+
+type Acknowledgement = mpsc::Receiver<Result<(), TypingError>>;
+
+/// What the current dictation has typed, plus streamed commits still waiting
+/// for the worker. Commits are queued without waiting so the worker can merge
+/// them; `typed` only grows once the keyboard API reports success.
+#[derive(Default)]
+struct DictationTyping {
+    typed: String,
+    queued: VecDeque<(String, Acknowledgement)>,
+    /// The window focused when the recording started, when it could be read.
+    target: Option<String>,
+    /// Set once the target lock stopped typing; nothing more is typed.
+    target_lost: bool,
+    /// Characters of a stopped run of merged commits not yet credited to
+    /// `typed`. Every commit in the run reports the run's progress, so it is
+    /// shared out across them in order.
+    stopped_chars: Option<usize>,
+    /// The final text with its lead-in, once the dictation has ended.
+    final_text: String,
+}
+
+impl DictationTyping {
+    /// Moves acknowledged commits into `typed`, in order. With `wait`, blocks
+    /// until every queued commit is acknowledged. Returns the first failure;
+    /// a failed commit is left out of `typed`.
+    fn settle(&mut self, wait: bool) -> Result<(), TypingError> {
+        let mut failure = None;
+        while let Some((_, acknowledgement)) = self.queued.front() {
+            let result = if wait {
+                acknowledgement
+                    .recv()
+                    .unwrap_or_else(|_| Err(closed_acknowledgement()))
+            } else {
+                match acknowledgement.try_recv() {
+                    Ok(result) => result,
+                    Err(mpsc::TryRecvError::Empty) => break,
+                    Err(mpsc::TryRecvError::Disconnected) => Err(closed_acknowledgement()),
+                }
+            };
+            let Some((text, _)) = self.queued.pop_front() else {
+                break;
+            };
+            match result {
+                Ok(()) => self.typed.push_str(&text),
+                Err(error) => {
+                    if let TypingError::TargetChanged(progress) = error {
+                        let remaining = self.stopped_chars.get_or_insert(progress.typed);
+                        let reached: String = text.chars().take(*remaining).collect();
+                        *remaining -= reached.chars().count();
+                        self.typed.push_str(&reached);
+                        self.target_lost = true;
+                    }
+                    failure.get_or_insert(error);
+                }
+            }
+        }
+        failure.map_or(Ok(()), Err)
+    }
+}
+
+fn closed_acknowledgement() -> TypingError {
+    TypingError::Worker("acknowledgement channel closed".to_string())
 }
 
-fn transcription_buffer() -> &'static Mutex<String> {
-    TRANSCRIPTION_BUFFER.get_or_init(|| Mutex::new(String::new()))
+fn transcription_buffer() -> &'static Mutex<DictationTyping> {
+    TRANSCRIPTION_BUFFER.get_or_init(|| Mutex::new(DictationTyping::default()))
 }
 
 fn typing_sender() -> Result<&'static mpsc::Sender<TypingRequest>, TypingError> {
@@ -59,54 +273,324 @@ fn typing_sender() -> Result<&'static mpsc::Sender<TypingRequest>, TypingError>
 
 /// The receiver owns `Enigo` for its entire lifetime. Every request carries an
 /// acknowledgement, so transcript state advances only after the keyboard API
-/// reports success.
+/// reports success. Appends arriving close together are merged and
+/// acknowledged together.
 fn typing_worker(receiver: mpsc::Receiver<TypingRequest>) {
-    let mut keyboard = Enigo::new(&Settings::default()).map_err(|error| error.to_string());
-    while let Ok(request) = receiver.recv() {
+    let mut keyboard = Enigo::new(&enigo::Settings::default())
+        .map_err(|error| TypingError::Keyboard(error.to_string()));
+    let mut rate = TypingRate::new();
+    let mut next = None;
+    loop {
+        let Some(request) = next.take().or_else(|| receiver.recv().ok()) else {
+            break;
+        };
+        let (delivery, pace, target, completions, following) = merge_appends(request, &receiver);
+        next = following;
         let result = match &mut keyboard {
-            Ok(keyboard) => perform_delivery(keyboard, &request.delivery),
+            Ok(keyboard) => {
+                let target = Target {
+                    lock: pace.target_lock,
+                    window: target.as_deref(),
+                };
+                perform_delivery(keyboard, &delivery, pace, target, &mut rate)
+            }
             Err(error) => Err(error.clone()),
         };
-        let _ = request.completion.send(result);
+        for completion in completions {
+            let _ = completion.send(result.clone());
+        }
+    }
+}
+
+/// Folds appends with the same pace and target that arrive within
+/// [`MERGE_WINDOW`] of each other into `first`. Returns the request that ended
+/// the run, if any, so it is delivered next.
+#[allow(clippy::type_complexity)]
+fn merge_appends(
+    first: TypingRequest,
+    receiver: &mpsc::Receiver<TypingRequest>,
+) -> (
+    FinalDelivery,
+    TypingPace,
+    Option<String>,
+    Vec<mpsc::Sender<Result<(), TypingError>>>,
+    Option<TypingRequest>,
+) {
+    let TypingRequest {
+        delivery,
+        pace,
+        target,
+        completion,
+    } = first;
+    let mut completions = vec![completion];
+    let FinalDelivery::Append(mut text) = delivery else {
+        return (delivery, pace, target, completions, None);
+    };
+    let following = loop {
+        match receiver.recv_timeout(MERGE_WINDOW) {
+            Ok(TypingRequest {
+                delivery: FinalDelivery::Append(more),
+                pace: more_pace,
+                target: more_target,
+                completion,
+            }) if more_pace == pace && more_target == target => {
+                text.push_str(&more);
+                completions.push(completion);
+            }
+            Ok(other) => break Some(other),
+            Err(_) => break None,
+        }
+    };
+    (
+        FinalDelivery::Append(text),
+        pace,
+        target,
+        completions,
+        following,
+    )
+}
+
+/// The window a delivery is locked to, checked before each chunk.
+#[derive(Clone, Copy)]
+struct Target<'a> {
+    lock: TargetLock,
+    window: Option<&'a str>,
+}
+
+impl Target<'_> {
+    /// Confirms the locked window still has focus, bringing it back under
+    /// [`TargetLock::Refocus`]. Each check is a platform query, so it runs
+    /// once per chunk rather than per character.
+    fn ensure_focused(self) -> Result<(), TypingError> {
+        let Some(window) = self.window.filter(|_| self.lock != TargetLock::Off) else {
+            return Ok(());
+        };
+        let focused = focused_window();
+        match check_target(self.lock, Some(window), focused.as_deref()) {
+            TargetCheck::Type => Ok(()),
+            TargetCheck::Refocus => {
+                if activate_window(window) {
+                    thread::sleep(REFOCUS_SETTLE);
+                    if focused_window().as_deref() == Some(window) {
+                        return Ok(());
+                    }
+                }
+                log::warn!("Could not refocus the dictation's window; typing stopped");
+                Err(TypingError::TargetChanged(DeliveryProgress::default()))
+            }
+            TargetCheck::Abort => {
+                log::warn!("Focus left the dictation's window; typing stopped");
+                Err(TypingError::TargetChanged(DeliveryProgress::default()))
+            }
+        }
     }
 }
 
-fn perform_delivery(keyboard: &mut Enigo, delivery: &FinalDelivery) -> Result<(), String> {
+/// Types `delivery`, reporting how far it got when the target lock stops it.
+fn perform_delivery(
+    keyboard: &mut Enigo,
+    delivery: &FinalDelivery,
+    pace: TypingPace,
+    target: Target<'_>,
+    rate: &mut TypingRate,
+) -> Result<(), TypingError> {
+    let mut progress = DeliveryProgress::default();
+    perform(keyboard, delivery, pace, target, rate, &mut progress).map_err(|error| match error {
+        TypingError::TargetChanged(_) => TypingError::TargetChanged(progress),
+        error => error,
+    })
+}
+
+fn perform(
+    keyboard: &mut Enigo,
+    delivery: &FinalDelivery,
+    pace: TypingPace,
+    target: Target<'_>,
+    rate: &mut TypingRate,
+    progress: &mut DeliveryProgress,
+) -> Result<(), TypingError> {
     match delivery {
         FinalDelivery::None => Ok(()),
-        FinalDelivery::Append(text) => keyboard.text(text).map_err(|error| error.to_string()),
+        FinalDelivery::Append(text) => type_text(keyboard, text, pace, target, rate, progress),
         FinalDelivery::Replace {
             previous_chars,
             text,
         } => {
-            for _ in 0..*previous_chars {
+            for index in 0..*previous_chars {
+                if index == 0 || (pace.chunk_chars > 0 && index % pace.chunk_chars == 0) {
+                    if index > 0 {
+                        pause(pace);
+                    }
+                    target.ensure_focused()?;
+                }
+                rate.before_output(1, pace);
                 keyboard
                     .key(Key::Backspace, Direction::Click)
-                    .map_err(|error| error.to_string())?;
+                    .map_err(|error| TypingError::Keyboard(error.to_string()))?;
+                progress.deleted += 1;
             }
             if text.is_empty() {
                 return Ok(());
             }
-            keyboard.text(text).map_err(|error| error.to_string())
+            if *previous_chars > 0 {
+                pause(pace);
+            }
+            type_text(keyboard, text, pace, target, rate, progress)
         }
     }
 }
 
-fn submit(delivery: FinalDelivery) -> Result<(), TypingError> {
-    if delivery == FinalDelivery::None {
-        return Ok(());
+fn type_text(
+    keyboard: &mut Enigo,
+    text: &str,
+    pace: TypingPace,
+    target: Target<'_>,
+    rate: &mut TypingRate,
+    progress: &mut DeliveryProgress,
+) -> Result<(), TypingError> {
+    for (index, chunk) in typing_chunks(text, pace.chunk_chars)
+        .into_iter()
+        .enumerate()
+    {
+        if index > 0 {
+            pause(pace);
+        }
+        target.ensure_focused()?;
+        for slice in typing_chunks(chunk, rate_slice_chars(pace.max_chars_per_sec)) {
+            rate.before_output(slice.chars().count(), pace);
+            keyboard
+                .text(slice)
+                .map_err(|error| TypingError::Keyboard(error.to_string()))?;
+            progress.typed += slice.chars().count();
+        }
+    }
+    Ok(())
+}
+
+/// Characters typed since output last went idle, for the characters per
+/// second limit.
+struct TypingRate {
+    started: Instant,
+    last_output: Instant,
+    chars: usize,
+}
+
+impl TypingRate {
+    fn new() -> Self {
+        let now = Instant::now();
+        Self {
+            started: now,
+            last_output: now,
+            chars: 0,
+        }
+    }
+
+    /// Waits until `chars` more characters fit the pace's rate and no
+    /// modifier is held, then counts them.
+    fn before_output(&mut self, chars: usize, pace: TypingPace) {
+        if pace.pause_for_modifiers {
+            wait_for_modifiers();
+        }
+        let now = Instant::now();
+        if now.duration_since(self.last_output) >= RATE_IDLE_RESET {
+            self.started = now;
+            self.chars = 0;
+        }
+        thread::sleep(rate_limit_delay(
+            self.chars,
+            pace.max_chars_per_sec,
+            now.duration_since(self.started),
+        ));
+        self.chars += chars;
+        self.last_output = Instant::now();
     }
-    let (completion, result) = mpsc::channel();
+}
+
+fn wait_for_modifiers() {
+    let started = Instant::now();
+    while modifiers::held() {
+        if started.elapsed() >= MAX_MODIFIER_WAIT {
+            log::warn!("Typing while a modifier still reads as held");
+            return;
+        }
+        thread::sleep(MODIFIER_POLL);
+    }
+}
+
+/// How long to wait before more output so that `typed` characters over
+/// `elapsed` stay within `max_chars_per_sec`. Zero leaves the rate unlimited.
+pub fn rate_limit_delay(typed: usize, max_chars_per_sec: u32, elapsed: Duration) -> Duration {
+    if max_chars_per_sec == 0 {
+        return Duration::ZERO;
+    }
+    Duration::from_secs_f64(typed as f64 / f64::from(max_chars_per_sec)).saturating_sub(elapsed)
+}
+
+/// Rate-limited text is typed in slices of about 50 ms each, so the limit
+/// holds within a commit as well as between commits.
+fn rate_slice_chars(max_chars_per_sec: u32) -> usize {
+    if max_chars_per_sec == 0 {
+        0
+    } else {
+        (max_chars_per_sec as usize / 20).max(1)
+    }
+}
+
+fn pause(pace: TypingPace) {
+    if pace.chunk_delay_ms > 0 {
+        thread::sleep(Duration::from_millis(pace.chunk_delay_ms));
+    }
+}
+
+/// Splits `text` into runs of at most `max_chars` characters, never inside a
+/// UTF-8 sequence. Zero keeps the text whole.
+pub fn typing_chunks(text: &str, max_chars: usize) -> Vec<&str> {
+    if text.is_empty() {
+        return Vec::new();
+    }
+    if max_chars == 0 {
+        return vec![text];
+    }
+    let mut chunks = Vec::new();
+    let mut start = 0;
+    for (count, (index, _)) in text.char_indices().enumerate() {
+        if count > 0 && count % max_chars == 0 {
+            chunks.push(&text[start..index]);
+            start = index;
+        }
+    }
+    chunks.push(&text[start..]);
+    chunks
+}
+
+fn enqueue(
+    delivery: FinalDelivery,
+    pace: TypingPace,
+    target: Option<String>,
+) -> Result<Acknowledgement, TypingError> {
+    let (completion, acknowledgement) = mpsc::channel();
     typing_sender()?
         .send(TypingRequest {
             delivery,
+            pace,
+            target,
             completion,
         })
         .map_err(|_| TypingError::Worker("request channel closed".to_string()))?;
-    result
+    Ok(acknowledgement)
+}
+
+fn submit(
+    delivery: FinalDelivery,
+    pace: TypingPace,
+    target: Option<String>,
+) -> Result<(), TypingError> {
+    if delivery == FinalDelivery::None {
+        return Ok(());
+    }
+    enqueue(delivery, pace, target)?
         .recv()
-        .map_err(|_| TypingError::Worker("acknowledgement channel closed".to_string()))?
-        .map_err(TypingError::Keyboard)
+        .unwrap_or_else(|_| Err(closed_acknowledgement()))
 }
 
 pub fn plan_final_delivery(current: &str, final_text: &str) -> FinalDelivery {
@@ -122,11 +606,114 @@ pub fn plan_final_delivery(current: &str, final_text: &str) -> FinalDelivery {
     }
 }
 
-pub(super) fn reset_buffer() -> Result<(), TypingError> {
+pub(crate) fn reset_buffer() -> Result<(), TypingError> {
+    *transcription_buffer()
+        .lock()
+        .map_err(|_| TypingError::State)? = DictationTyping::default();
+    *LEAD_IN.lock().map_err(|_| TypingError::State)? = LeadIn::new();
+    Ok(())
+}
+
+/// Locks the current dictation's output to `window`, the one focused when
+/// recording started. Cleared with the buffer.
+pub(super) fn set_target_window(window: Option<String>) -> Result<(), TypingError> {
     transcription_buffer()
         .lock()
         .map_err(|_| TypingError::State)?
-        .clear();
+        .target = window;
+    Ok(())
+}
+
+/// Whether a dictation typed after `before`, the character left of the
+/// caret, needs a space first. Nothing is added at the start of a field,
+/// after whitespace or an opening bracket or quote, or before punctuation.
+pub fn needs_leading_space(before: Option<char>, text: &str) -> bool {
+    let Some(before) = before else {
+        return false;
+    };
+    let starts_word = text
+        .trim_start()
+        .chars()
+        .next()
+        .is_some_and(char::is_alphanumeric);
+    starts_word
+        && !before.is_whitespace()
+        && !matches!(
+            before,
+            '(' | '[' | '{' | '<' | '"' | '\'' | '`' | '\u{201C}' | '\u{2018}' | '/' | '@' | '#'
+        )
+}
+
+fn recent_typing() -> &'static Mutex<RecentTyping> {
+    RECENT_TYPING.get_or_init(|| Mutex::new(RecentTyping::default()))
+}
+
+/// Sets what the recording is typed after: the character left of the caret
+/// when it could be read, otherwise the end of the last dictation typed into
+/// `target` within `continuation_window`.
+pub(super) fn set_lead_in(
+    caret: Option<char>,
+    target: Option<String>,
+    continuation_window: Option<Duration>,
+) -> Result<(), TypingError> {
+    *LEAD_IN.lock().map_err(|_| TypingError::State)? = lead_in(caret, target, continuation_window)?;
+    Ok(())
+}
+
+fn lead_in(
+    caret: Option<char>,
+    target: Option<String>,
+    continuation_window: Option<Duration>,
+) -> Result<LeadIn, TypingError> {
+    let target = target.filter(|_| continuation_window.is_some());
+    let remembered = match (&target, continuation_window) {
+        (Some(app), Some(window)) => recent_typing()
+            .lock()
+            .map_err(|_| TypingError::State)?
+            .context(app, Instant::now(), window)
+            .map(str::to_string),
+        _ => None,
+    };
+    let before = caret.map(String::from).or(remembered);
+    Ok(LeadIn {
+        continues_sentence: continuation_window.is_some()
+            && before.as_deref().is_some_and(continues_sentence),
+        before: before.and_then(|before| before.chars().last()),
+        target,
+    })
+}
+
+fn with_lead_in(text: String) -> Result<String, TypingError> {
+    Ok(apply_lead_in(
+        &*LEAD_IN.lock().map_err(|_| TypingError::State)?,
+        text,
+    ))
+}
+
+fn apply_lead_in(lead_in: &LeadIn, text: String) -> String {
+    let text = if lead_in.continues_sentence {
+        lowercase_sentence_start(&text)
+    } else {
+        text
+    };
+    if needs_leading_space(lead_in.before, &text) {
+        format!(" {}", text.trim_start())
+    } else {
+        text
+    }
+}
+
+fn remember_typed(typed: &str) -> Result<(), TypingError> {
+    remember(&*LEAD_IN.lock().map_err(|_| TypingError::State)?, typed)
+}
+
+fn remember(lead_in: &LeadIn, typed: &str) -> Result<(), TypingError> {
+    if let Some(app) = &lead_in.target {
+        recent_typing()
+            .lock()
+            .map_err(|_| TypingError::State)?
+            .record(app, typed, Instant::now());
+    }
     Ok(())
 }
 
@@ -170,16 +757,131 @@ pub fn append_for_tests(
     append(current, text, submit)
 }
 
-pub(super) fn append_streaming_text(text: String) -> Result<(), TypingError> {
-    let mut current = transcription_buffer()
+/// Queues a streamed commit without waiting for it to be typed, so commits
+/// in quick succession are merged. Reports the first earlier commit that
+/// failed since the last call.
+pub(super) fn append_streaming_text(text: String, pace: TypingPace) -> Result<(), TypingError> {
+    let mut typing = transcription_buffer()
         .lock()
         .map_err(|_| TypingError::State)?;
-    append(&mut current, text, submit)
+    let earlier = typing.settle(false);
+    if typing.target_lost {
+        return earlier;
+    }
+    let text = if typing.typed.is_empty() && typing.queued.is_empty() {
+        with_lead_in(text)?
+    } else {
+        text
+    };
+    let acknowledgement = enqueue(
+        FinalDelivery::Append(text.clone()),
+        pace,
+        typing.target.clone(),
+    )?;
+    typing.queued.push_back((text, acknowledgement));
+    earlier
 }
 
-pub(super) fn deliver_final_text(text: String) -> Result<(), TypingError> {
-    let mut current = transcription_buffer()
+pub(super) fn deliver_final_text(text: String, pace: TypingPace) -> Result<(), TypingError> {
+    let mut typing = transcription_buffer()
         .lock()
         .map_err(|_| TypingError::State)?;
-    deliver(&mut current, text, submit)
+    if let Err(error) = typing.settle(true) {
+        log::warn!("A streamed commit was not typed; the final text corrects it: {error}");
+    }
+    let text = with_lead_in(text)?;
+    typing.final_text = text.clone();
+    if typing.target_lost {
+        return Err(TypingError::TargetChanged(DeliveryProgress::default()));
+    }
+    let target = typing.target.clone();
+    let mut planned = FinalDelivery::None;
+    if let Err(error) = deliver(&mut typing.typed, text, |delivery| {
+        planned = delivery.clone();
+        submit(delivery, pace, target)
+    }) {
+        if let TypingError::TargetChanged(progress) = error {
+            typing.typed = reached_window(&typing.typed, &planned, progress);
+            typing.target_lost = true;
+        }
+        return Err(error);
+    }
+    remember_typed(&typing.typed)
+}
+
+/// The part of the final text that never reached the window after the target
+/// lock stopped the dictation, to put on the clipboard.
+pub(super) fn untyped_final_text() -> Result<String, TypingError> {
+    let typing = transcription_buffer()
+        .lock()
+        .map_err(|_| TypingError::State)?;
+    Ok(untyped_remainder(&typing.typed, &typing.final_text).to_string())
+}
+
+/// What the window holds after `delivery` to `current` stopped at `progress`.
+pub fn reached_window(
+    current: &str,
+    delivery: &FinalDelivery,
+    progress: DeliveryProgress,
+) -> String {
+    let (kept, text) = match delivery {
+        FinalDelivery::None => return current.to_string(),
+        FinalDelivery::Append(text) => (current.chars().count(), text),
+        FinalDelivery::Replace {
+            previous_chars,
+            text,
+        } => {
+            let deleted = progress.deleted.min(*previous_chars);
+            (current.chars().count().saturating_sub(deleted), text)
+        }
+    };
+    current
+        .chars()
+        .take(kept)
+        .chain(text.chars().take(progress.typed))
+        .collect()
+}
+
+/// The end of `final_text` after what is already in the window. When the
+/// window holds something else, say text a stopped correction only partly
+/// deleted, all of `final_text` is left to paste.
+pub fn untyped_remainder<'a>(in_window: &str, final_text: &'a str) -> &'a str {
+    final_text.strip_prefix(in_window).unwrap_or(final_text)
+}
+
+/// Types a routed patch: commits are appended and a replacement corrects
+/// what was typed to match `transcript`. Drafts are left untyped.
+pub(crate) fn type_patch(
+    patch: &TranscriptPatch,
+    transcript: &Transcript,
+    pace: TypingPace,
+) -> Result<(), TypingError> {
+    match patch {
+        TranscriptPatch::Draft { .. } | TranscriptPatch::DraftTail { .. } => Ok(()),
+        TranscriptPatch::Commit { append } => append_streaming_text(append.clone(), pace),
+        TranscriptPatch::Replace { .. } => {
+            deliver_final_text(transcript.committed().to_string(), pace)
+        }
+    }
+}
+
+/// Types `text` from another tool into the focused app through the same
+/// keyboard worker, lead-in, and pace as dictations. Refused while a
+/// recording is in progress, so it never interleaves with dictated output.
+pub(crate) fn inject_text(
+    settings: &Settings,
+    text: String,
+    options: &TypeTextOptions,
+) -> Result<(), TypeTextError> {
+    let _typing = activity::try_begin(AppActivity::Typing)?;
+    let plan = options.plan(settings)?;
+    if text.is_empty() {
+        return Ok(());
+    }
+    let caret = plan.caret_spacing.then(char_before_caret).flatten();
+    let target = plan.continuation_window.and_then(|_| focused_app());
+    let lead_in = lead_in(caret, target, plan.continuation_window)?;
+    let text = apply_lead_in(&lead_in, text);
+    submit(FinalDelivery::Append(text.clone()), plan.pace, None)?;
+    remember(&lead_in, &text).map_err(Into::into)
 }
@@ -1,7 +1,89 @@
-use crate::engine::{EngineError, SpeechEngine};
+use std::ops::ControlFlow;
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Mutex;
+use std::time::Duration;
+
+use serde::Serialize;
+use tauri::{AppHandle, Emitter, Manager};
+
+use crate::activity::AppActivity;
+use crate::asr::TARGET_SAMPLE_RATE;
+use crate::audio_file::AudioFileError;
+use crate::decode_cache::{self, DecodeCache};
+use crate::engine::{EngineError, OnSegment, SegmentProgress, SpeechEngine, Take};
 use crate::errors::UserFacing;
-use crate::recording::{RecordingError, RecordingReservation};
-use crate::streaming::{StreamingError, UpdateSink};
+use crate::extensions::{Extensions, FinalTranscript, RecordingStart};
+use crate::noise_profile::{NoiseProfile, NoiseProfileError, NoiseSuppression, MAX_NOISE_PROFILES};
+use crate::patch_routing::{PatchRouter, PatchSource, WINDOWED_DRAFT_MIN_CHARS};
+use crate::post_process::{PostProcessor, StageContext};
+use crate::recording::{
+    Lookback, MicrophoneTest, RecordingError, RecordingReservation, SessionId, MIC_TEST_DURATION,
+};
+use crate::retranscribe::RetranscribeConfig;
+use crate::settings::Settings;
+use crate::speaker_adaptation::{measure_voice, SpeakerAdaptation};
+use crate::speech_stats::SpeechStats;
+use crate::streaming::{RecordingMode, SessionUpdate, StreamingError, StreamingStrategy};
+use crate::sync::MutexRecover;
+use crate::time_stretch::TimeStretch;
+use crate::transcript::Transcript;
+use crate::vad::NoiseCalibration;
+use crate::webhook::{DictationPayload, WebhookSettings};
+
+pub(crate) const RECORDING_STARTED_EVENT: &str = "recording_started";
+pub(crate) const RECORDING_STOPPED_EVENT: &str = "recording_stopped";
+const TRANSCRIPTION_UPDATE_EVENT: &str = "transcription_update";
+/// Carries the segmented final transcript; `transcription_update` only has
+/// the joined text.
+const FINAL_TRANSCRIPT_EVENT: &str = "final_transcript";
+const CAPTURE_QUALITY_EVENT: &str = "capture_quality";
+const SPEECH_STATS_EVENT: &str = "speech_stats";
+const FILE_TRANSCRIPTION_PROGRESS_EVENT: &str = "file_transcription_progress";
+/// Long enough for the OS to deliver the first buffers of an opened input.
+const MIC_ACCESS_PROBE_DURATION: Duration = Duration::from_millis(300);
+/// Long enough to catch fans and hum cycling, short enough to stay quiet for.
+const CALIBRATION_DURATION: Duration = Duration::from_secs(3);
+
+/// Set once the window has asked for long drafts as tails.
+static WINDOWED_DRAFTS: AtomicBool = AtomicBool::new(false);
+/// Set to stop the running file transcription before its next segment.
+static FILE_TRANSCRIPTION_CANCELLED: AtomicBool = AtomicBool::new(false);
+/// The latest microphone test take, kept at 16 kHz so it can be replayed.
+static MIC_TEST_TAKE: Mutex<Vec<f32>> = Mutex::new(Vec::new());
+
+/// How `transcription_update` patches are sent, as agreed with the window.
+#[derive(Clone, Copy, Debug, Serialize)]
+pub struct PatchProtocol {
+    pub windowed_drafts: bool,
+    /// Drafts shorter than this are sent in full either way.
+    pub windowed_draft_min_chars: u32,
+}
+
+/// Records whether the window applies windowed drafts. Dictations started
+/// afterwards use the answer; one already running keeps its protocol.
+pub(crate) fn negotiate_patch_protocol(windowed_drafts: bool) -> PatchProtocol {
+    WINDOWED_DRAFTS.store(windowed_drafts, Ordering::Release);
+    PatchProtocol {
+        windowed_drafts,
+        windowed_draft_min_chars: WINDOWED_DRAFT_MIN_CHARS,
+    }
+}
+
+/// Emitted as `file_transcription_progress` after each segment of a file
+/// transcription, so a long file's transcript can be shown as it builds up.
+#[derive(Clone, Debug, Serialize)]
+pub struct FileTranscriptionProgress {
+    pub path: PathBuf,
+    #[serde(flatten)]
+    pub progress: SegmentProgress,
+}
+
+/// Asks the running file transcription, if any, to stop after the segment it
+/// is decoding; it then fails with [`EngineError::Cancelled`].
+pub(crate) fn cancel_file_transcription() {
+    FILE_TRANSCRIPTION_CANCELLED.store(true, Ordering::Release);
+}
 
 #[derive(thiserror::Error, Debug)]
 pub enum DictationError {
@@ -13,6 +95,14 @@ pub enum DictationError {
     Engine(#[from] EngineError),
     #[error("dictation output failed: {0}")]
     Output(String),
+    #[error("dictation is disabled")]
+    Disabled,
+    #[error(transparent)]
+    AudioFile(#[from] AudioFileError),
+    #[error("app is busy")]
+    Busy,
+    #[error(transparent)]
+    NoiseProfile(#[from] NoiseProfileError),
 }
 
 impl UserFacing for DictationError {
@@ -22,33 +112,139 @@ impl UserFacing for DictationError {
             Self::Streaming(error) => error.user_message(),
             Self::Engine(error) => error.user_message(),
             Self::Output(_) => "Could not deliver the transcription. Please try again.",
+            Self::Disabled => "SilentKeys is disabled. Turn it back on to record.",
+            Self::AudioFile(error) => error.user_message(),
+            Self::Busy => "SilentKeys is busy. Try again once it has finished.",
+            Self::NoiseProfile(error) => error.user_message(),
         }
     }
 }
 
 impl SpeechEngine {
     pub fn reserve_dictation(&self) -> Result<RecordingReservation, DictationError> {
+        if !crate::settings::get_settings(self.app()).dictation_enabled {
+            return Err(DictationError::Disabled);
+        }
         Ok(self.recorder().reserve()?)
     }
 
+    /// Like [`SpeechEngine::reserve_dictation`], but queues the recording
+    /// while the last dictation is still finishing, as
+    /// [`Recorder::reserve_or_queue`] does.
+    ///
+    /// [`Recorder::reserve_or_queue`]: crate::recording::Recorder::reserve_or_queue
+    pub fn reserve_or_queue_dictation(
+        &self,
+    ) -> Result<Option<RecordingReservation>, DictationError> {
+        if !crate::settings::get_settings(self.app()).dictation_enabled {
+            return Err(DictationError::Disabled);
+        }
+        Ok(self.recorder().reserve_or_queue()?)
+    }
+
+    /// Drops a recording queued by [`SpeechEngine::reserve_or_queue_dictation`].
+    /// Returns whether one was waiting.
+    pub fn cancel_queued_dictation(&self) -> bool {
+        self.recorder().cancel_queued_start()
+    }
+
+    pub fn has_queued_dictation(&self) -> bool {
+        self.recorder().has_queued_start()
+    }
+
+    /// A router for a new dictation that shows patches in the window when
+    /// `source` is routed there; callers add typing and mirrors.
+    pub(crate) fn patch_router(&self, session_id: SessionId, source: PatchSource) -> PatchRouter {
+        let destinations = crate::settings::get_settings(self.app())
+            .patch_routing
+            .destinations(source);
+        let app = self.app().clone();
+        let show = move |update: SessionUpdate| {
+            app.emit(TRANSCRIPTION_UPDATE_EVENT, update)
+                .map_err(|error| error.to_string())
+        };
+        PatchRouter::new(Some(session_id), destinations)
+            .with_window(show)
+            .with_windowed_drafts(WINDOWED_DRAFTS.load(Ordering::Acquire))
+    }
+
+    /// Starts recording and streams patches through `router`, which the stop
+    /// path takes back to route the final text.
     pub fn start_dictation(
         &self,
         reservation: RecordingReservation,
-        on_update: impl UpdateSink,
+        router: PatchRouter,
     ) -> Result<(), DictationError> {
-        let streaming = crate::settings::get_settings(self.app()).streaming_enabled;
+        self.start_dictation_in(reservation, router, None)
+    }
+
+    /// Like [`Self::start_dictation`], transcribing in `mode` instead of as
+    /// the streaming settings say when one is given.
+    pub fn start_dictation_in(
+        &self,
+        reservation: RecordingReservation,
+        router: PatchRouter,
+        mode: Option<RecordingMode>,
+    ) -> Result<(), DictationError> {
+        let settings = crate::settings::get_settings(self.app());
+        let live_strategy = match mode {
+            Some(mode) => mode.live_strategy(),
+            None => settings
+                .streaming_enabled
+                .then_some(settings.streaming_strategy),
+        };
+        let streaming = live_strategy.is_some() && self.is_ready();
+        if live_strategy.is_some() && !streaming {
+            log::info!("Speech model not ready; buffering audio for the final transcription");
+        }
+        let power_saving = settings.power_saving();
+        let session_id = reservation.session_id();
         self.reset_model_state();
         let streaming_tx = if streaming {
-            Some(self.start_streaming(on_update)?)
+            let live = router.clone();
+            let app = self.app().clone();
+            let strategy = if power_saving {
+                StreamingStrategy::Phrase
+            } else {
+                live_strategy.unwrap_or_default()
+            };
+            Some(self.start_streaming(
+                settings.pause_markers,
+                settings.vad.config(),
+                strategy,
+                move |patch| {
+                    app.state::<Extensions>().patch(session_id, &patch);
+                    live.live(patch)
+                },
+            )?)
         } else {
             None
         };
 
-        if let Err(error) = self.recorder().start(reservation, streaming_tx) {
+        if let Err(error) = self.recorder().start(
+            reservation
+                .with_input_device(settings.input_device)
+                .with_voice_activity(settings.vad)
+                .with_noise_reducer(settings.noise_suppression.reducer())
+                .with_speaker_normalizer(settings.speaker_adaptation.normalizer())
+                .with_waveform(settings.live_waveform && !power_saving),
+            streaming_tx,
+            settings.resampler_quality,
+        ) {
             let _ = self.finish_streaming();
             return Err(error.into());
         }
-        log::info!("Dictation started (streaming={streaming})");
+        self.set_session_router(router);
+        log::info!(
+            "Dictation {session_id} started (streaming={streaming}, power_saving={power_saving})"
+        );
+        self.emit_recording_state(RECORDING_STARTED_EVENT, session_id);
+        self.app()
+            .state::<Extensions>()
+            .recording_started(&RecordingStart {
+                session_id,
+                streaming,
+            });
         Ok(())
     }
 
@@ -58,7 +254,16 @@ impl SpeechEngine {
     where
         F: FnOnce(String) -> Result<(), String>,
     {
+        let session_id = self.recorder().session_id();
         let audio_result = self.recorder().stop();
+        let stopped_at = chrono::Utc::now();
+        if let Some(session_id) =
+            session_id.filter(|_| !matches!(audio_result, Err(RecordingError::NotRecording)))
+        {
+            self.emit_recording_state(RECORDING_STOPPED_EVENT, session_id);
+        }
+        self.report_audio_health();
+        self.report_capture_quality();
         if let Err(error) = self.finish_streaming() {
             log::warn!("Streaming failed; using final offline transcription: {error}");
         }
@@ -69,16 +274,437 @@ impl SpeechEngine {
                 return Err(error.into());
             }
         };
-        let mut text = match self.transcribe_samples(audio.samples()) {
-            Ok(text) => text,
+        let settings = crate::settings::get_settings(self.app());
+        self.learn_voice(&settings.speaker_adaptation, audio.samples());
+        let transcribed = self.transcribe(
+            audio.samples(),
+            &settings.vad.config(),
+            &settings.time_stretch,
+        );
+        let mut transcript = match transcribed {
+            Ok(transcript) => transcript,
             Err(error) => {
                 on_text(String::new()).map_err(DictationError::Output)?;
                 return Err(error.into());
             }
         };
-        if text.trim().is_empty() {
-            text.clear();
+        let text = output_text(self.app(), &mut transcript, &settings);
+        if text.is_empty() {
+            return on_text(text).map_err(DictationError::Output);
+        }
+        let split = settings.capture_file.enabled && settings.capture_file.split_utterances;
+        self.hold_take(split.then(|| Take {
+            transcript: transcript.clone(),
+            samples: audio.samples().to_vec(),
+        }));
+        let delivered = on_text(text.clone());
+        self.hold_take(None);
+        delivered.map_err(DictationError::Output)?;
+        if let Err(error) = self.app().emit(FINAL_TRANSCRIPT_EVENT, &transcript) {
+            log::warn!("Could not emit {FINAL_TRANSCRIPT_EVENT}: {error}");
+        }
+        self.app()
+            .state::<Extensions>()
+            .final_transcript(&FinalTranscript {
+                session_id,
+                text: &text,
+                transcript: &transcript,
+            });
+        #[cfg(desktop)]
+        crate::desktop::publish_typed_commit(self.app(), session_id, &text, &transcript);
+
+        let duration_ms = audio.duration_ms();
+        let recorded_at = stopped_at - chrono::TimeDelta::milliseconds(duration_ms as i64);
+        self.report_speech_stats(SpeechStats::measure(
+            &transcript,
+            duration_ms,
+            recorded_at.to_rfc3339(),
+        ));
+        if settings.webhook.enabled {
+            post_webhook(
+                settings.webhook,
+                DictationPayload {
+                    text,
+                    language: transcript.language,
+                    segments: transcript.segments,
+                    audio_duration_ms: duration_ms,
+                    profile: settings.profiles.active,
+                    recorded_at: recorded_at.to_rfc3339(),
+                    finished_at: chrono::Utc::now().to_rfc3339(),
+                },
+            );
+        }
+        Ok(())
+    }
+
+    /// Transcribes a WAV file with the same settings as a dictation. Holds the
+    /// recording slot meanwhile, since both need the model.
+    pub fn transcribe_file(&self, path: &Path) -> Result<String, DictationError> {
+        self.transcribe_file_segments(path).map(|(text, _)| text)
+    }
+
+    /// Like [`SpeechEngine::transcribe_file`], also returning the segments
+    /// after post-processing, for word timings. Each segment is emitted as
+    /// `file_transcription_progress` once decoded, and
+    /// [`cancel_file_transcription`] stops before the next one.
+    pub fn transcribe_file_segments(
+        &self,
+        path: &Path,
+    ) -> Result<(String, Transcript), DictationError> {
+        let _activity =
+            crate::activity::try_begin(AppActivity::Recording).map_err(|_| DictationError::Busy)?;
+        FILE_TRANSCRIPTION_CANCELLED.store(false, Ordering::Release);
+        let samples = crate::audio_file::read_wav(path)?;
+        let settings = crate::settings::get_settings(self.app());
+        let mut report_progress = |progress: SegmentProgress| {
+            let event = FileTranscriptionProgress {
+                path: path.to_path_buf(),
+                progress,
+            };
+            if let Err(error) = self.app().emit(FILE_TRANSCRIPTION_PROGRESS_EVENT, event) {
+                log::warn!("Could not emit {FILE_TRANSCRIPTION_PROGRESS_EVENT}: {error}");
+            }
+            if FILE_TRANSCRIPTION_CANCELLED.swap(false, Ordering::AcqRel) {
+                ControlFlow::Break(())
+            } else {
+                ControlFlow::Continue(())
+            }
+        };
+        let mut transcript = self.transcribe_cached(
+            &samples,
+            &settings,
+            &settings.time_stretch,
+            None,
+            &mut report_progress,
+        )?;
+        let text = output_text(self.app(), &mut transcript, &settings);
+        Ok((text, transcript))
+    }
+
+    /// Transcribes the last `transcribe_secs` the lookback listener heard, with
+    /// the same settings as a dictation. Holds the recording slot meanwhile.
+    pub fn transcribe_lookback(&self) -> Result<String, DictationError> {
+        let _activity =
+            crate::activity::try_begin(AppActivity::Recording).map_err(|_| DictationError::Busy)?;
+        let settings = crate::settings::get_settings(self.app());
+        let lookback = settings.lookback.clamped();
+        let samples = Lookback::global().latest(lookback.transcribe_samples());
+        if samples.is_empty() {
+            return Err(RecordingError::NoAudioCaptured.into());
+        }
+        log::info!(
+            "Transcribing the last {} ms the lookback listener heard",
+            samples.len() as u64 * 1_000 / u64::from(TARGET_SAMPLE_RATE)
+        );
+        let mut transcript =
+            self.transcribe(&samples, &settings.vad.config(), &settings.time_stretch)?;
+        let text = output_text(self.app(), &mut transcript, &settings);
+        if let Err(error) = self.app().emit(FINAL_TRANSCRIPT_EVENT, &transcript) {
+            log::warn!("Could not emit {FINAL_TRANSCRIPT_EVENT}: {error}");
+        }
+        Ok(text)
+    }
+
+    /// Transcribes stored utterance clips again with `config` in place of the
+    /// matching settings, one text per clip.
+    pub fn retranscribe_clips(
+        &self,
+        clips: &[PathBuf],
+        config: &RetranscribeConfig,
+    ) -> Result<Vec<String>, DictationError> {
+        let _activity =
+            crate::activity::try_begin(AppActivity::Recording).map_err(|_| DictationError::Busy)?;
+        let settings = crate::settings::get_settings(self.app());
+        let time_stretch = config
+            .time_stretch
+            .unwrap_or(settings.time_stretch)
+            .clamped();
+        clips
+            .iter()
+            .map(|clip| -> Result<String, DictationError> {
+                let samples = crate::audio_file::read_wav(clip)?;
+                let mut transcript = self.transcribe_cached(
+                    &samples,
+                    &settings,
+                    &time_stretch,
+                    config.language.as_deref(),
+                    &mut |_| ControlFlow::Continue(()),
+                )?;
+                Ok(if config.raw {
+                    transcript.text()
+                } else {
+                    output_text(self.app(), &mut transcript, &settings)
+                })
+            })
+            .collect()
+    }
+
+    /// Decodes a file's audio, or with the decode cache on, returns the raw
+    /// transcript of an earlier decode of the same audio under the same model,
+    /// language, and segmentation without loading the model. A cached
+    /// transcript's segments are handed to `on_segment` all at once.
+    fn transcribe_cached(
+        &self,
+        samples: &[f32],
+        settings: &Settings,
+        time_stretch: &TimeStretch,
+        language: Option<&str>,
+        on_segment: OnSegment<'_>,
+    ) -> Result<Transcript, DictationError> {
+        let vad = settings.vad.config();
+        let cache = settings.decode_cache.clamped();
+        let path = cache
+            .enabled
+            .then(|| decode_cache::cache_path(self.app()))
+            .flatten();
+        let Some(path) = path else {
+            return Ok(self.transcribe_with_progress(
+                samples,
+                &vad,
+                time_stretch,
+                language,
+                on_segment,
+            )?);
+        };
+        let context = decode_cache::decode_context(
+            &crate::asr::default_model_root(self.app()),
+            language.unwrap_or(&settings.asr_language),
+            &vad,
+            time_stretch.speed(),
+        );
+        let key = decode_cache::decode_key(&decode_cache::audio_fingerprint(samples), &context);
+        let mut stored = DecodeCache::load(&path);
+        let transcript = match stored.get(&key) {
+            Some(transcript) => {
+                log::info!("Decode cache hit; skipping the model");
+                let total = transcript.segments.len();
+                for (index, segment) in transcript.segments.iter().enumerate() {
+                    let progress = SegmentProgress {
+                        completed: index + 1,
+                        total,
+                        segment: Some(segment.clone()),
+                    };
+                    if on_segment(progress).is_break() {
+                        return Err(EngineError::Cancelled.into());
+                    }
+                }
+                transcript
+            }
+            None => {
+                let transcript = self.transcribe_with_progress(
+                    samples,
+                    &vad,
+                    time_stretch,
+                    language,
+                    on_segment,
+                )?;
+                stored.insert(key, transcript.clone(), cache.max_entries);
+                transcript
+            }
+        };
+        if let Err(error) = stored.save(&path) {
+            log::warn!("Could not save the decode cache: {error}");
+        }
+        Ok(transcript)
+    }
+
+    /// Records the room without the user speaking and recommends detection
+    /// settings for it. Nothing is saved; the caller decides whether to apply
+    /// the recommendation.
+    pub fn calibrate_microphone(&self) -> Result<NoiseCalibration, DictationError> {
+        let settings = crate::settings::get_settings(self.app());
+        let reservation = self.recorder().reserve_measurement()?;
+        let audio = self.recorder().measure(
+            reservation.with_input_device(settings.input_device),
+            CALIBRATION_DURATION,
+            settings.resampler_quality,
+        )?;
+        let calibration = crate::vad::calibrate(audio.samples(), settings.vad.noise_margin_db)
+            .ok_or(RecordingError::NoAudioCaptured)?;
+        log::info!(
+            "Microphone noise floor {:.5} RMS (peak {:.5}); recommending {:?}",
+            calibration.noise_floor,
+            calibration.peak,
+            calibration.recommended
+        );
+        Ok(calibration)
+    }
+
+    /// Records the room without the user speaking and saves its spectrum as
+    /// the noise profile `name`, replacing one with that name.
+    pub fn capture_noise_profile(&self, name: &str) -> Result<NoiseSuppression, DictationError> {
+        let settings = crate::settings::get_settings(self.app());
+        let mut noise_suppression = settings.noise_suppression.clone();
+        let name = crate::noise_profile::normalize_name(name)?;
+        if noise_suppression.find(&name).is_none()
+            && noise_suppression.profiles.len() >= MAX_NOISE_PROFILES
+        {
+            return Err(NoiseProfileError::TooManyProfiles.into());
+        }
+        let reservation = self.recorder().reserve_measurement()?;
+        // Gain applies before subtraction, so the profile is taken with it.
+        let audio = self.recorder().measure(
+            reservation
+                .with_input_device(settings.input_device)
+                .with_voice_activity(settings.vad),
+            CALIBRATION_DURATION,
+            settings.resampler_quality,
+        )?;
+        noise_suppression.save_profile(NoiseProfile::capture(&name, audio.samples())?)?;
+        log::info!("Captured noise profile {name:?}");
+        Ok(noise_suppression)
+    }
+
+    /// Records a short take through the same input chain as dictation and
+    /// measures what the speech model would receive. The take is kept for
+    /// [`Self::play_microphone_test`].
+    pub fn test_microphone(&self) -> Result<MicrophoneTest, DictationError> {
+        let settings = crate::settings::get_settings(self.app());
+        let reservation = self.recorder().reserve_measurement()?;
+        let audio = self.recorder().measure(
+            reservation
+                .with_input_device(settings.input_device)
+                .with_voice_activity(settings.vad)
+                .with_noise_reducer(settings.noise_suppression.reducer())
+                .with_speaker_normalizer(settings.speaker_adaptation.normalizer()),
+            MIC_TEST_DURATION,
+            settings.resampler_quality,
+        )?;
+        if audio.samples().is_empty() {
+            return Err(RecordingError::NoAudioCaptured.into());
+        }
+        let test = crate::recording::analyze_mic_test(
+            audio.samples(),
+            audio.duration_ms(),
+            self.recorder().last_quality(),
+        );
+        log::info!(
+            "Microphone test: peak {:.1} dBFS, RMS {:.1} dBFS over {} ms",
+            test.peak_dbfs,
+            test.rms_dbfs,
+            test.duration_ms
+        );
+        *MIC_TEST_TAKE.lock_recover() = audio.samples().to_vec();
+        Ok(test)
+    }
+
+    /// Opens the microphone for a moment, which asks for access where the OS
+    /// has not been told yet, and reports whether it could be opened.
+    pub fn check_microphone_access(&self) -> Result<(), DictationError> {
+        let settings = crate::settings::get_settings(self.app());
+        let reservation = self.recorder().reserve_measurement()?;
+        match self.recorder().measure(
+            reservation.with_input_device(settings.input_device),
+            MIC_ACCESS_PROBE_DURATION,
+            settings.resampler_quality,
+        ) {
+            Ok(_) | Err(RecordingError::NoAudioCaptured) => Ok(()),
+            Err(error) => Err(error.into()),
+        }
+    }
+
+    /// Plays the latest microphone test take on the default output device.
+    pub fn play_microphone_test(&self) -> Result<(), DictationError> {
+        let take = MIC_TEST_TAKE.lock_recover().clone();
+        if take.is_empty() {
+            return Err(RecordingError::NoAudioCaptured.into());
+        }
+        crate::recording::play_samples(&take, TARGET_SAMPLE_RATE)?;
+        Ok(())
+    }
+
+    /// Recording starts from both the UI and global shortcuts, so the UI
+    /// follows these events instead of its own button state.
+    fn emit_recording_state(&self, event: &str, session_id: SessionId) {
+        if let Err(error) = self.app().emit(event, session_id) {
+            log::warn!("Could not emit {event}: {error}");
         }
-        on_text(text).map_err(DictationError::Output)
     }
+
+    fn report_audio_health(&self) {
+        let Some(health) = self.recorder().last_health() else {
+            return;
+        };
+        if health.is_degraded() {
+            log::warn!("Audio capture degraded: {health:?}");
+        }
+        if let Err(error) = self.app().emit("audio_health", health) {
+            log::warn!("Could not emit audio health: {error}");
+        }
+    }
+
+    fn report_speech_stats(&self, stats: Option<SpeechStats>) {
+        let Some(stats) = stats else {
+            return;
+        };
+        if let Err(error) = crate::speech_stats::record_session(self.app(), stats.clone()) {
+            log::warn!("{error}");
+        }
+        if let Err(error) = self.app().emit(SPEECH_STATS_EVENT, stats) {
+            log::warn!("Could not emit {SPEECH_STATS_EVENT}: {error}");
+        }
+    }
+
+    /// Adds the recording's voice to the speaker profile. The recording was
+    /// corrected by the profile as it stood, which is undone first.
+    fn learn_voice(&self, adaptation: &SpeakerAdaptation, samples: &[f32]) {
+        if !adaptation.enabled {
+            return;
+        }
+        let restored = adaptation
+            .correction()
+            .map(|correction| correction.undo(samples));
+        let Some(stats) = measure_voice(restored.as_deref().unwrap_or(samples)) else {
+            return;
+        };
+        if let Err(error) = crate::settings::observe_voice(self.app(), stats) {
+            log::warn!("Could not update the speaker profile: {error}");
+        }
+    }
+
+    fn report_capture_quality(&self) {
+        let Some(quality) = self.recorder().last_quality() else {
+            return;
+        };
+        for suggestion in &quality.suggestions {
+            log::warn!(
+                "Capture quality, session {}: {suggestion}",
+                quality.session_id
+            );
+        }
+        if let Err(error) = self.app().emit(CAPTURE_QUALITY_EVENT, quality) {
+            log::warn!("Could not emit {CAPTURE_QUALITY_EVENT}: {error}");
+        }
+    }
+}
+
+/// Runs the post-processing stages selected for the transcript's language on
+/// each segment, then joins them with the configured pause markers.
+fn output_text(app: &AppHandle, transcript: &mut Transcript, settings: &Settings) -> String {
+    let processor = app.state::<PostProcessor>();
+    let context = StageContext {
+        language: transcript.language.as_deref(),
+        text_commands: &settings.text_commands,
+    };
+    for segment in &mut transcript.segments {
+        segment.text = processor.run(&segment.text, &context, &settings.post_processing);
+    }
+    transcript.text_with_pauses(&settings.pause_markers)
+}
+
+/// Reads the signing secret only once the webhook is known to be on, as the
+/// keychain may ask the user first. A secret that cannot be read skips the
+/// webhook rather than sending it unsigned.
+fn post_webhook(webhook: WebhookSettings, payload: DictationPayload) {
+    #[cfg(desktop)]
+    let secret = match crate::desktop::webhook_secret() {
+        Ok(secret) => secret,
+        Err(error) => {
+            log::warn!("Dictation webhook not sent: {error}");
+            return;
+        }
+    };
+    #[cfg(not(desktop))]
+    let secret = String::new();
+    crate::webhook::dispatch(webhook, secret, payload);
 }
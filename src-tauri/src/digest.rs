@@ -0,0 +1,186 @@
+use std::io;
+use std::path::{Path, PathBuf};
+
+use chrono::{NaiveDate, NaiveDateTime, Timelike};
+use serde::Serialize;
+
+use crate::capture_file::{capture_path, parse_heading};
+use crate::errors::UserFacing;
+
+const DATE_FORMAT: &str = "%Y-%m-%d";
+
+/// One dictation read back from the capture file.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct DigestEntry {
+    pub at: NaiveDateTime,
+    pub title: Option<String>,
+    pub text: String,
+}
+
+#[derive(Clone, Debug, Default, Eq, PartialEq, Serialize)]
+pub struct DigestExport {
+    pub markdown: String,
+    /// Where the digest was written, when a notes folder was given.
+    pub path: Option<String>,
+}
+
+#[derive(thiserror::Error, Debug)]
+pub enum DigestError {
+    #[error("invalid digest date {0:?}")]
+    InvalidDate(String),
+    #[error("digest range ends before it starts: {from} to {to}")]
+    InvalidRange { from: NaiveDate, to: NaiveDate },
+    #[error("no capture file is configured")]
+    NoCaptureFile,
+    #[error(transparent)]
+    CaptureFile(#[from] crate::capture_file::CaptureFileError),
+    #[error("read capture file {path:?}: {source}")]
+    Read {
+        path: PathBuf,
+        #[source]
+        source: io::Error,
+    },
+    #[error("write digest {path:?}: {source}")]
+    Write {
+        path: PathBuf,
+        #[source]
+        source: io::Error,
+    },
+}
+
+impl UserFacing for DigestError {
+    fn user_message(&self) -> &'static str {
+        match self {
+            Self::InvalidDate(_) => "Enter digest dates as YYYY-MM-DD.",
+            Self::InvalidRange { .. } => "Choose a start date on or before the end date.",
+            Self::NoCaptureFile | Self::CaptureFile(_) => {
+                "Set a capture file first; the digest is compiled from its entries."
+            }
+            Self::Read { .. } => "Could not read the capture file.",
+            Self::Write { .. } => {
+                "Could not write the digest. Check that the notes folder exists and is writable."
+            }
+        }
+    }
+}
+
+pub fn parse_date(date: &str) -> Result<NaiveDate, DigestError> {
+    NaiveDate::parse_from_str(date.trim(), DATE_FORMAT)
+        .map_err(|_| DigestError::InvalidDate(date.to_string()))
+}
+
+/// Splits capture file contents into entries at each timestamp heading. Text
+/// before the first heading, or under headings that are not timestamps, is
+/// skipped.
+pub fn parse_entries(contents: &str) -> Vec<DigestEntry> {
+    let mut entries = Vec::new();
+    let mut current: Option<(NaiveDateTime, Option<&str>, Vec<&str>)> = None;
+    for line in contents.lines() {
+        if let Some(heading) = line.strip_prefix("## ") {
+            entries.extend(current.take().map(finish_entry));
+            current = parse_heading(heading).map(|(at, title)| (at, title, Vec::new()));
+        } else if let Some((_, _, lines)) = current.as_mut() {
+            lines.push(line);
+        }
+    }
+    entries.extend(current.map(finish_entry));
+    entries.retain(|entry| !entry.text.is_empty());
+    entries
+}
+
+fn finish_entry((at, title, lines): (NaiveDateTime, Option<&str>, Vec<&str>)) -> DigestEntry {
+    DigestEntry {
+        at,
+        title: title.map(str::to_string),
+        text: lines.join("\n").trim().to_string(),
+    }
+}
+
+/// Renders the entries dated `from` through `to` as Markdown, one section per
+/// day and one subsection per hour, in time order.
+pub fn render_digest(entries: &[DigestEntry], from: NaiveDate, to: NaiveDate) -> String {
+    let mut selected = entries
+        .iter()
+        .filter(|entry| (from..=to).contains(&entry.at.date()))
+        .collect::<Vec<_>>();
+    selected.sort_by_key(|entry| entry.at);
+
+    let mut markdown = if from == to {
+        format!("# Dictation digest for {from}\n")
+    } else {
+        format!("# Dictation digest, {from} to {to}\n")
+    };
+    if selected.is_empty() {
+        markdown.push_str("\nNo dictations in this range.\n");
+        return markdown;
+    }
+    let mut day = None;
+    let mut hour = None;
+    for entry in selected {
+        if day != Some(entry.at.date()) {
+            day = Some(entry.at.date());
+            hour = None;
+            markdown.push_str(&format!("\n## {}\n", entry.at.format("%A, %Y-%m-%d")));
+        }
+        if hour != Some(entry.at.hour()) {
+            hour = Some(entry.at.hour());
+            markdown.push_str(&format!("\n### {:02}:00\n", entry.at.hour()));
+        }
+        let label = match &entry.title {
+            Some(title) => format!("{} {title}", entry.at.format("%H:%M")),
+            None => entry.at.format("%H:%M").to_string(),
+        };
+        markdown.push_str(&format!("\n**{label}** {}\n", entry.text));
+    }
+    markdown
+}
+
+pub fn digest_file_name(from: NaiveDate, to: NaiveDate) -> String {
+    if from == to {
+        format!("digest-{from}.md")
+    } else {
+        format!("digest-{from}-to-{to}.md")
+    }
+}
+
+/// Compiles the digest from the capture file at `capture_file` and, when
+/// `notes_dir` is given, writes it there, replacing an earlier digest of the
+/// same range.
+pub fn export_digest(
+    capture_file: &str,
+    from: &str,
+    to: &str,
+    notes_dir: Option<&str>,
+) -> Result<DigestExport, DigestError> {
+    let (from, to) = (parse_date(from)?, parse_date(to)?);
+    if to < from {
+        return Err(DigestError::InvalidRange { from, to });
+    }
+    if capture_file.trim().is_empty() {
+        return Err(DigestError::NoCaptureFile);
+    }
+    let source = capture_path(capture_file)?;
+    let contents = match crate::at_rest::read_to_string(&source) {
+        Ok(contents) => contents,
+        Err(error) if error.kind() == io::ErrorKind::NotFound => String::new(),
+        Err(source_error) => {
+            return Err(DigestError::Read {
+                path: source,
+                source: source_error,
+            })
+        }
+    };
+    let markdown = render_digest(&parse_entries(&contents), from, to);
+    let path = match notes_dir.map(str::trim).filter(|dir| !dir.is_empty()) {
+        Some(dir) => {
+            let path = Path::new(dir).join(digest_file_name(from, to));
+            std::fs::write(&path, &markdown).map_err(|source| DigestError::Write {
+                path: path.clone(),
+                source,
+            })?;
+            Some(path.display().to_string())
+        }
+        None => None,
+    };
+    Ok(DigestExport { markdown, path })
+}
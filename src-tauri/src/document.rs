@@ -0,0 +1,102 @@
+/// Spoken commands that shape a document: each is "new" followed by one of
+/// these words, and the punctuation the model attaches to it is dropped.
+const PARAGRAPH: &str = "paragraph";
+const LINE: &str = "line";
+const HEADING: &str = "heading";
+const SUBHEADING: &str = "subheading";
+
+/// Appends one dictation to a Markdown document. Dictations continue the
+/// current paragraph; "new paragraph" and "new line" break it, and "new
+/// heading" or "new subheading" turns the rest of the sentence into a heading.
+pub fn append_dictation(document: &mut String, text: &str) {
+    let words: Vec<&str> = text.split_whitespace().collect();
+    let mut in_heading = false;
+    let mut index = 0;
+    while index < words.len() {
+        let command = if normalize(words[index]) == "new" {
+            words.get(index + 1).map(|word| normalize(word))
+        } else {
+            None
+        };
+        match command.as_deref() {
+            Some(PARAGRAPH) => {
+                end_block(document);
+                in_heading = false;
+            }
+            Some(LINE) if in_heading => {
+                end_block(document);
+                in_heading = false;
+            }
+            Some(LINE) => {
+                trim_spaces(document);
+                if !document.is_empty() && !document.ends_with('\n') {
+                    document.push('\n');
+                }
+            }
+            Some(HEADING) | Some(SUBHEADING) => {
+                end_block(document);
+                document.push_str(if command.as_deref() == Some(HEADING) {
+                    "# "
+                } else {
+                    "## "
+                });
+                in_heading = true;
+            }
+            _ => {
+                push_word(document, words[index], &mut in_heading);
+                index += 1;
+                continue;
+            }
+        }
+        index += 2;
+    }
+    if in_heading {
+        end_block(document);
+    }
+}
+
+/// The document without heading markers, for typing into apps that do not
+/// render Markdown.
+pub fn plain_text(document: &str) -> String {
+    document
+        .trim_end()
+        .lines()
+        .map(|line| {
+            line.strip_prefix("## ")
+                .or_else(|| line.strip_prefix("# "))
+                .unwrap_or(line)
+        })
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+fn push_word(document: &mut String, word: &str, in_heading: &mut bool) {
+    if !document.is_empty() && !document.ends_with([' ', '\n']) {
+        document.push(' ');
+    }
+    if *in_heading && word.ends_with(['.', '!', '?']) {
+        document.push_str(word.trim_end_matches('.'));
+        end_block(document);
+        *in_heading = false;
+    } else {
+        document.push_str(word);
+    }
+}
+
+/// Finishes the current paragraph or heading so the next text starts a new
+/// block.
+fn end_block(document: &mut String) {
+    document.truncate(document.trim_end().len());
+    if !document.is_empty() {
+        document.push_str("\n\n");
+    }
+}
+
+fn trim_spaces(document: &mut String) {
+    document.truncate(document.trim_end_matches(' ').len());
+}
+
+fn normalize(word: &str) -> String {
+    word.trim_matches(|c: char| !c.is_alphanumeric())
+        .to_lowercase()
+}
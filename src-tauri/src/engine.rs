@@ -1,43 +1,71 @@
-use std::sync::{Arc, Condvar, Mutex, RwLock};
+use std::cell::Cell;
+use std::ops::{ControlFlow, Range};
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, Condvar, Mutex, MutexGuard, RwLock};
 use std::time::{Duration, Instant};
 
 use crate::asr::{
-    default_model_root, invalidate_model_verification, resolve_model_dir_with_progress, AsrError,
-    AsrModel,
+    cancel_model_download, current_download_progress, default_model_root, discard_model_download,
+    import_model_archive, invalidate_model_verification, model_download_cancelled,
+    pause_model_download, reset_model_download_cancel, resolve_model_dir_with_progress,
+    resume_model_download, signature_report, tune_threads, write_model, AsrError, AsrModel,
+    DownloadProgress, ModelLoadStage, OrtThreads, SessionPool, ThreadTuning, TARGET_SAMPLE_RATE,
 };
+use crate::asr::{decode_in_order, default_pool_size};
 use crate::errors::UserFacing;
+use crate::patch_routing::PatchRouter;
 use crate::recording::Recorder;
-use crate::streaming::{StreamingError, StreamingPipeline, UpdateSink};
-use serde::Serialize;
-use tauri::{AppHandle, Emitter};
+use crate::settings::ModelPrewarm;
+use crate::streaming::{StreamingError, StreamingPipeline, StreamingStrategy, UpdateSink};
+use crate::sync::{recover, MutexRecover, RwLockRecover};
+use crate::time_stretch::{stretch, TimeStretch};
+use crate::transcript::{PauseMarkers, Transcript, TranscriptSegment};
+use crate::vad::{speech_segments, VadConfig};
+use serde::{Deserialize, Serialize};
+use tauri::{AppHandle, Emitter, Manager};
 
-const MODEL_LOAD_TIMEOUT: Duration = Duration::from_secs(5 * 60);
+/// How often a wait for the model checks that its load is still moving.
+const LOAD_PROGRESS_POLL: Duration = Duration::from_secs(1);
+/// A load that neither downloads a byte nor reaches a new stage for this long
+/// is taken to be stuck. A paused download is the user's choice, not a stall.
+const MODEL_LOAD_STALL_TIMEOUT: Duration = Duration::from_secs(5 * 60);
+/// How often a reload checks whether the dictation it waits for has ended.
+const RELOAD_POLL_INTERVAL: Duration = Duration::from_millis(100);
+/// Installer resource folder whose loose model files seed the snapshot
+/// before anything is downloaded.
+const BUNDLED_MODEL_DIR: &str = "models";
+/// Audio at least this long is split across extra model sessions that decode
+/// its segments alongside the loaded model. Shorter audio does not repay
+/// loading them.
+const POOLED_DECODE_MIN_SAMPLES: usize = 60 * TARGET_SAMPLE_RATE as usize;
 
 #[derive(thiserror::Error, Debug)]
 pub enum EngineError {
     #[error(transparent)]
     Asr(#[from] AsrError),
-    #[error("speech engine state is unavailable")]
-    StateUnavailable,
     #[error("speech model is unavailable")]
     ModelUnavailable,
-    #[error("speech model load timed out")]
-    LoadTimeout,
+    #[error("speech model load stalled")]
+    LoadStalled,
+    #[error("transcription was cancelled")]
+    Cancelled,
 }
 
 impl UserFacing for EngineError {
     fn user_message(&self) -> &'static str {
         match self {
             Self::Asr(error) => error.user_message(),
-            Self::LoadTimeout => "The speech model took too long to load. Please try again.",
-            Self::StateUnavailable | Self::ModelUnavailable => {
-                "The speech engine is unavailable. Please restart the app."
+            Self::LoadStalled => {
+                "The speech model stopped loading. Check your connection and try again."
             }
+            Self::ModelUnavailable => "The speech engine is unavailable. Please restart the app.",
+            Self::Cancelled => "The transcription was cancelled.",
         }
     }
 }
 
-#[derive(Clone, Debug, PartialEq, Serialize)]
+#[derive(Clone, Debug, Deserialize, PartialEq, Serialize)]
 #[serde(tag = "state", content = "message", rename_all = "snake_case")]
 pub enum EngineState {
     Unloaded,
@@ -46,25 +74,100 @@ pub enum EngineState {
     Failed(String),
 }
 
+/// Sent when a shortcut press arrives before the model can transcribe, so the
+/// UI can explain the wait instead of recording silently.
+#[derive(Clone, Debug, Serialize)]
+pub struct ModelNotReadyNotice {
+    pub state: EngineState,
+    pub progress: Option<DownloadProgress>,
+    pub buffering: bool,
+}
+
+/// Emitted as `model_status` at the start of each load stage. `elapsed_ms`
+/// counts from the beginning of the load, including any download.
+#[derive(Clone, Debug, Serialize)]
+pub struct ModelStatus {
+    pub stage: ModelLoadStage,
+    pub elapsed_ms: u64,
+}
+
+/// One speech segment of a longer decode, reported as soon as it is
+/// decoded. `segment` is `None` when it held no words; its text is the
+/// model's, before post-processing.
+#[derive(Clone, Debug, Serialize)]
+pub struct SegmentProgress {
+    pub completed: usize,
+    pub total: usize,
+    pub segment: Option<TranscriptSegment>,
+}
+
+/// How long a caller waits on a model load that is still running.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum LoadWait {
+    /// Give up once the load makes no progress for
+    /// [`MODEL_LOAD_STALL_TIMEOUT`].
+    UntilStalled,
+    /// Wait until the load finishes or is skipped, for audio that would
+    /// otherwise be lost.
+    UntilDone,
+}
+
+/// Called with each decoded segment; breaking cancels the decode.
+pub type OnSegment<'a> = &'a mut dyn FnMut(SegmentProgress) -> ControlFlow<()>;
+
+/// A finished dictation's segments and the 16 kHz audio they were decoded
+/// from, held while its text is delivered so a capture can be split into
+/// utterances.
+#[derive(Clone, Debug)]
+pub struct Take {
+    pub transcript: Transcript,
+    pub samples: Vec<f32>,
+}
+
 #[derive(Clone)]
 pub struct SpeechEngine {
     model: Arc<RwLock<Option<AsrModel>>>,
+    /// Extra sessions for long decodes, loaded on the first one and kept
+    /// until the model reloads.
+    session_pool: Arc<Mutex<Option<SessionPool>>>,
     status: Arc<Mutex<EngineState>>,
     status_cv: Arc<Condvar>,
+    /// When the running load last downloaded a chunk or reached a stage.
+    load_progress: Arc<Mutex<Instant>>,
     streaming_pipeline: Arc<StreamingPipeline>,
     app_handle: AppHandle,
     recorder: &'static Recorder,
+    /// Where the current or last dictation's patches and final text go.
+    session_router: Arc<Mutex<PatchRouter>>,
+    held_take: Arc<Mutex<Option<Take>>>,
+    /// Set when the model location changed; the reload thread clears it, so
+    /// requests made while a reload runs are picked up by that thread.
+    reload_requested: Arc<AtomicBool>,
+    reloading: Arc<AtomicBool>,
 }
 
 impl SpeechEngine {
     pub fn new(app_handle: AppHandle) -> Self {
         Self {
             model: Arc::new(RwLock::new(None)),
+            session_pool: Arc::new(Mutex::new(None)),
             status: Arc::new(Mutex::new(EngineState::Unloaded)),
             status_cv: Arc::new(Condvar::new()),
-            streaming_pipeline: Arc::new(StreamingPipeline::new()),
+            load_progress: Arc::new(Mutex::new(Instant::now())),
+            streaming_pipeline: Arc::new(StreamingPipeline::new().with_activity_sink({
+                let app = app_handle.clone();
+                move |activity| {
+                    if let Err(error) = app.emit(activity.event_name(), activity) {
+                        log::warn!("Could not emit speech activity: {error}");
+                    }
+                }
+            })),
             app_handle,
             recorder: Recorder::global(),
+            session_router: Arc::new(Mutex::new(PatchRouter::default())),
+            held_take: Arc::new(Mutex::new(None)),
+            reload_requested: Arc::new(AtomicBool::new(false)),
+            reloading: Arc::new(AtomicBool::new(false)),
         }
     }
 
@@ -76,23 +179,191 @@ impl SpeechEngine {
         &self.app_handle
     }
 
+    /// Takes the current dictation's router, so its sinks are dropped once
+    /// the final text is delivered.
+    pub(crate) fn take_session_router(&self) -> PatchRouter {
+        std::mem::take(&mut *self.session_router.lock_recover())
+    }
+
+    pub(crate) fn set_session_router(&self, router: PatchRouter) {
+        *self.session_router.lock_recover() = router;
+    }
+
+    pub(crate) fn hold_take(&self, take: Option<Take>) {
+        *self.held_take.lock_recover() = take;
+    }
+
+    /// Takes the dictation being delivered, if the delivery asked for it to
+    /// be held and nothing took it yet.
+    pub(crate) fn take_held(&self) -> Option<Take> {
+        self.held_take.lock_recover().take()
+    }
+
     pub fn is_dictating(&self) -> bool {
         self.recorder.is_recording()
     }
 
     pub fn state(&self) -> EngineState {
-        self.status
-            .lock()
-            .map(|status| status.clone())
-            .unwrap_or_else(|_| EngineState::Failed("Speech engine state is unavailable.".into()))
+        self.status.lock_recover().clone()
     }
 
     pub fn is_ready(&self) -> bool {
         self.state() == EngineState::Loaded
     }
 
+    /// Explains a shortcut press that arrived before the model was ready,
+    /// starting the load first when prewarm waits for first use.
+    pub fn notify_model_not_ready(&self, buffering: bool) {
+        let prewarm = crate::settings::get_settings(&self.app_handle).effective_model_prewarm();
+        if prewarm == ModelPrewarm::OnFirstUse {
+            self.start_loading();
+        }
+        let notice = ModelNotReadyNotice {
+            state: self.state(),
+            progress: current_download_progress().filter(|progress| !progress.done),
+            buffering,
+        };
+        log::info!("Speech model not ready on shortcut ({:?})", notice.state);
+        if let Err(error) = self.app_handle.emit("model_not_ready", notice) {
+            log::warn!("Could not emit model readiness notice: {error}");
+        }
+    }
+
+    /// Starts loading the model in the background if nothing has started it.
+    pub fn start_loading(&self) {
+        let mut status = self.status.lock_recover();
+        if *status == EngineState::Unloaded {
+            *status = EngineState::Loading;
+            drop(status);
+            self.spawn_load();
+        }
+    }
+
+    /// Skips the current load. A download stops at its next read and keeps
+    /// what it fetched; once ONNX sessions are loading, the load finishes.
+    pub fn cancel_model_load(&self) {
+        if *self.status.lock_recover() == EngineState::Loading {
+            log::info!("Skipping speech model load");
+            cancel_model_download();
+        }
+    }
+
+    /// Cancels the current load like [`Self::cancel_model_load`], but deletes
+    /// the partially downloaded files instead of keeping them.
+    pub fn discard_model_download(&self) {
+        if *self.status.lock_recover() == EngineState::Loading {
+            log::info!("Cancelling speech model download");
+            discard_model_download();
+        }
+    }
+
+    /// Pauses or resumes the download of a running load. The paused state is
+    /// sent on `model_download_progress` right away, as the download itself
+    /// only notices at its next read.
+    pub fn set_model_download_paused(&self, paused: bool) {
+        if *self.status.lock_recover() != EngineState::Loading {
+            return;
+        }
+        if paused {
+            log::info!("Pausing speech model download");
+            pause_model_download();
+        } else {
+            log::info!("Resuming speech model download");
+            resume_model_download();
+        }
+        if let Some(progress) = current_download_progress().filter(|progress| !progress.done) {
+            if let Err(error) = self.app_handle.emit("model_download_progress", progress) {
+                log::warn!("Could not emit model download progress: {error}");
+            }
+        }
+    }
+
+    /// Loads the model again from the configured location, for a changed
+    /// model path. The reload waits for the current dictation to end and any
+    /// transcription to release the model, then drops the old sessions before
+    /// loading; `model_status` reports each step. Nothing happens while no
+    /// model has been loaded, as the next load already reads the new path.
+    pub fn reload_model(&self) {
+        if *self.status.lock_recover() == EngineState::Unloaded {
+            log::info!("Model path changed before the speech model was loaded");
+            return;
+        }
+        self.reload_requested.store(true, Ordering::SeqCst);
+        if self.reloading.swap(true, Ordering::SeqCst) {
+            return;
+        }
+        let engine = self.clone();
+        let result = std::thread::Builder::new()
+            .name("model-reload".to_string())
+            .spawn(move || loop {
+                while engine.reload_requested.swap(false, Ordering::SeqCst) {
+                    engine.run_reload();
+                }
+                engine.reloading.store(false, Ordering::SeqCst);
+                // A request made after the last check but before `reloading`
+                // cleared left its reload to this thread.
+                if !engine.reload_requested.load(Ordering::SeqCst)
+                    || engine.reloading.swap(true, Ordering::SeqCst)
+                {
+                    break;
+                }
+            });
+        if let Err(error) = result {
+            self.reloading.store(false, Ordering::SeqCst);
+            log::error!("Could not start the speech model reload: {error}");
+        }
+    }
+
+    fn run_reload(&self) {
+        let start = Instant::now();
+        log::info!("Reloading the speech model");
+        if self.is_dictating() {
+            emit_model_status(&self.app_handle, ModelLoadStage::WaitingForDictation, start);
+            while self.is_dictating() {
+                std::thread::sleep(RELOAD_POLL_INTERVAL);
+            }
+        }
+
+        let mut status = self.status.lock_recover();
+        if *status == EngineState::Loading {
+            // A load in flight would finish with the old path; stop a
+            // download, or let session loading finish, before reloading.
+            cancel_model_download();
+            status = match self.wait_for_load(status, LoadWait::UntilStalled) {
+                Ok(status) => status,
+                Err(_) => {
+                    log::error!("Speech model reload gave up waiting for the previous load");
+                    drop(status);
+                    emit_model_status(&self.app_handle, ModelLoadStage::Failed, start);
+                    return;
+                }
+            };
+        }
+        *status = EngineState::Loading;
+        drop(status);
+        *self.load_progress.lock_recover() = Instant::now();
+        self.emit_engine_state(&EngineState::Loading);
+
+        emit_model_status(&self.app_handle, ModelLoadStage::Unloading, start);
+        // Taking the write lock waits out any transcription still decoding.
+        let previous = write_model(&self.model).take();
+        drop(self.session_pool.lock_recover().take());
+        drop(previous);
+        log::info!("Released the previous speech model sessions");
+
+        reset_model_download_cancel();
+        Self::finish_load(
+            || Self::init_model(&self.app_handle, &self.load_progress, start),
+            &self.app_handle,
+            &self.model,
+            &self.status,
+            &self.status_cv,
+        );
+    }
+
     pub fn retry_model_download(&self) -> Result<(), EngineError> {
-        if let Ok(mut status) = self.status.lock() {
+        {
+            let mut status = self.status.lock_recover();
             if matches!(*status, EngineState::Failed(_)) {
                 *status = EngineState::Unloaded;
             }
@@ -100,56 +371,35 @@ impl SpeechEngine {
         self.ensure_model_loaded()
     }
 
+    /// Unpacks a model archive into the configured model location and loads
+    /// it if no model is loaded yet.
+    pub fn import_model_archive(&self, archive_path: &Path) -> Result<(), EngineError> {
+        import_model_archive(archive_path, default_model_root(&self.app_handle))?;
+        self.retry_model_download()
+    }
+
+    /// Loads the model if needed and waits for it, giving up with
+    /// [`EngineError::LoadStalled`] if the load stops making progress.
     pub fn ensure_model_loaded(&self) -> Result<(), EngineError> {
+        self.load_and_wait(LoadWait::UntilStalled)
+    }
+
+    fn load_and_wait(&self, until: LoadWait) -> Result<(), EngineError> {
         loop {
-            let mut status = self
-                .status
-                .lock()
-                .map_err(|_| EngineError::StateUnavailable)?;
+            let mut status = self.status.lock_recover();
             match *status {
                 EngineState::Loaded => return Ok(()),
                 EngineState::Unloaded => {
                     *status = EngineState::Loading;
                     drop(status);
-                    self.emit_engine_state(&EngineState::Loading);
-
-                    let app_handle = self.app_handle.clone();
-                    let model_arc = self.model.clone();
-                    let state_arc = self.status.clone();
-                    let condvar = self.status_cv.clone();
-
-                    std::thread::spawn(move || {
-                        let outcome = match Self::init_model(&app_handle) {
-                            Ok(model) => match model_arc.write() {
-                                Ok(mut model_slot) => {
-                                    *model_slot = Some(model);
-                                    EngineState::Loaded
-                                }
-                                Err(_) => {
-                                    EngineState::Failed("Speech model state is unavailable.".into())
-                                }
-                            },
-                            Err(error) => {
-                                log::error!("Speech model init failed: {error}");
-                                EngineState::Failed(error.user_message().to_string())
-                            }
-                        };
-                        if let Ok(mut state) = state_arc.lock() {
-                            *state = outcome.clone();
-                        }
-                        if let Err(error) = app_handle.emit("engine_state", outcome) {
-                            log::warn!("Could not emit speech engine state: {error}");
-                        }
-                        condvar.notify_all();
-                    });
+                    self.spawn_load();
                 }
                 EngineState::Loading => {
-                    let (_status, wait_result) = self
-                        .status_cv
-                        .wait_timeout(status, MODEL_LOAD_TIMEOUT)
-                        .map_err(|_| EngineError::StateUnavailable)?;
-                    if wait_result.timed_out() {
-                        return Err(EngineError::LoadTimeout);
+                    let status = self.wait_for_load(status, until)?;
+                    // Only a skipped load returns to `Unloaded`; starting
+                    // another here would undo the skip.
+                    if *status == EngineState::Unloaded {
+                        return Err(AsrError::Cancelled.into());
                     }
                 }
                 EngineState::Failed(_) => {
@@ -159,30 +409,226 @@ impl SpeechEngine {
         }
     }
 
-    pub fn transcribe_samples(&self, samples: &[f32]) -> Result<String, EngineError> {
+    /// Waits while `status` is `Loading`. The wait goes on as long as the
+    /// download or load keeps moving, or the download is paused.
+    fn wait_for_load<'a>(
+        &self,
+        mut status: MutexGuard<'a, EngineState>,
+        until: LoadWait,
+    ) -> Result<MutexGuard<'a, EngineState>, EngineError> {
+        let mut stall_logged = false;
+        while *status == EngineState::Loading {
+            status = recover(self.status_cv.wait_timeout(status, LOAD_PROGRESS_POLL)).0;
+            let paused = current_download_progress()
+                .is_some_and(|progress| progress.paused && !progress.done);
+            if paused {
+                *self.load_progress.lock_recover() = Instant::now();
+                continue;
+            }
+            let idle = self.load_progress.lock_recover().elapsed();
+            if idle < MODEL_LOAD_STALL_TIMEOUT {
+                continue;
+            }
+            match until {
+                LoadWait::UntilStalled => return Err(EngineError::LoadStalled),
+                LoadWait::UntilDone if !stall_logged => {
+                    log::warn!(
+                        "Speech model load has made no progress for {idle:?}; still waiting"
+                    );
+                    stall_logged = true;
+                }
+                LoadWait::UntilDone => {}
+            }
+        }
+        Ok(status)
+    }
+
+    /// Times decodes of `samples` with each candidate thread split. Every
+    /// candidate loads its own copy of the model, so the loaded one keeps
+    /// serving dictation while this runs.
+    pub fn tune_threads(
+        &self,
+        samples: &[f32],
+        candidates: &[OrtThreads],
+    ) -> Result<ThreadTuning, EngineError> {
         self.ensure_model_loaded()?;
+        let model_dir = resolve_model_dir_with_progress(
+            default_model_root(&self.app_handle),
+            bundled_model_dir(&self.app_handle).as_deref(),
+            |_| {},
+        )?;
+        let language = crate::settings::get_settings(&self.app_handle).asr_language;
+        let tuning = tune_threads(candidates, |threads| {
+            let elapsed = AsrModel::time_decode(&model_dir, &language, threads, samples)?;
+            log::info!(
+                "ASR decode with {} intra and {} inter threads took {elapsed:?}",
+                threads.intra_threads,
+                threads.inter_threads
+            );
+            Ok::<_, AsrError>(elapsed)
+        })?;
+        Ok(tuning)
+    }
+
+    /// The inputs and outputs of the model's ONNX graphs as text for a bug
+    /// report. Opens the graphs again, so it takes a few seconds.
+    pub fn model_signature_report(&self) -> Result<String, EngineError> {
+        let model_dir = resolve_model_dir_with_progress(
+            default_model_root(&self.app_handle),
+            bundled_model_dir(&self.app_handle).as_deref(),
+            |_| {},
+        )?;
+        Ok(signature_report(&model_dir))
+    }
+
+    /// Decodes each VAD speech segment separately, so silence around and
+    /// between phrases is skipped. Audio with no detected speech is decoded
+    /// whole rather than dropped. With time stretch on, each segment is slowed
+    /// before decoding; timestamps still refer to the recording.
+    pub fn transcribe(
+        &self,
+        samples: &[f32],
+        vad: &VadConfig,
+        time_stretch: &TimeStretch,
+    ) -> Result<Transcript, EngineError> {
+        self.transcribe_with_language(samples, vad, time_stretch, None)
+    }
+
+    /// Like [`SpeechEngine::transcribe`], prompting with `language` for this
+    /// decode only; the model's language is restored afterwards.
+    pub fn transcribe_with_language(
+        &self,
+        samples: &[f32],
+        vad: &VadConfig,
+        time_stretch: &TimeStretch,
+        language: Option<&str>,
+    ) -> Result<Transcript, EngineError> {
+        self.transcribe_with_progress(samples, vad, time_stretch, language, &mut |_| {
+            ControlFlow::Continue(())
+        })
+    }
 
-        let mut model_guard = self
-            .model
-            .write()
-            .map_err(|_| EngineError::ModelUnavailable)?;
+    /// Like [`SpeechEngine::transcribe_with_language`], handing each segment
+    /// to `on_segment` as it is decoded. Breaking from `on_segment` stops
+    /// before the next segment with [`EngineError::Cancelled`].
+    pub fn transcribe_with_progress(
+        &self,
+        samples: &[f32],
+        vad: &VadConfig,
+        time_stretch: &TimeStretch,
+        language: Option<&str>,
+        on_segment: OnSegment<'_>,
+    ) -> Result<Transcript, EngineError> {
+        // The audio is already captured, so it waits out a slow or stalled
+        // load rather than being dropped; skipping the load still ends it.
+        let mut model_guard = loop {
+            self.load_and_wait(LoadWait::UntilDone)?;
+            let guard = write_model(&self.model);
+            // A reload may drop the model between the check and the lock;
+            // it marks the engine loading first, so wait for the new one.
+            if guard.is_some() || self.is_ready() {
+                break guard;
+            }
+        };
         let model = model_guard.as_mut().ok_or(EngineError::ModelUnavailable)?;
 
-        let text = model.transcribe_samples(samples)?;
+        let previous = match language {
+            Some(language) => {
+                let previous = model.language().map(str::to_string);
+                model.set_language(language)?;
+                previous
+            }
+            None => None,
+        };
+        let decoded = self.decode_segments(model, samples, vad, time_stretch, on_segment);
+        if let Some(previous) = previous {
+            if let Err(error) = model.set_language(&previous) {
+                log::warn!("Could not restore speech language {previous}: {error}");
+            }
+        }
+        let transcript = decoded?;
 
-        if !text.trim().is_empty() {
-            let char_count = text.chars().count();
-            log::info!("Transcription complete ({} chars)", char_count);
+        if !transcript.is_empty() {
+            let char_count = transcript.text().chars().count();
+            log::info!(
+                "Transcription complete ({} chars in {} segments)",
+                char_count,
+                transcript.segments.len()
+            );
         }
 
-        Ok(text)
+        Ok(transcript)
+    }
+
+    /// Decodes each segment of long audio on the session pool as well as
+    /// `model`, reporting segments in order either way.
+    fn decode_segments(
+        &self,
+        model: &mut AsrModel,
+        samples: &[f32],
+        vad: &VadConfig,
+        time_stretch: &TimeStretch,
+        on_segment: OnSegment<'_>,
+    ) -> Result<Transcript, EngineError> {
+        let mut segments = speech_segments(samples, vad);
+        if segments.is_empty() {
+            segments.push(0..samples.len());
+        }
+        let decode = |model: &mut AsrModel, range: Range<usize>| {
+            let audio = &samples[range];
+            match time_stretch.speed() {
+                Some(speed) => model.transcribe_segment(&stretch(audio, speed)),
+                None => model.transcribe_segment(audio),
+            }
+        };
+        let report = |index: usize, text: &str| {
+            on_segment(SegmentProgress {
+                completed: index + 1,
+                total: segments.len(),
+                segment: TranscriptSegment::decoded(&segments[index], text),
+            })
+        };
+        let pool = (samples.len() >= POOLED_DECODE_MIN_SAMPLES && segments.len() > 1)
+            .then(|| self.session_pool(model))
+            .flatten();
+        let decoded = match pool.as_ref().and_then(|pool| pool.as_ref()) {
+            Some(pool) => {
+                pool.match_language(model.language())?;
+                pool.decode_with(model, &segments, decode, report)?
+            }
+            None => decode_in_order(vec![&mut *model], &segments, decode, report)?,
+        };
+        let ControlFlow::Continue(texts) = decoded else {
+            return Err(EngineError::Cancelled);
+        };
+        let language = model.language().map(str::to_string);
+        Ok(Transcript::from_segments(language, &segments, texts))
+    }
+
+    /// The session pool, loaded from `model`'s files on first use. `None`
+    /// on machines with too few cores for more than one session, or when
+    /// the extra sessions could not be loaded.
+    fn session_pool(&self, model: &AsrModel) -> Option<MutexGuard<'_, Option<SessionPool>>> {
+        let extra = default_pool_size().saturating_sub(1);
+        if extra == 0 {
+            return None;
+        }
+        let mut pool = self.session_pool.lock_recover();
+        if pool.is_none() {
+            let language = crate::settings::get_settings(&self.app_handle).asr_language;
+            match SessionPool::new(model.model_dir(), &language, extra) {
+                Ok(loaded) => *pool = Some(loaded),
+                Err(error) => {
+                    log::warn!("Decoding on one session; extra sessions failed to load: {error}");
+                    return None;
+                }
+            }
+        }
+        Some(pool)
     }
 
     pub fn languages(&self) -> Result<Vec<String>, EngineError> {
-        let model = self
-            .model
-            .read()
-            .map_err(|_| EngineError::ModelUnavailable)?;
+        let model = self.model.read_recover();
         Ok(model
             .as_ref()
             .ok_or(EngineError::ModelUnavailable)?
@@ -191,10 +637,7 @@ impl SpeechEngine {
     }
 
     pub fn validate_language(&self, language: &str) -> Result<(), EngineError> {
-        let model = self
-            .model
-            .read()
-            .map_err(|_| EngineError::ModelUnavailable)?;
+        let model = self.model.read_recover();
         if model
             .as_ref()
             .ok_or(EngineError::ModelUnavailable)?
@@ -206,10 +649,7 @@ impl SpeechEngine {
     }
 
     pub fn set_language(&self, language: &str) -> Result<String, EngineError> {
-        let mut model = self
-            .model
-            .write()
-            .map_err(|_| EngineError::ModelUnavailable)?;
+        let mut model = write_model(&self.model);
         let selected = model
             .as_mut()
             .ok_or(EngineError::ModelUnavailable)?
@@ -220,6 +660,9 @@ impl SpeechEngine {
 
     pub fn start_streaming(
         &self,
+        pause_markers: PauseMarkers,
+        vad: VadConfig,
+        strategy: StreamingStrategy,
         on_update: impl UpdateSink,
     ) -> Result<std::sync::mpsc::Sender<crate::audio_processing::AudioFrame>, StreamingError> {
         if !self.is_ready() {
@@ -227,8 +670,14 @@ impl SpeechEngine {
         }
 
         let (tx, rx) = std::sync::mpsc::channel();
-        self.streaming_pipeline
-            .start(rx, self.model.clone(), on_update)?;
+        self.streaming_pipeline.start(
+            rx,
+            self.model.clone(),
+            pause_markers,
+            vad,
+            strategy,
+            on_update,
+        )?;
         Ok(tx)
     }
 
@@ -237,28 +686,119 @@ impl SpeechEngine {
     }
 
     pub fn reset_model_state(&self) {
-        if let Ok(mut guard) = self.model.write() {
-            if let Some(model) = guard.as_mut() {
-                model.reset_state();
-                log::info!("ASR model state reset");
+        if let Some(model) = write_model(&self.model).as_mut() {
+            model.reset_state();
+            log::info!("ASR model state reset");
+        }
+    }
+
+    /// Runs a load on its own thread; the caller has already moved the
+    /// status to `Loading`.
+    fn spawn_load(&self) {
+        reset_model_download_cancel();
+        *self.load_progress.lock_recover() = Instant::now();
+        self.emit_engine_state(&EngineState::Loading);
+
+        let app_handle = self.app_handle.clone();
+        let load_progress = self.load_progress.clone();
+        let model_arc = self.model.clone();
+        let state_arc = self.status.clone();
+        let condvar = self.status_cv.clone();
+
+        std::thread::spawn(move || {
+            Self::finish_load(
+                || Self::init_model(&app_handle, &load_progress, Instant::now()),
+                &app_handle,
+                &model_arc,
+                &state_arc,
+                &condvar,
+            );
+        });
+    }
+
+    /// Stores a load's result and wakes everything waiting on it.
+    fn finish_load(
+        load: impl FnOnce() -> Result<AsrModel, AsrError>,
+        app_handle: &AppHandle,
+        model_arc: &RwLock<Option<AsrModel>>,
+        state_arc: &Mutex<EngineState>,
+        condvar: &Condvar,
+    ) {
+        // Loading runs outside the status lock, so a panic here would
+        // otherwise leave the engine `Loading` until every waiter timed out.
+        let loaded = std::panic::catch_unwind(std::panic::AssertUnwindSafe(load));
+        let outcome = match loaded {
+            Ok(Ok(model)) => {
+                *model_arc.write_recover() = Some(model);
+                EngineState::Loaded
             }
+            Ok(Err(AsrError::Cancelled)) => {
+                log::info!("Speech model load skipped");
+                EngineState::Unloaded
+            }
+            Ok(Err(error)) => {
+                log::error!("Speech model init failed: {error}");
+                EngineState::Failed(error.user_message().to_string())
+            }
+            Err(_) => {
+                log::error!("Speech model init panicked");
+                EngineState::Failed("The speech engine failed to start. Please try again.".into())
+            }
+        };
+        *state_arc.lock_recover() = outcome.clone();
+        if let Err(error) = app_handle.emit("engine_state", outcome) {
+            log::warn!("Could not emit speech engine state: {error}");
         }
+        condvar.notify_all();
     }
 
-    fn init_model(app_handle: &AppHandle) -> Result<AsrModel, AsrError> {
-        let start = Instant::now();
-        let model_root = default_model_root(app_handle);
-        let model_dir = resolve_model_dir_with_progress(&model_root, |progress| {
-            if let Err(error) = app_handle.emit("model_download_progress", progress) {
-                log::warn!("Could not emit model download progress: {error}");
+    /// `start` is when the load, or the reload it belongs to, began.
+    /// `load_progress` is stamped at each stage and downloaded chunk.
+    fn init_model(
+        app_handle: &AppHandle,
+        load_progress: &Mutex<Instant>,
+        start: Instant,
+    ) -> Result<AsrModel, AsrError> {
+        let emit_status = |stage: ModelLoadStage| {
+            *load_progress.lock_recover() = Instant::now();
+            emit_model_status(app_handle, stage, start);
+        };
+        let mut current_stage: Option<(ModelLoadStage, Instant)> = None;
+        let mut report_stage = |stage: ModelLoadStage| {
+            if let Some((previous, started)) = current_stage.replace((stage, Instant::now())) {
+                log::info!("ASR load stage {previous:?} took {:?}", started.elapsed());
             }
-        })?;
-        let language = crate::settings::get_settings(app_handle).asr_language;
+            emit_status(stage);
+        };
+
+        report_stage(ModelLoadStage::VerifyingFiles);
+        let model_root = default_model_root(app_handle);
+        let bundled_dir = bundled_model_dir(app_handle);
+        let downloading = Cell::new(false);
+        let model_dir =
+            resolve_model_dir_with_progress(&model_root, bundled_dir.as_deref(), |progress| {
+                if !downloading.replace(true) {
+                    emit_status(ModelLoadStage::Downloading);
+                }
+                *load_progress.lock_recover() = Instant::now();
+                if let Err(error) = app_handle.emit("model_download_progress", progress) {
+                    log::warn!("Could not emit model download progress: {error}");
+                }
+            })?;
+        if model_download_cancelled() {
+            return Err(AsrError::Cancelled);
+        }
+        let settings = crate::settings::get_settings(app_handle);
+        let language = settings.asr_language;
 
         log::info!("Loading ASR from {}", model_dir.display());
-        let model = AsrModel::new(&model_dir, &language).inspect_err(|_| {
-            invalidate_model_verification(&model_dir);
-        })?;
+        let model = AsrModel::with_progress(
+            &model_dir,
+            &language,
+            settings.ort_threads,
+            &mut report_stage,
+        );
+        let model = model.inspect_err(|_| invalidate_model_verification(&model_dir))?;
         if !model.supports_language(&language) {
             let mut settings = crate::settings::get_settings(app_handle);
             settings.asr_language = crate::settings::DEFAULT_ASR_LANGUAGE.to_string();
@@ -266,6 +806,7 @@ impl SpeechEngine {
                 log::warn!("Could not repair unsupported speech language setting: {error}");
             }
         }
+        report_stage(ModelLoadStage::Ready);
         log::info!("ASR init took {:?}", start.elapsed());
         Ok(model)
     }
@@ -276,3 +817,22 @@ impl SpeechEngine {
         }
     }
 }
+
+fn bundled_model_dir(app_handle: &AppHandle) -> Option<PathBuf> {
+    app_handle
+        .path()
+        .resource_dir()
+        .ok()
+        .map(|dir| dir.join(BUNDLED_MODEL_DIR))
+        .filter(|dir| dir.is_dir())
+}
+
+fn emit_model_status(app_handle: &AppHandle, stage: ModelLoadStage, start: Instant) {
+    let status = ModelStatus {
+        stage,
+        elapsed_ms: start.elapsed().as_millis() as u64,
+    };
+    if let Err(error) = app_handle.emit("model_status", status) {
+        log::warn!("Could not emit model status: {error}");
+    }
+}
@@ -2,3 +2,232 @@
 pub trait UserFacing {
     fn user_message(&self) -> &'static str;
 }
+
+#[cfg(feature = "desktop")]
+pub use app_error::AppError;
+
+#[cfg(feature = "desktop")]
+mod app_error {
+    use serde::ser::SerializeStruct;
+    use serde::{Serialize, Serializer};
+
+    use super::UserFacing;
+    use crate::asr::AsrError;
+    use crate::at_rest::AtRestError;
+    use crate::audio_file::AudioFileError;
+    use crate::autostart::AutostartError;
+    use crate::capture_file::CaptureFileError;
+    use crate::cli::CliError;
+    use crate::companion::CompanionError;
+    use crate::config_bundle::ConfigBundleError;
+    use crate::decode_cache::DecodeCacheError;
+    use crate::desktop::{AnnounceError, DocumentError, ShortcutError, TypeTextError, TypingError};
+    use crate::dictation::DictationError;
+    use crate::digest::DigestError;
+    use crate::engine::EngineError;
+    use crate::extensions::ExtensionError;
+    use crate::history::HistoryError;
+    use crate::llm_handoff::LlmHandoffError;
+    use crate::network_input::NetworkInputError;
+    use crate::noise_profile::NoiseProfileError;
+    use crate::output_template::OutputTemplateError;
+    use crate::post_process::PostProcessError;
+    use crate::profiles::ProfileError;
+    use crate::recent_logs::RecentLogsError;
+    use crate::recording::RecordingError;
+    use crate::settings::SettingsServiceError;
+    use crate::settings_lock::SettingsLockError;
+    use crate::streaming::StreamingError;
+    use crate::trigger::TriggerError;
+    use crate::typed_metadata::TypedMetadataError;
+    use crate::updater::AppUpdateError;
+    use crate::webhook::WebhookError;
+
+    /// What every command returns on failure. It reaches the frontend as
+    /// `{"code", "message"}`: a stable code naming the subsystem that failed,
+    /// which the UI can map to help, and the subsystem's user message. The
+    /// detailed error is only logged.
+    #[derive(thiserror::Error, Debug)]
+    pub enum AppError {
+        #[error(transparent)]
+        Asr(#[from] AsrError),
+        #[error(transparent)]
+        Engine(#[from] EngineError),
+        #[error(transparent)]
+        Recording(#[from] RecordingError),
+        #[error(transparent)]
+        AudioFile(#[from] AudioFileError),
+        #[error(transparent)]
+        Streaming(#[from] StreamingError),
+        #[error(transparent)]
+        Dictation(#[from] DictationError),
+        #[error(transparent)]
+        Settings(#[from] SettingsServiceError),
+        #[error(transparent)]
+        SettingsLock(#[from] SettingsLockError),
+        #[error(transparent)]
+        ConfigBundle(#[from] ConfigBundleError),
+        #[error(transparent)]
+        Profile(#[from] ProfileError),
+        #[error(transparent)]
+        Shortcut(#[from] ShortcutError),
+        #[error(transparent)]
+        Trigger(#[from] TriggerError),
+        #[error(transparent)]
+        NoiseProfile(#[from] NoiseProfileError),
+        #[error(transparent)]
+        PostProcess(#[from] PostProcessError),
+        #[error(transparent)]
+        OutputTemplate(#[from] OutputTemplateError),
+        #[error(transparent)]
+        Typing(#[from] TypingError),
+        #[error(transparent)]
+        TypeText(#[from] TypeTextError),
+        #[error(transparent)]
+        Document(#[from] DocumentError),
+        #[error(transparent)]
+        Announce(#[from] AnnounceError),
+        #[error(transparent)]
+        History(#[from] HistoryError),
+        #[error(transparent)]
+        Digest(#[from] DigestError),
+        #[error(transparent)]
+        CaptureFile(#[from] CaptureFileError),
+        #[error(transparent)]
+        AtRest(#[from] AtRestError),
+        #[error(transparent)]
+        DecodeCache(#[from] DecodeCacheError),
+        #[error(transparent)]
+        Webhook(#[from] WebhookError),
+        #[error(transparent)]
+        LlmHandoff(#[from] LlmHandoffError),
+        #[error(transparent)]
+        NetworkInput(#[from] NetworkInputError),
+        #[error(transparent)]
+        TypedMetadata(#[from] TypedMetadataError),
+        #[error(transparent)]
+        Companion(#[from] CompanionError),
+        #[error(transparent)]
+        Autostart(#[from] AutostartError),
+        #[error(transparent)]
+        Extension(#[from] ExtensionError),
+        #[error(transparent)]
+        RecentLogs(#[from] RecentLogsError),
+        #[error(transparent)]
+        Update(#[from] AppUpdateError),
+        #[error(transparent)]
+        Cli(#[from] CliError),
+        #[error("model path is not a directory")]
+        InvalidModelPath,
+        #[error("{0} worker failed")]
+        Worker(&'static str),
+    }
+
+    impl AppError {
+        /// Stable identifiers; renaming one breaks help links in the UI.
+        pub fn code(&self) -> &'static str {
+            match self {
+                Self::Asr(_) | Self::Engine(EngineError::Asr(_)) => "speech_model",
+                Self::Engine(_) => "speech_engine",
+                Self::Recording(_) => "recording",
+                Self::AudioFile(_) => "audio_file",
+                Self::Streaming(_) => "streaming",
+                Self::Dictation(_) => "dictation",
+                Self::Settings(SettingsServiceError::Lock(_)) | Self::SettingsLock(_) => {
+                    "settings_lock"
+                }
+                Self::Settings(SettingsServiceError::Profile(_)) | Self::Profile(_) => "profile",
+                Self::Settings(_) => "settings",
+                Self::ConfigBundle(_) => "config_file",
+                Self::Shortcut(ShortcutError::Conflict { .. }) => "shortcut_conflict",
+                Self::Shortcut(_) => "shortcut",
+                Self::Trigger(_) => "trigger",
+                Self::NoiseProfile(_) => "noise_profile",
+                Self::PostProcess(_) => "post_processing",
+                Self::OutputTemplate(_) => "output_template",
+                Self::Typing(_) | Self::TypeText(_) => "typing",
+                Self::Document(_) => "document",
+                Self::Announce(_) => "announce",
+                Self::History(_) => "history",
+                Self::Digest(_) => "digest",
+                Self::CaptureFile(_) => "capture_file",
+                Self::AtRest(_) => "encryption",
+                Self::DecodeCache(_) => "decode_cache",
+                Self::Webhook(_) => "webhook",
+                Self::LlmHandoff(_) => "llm_handoff",
+                Self::NetworkInput(_) => "network_input",
+                Self::TypedMetadata(_) => "typed_metadata",
+                Self::Companion(_) => "companion",
+                Self::Autostart(_) => "autostart",
+                Self::Extension(_) => "extension",
+                Self::RecentLogs(_) => "logs",
+                Self::Update(_) => "app_update",
+                Self::Cli(_) => "command_line",
+                Self::InvalidModelPath => "model_path",
+                Self::Worker(_) => "internal",
+            }
+        }
+    }
+
+    impl UserFacing for AppError {
+        fn user_message(&self) -> &'static str {
+            match self {
+                Self::Asr(error) => error.user_message(),
+                Self::Engine(error) => error.user_message(),
+                Self::Recording(error) => error.user_message(),
+                Self::AudioFile(error) => error.user_message(),
+                Self::Streaming(error) => error.user_message(),
+                Self::Dictation(error) => error.user_message(),
+                Self::Settings(error) => error.user_message(),
+                Self::SettingsLock(error) => error.user_message(),
+                Self::ConfigBundle(error) => error.user_message(),
+                Self::Profile(error) => error.user_message(),
+                Self::Shortcut(error) => error.user_message(),
+                Self::Trigger(error) => error.user_message(),
+                Self::NoiseProfile(error) => error.user_message(),
+                Self::PostProcess(error) => error.user_message(),
+                Self::OutputTemplate(error) => error.user_message(),
+                Self::Typing(error) => error.user_message(),
+                Self::TypeText(error) => error.user_message(),
+                Self::Document(error) => error.user_message(),
+                Self::Announce(error) => error.user_message(),
+                Self::History(error) => error.user_message(),
+                Self::Digest(error) => error.user_message(),
+                Self::CaptureFile(error) => error.user_message(),
+                Self::AtRest(error) => error.user_message(),
+                Self::DecodeCache(error) => error.user_message(),
+                Self::Webhook(error) => error.user_message(),
+                Self::LlmHandoff(error) => error.user_message(),
+                Self::NetworkInput(error) => error.user_message(),
+                Self::TypedMetadata(error) => error.user_message(),
+                Self::Companion(error) => error.user_message(),
+                Self::Autostart(error) => error.user_message(),
+                Self::Extension(error) => error.user_message(),
+                Self::RecentLogs(error) => error.user_message(),
+                Self::Update(error) => error.user_message(),
+                Self::Cli(error) => error.user_message(),
+                Self::InvalidModelPath => "Path does not exist or is not a directory.",
+                Self::Worker(_) => "The operation could not complete. Please try again.",
+            }
+        }
+    }
+
+    /// Conflicts also send what was found, so the UI can offer the free
+    /// chords.
+    impl Serialize for AppError {
+        fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+            let conflict = match self {
+                Self::Shortcut(ShortcutError::Conflict { conflict, .. }) => Some(conflict),
+                _ => None,
+            };
+            let mut error =
+                serializer.serialize_struct("AppError", 2 + usize::from(conflict.is_some()))?;
+            error.serialize_field("code", self.code())?;
+            error.serialize_field("message", self.user_message())?;
+            if let Some(conflict) = conflict {
+                error.serialize_field("conflict", conflict)?;
+            }
+            error.end()
+        }
+    }
+}
@@ -0,0 +1,119 @@
+//! Hooks for builds that add their own sinks and processors to SilentKeys.
+//!
+//! The stable surface is [`Extension`], [`Extensions`], the event structs
+//! passed to the hooks, and, with the `extensions` feature, `run_with`, which
+//! starts the app with the registered extensions and a
+//! [`PostProcessor`](crate::post_process::PostProcessor) that may carry extra
+//! [`PostProcessStage`](crate::post_process::PostProcessStage)s. Hooks observe;
+//! text is rewritten by post-processing stages, not here.
+
+use std::panic::{catch_unwind, AssertUnwindSafe};
+
+use crate::errors::UserFacing;
+use crate::recording::SessionId;
+use crate::streaming::TranscriptPatch;
+use crate::transcript::Transcript;
+
+#[derive(thiserror::Error, Debug, Eq, PartialEq)]
+pub enum ExtensionError {
+    #[error("an extension named {0:?} is already registered")]
+    DuplicateExtension(String),
+}
+
+impl UserFacing for ExtensionError {
+    fn user_message(&self) -> &'static str {
+        match self {
+            Self::DuplicateExtension(_) => "Two extensions share a name.",
+        }
+    }
+}
+
+/// A dictation that has started recording.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct RecordingStart {
+    pub session_id: SessionId,
+    /// Whether live patches will follow, or only the final transcript.
+    pub streaming: bool,
+}
+
+/// A dictation's final transcript, after post-processing, once it has been
+/// delivered.
+#[derive(Clone, Copy, Debug)]
+pub struct FinalTranscript<'a> {
+    pub session_id: Option<SessionId>,
+    /// The text as typed or captured.
+    pub text: &'a str,
+    /// The segments behind `text`, each already post-processed.
+    pub transcript: &'a Transcript,
+}
+
+/// Observes dictations. Every hook has an empty default, so an extension
+/// implements only those it needs. Hooks run on the dictation's own threads,
+/// `on_patch` between streaming decodes, so slow work belongs on a thread of
+/// the extension's own. A hook that panics is logged and skipped.
+pub trait Extension: Send + Sync {
+    /// Identifies the extension in logs; unique within [`Extensions`].
+    fn name(&self) -> &'static str;
+
+    fn on_recording_start(&self, _start: &RecordingStart) {}
+
+    /// Each live draft, commit, or replace patch, before it is routed.
+    fn on_patch(&self, _session_id: SessionId, _patch: &TranscriptPatch) {}
+
+    fn on_final_transcript(&self, _transcript: &FinalTranscript<'_>) {}
+}
+
+/// The extensions the app calls, in registration order.
+#[derive(Default)]
+pub struct Extensions {
+    extensions: Vec<Box<dyn Extension>>,
+}
+
+impl Extensions {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn register(&mut self, extension: Box<dyn Extension>) -> Result<(), ExtensionError> {
+        if self.names().contains(&extension.name()) {
+            return Err(ExtensionError::DuplicateExtension(
+                extension.name().to_string(),
+            ));
+        }
+        self.extensions.push(extension);
+        Ok(())
+    }
+
+    pub fn names(&self) -> Vec<&'static str> {
+        self.extensions
+            .iter()
+            .map(|extension| extension.name())
+            .collect()
+    }
+
+    pub fn recording_started(&self, start: &RecordingStart) {
+        self.each("on_recording_start", |extension| {
+            extension.on_recording_start(start)
+        });
+    }
+
+    pub fn patch(&self, session_id: SessionId, patch: &TranscriptPatch) {
+        self.each("on_patch", |extension| {
+            extension.on_patch(session_id, patch)
+        });
+    }
+
+    pub fn final_transcript(&self, transcript: &FinalTranscript<'_>) {
+        self.each("on_final_transcript", |extension| {
+            extension.on_final_transcript(transcript)
+        });
+    }
+
+    fn each(&self, hook: &str, call: impl Fn(&dyn Extension)) {
+        for extension in &self.extensions {
+            if catch_unwind(AssertUnwindSafe(|| call(extension.as_ref()))).is_err() {
+                log::warn!("Extension {} panicked in {hook}", extension.name());
+            }
+        }
+    }
+}
@@ -0,0 +1,222 @@
+use serde::{Deserialize, Serialize};
+
+use crate::audio_processing::ResamplerQuality;
+
+const GIB: u64 = 1 << 30;
+/// Below this many cores live decoding competes with the app being typed
+/// into, and the polynomial resampler is preferred.
+const STREAMING_MIN_CORES: usize = 4;
+const STREAMING_MIN_MEMORY: u64 = 8 * GIB;
+/// The full-precision model needs roughly four times the int8 model's memory.
+const FP32_MIN_CORES: usize = 8;
+const FP32_MIN_MEMORY: u64 = 16 * GIB;
+
+/// The widest vector extension the speech model's CPU kernels can use.
+#[derive(Clone, Copy, Debug, Deserialize, Eq, PartialEq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum SimdSupport {
+    None,
+    Avx2,
+    Avx512,
+    Neon,
+}
+
+#[derive(Clone, Copy, Debug, Deserialize, Eq, PartialEq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ModelPrecision {
+    Int8,
+    Fp32,
+}
+
+/// What the probe found. Memory and GPU are `None` when the platform could not
+/// be queried.
+#[derive(Clone, Copy, Debug, Deserialize, Eq, PartialEq, Serialize)]
+pub struct HardwareProfile {
+    pub cores: usize,
+    pub simd: SimdSupport,
+    pub memory_bytes: Option<u64>,
+    pub gpu: Option<bool>,
+}
+
+/// Defaults suited to a [`HardwareProfile`]. Only the int8 model is published,
+/// so an `Fp32` precision is reported but the int8 model is still loaded.
+#[derive(Clone, Copy, Debug, Deserialize, Eq, PartialEq, Serialize)]
+pub struct HardwareRecommendation {
+    pub precision: ModelPrecision,
+    pub streaming_enabled: bool,
+    pub resampler_quality: ResamplerQuality,
+}
+
+/// A probe and the recommendation made from it, kept in settings so the probe
+/// runs once per install until detection is run again.
+#[derive(Clone, Copy, Debug, Deserialize, Eq, PartialEq, Serialize)]
+pub struct HardwareDetection {
+    pub profile: HardwareProfile,
+    pub recommendation: HardwareRecommendation,
+}
+
+impl HardwareProfile {
+    pub fn recommend(&self) -> HardwareRecommendation {
+        let capable = self.simd != SimdSupport::None && self.cores >= STREAMING_MIN_CORES;
+        let precision = if self.cores >= FP32_MIN_CORES
+            && self
+                .memory_bytes
+                .is_some_and(|memory| memory >= FP32_MIN_MEMORY)
+            && (self.simd == SimdSupport::Avx512 || self.gpu == Some(true))
+        {
+            ModelPrecision::Fp32
+        } else {
+            ModelPrecision::Int8
+        };
+        HardwareRecommendation {
+            precision,
+            streaming_enabled: capable
+                && self
+                    .memory_bytes
+                    .is_none_or(|memory| memory >= STREAMING_MIN_MEMORY),
+            resampler_quality: if capable {
+                ResamplerQuality::High
+            } else {
+                ResamplerQuality::Fast
+            },
+        }
+    }
+}
+
+pub fn detect_hardware() -> HardwareDetection {
+    let profile = probe();
+    log::info!("Detected hardware: {profile:?}");
+    HardwareDetection {
+        profile,
+        recommendation: profile.recommend(),
+    }
+}
+
+pub fn probe() -> HardwareProfile {
+    HardwareProfile {
+        cores: std::thread::available_parallelism()
+            .map(|cores| cores.get())
+            .unwrap_or(1),
+        simd: simd_support(),
+        memory_bytes: memory_bytes(),
+        gpu: gpu_present(),
+    }
+}
+
+#[cfg(any(target_arch = "x86", target_arch = "x86_64"))]
+fn simd_support() -> SimdSupport {
+    if std::arch::is_x86_feature_detected!("avx512f") {
+        SimdSupport::Avx512
+    } else if std::arch::is_x86_feature_detected!("avx2") {
+        SimdSupport::Avx2
+    } else {
+        SimdSupport::None
+    }
+}
+
+#[cfg(target_arch = "aarch64")]
+fn simd_support() -> SimdSupport {
+    if std::arch::is_aarch64_feature_detected!("neon") {
+        SimdSupport::Neon
+    } else {
+        SimdSupport::None
+    }
+}
+
+#[cfg(not(any(target_arch = "x86", target_arch = "x86_64", target_arch = "aarch64")))]
+fn simd_support() -> SimdSupport {
+    SimdSupport::None
+}
+
+/// Reads `MemTotal` from `/proc/meminfo` contents.
+pub fn parse_meminfo(meminfo: &str) -> Option<u64> {
+    let line = meminfo.lines().find(|line| line.starts_with("MemTotal:"))?;
+    let kib = line.split_whitespace().nth(1)?.parse::<u64>().ok()?;
+    kib.checked_mul(1024)
+}
+
+#[cfg(target_os = "linux")]
+fn memory_bytes() -> Option<u64> {
+    parse_meminfo(&std::fs::read_to_string("/proc/meminfo").ok()?)
+}
+
+#[cfg(target_os = "macos")]
+fn memory_bytes() -> Option<u64> {
+    command_output("sysctl", &["-n", "hw.memsize"])?
+        .parse()
+        .ok()
+}
+
+#[cfg(target_os = "windows")]
+fn memory_bytes() -> Option<u64> {
+    command_output(
+        "powershell",
+        &[
+            "-NoProfile",
+            "-NonInteractive",
+            "-Command",
+            "(Get-CimInstance Win32_ComputerSystem).TotalPhysicalMemory",
+        ],
+    )?
+    .parse()
+    .ok()
+}
+
+#[cfg(not(any(target_os = "linux", target_os = "macos", target_os = "windows")))]
+fn memory_bytes() -> Option<u64> {
+    None
+}
+
+/// Any DRM card counts, including integrated graphics.
+#[cfg(target_os = "linux")]
+fn gpu_present() -> Option<bool> {
+    let cards = std::fs::read_dir("/sys/class/drm").ok()?;
+    Some(cards.flatten().any(|entry| {
+        let name = entry.file_name();
+        let name = name.to_string_lossy();
+        name.starts_with("card") && !name.contains('-')
+    }))
+}
+
+/// Every supported Mac has a GPU.
+#[cfg(target_os = "macos")]
+fn gpu_present() -> Option<bool> {
+    Some(true)
+}
+
+#[cfg(target_os = "windows")]
+fn gpu_present() -> Option<bool> {
+    let names = command_output(
+        "powershell",
+        &[
+            "-NoProfile",
+            "-NonInteractive",
+            "-Command",
+            "(Get-CimInstance Win32_VideoController).Name",
+        ],
+    )?;
+    // The basic display adapter is what Windows uses without a real driver.
+    Some(
+        names
+            .lines()
+            .any(|name| !name.trim().is_empty() && !name.contains("Basic Display")),
+    )
+}
+
+#[cfg(not(any(target_os = "linux", target_os = "macos", target_os = "windows")))]
+fn gpu_present() -> Option<bool> {
+    None
+}
+
+#[cfg(any(target_os = "macos", target_os = "windows"))]
+fn command_output(program: &str, args: &[&str]) -> Option<String> {
+    let output = std::process::Command::new(program)
+        .args(args)
+        .output()
+        .ok()?;
+    if !output.status.success() {
+        return None;
+    }
+    let text = String::from_utf8(output.stdout).ok()?;
+    Some(text.trim().to_string())
+}
@@ -0,0 +1,359 @@
+use std::collections::HashMap;
+use std::io;
+use std::ops::Range;
+use std::path::{Component, Path, PathBuf};
+
+use serde::Serialize;
+
+use crate::at_rest;
+use crate::capture_file::{
+    capture_path, format_heading, parse_heading, parse_utterance, UtteranceLine,
+};
+use crate::decode_cache::audio_fingerprint;
+use crate::errors::UserFacing;
+
+const PREVIEW_CHARS: usize = 80;
+
+/// A dictation in the capture file, which is the history of captured
+/// dictations. `index` counts entry headings from the top of the file.
+#[derive(Clone, Debug, Eq, PartialEq, Serialize)]
+pub struct HistoryEntry {
+    pub index: usize,
+    /// The heading's timestamp, as `YYYY-MM-DD HH:MM`.
+    pub at: String,
+    pub title: Option<String>,
+    pub preview: String,
+    /// The entry's utterances when it was split; empty otherwise.
+    pub utterances: Vec<HistoryUtterance>,
+    /// The first earlier entry whose recording is identical to this one's,
+    /// when duplicates were looked for.
+    pub duplicate_of: Option<usize>,
+}
+
+/// An utterance of a split entry. `index` counts from the entry's first.
+#[derive(Clone, Debug, Eq, PartialEq, Serialize)]
+pub struct HistoryUtterance {
+    pub index: usize,
+    /// Where it starts in the recording, as `MM:SS`.
+    pub at: String,
+    pub text: String,
+}
+
+#[derive(thiserror::Error, Debug)]
+pub enum HistoryError {
+    #[error("no capture file is configured")]
+    NoCaptureFile,
+    #[error(transparent)]
+    CaptureFile(#[from] crate::capture_file::CaptureFileError),
+    #[error("history entry {0} not found")]
+    NotFound(usize),
+    #[error("utterance {utterance} of history entry {entry} not found")]
+    UtteranceNotFound { entry: usize, utterance: usize },
+    #[error("utterance audio {0:?} is outside the capture file's folder")]
+    InvalidClip(String),
+    #[error("history entry {0} has no recording")]
+    NoRecording(usize),
+    #[error("read capture file {path:?}: {source}")]
+    Read {
+        path: PathBuf,
+        #[source]
+        source: io::Error,
+    },
+    #[error("write capture file {path:?}: {source}")]
+    Write {
+        path: PathBuf,
+        #[source]
+        source: io::Error,
+    },
+}
+
+impl UserFacing for HistoryError {
+    fn user_message(&self) -> &'static str {
+        match self {
+            Self::NoCaptureFile | Self::CaptureFile(_) => {
+                "Set a capture file first; the history is read from its entries."
+            }
+            Self::NotFound(_) | Self::UtteranceNotFound { .. } => {
+                "That entry is no longer in the capture file. Reload the list."
+            }
+            Self::InvalidClip(_) => "The utterance's audio link was edited and cannot be used.",
+            Self::NoRecording(_) => {
+                "Only entries captured with Split Utterances keep their recording."
+            }
+            Self::Read { .. } => "Could not read the capture file.",
+            Self::Write { .. } => {
+                "Could not update the entry. Check that the capture file is writable."
+            }
+        }
+    }
+}
+
+/// Lists the entries in capture file contents, oldest first.
+pub fn list_entries(contents: &str) -> Vec<HistoryEntry> {
+    let mut entries: Vec<HistoryEntry> = Vec::new();
+    let mut in_entry = false;
+    for line in contents.lines() {
+        if let Some(heading) = line.strip_prefix("## ") {
+            in_entry = false;
+            if let Some((at, title)) = parse_heading(heading) {
+                entries.push(HistoryEntry {
+                    index: entries.len(),
+                    at: format_heading(at, None),
+                    title: title.map(str::to_string),
+                    preview: String::new(),
+                    utterances: Vec::new(),
+                    duplicate_of: None,
+                });
+                in_entry = true;
+            }
+        } else if let Some(entry) = entries.last_mut().filter(|_| in_entry) {
+            match parse_utterance(line) {
+                Some(utterance) => {
+                    extend_preview(&mut entry.preview, utterance.text);
+                    entry.utterances.push(HistoryUtterance {
+                        index: entry.utterances.len(),
+                        at: utterance.at.to_string(),
+                        text: utterance.text.to_string(),
+                    });
+                }
+                None => extend_preview(&mut entry.preview, line),
+            }
+        }
+    }
+    entries
+}
+
+/// The utterances of entry `entry`, each with the byte range of its line,
+/// newline excluded.
+fn entry_utterances(contents: &str, entry: usize) -> Vec<(Range<usize>, UtteranceLine<'_>)> {
+    let mut headings = 0;
+    let mut offset = 0;
+    let mut utterances = Vec::new();
+    for line in contents.split_inclusive('\n') {
+        let start = offset;
+        offset += line.len();
+        let line = line.trim_end_matches(['\n', '\r']);
+        if line.strip_prefix("## ").and_then(parse_heading).is_some() {
+            headings += 1;
+            continue;
+        }
+        if headings != entry + 1 {
+            continue;
+        }
+        if let Some(parsed) = parse_utterance(line) {
+            utterances.push((start..start + line.len(), parsed));
+        }
+    }
+    utterances
+}
+
+fn find_utterance(
+    contents: &str,
+    entry: usize,
+    utterance: usize,
+) -> Option<(Range<usize>, UtteranceLine<'_>)> {
+    entry_utterances(contents, entry).into_iter().nth(utterance)
+}
+
+/// Replaces the text of an utterance, keeping its offset and audio. Returns
+/// `None` when there is no such utterance.
+pub fn replace_utterance(
+    contents: &str,
+    entry: usize,
+    utterance: usize,
+    text: &str,
+) -> Option<String> {
+    let (range, parsed) = find_utterance(contents, entry, utterance)?;
+    let line = UtteranceLine { text, ..parsed }.to_string();
+    let mut replaced = contents.to_string();
+    replaced.replace_range(range, &line);
+    Some(replaced)
+}
+/// Retitles entry `index`, keeping its timestamp. An empty title removes it.
+/// Returns `None` when there is no such entry.
+pub fn rename_entry(contents: &str, index: usize, title: &str) -> Option<String> {
+    let mut found = false;
+    let mut headings = 0;
+    let mut renamed = String::with_capacity(contents.len() + title.len());
+    for line in contents.split_inclusive('\n') {
+        let parsed = line
+            .trim_end_matches(['\n', '\r'])
+            .strip_prefix("## ")
+            .and_then(parse_heading);
+        match parsed {
+            Some((at, _)) if headings == index => {
+                renamed.push_str("## ");
+                renamed.push_str(&format_heading(at, Some(title)));
+                renamed.push_str(&line[line.trim_end_matches(['\n', '\r']).len()..]);
+                found = true;
+                headings += 1;
+            }
+            Some(_) => {
+                renamed.push_str(line);
+                headings += 1;
+            }
+            None => renamed.push_str(line),
+        }
+    }
+    found.then_some(renamed)
+}
+
+/// Reads the history from the capture file at `capture_file`. A file that
+/// does not exist yet has no entries.
+pub fn read_history(capture_file: &str) -> Result<Vec<HistoryEntry>, HistoryError> {
+    let (_, contents) = read_capture_file(capture_file)?;
+    Ok(list_entries(&contents))
+}
+
+/// Like [`read_history`], also pointing each split entry whose recording is
+/// identical to an earlier entry's at that entry. Reads every clip.
+pub fn read_history_deduplicated(capture_file: &str) -> Result<Vec<HistoryEntry>, HistoryError> {
+    let (path, contents) = read_capture_file(capture_file)?;
+    let mut entries = list_entries(&contents);
+    let fingerprints: Vec<Option<String>> = (0..entries.len())
+        .map(|entry| recording_fingerprint(&path, &contents, entry))
+        .collect();
+    mark_duplicates(&mut entries, &fingerprints);
+    Ok(entries)
+}
+
+/// Sets `duplicate_of` on each entry whose fingerprint an earlier entry
+/// already had. Entries without one are never duplicates.
+pub fn mark_duplicates(entries: &mut [HistoryEntry], fingerprints: &[Option<String>]) {
+    let mut first_with: HashMap<&str, usize> = HashMap::new();
+    for (entry, fingerprint) in entries.iter_mut().zip(fingerprints) {
+        let Some(fingerprint) = fingerprint else {
+            continue;
+        };
+        match first_with.get(fingerprint.as_str()) {
+            Some(&first) => entry.duplicate_of = Some(first),
+            None => {
+                first_with.insert(fingerprint, entry.index);
+            }
+        }
+    }
+}
+
+/// The audio fingerprint of a split entry's clips joined in order. `None`
+/// for an entry without a recording or with a clip that cannot be read.
+fn recording_fingerprint(capture: &Path, contents: &str, entry: usize) -> Option<String> {
+    let utterances = entry_utterances(contents, entry);
+    if utterances.is_empty() {
+        return None;
+    }
+    let mut samples = Vec::new();
+    for (_, parsed) in utterances {
+        let clip = resolve_clip(capture, parsed.clip).ok()?;
+        samples.extend(crate::audio_file::read_wav(&clip).ok()?);
+    }
+    Some(audio_fingerprint(&samples))
+}
+
+pub fn rename_history_entry(
+    capture_file: &str,
+    index: usize,
+    title: &str,
+) -> Result<(), HistoryError> {
+    let (path, contents) = read_capture_file(capture_file)?;
+    let renamed = rename_entry(&contents, index, title).ok_or(HistoryError::NotFound(index))?;
+    at_rest::write_as_found(&path, renamed).map_err(|source| HistoryError::Write { path, source })
+}
+
+/// The audio of an utterance, from the link in its line. Only paths inside
+/// the capture file's folder are followed.
+pub fn utterance_clip(
+    capture_file: &str,
+    entry: usize,
+    utterance: usize,
+) -> Result<PathBuf, HistoryError> {
+    let (path, contents) = read_capture_file(capture_file)?;
+    let (_, parsed) = find_utterance(&contents, entry, utterance)
+        .ok_or(HistoryError::UtteranceNotFound { entry, utterance })?;
+    resolve_clip(&path, parsed.clip)
+}
+
+/// The text and audio of each utterance of a split entry.
+pub fn entry_clips(
+    capture_file: &str,
+    entry: usize,
+) -> Result<Vec<(String, PathBuf)>, HistoryError> {
+    let (path, contents) = read_capture_file(capture_file)?;
+    let utterances = entry_utterances(&contents, entry);
+    if utterances.is_empty() {
+        return Err(HistoryError::NoRecording(entry));
+    }
+    utterances
+        .into_iter()
+        .map(|(_, parsed)| Ok((parsed.text.to_string(), resolve_clip(&path, parsed.clip)?)))
+        .collect()
+}
+
+fn resolve_clip(capture: &Path, clip: &str) -> Result<PathBuf, HistoryError> {
+    let relative = Path::new(clip);
+    if !relative
+        .components()
+        .all(|component| matches!(component, Component::Normal(_)))
+    {
+        return Err(HistoryError::InvalidClip(clip.to_string()));
+    }
+    Ok(capture.with_file_name(relative))
+}
+
+pub fn set_history_utterance(
+    capture_file: &str,
+    entry: usize,
+    utterance: usize,
+    text: &str,
+) -> Result<(), HistoryError> {
+    let (path, contents) = read_capture_file(capture_file)?;
+    let replaced = replace_utterance(&contents, entry, utterance, text)
+        .ok_or(HistoryError::UtteranceNotFound { entry, utterance })?;
+    at_rest::write_as_found(&path, replaced).map_err(|source| HistoryError::Write { path, source })
+}
+
+/// Replaces the text of every utterance of a split entry, in order, as when
+/// keeping a re-transcription.
+pub fn set_history_utterances(
+    capture_file: &str,
+    entry: usize,
+    texts: &[String],
+) -> Result<(), HistoryError> {
+    let (path, contents) = read_capture_file(capture_file)?;
+    let utterances = entry_utterances(&contents, entry);
+    if utterances.len() != texts.len() {
+        return Err(HistoryError::UtteranceNotFound {
+            entry,
+            utterance: utterances.len().min(texts.len()),
+        });
+    }
+    let mut replaced = contents.clone();
+    // Back to front, so earlier ranges stay valid.
+    for ((range, parsed), text) in utterances.into_iter().zip(texts).rev() {
+        replaced.replace_range(range, &UtteranceLine { text, ..parsed }.to_string());
+    }
+    at_rest::write_as_found(&path, replaced).map_err(|source| HistoryError::Write { path, source })
+}
+
+fn read_capture_file(capture_file: &str) -> Result<(PathBuf, String), HistoryError> {
+    if capture_file.trim().is_empty() {
+        return Err(HistoryError::NoCaptureFile);
+    }
+    let path = capture_path(capture_file)?;
+    match at_rest::read_to_string(&path) {
+        Ok(contents) => Ok((path, contents)),
+        Err(error) if error.kind() == io::ErrorKind::NotFound => Ok((path, String::new())),
+        Err(source) => Err(HistoryError::Read { path, source }),
+    }
+}
+
+fn extend_preview(preview: &mut String, line: &str) {
+    let line = line.trim();
+    if line.is_empty() || preview.chars().count() >= PREVIEW_CHARS {
+        return;
+    }
+    if !preview.is_empty() {
+        preview.push(' ');
+    }
+    let room = PREVIEW_CHARS - preview.chars().count();
+    preview.extend(line.chars().take(room));
+}
@@ -0,0 +1,109 @@
+use std::time::{Duration, Instant};
+
+use serde::{Deserialize, Serialize};
+
+pub const MIN_INACTIVITY_MINUTES: u64 = 1;
+pub const MAX_INACTIVITY_MINUTES: u64 = 120;
+pub const MIN_INACTIVITY_GRACE_SECS: u64 = 10;
+pub const MAX_INACTIVITY_GRACE_SECS: u64 = 300;
+
+/// Ends a latched recording that has heard no speech for a while, so one
+/// left running by mistake does not listen indefinitely.
+#[derive(Clone, Copy, Debug, Deserialize, Eq, PartialEq, Serialize)]
+#[serde(default)]
+pub struct InactivityTimeout {
+    pub enabled: bool,
+    /// Silence before the warning.
+    pub minutes: u64,
+    /// Time the warning gives to keep going before the recording is
+    /// finalized.
+    pub grace_secs: u64,
+}
+
+impl Default for InactivityTimeout {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            minutes: 5,
+            grace_secs: 30,
+        }
+    }
+}
+
+impl InactivityTimeout {
+    pub fn clamped(self) -> Self {
+        Self {
+            minutes: self
+                .minutes
+                .clamp(MIN_INACTIVITY_MINUTES, MAX_INACTIVITY_MINUTES),
+            grace_secs: self
+                .grace_secs
+                .clamp(MIN_INACTIVITY_GRACE_SECS, MAX_INACTIVITY_GRACE_SECS),
+            ..self
+        }
+    }
+
+    /// A watch for one recording, or `None` when the rule is off.
+    pub fn watch(&self, now: Instant) -> Option<InactivityWatch> {
+        self.enabled.then(|| InactivityWatch {
+            timeout: Duration::from_secs(self.minutes * 60),
+            grace: Duration::from_secs(self.grace_secs),
+            last_activity: now,
+            warned: false,
+        })
+    }
+}
+
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum InactivityAction {
+    None,
+    /// The silence reached the timeout; the recording will be finalized
+    /// after `remaining_secs` unless speech or the user keeps it going.
+    Warn {
+        remaining_secs: u64,
+    },
+    /// A warning was answered, by speech or by keeping going.
+    Resume,
+    Finalize,
+}
+
+/// Tracks one recording's silence. [`InactivityWatch::poll`] reports a
+/// warning, and its withdrawal, once each.
+#[derive(Clone, Debug)]
+pub struct InactivityWatch {
+    timeout: Duration,
+    grace: Duration,
+    last_activity: Instant,
+    warned: bool,
+}
+
+impl InactivityWatch {
+    pub fn heard_speech(&mut self, now: Instant) {
+        self.last_activity = now;
+    }
+
+    /// The user chose to keep recording; the silence count starts over.
+    pub fn keep_going(&mut self, now: Instant) {
+        self.last_activity = now;
+    }
+
+    pub fn poll(&mut self, now: Instant) -> InactivityAction {
+        let idle = now.saturating_duration_since(self.last_activity);
+        if idle < self.timeout {
+            if std::mem::take(&mut self.warned) {
+                return InactivityAction::Resume;
+            }
+            return InactivityAction::None;
+        }
+        let deadline = self.timeout + self.grace;
+        if idle >= deadline {
+            return InactivityAction::Finalize;
+        }
+        if std::mem::replace(&mut self.warned, true) {
+            return InactivityAction::None;
+        }
+        InactivityAction::Warn {
+            remaining_secs: (deadline - idle).as_secs_f64().ceil() as u64,
+        }
+    }
+}
@@ -0,0 +1,84 @@
+/// Modifier kinds as the suppressor sees them, with left and right merged.
+pub const MODIFIER_SHIFT: u8 = 1;
+pub const MODIFIER_CONTROL: u8 = 2;
+pub const MODIFIER_ALT: u8 = 4;
+pub const MODIFIER_META: u8 = 8;
+
+/// What the keyboard hook does with one key event.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum HookDecision {
+    /// Let the event through to the focused app.
+    Pass,
+    /// Swallow the event without acting on it, as for auto-repeat.
+    Swallow,
+    /// Swallow the event and start push-to-talk. `mask` is set when the
+    /// chord holds Alt or the Windows key, whose release on its own would
+    /// open a menu or the Start menu unless another key is seen first.
+    Press { mask: bool },
+    /// Swallow the event and stop push-to-talk.
+    Release,
+}
+
+/// Decides which key events a low-level keyboard hook keeps from the focused
+/// app so the record shortcut works as a dedicated push-to-talk key. The
+/// shortcut's key is swallowed from press to release, including repeats,
+/// while its modifiers pass through untouched.
+#[derive(Debug)]
+pub struct KeySuppressor<K> {
+    chord: Option<(K, u8)>,
+    down: bool,
+}
+
+impl<K: Copy + PartialEq> KeySuppressor<K> {
+    pub const fn new() -> Self {
+        Self {
+            chord: None,
+            down: false,
+        }
+    }
+
+    /// Binds the key and modifier kinds to suppress, or nothing. A key held
+    /// across a rebind is released by the caller, not here.
+    pub fn bind(&mut self, chord: Option<(K, u8)>) {
+        if self.chord != chord {
+            self.chord = chord;
+            self.down = false;
+        }
+    }
+
+    pub fn is_down(&self) -> bool {
+        self.down
+    }
+
+    /// `held` is the modifier kinds down when the event arrives. The chord
+    /// starts only when exactly its modifiers are held, so Ctrl+Alt+Z still
+    /// reaches the app when the shortcut is Alt+Z.
+    pub fn key(&mut self, key: K, pressed: bool, held: u8) -> HookDecision {
+        let Some((bound, modifiers)) = self.chord else {
+            return HookDecision::Pass;
+        };
+        if key != bound {
+            return HookDecision::Pass;
+        }
+        match (pressed, self.down) {
+            (true, true) => HookDecision::Swallow,
+            (true, false) if held == modifiers => {
+                self.down = true;
+                HookDecision::Press {
+                    mask: modifiers & (MODIFIER_ALT | MODIFIER_META) != 0,
+                }
+            }
+            (false, true) => {
+                self.down = false;
+                HookDecision::Release
+            }
+            _ => HookDecision::Pass,
+        }
+    }
+}
+
+impl<K: Copy + PartialEq> Default for KeySuppressor<K> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
@@ -1,18 +1,73 @@
 pub mod asr;
+pub mod at_rest;
+pub mod audio_file;
 pub mod audio_processing;
+pub mod autostart;
+pub mod capture_file;
+pub mod cli;
+pub mod companion;
+#[cfg(feature = "desktop")]
+pub mod config_bundle;
+pub mod continuation;
 pub mod streaming;
+pub mod sync;
+pub mod time_stretch;
+pub mod vad;
 
 #[doc(hidden)]
 pub mod activity;
+#[cfg(feature = "desktop")]
 pub mod app;
+#[cfg(feature = "desktop")]
 pub mod commands;
+pub mod decode_cache;
+#[cfg(feature = "desktop")]
 pub mod desktop;
+#[cfg(feature = "desktop")]
 mod dictation;
+pub mod digest;
+pub mod document;
+#[cfg(feature = "desktop")]
 mod engine;
 pub mod errors;
+pub mod extensions;
+pub mod hardware;
+pub mod history;
+pub mod inactivity;
+pub mod key_suppression;
+pub mod llm_handoff;
+pub mod network_input;
+pub mod noise_profile;
+pub mod numbers_mode;
+pub mod onboarding;
+pub mod output_template;
+pub mod patch_routing;
+pub mod post_process;
+pub mod power;
+#[cfg(feature = "desktop")]
+pub mod profiles;
+pub mod quiet_hours;
+pub mod recent_logs;
 pub mod recording;
+pub mod retranscribe;
+#[cfg(feature = "desktop")]
 pub mod settings;
+pub mod settings_lock;
+pub mod shortcut_gesture;
+pub mod speaker_adaptation;
+pub mod speech_stats;
+pub mod target_lock;
+pub mod text_commands;
+pub mod transcript;
+pub mod trigger;
+pub mod typed_metadata;
+#[cfg(feature = "desktop")]
 #[doc(hidden)]
 pub mod updater;
+pub mod webhook;
+pub mod word_timings;
 
+#[cfg(feature = "desktop")]
 pub use app::run;
+#[cfg(all(feature = "desktop", feature = "extensions"))]
+pub use app::run_with;
@@ -0,0 +1,174 @@
+use std::time::Duration;
+
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Value};
+
+use crate::errors::UserFacing;
+
+pub const DEFAULT_LLM_ENDPOINT: &str = "http://localhost:11434/v1";
+
+const CHAT_COMPLETIONS_PATH: &str = "/chat/completions";
+const REQUEST_TIMEOUT: Duration = Duration::from_secs(60);
+
+#[derive(Clone, Copy, Debug, Default, Deserialize, Eq, PartialEq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum PromptTemplate {
+    #[default]
+    RewriteFormally,
+    Summarize,
+}
+
+impl PromptTemplate {
+    pub fn instruction(self) -> &'static str {
+        match self {
+            Self::RewriteFormally => {
+                "Rewrite the user's dictated text in a clear, formal register. \
+                 Keep its meaning and language. Reply with the rewritten text only."
+            }
+            Self::Summarize => {
+                "Summarize the user's dictated text in a few concise sentences in \
+                 its original language. Reply with the summary only."
+            }
+        }
+    }
+}
+
+/// An OpenAI-compatible chat endpoint that rewrites transcripts recorded with
+/// the handoff shortcut. Nothing is sent unless `enabled` is set. The API key
+/// is not saved here but in the system keychain.
+#[derive(Clone, Debug, Deserialize, Eq, PartialEq, Serialize)]
+pub struct LlmHandoffSettings {
+    pub enabled: bool,
+    pub endpoint: String,
+    pub model: String,
+    pub template: PromptTemplate,
+}
+
+/// What the handoff settings UI reads and writes: the saved settings and the
+/// API key from the keychain. An empty key sends no `Authorization` header.
+#[derive(Clone, Debug, Default, Deserialize, Eq, PartialEq, Serialize)]
+pub struct LlmHandoffConfig {
+    #[serde(flatten)]
+    pub settings: LlmHandoffSettings,
+    pub api_key: String,
+}
+
+impl Default for LlmHandoffSettings {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            endpoint: DEFAULT_LLM_ENDPOINT.to_string(),
+            model: String::new(),
+            template: PromptTemplate::default(),
+        }
+    }
+}
+
+#[derive(thiserror::Error, Debug)]
+pub enum LlmHandoffError {
+    #[error("LLM handoff is disabled")]
+    Disabled,
+    #[error("LLM endpoint must be an http:// or https:// URL with a host: {0}")]
+    InvalidEndpoint(String),
+    #[error("LLM request failed: {0}")]
+    Request(String),
+    #[error("LLM endpoint returned HTTP {0}")]
+    Status(u16),
+    #[error("LLM response has no message content")]
+    EmptyResponse,
+    #[error("keychain: {0}")]
+    Keychain(String),
+}
+
+impl UserFacing for LlmHandoffError {
+    fn user_message(&self) -> &'static str {
+        match self {
+            Self::Disabled => "Turn on the LLM handoff in settings to use it.",
+            Self::InvalidEndpoint(_) => {
+                "Enter a valid LLM endpoint that starts with http:// or https://."
+            }
+            Self::Request(_) | Self::Status(_) | Self::EmptyResponse => {
+                "The language model did not respond; typed the transcript instead."
+            }
+            Self::Keychain(_) => {
+                "Could not reach the system keychain, which holds the LLM API key."
+            }
+        }
+    }
+}
+
+/// Accepts either a base URL such as `http://localhost:11434/v1` or the full
+/// chat completions URL. The path is appended segment by segment, so a query
+/// string on the endpoint stays after it.
+pub fn chat_completions_url(endpoint: &str) -> Result<String, LlmHandoffError> {
+    let invalid = || LlmHandoffError::InvalidEndpoint(endpoint.trim().to_string());
+    let mut url = crate::webhook::parse_http_url(endpoint).ok_or_else(invalid)?;
+    let complete = url
+        .path()
+        .trim_end_matches('/')
+        .ends_with(CHAT_COMPLETIONS_PATH);
+    let mut segments = url.path_segments_mut().map_err(|()| invalid())?;
+    segments.pop_if_empty();
+    if !complete {
+        segments.extend(
+            CHAT_COMPLETIONS_PATH
+                .split('/')
+                .filter(|segment| !segment.is_empty()),
+        );
+    }
+    drop(segments);
+    Ok(url.into())
+}
+
+pub fn request_body(settings: &LlmHandoffSettings, transcript: &str) -> Value {
+    json!({
+        "model": settings.model.trim(),
+        "stream": false,
+        "messages": [
+            { "role": "system", "content": settings.template.instruction() },
+            { "role": "user", "content": transcript },
+        ],
+    })
+}
+
+pub fn response_text(response: &Value) -> Result<String, LlmHandoffError> {
+    response
+        .pointer("/choices/0/message/content")
+        .and_then(Value::as_str)
+        .map(str::trim)
+        .filter(|text| !text.is_empty())
+        .map(str::to_owned)
+        .ok_or(LlmHandoffError::EmptyResponse)
+}
+
+/// Sends `transcript` through the configured prompt template and returns the
+/// model's reply. Blocks until the endpoint answers or times out.
+pub fn rewrite(
+    settings: &LlmHandoffSettings,
+    api_key: &str,
+    transcript: &str,
+) -> Result<String, LlmHandoffError> {
+    if !settings.enabled {
+        return Err(LlmHandoffError::Disabled);
+    }
+    let url = chat_completions_url(&settings.endpoint)?;
+    let config = ureq::config::Config::builder()
+        .timeout_global(Some(REQUEST_TIMEOUT))
+        .build();
+    let agent = ureq::Agent::new_with_config(config);
+
+    let mut request = agent.post(&url);
+    if !api_key.is_empty() {
+        request = request.header("Authorization", &format!("Bearer {api_key}"));
+    }
+    let mut response = match request.send_json(request_body(settings, transcript)) {
+        Ok(response) => response,
+        Err(ureq::Error::StatusCode(status)) => return Err(LlmHandoffError::Status(status)),
+        Err(error) => return Err(LlmHandoffError::Request(error.to_string())),
+    };
+    let reply = response
+        .body_mut()
+        .read_json::<Value>()
+        .map_err(|error| LlmHandoffError::Request(error.to_string()))?;
+    response_text(&reply)
+}
@@ -0,0 +1,107 @@
+use std::io::{self, Read};
+use std::net::{Ipv4Addr, TcpListener};
+
+use serde::{Deserialize, Serialize};
+
+use crate::errors::UserFacing;
+
+pub const DEFAULT_NETWORK_INPUT_PORT: u16 = 47_613;
+/// Largest accepted frame: one second of 16 kHz 16-bit mono audio.
+pub const MAX_FRAME_BYTES: usize = 32_000;
+
+/// A localhost TCP port that accepts dictation audio in place of the
+/// microphone. Each connection is one dictation, delivered like the record
+/// shortcut when the stream ends. Other machines reach it through a tunnel.
+#[derive(Clone, Copy, Debug, Deserialize, Eq, PartialEq, Serialize)]
+pub struct NetworkInputSettings {
+    pub enabled: bool,
+    pub port: u16,
+}
+
+impl Default for NetworkInputSettings {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            port: DEFAULT_NETWORK_INPUT_PORT,
+        }
+    }
+}
+
+#[derive(thiserror::Error, Debug)]
+pub enum NetworkInputError {
+    #[error("network audio port must not be 0")]
+    InvalidPort,
+    #[error("listen on 127.0.0.1:{port}: {source}")]
+    Bind {
+        port: u16,
+        #[source]
+        source: io::Error,
+    },
+    #[error("read network audio: {0}")]
+    Read(#[source] io::Error),
+    #[error("network audio frame of {0} bytes exceeds the {MAX_FRAME_BYTES}-byte limit")]
+    FrameTooLarge(usize),
+    #[error("network audio frame of {0} bytes is not whole 16-bit samples")]
+    OddFrameLength(usize),
+}
+
+impl UserFacing for NetworkInputError {
+    fn user_message(&self) -> &'static str {
+        match self {
+            Self::InvalidPort => "Choose a network audio port between 1 and 65535.",
+            Self::Bind { .. } => {
+                "Could not listen for network audio. Check that the port is not already in use."
+            }
+            Self::Read(_) | Self::FrameTooLarge(_) | Self::OddFrameLength(_) => {
+                "The network audio stream was interrupted or malformed. Please reconnect."
+            }
+        }
+    }
+}
+
+pub fn validate(settings: &NetworkInputSettings) -> Result<(), NetworkInputError> {
+    if settings.port == 0 {
+        return Err(NetworkInputError::InvalidPort);
+    }
+    Ok(())
+}
+
+/// Binds the loopback interface only; the stream is unauthenticated.
+pub fn listen(port: u16) -> Result<TcpListener, NetworkInputError> {
+    TcpListener::bind((Ipv4Addr::LOCALHOST, port))
+        .map_err(|source| NetworkInputError::Bind { port, source })
+}
+
+pub fn decode_pcm16(bytes: &[u8]) -> Vec<f32> {
+    bytes
+        .chunks_exact(2)
+        .map(|pair| f32::from(i16::from_le_bytes([pair[0], pair[1]])) / 32_768.0)
+        .collect()
+}
+
+/// Reads one frame: a little-endian `u32` byte length followed by that many
+/// bytes of 16 kHz mono signed 16-bit little-endian PCM. Returns `None` at the
+/// end of the dictation, marked by a zero-length frame or a closed connection.
+pub fn read_frame(reader: &mut impl Read) -> Result<Option<Vec<f32>>, NetworkInputError> {
+    let mut header = [0_u8; 4];
+    match reader.read_exact(&mut header) {
+        Ok(()) => {}
+        Err(error) if error.kind() == io::ErrorKind::UnexpectedEof => return Ok(None),
+        Err(error) => return Err(NetworkInputError::Read(error)),
+    }
+    let len = u32::from_le_bytes(header) as usize;
+    if len == 0 {
+        return Ok(None);
+    }
+    if len > MAX_FRAME_BYTES {
+        return Err(NetworkInputError::FrameTooLarge(len));
+    }
+    if !len.is_multiple_of(2) {
+        return Err(NetworkInputError::OddFrameLength(len));
+    }
+    let mut payload = vec![0; len];
+    reader
+        .read_exact(&mut payload)
+        .map_err(NetworkInputError::Read)?;
+    Ok(Some(decode_pcm16(&payload)))
+}
@@ -0,0 +1,502 @@
+use std::f32::consts::PI;
+
+use serde::{Deserialize, Serialize};
+
+use crate::asr::TARGET_SAMPLE_RATE;
+use crate::errors::UserFacing;
+
+/// 32 ms frames at 16 kHz, overlapped by half.
+pub const FFT_SIZE: usize = 512;
+pub const SPECTRUM_BINS: usize = FFT_SIZE / 2 + 1;
+const HOP: usize = FFT_SIZE / 2;
+
+pub const MAX_NOISE_PROFILES: usize = 8;
+pub const MAX_NOISE_PROFILE_NAME_CHARS: usize = 40;
+/// How much of the start of a recording `Auto` listens to before choosing a
+/// profile. Held back from the rest of the pipeline meanwhile.
+pub const AUTO_DETECT_MS: u64 = 300;
+/// Similarity below which no profile is close enough for `Auto` to apply
+/// it: a shape within 4 dB on average.
+pub const MIN_NOISE_SIMILARITY: f32 = 0.8;
+const SIMILARITY_RANGE_DB: f32 = 20.0;
+const BAND_BINS: usize = 16;
+
+/// How far above the profile a bin's magnitude must be to survive, and the
+/// share of its magnitude always kept so quiet speech is not hollowed out.
+const OVER_SUBTRACTION: f32 = 1.5;
+const SPECTRAL_FLOOR: f32 = 0.1;
+
+#[derive(thiserror::Error, Debug, Eq, PartialEq)]
+pub enum NoiseProfileError {
+    #[error("noise profile name is empty")]
+    EmptyName,
+    #[error("noise profile name is longer than {MAX_NOISE_PROFILE_NAME_CHARS} characters")]
+    NameTooLong,
+    #[error("no noise profile named {0:?}")]
+    NotFound(String),
+    #[error("more than {MAX_NOISE_PROFILES} noise profiles")]
+    TooManyProfiles,
+    #[error("too little audio for a noise profile")]
+    TooShort,
+}
+
+impl UserFacing for NoiseProfileError {
+    fn user_message(&self) -> &'static str {
+        match self {
+            Self::EmptyName => "Give the noise profile a name.",
+            Self::NameTooLong => "Noise profile names can be at most 40 characters.",
+            Self::NotFound(_) => "That noise profile no longer exists.",
+            Self::TooManyProfiles => "Delete a noise profile before capturing another.",
+            Self::TooShort => "Not enough audio was recorded for a noise profile.",
+        }
+    }
+}
+
+/// The average spectrum of a room's background noise, such as an office, a
+/// car, or a fan, recorded without anyone speaking.
+#[derive(Clone, Debug, Deserialize, PartialEq, Serialize)]
+pub struct NoiseProfile {
+    pub name: String,
+    /// Mean magnitude per frequency bin, [`SPECTRUM_BINS`] long.
+    pub spectrum: Vec<f32>,
+}
+
+impl NoiseProfile {
+    pub fn capture(name: &str, samples: &[f32]) -> Result<Self, NoiseProfileError> {
+        Ok(Self {
+            name: normalize_name(name)?,
+            spectrum: noise_spectrum(samples).ok_or(NoiseProfileError::TooShort)?,
+        })
+    }
+
+    fn is_valid(&self) -> bool {
+        self.spectrum.len() == SPECTRUM_BINS
+            && self
+                .spectrum
+                .iter()
+                .all(|magnitude| magnitude.is_finite() && *magnitude >= 0.0)
+    }
+}
+
+/// Which noise profile the capture path subtracts.
+#[derive(Clone, Debug, Default, Deserialize, Eq, PartialEq, Serialize)]
+#[serde(tag = "mode", content = "profile", rename_all = "snake_case")]
+pub enum NoiseSelection {
+    #[default]
+    Off,
+    Profile(String),
+    /// The profile closest to the first moments of each recording, if any
+    /// is close enough.
+    Auto,
+}
+
+#[derive(Clone, Debug, Default, Deserialize, PartialEq, Serialize)]
+#[serde(default)]
+pub struct NoiseSuppression {
+    pub profiles: Vec<NoiseProfile>,
+    pub selection: NoiseSelection,
+}
+
+/// The noise suppression settings without the spectra, for the window.
+#[derive(Clone, Debug, Eq, PartialEq, Serialize)]
+pub struct NoiseSuppressionInfo {
+    pub profiles: Vec<String>,
+    pub selection: NoiseSelection,
+}
+
+impl NoiseSuppression {
+    /// Drops malformed and duplicate profiles and any past
+    /// [`MAX_NOISE_PROFILES`], and turns suppression off when the selected
+    /// profile is gone.
+    pub fn normalized(self) -> Self {
+        let mut profiles: Vec<NoiseProfile> = Vec::new();
+        for profile in self.profiles {
+            let Ok(name) = normalize_name(&profile.name) else {
+                continue;
+            };
+            if profile.is_valid()
+                && profiles.len() < MAX_NOISE_PROFILES
+                && profiles.iter().all(|kept| kept.name != name)
+            {
+                profiles.push(NoiseProfile { name, ..profile });
+            }
+        }
+        let mut normalized = Self {
+            profiles,
+            selection: NoiseSelection::Off,
+        };
+        normalized.select(self.selection);
+        normalized
+    }
+
+    pub fn info(&self) -> NoiseSuppressionInfo {
+        NoiseSuppressionInfo {
+            profiles: self
+                .profiles
+                .iter()
+                .map(|profile| profile.name.clone())
+                .collect(),
+            selection: self.selection.clone(),
+        }
+    }
+
+    pub fn find(&self, name: &str) -> Option<&NoiseProfile> {
+        self.profiles.iter().find(|profile| profile.name == name)
+    }
+
+    /// Selects `selection`, or turns suppression off when it names a profile
+    /// that does not exist.
+    pub fn select(&mut self, selection: NoiseSelection) {
+        self.selection = match selection {
+            NoiseSelection::Profile(name) if self.find(&name).is_none() => NoiseSelection::Off,
+            selection => selection,
+        };
+    }
+
+    /// Adds `profile`, replacing one with the same name.
+    pub fn save_profile(&mut self, profile: NoiseProfile) -> Result<(), NoiseProfileError> {
+        if let Some(kept) = self
+            .profiles
+            .iter_mut()
+            .find(|kept| kept.name == profile.name)
+        {
+            *kept = profile;
+        } else if self.profiles.len() >= MAX_NOISE_PROFILES {
+            return Err(NoiseProfileError::TooManyProfiles);
+        } else {
+            self.profiles.push(profile);
+        }
+        Ok(())
+    }
+
+    pub fn delete_profile(&mut self, name: &str) -> Result<(), NoiseProfileError> {
+        let before = self.profiles.len();
+        self.profiles.retain(|profile| profile.name != name);
+        if self.profiles.len() == before {
+            return Err(NoiseProfileError::NotFound(name.to_string()));
+        }
+        if self.selection == NoiseSelection::Profile(name.to_string()) {
+            self.selection = NoiseSelection::Off;
+        }
+        Ok(())
+    }
+
+    /// A reducer for one recording.
+    pub fn reducer(&self) -> NoiseReducer {
+        let state = match &self.selection {
+            NoiseSelection::Off => ReducerState::Off,
+            NoiseSelection::Profile(name) => match self.find(name) {
+                Some(profile) => ReducerState::active(profile),
+                None => ReducerState::Off,
+            },
+            NoiseSelection::Auto if self.profiles.is_empty() => ReducerState::Off,
+            NoiseSelection::Auto => ReducerState::Detecting {
+                profiles: self.profiles.clone(),
+                pending: Vec::new(),
+            },
+        };
+        NoiseReducer { state }
+    }
+}
+
+pub fn normalize_name(name: &str) -> Result<String, NoiseProfileError> {
+    let name = name.trim();
+    if name.is_empty() {
+        return Err(NoiseProfileError::EmptyName);
+    }
+    if name.chars().count() > MAX_NOISE_PROFILE_NAME_CHARS {
+        return Err(NoiseProfileError::NameTooLong);
+    }
+    Ok(name.to_string())
+}
+
+/// The mean magnitude spectrum of `samples`, or `None` when they are
+/// shorter than one frame.
+pub fn noise_spectrum(samples: &[f32]) -> Option<Vec<f32>> {
+    let window = sqrt_hann();
+    let mut spectrum = vec![0.0; SPECTRUM_BINS];
+    let mut frames = 0;
+    let mut re = vec![0.0; FFT_SIZE];
+    let mut im = vec![0.0; FFT_SIZE];
+    for start in (0..samples.len().saturating_sub(FFT_SIZE - 1)).step_by(HOP) {
+        for (index, slot) in re.iter_mut().enumerate() {
+            *slot = samples[start + index] * window[index];
+        }
+        im.fill(0.0);
+        fft(&mut re, &mut im, false);
+        for (bin, magnitude) in spectrum.iter_mut().enumerate() {
+            *magnitude += re[bin].hypot(im[bin]);
+        }
+        frames += 1;
+    }
+    if frames == 0 {
+        return None;
+    }
+    for magnitude in &mut spectrum {
+        *magnitude /= frames as f32;
+    }
+    Some(spectrum)
+}
+
+/// How alike two noise spectra are in shape, from 0 to 1 for the same shape
+/// at any loudness. Compares levels in dB over bands of [`BAND_BINS`] bins,
+/// which smooths out the frame-to-frame scatter of a short sample.
+pub fn noise_similarity(a: &[f32], b: &[f32]) -> f32 {
+    if a.len() != b.len() || a.is_empty() {
+        return 0.0;
+    }
+    let shape = |spectrum: &[f32]| -> Vec<f32> {
+        let bands: Vec<f32> = spectrum
+            .chunks(BAND_BINS)
+            .map(|band| {
+                let mean = band.iter().sum::<f32>() / band.len() as f32;
+                20.0 * mean.max(1e-6).log10()
+            })
+            .collect();
+        let level = bands.iter().sum::<f32>() / bands.len() as f32;
+        bands.into_iter().map(|db| db - level).collect()
+    };
+    let (a, b) = (shape(a), shape(b));
+    let distance_db =
+        (a.iter().zip(&b).map(|(a, b)| (a - b).powi(2)).sum::<f32>() / a.len() as f32).sqrt();
+    (1.0 - distance_db / SIMILARITY_RANGE_DB).clamp(0.0, 1.0)
+}
+
+/// The profile most like `spectrum`, if any reaches
+/// [`MIN_NOISE_SIMILARITY`].
+pub fn closest_profile<'a>(
+    profiles: &'a [NoiseProfile],
+    spectrum: &[f32],
+) -> Option<&'a NoiseProfile> {
+    profiles
+        .iter()
+        .map(|profile| (profile, noise_similarity(&profile.spectrum, spectrum)))
+        .filter(|(_, similarity)| *similarity >= MIN_NOISE_SIMILARITY)
+        .max_by(|(_, a), (_, b)| a.total_cmp(b))
+        .map(|(profile, _)| profile)
+}
+
+/// Subtracts a noise profile from the capture as it streams in. Output lags
+/// input by half a frame and is flushed by [`NoiseReducer::finish`], so a
+/// recording keeps its length.
+pub struct NoiseReducer {
+    state: ReducerState,
+}
+
+enum ReducerState {
+    Off,
+    Detecting {
+        profiles: Vec<NoiseProfile>,
+        pending: Vec<f32>,
+    },
+    Active {
+        name: String,
+        subtractor: Box<SpectralSubtractor>,
+    },
+}
+
+impl ReducerState {
+    fn active(profile: &NoiseProfile) -> Self {
+        Self::Active {
+            name: profile.name.clone(),
+            subtractor: Box::new(SpectralSubtractor::new(&profile.spectrum)),
+        }
+    }
+}
+
+impl NoiseReducer {
+    pub fn off() -> Self {
+        Self {
+            state: ReducerState::Off,
+        }
+    }
+
+    /// The profile being subtracted, once one is chosen.
+    pub fn profile(&self) -> Option<&str> {
+        match &self.state {
+            ReducerState::Active { name, .. } => Some(name),
+            _ => None,
+        }
+    }
+
+    /// Replaces `samples` with the cleaned audio ready so far, which may be
+    /// shorter or longer.
+    pub fn process(&mut self, samples: &mut Vec<f32>) {
+        match &mut self.state {
+            ReducerState::Off => {}
+            ReducerState::Active { subtractor, .. } => *samples = subtractor.process(samples),
+            ReducerState::Detecting { profiles, pending } => {
+                pending.append(samples);
+                let needed = (AUTO_DETECT_MS * u64::from(TARGET_SAMPLE_RATE) / 1_000) as usize;
+                if pending.len() < needed {
+                    return;
+                }
+                let backlog = std::mem::take(pending);
+                let chosen = noise_spectrum(&backlog)
+                    .and_then(|spectrum| closest_profile(profiles, &spectrum).cloned());
+                match chosen {
+                    Some(profile) => {
+                        log::info!("Recording sounds like noise profile {:?}", profile.name);
+                        self.state = ReducerState::active(&profile);
+                    }
+                    None => {
+                        log::info!("No noise profile matches this recording");
+                        self.state = ReducerState::Off;
+                    }
+                }
+                *samples = backlog;
+                self.process(samples);
+            }
+        }
+    }
+
+    /// The audio still held back, at the end of a recording.
+    pub fn finish(&mut self) -> Vec<f32> {
+        match &mut self.state {
+            ReducerState::Off => Vec::new(),
+            ReducerState::Detecting { pending, .. } => std::mem::take(pending),
+            ReducerState::Active { subtractor, .. } => subtractor.finish(),
+        }
+    }
+}
+
+/// Magnitude spectral subtraction with square-root Hann windows overlapped by
+/// half, which add back to the input wherever nothing is subtracted.
+pub struct SpectralSubtractor {
+    noise: Vec<f32>,
+    window: Vec<f32>,
+    pending: Vec<f32>,
+    overlap: Vec<f32>,
+    /// Output samples owed to the priming frame, dropped so output lines up
+    /// with input.
+    skip: usize,
+    received: usize,
+    emitted: usize,
+    re: Vec<f32>,
+    im: Vec<f32>,
+}
+
+impl SpectralSubtractor {
+    pub fn new(noise: &[f32]) -> Self {
+        let mut noise = noise.to_vec();
+        noise.resize(SPECTRUM_BINS, 0.0);
+        Self {
+            noise,
+            window: sqrt_hann(),
+            pending: vec![0.0; FFT_SIZE - HOP],
+            overlap: vec![0.0; FFT_SIZE],
+            skip: FFT_SIZE - HOP,
+            received: 0,
+            emitted: 0,
+            re: vec![0.0; FFT_SIZE],
+            im: vec![0.0; FFT_SIZE],
+        }
+    }
+
+    pub fn process(&mut self, samples: &[f32]) -> Vec<f32> {
+        self.received += samples.len();
+        self.pending.extend_from_slice(samples);
+        let mut output = Vec::with_capacity(samples.len() + HOP);
+        while self.pending.len() >= FFT_SIZE {
+            self.frame(&mut output);
+        }
+        output
+    }
+
+    /// The input not yet returned, padded with silence to finish its frames.
+    pub fn finish(&mut self) -> Vec<f32> {
+        let owed = self.received - self.emitted;
+        self.pending.resize(self.pending.len() + FFT_SIZE, 0.0);
+        let mut output = Vec::with_capacity(owed + FFT_SIZE);
+        while output.len() < owed && self.pending.len() >= FFT_SIZE {
+            self.frame(&mut output);
+        }
+        output.truncate(owed);
+        self.emitted = self.received;
+        self.pending.clear();
+        output
+    }
+
+    fn frame(&mut self, output: &mut Vec<f32>) {
+        for (index, slot) in self.re.iter_mut().enumerate() {
+            *slot = self.pending[index] * self.window[index];
+        }
+        self.im.fill(0.0);
+        fft(&mut self.re, &mut self.im, false);
+        for bin in 0..SPECTRUM_BINS {
+            let magnitude = self.re[bin].hypot(self.im[bin]);
+            if magnitude <= f32::EPSILON {
+                continue;
+            }
+            let cleaned =
+                (magnitude - OVER_SUBTRACTION * self.noise[bin]).max(SPECTRAL_FLOOR * magnitude);
+            let gain = cleaned / magnitude;
+            self.re[bin] *= gain;
+            self.im[bin] *= gain;
+            // Keeps the spectrum conjugate-symmetric so the output is real.
+            if bin > 0 && bin < FFT_SIZE / 2 {
+                self.re[FFT_SIZE - bin] *= gain;
+                self.im[FFT_SIZE - bin] *= gain;
+            }
+        }
+        fft(&mut self.re, &mut self.im, true);
+        for (index, slot) in self.overlap.iter_mut().enumerate() {
+            *slot += self.re[index] * self.window[index];
+        }
+
+        let ready = &self.overlap[..HOP];
+        let skipped = self.skip.min(HOP);
+        self.skip -= skipped;
+        output.extend_from_slice(&ready[skipped..]);
+        self.emitted += HOP - skipped;
+        self.overlap.copy_within(HOP.., 0);
+        self.overlap[FFT_SIZE - HOP..].fill(0.0);
+        self.pending.drain(..HOP);
+    }
+}
+
+fn sqrt_hann() -> Vec<f32> {
+    (0..FFT_SIZE)
+        .map(|index| (0.5 - 0.5 * (2.0 * PI * index as f32 / FFT_SIZE as f32).cos()).sqrt())
+        .collect()
+}
+
+/// In-place radix-2 FFT; the inverse is scaled by `1 / n`.
+fn fft(re: &mut [f32], im: &mut [f32], inverse: bool) {
+    let n = re.len();
+    let mut j = 0;
+    for i in 1..n {
+        let mut bit = n >> 1;
+        while j & bit != 0 {
+            j ^= bit;
+            bit >>= 1;
+        }
+        j |= bit;
+        if i < j {
+            re.swap(i, j);
+            im.swap(i, j);
+        }
+    }
+    let sign = if inverse { 1.0 } else { -1.0 };
+    let mut len = 2;
+    while len <= n {
+        let angle = sign * 2.0 * PI / len as f32;
+        for start in (0..n).step_by(len) {
+            for k in 0..len / 2 {
+                let (sin, cos) = (angle * k as f32).sin_cos();
+                let (a, b) = (start + k, start + k + len / 2);
+                let (b_re, b_im) = (re[b] * cos - im[b] * sin, re[b] * sin + im[b] * cos);
+                re[b] = re[a] - b_re;
+                im[b] = im[a] - b_im;
+                re[a] += b_re;
+                im[a] += b_im;
+            }
+        }
+        len <<= 1;
+    }
+    if inverse {
+        for value in re.iter_mut().chain(im.iter_mut()) {
+            *value /= n as f32;
+        }
+    }
+}
@@ -0,0 +1,231 @@
+/// Characters kept as typed when the model writes them between digits, as in
+/// "+1 (555) 010-4477".
+pub const NUMBER_SEPARATORS: [char; 6] = ['+', '-', '.', '/', '(', ')'];
+
+/// Spoken digits. Only words that always mean a number are listed: "oh",
+/// "to", "for", and the like are ordinary words far more often than digits,
+/// so they are dropped like any other word.
+const DIGIT_WORDS: [(&str, u64); 11] = [
+    ("zero", 0),
+    ("nought", 0),
+    ("one", 1),
+    ("two", 2),
+    ("three", 3),
+    ("four", 4),
+    ("five", 5),
+    ("six", 6),
+    ("seven", 7),
+    ("eight", 8),
+    ("nine", 9),
+];
+
+const TEEN_WORDS: [(&str, u64); 10] = [
+    ("ten", 10),
+    ("eleven", 11),
+    ("twelve", 12),
+    ("thirteen", 13),
+    ("fourteen", 14),
+    ("fifteen", 15),
+    ("sixteen", 16),
+    ("seventeen", 17),
+    ("eighteen", 18),
+    ("nineteen", 19),
+];
+
+/// Tens, which take the digit spoken after them: "twenty one" is 21.
+const TENS_WORDS: [(&str, u64); 8] = [
+    ("twenty", 20),
+    ("thirty", 30),
+    ("forty", 40),
+    ("fifty", 50),
+    ("sixty", 60),
+    ("seventy", 70),
+    ("eighty", 80),
+    ("ninety", 90),
+];
+
+/// Multiply the number before them and add the smaller ones after:
+/// "two thousand twenty" is 2020.
+const SCALE_WORDS: [(&str, u64); 2] = [("hundred", 100), ("thousand", 1_000)];
+
+/// "double five" is 55, as phone numbers are often read.
+const REPEAT_WORDS: [(&str, usize); 2] = [("double", 2), ("triple", 3)];
+
+const SEPARATOR_WORDS: [(&str, char); 7] = [
+    ("plus", '+'),
+    ("dash", '-'),
+    ("hyphen", '-'),
+    ("dot", '.'),
+    ("point", '.'),
+    ("slash", '/'),
+    ("period", '.'),
+];
+
+/// A number spoken with scale words, summed until a word that cannot be part
+/// of it.
+struct Spelled {
+    total: u64,
+    /// The largest scale spoken so far.
+    scale: u64,
+    /// A number below this is added; after an addition it is zero.
+    room: u64,
+    /// The number last added, which a smaller scale multiplies.
+    last: Option<u64>,
+}
+
+#[derive(Default)]
+struct DigitWriter {
+    digits: String,
+    spelled: Option<Spelled>,
+    /// Where the number just written starts and its value, for a scale word
+    /// spoken after it.
+    last: Option<(usize, u64)>,
+}
+
+impl DigitWriter {
+    fn number(&mut self, value: u64) {
+        if let Some(spelled) = &mut self.spelled {
+            if value < spelled.room {
+                spelled.total = spelled.total.saturating_add(value);
+                spelled.room = 0;
+                spelled.last = Some(value);
+                return;
+            }
+        }
+        self.flush();
+        self.last = Some((self.digits.len(), value));
+        self.digits.push_str(&value.to_string());
+    }
+
+    /// Writes `value` `count` times over; "double five" is read digit by
+    /// digit, not as a number.
+    fn repeated(&mut self, value: u64, count: usize) {
+        if count == 1 {
+            self.number(value);
+        } else {
+            self.write(value.to_string().repeat(count).chars());
+        }
+    }
+
+    fn scale(&mut self, scale: u64) {
+        if let Some(spelled) = &mut self.spelled {
+            if scale > spelled.scale {
+                spelled.total = spelled.total.saturating_mul(scale);
+                spelled.scale = scale;
+                spelled.room = scale;
+                spelled.last = None;
+                return;
+            }
+            if let Some(last) = spelled.last.take() {
+                spelled.total = spelled.total.saturating_add(last * (scale - 1));
+                spelled.room = scale;
+                return;
+            }
+        }
+        let multiplier = match self.last.take() {
+            Some((start, value)) => {
+                self.digits.truncate(start);
+                value
+            }
+            None => 1,
+        };
+        self.flush();
+        self.spelled = Some(Spelled {
+            total: multiplier * scale,
+            scale,
+            room: scale,
+            last: None,
+        });
+    }
+
+    fn write(&mut self, chars: impl IntoIterator<Item = char>) {
+        let mut chars = chars.into_iter().peekable();
+        if chars.peek().is_some() {
+            self.flush();
+            self.digits.extend(chars);
+        }
+    }
+
+    fn flush(&mut self) {
+        self.last = None;
+        if let Some(spelled) = self.spelled.take() {
+            self.digits.push_str(&spelled.total.to_string());
+        }
+    }
+
+    fn finish(mut self) -> String {
+        self.flush();
+        self.digits
+    }
+}
+
+fn lookup<T: Copy>(table: &[(&str, T)], word: &str) -> Option<T> {
+    table
+        .iter()
+        .find(|(spoken, _)| *spoken == word)
+        .map(|(_, value)| *value)
+}
+
+/// Restricts a numbers mode transcript to digits and [`NUMBER_SEPARATORS`],
+/// for phone numbers, IDs, and codes. Number words become digits, spoken
+/// separators ("dash", "plus") become symbols, and every other word, space,
+/// and sentence punctuation is dropped. Digits spoken one after another are
+/// written one after another, as phone numbers are read; "hundred" and
+/// "thousand" make a single number of the words around them. This runs on the
+/// decoded text: the model's token search happens inside parakeet-rs, which
+/// offers no way to restrict it to digits.
+pub fn constrain_to_digits(text: &str) -> String {
+    let mut digits = DigitWriter::default();
+    let mut repeat = 1;
+    let mut tens = None;
+    let words = text.split_whitespace().flat_map(|token| {
+        // "twenty-one" is two words; "555-0100" keeps its dash.
+        if token.chars().any(|c| c.is_ascii_digit()) {
+            vec![token]
+        } else {
+            token.split('-').collect()
+        }
+    });
+    for token in words {
+        let word = token
+            .trim_matches(|c: char| !c.is_alphanumeric())
+            .to_lowercase();
+        let digit = lookup(&DIGIT_WORDS, &word);
+        if let Some(tens) = tens.take() {
+            match digit.filter(|digit| *digit != 0) {
+                Some(unit) => {
+                    digits.number(tens + unit);
+                    continue;
+                }
+                None => digits.number(tens),
+            }
+        }
+        let count = std::mem::replace(&mut repeat, 1);
+        if let Some(digit) = digit {
+            digits.repeated(digit, count);
+        } else if let Some(teen) = lookup(&TEEN_WORDS, &word) {
+            digits.repeated(teen, count);
+        } else if let Some(value) = lookup(&TENS_WORDS, &word) {
+            tens = Some(value);
+        } else if let Some(scale) = lookup(&SCALE_WORDS, &word) {
+            digits.scale(scale);
+        } else if let Some(count) = lookup(&REPEAT_WORDS, &word) {
+            repeat = count;
+        } else if let Some(separator) = lookup(&SEPARATOR_WORDS, &word) {
+            digits.write([separator]);
+        } else if word.is_empty() || word.chars().any(|c| c.is_ascii_digit()) {
+            // Written by the model: keep its digits and separators, less a
+            // sentence's closing punctuation.
+            let written = token.trim_end_matches(['.', ',', '?', '!', ';', ':']);
+            digits.write(
+                written
+                    .chars()
+                    .filter(|c| c.is_ascii_digit() || NUMBER_SEPARATORS.contains(c)),
+            );
+        }
+    }
+    if let Some(tens) = tens {
+        digits.number(tens);
+    }
+    digits.finish()
+}
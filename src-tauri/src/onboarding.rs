@@ -0,0 +1,122 @@
+use serde::{Deserialize, Serialize};
+
+pub const ONBOARDING_EVENT: &str = "onboarding_state";
+
+/// First-run setup, in the order it is walked through.
+#[derive(Clone, Copy, Debug, Deserialize, Eq, PartialEq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum OnboardingStep {
+    ModelDownload,
+    MicPermission,
+    MicTest,
+    ShortcutCapture,
+    SampleDictation,
+    Done,
+}
+
+impl OnboardingStep {
+    pub fn next(self) -> Self {
+        match self {
+            Self::ModelDownload => Self::MicPermission,
+            Self::MicPermission => Self::MicTest,
+            Self::MicTest => Self::ShortcutCapture,
+            Self::ShortcutCapture => Self::SampleDictation,
+            Self::SampleDictation | Self::Done => Self::Done,
+        }
+    }
+}
+
+/// What happened that may move setup along. Each outcome only counts in the
+/// step it belongs to, so a model finishing its download later, or a
+/// dictation made before the sample step, changes nothing.
+#[derive(Clone, Debug, PartialEq)]
+pub enum OnboardingEvent {
+    ModelReady,
+    ModelFailed(String),
+    MicrophoneOpened,
+    MicrophoneBlocked(String),
+    MicTestPassed,
+    MicTestFailed(String),
+    ShortcutConfirmed,
+    /// A dictation's final text, empty when nothing was heard.
+    SampleDictated(String),
+    /// Leaves the current step for the next one without passing it.
+    SkipStep,
+    /// Ends setup from any step.
+    Finish,
+}
+
+/// Where a new user is in first-run setup. Emitted as `onboarding_state`
+/// whenever it changes.
+#[derive(Clone, Debug, PartialEq, Serialize)]
+pub struct Onboarding {
+    pub step: OnboardingStep,
+    /// Why the current step has not passed yet, until it is tried again.
+    pub problem: Option<String>,
+    /// What the sample dictation heard.
+    pub sample_text: Option<String>,
+}
+
+impl Onboarding {
+    /// Setup for an install that has not finished it, starting past the
+    /// download when the model is already loaded.
+    pub fn new(completed: bool, model_ready: bool) -> Self {
+        let step = if completed {
+            OnboardingStep::Done
+        } else if model_ready {
+            OnboardingStep::MicPermission
+        } else {
+            OnboardingStep::ModelDownload
+        };
+        Self {
+            step,
+            problem: None,
+            sample_text: None,
+        }
+    }
+
+    pub fn is_done(&self) -> bool {
+        self.step == OnboardingStep::Done
+    }
+
+    /// Applies `event`, returning whether anything changed.
+    pub fn apply(&mut self, event: OnboardingEvent) -> bool {
+        use OnboardingEvent as Event;
+        use OnboardingStep as Step;
+
+        let before = self.clone();
+        match (self.step, event) {
+            (Step::Done, _) => {}
+            (_, Event::Finish) => self.advance_to(Step::Done),
+            (step, Event::SkipStep) => self.advance_to(step.next()),
+            (Step::ModelDownload, Event::ModelReady)
+            | (Step::MicPermission, Event::MicrophoneOpened)
+            | (Step::MicTest, Event::MicTestPassed)
+            | (Step::ShortcutCapture, Event::ShortcutConfirmed) => {
+                self.advance_to(self.step.next())
+            }
+            (Step::ModelDownload, Event::ModelFailed(problem))
+            | (Step::MicPermission, Event::MicrophoneBlocked(problem))
+            | (Step::MicTest, Event::MicTestFailed(problem)) => self.problem = Some(problem),
+            (Step::SampleDictation, Event::SampleDictated(text)) => {
+                let text = text.trim();
+                if text.is_empty() {
+                    self.problem = Some(
+                        "Nothing was heard. Hold the shortcut a little longer and speak up."
+                            .to_string(),
+                    );
+                } else {
+                    self.sample_text = Some(text.to_string());
+                    self.advance_to(Step::Done);
+                }
+            }
+            _ => {}
+        }
+        *self != before
+    }
+
+    fn advance_to(&mut self, step: OnboardingStep) {
+        self.step = step;
+        self.problem = None;
+    }
+}
@@ -0,0 +1,84 @@
+use chrono::NaiveDateTime;
+use serde::{Deserialize, Serialize};
+
+use crate::errors::UserFacing;
+
+const TEXT_VARIABLE: &str = "text";
+
+/// Patterns wrapped around the final transcript of each dictation shortcut.
+/// An empty pattern types the transcript unchanged.
+#[derive(Clone, Debug, Default, Deserialize, Eq, PartialEq, Serialize)]
+pub struct OutputTemplates {
+    pub record: String,
+    pub llm_handoff: String,
+}
+
+impl OutputTemplates {
+    pub fn validate(&self) -> Result<(), OutputTemplateError> {
+        [&self.record, &self.llm_handoff]
+            .into_iter()
+            .map(String::as_str)
+            .try_for_each(validate)
+    }
+}
+
+#[derive(thiserror::Error, Debug)]
+pub enum OutputTemplateError {
+    #[error("output template does not contain {{{{text}}}}: {0}")]
+    MissingText(String),
+}
+
+impl UserFacing for OutputTemplateError {
+    fn user_message(&self) -> &'static str {
+        match self {
+            Self::MissingText(_) => "Output templates must include {{text}} for the transcript.",
+        }
+    }
+}
+
+pub fn validate(template: &str) -> Result<(), OutputTemplateError> {
+    let mut has_text = template.trim().is_empty();
+    expand(template, |name| {
+        has_text |= name == TEXT_VARIABLE;
+        None
+    });
+    if has_text {
+        Ok(())
+    } else {
+        Err(OutputTemplateError::MissingText(template.to_string()))
+    }
+}
+
+/// Expands `{{text}}`, `{{date}}` (YYYY-MM-DD), and `{{time}}` (HH:MM) in one
+/// pass, so braces inside the transcript are never expanded. Unknown
+/// variables are kept verbatim.
+pub fn render(template: &str, text: &str, now: NaiveDateTime) -> String {
+    if template.trim().is_empty() {
+        return text.to_string();
+    }
+    expand(template, |name| match name {
+        TEXT_VARIABLE => Some(text.to_string()),
+        "date" => Some(now.format("%Y-%m-%d").to_string()),
+        "time" => Some(now.format("%H:%M").to_string()),
+        _ => None,
+    })
+}
+
+fn expand(template: &str, mut resolve: impl FnMut(&str) -> Option<String>) -> String {
+    let mut output = String::with_capacity(template.len());
+    let mut rest = template;
+    while let Some(start) = rest.find("{{") {
+        let Some(length) = rest[start + 2..].find("}}") else {
+            break;
+        };
+        let end = start + 2 + length + 2;
+        output.push_str(&rest[..start]);
+        match resolve(rest[start + 2..end - 2].trim()) {
+            Some(value) => output.push_str(&value),
+            None => output.push_str(&rest[start..end]),
+        }
+        rest = &rest[end..];
+    }
+    output.push_str(rest);
+    output
+}
@@ -0,0 +1,224 @@
+use std::sync::{Arc, Mutex};
+
+use serde::{Deserialize, Serialize};
+
+use crate::recording::SessionId;
+use crate::streaming::{SessionUpdate, Transcript, TranscriptPatch};
+use crate::sync::MutexRecover;
+
+type PatchSink = Arc<dyn Fn(&TranscriptPatch, &Transcript) -> Result<(), String> + Send + Sync>;
+type WindowSink = Arc<dyn Fn(SessionUpdate) -> Result<(), String> + Send + Sync>;
+type MirrorSink = Arc<dyn Fn(TranscriptPatch) + Send + Sync>;
+
+/// Drafts at least this long go to a window that asked for windowed drafts
+/// as a [`TranscriptPatch::DraftTail`]; shorter ones cost little in full.
+pub const WINDOWED_DRAFT_MIN_CHARS: u32 = 2_000;
+
+/// What started a dictation.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum PatchSource {
+    /// The record shortcut, a mouse or pedal trigger, or network audio.
+    Shortcut,
+    /// The recorder button in the SilentKeys window.
+    App,
+}
+
+/// Where a dictation's transcript goes. `Type` is the source's text output:
+/// synthetic typing, or the capture file when one is enabled.
+#[derive(Clone, Copy, Debug, Deserialize, Eq, PartialEq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum PatchDestinations {
+    Type,
+    Window,
+    Both,
+    Neither,
+}
+
+impl PatchDestinations {
+    pub fn types(self) -> bool {
+        matches!(self, Self::Type | Self::Both)
+    }
+
+    pub fn shows(self) -> bool {
+        matches!(self, Self::Window | Self::Both)
+    }
+}
+
+#[derive(Clone, Copy, Debug, Deserialize, Eq, PartialEq, Serialize)]
+#[serde(default)]
+pub struct PatchRouting {
+    pub shortcut: PatchDestinations,
+    pub app: PatchDestinations,
+}
+
+impl Default for PatchRouting {
+    fn default() -> Self {
+        Self {
+            shortcut: PatchDestinations::Both,
+            app: PatchDestinations::Window,
+        }
+    }
+}
+
+impl PatchRouting {
+    pub fn destinations(&self, source: PatchSource) -> PatchDestinations {
+        match source {
+            PatchSource::Shortcut => self.shortcut,
+            PatchSource::App => self.app,
+        }
+    }
+}
+
+/// Sends one dictation's live patches and final text to the destinations
+/// chosen for its source. Window failures are logged rather than returned, so
+/// a closed window never interrupts typing. Clones share the transcript the
+/// patches build, so the final text replaces exactly what was streamed.
+#[derive(Clone)]
+pub struct PatchRouter {
+    session_id: Option<SessionId>,
+    destinations: PatchDestinations,
+    live_typing: bool,
+    transcript: Arc<Mutex<Transcript>>,
+    typing: Option<PatchSink>,
+    window: Option<WindowSink>,
+    windowed_drafts: bool,
+    mirror: Option<MirrorSink>,
+}
+
+impl Default for PatchRouter {
+    fn default() -> Self {
+        Self::new(None, PatchDestinations::Neither)
+    }
+}
+
+impl PatchRouter {
+    pub fn new(session_id: Option<SessionId>, destinations: PatchDestinations) -> Self {
+        Self {
+            session_id,
+            destinations,
+            live_typing: true,
+            transcript: Arc::default(),
+            typing: None,
+            window: None,
+            windowed_drafts: false,
+            mirror: None,
+        }
+    }
+
+    /// Types each patch; the sink also gets the transcript with the patch
+    /// applied, which a replacement should leave typed.
+    pub fn with_typing(
+        self,
+        sink: impl Fn(&TranscriptPatch, &Transcript) -> Result<(), String> + Send + Sync + 'static,
+    ) -> Self {
+        Self {
+            typing: Some(Arc::new(sink)),
+            ..self
+        }
+    }
+
+    pub fn with_window(
+        self,
+        sink: impl Fn(SessionUpdate) -> Result<(), String> + Send + Sync + 'static,
+    ) -> Self {
+        Self {
+            window: Some(Arc::new(sink)),
+            ..self
+        }
+    }
+
+    /// Sends the window long drafts as tails of the text it shows, for a
+    /// window that applies [`TranscriptPatch::DraftTail`]. The typing sink and
+    /// the mirror still get full drafts.
+    pub fn with_windowed_drafts(self, enabled: bool) -> Self {
+        Self {
+            windowed_drafts: enabled,
+            ..self
+        }
+    }
+
+    /// Receives every patch and the final text whatever the destinations,
+    /// for clients that show the transcript on another device.
+    pub fn with_mirror(self, sink: impl Fn(TranscriptPatch) + Send + Sync + 'static) -> Self {
+        Self {
+            mirror: Some(Arc::new(sink)),
+            ..self
+        }
+    }
+
+    /// Whether live patches are typed as well as the final text; off when
+    /// the final text will not simply extend them.
+    pub fn with_live_typing(self, enabled: bool) -> Self {
+        Self {
+            live_typing: enabled,
+            ..self
+        }
+    }
+
+    pub fn destinations(&self) -> PatchDestinations {
+        self.destinations
+    }
+
+    pub fn types(&self) -> bool {
+        self.destinations.types() && self.typing.is_some()
+    }
+
+    /// Routes a streaming patch. Only commits are typed; the window also
+    /// gets drafts and replacements.
+    pub fn live(&self, patch: TranscriptPatch) -> Result<(), String> {
+        let (shown, transcript) = self.apply(&patch)?;
+        self.mirror(&patch);
+        self.show(shown);
+        match patch {
+            TranscriptPatch::Commit { .. } if self.live_typing => {
+                self.type_patch(&patch, &transcript)
+            }
+            _ => Ok(()),
+        }
+    }
+
+    /// Routes the final text as a replacement of everything committed so far.
+    pub fn finish(&self, text: String) -> Result<(), String> {
+        let patch = self.transcript.lock_recover().replace_all(text);
+        let (shown, transcript) = self.apply(&patch)?;
+        self.mirror(&patch);
+        self.show(shown);
+        self.type_patch(&patch, &transcript)
+    }
+
+    /// Applies `patch` to the shared transcript, returning the patch the
+    /// window should get and the transcript after it.
+    fn apply(&self, patch: &TranscriptPatch) -> Result<(TranscriptPatch, Transcript), String> {
+        let mut transcript = self.transcript.lock_recover();
+        let shown = if self.windowed_drafts {
+            transcript.windowed(patch.clone(), WINDOWED_DRAFT_MIN_CHARS)
+        } else {
+            patch.clone()
+        };
+        transcript.apply(patch).map_err(|error| error.to_string())?;
+        Ok((shown, transcript.clone()))
+    }
+
+    fn mirror(&self, patch: &TranscriptPatch) {
+        if let Some(mirror) = &self.mirror {
+            mirror(patch.clone());
+        }
+    }
+
+    fn show(&self, patch: TranscriptPatch) {
+        let Some(window) = self.window.as_ref().filter(|_| self.destinations.shows()) else {
+            return;
+        };
+        let session_id = self.session_id;
+        if let Err(error) = window(SessionUpdate { session_id, patch }) {
+            log::warn!("Could not show transcript patch: {error}");
+        }
+    }
+
+    fn type_patch(&self, patch: &TranscriptPatch, transcript: &Transcript) -> Result<(), String> {
+        match self.typing.as_ref().filter(|_| self.destinations.types()) {
+            Some(typing) => typing(patch, transcript),
+            None => Ok(()),
+        }
+    }
+}
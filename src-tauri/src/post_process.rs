@@ -0,0 +1,256 @@
+use std::collections::BTreeMap;
+
+use serde::{Deserialize, Serialize};
+
+use crate::errors::UserFacing;
+use crate::text_commands::{apply_code_mode, apply_spelling, TextCommands};
+
+pub const SPELLING_STAGE: &str = "spelling";
+pub const CODE_MODE_STAGE: &str = "code_mode";
+
+#[derive(thiserror::Error, Debug, Eq, PartialEq)]
+pub enum PostProcessError {
+    #[error("a post-processing stage named {0:?} is already registered")]
+    DuplicateStage(String),
+    #[error("no post-processing stage named {0:?}")]
+    UnknownStage(String),
+}
+
+impl UserFacing for PostProcessError {
+    fn user_message(&self) -> &'static str {
+        match self {
+            Self::DuplicateStage(_) => "Two post-processing stages share a name.",
+            Self::UnknownStage(_) => {
+                "A post-processing stage is not installed. Check the stage names."
+            }
+        }
+    }
+}
+
+/// What a stage knows about the dictation it is rewriting.
+#[derive(Clone, Copy, Debug)]
+pub struct StageContext<'a> {
+    /// The language prompt the model decoded with, when it takes one.
+    pub language: Option<&'a str>,
+    pub text_commands: &'a TextCommands,
+}
+
+/// One step of final-transcript post-processing, such as punctuation,
+/// number formatting, replacements, or macros. Stages run on each segment of
+/// the final transcript in turn; streaming partials are shown as spoken.
+pub trait PostProcessStage: Send + Sync {
+    /// Identifies the stage in settings; unique within a [`PostProcessor`].
+    fn name(&self) -> &'static str;
+
+    /// Language codes the stage is written for, matched on their primary
+    /// subtag so `de` covers `de-AT`. Empty means every language.
+    fn languages(&self) -> &'static [&'static str] {
+        &[]
+    }
+
+    fn apply(&self, text: &str, context: &StageContext<'_>) -> String;
+}
+
+/// A registered stage as the settings window lists it.
+#[derive(Clone, Debug, Eq, PartialEq, Serialize)]
+pub struct StageInfo {
+    pub name: &'static str,
+    pub languages: Vec<&'static str>,
+}
+
+/// Which stages run for which language. A language without an entry runs
+/// every stage written for it or for all languages, in registration order.
+#[derive(Clone, Debug, Default, Deserialize, Eq, PartialEq, Serialize)]
+#[serde(default)]
+pub struct PostProcessing {
+    /// Stage names to run, in order, keyed by language code. An exact code
+    /// wins over its primary subtag, so `en-GB` can differ from `en`.
+    pub languages: BTreeMap<String, Vec<String>>,
+}
+
+impl PostProcessing {
+    /// Lowercases and trims language codes and stage names and drops blank
+    /// ones.
+    pub fn normalized(self) -> Self {
+        let languages = self
+            .languages
+            .into_iter()
+            .map(|(language, stages)| {
+                let stages = stages
+                    .into_iter()
+                    .map(|stage| stage.trim().to_lowercase())
+                    .filter(|stage| !stage.is_empty())
+                    .collect();
+                (language.trim().to_lowercase(), stages)
+            })
+            .filter(|(language, _)| !language.is_empty())
+            .collect();
+        Self { languages }
+    }
+
+    fn selection(&self, language: Option<&str>) -> Option<&[String]> {
+        let language = language?.to_lowercase();
+        self.languages
+            .get(&language)
+            .or_else(|| self.languages.get(primary_subtag(&language)))
+            .map(Vec::as_slice)
+    }
+}
+
+/// The chain of post-processing stages. The app registers the built-in
+/// stages at startup; a language pack adds its own with
+/// [`PostProcessor::register`] before the processor is handed to the app.
+pub struct PostProcessor {
+    stages: Vec<Box<dyn PostProcessStage>>,
+}
+
+impl PostProcessor {
+    pub fn new() -> Self {
+        Self { stages: Vec::new() }
+    }
+
+    /// Spelling, then code mode, as the text commands settings enable them.
+    pub fn builtin() -> Self {
+        Self {
+            stages: vec![Box::new(SpellingStage), Box::new(CodeModeStage)],
+        }
+    }
+
+    pub fn register(&mut self, stage: Box<dyn PostProcessStage>) -> Result<(), PostProcessError> {
+        if self.stage(stage.name()).is_some() {
+            return Err(PostProcessError::DuplicateStage(stage.name().to_string()));
+        }
+        self.stages.push(stage);
+        Ok(())
+    }
+
+    pub fn stages(&self) -> Vec<StageInfo> {
+        self.stages
+            .iter()
+            .map(|stage| StageInfo {
+                name: stage.name(),
+                languages: stage.languages().to_vec(),
+            })
+            .collect()
+    }
+
+    /// Checks that every selected stage is registered.
+    pub fn validate(&self, post_processing: &PostProcessing) -> Result<(), PostProcessError> {
+        match post_processing
+            .languages
+            .values()
+            .flatten()
+            .find(|name| self.stage(name).is_none())
+        {
+            Some(name) => Err(PostProcessError::UnknownStage(name.clone())),
+            None => Ok(()),
+        }
+    }
+
+    /// The names of the stages that run for `language`, in order.
+    pub fn plan(&self, language: Option<&str>, post_processing: &PostProcessing) -> Vec<&str> {
+        self.planned(language, post_processing)
+            .into_iter()
+            .map(|stage| stage.name())
+            .collect()
+    }
+
+    pub fn run(
+        &self,
+        text: &str,
+        context: &StageContext<'_>,
+        post_processing: &PostProcessing,
+    ) -> String {
+        self.planned(context.language, post_processing)
+            .into_iter()
+            .fold(text.to_string(), |text, stage| stage.apply(&text, context))
+    }
+
+    fn planned(
+        &self,
+        language: Option<&str>,
+        post_processing: &PostProcessing,
+    ) -> Vec<&dyn PostProcessStage> {
+        match post_processing.selection(language) {
+            Some(names) => names
+                .iter()
+                .filter_map(|name| {
+                    let stage = self.stage(name);
+                    if stage.is_none() {
+                        log::warn!("Skipping unknown post-processing stage {name:?}");
+                    }
+                    stage
+                })
+                .collect(),
+            None => self
+                .stages
+                .iter()
+                .map(Box::as_ref)
+                .filter(|stage| supports(*stage, language))
+                .collect(),
+        }
+    }
+
+    fn stage(&self, name: &str) -> Option<&dyn PostProcessStage> {
+        self.stages
+            .iter()
+            .map(Box::as_ref)
+            .find(|stage| stage.name() == name)
+    }
+}
+
+impl Default for PostProcessor {
+    fn default() -> Self {
+        Self::builtin()
+    }
+}
+
+fn supports(stage: &dyn PostProcessStage, language: Option<&str>) -> bool {
+    let languages = stage.languages();
+    if languages.is_empty() {
+        return true;
+    }
+    let Some(language) = language.map(str::to_lowercase) else {
+        return false;
+    };
+    languages.iter().any(|supported| {
+        let supported = supported.to_lowercase();
+        supported == language || supported == primary_subtag(&language)
+    })
+}
+
+fn primary_subtag(language: &str) -> &str {
+    language.split(['-', '_']).next().unwrap_or(language)
+}
+
+struct SpellingStage;
+
+impl PostProcessStage for SpellingStage {
+    fn name(&self) -> &'static str {
+        SPELLING_STAGE
+    }
+
+    fn apply(&self, text: &str, context: &StageContext<'_>) -> String {
+        if context.text_commands.spelling {
+            apply_spelling(text)
+        } else {
+            text.to_string()
+        }
+    }
+}
+
+struct CodeModeStage;
+
+impl PostProcessStage for CodeModeStage {
+    fn name(&self) -> &'static str {
+        CODE_MODE_STAGE
+    }
+
+    fn apply(&self, text: &str, context: &StageContext<'_>) -> String {
+        if context.text_commands.code_mode {
+            apply_code_mode(text)
+        } else {
+            text.to_string()
+        }
+    }
+}
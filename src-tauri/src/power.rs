@@ -0,0 +1,120 @@
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+
+use serde::{Deserialize, Serialize};
+
+use crate::sync::MutexRecover;
+
+/// Battery status is cached this long, since querying it can spawn a process.
+const BATTERY_CHECK_INTERVAL: Duration = Duration::from_secs(60);
+
+static BATTERY_STATUS: Mutex<Option<(Instant, bool)>> = Mutex::new(None);
+
+/// When power saving applies: decoding once per phrase, no live waveform, and
+/// a model loaded on first use rather than at launch. `Auto` follows the
+/// battery.
+#[derive(Clone, Copy, Debug, Default, Deserialize, Eq, PartialEq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum PowerSaverMode {
+    #[default]
+    Auto,
+    On,
+    Off,
+}
+
+impl PowerSaverMode {
+    pub fn active(self, on_battery: impl FnOnce() -> bool) -> bool {
+        match self {
+            Self::Auto => on_battery(),
+            Self::On => true,
+            Self::Off => false,
+        }
+    }
+}
+
+/// Whether the machine is running on battery. Desktops, and platforms that
+/// cannot be queried, count as plugged in.
+pub fn on_battery() -> bool {
+    let mut cached = BATTERY_STATUS.lock_recover();
+    if let Some((checked_at, on_battery)) = *cached {
+        if checked_at.elapsed() < BATTERY_CHECK_INTERVAL {
+            return on_battery;
+        }
+    }
+    let on_battery = query_on_battery().unwrap_or(false);
+    *cached = Some((Instant::now(), on_battery));
+    on_battery
+}
+
+/// Reads `pmset -g batt` output, whose first line names the power source.
+pub fn pmset_on_battery(output: &str) -> Option<bool> {
+    let source = output.lines().next()?;
+    if source.contains("'Battery Power'") {
+        Some(true)
+    } else if source.contains("'AC Power'") {
+        Some(false)
+    } else {
+        None
+    }
+}
+
+/// Reads the `status` of each `/sys/class/power_supply` battery; any battery
+/// discharging means the machine is unplugged.
+pub fn battery_statuses_on_battery<'a>(statuses: impl IntoIterator<Item = &'a str>) -> bool {
+    statuses
+        .into_iter()
+        .any(|status| status.trim().eq_ignore_ascii_case("Discharging"))
+}
+
+/// Reads `Win32_Battery.BatteryStatus` values, one per line; 1 is discharging.
+pub fn win32_battery_on_battery(output: &str) -> Option<bool> {
+    let statuses: Vec<u16> = output
+        .lines()
+        .filter_map(|line| line.trim().parse().ok())
+        .collect();
+    (!statuses.is_empty()).then(|| statuses.contains(&1))
+}
+
+#[cfg(target_os = "linux")]
+fn query_on_battery() -> Option<bool> {
+    let supplies = std::fs::read_dir("/sys/class/power_supply").ok()?;
+    let statuses: Vec<String> = supplies
+        .flatten()
+        .filter(|supply| {
+            std::fs::read_to_string(supply.path().join("type"))
+                .is_ok_and(|kind| kind.trim() == "Battery")
+        })
+        .filter_map(|supply| std::fs::read_to_string(supply.path().join("status")).ok())
+        .collect();
+    Some(battery_statuses_on_battery(
+        statuses.iter().map(String::as_str),
+    ))
+}
+
+#[cfg(target_os = "macos")]
+fn query_on_battery() -> Option<bool> {
+    let output = std::process::Command::new("pmset")
+        .args(["-g", "batt"])
+        .output()
+        .ok()?;
+    pmset_on_battery(&String::from_utf8_lossy(&output.stdout))
+}
+
+#[cfg(target_os = "windows")]
+fn query_on_battery() -> Option<bool> {
+    let output = std::process::Command::new("powershell")
+        .args([
+            "-NoProfile",
+            "-NonInteractive",
+            "-Command",
+            "(Get-CimInstance Win32_Battery).BatteryStatus",
+        ])
+        .output()
+        .ok()?;
+    win32_battery_on_battery(&String::from_utf8_lossy(&output.stdout))
+}
+
+#[cfg(not(any(target_os = "linux", target_os = "macos", target_os = "windows")))]
+fn query_on_battery() -> Option<bool> {
+    None
+}
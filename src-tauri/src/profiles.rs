@@ -0,0 +1,205 @@
+use serde::{Deserialize, Serialize};
+
+use crate::capture_file::CaptureFileSettings;
+use crate::errors::UserFacing;
+use crate::noise_profile::NoiseSelection;
+use crate::output_template::OutputTemplates;
+use crate::settings::{Settings, TypingPace};
+use crate::speaker_adaptation::SpeakerAdaptation;
+use crate::text_commands::TextCommands;
+use crate::time_stretch::TimeStretch;
+
+/// Emitted with the profile name after a switch, so the window reloads the
+/// settings the profile replaced.
+pub const PROFILE_CHANGED_EVENT: &str = "profile_changed";
+pub const MAX_PROFILE_NAME_CHARS: usize = 40;
+
+/// The settings each profile keeps its own copy of. Everything else, such as
+/// the record shortcut or quiet hours, is shared by all profiles.
+#[derive(Clone, Debug, Deserialize, PartialEq, Serialize)]
+pub struct ProfileSettings {
+    pub asr_language: String,
+    pub streaming_enabled: bool,
+    pub typing_pace: TypingPace,
+    pub output_templates: OutputTemplates,
+    pub capture_file: CaptureFileSettings,
+    /// Lets a "Code" profile turn on code mode. Profiles saved before text
+    /// commands existed load with them off.
+    #[serde(default)]
+    pub text_commands: TextCommands,
+    /// Lets a profile for a fast speaker slow the final decode down.
+    #[serde(default)]
+    pub time_stretch: TimeStretch,
+    /// Lets a "Car" profile subtract the car's noise. The recorded profiles
+    /// themselves are shared.
+    #[serde(default)]
+    pub noise_selection: NoiseSelection,
+    /// Each speaker sharing the machine keeps the voice learned for them.
+    #[serde(default)]
+    pub speaker_adaptation: SpeakerAdaptation,
+}
+
+impl ProfileSettings {
+    pub fn capture(settings: &Settings) -> Self {
+        Self {
+            asr_language: settings.asr_language.clone(),
+            streaming_enabled: settings.streaming_enabled,
+            typing_pace: settings.typing_pace,
+            output_templates: settings.output_templates.clone(),
+            capture_file: settings.capture_file.clone(),
+            text_commands: settings.text_commands.clone(),
+            time_stretch: settings.time_stretch,
+            noise_selection: settings.noise_suppression.selection.clone(),
+            speaker_adaptation: settings.speaker_adaptation,
+        }
+    }
+
+    pub fn apply_to(&self, settings: &mut Settings) {
+        settings.asr_language = self.asr_language.clone();
+        settings.streaming_enabled = self.streaming_enabled;
+        settings.typing_pace = self.typing_pace;
+        settings.output_templates = self.output_templates.clone();
+        settings.capture_file = self.capture_file.clone();
+        settings.text_commands = self.text_commands.clone();
+        settings.time_stretch = self.time_stretch.clamped();
+        settings
+            .noise_suppression
+            .select(self.noise_selection.clone());
+        settings.speaker_adaptation = self.speaker_adaptation;
+    }
+}
+
+#[derive(Clone, Debug, Deserialize, PartialEq, Serialize)]
+pub struct Profile {
+    pub name: String,
+    pub settings: ProfileSettings,
+}
+
+/// Saved profiles and the one in use. The active profile's values live in the
+/// regular settings while it is in use; its saved copy is refreshed when
+/// another profile is chosen.
+#[derive(Clone, Debug, Default, Deserialize, PartialEq, Serialize)]
+pub struct Profiles {
+    pub active: Option<String>,
+    pub saved: Vec<Profile>,
+}
+
+/// What the settings UI lists; profile values are shown through the regular
+/// settings once a profile is active.
+#[derive(Clone, Debug, Serialize)]
+pub struct ProfileList {
+    pub active: Option<String>,
+    pub names: Vec<String>,
+}
+
+impl From<&Profiles> for ProfileList {
+    fn from(profiles: &Profiles) -> Self {
+        Self {
+            active: profiles.active.clone(),
+            names: profiles.names(),
+        }
+    }
+}
+
+#[derive(thiserror::Error, Debug, Eq, PartialEq)]
+pub enum ProfileError {
+    #[error("profile name is empty")]
+    EmptyName,
+    #[error("profile name is longer than {MAX_PROFILE_NAME_CHARS} characters")]
+    NameTooLong,
+    #[error("profile {0} already exists")]
+    Duplicate(String),
+    #[error("profile {0} does not exist")]
+    NotFound(String),
+}
+
+impl UserFacing for ProfileError {
+    fn user_message(&self) -> &'static str {
+        match self {
+            Self::EmptyName => "Enter a profile name.",
+            Self::NameTooLong => "Profile names can be at most 40 characters.",
+            Self::Duplicate(_) => "A profile with that name already exists.",
+            Self::NotFound(_) => "That profile no longer exists.",
+        }
+    }
+}
+
+pub fn normalize_name(name: &str) -> Result<String, ProfileError> {
+    let name = name.trim();
+    if name.is_empty() {
+        return Err(ProfileError::EmptyName);
+    }
+    if name.chars().count() > MAX_PROFILE_NAME_CHARS {
+        return Err(ProfileError::NameTooLong);
+    }
+    Ok(name.to_string())
+}
+
+impl Profiles {
+    fn position(&self, name: &str) -> Option<usize> {
+        self.saved
+            .iter()
+            .position(|profile| profile.name.eq_ignore_ascii_case(name))
+    }
+
+    /// The saved profile called `name`, ignoring case.
+    pub fn find(&self, name: &str) -> Option<&Profile> {
+        self.position(name).map(|index| &self.saved[index])
+    }
+
+    pub fn names(&self) -> Vec<String> {
+        self.saved
+            .iter()
+            .map(|profile| profile.name.clone())
+            .collect()
+    }
+
+    /// Saves the current settings as a new profile, which becomes the active
+    /// one when no profile is, so the settings in use belong to a profile.
+    pub fn create(&mut self, name: &str, current: &Settings) -> Result<(), ProfileError> {
+        let name = normalize_name(name)?;
+        if self.position(&name).is_some() {
+            return Err(ProfileError::Duplicate(name));
+        }
+        if self.active.is_none() {
+            self.active = Some(name.clone());
+        }
+        self.saved.push(Profile {
+            name,
+            settings: ProfileSettings::capture(current),
+        });
+        Ok(())
+    }
+
+    /// Deleting the active profile keeps its values in use without a profile
+    /// to save them back to.
+    pub fn delete(&mut self, name: &str) -> Result<(), ProfileError> {
+        let index = self
+            .position(name)
+            .ok_or_else(|| ProfileError::NotFound(name.to_string()))?;
+        let removed = self.saved.remove(index);
+        if self.active.as_deref() == Some(removed.name.as_str()) {
+            self.active = None;
+        }
+        Ok(())
+    }
+}
+
+/// Returns `current` with `name` as the active profile: the profile being left
+/// keeps the values in use, and the chosen profile's values replace them.
+pub fn switch(current: &Settings, name: &str) -> Result<Settings, ProfileError> {
+    let mut next = current.clone();
+    let index = next
+        .profiles
+        .position(name)
+        .ok_or_else(|| ProfileError::NotFound(name.to_string()))?;
+    if let Some(active) = next.profiles.active.clone() {
+        if let Some(leaving) = next.profiles.position(&active) {
+            next.profiles.saved[leaving].settings = ProfileSettings::capture(current);
+        }
+    }
+    let chosen = next.profiles.saved[index].clone();
+    chosen.settings.apply_to(&mut next);
+    next.profiles.active = Some(chosen.name);
+    Ok(next)
+}
@@ -0,0 +1,74 @@
+use chrono::{Datelike, Local, Timelike};
+use serde::{Deserialize, Serialize};
+
+const MINUTES_PER_DAY: u16 = 24 * 60;
+const DAYS_PER_WEEK: u8 = 7;
+
+/// A daily window in local time. Days count from Monday as 0. A window whose
+/// end is earlier than its start runs past midnight into the following day;
+/// equal start and end cover the whole day.
+#[derive(Clone, Debug, Deserialize, Eq, PartialEq, Serialize)]
+pub struct QuietRange {
+    pub days: Vec<u8>,
+    pub start_minute: u16,
+    pub end_minute: u16,
+}
+
+impl QuietRange {
+    fn applies_to(&self, day: u8) -> bool {
+        self.days.contains(&day)
+    }
+
+    pub fn contains(&self, day: u8, minute: u16) -> bool {
+        let (start, end) = (self.start_minute, self.end_minute);
+        if start == end {
+            return self.applies_to(day);
+        }
+        if start < end {
+            return self.applies_to(day) && (start..end).contains(&minute);
+        }
+        let previous_day = (day + DAYS_PER_WEEK - 1) % DAYS_PER_WEEK;
+        (self.applies_to(day) && minute >= start) || (self.applies_to(previous_day) && minute < end)
+    }
+
+    fn normalized(mut self) -> Self {
+        self.days.retain(|day| *day < DAYS_PER_WEEK);
+        self.days.sort_unstable();
+        self.days.dedup();
+        self.start_minute = self.start_minute.min(MINUTES_PER_DAY - 1);
+        self.end_minute = self.end_minute.min(MINUTES_PER_DAY - 1);
+        self
+    }
+}
+
+/// Scheduled windows during which the record shortcut stays inactive.
+#[derive(Clone, Debug, Default, Deserialize, Eq, PartialEq, Serialize)]
+pub struct QuietHours {
+    pub enabled: bool,
+    pub ranges: Vec<QuietRange>,
+}
+
+impl QuietHours {
+    /// Drops out-of-range days and clamps minutes so stored values stay valid.
+    pub fn normalized(self) -> Self {
+        Self {
+            enabled: self.enabled,
+            ranges: self
+                .ranges
+                .into_iter()
+                .map(QuietRange::normalized)
+                .collect(),
+        }
+    }
+
+    pub fn is_quiet(&self, day: u8, minute: u16) -> bool {
+        self.enabled && self.ranges.iter().any(|range| range.contains(day, minute))
+    }
+
+    pub fn is_quiet_now(&self) -> bool {
+        let now = Local::now();
+        let day = now.weekday().num_days_from_monday() as u8;
+        let minute = (now.hour() * 60 + now.minute()) as u16;
+        self.is_quiet(day, minute)
+    }
+}
@@ -0,0 +1,113 @@
+use std::collections::VecDeque;
+use std::str::FromStr;
+use std::sync::{Mutex, OnceLock};
+
+use log::{Level, Log, Metadata, Record};
+use serde::Serialize;
+
+use crate::errors::UserFacing;
+
+/// Records kept for the in-app log viewer; the log files hold the rest.
+pub const RECENT_LOG_CAPACITY: usize = 1_000;
+
+static RECENT_LOGS: OnceLock<RecentLogs> = OnceLock::new();
+
+#[derive(Clone, Debug, Eq, PartialEq, Serialize)]
+pub struct LogEntry {
+    pub timestamp: String,
+    pub level: String,
+    pub target: String,
+    pub message: String,
+}
+
+#[derive(thiserror::Error, Debug, Eq, PartialEq)]
+pub enum RecentLogsError {
+    #[error("unknown log level {0}")]
+    UnknownLevel(String),
+}
+
+impl UserFacing for RecentLogsError {
+    fn user_message(&self) -> &'static str {
+        match self {
+            Self::UnknownLevel(_) => "Choose error, warn, info, debug, or trace as the log level.",
+        }
+    }
+}
+
+pub fn parse_level(level: &str) -> Result<Level, RecentLogsError> {
+    Level::from_str(level.trim()).map_err(|_| RecentLogsError::UnknownLevel(level.to_string()))
+}
+
+/// A bounded ring of the latest log records. Oldest records are dropped once
+/// `capacity` is reached.
+pub struct RecentLogs {
+    capacity: usize,
+    entries: Mutex<VecDeque<(Level, LogEntry)>>,
+}
+
+impl RecentLogs {
+    pub fn new(capacity: usize) -> Self {
+        Self {
+            capacity,
+            entries: Mutex::new(VecDeque::with_capacity(capacity)),
+        }
+    }
+
+    pub fn push(&self, level: Level, entry: LogEntry) {
+        let Ok(mut entries) = self.entries.lock() else {
+            return;
+        };
+        if entries.len() == self.capacity {
+            entries.pop_front();
+        }
+        if self.capacity > 0 {
+            entries.push_back((level, entry));
+        }
+    }
+
+    /// The newest `limit` records at `level` or more severe, oldest first.
+    pub fn recent(&self, level: Level, limit: usize) -> Vec<LogEntry> {
+        let Ok(entries) = self.entries.lock() else {
+            return Vec::new();
+        };
+        let mut matching = entries
+            .iter()
+            .rev()
+            .filter(|(entry_level, _)| *entry_level <= level)
+            .take(limit)
+            .map(|(_, entry)| entry.clone())
+            .collect::<Vec<_>>();
+        matching.reverse();
+        matching
+    }
+}
+
+/// The process-wide ring that the log plugin feeds.
+pub fn recent_logs() -> &'static RecentLogs {
+    RECENT_LOGS.get_or_init(|| RecentLogs::new(RECENT_LOG_CAPACITY))
+}
+
+/// A `log` sink that copies each record into [`recent_logs`].
+pub struct RecentLogsSink;
+
+impl Log for RecentLogsSink {
+    fn enabled(&self, _metadata: &Metadata) -> bool {
+        true
+    }
+
+    fn log(&self, record: &Record) {
+        recent_logs().push(
+            record.level(),
+            LogEntry {
+                timestamp: chrono::Local::now()
+                    .format("%Y-%m-%d %H:%M:%S%.3f")
+                    .to_string(),
+                level: record.level().as_str().to_ascii_lowercase(),
+                target: record.target().to_string(),
+                message: record.args().to_string(),
+            },
+        );
+    }
+
+    fn flush(&self) {}
+}
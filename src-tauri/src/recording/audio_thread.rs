@@ -4,16 +4,25 @@ use std::sync::{
     Arc, Mutex,
 };
 use std::thread;
-use std::time::Duration;
+use std::time::{Duration, Instant};
 
-use cpal::traits::{DeviceTrait, HostTrait, StreamTrait};
+use cpal::traits::{DeviceTrait, StreamTrait};
 use cpal::{Sample, SizedSample};
 use rtrb::{Producer, RingBuffer};
 
 use crate::asr::TARGET_SAMPLE_RATE;
-use crate::audio_processing::{AudioFrame, AudioProcessor, PROCESS_CHUNK_SIZE};
+use crate::audio_processing::{
+    AudioFrame, AudioProcessor, ProcessingStage, ResamplerQuality, PROCESS_CHUNK_SIZE,
+};
+use crate::sync::MutexRecover;
 
-use super::{AudioCmd, RecordingError};
+use super::input_device::{device_name, downmix, open_input_device, stream_config};
+use super::lookback::{ListenConfig, RollingBuffer};
+use super::vad_monitor::VadTap;
+use super::waveform::WaveformTap;
+use super::{
+    app_playback, forward_to_stream, AudioCmd, AudioHealth, CaptureOptions, RecordingError,
+};
 
 pub(super) fn init_and_run_audio_thread(
     cmd_rx: Receiver<AudioCmd>,
@@ -21,27 +30,35 @@ pub(super) fn init_and_run_audio_thread(
     init_tx: Sender<Result<(), ()>>,
     streaming_tx: Option<Sender<AudioFrame>>,
     overrun_count: Arc<AtomicUsize>,
-) -> Result<(), RecordingError> {
+    resampler_quality: ResamplerQuality,
+    capture: CaptureOptions,
+) -> Result<AudioHealth, RecordingError> {
+    let CaptureOptions {
+        input_device,
+        voice_activity,
+        mut noise_reducer,
+        mut speaker_normalizer,
+        waveform,
+        length,
+    } = capture;
     let host = cpal::default_host();
-    let device = host
-        .default_input_device()
-        .ok_or(RecordingError::NoInputDevice)?;
+    let device = open_input_device(&host, input_device.device.as_deref())?;
+    let name = device_name(&device).unwrap_or_else(|| "unknown input device".to_string());
+    let device_config = input_device.config_for(&name);
 
-    let stream_config = device.default_input_config().map_err(|error| {
-        RecordingError::Device(format!("read default input configuration: {error}"))
-    })?;
+    let (stream_config, buffer_size) = stream_config(&device, device_config)?;
 
     let sample_rate = stream_config.sample_rate();
     let channels = stream_config.channels() as usize;
+    let mixed_channels = device_config.mixed_channels(channels);
 
     log::info!(
-        "Audio: {} Hz, {} channels, device={:?}",
+        "Audio: {} Hz, {} channels (mixing {:?}), buffer={:?}, device={:?}",
         sample_rate,
         channels,
-        device
-            .description()
-            .map(|description| description.to_string())
-            .unwrap_or_else(|_| "unknown input device".to_string())
+        mixed_channels,
+        buffer_size,
+        name
     );
 
     let (producer, mut consumer) = RingBuffer::<f32>::new(sample_rate as usize);
@@ -49,38 +66,30 @@ pub(super) fn init_and_run_audio_thread(
     let callback_failed = stream_failed.clone();
     let err_fn = move |_| callback_failed.store(true, Ordering::Release);
     let sample_format = stream_config.sample_format();
-    let stream_config = stream_config.into();
-    let mut processor = AudioProcessor::new(sample_rate as usize, TARGET_SAMPLE_RATE as usize)
-        .map_err(|e| RecordingError::AudioProcessingError(e.to_string()))?;
+    let stream_config = cpal::StreamConfig {
+        buffer_size,
+        ..stream_config.into()
+    };
+    let mut processor = AudioProcessor::for_source(
+        sample_rate as usize,
+        channels,
+        TARGET_SAMPLE_RATE as usize,
+        resampler_quality,
+    )
+    .map_err(|e| RecordingError::AudioProcessingError(e.to_string()))?;
+    let resampling = processor.is_resampling();
+    let bypassed = processor.is_bypassed();
 
-    let stream = match sample_format {
-        cpal::SampleFormat::F32 => build_stream::<f32>(
-            &device,
-            stream_config,
-            producer,
-            channels,
-            err_fn,
-            overrun_count.clone(),
-        ),
-        cpal::SampleFormat::I16 => build_stream::<i16>(
-            &device,
-            stream_config,
-            producer,
-            channels,
-            err_fn,
-            overrun_count.clone(),
-        ),
-        cpal::SampleFormat::U16 => build_stream::<u16>(
-            &device,
-            stream_config,
-            producer,
-            channels,
-            err_fn,
-            overrun_count.clone(),
-        ),
-        _ => Err(cpal::Error::new(cpal::ErrorKind::UnsupportedConfig)),
-    }
-    .map_err(|error| RecordingError::Device(format!("build input stream: {error}")))?;
+    let stream = build_input_stream(
+        &device,
+        sample_format,
+        stream_config,
+        producer,
+        channels,
+        mixed_channels,
+        err_fn,
+        overrun_count.clone(),
+    )?;
 
     stream
         .play()
@@ -89,8 +98,25 @@ pub(super) fn init_and_run_audio_thread(
 
     let _ = init_tx.send(Ok(()));
 
+    // Counted in device frames; a device that stops delivering ends the take
+    // at twice its length instead of holding the recorder.
+    let mut frames_left =
+        length.map(|length| (length.as_secs_f64() * f64::from(sample_rate)).ceil() as usize);
+    let deadline = length.map(|length| Instant::now() + length * 2);
     let mut processed_local = Vec::new();
+    let mut dropped_streaming_samples = 0;
+    let mut muted_samples = 0;
+    let gain = voice_activity.gain;
+    let mut vad = VadTap::new(voice_activity.config());
+    let mut waveform = WaveformTap::new(waveform);
     let mut stopping = false;
+    let mut deliver = |mut frame: AudioFrame| {
+        speaker_normalizer.process(&mut frame.samples);
+        vad.observe(&frame.samples);
+        waveform.observe(&frame.samples);
+        processed_local.extend_from_slice(&frame.samples);
+        dropped_streaming_samples += forward_to_stream(&streaming_tx, frame);
+    };
 
     loop {
         if stream_failed.load(Ordering::Acquire) {
@@ -98,19 +124,35 @@ pub(super) fn init_and_run_audio_thread(
                 "input stream failed during capture".to_string(),
             ));
         }
-        if !stopping && matches!(cmd_rx.try_recv(), Ok(AudioCmd::Stop)) {
+        let captured =
+            frames_left == Some(0) || deadline.is_some_and(|deadline| Instant::now() >= deadline);
+        if !stopping && (captured || matches!(cmd_rx.try_recv(), Ok(AudioCmd::Stop))) {
             stopping = true;
             stream.take();
         }
 
         let available = consumer.slots();
-        if available >= PROCESS_CHUNK_SIZE || (stopping && available > 0) {
+        // A bypassed source is read as it arrives rather than a frame at a time.
+        let ready = if bypassed {
+            available > 0
+        } else {
+            available >= PROCESS_CHUNK_SIZE
+        };
+        if ready || (stopping && available > 0) {
             if let Ok(chunk) = consumer.read_chunk(available.min(PROCESS_CHUNK_SIZE * 8)) {
                 let (f, s) = chunk.as_slices();
-                let mut dispatch = |frame: AudioFrame| {
-                    processed_local.extend_from_slice(&frame.samples);
-                    if let Some(tx) = &streaming_tx {
-                        let _ = tx.send(frame);
+                let mut dispatch = |mut frame: AudioFrame| {
+                    // Spoken read-back reaches the microphone; silence it
+                    // rather than transcribe the app's own voice.
+                    if app_playback().is_muted_at(Instant::now()) {
+                        frame.samples.fill(0.0);
+                        muted_samples += frame.samples.len();
+                    } else {
+                        apply_gain(&mut frame.samples, gain);
+                    }
+                    noise_reducer.process(&mut frame.samples);
+                    if !frame.samples.is_empty() {
+                        deliver(frame);
                     }
                 };
                 processor
@@ -121,6 +163,9 @@ pub(super) fn init_and_run_audio_thread(
                         .process(s, &mut dispatch)
                         .map_err(|e| RecordingError::AudioProcessingError(e.to_string()))?;
                 }
+                if let Some(frames_left) = &mut frames_left {
+                    *frames_left = frames_left.saturating_sub(f.len() + s.len());
+                }
                 chunk.commit_all();
             }
         } else if stopping {
@@ -131,26 +176,270 @@ pub(super) fn init_and_run_audio_thread(
     }
 
     processor
-        .flush(&mut |frame: AudioFrame| {
-            processed_local.extend_from_slice(&frame.samples);
-            if let Some(tx) = &streaming_tx {
-                let _ = tx.send(frame);
+        .flush(&mut |mut frame: AudioFrame| {
+            apply_gain(&mut frame.samples, gain);
+            noise_reducer.process(&mut frame.samples);
+            if !frame.samples.is_empty() {
+                deliver(frame);
             }
         })
         .map_err(|e| RecordingError::AudioProcessingError(e.to_string()))?;
+    let held_back = noise_reducer.finish();
+    if !held_back.is_empty() {
+        deliver(AudioFrame { samples: held_back });
+    }
+    if let Some(profile) = noise_reducer.profile() {
+        log::info!("Subtracted noise profile {profile:?}");
+    }
+    vad.finish();
 
-    if let Ok(mut guard) = processed_samples.lock() {
-        *guard = processed_local;
+    if muted_samples > 0 {
+        log::info!(
+            "Muted {} ms of capture during app audio playback",
+            muted_samples * 1_000 / TARGET_SAMPLE_RATE as usize
+        );
     }
 
+    *processed_samples.lock_recover() = processed_local;
+
+    let processing_chain: Vec<_> = [
+        (channels > 1).then_some(ProcessingStage::Downmix),
+        processor.stage(),
+        (gain != 1.0).then_some(ProcessingStage::Gain),
+        noise_reducer
+            .profile()
+            .map(|_| ProcessingStage::NoiseReduction),
+        speaker_normalizer
+            .is_active()
+            .then_some(ProcessingStage::SpeakerNormalization),
+    ]
+    .into_iter()
+    .flatten()
+    .collect();
+    log::info!("Audio processing chain: {processing_chain:?}");
+
+    Ok(AudioHealth {
+        device_sample_rate: sample_rate,
+        resampling,
+        overrun_samples: 0,
+        dropped_streaming_samples,
+        processing_chain,
+    })
+}
+
+/// Keeps the lookback buffer filled with the microphone at the model rate
+/// until stopped. Overruns only lose a moment of the buffer, so they are
+/// counted and logged rather than failing the listener.
+pub(super) fn run_lookback_thread(
+    cmd_rx: Receiver<AudioCmd>,
+    buffer: Arc<Mutex<RollingBuffer>>,
+    init_tx: Sender<Result<(), ()>>,
+    config: ListenConfig,
+) -> Result<(), RecordingError> {
+    let host = cpal::default_host();
+    let device = open_input_device(&host, config.input_device.device.as_deref())?;
+    let name = device_name(&device).unwrap_or_else(|| "unknown input device".to_string());
+    let device_config = config.input_device.config_for(&name);
+    let (stream_config, buffer_size) = stream_config(&device, device_config)?;
+    let sample_rate = stream_config.sample_rate();
+    let channels = stream_config.channels() as usize;
+    log::info!("Lookback audio: {sample_rate} Hz, {channels} channels, device={name:?}");
+
+    let (producer, mut consumer) = RingBuffer::<f32>::new(sample_rate as usize);
+    let stream_failed = Arc::new(AtomicBool::new(false));
+    let callback_failed = stream_failed.clone();
+    let overrun_count = Arc::new(AtomicUsize::new(0));
+    let sample_format = stream_config.sample_format();
+    let mut processor = AudioProcessor::for_source(
+        sample_rate as usize,
+        channels,
+        TARGET_SAMPLE_RATE as usize,
+        config.resampler_quality,
+    )
+    .map_err(|e| RecordingError::AudioProcessingError(e.to_string()))?;
+    let stream = build_input_stream(
+        &device,
+        sample_format,
+        cpal::StreamConfig {
+            buffer_size,
+            ..stream_config.into()
+        },
+        producer,
+        channels,
+        device_config.mixed_channels(channels),
+        move |_| callback_failed.store(true, Ordering::Release),
+        overrun_count.clone(),
+    )?;
+    stream
+        .play()
+        .map_err(|error| RecordingError::Device(format!("start input stream: {error}")))?;
+    let _ = init_tx.send(Ok(()));
+
+    let mut keep = |mut frame: AudioFrame| {
+        apply_gain(&mut frame.samples, config.gain);
+        buffer.lock_recover().push(&frame.samples);
+    };
+    loop {
+        if stream_failed.load(Ordering::Acquire) {
+            return Err(RecordingError::Device(
+                "input stream failed while listening".to_string(),
+            ));
+        }
+        if matches!(cmd_rx.try_recv(), Ok(AudioCmd::Stop)) {
+            break;
+        }
+        let overruns = overrun_count.swap(0, Ordering::Relaxed);
+        if overruns > 0 {
+            log::warn!("Lookback listener overran by {overruns} samples");
+        }
+        let available = consumer.slots();
+        if available < PROCESS_CHUNK_SIZE {
+            thread::sleep(Duration::from_millis(20));
+            continue;
+        }
+        if let Ok(chunk) = consumer.read_chunk(available.min(PROCESS_CHUNK_SIZE * 8)) {
+            let (f, s) = chunk.as_slices();
+            processor
+                .process(f, &mut keep)
+                .map_err(|e| RecordingError::AudioProcessingError(e.to_string()))?;
+            if !s.is_empty() {
+                processor
+                    .process(s, &mut keep)
+                    .map_err(|e| RecordingError::AudioProcessingError(e.to_string()))?;
+            }
+            chunk.commit_all();
+        }
+    }
     Ok(())
 }
 
+/// Boosts a quiet microphone, clipping rather than wrapping at full scale.
+fn apply_gain(samples: &mut [f32], gain: f32) {
+    if gain != 1.0 {
+        for sample in samples {
+            *sample = (*sample * gain).clamp(-1.0, 1.0);
+        }
+    }
+}
+
+/// Opens an input stream in the device's sample format that downmixes into
+/// `producer`.
+#[allow(clippy::too_many_arguments)]
+pub(super) fn build_input_stream(
+    device: &cpal::Device,
+    sample_format: cpal::SampleFormat,
+    stream_config: cpal::StreamConfig,
+    producer: Producer<f32>,
+    channels: usize,
+    mixed_channels: Vec<usize>,
+    err_fn: impl FnMut(cpal::Error) + Send + 'static,
+    overrun_count: Arc<AtomicUsize>,
+) -> Result<cpal::Stream, RecordingError> {
+    match sample_format {
+        cpal::SampleFormat::F32 => build_stream::<f32>(
+            device,
+            stream_config,
+            producer,
+            channels,
+            mixed_channels,
+            err_fn,
+            overrun_count,
+        ),
+        cpal::SampleFormat::I16 => build_stream::<i16>(
+            device,
+            stream_config,
+            producer,
+            channels,
+            mixed_channels,
+            err_fn,
+            overrun_count,
+        ),
+        cpal::SampleFormat::U16 => build_stream::<u16>(
+            device,
+            stream_config,
+            producer,
+            channels,
+            mixed_channels,
+            err_fn,
+            overrun_count,
+        ),
+        cpal::SampleFormat::I8 => build_stream::<i8>(
+            device,
+            stream_config,
+            producer,
+            channels,
+            mixed_channels,
+            err_fn,
+            overrun_count,
+        ),
+        cpal::SampleFormat::I32 => build_stream::<i32>(
+            device,
+            stream_config,
+            producer,
+            channels,
+            mixed_channels,
+            err_fn,
+            overrun_count,
+        ),
+        cpal::SampleFormat::I64 => build_stream::<i64>(
+            device,
+            stream_config,
+            producer,
+            channels,
+            mixed_channels,
+            err_fn,
+            overrun_count,
+        ),
+        cpal::SampleFormat::U8 => build_stream::<u8>(
+            device,
+            stream_config,
+            producer,
+            channels,
+            mixed_channels,
+            err_fn,
+            overrun_count,
+        ),
+        cpal::SampleFormat::U32 => build_stream::<u32>(
+            device,
+            stream_config,
+            producer,
+            channels,
+            mixed_channels,
+            err_fn,
+            overrun_count,
+        ),
+        cpal::SampleFormat::U64 => build_stream::<u64>(
+            device,
+            stream_config,
+            producer,
+            channels,
+            mixed_channels,
+            err_fn,
+            overrun_count,
+        ),
+        cpal::SampleFormat::F64 => build_stream::<f64>(
+            device,
+            stream_config,
+            producer,
+            channels,
+            mixed_channels,
+            err_fn,
+            overrun_count,
+        ),
+        other => {
+            log::warn!("Input sample format {other} is not supported");
+            Err(cpal::Error::new(cpal::ErrorKind::UnsupportedConfig))
+        }
+    }
+    .map_err(|error| RecordingError::Device(format!("build input stream: {error}")))
+}
+
 fn build_stream<T>(
     device: &cpal::Device,
     config: cpal::StreamConfig,
     mut producer: Producer<f32>,
     channels: usize,
+    mixed_channels: Vec<usize>,
     err_fn: impl FnMut(cpal::Error) + Send + 'static,
     overrun_count: Arc<AtomicUsize>,
 ) -> Result<cpal::Stream, cpal::Error>
@@ -158,6 +447,7 @@ where
     T: Sample + SizedSample + Send + 'static,
     f32: cpal::FromSample<T>,
 {
+    let mut frame = vec![0.0; channels];
     device.build_input_stream(
         config,
         move |data: &[T], _: &_| {
@@ -168,12 +458,14 @@ where
                     }
                 }
             } else {
-                for frame in data.chunks(channels) {
-                    let mut sum = 0.0;
-                    for &sample in frame {
-                        sum += sample.to_sample::<f32>();
+                for samples in data.chunks(channels) {
+                    for (slot, &sample) in frame.iter_mut().zip(samples) {
+                        *slot = sample.to_sample::<f32>();
                     }
-                    if producer.push(sum / channels as f32).is_err() {
+                    if producer
+                        .push(downmix(&frame[..samples.len()], &mixed_channels))
+                        .is_err()
+                    {
                         overrun_count.fetch_add(1, Ordering::Relaxed);
                     }
                 }
@@ -0,0 +1,171 @@
+use serde::Serialize;
+
+use super::SessionId;
+use crate::asr::TARGET_SAMPLE_RATE;
+
+/// 20 ms windows for clipping, counted like the frames a listener would hear
+/// as distorted.
+const CLIP_FRAME: usize = 320;
+const CLIP_LEVEL: f32 = 0.99;
+const CLIPPED_FRAME_LIMIT: f32 = 0.01;
+const DC_OFFSET_LIMIT: f32 = 0.01;
+/// Frames for the spectrum estimate, and the level below which a frame is too
+/// quiet to say anything about bandwidth.
+const SPECTRUM_FRAME: usize = 512;
+const SPECTRUM_MIN_RMS: f32 = 0.005;
+const MAX_SPECTRUM_FRAMES: usize = 400;
+/// Width of the bands whose power is probed, up to the 8 kHz Nyquist limit.
+const BAND_HZ: u32 = 250;
+/// A band counts towards the bandwidth while its power is within this many
+/// decibels of the strongest band.
+const BANDWIDTH_FLOOR_DB: f32 = 50.0;
+/// Below this, speech sounds like a phone call: sibilants are lost.
+const NARROWBAND_HZ: u32 = 5_500;
+
+#[derive(Clone, Copy, Debug, Eq, PartialEq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum QualityFlag {
+    Good,
+    Poor,
+}
+
+/// Signal checks on a session's captured audio, with what to change when they
+/// point at the input settings rather than the speaker.
+#[derive(Clone, Debug, PartialEq, Serialize)]
+pub struct CaptureQuality {
+    pub session_id: SessionId,
+    pub flag: QualityFlag,
+    pub device_sample_rate: u32,
+    /// Share of 20 ms frames with at least one sample at full scale.
+    pub clipped_frame_ratio: f32,
+    /// Mean sample value; speech centres on zero.
+    pub dc_offset: f32,
+    /// Highest frequency the recording carries, when it has enough sound to
+    /// tell.
+    pub bandwidth_hz: Option<u32>,
+    pub suggestions: Vec<String>,
+}
+
+/// Checks 16 kHz mono samples, after gain, for clipping, DC offset, and lost
+/// high frequencies.
+pub fn analyze_capture(
+    session_id: SessionId,
+    samples: &[f32],
+    device_sample_rate: u32,
+) -> CaptureQuality {
+    let clipped_frame_ratio = clipped_frame_ratio(samples);
+    let dc_offset = if samples.is_empty() {
+        0.0
+    } else {
+        samples.iter().sum::<f32>() / samples.len() as f32
+    };
+    let bandwidth_hz = bandwidth_hz(samples);
+
+    let mut suggestions = Vec::new();
+    if clipped_frame_ratio > CLIPPED_FRAME_LIMIT {
+        suggestions.push(format!(
+            "Input clipped in {:.0}% of frames. Lower the microphone gain in Voice Detection or the system input level.",
+            clipped_frame_ratio * 100.0
+        ));
+    }
+    if dc_offset.abs() > DC_OFFSET_LIMIT {
+        suggestions.push(format!(
+            "Input has a DC offset of {dc_offset:+.3}. The microphone or its driver may be faulty; try another input."
+        ));
+    }
+    if device_sample_rate < TARGET_SAMPLE_RATE {
+        suggestions.push(format!(
+            "The input device records at {} kHz. Request 16 kHz or more in Input Device.",
+            device_sample_rate as f32 / 1_000.0
+        ));
+    } else if let Some(bandwidth) = bandwidth_hz.filter(|hz| *hz < NARROWBAND_HZ) {
+        suggestions.push(format!(
+            "Input only carries sound up to about {:.1} kHz, like a phone call. Bluetooth headsets do this while their microphone is on; try a wired or built-in microphone.",
+            bandwidth as f32 / 1_000.0
+        ));
+    }
+
+    CaptureQuality {
+        session_id,
+        flag: if suggestions.is_empty() {
+            QualityFlag::Good
+        } else {
+            QualityFlag::Poor
+        },
+        device_sample_rate,
+        clipped_frame_ratio,
+        dc_offset,
+        bandwidth_hz,
+        suggestions,
+    }
+}
+
+fn clipped_frame_ratio(samples: &[f32]) -> f32 {
+    let frames = samples.len().div_ceil(CLIP_FRAME);
+    if frames == 0 {
+        return 0.0;
+    }
+    let clipped = samples
+        .chunks(CLIP_FRAME)
+        .filter(|frame| frame.iter().any(|sample| sample.abs() >= CLIP_LEVEL))
+        .count();
+    clipped as f32 / frames as f32
+}
+
+/// Averages band powers over the louder frames and returns the upper edge of
+/// the highest band within [`BANDWIDTH_FLOOR_DB`] of the strongest.
+fn bandwidth_hz(samples: &[f32]) -> Option<u32> {
+    let loud: Vec<&[f32]> = samples
+        .chunks_exact(SPECTRUM_FRAME)
+        .filter(|frame| rms(frame) >= SPECTRUM_MIN_RMS)
+        .collect();
+    if loud.is_empty() {
+        return None;
+    }
+    let stride = loud.len().div_ceil(MAX_SPECTRUM_FRAMES);
+    let window = hann_window();
+    let bands = TARGET_SAMPLE_RATE / 2 / BAND_HZ;
+    let mut power = vec![0.0f32; bands as usize];
+    let mut windowed = vec![0.0f32; SPECTRUM_FRAME];
+    for frame in loud.iter().step_by(stride) {
+        for ((slot, sample), weight) in windowed.iter_mut().zip(*frame).zip(&window) {
+            *slot = sample * weight;
+        }
+        for (band, band_power) in power.iter_mut().enumerate() {
+            let center = band as f32 * BAND_HZ as f32 + BAND_HZ as f32 / 2.0;
+            *band_power += goertzel_power(&windowed, center);
+        }
+    }
+
+    let peak = power.iter().copied().fold(0.0f32, f32::max);
+    if peak <= 0.0 {
+        return None;
+    }
+    let floor = peak * 10f32.powf(-BANDWIDTH_FLOOR_DB / 10.0);
+    let highest = power.iter().rposition(|band_power| *band_power >= floor)?;
+    Some((highest as u32 + 1) * BAND_HZ)
+}
+
+fn goertzel_power(samples: &[f32], frequency: f32) -> f32 {
+    let coefficient = 2.0 * (std::f32::consts::TAU * frequency / TARGET_SAMPLE_RATE as f32).cos();
+    let (mut previous, mut before) = (0.0f32, 0.0f32);
+    for sample in samples {
+        let current = sample + coefficient * previous - before;
+        before = previous;
+        previous = current;
+    }
+    previous * previous + before * before - coefficient * previous * before
+}
+
+fn rms(samples: &[f32]) -> f32 {
+    (samples.iter().map(|sample| sample * sample).sum::<f32>() / samples.len() as f32).sqrt()
+}
+
+fn hann_window() -> Vec<f32> {
+    (0..SPECTRUM_FRAME)
+        .map(|index| {
+            let phase = std::f32::consts::TAU * index as f32 / SPECTRUM_FRAME as f32;
+            0.5 - 0.5 * phase.cos()
+        })
+        .collect()
+}
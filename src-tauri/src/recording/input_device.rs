@@ -0,0 +1,196 @@
+use std::collections::BTreeMap;
+
+#[cfg(feature = "desktop")]
+use cpal::traits::{DeviceTrait, HostTrait};
+use serde::{Deserialize, Serialize};
+
+#[cfg(feature = "desktop")]
+use super::RecordingError;
+
+pub const MIN_DEVICE_SAMPLE_RATE: u32 = 8_000;
+pub const MAX_DEVICE_SAMPLE_RATE: u32 = 384_000;
+pub const MIN_BUFFER_FRAMES: u32 = 32;
+pub const MAX_BUFFER_FRAMES: u32 = 16_384;
+
+/// Which input device to record from, and capture overrides for devices whose
+/// default configuration records poorly, such as multichannel virtual cables.
+#[derive(Clone, Debug, Default, Deserialize, Eq, PartialEq, Serialize)]
+#[serde(default)]
+pub struct InputDeviceSettings {
+    /// Device name as the system lists it; the default input when unset.
+    pub device: Option<String>,
+    /// Overrides keyed by device name.
+    pub configs: BTreeMap<String, InputDeviceConfig>,
+}
+
+/// Unset fields keep what the device reports as its default.
+#[derive(Clone, Copy, Debug, Default, Deserialize, Eq, PartialEq, Serialize)]
+#[serde(default)]
+pub struct InputDeviceConfig {
+    /// Requested when the device supports it, so audio is resampled once
+    /// rather than after the driver's own conversion.
+    pub sample_rate: Option<u32>,
+    /// Channels mixed into the recording, bit 0 being the first channel.
+    /// Zero mixes every channel.
+    pub channel_mask: u64,
+    /// Frames per callback asked of the driver, within what it supports.
+    pub buffer_frames: Option<u32>,
+}
+
+impl InputDeviceSettings {
+    /// Drops empty device names and overrides that change nothing.
+    pub fn normalized(self) -> Self {
+        Self {
+            device: self.device.filter(|device| !device.trim().is_empty()),
+            configs: self
+                .configs
+                .into_iter()
+                .map(|(device, config)| (device, config.clamped()))
+                .filter(|(device, config)| {
+                    !device.trim().is_empty() && *config != InputDeviceConfig::default()
+                })
+                .collect(),
+        }
+    }
+
+    pub fn config_for(&self, device: &str) -> InputDeviceConfig {
+        self.configs.get(device).copied().unwrap_or_default()
+    }
+}
+
+impl InputDeviceConfig {
+    pub fn clamped(self) -> Self {
+        Self {
+            sample_rate: self
+                .sample_rate
+                .map(|rate| rate.clamp(MIN_DEVICE_SAMPLE_RATE, MAX_DEVICE_SAMPLE_RATE)),
+            buffer_frames: self
+                .buffer_frames
+                .map(|frames| frames.clamp(MIN_BUFFER_FRAMES, MAX_BUFFER_FRAMES)),
+            ..self
+        }
+    }
+
+    /// Indices of the channels to mix out of `channels`. A mask that selects
+    /// none of the device's channels mixes all of them.
+    pub fn mixed_channels(&self, channels: usize) -> Vec<usize> {
+        let selected = (0..channels.min(u64::BITS as usize))
+            .filter(|channel| self.channel_mask & (1 << channel) != 0)
+            .collect::<Vec<_>>();
+        if selected.is_empty() {
+            (0..channels).collect()
+        } else {
+            selected
+        }
+    }
+}
+
+/// Averages the selected channels of one interleaved frame.
+pub fn downmix(frame: &[f32], channels: &[usize]) -> f32 {
+    let mut sum = 0.0;
+    let mut count = 0;
+    for &channel in channels {
+        if let Some(sample) = frame.get(channel) {
+            sum += sample;
+            count += 1;
+        }
+    }
+    if count == 0 {
+        0.0
+    } else {
+        sum / count as f32
+    }
+}
+
+/// The buffer size to request: `requested` within the driver's range, or
+/// unchanged when the driver does not report one.
+pub fn fixed_buffer_frames(requested: u32, supported: Option<(u32, u32)>) -> u32 {
+    match supported {
+        Some((min, max)) if min <= max => requested.clamp(min, max),
+        _ => requested,
+    }
+}
+
+#[cfg(feature = "desktop")]
+pub(super) fn device_name(device: &cpal::Device) -> Option<String> {
+    device
+        .description()
+        .map(|description| description.to_string())
+        .ok()
+}
+
+/// Names of the input devices the system currently lists.
+#[cfg(feature = "desktop")]
+pub fn input_device_names() -> Result<Vec<String>, RecordingError> {
+    let devices = cpal::default_host()
+        .input_devices()
+        .map_err(|error| RecordingError::Device(format!("list input devices: {error}")))?;
+    let mut names = devices
+        .filter_map(|device| device_name(&device))
+        .collect::<Vec<_>>();
+    names.sort();
+    names.dedup();
+    Ok(names)
+}
+
+#[cfg(feature = "desktop")]
+pub(super) fn open_input_device(
+    host: &cpal::Host,
+    selected: Option<&str>,
+) -> Result<cpal::Device, RecordingError> {
+    let Some(selected) = selected else {
+        return host
+            .default_input_device()
+            .ok_or(RecordingError::NoInputDevice);
+    };
+    host.input_devices()
+        .map_err(|error| RecordingError::Device(format!("list input devices: {error}")))?
+        .find(|device| device_name(device).as_deref() == Some(selected))
+        .ok_or_else(|| RecordingError::InputDeviceNotFound(selected.to_string()))
+}
+
+/// The device's default configuration with the override's sample rate when a
+/// supported range with the default channel count and sample format allows
+/// it, and the override's buffer size.
+#[cfg(feature = "desktop")]
+pub(super) fn stream_config(
+    device: &cpal::Device,
+    config: InputDeviceConfig,
+) -> Result<(cpal::SupportedStreamConfig, cpal::BufferSize), RecordingError> {
+    let default = device.default_input_config().map_err(|error| {
+        RecordingError::Device(format!("read default input configuration: {error}"))
+    })?;
+    let supported = match config.sample_rate {
+        Some(rate) if rate != default.sample_rate() => {
+            let preferred = device
+                .supported_input_configs()
+                .map_err(|error| {
+                    RecordingError::Device(format!("read input configurations: {error}"))
+                })?
+                .filter(|range| {
+                    range.channels() == default.channels()
+                        && range.sample_format() == default.sample_format()
+                })
+                .find_map(|range| range.try_with_sample_rate(rate));
+            preferred.unwrap_or_else(|| {
+                log::warn!(
+                    "Input device does not support {rate} Hz; using {} Hz",
+                    default.sample_rate()
+                );
+                default
+            })
+        }
+        _ => default,
+    };
+    let buffer_size = match config.buffer_frames {
+        Some(frames) => {
+            let range = match supported.buffer_size() {
+                cpal::SupportedBufferSize::Range { min, max } => Some((*min, *max)),
+                cpal::SupportedBufferSize::Unknown => None,
+            };
+            cpal::BufferSize::Fixed(fixed_buffer_frames(frames, range))
+        }
+        None => cpal::BufferSize::Default,
+    };
+    Ok((supported, buffer_size))
+}
@@ -0,0 +1,223 @@
+use std::collections::VecDeque;
+use std::sync::mpsc::{self, Sender};
+use std::sync::{Arc, Mutex, OnceLock};
+use std::thread;
+use std::time::Duration;
+
+use serde::{Deserialize, Serialize};
+
+use crate::asr::TARGET_SAMPLE_RATE;
+use crate::audio_processing::ResamplerQuality;
+use crate::sync::MutexRecover;
+
+use super::{AudioCmd, InputDeviceSettings, RecordingError};
+
+pub const DEFAULT_BUFFER_SECS: u32 = 120;
+pub const MIN_BUFFER_SECS: u32 = 10;
+pub const MAX_BUFFER_SECS: u32 = 600;
+pub const DEFAULT_TRANSCRIBE_SECS: u32 = 30;
+pub const MIN_TRANSCRIBE_SECS: u32 = 5;
+
+/// Whether the microphone is kept listening into a rolling buffer, how much
+/// of it is kept, and how much of it the lookback shortcut transcribes.
+#[derive(Clone, Copy, Debug, Deserialize, Eq, PartialEq, Serialize)]
+#[serde(default)]
+pub struct LookbackSettings {
+    pub enabled: bool,
+    pub buffer_secs: u32,
+    pub transcribe_secs: u32,
+}
+
+impl Default for LookbackSettings {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            buffer_secs: DEFAULT_BUFFER_SECS,
+            transcribe_secs: DEFAULT_TRANSCRIBE_SECS,
+        }
+    }
+}
+
+impl LookbackSettings {
+    /// Keeps the buffer within memory limits and the transcribed span within
+    /// the buffer.
+    pub fn clamped(self) -> Self {
+        let buffer_secs = self.buffer_secs.clamp(MIN_BUFFER_SECS, MAX_BUFFER_SECS);
+        Self {
+            buffer_secs,
+            transcribe_secs: self.transcribe_secs.clamp(MIN_TRANSCRIBE_SECS, buffer_secs),
+            ..self
+        }
+    }
+
+    /// Samples at the model rate the buffer holds.
+    pub fn buffer_samples(&self) -> usize {
+        self.buffer_secs as usize * TARGET_SAMPLE_RATE as usize
+    }
+
+    /// Samples at the model rate the shortcut transcribes.
+    pub fn transcribe_samples(&self) -> usize {
+        self.transcribe_secs as usize * TARGET_SAMPLE_RATE as usize
+    }
+}
+
+/// The most recent `capacity` samples; older ones are dropped as new ones
+/// arrive.
+#[derive(Clone, Debug, Default)]
+pub struct RollingBuffer {
+    samples: VecDeque<f32>,
+    capacity: usize,
+}
+
+impl RollingBuffer {
+    pub fn new(capacity: usize) -> Self {
+        Self {
+            samples: VecDeque::with_capacity(capacity),
+            capacity,
+        }
+    }
+
+    pub fn push(&mut self, samples: &[f32]) {
+        let kept = &samples[samples.len().saturating_sub(self.capacity)..];
+        let excess = (self.samples.len() + kept.len()).saturating_sub(self.capacity);
+        self.samples.drain(..excess);
+        self.samples.extend(kept);
+    }
+
+    /// Up to `count` of the newest samples, oldest first.
+    pub fn latest(&self, count: usize) -> Vec<f32> {
+        let start = self.samples.len().saturating_sub(count);
+        self.samples.range(start..).copied().collect()
+    }
+
+    pub fn len(&self) -> usize {
+        self.samples.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.samples.is_empty()
+    }
+}
+
+/// What the listener captures with; a change restarts it.
+#[derive(Clone, Debug, PartialEq)]
+pub struct ListenConfig {
+    pub capacity: usize,
+    pub input_device: InputDeviceSettings,
+    pub gain: f32,
+    pub resampler_quality: ResamplerQuality,
+}
+
+struct ListenSession {
+    config: ListenConfig,
+    cmd_tx: Sender<AudioCmd>,
+    worker_handle: thread::JoinHandle<Result<(), RecordingError>>,
+}
+
+/// The always-on microphone capture behind the lookback shortcut. It runs
+/// beside dictation recordings on a stream of its own, and never keeps more
+/// than its buffer in memory.
+pub struct Lookback {
+    buffer: Arc<Mutex<RollingBuffer>>,
+    session: Mutex<Option<ListenSession>>,
+}
+
+impl Lookback {
+    fn new() -> Self {
+        Self {
+            buffer: Arc::new(Mutex::new(RollingBuffer::default())),
+            session: Mutex::new(None),
+        }
+    }
+
+    pub fn global() -> &'static Self {
+        static LOOKBACK: OnceLock<Lookback> = OnceLock::new();
+        LOOKBACK.get_or_init(Self::new)
+    }
+
+    pub fn is_listening(&self) -> bool {
+        self.session.lock_recover().is_some()
+    }
+
+    /// Starts listening with `config`, restarting with an empty buffer when
+    /// it differs from the running one or the device failed, or stops with
+    /// `None`.
+    pub fn sync(&self, config: Option<ListenConfig>) -> Result<(), RecordingError> {
+        let mut session = self.session.lock_recover();
+        let running = session
+            .as_ref()
+            .filter(|session| !session.worker_handle.is_finished())
+            .map(|session| &session.config);
+        if running == config.as_ref() {
+            return Ok(());
+        }
+        if let Some(previous) = session.take() {
+            let _ = previous.cmd_tx.send(AudioCmd::Stop);
+            match previous.worker_handle.join() {
+                Ok(Err(error)) => log::warn!("Lookback listener failed: {error}"),
+                Err(_) => log::warn!("Lookback listener panicked"),
+                Ok(Ok(())) => {}
+            }
+            log::info!("Lookback listener stopped");
+        }
+        *self.buffer.lock_recover() = RollingBuffer::default();
+        let Some(config) = config else {
+            return Ok(());
+        };
+        *session = Some(self.spawn(config)?);
+        log::info!("Lookback listener started");
+        Ok(())
+    }
+
+    fn spawn(&self, config: ListenConfig) -> Result<ListenSession, RecordingError> {
+        *self.buffer.lock_recover() = RollingBuffer::new(config.capacity);
+        let (cmd_tx, cmd_rx) = mpsc::channel();
+        let (init_tx, init_rx) = mpsc::channel();
+        let init_error_tx = init_tx.clone();
+        let buffer = self.buffer.clone();
+        let worker_config = config.clone();
+        let handle = thread::Builder::new()
+            .name("lookback-capture".to_string())
+            .spawn(move || {
+                #[cfg(feature = "desktop")]
+                let result = super::audio_thread::run_lookback_thread(
+                    cmd_rx,
+                    buffer,
+                    init_tx,
+                    worker_config,
+                );
+                // Headless builds have no audio host to listen on.
+                #[cfg(not(feature = "desktop"))]
+                let result = {
+                    let _ = (cmd_rx, buffer, init_tx, worker_config);
+                    Err(RecordingError::NoInputDevice)
+                };
+                if result.is_err() {
+                    let _ = init_error_tx.send(Err(()));
+                }
+                result
+            })
+            .map_err(RecordingError::ThreadStart)?;
+
+        match init_rx.recv_timeout(Duration::from_secs(3)) {
+            Ok(Ok(())) => Ok(ListenSession {
+                config,
+                cmd_tx,
+                worker_handle: handle,
+            }),
+            init_result => {
+                let _ = cmd_tx.send(AudioCmd::Stop);
+                let worker_result = handle.join();
+                match (init_result, worker_result) {
+                    (_, Ok(Err(err))) => Err(err),
+                    _ => Err(RecordingError::ThreadError),
+                }
+            }
+        }
+    }
+
+    /// Up to `count` of the most recently heard samples at the model rate.
+    pub fn latest(&self, count: usize) -> Vec<f32> {
+        self.buffer.lock_recover().latest(count)
+    }
+}
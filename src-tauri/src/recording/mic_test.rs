@@ -0,0 +1,75 @@
+use std::time::Duration;
+
+use serde::Serialize;
+
+use super::CaptureQuality;
+
+/// How long the microphone test records before playing the take back.
+pub const MIC_TEST_DURATION: Duration = Duration::from_secs(3);
+/// Bars in the preview of the 16 kHz take.
+pub const MIC_TEST_PREVIEW_BUCKETS: usize = 120;
+/// Level reported for silence, where the logarithm has no floor.
+pub const SILENCE_DBFS: f32 = -96.0;
+/// Peaks below this mean the microphone is muted or turned far down.
+const QUIET_PEAK_DBFS: f32 = -40.0;
+
+/// Levels of a short test take, measured on the 16 kHz audio the speech model
+/// receives, with a peak preview of it for the settings page.
+#[derive(Clone, Debug, PartialEq, Serialize)]
+pub struct MicrophoneTest {
+    pub duration_ms: u64,
+    pub peak_dbfs: f32,
+    pub rms_dbfs: f32,
+    /// Peak amplitude of consecutive slices of the take, oldest first.
+    pub preview: Vec<f32>,
+    pub too_quiet: bool,
+    pub quality: Option<CaptureQuality>,
+}
+
+/// Measures `samples`, 16 kHz mono audio after gain, lasting `duration_ms`.
+pub fn analyze_mic_test(
+    samples: &[f32],
+    duration_ms: u64,
+    quality: Option<CaptureQuality>,
+) -> MicrophoneTest {
+    let peak = samples
+        .iter()
+        .fold(0.0f32, |peak, sample| peak.max(sample.abs()));
+    let rms = if samples.is_empty() {
+        0.0
+    } else {
+        (samples.iter().map(|sample| sample * sample).sum::<f32>() / samples.len() as f32).sqrt()
+    };
+    let peak_dbfs = level_dbfs(peak);
+    MicrophoneTest {
+        duration_ms,
+        peak_dbfs,
+        rms_dbfs: level_dbfs(rms),
+        preview: peak_preview(samples, MIC_TEST_PREVIEW_BUCKETS),
+        too_quiet: peak_dbfs < QUIET_PEAK_DBFS,
+        quality,
+    }
+}
+
+/// Decibels relative to full scale, floored at [`SILENCE_DBFS`].
+pub fn level_dbfs(amplitude: f32) -> f32 {
+    if amplitude <= 0.0 {
+        return SILENCE_DBFS;
+    }
+    (20.0 * amplitude.log10()).max(SILENCE_DBFS)
+}
+
+/// Splits `samples` into at most `buckets` even slices and keeps each slice's
+/// peak amplitude. Short takes get one bucket per sample.
+pub fn peak_preview(samples: &[f32], buckets: usize) -> Vec<f32> {
+    let buckets = buckets.max(1).min(samples.len());
+    (0..buckets)
+        .map(|bucket| {
+            let start = bucket * samples.len() / buckets;
+            let end = (bucket + 1) * samples.len() / buckets;
+            samples[start..end]
+                .iter()
+                .fold(0.0f32, |peak, sample| peak.max(sample.abs()))
+        })
+        .collect()
+}
@@ -1,19 +1,61 @@
+#[cfg(feature = "desktop")]
 mod audio_thread;
+mod capture_quality;
+mod input_device;
+mod lookback;
+mod mic_test;
+mod network_thread;
+#[cfg(feature = "desktop")]
+mod playback;
+mod playback_gate;
+mod vad_monitor;
+mod waveform;
 
+use std::fmt;
 use std::mem;
 use std::sync::{
-    atomic::{AtomicBool, AtomicUsize, Ordering},
-    mpsc::{self, Sender},
+    atomic::{AtomicBool, AtomicU64, AtomicUsize, Ordering},
+    mpsc::{self, Receiver, Sender},
     Arc, Condvar, Mutex, OnceLock,
 };
 use std::thread;
 use std::time::Duration;
 
+use serde::Serialize;
 use thiserror::Error;
 
 use crate::activity::{self, ActivityError, ActivityGuard, AppActivity};
-use crate::audio_processing::AudioFrame;
+use crate::asr::TARGET_SAMPLE_RATE;
+use crate::audio_processing::{AudioFrame, ProcessingStage, ResamplerQuality};
 use crate::errors::UserFacing;
+use crate::noise_profile::NoiseReducer;
+use crate::speaker_adaptation::SpeakerNormalizer;
+use crate::sync::{recover, MutexRecover};
+use crate::vad::VadSettings;
+
+pub use capture_quality::{analyze_capture, CaptureQuality, QualityFlag};
+#[cfg(feature = "desktop")]
+pub use input_device::input_device_names;
+pub use input_device::{
+    downmix, fixed_buffer_frames, InputDeviceConfig, InputDeviceSettings, MAX_BUFFER_FRAMES,
+    MAX_DEVICE_SAMPLE_RATE, MIN_BUFFER_FRAMES, MIN_DEVICE_SAMPLE_RATE,
+};
+pub use lookback::{
+    ListenConfig, Lookback, LookbackSettings, RollingBuffer, DEFAULT_BUFFER_SECS,
+    DEFAULT_TRANSCRIBE_SECS, MAX_BUFFER_SECS, MIN_BUFFER_SECS, MIN_TRANSCRIBE_SECS,
+};
+pub use mic_test::{
+    analyze_mic_test, level_dbfs, peak_preview, MicrophoneTest, MIC_TEST_DURATION,
+    MIC_TEST_PREVIEW_BUCKETS, SILENCE_DBFS,
+};
+#[cfg(feature = "desktop")]
+pub use playback::play_samples;
+pub use playback_gate::{app_playback, PlaybackGate, PlaybackGuard, PLAYBACK_ECHO_TAIL};
+pub use vad_monitor::{vad_monitor, VadMonitor, VAD_EVENTS_EVENT};
+pub use waveform::{
+    set_waveform_sink, WaveformBucketer, WaveformFrame, WAVEFORM_BUCKETS, WAVEFORM_EVENT,
+    WAVEFORM_FRAMES_PER_SECOND,
+};
 
 #[derive(Error, Debug)]
 pub enum RecordingError {
@@ -23,6 +65,12 @@ pub enum RecordingError {
     NotRecording,
     #[error("No default input device available")]
     NoInputDevice,
+    #[error("No default output device available")]
+    NoOutputDevice,
+    #[error("Playback error: {0}")]
+    Playback(String),
+    #[error("Input device {0:?} is not connected")]
+    InputDeviceNotFound(String),
     #[error("No audio captured")]
     NoAudioCaptured,
     #[error("Device error: {0}")]
@@ -41,6 +89,8 @@ pub enum RecordingError {
     UpdateInProgress,
     #[error("Speech settings are being changed")]
     SettingsInProgress,
+    #[error("Text is being typed")]
+    TypingInProgress,
 }
 
 impl UserFacing for RecordingError {
@@ -49,6 +99,11 @@ impl UserFacing for RecordingError {
             Self::AlreadyRecording => "Recording is already in progress.",
             Self::NotRecording => "No recording in progress.",
             Self::NoInputDevice => "No microphone found. Please check your audio settings.",
+            Self::NoOutputDevice => "No speakers found. Please check your audio settings.",
+            Self::Playback(_) => "The recording could not be played back. Check your speakers.",
+            Self::InputDeviceNotFound(_) => {
+                "The selected input device is not connected. Choose another one in settings."
+            }
             Self::NoAudioCaptured => "No audio was captured. Please try again.",
             Self::Device(_) => "A microphone error occurred. Please check your audio settings.",
             Self::LockFailed => "The recorder is busy. Please try again.",
@@ -58,6 +113,7 @@ impl UserFacing for RecordingError {
             Self::SettingsInProgress => {
                 "Speech settings are being changed. Please try recording again."
             }
+            Self::TypingInProgress => "Text is still being typed. Please try recording again.",
             Self::ThreadError | Self::ThreadStart(_) | Self::AudioProcessingError(_) => {
                 "Internal audio error. Please restart the app."
             }
@@ -72,10 +128,120 @@ pub(super) enum AudioCmd {
     Stop,
 }
 
+/// Capture diagnostics for the most recent session, reported after every stop
+/// so missed words can be traced to the input path rather than the model.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize)]
+pub struct AudioHealth {
+    pub device_sample_rate: u32,
+    pub resampling: bool,
+    pub overrun_samples: usize,
+    pub dropped_streaming_samples: usize,
+    /// The steps the capture went through; empty when a mono source at the
+    /// model rate was passed straight through.
+    pub processing_chain: Vec<ProcessingStage>,
+}
+
+impl AudioHealth {
+    pub fn is_degraded(&self) -> bool {
+        self.overrun_samples > 0 || self.dropped_streaming_samples > 0
+    }
+}
+
+/// Identifies one recording from its reservation through its final
+/// transcript, so events and logs from back-to-back utterances can be told
+/// apart.
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq, Serialize)]
+#[serde(transparent)]
+pub struct SessionId(u64);
+
+impl SessionId {
+    fn next() -> Self {
+        static NEXT: AtomicU64 = AtomicU64::new(1);
+        Self(NEXT.fetch_add(1, Ordering::Relaxed))
+    }
+
+    #[doc(hidden)]
+    pub fn from_raw_for_tests(raw: u64) -> Self {
+        Self(raw)
+    }
+}
+
+impl fmt::Display for SessionId {
+    fn fmt(&self, formatter: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(formatter, "{}", self.0)
+    }
+}
+
+/// Where a recording's audio comes from. Network input carries samples
+/// already decoded at the target rate.
+#[derive(Debug, Default)]
+pub enum AudioInput {
+    #[default]
+    Microphone,
+    Network(Receiver<Vec<f32>>),
+}
+
+/// What a reservation records for. Only dictation is ended by
+/// [`Recorder::stop`] or waited on by a queued start, so a measurement always
+/// gets its full take.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+enum RecordingKind {
+    Dictation,
+    Measurement,
+}
+
+/// How a reservation's microphone input is captured and processed.
+struct CaptureOptions {
+    input_device: InputDeviceSettings,
+    voice_activity: VadSettings,
+    noise_reducer: NoiseReducer,
+    speaker_normalizer: SpeakerNormalizer,
+    waveform: bool,
+    /// Ends capture on its own once this much input has arrived.
+    length: Option<Duration>,
+}
+
 struct RecordingSession {
+    id: SessionId,
     cmd_tx: Sender<AudioCmd>,
-    worker_handle: thread::JoinHandle<Result<(), RecordingError>>,
-    activity_guard: ActivityGuard,
+    worker_handle: thread::JoinHandle<Result<AudioHealth, RecordingError>>,
+    slot: RecordingSlot,
+}
+
+/// Receives the reservation of a recording queued while the last one was
+/// still finishing.
+type QueuedStartSink = Box<dyn Fn(RecordingReservation) + Send + Sync>;
+
+static QUEUED_START_SINK: OnceLock<QueuedStartSink> = OnceLock::new();
+
+/// Sets where queued recordings are started. Only the first sink is kept.
+pub fn set_queued_start_sink(sink: impl Fn(RecordingReservation) + Send + Sync + 'static) -> bool {
+    QUEUED_START_SINK.set(Box::new(sink)).is_ok()
+}
+
+/// Whether a recording holds the recorder, and whether another is waiting
+/// for it. Both change only under the same lock, so a start queued just as
+/// the last recording finishes is either run or never queued.
+#[derive(Default)]
+struct Reservations {
+    held: Option<RecordingKind>,
+    queued_start: bool,
+}
+
+/// The recording activity a recording holds from its reservation until its
+/// audio is dropped, so the model is not shared while its transcript is
+/// finished. Letting go of it starts a queued recording in its place.
+struct RecordingSlot {
+    kind: RecordingKind,
+    activity_guard: Option<ActivityGuard>,
+}
+
+impl Drop for RecordingSlot {
+    fn drop(&mut self) {
+        if let Some(activity_guard) = self.activity_guard.take() {
+            Recorder::global().release(self.kind, activity_guard);
+        }
+    }
 }
 
 /// Resets the recorder's `starting` flag when the reservation ends without a
@@ -91,19 +257,90 @@ impl Drop for StartingGuard {
 }
 
 pub struct RecordingReservation {
-    activity_guard: ActivityGuard,
+    id: SessionId,
+    input: AudioInput,
+    capture: CaptureOptions,
+    // Dropped before `slot`, so a queued recording handed the slot keeps
+    // showing as starting.
     starting: StartingGuard,
+    slot: RecordingSlot,
+}
+
+impl RecordingReservation {
+    /// Known before capture starts so streaming updates can carry it.
+    pub fn session_id(&self) -> SessionId {
+        self.id
+    }
+
+    /// Records from `input` instead of the default microphone.
+    pub fn with_input(self, input: AudioInput) -> Self {
+        Self { input, ..self }
+    }
+
+    /// Chooses the device and capture overrides used for microphone input.
+    pub fn with_input_device(mut self, input_device: InputDeviceSettings) -> Self {
+        self.capture.input_device = input_device;
+        self
+    }
+
+    /// Sets the microphone gain and the thresholds the live detector uses.
+    pub fn with_voice_activity(mut self, voice_activity: VadSettings) -> Self {
+        self.capture.voice_activity = voice_activity;
+        self
+    }
+
+    /// Subtracts background noise from microphone input. Network input
+    /// arrives already captured and is left alone.
+    pub fn with_noise_reducer(mut self, noise_reducer: NoiseReducer) -> Self {
+        self.capture.noise_reducer = noise_reducer;
+        self
+    }
+
+    /// Moves microphone input toward the level and tone the speech model
+    /// expects, after noise subtraction.
+    pub fn with_speaker_normalizer(mut self, speaker_normalizer: SpeakerNormalizer) -> Self {
+        self.capture.speaker_normalizer = speaker_normalizer;
+        self
+    }
+
+    /// Publishes a live waveform of the recording when enabled.
+    pub fn with_waveform(mut self, waveform: bool) -> Self {
+        self.capture.waveform = waveform;
+        self
+    }
+}
+
+/// Returns the number of samples the streaming decoder did not receive
+/// because its receiver had already gone away.
+fn forward_to_stream(streaming_tx: &Option<Sender<AudioFrame>>, frame: AudioFrame) -> usize {
+    let Some(tx) = streaming_tx else {
+        return 0;
+    };
+    let len = frame.samples.len();
+    match tx.send(frame) {
+        Ok(()) => 0,
+        Err(_) => len,
+    }
 }
 
 pub struct RecordedAudio {
     samples: Vec<f32>,
-    _activity_guard: ActivityGuard,
+    health: AudioHealth,
+    _slot: RecordingSlot,
 }
 
 impl RecordedAudio {
     pub fn samples(&self) -> &[f32] {
         &self.samples
     }
+
+    pub fn health(&self) -> &AudioHealth {
+        &self.health
+    }
+
+    pub fn duration_ms(&self) -> u64 {
+        self.samples.len() as u64 * 1_000 / u64::from(TARGET_SAMPLE_RATE)
+    }
 }
 
 pub struct Recorder {
@@ -111,7 +348,12 @@ pub struct Recorder {
     session: Mutex<Option<RecordingSession>>,
     session_ready: Condvar,
     starting: AtomicBool,
+    reservations: Mutex<Reservations>,
     overrun_count: Arc<AtomicUsize>,
+    last_health: Mutex<Option<AudioHealth>>,
+    last_quality: Mutex<Option<CaptureQuality>>,
+    /// Raw id of the most recently started session; zero before the first.
+    last_session_id: AtomicU64,
 }
 
 impl Recorder {
@@ -121,7 +363,11 @@ impl Recorder {
             session: Mutex::new(None),
             session_ready: Condvar::new(),
             starting: AtomicBool::new(false),
+            reservations: Mutex::new(Reservations::default()),
             overrun_count: Arc::new(AtomicUsize::new(0)),
+            last_health: Mutex::new(None),
+            last_quality: Mutex::new(None),
+            last_session_id: AtomicU64::new(0),
         }
     }
 
@@ -135,13 +381,73 @@ impl Recorder {
             return true;
         }
 
-        self.session
-            .lock()
-            .map(|session| session.is_some())
-            .unwrap_or(false)
+        self.session.lock_recover().is_some()
+    }
+
+    /// The active session, or the most recent one while its transcript is
+    /// still being finished.
+    pub fn session_id(&self) -> Option<SessionId> {
+        match self.last_session_id.load(Ordering::Acquire) {
+            0 => None,
+            raw => Some(SessionId(raw)),
+        }
+    }
+
+    /// Diagnostics from the last completed session, including sessions whose
+    /// audio was rejected for overruns.
+    pub fn last_health(&self) -> Option<AudioHealth> {
+        self.last_health.lock_recover().clone()
+    }
+
+    /// Signal checks on the last session that captured audio.
+    pub fn last_quality(&self) -> Option<CaptureQuality> {
+        self.last_quality.lock_recover().clone()
     }
 
     pub fn reserve(&self) -> Result<RecordingReservation, RecordingError> {
+        let mut reservations = self.reservations.lock_recover();
+        self.reserve_locked(&mut reservations, RecordingKind::Dictation)
+    }
+
+    /// Reserves the recorder for [`Recorder::measure`]. A dictation stop
+    /// cannot end it, and no queued recording waits for it.
+    pub fn reserve_measurement(&self) -> Result<RecordingReservation, RecordingError> {
+        let mut reservations = self.reservations.lock_recover();
+        self.reserve_locked(&mut reservations, RecordingKind::Measurement)
+    }
+
+    /// Like [`Recorder::reserve`], but while another recording is still
+    /// being finished, queues this one to start as soon as it is and returns
+    /// `None`. The queued reservation goes to the [`set_queued_start_sink`]
+    /// sink.
+    pub fn reserve_or_queue(&self) -> Result<Option<RecordingReservation>, RecordingError> {
+        let mut reservations = self.reservations.lock_recover();
+        match reservations.held {
+            Some(RecordingKind::Dictation) => {
+                reservations.queued_start = true;
+                Ok(None)
+            }
+            Some(RecordingKind::Measurement) => Err(RecordingError::AlreadyRecording),
+            None => self
+                .reserve_locked(&mut reservations, RecordingKind::Dictation)
+                .map(Some),
+        }
+    }
+
+    /// Drops a queued start. Returns whether one was waiting.
+    pub fn cancel_queued_start(&self) -> bool {
+        mem::take(&mut self.reservations.lock_recover().queued_start)
+    }
+
+    pub fn has_queued_start(&self) -> bool {
+        self.reservations.lock_recover().queued_start
+    }
+
+    fn reserve_locked(
+        &self,
+        reservations: &mut Reservations,
+        kind: RecordingKind,
+    ) -> Result<RecordingReservation, RecordingError> {
         let activity_guard = match activity::try_begin(AppActivity::Recording) {
             Ok(guard) => guard,
             Err(ActivityError::Busy(AppActivity::Recording)) => {
@@ -153,39 +459,81 @@ impl Recorder {
             Err(ActivityError::Busy(AppActivity::Configuring)) => {
                 return Err(RecordingError::SettingsInProgress)
             }
+            Err(ActivityError::Busy(AppActivity::Typing)) => {
+                return Err(RecordingError::TypingInProgress)
+            }
             Err(ActivityError::LockFailed) => return Err(RecordingError::LockFailed),
         };
 
-        if self
-            .session
-            .lock()
-            .map_err(|_| RecordingError::LockFailed)?
-            .is_some()
-        {
+        if self.session.lock_recover().is_some() {
             return Err(RecordingError::AlreadyRecording);
         }
 
+        reservations.held = Some(kind);
+        Ok(self.reservation(kind, activity_guard))
+    }
+
+    fn reservation(
+        &self,
+        kind: RecordingKind,
+        activity_guard: ActivityGuard,
+    ) -> RecordingReservation {
         self.starting.store(true, Ordering::Release);
-        Ok(RecordingReservation {
-            activity_guard,
+        RecordingReservation {
+            id: SessionId::next(),
+            input: AudioInput::Microphone,
+            capture: CaptureOptions {
+                input_device: InputDeviceSettings::default(),
+                voice_activity: VadSettings::default(),
+                noise_reducer: NoiseReducer::off(),
+                speaker_normalizer: SpeakerNormalizer::off(),
+                waveform: false,
+                length: None,
+            },
+            slot: RecordingSlot {
+                kind,
+                activity_guard: Some(activity_guard),
+            },
             starting: StartingGuard,
-        })
+        }
+    }
+
+    /// Ends a recording's hold on the recorder, or hands it straight to the
+    /// queued recording, which shows as recording before the lock is let go.
+    fn release(&self, kind: RecordingKind, activity_guard: ActivityGuard) {
+        let mut reservations = self.reservations.lock_recover();
+        if kind != RecordingKind::Dictation || !mem::take(&mut reservations.queued_start) {
+            reservations.held = None;
+            drop(activity_guard);
+            return;
+        }
+        let reservation = self.reservation(kind, activity_guard);
+        drop(reservations);
+        log::info!("Starting queued recording {}", reservation.id);
+        match QUEUED_START_SINK.get() {
+            Some(sink) => sink(reservation),
+            None => log::warn!("Dropping queued recording: nothing starts it"),
+        }
     }
 
     pub fn start(
         &self,
         reservation: RecordingReservation,
         streaming_tx: Option<Sender<AudioFrame>>,
+        resampler_quality: ResamplerQuality,
     ) -> Result<(), RecordingError> {
         let RecordingReservation {
-            activity_guard,
+            id,
+            input,
+            capture,
+            slot,
             starting,
         } = reservation;
-        self.processed_samples
-            .lock()
-            .map_err(|_| RecordingError::LockFailed)?
-            .clear();
+        // Clearing also discards whatever a panicked capture thread left.
+        self.processed_samples.lock_recover().clear();
         self.overrun_count.store(0, Ordering::Relaxed);
+        *self.last_health.lock_recover() = None;
+        *self.last_quality.lock_recover() = None;
 
         // The session owns the stop sender and joins its sole audio worker;
         // the one-shot init channel cannot outlive startup.
@@ -198,13 +546,32 @@ impl Recorder {
         let handle = thread::Builder::new()
             .name("audio-capture".to_string())
             .spawn(move || {
-                let result = audio_thread::init_and_run_audio_thread(
-                    cmd_rx,
-                    samples_clone,
-                    init_tx,
-                    streaming_tx,
-                    overrun_clone,
-                );
+                let result = match input {
+                    #[cfg(feature = "desktop")]
+                    AudioInput::Microphone => audio_thread::init_and_run_audio_thread(
+                        cmd_rx,
+                        samples_clone,
+                        init_tx,
+                        streaming_tx,
+                        overrun_clone,
+                        resampler_quality,
+                        capture,
+                    ),
+                    // Headless builds have no audio host; only network input records.
+                    #[cfg(not(feature = "desktop"))]
+                    AudioInput::Microphone => {
+                        let _ = (overrun_clone, resampler_quality, capture);
+                        Err(RecordingError::NoInputDevice)
+                    }
+                    AudioInput::Network(audio_rx) => network_thread::run_network_audio_thread(
+                        cmd_rx,
+                        samples_clone,
+                        init_tx,
+                        streaming_tx,
+                        audio_rx,
+                        capture,
+                    ),
+                };
                 if result.is_err() {
                     let _ = init_error_tx.send(Err(()));
                 }
@@ -214,21 +581,17 @@ impl Recorder {
 
         match init_rx.recv_timeout(Duration::from_secs(3)) {
             Ok(Ok(())) => {
-                let mut session = match self.session.lock() {
-                    Ok(session) => session,
-                    Err(_) => {
-                        let _ = cmd_tx.send(AudioCmd::Stop);
-                        let _ = handle.join();
-                        return Err(RecordingError::LockFailed);
-                    }
-                };
+                let mut session = self.session.lock_recover();
                 *session = Some(RecordingSession {
+                    id,
                     cmd_tx,
                     worker_handle: handle,
-                    activity_guard,
+                    slot,
                 });
+                self.last_session_id.store(id.0, Ordering::Release);
                 drop(session);
                 drop(starting);
+                log::info!("Recording session {id} started");
                 Ok(())
             }
             init_result => {
@@ -242,56 +605,80 @@ impl Recorder {
         }
     }
 
+    /// Stops the dictation recording. A measurement is left to finish.
     pub fn stop(&self) -> Result<RecordedAudio, RecordingError> {
-        let mut session_guard = self
-            .session
-            .lock()
-            .map_err(|_| RecordingError::LockFailed)?;
+        let mut session_guard = self.session.lock_recover();
         while session_guard.is_none() && self.starting.load(Ordering::Acquire) {
-            session_guard = self
-                .session_ready
-                .wait(session_guard)
-                .map_err(|_| RecordingError::LockFailed)?;
+            session_guard = recover(self.session_ready.wait(session_guard));
         }
-        let session = session_guard.take().ok_or(RecordingError::NotRecording)?;
+        let session = session_guard
+            .take_if(|session| session.slot.kind == RecordingKind::Dictation)
+            .ok_or(RecordingError::NotRecording)?;
         drop(session_guard);
+        let _ = session.cmd_tx.send(AudioCmd::Stop);
+        self.finish(session)
+    }
 
+    /// Records `length` of input under a [`Recorder::reserve_measurement`]
+    /// reservation. Capture ends once that much has arrived, so a slow start
+    /// never shortens the take.
+    pub fn measure(
+        &self,
+        mut reservation: RecordingReservation,
+        length: Duration,
+        resampler_quality: ResamplerQuality,
+    ) -> Result<RecordedAudio, RecordingError> {
+        let id = reservation.id;
+        reservation.capture.length = Some(length);
+        self.start(reservation, None, resampler_quality)?;
+        let session = self
+            .session
+            .lock_recover()
+            .take_if(|session| session.id == id)
+            .ok_or(RecordingError::NotRecording)?;
+        self.finish(session)
+    }
+
+    fn finish(&self, session: RecordingSession) -> Result<RecordedAudio, RecordingError> {
         let RecordingSession {
+            id,
             cmd_tx,
             worker_handle,
-            activity_guard,
+            slot,
         } = session;
-        let _ = cmd_tx.send(AudioCmd::Stop);
-        match worker_handle.join() {
-            Ok(Ok(())) => {}
+        // A measurement's capture ends itself; a network feed that closed
+        // early only waits for this sender to go.
+        drop(cmd_tx);
+        let mut health = match worker_handle.join() {
+            Ok(Ok(health)) => health,
             Ok(Err(err)) => return Err(err),
             Err(_) => return Err(RecordingError::ThreadError),
-        }
+        };
 
-        let overrun_count = self.overrun_count.swap(0, Ordering::Relaxed);
-        if overrun_count > 0 {
-            return Err(RecordingError::AudioOverrun(overrun_count));
+        health.overrun_samples = self.overrun_count.swap(0, Ordering::Relaxed);
+        *self.last_health.lock_recover() = Some(health.clone());
+        if health.overrun_samples > 0 {
+            return Err(RecordingError::AudioOverrun(health.overrun_samples));
         }
 
-        let mut samples_guard = self
-            .processed_samples
-            .lock()
-            .map_err(|_| RecordingError::LockFailed)?;
-        let samples = mem::take(&mut *samples_guard);
+        let samples = mem::take(&mut *self.processed_samples.lock_recover());
 
         log::info!(
-            "Recorder stopped. Total samples captured: {}",
+            "Recording session {id} stopped. Total samples captured: {}",
             samples.len()
         );
 
         if samples.is_empty() {
-            log::warn!("Recorder error: 0 samples in buffer.");
+            log::warn!("Recording session {id}: 0 samples in buffer.");
             return Err(RecordingError::NoAudioCaptured);
         }
+        *self.last_quality.lock_recover() =
+            Some(analyze_capture(id, &samples, health.device_sample_rate));
 
         Ok(RecordedAudio {
             samples,
-            _activity_guard: activity_guard,
+            health,
+            _slot: slot,
         })
     }
 }
@@ -0,0 +1,95 @@
+use std::sync::{
+    mpsc::{Receiver, RecvTimeoutError, Sender},
+    Arc, Mutex,
+};
+use std::time::{Duration, Instant};
+
+use crate::asr::TARGET_SAMPLE_RATE;
+use crate::audio_processing::{AudioFrame, AudioProcessor};
+use crate::sync::MutexRecover;
+
+use super::vad_monitor::VadTap;
+use super::waveform::WaveformTap;
+use super::{forward_to_stream, AudioCmd, AudioHealth, CaptureOptions, RecordingError};
+
+const POLL_INTERVAL: Duration = Duration::from_millis(5);
+
+/// Runs the capture loop over samples already decoded from a network stream
+/// as mono at the target rate, so they pass straight to the shared output.
+/// Only the detector, waveform, and length of `capture` apply.
+pub(super) fn run_network_audio_thread(
+    cmd_rx: Receiver<AudioCmd>,
+    processed_samples: Arc<Mutex<Vec<f32>>>,
+    init_tx: Sender<Result<(), ()>>,
+    streaming_tx: Option<Sender<AudioFrame>>,
+    audio_rx: Receiver<Vec<f32>>,
+    capture: CaptureOptions,
+) -> Result<AudioHealth, RecordingError> {
+    let CaptureOptions {
+        voice_activity,
+        waveform,
+        length,
+        ..
+    } = capture;
+    let mut samples_left =
+        length.map(|length| (length.as_secs_f64() * f64::from(TARGET_SAMPLE_RATE)).ceil() as usize);
+    let deadline = length.map(|length| Instant::now() + length * 2);
+    let mut processor = AudioProcessor::bypass();
+    log::info!("Audio: {TARGET_SAMPLE_RATE} Hz network input");
+    let _ = init_tx.send(Ok(()));
+
+    let mut processed_local = Vec::new();
+    let mut dropped_streaming_samples = 0;
+    let mut vad = VadTap::new(voice_activity.config());
+    let mut waveform = WaveformTap::new(waveform);
+    let mut dispatch = |frame: AudioFrame| {
+        vad.observe(&frame.samples);
+        waveform.observe(&frame.samples);
+        processed_local.extend_from_slice(&frame.samples);
+        dropped_streaming_samples += forward_to_stream(&streaming_tx, frame);
+    };
+
+    loop {
+        let captured =
+            samples_left == Some(0) || deadline.is_some_and(|deadline| Instant::now() >= deadline);
+        if captured || matches!(cmd_rx.try_recv(), Ok(AudioCmd::Stop)) {
+            break;
+        }
+        match audio_rx.recv_timeout(POLL_INTERVAL) {
+            Ok(samples) => {
+                if let Some(samples_left) = &mut samples_left {
+                    *samples_left = samples_left.saturating_sub(samples.len());
+                }
+                processor
+                    .process(&samples, &mut dispatch)
+                    .map_err(|e| RecordingError::AudioProcessingError(e.to_string()))?
+            }
+            Err(RecvTimeoutError::Timeout) => {}
+            Err(RecvTimeoutError::Disconnected) => {
+                // The connection has ended; wait for the dictation to be stopped.
+                let _ = cmd_rx.recv();
+                break;
+            }
+        }
+    }
+    // Frames received before the stop belong to this recording.
+    for samples in audio_rx.try_iter() {
+        processor
+            .process(&samples, &mut dispatch)
+            .map_err(|e| RecordingError::AudioProcessingError(e.to_string()))?;
+    }
+    processor
+        .flush(&mut dispatch)
+        .map_err(|e| RecordingError::AudioProcessingError(e.to_string()))?;
+    vad.finish();
+
+    *processed_samples.lock_recover() = processed_local;
+
+    Ok(AudioHealth {
+        device_sample_rate: TARGET_SAMPLE_RATE,
+        resampling: false,
+        overrun_samples: 0,
+        dropped_streaming_samples,
+        processing_chain: Vec::new(),
+    })
+}
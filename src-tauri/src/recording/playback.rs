@@ -0,0 +1,130 @@
+use std::sync::{
+    atomic::{AtomicBool, Ordering},
+    Arc, Mutex,
+};
+use std::thread;
+use std::time::{Duration, Instant};
+
+use cpal::traits::{DeviceTrait, HostTrait, StreamTrait};
+use cpal::{FromSample, Sample, SizedSample};
+
+use crate::audio_processing::{AudioProcessor, ResamplerQuality};
+use crate::sync::MutexRecover;
+
+use super::{app_playback, RecordingError};
+
+/// Extra wait past the clip's length for the output buffer to drain.
+const DRAIN_MARGIN: Duration = Duration::from_millis(250);
+const POLL_INTERVAL: Duration = Duration::from_millis(20);
+
+/// Plays mono `samples` at `sample_rate` on the default output device,
+/// returning once they have been heard. The microphone is muted meanwhile.
+pub fn play_samples(samples: &[f32], sample_rate: u32) -> Result<(), RecordingError> {
+    let host = cpal::default_host();
+    let device = host
+        .default_output_device()
+        .ok_or(RecordingError::NoOutputDevice)?;
+    let config = device
+        .default_output_config()
+        .map_err(|error| RecordingError::Playback(format!("output config: {error}")))?;
+    let sample_format = config.sample_format();
+    let output_rate = config.sample_rate();
+    let channels = config.channels() as usize;
+    let stream_config: cpal::StreamConfig = config.into();
+
+    let mut resampled =
+        Vec::with_capacity(samples.len() * output_rate as usize / sample_rate.max(1) as usize + 1);
+    let mut processor = AudioProcessor::with_quality(
+        sample_rate as usize,
+        output_rate as usize,
+        ResamplerQuality::Fast,
+    )
+    .map_err(|error| RecordingError::AudioProcessingError(error.to_string()))?;
+    processor
+        .process(samples, |frame| resampled.extend_from_slice(&frame.samples))
+        .and_then(|()| processor.flush(|frame| resampled.extend_from_slice(&frame.samples)))
+        .map_err(|error| RecordingError::AudioProcessingError(error.to_string()))?;
+
+    let length = Duration::from_secs_f64(resampled.len() as f64 / f64::from(output_rate.max(1)));
+    let finished = Arc::new(AtomicBool::new(false));
+    let failed = Arc::new(Mutex::new(None));
+    let source = Source {
+        samples: resampled,
+        position: 0,
+        channels,
+        finished: finished.clone(),
+    };
+    let stream_failed = failed.clone();
+    let err_fn = move |error: cpal::Error| {
+        *stream_failed.lock_recover() = Some(error.to_string());
+    };
+
+    let stream = match sample_format {
+        cpal::SampleFormat::F32 => build_stream::<f32>(&device, stream_config, source, err_fn),
+        cpal::SampleFormat::I16 => build_stream::<i16>(&device, stream_config, source, err_fn),
+        cpal::SampleFormat::U16 => build_stream::<u16>(&device, stream_config, source, err_fn),
+        cpal::SampleFormat::I32 => build_stream::<i32>(&device, stream_config, source, err_fn),
+        cpal::SampleFormat::F64 => build_stream::<f64>(&device, stream_config, source, err_fn),
+        other => {
+            log::warn!("Output sample format {other} is not supported");
+            Err(cpal::Error::new(cpal::ErrorKind::UnsupportedConfig))
+        }
+    }
+    .map_err(|error| RecordingError::Playback(format!("build output stream: {error}")))?;
+
+    let _playback = app_playback().begin();
+    stream
+        .play()
+        .map_err(|error| RecordingError::Playback(format!("start output stream: {error}")))?;
+    let deadline = Instant::now() + length + DRAIN_MARGIN;
+    while !finished.load(Ordering::Acquire) && Instant::now() < deadline {
+        if let Some(error) = failed.lock_recover().take() {
+            return Err(RecordingError::Playback(error));
+        }
+        thread::sleep(POLL_INTERVAL);
+    }
+    // The callback runs out of samples before the device has played them.
+    thread::sleep(DRAIN_MARGIN.min(deadline.saturating_duration_since(Instant::now())));
+    Ok(())
+}
+
+/// Mono samples copied to every output channel, then silence.
+struct Source {
+    samples: Vec<f32>,
+    position: usize,
+    channels: usize,
+    finished: Arc<AtomicBool>,
+}
+
+impl Source {
+    fn fill<T>(&mut self, data: &mut [T])
+    where
+        T: Sample + FromSample<f32>,
+    {
+        for frame in data.chunks_mut(self.channels.max(1)) {
+            let sample = self.samples.get(self.position).copied().unwrap_or(0.0);
+            self.position += 1;
+            frame.fill(T::from_sample(sample));
+        }
+        if self.position >= self.samples.len() {
+            self.finished.store(true, Ordering::Release);
+        }
+    }
+}
+
+fn build_stream<T>(
+    device: &cpal::Device,
+    config: cpal::StreamConfig,
+    mut source: Source,
+    err_fn: impl FnMut(cpal::Error) + Send + 'static,
+) -> Result<cpal::Stream, cpal::Error>
+where
+    T: Sample + SizedSample + FromSample<f32> + Send + 'static,
+{
+    device.build_output_stream(
+        config,
+        move |data: &mut [T], _: &_| source.fill(data),
+        err_fn,
+        None,
+    )
+}
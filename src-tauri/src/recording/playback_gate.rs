@@ -0,0 +1,62 @@
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+
+/// How long capture stays muted after playback ends, covering output latency
+/// and room echo.
+pub const PLAYBACK_ECHO_TAIL: Duration = Duration::from_millis(300);
+
+static APP_PLAYBACK: PlaybackGate = PlaybackGate::new(PLAYBACK_ECHO_TAIL);
+
+/// Tracks audio the app itself is playing, such as spoken read-back, so the
+/// recorder can mute the microphone instead of transcribing it.
+pub struct PlaybackGate {
+    active: AtomicUsize,
+    ended_at: Mutex<Option<Instant>>,
+    tail: Duration,
+}
+
+impl PlaybackGate {
+    pub const fn new(tail: Duration) -> Self {
+        Self {
+            active: AtomicUsize::new(0),
+            ended_at: Mutex::new(None),
+            tail,
+        }
+    }
+
+    /// Marks playback as started until the returned guard is dropped.
+    pub fn begin(&self) -> PlaybackGuard<'_> {
+        self.active.fetch_add(1, Ordering::AcqRel);
+        PlaybackGuard { gate: self }
+    }
+
+    pub fn is_muted_at(&self, now: Instant) -> bool {
+        if self.active.load(Ordering::Acquire) > 0 {
+            return true;
+        }
+        self.ended_at
+            .lock()
+            .ok()
+            .and_then(|ended_at| *ended_at)
+            .is_some_and(|ended_at| now.saturating_duration_since(ended_at) < self.tail)
+    }
+}
+
+pub struct PlaybackGuard<'a> {
+    gate: &'a PlaybackGate,
+}
+
+impl Drop for PlaybackGuard<'_> {
+    fn drop(&mut self) {
+        if let Ok(mut ended_at) = self.gate.ended_at.lock() {
+            *ended_at = Some(Instant::now());
+        }
+        self.gate.active.fetch_sub(1, Ordering::AcqRel);
+    }
+}
+
+/// The gate that the microphone recorder consults.
+pub fn app_playback() -> &'static PlaybackGate {
+    &APP_PLAYBACK
+}
@@ -0,0 +1,106 @@
+use std::sync::atomic::{AtomicU64, AtomicUsize, Ordering};
+use std::sync::OnceLock;
+
+use crate::vad::{VadConfig, VadEvent, VadSegmenter};
+
+/// Event carrying each batch of [`VadEvent`]s while subscribed.
+pub const VAD_EVENTS_EVENT: &str = "vad_events";
+
+type VadSink = Box<dyn Fn(&[VadEvent]) + Send + Sync>;
+
+static VAD_MONITOR: VadMonitor = VadMonitor::new();
+
+/// Publishes voice activity for the audio being recorded while anyone is
+/// subscribed. The capture threads run the detector either way; only
+/// delivery depends on subscribers.
+pub struct VadMonitor {
+    subscribers: AtomicUsize,
+    sink: OnceLock<VadSink>,
+    speech_frames: AtomicU64,
+}
+
+impl VadMonitor {
+    pub const fn new() -> Self {
+        Self {
+            subscribers: AtomicUsize::new(0),
+            sink: OnceLock::new(),
+            speech_frames: AtomicU64::new(0),
+        }
+    }
+
+    /// Sets where events go. Only the first sink is kept.
+    pub fn set_sink(&self, sink: impl Fn(&[VadEvent]) + Send + Sync + 'static) -> bool {
+        self.sink.set(Box::new(sink)).is_ok()
+    }
+
+    pub fn subscribe(&self) {
+        self.subscribers.fetch_add(1, Ordering::AcqRel);
+    }
+
+    pub fn unsubscribe(&self) {
+        let _ = self
+            .subscribers
+            .fetch_update(Ordering::AcqRel, Ordering::Acquire, |count| {
+                count.checked_sub(1)
+            });
+    }
+
+    pub fn is_active(&self) -> bool {
+        self.subscribers.load(Ordering::Acquire) > 0 && self.sink.get().is_some()
+    }
+
+    /// Analysis frames judged to be speech since launch, whether or not
+    /// anyone is subscribed. A change between two reads means speech was
+    /// heard in between.
+    pub fn speech_frames(&self) -> u64 {
+        self.speech_frames.load(Ordering::Acquire)
+    }
+
+    pub fn publish(&self, events: &[VadEvent]) {
+        let speech = events
+            .iter()
+            .filter(|event| matches!(event, VadEvent::Frame { speech: true, .. }))
+            .count() as u64;
+        if speech > 0 {
+            self.speech_frames.fetch_add(speech, Ordering::AcqRel);
+        }
+        if events.is_empty() || !self.is_active() {
+            return;
+        }
+        if let Some(sink) = self.sink.get() {
+            sink(events);
+        }
+    }
+}
+
+impl Default for VadMonitor {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// The monitor that the capture threads publish to.
+pub fn vad_monitor() -> &'static VadMonitor {
+    &VAD_MONITOR
+}
+
+/// One recording's detector, fed with the processed 16 kHz frames.
+pub(super) struct VadTap {
+    segmenter: VadSegmenter,
+}
+
+impl VadTap {
+    pub(super) fn new(config: VadConfig) -> Self {
+        Self {
+            segmenter: VadSegmenter::new(config),
+        }
+    }
+
+    pub(super) fn observe(&mut self, samples: &[f32]) {
+        vad_monitor().publish(&self.segmenter.feed(samples));
+    }
+
+    pub(super) fn finish(mut self) {
+        vad_monitor().publish(&self.segmenter.finish());
+    }
+}
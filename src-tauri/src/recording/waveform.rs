@@ -0,0 +1,92 @@
+use std::sync::OnceLock;
+
+use serde::Serialize;
+
+use crate::asr::TARGET_SAMPLE_RATE;
+
+/// Event carrying each [`WaveformFrame`] while the live waveform is enabled.
+pub const WAVEFORM_EVENT: &str = "waveform";
+pub const WAVEFORM_FRAMES_PER_SECOND: u32 = 30;
+pub const WAVEFORM_BUCKETS: usize = 48;
+
+type WaveformSink = Box<dyn Fn(&WaveformFrame) + Send + Sync>;
+
+static WAVEFORM_SINK: OnceLock<WaveformSink> = OnceLock::new();
+
+/// Peak amplitude of consecutive slices of one display frame, oldest first.
+#[derive(Clone, Debug, PartialEq, Serialize)]
+pub struct WaveformFrame {
+    /// End of the frame, from the start of the recording.
+    pub at_ms: u64,
+    pub peaks: Vec<f32>,
+}
+
+/// Reduces 16 kHz audio to fixed-size peak frames at a steady frame rate,
+/// however the capture thread happens to chunk it.
+pub struct WaveformBucketer {
+    frame_samples: usize,
+    peaks: Vec<f32>,
+    filled: usize,
+    consumed: u64,
+}
+
+impl WaveformBucketer {
+    pub fn new(frames_per_second: u32, buckets: usize) -> Self {
+        let buckets = buckets.max(1);
+        let frame_samples = (TARGET_SAMPLE_RATE / frames_per_second.max(1)) as usize;
+        Self {
+            frame_samples: frame_samples.max(buckets),
+            peaks: vec![0.0; buckets],
+            filled: 0,
+            consumed: 0,
+        }
+    }
+
+    pub fn feed(&mut self, samples: &[f32]) -> Vec<WaveformFrame> {
+        let mut frames = Vec::new();
+        let buckets = self.peaks.len();
+        for &sample in samples {
+            let bucket = self.filled * buckets / self.frame_samples;
+            self.peaks[bucket] = self.peaks[bucket].max(sample.abs());
+            self.filled += 1;
+            self.consumed += 1;
+            if self.filled == self.frame_samples {
+                frames.push(WaveformFrame {
+                    at_ms: self.consumed * 1_000 / u64::from(TARGET_SAMPLE_RATE),
+                    peaks: self.peaks.clone(),
+                });
+                self.peaks.fill(0.0);
+                self.filled = 0;
+            }
+        }
+        frames
+    }
+}
+
+/// Sets where waveform frames go. Only the first sink is kept.
+pub fn set_waveform_sink(sink: impl Fn(&WaveformFrame) + Send + Sync + 'static) -> bool {
+    WAVEFORM_SINK.set(Box::new(sink)).is_ok()
+}
+
+/// One recording's waveform, computed only when the setting is on.
+pub(super) struct WaveformTap {
+    bucketer: Option<WaveformBucketer>,
+}
+
+impl WaveformTap {
+    pub(super) fn new(enabled: bool) -> Self {
+        Self {
+            bucketer: (enabled && WAVEFORM_SINK.get().is_some())
+                .then(|| WaveformBucketer::new(WAVEFORM_FRAMES_PER_SECOND, WAVEFORM_BUCKETS)),
+        }
+    }
+
+    pub(super) fn observe(&mut self, samples: &[f32]) {
+        let (Some(bucketer), Some(sink)) = (self.bucketer.as_mut(), WAVEFORM_SINK.get()) else {
+            return;
+        };
+        for frame in bucketer.feed(samples) {
+            sink(&frame);
+        }
+    }
+}
@@ -0,0 +1,91 @@
+use serde::{Deserialize, Serialize};
+
+use crate::time_stretch::TimeStretch;
+
+/// Settings to transcribe a stored recording again with. Anything left unset
+/// uses the app's current setting, so a retry can change one thing at a time.
+#[derive(Clone, Debug, Default, Deserialize, Eq, PartialEq, Serialize)]
+#[serde(default)]
+pub struct RetranscribeConfig {
+    /// The language prompt, for multilingual models.
+    pub language: Option<String>,
+    pub time_stretch: Option<TimeStretch>,
+    /// Keeps the decoder's text as heard, without post-processing stages.
+    pub raw: bool,
+}
+
+/// A word of a comparison between two transcripts.
+#[derive(Clone, Debug, Deserialize, Eq, PartialEq, Serialize)]
+#[serde(tag = "kind", content = "text", rename_all = "snake_case")]
+pub enum DiffWord {
+    Same(String),
+    /// Only in the original.
+    Removed(String),
+    /// Only in the new transcript.
+    Added(String),
+}
+
+/// A stored recording transcribed again, utterance by utterance, next to the
+/// transcript it had.
+#[derive(Clone, Debug, Eq, PartialEq, Serialize)]
+pub struct Retranscription {
+    pub original: Vec<String>,
+    pub retranscribed: Vec<String>,
+    pub diff: Vec<DiffWord>,
+}
+
+impl Retranscription {
+    /// Compares utterance by utterance, which keeps each comparison short
+    /// however long the recording.
+    pub fn new(original: Vec<String>, retranscribed: Vec<String>) -> Self {
+        let empty = String::new();
+        let count = original.len().max(retranscribed.len());
+        let diff = (0..count)
+            .flat_map(|index| {
+                word_diff(
+                    original.get(index).unwrap_or(&empty),
+                    retranscribed.get(index).unwrap_or(&empty),
+                )
+            })
+            .collect();
+        Self {
+            original,
+            retranscribed,
+            diff,
+        }
+    }
+}
+
+/// Word-level differences from `original` to `retranscribed`, by longest
+/// common subsequence. Removals come before additions where words changed.
+pub fn word_diff(original: &str, retranscribed: &str) -> Vec<DiffWord> {
+    let old: Vec<&str> = original.split_whitespace().collect();
+    let new: Vec<&str> = retranscribed.split_whitespace().collect();
+    // common[i][j] is the LCS length of old[i..] and new[j..].
+    let mut common = vec![vec![0_usize; new.len() + 1]; old.len() + 1];
+    for i in (0..old.len()).rev() {
+        for j in (0..new.len()).rev() {
+            common[i][j] = if old[i] == new[j] {
+                common[i + 1][j + 1] + 1
+            } else {
+                common[i + 1][j].max(common[i][j + 1])
+            };
+        }
+    }
+    let (mut i, mut j) = (0, 0);
+    let mut diff = Vec::with_capacity(old.len().max(new.len()));
+    while i < old.len() || j < new.len() {
+        if i < old.len() && j < new.len() && old[i] == new[j] {
+            diff.push(DiffWord::Same(old[i].to_string()));
+            i += 1;
+            j += 1;
+        } else if i < old.len() && (j == new.len() || common[i + 1][j] >= common[i][j + 1]) {
+            diff.push(DiffWord::Removed(old[i].to_string()));
+            i += 1;
+        } else {
+            diff.push(DiffWord::Added(new[j].to_string()));
+            j += 1;
+        }
+    }
+    diff
+}
@@ -3,23 +3,197 @@ use std::path::PathBuf;
 use tauri::AppHandle;
 use tauri_plugin_store::StoreExt;
 
+use crate::asr::OrtThreads;
+use crate::audio_processing::ResamplerQuality;
+use crate::autostart::AutostartSettings;
+use crate::capture_file::CaptureFileSettings;
+use crate::companion::CompanionSettings;
+use crate::continuation::ContinuationSettings;
+use crate::decode_cache::DecodeCacheSettings;
+use crate::hardware::HardwareDetection;
+use crate::inactivity::InactivityTimeout;
+use crate::llm_handoff::LlmHandoffSettings;
+use crate::network_input::NetworkInputSettings;
+use crate::noise_profile::NoiseSuppression;
+use crate::output_template::OutputTemplates;
+use crate::patch_routing::PatchRouting;
+use crate::post_process::PostProcessing;
+use crate::power::PowerSaverMode;
+use crate::profiles::Profiles;
+use crate::quiet_hours::QuietHours;
+use crate::recording::{InputDeviceSettings, LookbackSettings};
+use crate::settings_lock::SettingsLock;
+use crate::shortcut_gesture::ShortcutGestureSettings;
+use crate::speaker_adaptation::SpeakerAdaptation;
+use crate::streaming::StreamingStrategy;
+use crate::target_lock::TargetLock;
+use crate::text_commands::TextCommands;
+use crate::time_stretch::TimeStretch;
+use crate::transcript::PauseMarkers;
+use crate::trigger::TriggerSettings;
+use crate::typed_metadata::TypedMetadataSettings;
+use crate::vad::VadSettings;
+use crate::webhook::WebhookSettings;
+
 mod service;
 mod transaction;
 
-pub(crate) use service::{reset_settings, set_asr_language, set_model_path, set_streaming_enabled};
+pub use service::SettingsServiceError;
+pub(crate) use service::{
+    add_companion_device, create_profile, delete_profile, ensure_settings_unlocked,
+    import_settings, lock_settings, observe_voice, record_hardware_detection,
+    remove_companion_device, remove_settings_lock, reset_settings, reset_voice_profile,
+    set_announce_transcripts, set_asr_language, set_autostart, set_buffer_until_model_ready,
+    set_capture_file, set_caret_spacing, set_companion, set_continuation, set_decode_cache,
+    set_deep_links_enabled, set_dictation_enabled, set_dictation_toggle_shortcut,
+    set_encrypt_at_rest, set_inactivity, set_input_device, set_live_waveform, set_llm_handoff,
+    set_lookback, set_model_path, set_model_prewarm, set_network_input, set_noise_suppression,
+    set_numbers_mode, set_onboarding_completed, set_ort_threads, set_output_templates,
+    set_patch_routing, set_pause_markers, set_post_processing, set_power_saver, set_quiet_hours,
+    set_recording_indicator, set_resampler_quality, set_settings_lock, set_shortcut_gesture,
+    set_speak_transcripts, set_speaker_adaptation, set_streaming_enabled, set_streaming_strategy,
+    set_suppress_record_key, set_text_commands, set_time_stretch, set_triggers, set_typed_metadata,
+    set_typing_pace, set_vad_settings, set_webhook, settings_lock_status, switch_profile,
+    unlock_settings,
+};
 #[doc(hidden)]
 pub use transaction::{
-    reset_settings_transaction, set_asr_language_transaction, EngineReadiness, SettingsAction,
-    SettingsTransactionBackend, TransactionFailure,
+    import_settings_transaction, reset_settings_transaction, set_asr_language_transaction,
+    switch_profile_transaction, EngineReadiness, SettingsAction, SettingsTransactionBackend,
+    TransactionFailure,
 };
 
-pub const DEFAULT_ASR_LANGUAGE: &str = "en-US";
+pub use crate::asr::DEFAULT_ASR_LANGUAGE;
+pub const MAX_TYPING_CHUNK_DELAY_MS: u64 = 1_000;
+pub const MAX_TYPING_CHARS_PER_SEC: u32 = 2_000;
+
+/// How typed output is paced for targets that drop keystrokes, such as remote
+/// desktops. A `chunk_chars` of zero types each commit in one call, and a
+/// `max_chars_per_sec` of zero leaves the rate unlimited.
+#[derive(Clone, Copy, Debug, Deserialize, Eq, PartialEq, Serialize)]
+#[serde(default)]
+pub struct TypingPace {
+    pub chunk_chars: usize,
+    pub chunk_delay_ms: u64,
+    pub max_chars_per_sec: u32,
+    /// Hold output while Shift, Control, Alt, or the platform key is
+    /// physically held, so typed text never picks up the user's modifiers.
+    pub pause_for_modifiers: bool,
+    /// Keep dictated text in the window focused when recording started.
+    pub target_lock: TargetLock,
+}
+
+impl Default for TypingPace {
+    fn default() -> Self {
+        Self {
+            chunk_chars: 0,
+            chunk_delay_ms: 0,
+            max_chars_per_sec: 0,
+            pause_for_modifiers: true,
+            target_lock: TargetLock::Off,
+        }
+    }
+}
+
+impl TypingPace {
+    pub fn clamped(self) -> Self {
+        Self {
+            chunk_delay_ms: self.chunk_delay_ms.min(MAX_TYPING_CHUNK_DELAY_MS),
+            max_chars_per_sec: self.max_chars_per_sec.min(MAX_TYPING_CHARS_PER_SEC),
+            ..self
+        }
+    }
+}
+
+/// When the speech model starts loading. `OnFirstUse` waits for the first
+/// record trigger; `Manual` waits for the load button in the recorder.
+#[derive(Clone, Copy, Debug, Default, Deserialize, Eq, PartialEq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ModelPrewarm {
+    #[default]
+    OnLaunch,
+    OnFirstUse,
+    Manual,
+}
 
-#[derive(Clone, Debug, Deserialize, Eq, PartialEq, Serialize)]
+/// How the tray icon shows a recording in progress. macOS also lights its
+/// own microphone dot in the menu bar, which `Off` leaves as the only sign.
+#[derive(Clone, Copy, Debug, Default, Deserialize, Eq, PartialEq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum RecordingIndicator {
+    #[default]
+    Pulse,
+    Steady,
+    Off,
+}
+
+#[derive(Clone, Debug, Deserialize, PartialEq, Serialize)]
 pub struct Settings {
     pub model_path: Option<String>,
     pub streaming_enabled: bool,
+    pub streaming_strategy: StreamingStrategy,
+    pub shortcut_gesture: ShortcutGestureSettings,
+    pub triggers: TriggerSettings,
+    /// Keeps the record shortcut's key from reaching the focused app. Only
+    /// Windows lets it through otherwise.
+    pub suppress_record_key: bool,
+    pub patch_routing: PatchRouting,
     pub asr_language: String,
+    pub resampler_quality: ResamplerQuality,
+    pub input_device: InputDeviceSettings,
+    pub vad: VadSettings,
+    pub noise_suppression: NoiseSuppression,
+    #[serde(default)]
+    pub speaker_adaptation: SpeakerAdaptation,
+    pub time_stretch: TimeStretch,
+    #[serde(default)]
+    pub decode_cache: DecodeCacheSettings,
+    #[serde(default)]
+    pub lookback: LookbackSettings,
+    pub live_waveform: bool,
+    pub recording_indicator: RecordingIndicator,
+    pub buffer_until_model_ready: bool,
+    pub model_prewarm: ModelPrewarm,
+    pub hardware_detection: Option<HardwareDetection>,
+    pub power_saver: PowerSaverMode,
+    pub typing_pace: TypingPace,
+    pub caret_spacing: bool,
+    pub continuation: ContinuationSettings,
+    pub inactivity: InactivityTimeout,
+    pub pause_markers: PauseMarkers,
+    pub text_commands: TextCommands,
+    pub post_processing: PostProcessing,
+    pub dictation_enabled: bool,
+    #[serde(default)]
+    pub dictation_toggle_shortcut: bool,
+    pub quiet_hours: QuietHours,
+    pub webhook: WebhookSettings,
+    pub llm_handoff: LlmHandoffSettings,
+    #[serde(default)]
+    pub numbers_mode: bool,
+    pub output_templates: OutputTemplates,
+    pub capture_file: CaptureFileSettings,
+    pub announce_transcripts: bool,
+    pub speak_transcripts: bool,
+    pub network_input: NetworkInputSettings,
+    #[serde(default)]
+    pub typed_metadata: TypedMetadataSettings,
+    pub companion: CompanionSettings,
+    pub profiles: Profiles,
+    pub autostart: AutostartSettings,
+    #[serde(default)]
+    pub settings_lock: SettingsLock,
+    #[serde(default)]
+    pub ort_threads: OrtThreads,
+    /// Encrypts history and recordings with a key kept in the OS keychain.
+    #[serde(default)]
+    pub encrypt_at_rest: bool,
+    /// Lets other apps drive recordings through `silentkeys://` links.
+    #[serde(default)]
+    pub deep_links_enabled: bool,
+    /// Set once first-run setup is finished or skipped.
+    #[serde(default)]
+    pub onboarding_completed: bool,
 }
 
 const STORE_PATH: &str = "settings.json";
@@ -37,7 +211,69 @@ impl Default for Settings {
         Self {
             model_path: None,
             streaming_enabled: false,
+            streaming_strategy: StreamingStrategy::default(),
+            shortcut_gesture: ShortcutGestureSettings::default(),
+            triggers: TriggerSettings::default(),
+            suppress_record_key: false,
+            patch_routing: PatchRouting::default(),
             asr_language: DEFAULT_ASR_LANGUAGE.to_string(),
+            resampler_quality: ResamplerQuality::default(),
+            input_device: InputDeviceSettings::default(),
+            vad: VadSettings::default(),
+            noise_suppression: NoiseSuppression::default(),
+            speaker_adaptation: SpeakerAdaptation::default(),
+            time_stretch: TimeStretch::default(),
+            decode_cache: DecodeCacheSettings::default(),
+            lookback: LookbackSettings::default(),
+            live_waveform: false,
+            recording_indicator: RecordingIndicator::default(),
+            buffer_until_model_ready: false,
+            model_prewarm: ModelPrewarm::default(),
+            hardware_detection: None,
+            power_saver: PowerSaverMode::default(),
+            typing_pace: TypingPace::default(),
+            caret_spacing: false,
+            continuation: ContinuationSettings::default(),
+            inactivity: InactivityTimeout::default(),
+            pause_markers: PauseMarkers::default(),
+            text_commands: TextCommands::default(),
+            post_processing: PostProcessing::default(),
+            dictation_enabled: true,
+            dictation_toggle_shortcut: false,
+            quiet_hours: QuietHours::default(),
+            webhook: WebhookSettings::default(),
+            llm_handoff: LlmHandoffSettings::default(),
+            numbers_mode: false,
+            output_templates: OutputTemplates::default(),
+            capture_file: CaptureFileSettings::default(),
+            announce_transcripts: false,
+            speak_transcripts: false,
+            network_input: NetworkInputSettings::default(),
+            typed_metadata: TypedMetadataSettings::default(),
+            companion: CompanionSettings::default(),
+            profiles: Profiles::default(),
+            autostart: AutostartSettings::default(),
+            settings_lock: SettingsLock::default(),
+            ort_threads: OrtThreads::default(),
+            encrypt_at_rest: false,
+            deep_links_enabled: false,
+            onboarding_completed: false,
+        }
+    }
+}
+
+impl Settings {
+    /// Whether the battery saver applies right now.
+    pub fn power_saving(&self) -> bool {
+        self.power_saver.active(crate::power::on_battery)
+    }
+
+    /// The prewarm preference with the battery saver applied: a launch-time
+    /// load waits for first use while saving power.
+    pub fn effective_model_prewarm(&self) -> ModelPrewarm {
+        match self.model_prewarm {
+            ModelPrewarm::OnLaunch if self.power_saving() => ModelPrewarm::OnFirstUse,
+            prewarm => prewarm,
         }
     }
 }
@@ -52,14 +288,259 @@ pub fn get_settings(app: &AppHandle) -> Settings {
                 .get("streaming_enabled")
                 .and_then(|v| v.as_bool())
                 .unwrap_or(false);
+            let streaming_strategy = store
+                .get("streaming_strategy")
+                .and_then(|value| serde_json::from_value(value).ok())
+                .unwrap_or_default();
             let asr_language = store
                 .get("asr_language")
                 .and_then(|value| value.as_str().map(str::to_owned))
                 .unwrap_or_else(|| DEFAULT_ASR_LANGUAGE.to_string());
+            let resampler_quality = store
+                .get("resampler_quality")
+                .and_then(|value| serde_json::from_value(value).ok())
+                .unwrap_or_default();
+            let input_device = store
+                .get("input_device")
+                .and_then(|value| serde_json::from_value::<InputDeviceSettings>(value).ok())
+                .map(InputDeviceSettings::normalized)
+                .unwrap_or_default();
+            let buffer_until_model_ready = store
+                .get("buffer_until_model_ready")
+                .and_then(|v| v.as_bool())
+                .unwrap_or(false);
+            let model_prewarm = store
+                .get("model_prewarm")
+                .and_then(|value| serde_json::from_value(value).ok())
+                .unwrap_or_default();
+            let hardware_detection = store
+                .get("hardware_detection")
+                .and_then(|value| serde_json::from_value(value).ok());
+            let power_saver = store
+                .get("power_saver")
+                .and_then(|value| serde_json::from_value(value).ok())
+                .unwrap_or_default();
+            let recording_indicator = store
+                .get("recording_indicator")
+                .and_then(|value| serde_json::from_value(value).ok())
+                .unwrap_or_default();
+            let shortcut_gesture = store
+                .get("shortcut_gesture")
+                .and_then(|value| serde_json::from_value::<ShortcutGestureSettings>(value).ok())
+                .map(ShortcutGestureSettings::clamped)
+                .unwrap_or_default();
+            let triggers = store
+                .get("triggers")
+                .and_then(|value| serde_json::from_value::<TriggerSettings>(value).ok())
+                .map(TriggerSettings::normalized)
+                .unwrap_or_default();
+            let suppress_record_key = store
+                .get("suppress_record_key")
+                .and_then(|v| v.as_bool())
+                .unwrap_or(false);
+            let patch_routing = store
+                .get("patch_routing")
+                .and_then(|value| serde_json::from_value::<PatchRouting>(value).ok())
+                .unwrap_or_default();
+            let vad = store
+                .get("vad")
+                .and_then(|value| serde_json::from_value::<VadSettings>(value).ok())
+                .map(VadSettings::clamped)
+                .unwrap_or_default();
+            let noise_suppression = store
+                .get("noise_suppression")
+                .and_then(|value| serde_json::from_value::<NoiseSuppression>(value).ok())
+                .map(NoiseSuppression::normalized)
+                .unwrap_or_default();
+            let speaker_adaptation = store
+                .get("speaker_adaptation")
+                .and_then(|value| serde_json::from_value::<SpeakerAdaptation>(value).ok())
+                .unwrap_or_default();
+            let time_stretch = store
+                .get("time_stretch")
+                .and_then(|value| serde_json::from_value::<TimeStretch>(value).ok())
+                .map(TimeStretch::clamped)
+                .unwrap_or_default();
+            let decode_cache = store
+                .get("decode_cache")
+                .and_then(|value| serde_json::from_value::<DecodeCacheSettings>(value).ok())
+                .map(DecodeCacheSettings::clamped)
+                .unwrap_or_default();
+            let lookback = store
+                .get("lookback")
+                .and_then(|value| serde_json::from_value::<LookbackSettings>(value).ok())
+                .map(LookbackSettings::clamped)
+                .unwrap_or_default();
+            let live_waveform = store
+                .get("live_waveform")
+                .and_then(|v| v.as_bool())
+                .unwrap_or(false);
+            let typing_pace = store
+                .get("typing_pace")
+                .and_then(|value| serde_json::from_value::<TypingPace>(value).ok())
+                .map(TypingPace::clamped)
+                .unwrap_or_default();
+            let caret_spacing = store
+                .get("caret_spacing")
+                .and_then(|v| v.as_bool())
+                .unwrap_or(false);
+            let continuation = store
+                .get("continuation")
+                .and_then(|value| serde_json::from_value::<ContinuationSettings>(value).ok())
+                .map(ContinuationSettings::clamped)
+                .unwrap_or_default();
+            let inactivity = store
+                .get("inactivity")
+                .and_then(|value| serde_json::from_value::<InactivityTimeout>(value).ok())
+                .map(InactivityTimeout::clamped)
+                .unwrap_or_default();
+            let pause_markers = store
+                .get("pause_markers")
+                .and_then(|value| serde_json::from_value::<PauseMarkers>(value).ok())
+                .map(PauseMarkers::clamped)
+                .unwrap_or_default();
+            let text_commands = store
+                .get("text_commands")
+                .and_then(|value| serde_json::from_value::<TextCommands>(value).ok())
+                .unwrap_or_default();
+            let post_processing = store
+                .get("post_processing")
+                .and_then(|value| serde_json::from_value::<PostProcessing>(value).ok())
+                .map(PostProcessing::normalized)
+                .unwrap_or_default();
+            let dictation_enabled = store
+                .get("dictation_enabled")
+                .and_then(|v| v.as_bool())
+                .unwrap_or(true);
+            let dictation_toggle_shortcut = store
+                .get("dictation_toggle_shortcut")
+                .and_then(|v| v.as_bool())
+                .unwrap_or(false);
+            let quiet_hours = store
+                .get("quiet_hours")
+                .and_then(|value| serde_json::from_value::<QuietHours>(value).ok())
+                .map(QuietHours::normalized)
+                .unwrap_or_default();
+            let webhook = store
+                .get("webhook")
+                .and_then(|value| serde_json::from_value::<WebhookSettings>(value).ok())
+                .unwrap_or_default();
+            let llm_handoff = store
+                .get("llm_handoff")
+                .and_then(|value| serde_json::from_value::<LlmHandoffSettings>(value).ok())
+                .unwrap_or_default();
+            let numbers_mode = store
+                .get("numbers_mode")
+                .and_then(|v| v.as_bool())
+                .unwrap_or(false);
+            let output_templates = store
+                .get("output_templates")
+                .and_then(|value| serde_json::from_value::<OutputTemplates>(value).ok())
+                .unwrap_or_default();
+            let capture_file = store
+                .get("capture_file")
+                .and_then(|value| serde_json::from_value::<CaptureFileSettings>(value).ok())
+                .unwrap_or_default();
+            let announce_transcripts = store
+                .get("announce_transcripts")
+                .and_then(|v| v.as_bool())
+                .unwrap_or(false);
+            let speak_transcripts = store
+                .get("speak_transcripts")
+                .and_then(|v| v.as_bool())
+                .unwrap_or(false);
+            let network_input = store
+                .get("network_input")
+                .and_then(|value| serde_json::from_value::<NetworkInputSettings>(value).ok())
+                .unwrap_or_default();
+            let typed_metadata = store
+                .get("typed_metadata")
+                .and_then(|value| serde_json::from_value::<TypedMetadataSettings>(value).ok())
+                .unwrap_or_default();
+            let companion = store
+                .get("companion")
+                .and_then(|value| serde_json::from_value::<CompanionSettings>(value).ok())
+                .unwrap_or_default();
+            let profiles = store
+                .get("profiles")
+                .and_then(|value| serde_json::from_value::<Profiles>(value).ok())
+                .unwrap_or_default();
+            let autostart = store
+                .get("autostart")
+                .and_then(|value| serde_json::from_value::<AutostartSettings>(value).ok())
+                .unwrap_or_default();
+            let settings_lock = store
+                .get("settings_lock")
+                .and_then(|value| serde_json::from_value::<SettingsLock>(value).ok())
+                .unwrap_or_default();
+            let ort_threads = store
+                .get("ort_threads")
+                .and_then(|value| serde_json::from_value::<OrtThreads>(value).ok())
+                .map(OrtThreads::clamped)
+                .unwrap_or_default();
+            let encrypt_at_rest = store
+                .get("encrypt_at_rest")
+                .and_then(|v| v.as_bool())
+                .unwrap_or(false);
+            let deep_links_enabled = store
+                .get("deep_links_enabled")
+                .and_then(|v| v.as_bool())
+                .unwrap_or(false);
+            // Installs from before onboarding existed are already set up.
+            let onboarding_completed = store
+                .get("onboarding_completed")
+                .and_then(|v| v.as_bool())
+                .unwrap_or_else(|| store.has("streaming_enabled"));
             Settings {
                 model_path,
                 streaming_enabled,
+                streaming_strategy,
+                shortcut_gesture,
+                triggers,
+                suppress_record_key,
+                patch_routing,
                 asr_language,
+                resampler_quality,
+                input_device,
+                vad,
+                noise_suppression,
+                speaker_adaptation,
+                time_stretch,
+                decode_cache,
+                lookback,
+                live_waveform,
+                recording_indicator,
+                buffer_until_model_ready,
+                model_prewarm,
+                hardware_detection,
+                power_saver,
+                typing_pace,
+                caret_spacing,
+                continuation,
+                inactivity,
+                pause_markers,
+                text_commands,
+                post_processing,
+                dictation_enabled,
+                dictation_toggle_shortcut,
+                quiet_hours,
+                webhook,
+                llm_handoff,
+                numbers_mode,
+                output_templates,
+                capture_file,
+                announce_transcripts,
+                speak_transcripts,
+                network_input,
+                typed_metadata,
+                companion,
+                profiles,
+                autostart,
+                settings_lock,
+                ort_threads,
+                encrypt_at_rest,
+                deep_links_enabled,
+                onboarding_completed,
             }
         }
         Err(e) => {
@@ -69,6 +550,12 @@ pub fn get_settings(app: &AppHandle) -> Settings {
     }
 }
 
+/// Whether settings have ever been saved; `false` only on a first run.
+pub fn has_saved_settings(app: &AppHandle) -> bool {
+    app.store(STORE_PATH)
+        .is_ok_and(|store| store.has("streaming_enabled"))
+}
+
 pub fn save_settings(app: &AppHandle, settings: &Settings) -> Result<(), SettingsStoreError> {
     let store = app.store(STORE_PATH).map_err(SettingsStoreError::Open)?;
 
@@ -82,7 +569,108 @@ pub fn save_settings(app: &AppHandle, settings: &Settings) -> Result<(), Setting
         "streaming_enabled",
         serde_json::json!(settings.streaming_enabled),
     );
+    store.set(
+        "streaming_strategy",
+        serde_json::json!(settings.streaming_strategy),
+    );
+    store.set(
+        "shortcut_gesture",
+        serde_json::json!(settings.shortcut_gesture),
+    );
+    store.set("triggers", serde_json::json!(settings.triggers));
+    store.set(
+        "suppress_record_key",
+        serde_json::json!(settings.suppress_record_key),
+    );
+    store.set("patch_routing", serde_json::json!(settings.patch_routing));
     store.set("asr_language", serde_json::json!(settings.asr_language));
+    store.set(
+        "resampler_quality",
+        serde_json::json!(settings.resampler_quality),
+    );
+    store.set("input_device", serde_json::json!(settings.input_device));
+    store.set("vad", serde_json::json!(settings.vad));
+    store.set(
+        "noise_suppression",
+        serde_json::json!(settings.noise_suppression),
+    );
+    store.set(
+        "speaker_adaptation",
+        serde_json::json!(settings.speaker_adaptation),
+    );
+    store.set("time_stretch", serde_json::json!(settings.time_stretch));
+    store.set("decode_cache", serde_json::json!(settings.decode_cache));
+    store.set("lookback", serde_json::json!(settings.lookback));
+    store.set("live_waveform", serde_json::json!(settings.live_waveform));
+    store.set(
+        "buffer_until_model_ready",
+        serde_json::json!(settings.buffer_until_model_ready),
+    );
+    store.set("model_prewarm", serde_json::json!(settings.model_prewarm));
+    if let Some(detection) = &settings.hardware_detection {
+        store.set("hardware_detection", serde_json::json!(detection));
+    } else {
+        store.delete("hardware_detection");
+    }
+    store.set("power_saver", serde_json::json!(settings.power_saver));
+    store.set(
+        "recording_indicator",
+        serde_json::json!(settings.recording_indicator),
+    );
+    store.set("typing_pace", serde_json::json!(settings.typing_pace));
+    store.set("caret_spacing", serde_json::json!(settings.caret_spacing));
+    store.set("continuation", serde_json::json!(settings.continuation));
+    store.set("inactivity", serde_json::json!(settings.inactivity));
+    store.set("pause_markers", serde_json::json!(settings.pause_markers));
+    store.set("text_commands", serde_json::json!(settings.text_commands));
+    store.set(
+        "post_processing",
+        serde_json::json!(settings.post_processing),
+    );
+    store.set(
+        "dictation_enabled",
+        serde_json::json!(settings.dictation_enabled),
+    );
+    store.set(
+        "dictation_toggle_shortcut",
+        serde_json::json!(settings.dictation_toggle_shortcut),
+    );
+    store.set("quiet_hours", serde_json::json!(settings.quiet_hours));
+    store.set("webhook", serde_json::json!(settings.webhook));
+    store.set("llm_handoff", serde_json::json!(settings.llm_handoff));
+    store.set("numbers_mode", serde_json::json!(settings.numbers_mode));
+    store.set(
+        "output_templates",
+        serde_json::json!(settings.output_templates),
+    );
+    store.set("capture_file", serde_json::json!(settings.capture_file));
+    store.set(
+        "announce_transcripts",
+        serde_json::json!(settings.announce_transcripts),
+    );
+    store.set(
+        "speak_transcripts",
+        serde_json::json!(settings.speak_transcripts),
+    );
+    store.set("network_input", serde_json::json!(settings.network_input));
+    store.set("typed_metadata", serde_json::json!(settings.typed_metadata));
+    store.set("companion", serde_json::json!(settings.companion));
+    store.set("profiles", serde_json::json!(settings.profiles));
+    store.set("autostart", serde_json::json!(settings.autostart));
+    store.set("settings_lock", serde_json::json!(settings.settings_lock));
+    store.set("ort_threads", serde_json::json!(settings.ort_threads));
+    store.set(
+        "encrypt_at_rest",
+        serde_json::json!(settings.encrypt_at_rest),
+    );
+    store.set(
+        "deep_links_enabled",
+        serde_json::json!(settings.deep_links_enabled),
+    );
+    store.set(
+        "onboarding_completed",
+        serde_json::json!(settings.onboarding_completed),
+    );
 
     log::info!(
         "Saving settings: streaming_enabled={}",
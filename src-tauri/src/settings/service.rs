@@ -1,18 +1,56 @@
+use std::sync::Mutex;
+use std::time::Instant;
+
 use tauri::AppHandle;
 
 use crate::activity::{self, ActivityError, ActivityGuard, AppActivity};
+use crate::asr::OrtThreads;
+use crate::audio_processing::ResamplerQuality;
+use crate::autostart::AutostartSettings;
+use crate::capture_file::CaptureFileSettings;
+use crate::companion::PairedDevice;
+use crate::config_bundle::ImportedConfig;
+use crate::continuation::ContinuationSettings;
+use crate::decode_cache::DecodeCacheSettings;
 #[cfg(desktop)]
 use crate::desktop;
 use crate::engine::{EngineError, EngineState, SpeechEngine};
 use crate::errors::UserFacing;
+use crate::hardware::{self, HardwareDetection};
+use crate::inactivity::InactivityTimeout;
+use crate::llm_handoff::LlmHandoffSettings;
+use crate::network_input::NetworkInputSettings;
+use crate::noise_profile::NoiseSuppression;
+use crate::output_template::OutputTemplates;
+use crate::patch_routing::PatchRouting;
+use crate::post_process::PostProcessing;
+use crate::power::PowerSaverMode;
+use crate::profiles::ProfileError;
+use crate::quiet_hours::QuietHours;
+use crate::recording::{InputDeviceSettings, LookbackSettings};
+use crate::settings_lock::{SettingsLock, SettingsLockError, SettingsLockStatus, UnlockSession};
+use crate::shortcut_gesture::ShortcutGestureSettings;
+use crate::speaker_adaptation::{VoiceProfile, VoiceStats};
+use crate::streaming::StreamingStrategy;
+use crate::sync::MutexRecover;
+use crate::text_commands::TextCommands;
+use crate::time_stretch::TimeStretch;
+use crate::transcript::PauseMarkers;
+use crate::trigger::TriggerSettings;
+use crate::typed_metadata::TypedMetadataSettings;
+use crate::vad::VadSettings;
+use crate::webhook::WebhookSettings;
 
 use super::transaction::{
     self, EngineReadiness, SettingsAction, SettingsTransactionBackend, TransactionFailure,
 };
-use super::{get_settings, save_settings, Settings, SettingsStoreError};
+use super::{
+    get_settings, save_settings, ModelPrewarm, RecordingIndicator, Settings, SettingsStoreError,
+    TypingPace,
+};
 
 #[derive(thiserror::Error, Debug)]
-pub(crate) enum SettingsServiceError {
+pub enum SettingsServiceError {
     #[error(transparent)]
     Activity(#[from] ActivityError),
     #[error("{action}: {source}")]
@@ -28,11 +66,16 @@ pub(crate) enum SettingsServiceError {
         source: EngineError,
     },
     #[cfg(desktop)]
-    #[error("{action}: {detail}")]
+    #[error("{action}: {source}")]
     Shortcut {
         action: SettingsAction,
-        detail: String,
+        #[source]
+        source: desktop::ShortcutError,
     },
+    #[error(transparent)]
+    Profile(#[from] ProfileError),
+    #[error(transparent)]
+    Lock(#[from] SettingsLockError),
     #[error("speech model is still loading")]
     ModelLoading,
     #[error("{action}; original error: {primary}; rollback error: {rollback}")]
@@ -56,13 +99,18 @@ impl UserFacing for SettingsServiceError {
             Self::Activity(ActivityError::Busy(AppActivity::Configuring)) => {
                 "A settings change is already in progress."
             }
+            Self::Activity(ActivityError::Busy(AppActivity::Typing)) => {
+                "Wait for the text being typed to finish before changing settings."
+            }
             Self::Activity(ActivityError::LockFailed) => "Settings are temporarily unavailable.",
             Self::Storage { .. } => "Could not save settings. Please try again.",
             Self::Engine { source, .. } => source.user_message(),
             #[cfg(desktop)]
-            Self::Shortcut { .. } => "Could not update the record shortcut.",
+            Self::Shortcut { source, .. } => source.user_message(),
+            Self::Profile(error) => error.user_message(),
+            Self::Lock(error) => error.user_message(),
             Self::ModelLoading => {
-                "Wait for the speech model to finish loading before resetting settings."
+                "Wait for the speech model to finish loading before resetting or importing settings."
             }
             Self::Rollback { .. } => {
                 "Could not apply settings safely. Please restart the app and try again."
@@ -71,6 +119,70 @@ impl UserFacing for SettingsServiceError {
     }
 }
 
+/// Whether the settings lock is open. Kept in memory, so a restart locks
+/// settings again.
+static UNLOCK_SESSION: Mutex<UnlockSession> = Mutex::new(UnlockSession::new());
+
+pub(crate) fn settings_lock_status(app: &AppHandle) -> SettingsLockStatus {
+    UNLOCK_SESSION
+        .lock_recover()
+        .status(&get_settings(app).settings_lock, Instant::now())
+}
+
+/// Refuses a change while a settings lock is set and not unlocked.
+pub(crate) fn ensure_settings_unlocked(app: &AppHandle) -> Result<(), SettingsServiceError> {
+    UNLOCK_SESSION
+        .lock_recover()
+        .check(&get_settings(app).settings_lock, Instant::now())
+        .map_err(Into::into)
+}
+
+pub(crate) fn unlock_settings(
+    app: &AppHandle,
+    passphrase: &str,
+) -> Result<(), SettingsServiceError> {
+    let lock = get_settings(app).settings_lock;
+    UNLOCK_SESSION
+        .lock_recover()
+        .unlock(&lock, passphrase, Instant::now())?;
+    log::info!("Settings unlocked");
+    Ok(())
+}
+
+pub(crate) fn lock_settings() {
+    UNLOCK_SESSION.lock_recover().relock();
+}
+
+/// Sets or replaces the passphrase. Replacing one needs the lock open; the
+/// new lock starts closed.
+pub(crate) fn set_settings_lock(
+    app: &AppHandle,
+    passphrase: &str,
+) -> Result<(), SettingsServiceError> {
+    ensure_settings_unlocked(app)?;
+    let mut settings = get_settings(app);
+    settings.settings_lock = SettingsLock::new(passphrase)?;
+    persist(app, &settings, SettingsAction::PersistSettingsLock)?;
+    lock_settings();
+    log::info!("Settings lock set");
+    Ok(())
+}
+
+pub(crate) fn remove_settings_lock(
+    app: &AppHandle,
+    passphrase: &str,
+) -> Result<(), SettingsServiceError> {
+    let mut settings = get_settings(app);
+    UNLOCK_SESSION
+        .lock_recover()
+        .unlock(&settings.settings_lock, passphrase, Instant::now())?;
+    settings.settings_lock = SettingsLock::default();
+    persist(app, &settings, SettingsAction::PersistSettingsLock)?;
+    lock_settings();
+    log::info!("Settings lock removed");
+    Ok(())
+}
+
 pub(crate) fn set_model_path(app: &AppHandle, path: String) -> Result<(), SettingsServiceError> {
     let mut settings = get_settings(app);
     settings.model_path = Some(path);
@@ -86,11 +198,478 @@ pub(crate) fn set_streaming_enabled(
     persist(app, &settings, SettingsAction::PersistStreamingPreference)
 }
 
+pub(crate) fn set_streaming_strategy(
+    app: &AppHandle,
+    strategy: StreamingStrategy,
+) -> Result<(), SettingsServiceError> {
+    let mut settings = get_settings(app);
+    settings.streaming_strategy = strategy;
+    persist(app, &settings, SettingsAction::PersistStreamingStrategy)
+}
+
+pub(crate) fn set_resampler_quality(
+    app: &AppHandle,
+    quality: ResamplerQuality,
+) -> Result<(), SettingsServiceError> {
+    let mut settings = get_settings(app);
+    settings.resampler_quality = quality;
+    persist(app, &settings, SettingsAction::PersistResamplerQuality)
+}
+
+pub(crate) fn set_buffer_until_model_ready(
+    app: &AppHandle,
+    enabled: bool,
+) -> Result<(), SettingsServiceError> {
+    let mut settings = get_settings(app);
+    settings.buffer_until_model_ready = enabled;
+    persist(app, &settings, SettingsAction::PersistBufferPreference)
+}
+
+pub(crate) fn set_model_prewarm(
+    app: &AppHandle,
+    prewarm: ModelPrewarm,
+) -> Result<(), SettingsServiceError> {
+    let mut settings = get_settings(app);
+    settings.model_prewarm = prewarm;
+    persist(app, &settings, SettingsAction::PersistModelPrewarm)
+}
+
+pub(crate) fn set_power_saver(
+    app: &AppHandle,
+    mode: PowerSaverMode,
+) -> Result<(), SettingsServiceError> {
+    let mut settings = get_settings(app);
+    settings.power_saver = mode;
+    persist(app, &settings, SettingsAction::PersistPowerSaver)
+}
+
+pub(crate) fn set_recording_indicator(
+    app: &AppHandle,
+    indicator: RecordingIndicator,
+) -> Result<(), SettingsServiceError> {
+    let mut settings = get_settings(app);
+    settings.recording_indicator = indicator;
+    persist(app, &settings, SettingsAction::PersistRecordingIndicator)
+}
+
+/// Probes the hardware and stores the result. With `apply`, the recommended
+/// streaming and resampler defaults replace the current ones.
+pub(crate) fn record_hardware_detection(
+    app: &AppHandle,
+    apply: bool,
+) -> Result<HardwareDetection, SettingsServiceError> {
+    let detection = hardware::detect_hardware();
+    let mut settings = get_settings(app);
+    settings.hardware_detection = Some(detection);
+    if apply {
+        settings.streaming_enabled = detection.recommendation.streaming_enabled;
+        settings.resampler_quality = detection.recommendation.resampler_quality;
+    }
+    persist(app, &settings, SettingsAction::PersistHardwareDetection)?;
+    Ok(detection)
+}
+
+pub(crate) fn set_input_device(
+    app: &AppHandle,
+    input_device: InputDeviceSettings,
+) -> Result<(), SettingsServiceError> {
+    let mut settings = get_settings(app);
+    settings.input_device = input_device.normalized();
+    persist(app, &settings, SettingsAction::PersistInputDevice)
+}
+
+pub(crate) fn set_announce_transcripts(
+    app: &AppHandle,
+    enabled: bool,
+) -> Result<(), SettingsServiceError> {
+    let mut settings = get_settings(app);
+    settings.announce_transcripts = enabled;
+    persist(app, &settings, SettingsAction::PersistAnnouncePreference)
+}
+
+pub(crate) fn set_shortcut_gesture(
+    app: &AppHandle,
+    gesture: ShortcutGestureSettings,
+) -> Result<(), SettingsServiceError> {
+    let mut settings = get_settings(app);
+    settings.shortcut_gesture = gesture.clamped();
+    persist(app, &settings, SettingsAction::PersistShortcutGesture)
+}
+
+pub(crate) fn set_triggers(
+    app: &AppHandle,
+    triggers: TriggerSettings,
+) -> Result<(), SettingsServiceError> {
+    let mut settings = get_settings(app);
+    settings.triggers = triggers.normalized();
+    persist(app, &settings, SettingsAction::PersistTriggers)
+}
+
+pub(crate) fn set_suppress_record_key(
+    app: &AppHandle,
+    enabled: bool,
+) -> Result<(), SettingsServiceError> {
+    let mut settings = get_settings(app);
+    settings.suppress_record_key = enabled;
+    persist(app, &settings, SettingsAction::PersistRecordKeySuppression)
+}
+
+pub(crate) fn set_patch_routing(
+    app: &AppHandle,
+    routing: PatchRouting,
+) -> Result<(), SettingsServiceError> {
+    let mut settings = get_settings(app);
+    settings.patch_routing = routing;
+    persist(app, &settings, SettingsAction::PersistPatchRouting)
+}
+
+pub(crate) fn set_live_waveform(
+    app: &AppHandle,
+    enabled: bool,
+) -> Result<(), SettingsServiceError> {
+    let mut settings = get_settings(app);
+    settings.live_waveform = enabled;
+    persist(app, &settings, SettingsAction::PersistLiveWaveform)
+}
+
+pub(crate) fn set_caret_spacing(
+    app: &AppHandle,
+    enabled: bool,
+) -> Result<(), SettingsServiceError> {
+    let mut settings = get_settings(app);
+    settings.caret_spacing = enabled;
+    persist(app, &settings, SettingsAction::PersistCaretSpacing)
+}
+
+pub(crate) fn set_continuation(
+    app: &AppHandle,
+    continuation: ContinuationSettings,
+) -> Result<(), SettingsServiceError> {
+    let mut settings = get_settings(app);
+    settings.continuation = continuation.clamped();
+    persist(app, &settings, SettingsAction::PersistContinuation)
+}
+
+pub(crate) fn set_inactivity(
+    app: &AppHandle,
+    inactivity: InactivityTimeout,
+) -> Result<(), SettingsServiceError> {
+    let mut settings = get_settings(app);
+    settings.inactivity = inactivity.clamped();
+    persist(app, &settings, SettingsAction::PersistInactivity)
+}
+
+pub(crate) fn set_vad_settings(
+    app: &AppHandle,
+    vad: VadSettings,
+) -> Result<(), SettingsServiceError> {
+    let mut settings = get_settings(app);
+    settings.vad = vad.clamped();
+    persist(app, &settings, SettingsAction::PersistVadSettings)
+}
+
+pub(crate) fn set_noise_suppression(
+    app: &AppHandle,
+    noise_suppression: NoiseSuppression,
+) -> Result<(), SettingsServiceError> {
+    let mut settings = get_settings(app);
+    settings.noise_suppression = noise_suppression.normalized();
+    persist(app, &settings, SettingsAction::PersistNoiseSuppression)
+}
+
+pub(crate) fn set_speaker_adaptation(
+    app: &AppHandle,
+    enabled: bool,
+) -> Result<(), SettingsServiceError> {
+    let mut settings = get_settings(app);
+    settings.speaker_adaptation.enabled = enabled;
+    persist(app, &settings, SettingsAction::PersistSpeakerAdaptation)
+}
+
+/// Forgets the learned voice, as for a new speaker on this profile.
+pub(crate) fn reset_voice_profile(app: &AppHandle) -> Result<(), SettingsServiceError> {
+    let mut settings = get_settings(app);
+    settings.speaker_adaptation.voice = VoiceProfile::default();
+    persist(app, &settings, SettingsAction::PersistSpeakerAdaptation)
+}
+
+/// Adds a dictation's voice to the profile while adaptation is on.
+pub(crate) fn observe_voice(
+    app: &AppHandle,
+    stats: VoiceStats,
+) -> Result<(), SettingsServiceError> {
+    let mut settings = get_settings(app);
+    if !settings.speaker_adaptation.enabled {
+        return Ok(());
+    }
+    settings.speaker_adaptation.voice.observe(stats);
+    persist(app, &settings, SettingsAction::RecordVoiceProfile)
+}
+
+pub(crate) fn set_speak_transcripts(
+    app: &AppHandle,
+    enabled: bool,
+) -> Result<(), SettingsServiceError> {
+    let mut settings = get_settings(app);
+    settings.speak_transcripts = enabled;
+    persist(app, &settings, SettingsAction::PersistSpeechPreference)
+}
+
+pub(crate) fn set_capture_file(
+    app: &AppHandle,
+    capture_file: CaptureFileSettings,
+) -> Result<(), SettingsServiceError> {
+    let mut settings = get_settings(app);
+    settings.capture_file = CaptureFileSettings {
+        path: capture_file.path.trim().to_string(),
+        ..capture_file
+    };
+    persist(app, &settings, SettingsAction::PersistCaptureFile)
+}
+
+pub(crate) fn set_encrypt_at_rest(
+    app: &AppHandle,
+    enabled: bool,
+) -> Result<(), SettingsServiceError> {
+    let mut settings = get_settings(app);
+    settings.encrypt_at_rest = enabled;
+    persist(app, &settings, SettingsAction::PersistEncryptAtRest)
+}
+
+pub(crate) fn set_deep_links_enabled(
+    app: &AppHandle,
+    enabled: bool,
+) -> Result<(), SettingsServiceError> {
+    let mut settings = get_settings(app);
+    settings.deep_links_enabled = enabled;
+    persist(app, &settings, SettingsAction::PersistDeepLinks)
+}
+
+pub(crate) fn set_onboarding_completed(
+    app: &AppHandle,
+    completed: bool,
+) -> Result<(), SettingsServiceError> {
+    let mut settings = get_settings(app);
+    settings.onboarding_completed = completed;
+    persist(app, &settings, SettingsAction::PersistOnboarding)
+}
+
+pub(crate) fn set_dictation_enabled(
+    app: &AppHandle,
+    enabled: bool,
+) -> Result<(), SettingsServiceError> {
+    let mut settings = get_settings(app);
+    settings.dictation_enabled = enabled;
+    persist(app, &settings, SettingsAction::PersistDictationEnabled)
+}
+
+pub(crate) fn set_dictation_toggle_shortcut(
+    app: &AppHandle,
+    enabled: bool,
+) -> Result<(), SettingsServiceError> {
+    let mut settings = get_settings(app);
+    settings.dictation_toggle_shortcut = enabled;
+    persist(
+        app,
+        &settings,
+        SettingsAction::PersistDictationToggleShortcut,
+    )
+}
+
+pub(crate) fn set_llm_handoff(
+    app: &AppHandle,
+    llm_handoff: LlmHandoffSettings,
+) -> Result<(), SettingsServiceError> {
+    let mut settings = get_settings(app);
+    settings.llm_handoff = LlmHandoffSettings {
+        endpoint: llm_handoff.endpoint.trim().to_string(),
+        model: llm_handoff.model.trim().to_string(),
+        ..llm_handoff
+    };
+    persist(app, &settings, SettingsAction::PersistLlmHandoff)
+}
+
+pub(crate) fn set_numbers_mode(app: &AppHandle, enabled: bool) -> Result<(), SettingsServiceError> {
+    let mut settings = get_settings(app);
+    settings.numbers_mode = enabled;
+    persist(app, &settings, SettingsAction::PersistNumbersMode)
+}
+
+pub(crate) fn set_output_templates(
+    app: &AppHandle,
+    output_templates: OutputTemplates,
+) -> Result<(), SettingsServiceError> {
+    let mut settings = get_settings(app);
+    settings.output_templates = output_templates;
+    persist(app, &settings, SettingsAction::PersistOutputTemplates)
+}
+
+pub(crate) fn set_quiet_hours(
+    app: &AppHandle,
+    quiet_hours: QuietHours,
+) -> Result<(), SettingsServiceError> {
+    let mut settings = get_settings(app);
+    settings.quiet_hours = quiet_hours.normalized();
+    persist(app, &settings, SettingsAction::PersistQuietHours)
+}
+
+pub(crate) fn set_network_input(
+    app: &AppHandle,
+    network_input: NetworkInputSettings,
+) -> Result<(), SettingsServiceError> {
+    let mut settings = get_settings(app);
+    settings.network_input = network_input;
+    persist(app, &settings, SettingsAction::PersistNetworkInput)
+}
+
+pub(crate) fn set_typed_metadata(
+    app: &AppHandle,
+    typed_metadata: TypedMetadataSettings,
+) -> Result<(), SettingsServiceError> {
+    let mut settings = get_settings(app);
+    settings.typed_metadata = typed_metadata;
+    persist(app, &settings, SettingsAction::PersistTypedMetadata)
+}
+
+pub(crate) fn set_autostart(
+    app: &AppHandle,
+    autostart: AutostartSettings,
+) -> Result<(), SettingsServiceError> {
+    let mut settings = get_settings(app);
+    settings.autostart = autostart;
+    persist(app, &settings, SettingsAction::PersistAutostart)
+}
+
+/// Changes the companion listener while keeping its paired devices.
+pub(crate) fn set_companion(
+    app: &AppHandle,
+    enabled: bool,
+    port: u16,
+) -> Result<(), SettingsServiceError> {
+    let mut settings = get_settings(app);
+    settings.companion.enabled = enabled;
+    settings.companion.port = port;
+    persist(app, &settings, SettingsAction::PersistCompanion)
+}
+
+pub(crate) fn add_companion_device(
+    app: &AppHandle,
+    device: PairedDevice,
+) -> Result<(), SettingsServiceError> {
+    let mut settings = get_settings(app);
+    settings.companion.devices.push(device);
+    persist(app, &settings, SettingsAction::PairCompanionDevice)
+}
+
+pub(crate) fn remove_companion_device(
+    app: &AppHandle,
+    device_id: &str,
+) -> Result<(), SettingsServiceError> {
+    let mut settings = get_settings(app);
+    settings
+        .companion
+        .devices
+        .retain(|device| device.id != device_id);
+    persist(app, &settings, SettingsAction::RevokeCompanionDevice)
+}
+
+pub(crate) fn set_webhook(
+    app: &AppHandle,
+    webhook: WebhookSettings,
+) -> Result<(), SettingsServiceError> {
+    let mut settings = get_settings(app);
+    settings.webhook = WebhookSettings {
+        url: webhook.url.trim().to_string(),
+        ..webhook
+    };
+    persist(app, &settings, SettingsAction::PersistWebhook)
+}
+
+pub(crate) fn set_typing_pace(
+    app: &AppHandle,
+    pace: TypingPace,
+) -> Result<(), SettingsServiceError> {
+    let mut settings = get_settings(app);
+    settings.typing_pace = pace.clamped();
+    persist(app, &settings, SettingsAction::PersistTypingPace)
+}
+
+/// Returns whether the thread counts changed; new counts apply once the
+/// model reloads.
+pub(crate) fn set_ort_threads(
+    app: &AppHandle,
+    threads: OrtThreads,
+) -> Result<bool, SettingsServiceError> {
+    let mut settings = get_settings(app);
+    let threads = threads.clamped();
+    if settings.ort_threads == threads {
+        return Ok(false);
+    }
+    settings.ort_threads = threads;
+    persist(app, &settings, SettingsAction::PersistOrtThreads)?;
+    Ok(true)
+}
+
+pub(crate) fn set_time_stretch(
+    app: &AppHandle,
+    time_stretch: TimeStretch,
+) -> Result<(), SettingsServiceError> {
+    let mut settings = get_settings(app);
+    settings.time_stretch = time_stretch.clamped();
+    persist(app, &settings, SettingsAction::PersistTimeStretch)
+}
+
+pub(crate) fn set_decode_cache(
+    app: &AppHandle,
+    decode_cache: DecodeCacheSettings,
+) -> Result<(), SettingsServiceError> {
+    let mut settings = get_settings(app);
+    settings.decode_cache = decode_cache.clamped();
+    persist(app, &settings, SettingsAction::PersistDecodeCache)
+}
+
+pub(crate) fn set_lookback(
+    app: &AppHandle,
+    lookback: LookbackSettings,
+) -> Result<(), SettingsServiceError> {
+    let mut settings = get_settings(app);
+    settings.lookback = lookback.clamped();
+    persist(app, &settings, SettingsAction::PersistLookback)
+}
+
+pub(crate) fn set_pause_markers(
+    app: &AppHandle,
+    pause_markers: PauseMarkers,
+) -> Result<(), SettingsServiceError> {
+    let mut settings = get_settings(app);
+    settings.pause_markers = pause_markers.clamped();
+    persist(app, &settings, SettingsAction::PersistPauseMarkers)
+}
+
+pub(crate) fn set_text_commands(
+    app: &AppHandle,
+    text_commands: TextCommands,
+) -> Result<(), SettingsServiceError> {
+    let mut settings = get_settings(app);
+    settings.text_commands = text_commands;
+    persist(app, &settings, SettingsAction::PersistTextCommands)
+}
+
+pub(crate) fn set_post_processing(
+    app: &AppHandle,
+    post_processing: PostProcessing,
+) -> Result<(), SettingsServiceError> {
+    let mut settings = get_settings(app);
+    settings.post_processing = post_processing.normalized();
+    persist(app, &settings, SettingsAction::PersistPostProcessing)
+}
+
 pub(crate) fn set_asr_language(
     app: &AppHandle,
     engine: &SpeechEngine,
     language: String,
 ) -> Result<(), SettingsServiceError> {
+    ensure_settings_unlocked(app)?;
     let _activity = reserve_change()?;
     let mut backend = AppSettingsBackend { app, engine };
     transaction::set_asr_language_transaction(&mut backend, &language)
@@ -101,12 +680,54 @@ pub(crate) fn reset_settings(
     app: &AppHandle,
     engine: &SpeechEngine,
 ) -> Result<(), SettingsServiceError> {
+    ensure_settings_unlocked(app)?;
     let _activity = reserve_change()?;
     let mut backend = AppSettingsBackend { app, engine };
     transaction::reset_settings_transaction(&mut backend)
         .map_err(|failure| transaction_error(SettingsAction::SettingsReset, failure))
 }
 
+pub(crate) fn import_settings(
+    app: &AppHandle,
+    engine: &SpeechEngine,
+    imported: &ImportedConfig,
+) -> Result<(), SettingsServiceError> {
+    ensure_settings_unlocked(app)?;
+    let _activity = reserve_change()?;
+    let mut backend = AppSettingsBackend { app, engine };
+    transaction::import_settings_transaction(
+        &mut backend,
+        &imported.settings,
+        imported.record_shortcut.as_deref(),
+    )
+    .map_err(|failure| transaction_error(SettingsAction::SettingsImport, failure))
+}
+
+pub(crate) fn create_profile(app: &AppHandle, name: &str) -> Result<(), SettingsServiceError> {
+    let mut settings = get_settings(app);
+    let current = settings.clone();
+    settings.profiles.create(name, &current)?;
+    persist(app, &settings, SettingsAction::PersistProfiles)
+}
+
+pub(crate) fn delete_profile(app: &AppHandle, name: &str) -> Result<(), SettingsServiceError> {
+    let mut settings = get_settings(app);
+    settings.profiles.delete(name)?;
+    persist(app, &settings, SettingsAction::PersistProfiles)
+}
+
+pub(crate) fn switch_profile(
+    app: &AppHandle,
+    engine: &SpeechEngine,
+    name: &str,
+) -> Result<(), SettingsServiceError> {
+    let _activity = reserve_change()?;
+    let next = crate::profiles::switch(&get_settings(app), name)?;
+    let mut backend = AppSettingsBackend { app, engine };
+    transaction::switch_profile_transaction(&mut backend, &next)
+        .map_err(|failure| transaction_error(SettingsAction::ProfileSwitch, failure))
+}
+
 struct AppSettingsBackend<'a> {
     app: &'a AppHandle,
     engine: &'a SpeechEngine,
@@ -187,7 +808,7 @@ impl SettingsTransactionBackend for AppSettingsBackend<'_> {
         {
             desktop::update_record_shortcut(self.app.clone(), shortcut.to_string())
                 .map(|_| ())
-                .map_err(|detail| SettingsServiceError::Shortcut { action, detail })
+                .map_err(|source| SettingsServiceError::Shortcut { action, source })
         }
         #[cfg(not(desktop))]
         {
@@ -206,9 +827,30 @@ fn persist(
     settings: &Settings,
     action: SettingsAction,
 ) -> Result<(), SettingsServiceError> {
+    if guarded_by_settings_lock(action) {
+        ensure_settings_unlocked(app)?;
+    }
     save_settings(app, settings).map_err(|source| SettingsServiceError::Storage { action, source })
 }
 
+/// Everything the user configures is locked. Switching between the profiles
+/// already set up, turning dictation off and on, recording detected hardware,
+/// saving the learned voice profile, finishing first-run setup, and undoing a
+/// failed change stay available; the lock guards its own changes with the
+/// passphrase.
+fn guarded_by_settings_lock(action: SettingsAction) -> bool {
+    !matches!(
+        action,
+        SettingsAction::PersistHardwareDetection
+            | SettingsAction::PersistOnboarding
+            | SettingsAction::RecordVoiceProfile
+            | SettingsAction::PersistDictationEnabled
+            | SettingsAction::PersistProfileSettings
+            | SettingsAction::RestoreSettings
+            | SettingsAction::PersistSettingsLock
+    )
+}
+
 fn transaction_error(
     action: SettingsAction,
     failure: TransactionFailure<SettingsServiceError>,
@@ -13,6 +13,54 @@ pub enum EngineReadiness {
 pub enum SettingsAction {
     PersistModelPath,
     PersistStreamingPreference,
+    PersistStreamingStrategy,
+    PersistResamplerQuality,
+    PersistBufferPreference,
+    PersistModelPrewarm,
+    PersistHardwareDetection,
+    PersistPowerSaver,
+    PersistRecordingIndicator,
+    PersistTypingPace,
+    PersistOrtThreads,
+    PersistTimeStretch,
+    PersistDecodeCache,
+    PersistLookback,
+    PersistPauseMarkers,
+    PersistTextCommands,
+    PersistPostProcessing,
+    PersistDictationEnabled,
+    PersistDictationToggleShortcut,
+    PersistQuietHours,
+    PersistWebhook,
+    PersistLlmHandoff,
+    PersistNumbersMode,
+    PersistOutputTemplates,
+    PersistCaptureFile,
+    PersistEncryptAtRest,
+    PersistDeepLinks,
+    PersistOnboarding,
+    PersistInputDevice,
+    PersistVadSettings,
+    PersistNoiseSuppression,
+    PersistSpeakerAdaptation,
+    RecordVoiceProfile,
+    PersistLiveWaveform,
+    PersistShortcutGesture,
+    PersistTriggers,
+    PersistRecordKeySuppression,
+    PersistPatchRouting,
+    PersistAnnouncePreference,
+    PersistCaretSpacing,
+    PersistContinuation,
+    PersistInactivity,
+    PersistSpeechPreference,
+    PersistNetworkInput,
+    PersistTypedMetadata,
+    PersistCompanion,
+    PersistAutostart,
+    PersistSettingsLock,
+    PairCompanionDevice,
+    RevokeCompanionDevice,
     ValidateSpeechLanguage,
     PersistSpeechLanguage,
     ApplySpeechLanguage,
@@ -21,10 +69,20 @@ pub enum SettingsAction {
     ResetRecordShortcut,
     PersistDefaultSettings,
     ApplyDefaultSpeechLanguage,
+    ValidateImportedSpeechLanguage,
+    ImportRecordShortcut,
+    PersistImportedSettings,
+    ApplyImportedSpeechLanguage,
+    ValidateProfileSpeechLanguage,
+    PersistProfileSettings,
+    ApplyProfileSpeechLanguage,
+    PersistProfiles,
     RestoreSettings,
     RestoreRecordShortcut,
     SpeechLanguageUpdate,
     SettingsReset,
+    SettingsImport,
+    ProfileSwitch,
 }
 
 impl fmt::Display for SettingsAction {
@@ -32,6 +90,54 @@ impl fmt::Display for SettingsAction {
         let description = match self {
             Self::PersistModelPath => "persist model path",
             Self::PersistStreamingPreference => "persist streaming preference",
+            Self::PersistStreamingStrategy => "persist streaming strategy",
+            Self::PersistResamplerQuality => "persist resampler quality",
+            Self::PersistBufferPreference => "persist model-loading buffer preference",
+            Self::PersistModelPrewarm => "persist model prewarm preference",
+            Self::PersistHardwareDetection => "persist hardware detection",
+            Self::PersistPowerSaver => "persist battery saver mode",
+            Self::PersistRecordingIndicator => "persist recording indicator",
+            Self::PersistTypingPace => "persist typing pace",
+            Self::PersistOrtThreads => "persist speech model threads",
+            Self::PersistTimeStretch => "persist time stretch",
+            Self::PersistDecodeCache => "persist decode cache",
+            Self::PersistLookback => "persist lookback listening",
+            Self::PersistPauseMarkers => "persist pause markers",
+            Self::PersistTextCommands => "persist spoken text commands",
+            Self::PersistPostProcessing => "persist post-processing stages",
+            Self::PersistDictationEnabled => "persist dictation enabled state",
+            Self::PersistDictationToggleShortcut => "persist dictation toggle shortcut",
+            Self::PersistQuietHours => "persist quiet hours",
+            Self::PersistWebhook => "persist webhook",
+            Self::PersistLlmHandoff => "persist LLM handoff",
+            Self::PersistNumbersMode => "persist numbers mode",
+            Self::PersistOutputTemplates => "persist output templates",
+            Self::PersistCaptureFile => "persist capture file",
+            Self::PersistEncryptAtRest => "persist at-rest encryption",
+            Self::PersistDeepLinks => "persist deep links",
+            Self::PersistOnboarding => "persist onboarding progress",
+            Self::PersistInputDevice => "persist input device",
+            Self::PersistVadSettings => "persist voice detection settings",
+            Self::PersistNoiseSuppression => "persist noise profiles",
+            Self::PersistSpeakerAdaptation => "persist speaker adaptation",
+            Self::RecordVoiceProfile => "record learned voice profile",
+            Self::PersistLiveWaveform => "persist live waveform preference",
+            Self::PersistShortcutGesture => "persist shortcut gesture",
+            Self::PersistTriggers => "persist record triggers",
+            Self::PersistRecordKeySuppression => "persist record key suppression",
+            Self::PersistPatchRouting => "persist transcript routing",
+            Self::PersistAnnouncePreference => "persist transcript announcement preference",
+            Self::PersistCaretSpacing => "persist caret-aware spacing preference",
+            Self::PersistContinuation => "persist sentence continuation settings",
+            Self::PersistInactivity => "persist inactivity timeout",
+            Self::PersistSpeechPreference => "persist spoken read-back preference",
+            Self::PersistNetworkInput => "persist network audio input",
+            Self::PersistTypedMetadata => "persist editor metadata publishing",
+            Self::PersistCompanion => "persist companion listener",
+            Self::PersistAutostart => "persist start at login",
+            Self::PersistSettingsLock => "persist settings lock",
+            Self::PairCompanionDevice => "pair companion device",
+            Self::RevokeCompanionDevice => "revoke companion device",
             Self::ValidateSpeechLanguage => "validate speech language",
             Self::PersistSpeechLanguage => "persist speech language",
             Self::ApplySpeechLanguage => "apply speech language",
@@ -40,10 +146,20 @@ impl fmt::Display for SettingsAction {
             Self::ResetRecordShortcut => "reset record shortcut",
             Self::PersistDefaultSettings => "persist default settings",
             Self::ApplyDefaultSpeechLanguage => "apply default speech language",
+            Self::ValidateImportedSpeechLanguage => "validate imported speech language",
+            Self::ImportRecordShortcut => "import record shortcut",
+            Self::PersistImportedSettings => "persist imported settings",
+            Self::ApplyImportedSpeechLanguage => "apply imported speech language",
+            Self::ValidateProfileSpeechLanguage => "validate profile speech language",
+            Self::PersistProfileSettings => "persist profile settings",
+            Self::ApplyProfileSpeechLanguage => "apply profile speech language",
+            Self::PersistProfiles => "persist profiles",
             Self::RestoreSettings => "restore settings",
             Self::RestoreRecordShortcut => "restore record shortcut",
             Self::SpeechLanguageUpdate => "speech language update",
             Self::SettingsReset => "settings reset",
+            Self::SettingsImport => "settings import",
+            Self::ProfileSwitch => "profile switch",
         };
         formatter.write_str(description)
     }
@@ -105,6 +221,70 @@ pub fn set_asr_language_transaction<B: SettingsTransactionBackend>(
 
 pub fn reset_settings_transaction<B: SettingsTransactionBackend>(
     backend: &mut B,
+) -> Result<(), TransactionFailure<B::Error>> {
+    let shortcut = backend.default_shortcut();
+    replace_settings(
+        backend,
+        &Settings::default(),
+        shortcut
+            .as_deref()
+            .map(|shortcut| (shortcut, SettingsAction::ResetRecordShortcut)),
+        ReplaceActions {
+            validate_language: SettingsAction::ValidateDefaultSpeechLanguage,
+            persist: SettingsAction::PersistDefaultSettings,
+            apply_language: SettingsAction::ApplyDefaultSpeechLanguage,
+        },
+    )
+}
+
+/// Replaces every setting with an imported configuration. Without a
+/// `shortcut` the current record shortcut is kept.
+pub fn import_settings_transaction<B: SettingsTransactionBackend>(
+    backend: &mut B,
+    settings: &Settings,
+    shortcut: Option<&str>,
+) -> Result<(), TransactionFailure<B::Error>> {
+    replace_settings(
+        backend,
+        settings,
+        shortcut.map(|shortcut| (shortcut, SettingsAction::ImportRecordShortcut)),
+        ReplaceActions {
+            validate_language: SettingsAction::ValidateImportedSpeechLanguage,
+            persist: SettingsAction::PersistImportedSettings,
+            apply_language: SettingsAction::ApplyImportedSpeechLanguage,
+        },
+    )
+}
+
+/// Replaces the settings with those of a newly chosen profile, reconfiguring
+/// the speech engine when the profile uses another language.
+pub fn switch_profile_transaction<B: SettingsTransactionBackend>(
+    backend: &mut B,
+    settings: &Settings,
+) -> Result<(), TransactionFailure<B::Error>> {
+    replace_settings(
+        backend,
+        settings,
+        None,
+        ReplaceActions {
+            validate_language: SettingsAction::ValidateProfileSpeechLanguage,
+            persist: SettingsAction::PersistProfileSettings,
+            apply_language: SettingsAction::ApplyProfileSpeechLanguage,
+        },
+    )
+}
+
+struct ReplaceActions {
+    validate_language: SettingsAction,
+    persist: SettingsAction,
+    apply_language: SettingsAction,
+}
+
+fn replace_settings<B: SettingsTransactionBackend>(
+    backend: &mut B,
+    settings: &Settings,
+    shortcut: Option<(&str, SettingsAction)>,
+    actions: ReplaceActions,
 ) -> Result<(), TransactionFailure<B::Error>> {
     let readiness = backend.engine_readiness();
     if readiness == EngineReadiness::Loading {
@@ -112,21 +292,16 @@ pub fn reset_settings_transaction<B: SettingsTransactionBackend>(
     }
 
     let previous_settings = backend.current_settings();
-    let defaults = Settings::default();
     if readiness == EngineReadiness::Ready {
-        operation(backend.validate_language(
-            &defaults.asr_language,
-            SettingsAction::ValidateDefaultSpeechLanguage,
-        ))?;
+        operation(backend.validate_language(&settings.asr_language, actions.validate_language))?;
     }
 
     let previous_shortcut = backend.current_shortcut();
-    if let Some(default_shortcut) = backend.default_shortcut() {
-        operation(backend.update_shortcut(&default_shortcut, SettingsAction::ResetRecordShortcut))?;
+    if let Some((shortcut, action)) = shortcut {
+        operation(backend.update_shortcut(shortcut, action))?;
     }
 
-    let persist =
-        operation(backend.persist_settings(&defaults, SettingsAction::PersistDefaultSettings));
+    let persist = operation(backend.persist_settings(settings, actions.persist));
     rollback_on_failure(persist, || {
         restore_shortcut(backend, previous_shortcut.as_deref())
     })?;
@@ -135,10 +310,7 @@ pub fn reset_settings_transaction<B: SettingsTransactionBackend>(
         return Ok(());
     }
 
-    let apply = operation(backend.apply_language(
-        &defaults.asr_language,
-        SettingsAction::ApplyDefaultSpeechLanguage,
-    ));
+    let apply = operation(backend.apply_language(&settings.asr_language, actions.apply_language));
     rollback_on_failure(apply, || {
         let settings = operation(
             backend.persist_settings(&previous_settings, SettingsAction::RestoreSettings),
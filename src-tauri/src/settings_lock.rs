@@ -0,0 +1,186 @@
+use std::time::{Duration, Instant};
+
+use argon2::password_hash::{PasswordHash, PasswordHasher, PasswordVerifier, SaltString};
+use argon2::{Algorithm, Argon2, Params, Version};
+use serde::{Deserialize, Serialize};
+
+use crate::errors::UserFacing;
+
+pub const MIN_PASSPHRASE_CHARS: usize = 4;
+/// How long a correct passphrase lets settings change before they lock again.
+pub const UNLOCK_DURATION: Duration = Duration::from_secs(10 * 60);
+/// Wrong passphrases allowed before unlocking pauses for [`RETRY_DELAY`].
+pub const MAX_FAILED_ATTEMPTS: u8 = 5;
+pub const RETRY_DELAY: Duration = Duration::from_secs(30);
+
+const SALT_BYTES: usize = 16;
+/// Argon2id cost: 64 MiB of memory and three passes, so guessing passphrases
+/// from a copied settings file is slow even on a GPU.
+const HASH_MEMORY_KIB: u32 = 64 * 1024;
+const HASH_PASSES: u32 = 3;
+
+/// Keeps settings from changing on a shared machine unless the passphrase is
+/// entered. Only a salted hash of the passphrase is stored.
+#[derive(Clone, Debug, Default, Deserialize, Eq, PartialEq, Serialize)]
+#[serde(default)]
+pub struct SettingsLock {
+    /// Argon2id hash in PHC string form, carrying its own salt and cost.
+    /// `None` when no lock is set.
+    pub passphrase_hash: Option<String>,
+}
+
+/// What the settings UI sees: whether a lock is set and whether it is open
+/// right now.
+#[derive(Clone, Copy, Debug, Eq, PartialEq, Serialize)]
+pub struct SettingsLockStatus {
+    pub enabled: bool,
+    pub unlocked: bool,
+}
+
+#[derive(thiserror::Error, Debug, Eq, PartialEq)]
+pub enum SettingsLockError {
+    #[error("settings are locked")]
+    Locked,
+    #[error("wrong settings passphrase")]
+    WrongPassphrase,
+    #[error("too many wrong passphrases; retry in {0:?}")]
+    TooManyAttempts(Duration),
+    #[error("passphrase is shorter than {MIN_PASSPHRASE_CHARS} characters")]
+    PassphraseTooShort,
+    #[error("no settings lock is set")]
+    NotEnabled,
+    #[error("could not generate a salt: {0}")]
+    Random(String),
+    #[error("could not hash the passphrase: {0}")]
+    Hash(String),
+}
+
+impl UserFacing for SettingsLockError {
+    fn user_message(&self) -> &'static str {
+        match self {
+            Self::Locked => "Settings are locked. Enter the passphrase to change them.",
+            Self::WrongPassphrase => "That passphrase is not correct.",
+            Self::TooManyAttempts(_) => {
+                "Too many wrong passphrases. Wait 30 seconds and try again."
+            }
+            Self::PassphraseTooShort => "Use a passphrase of at least 4 characters.",
+            Self::NotEnabled => "Settings are not locked.",
+            Self::Random(_) | Self::Hash(_) => "Could not set the settings lock. Please try again.",
+        }
+    }
+}
+
+impl SettingsLock {
+    pub fn new(passphrase: &str) -> Result<Self, SettingsLockError> {
+        let mut salt = [0_u8; SALT_BYTES];
+        getrandom::fill(&mut salt).map_err(|error| SettingsLockError::Random(error.to_string()))?;
+        Self::with_salt(passphrase, &salt)
+    }
+
+    pub fn with_salt(passphrase: &str, salt: &[u8]) -> Result<Self, SettingsLockError> {
+        if passphrase.chars().count() < MIN_PASSPHRASE_CHARS {
+            return Err(SettingsLockError::PassphraseTooShort);
+        }
+        let hash_error =
+            |error: argon2::password_hash::Error| SettingsLockError::Hash(error.to_string());
+        let salt = SaltString::encode_b64(salt).map_err(hash_error)?;
+        let hash = hasher()
+            .map_err(|error| SettingsLockError::Hash(error.to_string()))?
+            .hash_password(passphrase.as_bytes(), &salt)
+            .map_err(hash_error)?;
+        Ok(Self {
+            passphrase_hash: Some(hash.to_string()),
+        })
+    }
+
+    pub fn is_enabled(&self) -> bool {
+        self.passphrase_hash.is_some()
+    }
+
+    pub fn verify(&self, passphrase: &str) -> bool {
+        // The cost is read from the stored hash, so locks set with other
+        // parameters still verify.
+        self.passphrase_hash
+            .as_deref()
+            .and_then(|hash| PasswordHash::new(hash).ok())
+            .is_some_and(|hash| {
+                Argon2::default()
+                    .verify_password(passphrase.as_bytes(), &hash)
+                    .is_ok()
+            })
+    }
+}
+
+/// Whether a set lock is open, and how many wrong passphrases came before.
+/// It lives in memory only, so restarting the app locks settings again.
+#[derive(Debug, Default)]
+pub struct UnlockSession {
+    unlocked_until: Option<Instant>,
+    failed_attempts: u8,
+    retry_after: Option<Instant>,
+}
+
+impl UnlockSession {
+    pub const fn new() -> Self {
+        Self {
+            unlocked_until: None,
+            failed_attempts: 0,
+            retry_after: None,
+        }
+    }
+
+    pub fn is_unlocked(&self, now: Instant) -> bool {
+        self.unlocked_until.is_some_and(|until| now < until)
+    }
+
+    /// Checks `passphrase` and opens the lock for [`UNLOCK_DURATION`].
+    pub fn unlock(
+        &mut self,
+        lock: &SettingsLock,
+        passphrase: &str,
+        now: Instant,
+    ) -> Result<(), SettingsLockError> {
+        if !lock.is_enabled() {
+            return Err(SettingsLockError::NotEnabled);
+        }
+        if let Some(retry_after) = self.retry_after.filter(|retry_after| now < *retry_after) {
+            return Err(SettingsLockError::TooManyAttempts(retry_after - now));
+        }
+        if !lock.verify(passphrase) {
+            self.failed_attempts += 1;
+            if self.failed_attempts >= MAX_FAILED_ATTEMPTS {
+                self.failed_attempts = 0;
+                self.retry_after = Some(now + RETRY_DELAY);
+            }
+            return Err(SettingsLockError::WrongPassphrase);
+        }
+        self.failed_attempts = 0;
+        self.retry_after = None;
+        self.unlocked_until = Some(now + UNLOCK_DURATION);
+        Ok(())
+    }
+
+    pub fn relock(&mut self) {
+        self.unlocked_until = None;
+    }
+
+    /// Allows a change when no lock is set or the lock is open.
+    pub fn check(&self, lock: &SettingsLock, now: Instant) -> Result<(), SettingsLockError> {
+        if !lock.is_enabled() || self.is_unlocked(now) {
+            return Ok(());
+        }
+        Err(SettingsLockError::Locked)
+    }
+
+    pub fn status(&self, lock: &SettingsLock, now: Instant) -> SettingsLockStatus {
+        SettingsLockStatus {
+            enabled: lock.is_enabled(),
+            unlocked: lock.is_enabled() && self.is_unlocked(now),
+        }
+    }
+}
+
+fn hasher() -> Result<Argon2<'static>, argon2::Error> {
+    let params = Params::new(HASH_MEMORY_KIB, HASH_PASSES, 1, None)?;
+    Ok(Argon2::new(Algorithm::Argon2id, Version::V0x13, params))
+}
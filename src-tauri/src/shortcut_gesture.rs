@@ -0,0 +1,224 @@
+use std::time::{Duration, Instant};
+
+use serde::{Deserialize, Serialize};
+
+pub const MIN_GESTURE_WINDOW_MS: u64 = 150;
+pub const MAX_GESTURE_WINDOW_MS: u64 = 3_000;
+
+/// How presses of the record shortcut start and stop a dictation.
+#[derive(Clone, Copy, Debug, Default, Deserialize, Eq, PartialEq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum GestureMode {
+    /// Records while the shortcut is held.
+    #[default]
+    Hold,
+    /// Holding still records push-to-talk; two quick taps latch recording on
+    /// until the next tap.
+    DoubleTap,
+    /// The shortcut only arms a follow-up key; pressing it in time starts or
+    /// stops recording.
+    Chord,
+}
+
+#[derive(Clone, Debug, Deserialize, Eq, PartialEq, Serialize)]
+#[serde(default)]
+pub struct ShortcutGestureSettings {
+    pub mode: GestureMode,
+    /// Longest tap, gap between taps, or wait for the follow-up key.
+    pub window_ms: u64,
+    /// Shortcut pressed after the record shortcut in chord mode, such as `D`.
+    pub follow_up: String,
+}
+
+impl Default for ShortcutGestureSettings {
+    fn default() -> Self {
+        Self {
+            mode: GestureMode::Hold,
+            window_ms: 400,
+            follow_up: String::new(),
+        }
+    }
+}
+
+impl ShortcutGestureSettings {
+    pub fn clamped(self) -> Self {
+        Self {
+            window_ms: self
+                .window_ms
+                .clamp(MIN_GESTURE_WINDOW_MS, MAX_GESTURE_WINDOW_MS),
+            follow_up: self.follow_up.trim().to_string(),
+            ..self
+        }
+    }
+
+    pub fn window(&self) -> Duration {
+        Duration::from_millis(self.window_ms)
+    }
+}
+
+/// One key event, or the passing of a deadline the machine asked for.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum GestureInput {
+    Pressed,
+    Released,
+    FollowUp,
+    Expired,
+}
+
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum GestureAction {
+    None,
+    Start,
+    Stop,
+    /// Start when idle, stop when recording.
+    Toggle,
+    /// Listen for the chord's follow-up key.
+    ArmFollowUp,
+    DisarmFollowUp,
+}
+
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+enum GestureState {
+    Idle,
+    /// Down since the instant, recording push-to-talk.
+    Held(Instant),
+    /// A short tap is recording; a second tap before the deadline latches it.
+    AwaitingSecondTap(Instant),
+    /// The second tap is still down.
+    Latching,
+    Latched,
+    /// The tap that stopped a latched recording is still down.
+    Unlatching,
+    /// Chord mode: the follow-up key counts until the deadline.
+    Armed(Instant),
+}
+
+/// Turns record shortcut events into dictation actions for the double-tap
+/// and chord gestures. [`GestureMode::Hold`] maps presses straight through.
+#[derive(Debug)]
+pub struct GestureMachine {
+    mode: GestureMode,
+    window: Duration,
+    state: GestureState,
+}
+
+impl GestureMachine {
+    pub const fn new(mode: GestureMode, window: Duration) -> Self {
+        Self {
+            mode,
+            window,
+            state: GestureState::Idle,
+        }
+    }
+
+    /// Applies changed settings, dropping any gesture in progress.
+    pub fn configure(&mut self, mode: GestureMode, window: Duration) {
+        if self.mode != mode || self.window != window {
+            *self = Self::new(mode, window);
+        }
+    }
+
+    /// Forgets a latched or pending recording that was stopped some other
+    /// way, such as from the app window.
+    pub fn sync(&mut self, recording: bool) {
+        if !recording
+            && matches!(
+                self.state,
+                GestureState::AwaitingSecondTap(_) | GestureState::Latched
+            )
+        {
+            self.state = GestureState::Idle;
+        }
+    }
+
+    pub fn reset(&mut self) {
+        self.state = GestureState::Idle;
+    }
+
+    /// When the caller should feed [`GestureInput::Expired`].
+    pub fn deadline(&self) -> Option<Instant> {
+        match self.state {
+            GestureState::AwaitingSecondTap(deadline) | GestureState::Armed(deadline) => {
+                Some(deadline)
+            }
+            _ => None,
+        }
+    }
+
+    pub fn handle(&mut self, input: GestureInput, now: Instant) -> GestureAction {
+        match self.mode {
+            GestureMode::Hold => match input {
+                GestureInput::Pressed => GestureAction::Start,
+                GestureInput::Released => GestureAction::Stop,
+                _ => GestureAction::None,
+            },
+            GestureMode::DoubleTap => self.double_tap(input, now),
+            GestureMode::Chord => self.chord(input, now),
+        }
+    }
+
+    fn double_tap(&mut self, input: GestureInput, now: Instant) -> GestureAction {
+        let (state, action) = match (self.state, input) {
+            (GestureState::Idle, GestureInput::Pressed) => {
+                (GestureState::Held(now), GestureAction::Start)
+            }
+            (GestureState::Held(since), GestureInput::Released) => {
+                if now.duration_since(since) < self.window {
+                    (
+                        GestureState::AwaitingSecondTap(now + self.window),
+                        GestureAction::None,
+                    )
+                } else {
+                    (GestureState::Idle, GestureAction::Stop)
+                }
+            }
+            (GestureState::AwaitingSecondTap(deadline), GestureInput::Pressed) => {
+                if now <= deadline {
+                    (GestureState::Latching, GestureAction::None)
+                } else {
+                    // The expiry has not been handled yet; end the tap's
+                    // recording as it would have.
+                    (GestureState::Unlatching, GestureAction::Stop)
+                }
+            }
+            (GestureState::AwaitingSecondTap(deadline), GestureInput::Expired)
+                if now >= deadline =>
+            {
+                (GestureState::Idle, GestureAction::Stop)
+            }
+            (GestureState::Latching, GestureInput::Released) => {
+                (GestureState::Latched, GestureAction::None)
+            }
+            (GestureState::Latched, GestureInput::Pressed) => {
+                (GestureState::Unlatching, GestureAction::Stop)
+            }
+            (GestureState::Unlatching, GestureInput::Released) => {
+                (GestureState::Idle, GestureAction::None)
+            }
+            (state, _) => (state, GestureAction::None),
+        };
+        self.state = state;
+        action
+    }
+
+    fn chord(&mut self, input: GestureInput, now: Instant) -> GestureAction {
+        let (state, action) = match (self.state, input) {
+            (_, GestureInput::Pressed) => (
+                GestureState::Armed(now + self.window),
+                GestureAction::ArmFollowUp,
+            ),
+            (GestureState::Armed(deadline), GestureInput::FollowUp) if now <= deadline => {
+                (GestureState::Idle, GestureAction::Toggle)
+            }
+            (GestureState::Armed(_), GestureInput::FollowUp) => {
+                (GestureState::Idle, GestureAction::DisarmFollowUp)
+            }
+            (GestureState::Armed(deadline), GestureInput::Expired) if now >= deadline => {
+                (GestureState::Idle, GestureAction::DisarmFollowUp)
+            }
+            (state, _) => (state, GestureAction::None),
+        };
+        self.state = state;
+        action
+    }
+}
@@ -0,0 +1,247 @@
+use std::f32::consts::PI;
+
+use serde::{Deserialize, Serialize};
+
+use crate::asr::TARGET_SAMPLE_RATE;
+use crate::recording::{level_dbfs, SILENCE_DBFS};
+
+/// Active speech level the normalizer steers toward, close to that of the
+/// read-speech corpora the model was trained on.
+pub const TARGET_LEVEL_DBFS: f32 = -26.0;
+/// Energy above [`TILT_SPLIT_HZ`] relative to below it in that speech.
+pub const TARGET_TILT_DB: f32 = -12.0;
+/// Where the tilt measurement and correction split the spectrum.
+pub const TILT_SPLIT_HZ: f32 = 1_000.0;
+/// Utterances heard before the profile is trusted enough to correct audio.
+pub const MIN_UTTERANCES: u32 = 3;
+/// The running mean weighs each new utterance at least this much, so the
+/// profile keeps following a voice that changes with a new microphone.
+const WINDOW_UTTERANCES: u32 = 20;
+pub const MAX_GAIN_DB: f32 = 20.0;
+pub const MAX_TILT_CORRECTION_DB: f32 = 9.0;
+
+/// 20 ms frames at 16 kHz.
+const FRAME: usize = 320;
+/// Frames more than this far below the loudest are pauses, not speech.
+const SPEECH_RANGE_DB: f32 = 30.0;
+const MIN_SPEECH_FRAME_DBFS: f32 = -60.0;
+/// Speech needed for an utterance to count toward the profile: half a second.
+const MIN_SPEECH_FRAMES: usize = 25;
+
+/// The speaker adaptation setting and what it has learned about the voice.
+#[derive(Clone, Copy, Debug, Default, Deserialize, PartialEq, Serialize)]
+#[serde(default)]
+pub struct SpeakerAdaptation {
+    pub enabled: bool,
+    pub voice: VoiceProfile,
+}
+
+/// What the settings page shows: the learned voice and the correction it
+/// leads to, even while adaptation is off.
+#[derive(Clone, Debug, PartialEq, Serialize)]
+pub struct SpeakerAdaptationInfo {
+    pub enabled: bool,
+    pub voice: VoiceProfile,
+    pub correction: Option<Correction>,
+}
+
+impl SpeakerAdaptation {
+    pub fn info(&self) -> SpeakerAdaptationInfo {
+        SpeakerAdaptationInfo {
+            enabled: self.enabled,
+            voice: self.voice,
+            correction: self.voice.correction(),
+        }
+    }
+
+    /// A normalizer for one recording, which passes audio through while
+    /// adaptation is off or still learning.
+    pub fn normalizer(&self) -> SpeakerNormalizer {
+        match self.correction() {
+            Some(correction) => SpeakerNormalizer::new(correction),
+            None => SpeakerNormalizer::off(),
+        }
+    }
+
+    pub fn correction(&self) -> Option<Correction> {
+        self.enabled.then(|| self.voice.correction()).flatten()
+    }
+}
+
+/// Level and spectral tilt of one utterance's speech, in dB.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct VoiceStats {
+    pub level_db: f32,
+    pub tilt_db: f32,
+}
+
+/// Running means of [`VoiceStats`] over the speaker's utterances: cepstral
+/// mean normalization reduced to the two features a voice differs in most.
+#[derive(Clone, Copy, Debug, Default, Deserialize, PartialEq, Serialize)]
+#[serde(default)]
+pub struct VoiceProfile {
+    pub utterances: u32,
+    pub level_db: f32,
+    pub tilt_db: f32,
+}
+
+impl VoiceProfile {
+    pub fn observe(&mut self, stats: VoiceStats) {
+        self.utterances = self.utterances.saturating_add(1);
+        let weight = 1.0 / self.utterances.min(WINDOW_UTTERANCES) as f32;
+        self.level_db += (stats.level_db - self.level_db) * weight;
+        self.tilt_db += (stats.tilt_db - self.tilt_db) * weight;
+    }
+
+    /// What moves this voice to the target level and tilt, once
+    /// [`MIN_UTTERANCES`] have been heard.
+    pub fn correction(&self) -> Option<Correction> {
+        if self.utterances < MIN_UTTERANCES {
+            return None;
+        }
+        let tilt_db =
+            (TARGET_TILT_DB - self.tilt_db).clamp(-MAX_TILT_CORRECTION_DB, MAX_TILT_CORRECTION_DB);
+        // Cutting the bass of a bass-heavy voice also quietens it; the gain
+        // makes up for that so the level still lands on target.
+        let treble_share = 10f32.powf(self.tilt_db / 10.0);
+        let tilt = 10f32.powf(tilt_db / 20.0);
+        let tilt_loss_db =
+            10.0 * ((1.0 / tilt + tilt * treble_share) / (1.0 + treble_share)).log10();
+        Some(Correction {
+            gain_db: (TARGET_LEVEL_DBFS - self.level_db - tilt_loss_db)
+                .clamp(-MAX_GAIN_DB, MAX_GAIN_DB),
+            tilt_db,
+        })
+    }
+}
+
+/// A gain and a treble-versus-bass balance, both in dB.
+#[derive(Clone, Copy, Debug, PartialEq, Serialize)]
+pub struct Correction {
+    pub gain_db: f32,
+    pub tilt_db: f32,
+}
+
+impl Correction {
+    /// Bass and treble gains: half of `tilt_db` each way, so the balance
+    /// moves by all of it, on top of `gain_db`.
+    fn band_gains(&self) -> (f32, f32) {
+        let gain = 10f32.powf(self.gain_db / 20.0);
+        let tilt = 10f32.powf(self.tilt_db / 40.0);
+        (gain / tilt, gain * tilt)
+    }
+
+    /// The audio from before [`SpeakerNormalizer`] applied this correction,
+    /// so the profile keeps learning the voice rather than its own output.
+    /// Exact apart from samples the normalizer clipped.
+    pub fn undo(&self, samples: &[f32]) -> Vec<f32> {
+        let (low_gain, high_gain) = self.band_gains();
+        let coefficient = BandSplit::new().coefficient;
+        let scale = high_gain + (low_gain - high_gain) * (1.0 - coefficient);
+        let mut low = 0.0;
+        samples
+            .iter()
+            .map(|&sample| {
+                let input = (sample - (low_gain - high_gain) * coefficient * low) / scale;
+                low = input + (low - input) * coefficient;
+                input
+            })
+            .collect()
+    }
+}
+
+/// Measures the speech in `samples`, 16 kHz mono, ignoring pauses. `None`
+/// when there is too little speech to say anything about the voice.
+pub fn measure_voice(samples: &[f32]) -> Option<VoiceStats> {
+    let mut split = BandSplit::new();
+    let frames: Vec<(f32, f32, f32)> = samples
+        .chunks_exact(FRAME)
+        .map(|frame| {
+            let (mut total, mut low, mut high) = (0.0, 0.0, 0.0);
+            for &sample in frame {
+                let (bass, treble) = split.split(sample);
+                total += sample * sample;
+                low += bass * bass;
+                high += treble * treble;
+            }
+            (level_dbfs((total / FRAME as f32).sqrt()), low, high)
+        })
+        .collect();
+    let loudest = frames
+        .iter()
+        .map(|(level, _, _)| *level)
+        .fold(SILENCE_DBFS, f32::max);
+    let floor = (loudest - SPEECH_RANGE_DB).max(MIN_SPEECH_FRAME_DBFS);
+    let speech: Vec<_> = frames
+        .into_iter()
+        .filter(|(level, _, _)| *level >= floor)
+        .collect();
+    if speech.len() < MIN_SPEECH_FRAMES {
+        return None;
+    }
+    let level_db = speech.iter().map(|(level, _, _)| level).sum::<f32>() / speech.len() as f32;
+    let low: f32 = speech.iter().map(|(_, low, _)| low).sum();
+    let high: f32 = speech.iter().map(|(_, _, high)| high).sum();
+    Some(VoiceStats {
+        level_db,
+        tilt_db: 10.0 * (high.max(1e-12) / low.max(1e-12)).log10(),
+    })
+}
+
+/// Applies a [`Correction`] to the capture as it streams in, clipping at
+/// full scale.
+pub struct SpeakerNormalizer {
+    correction: Option<(f32, f32)>,
+    split: BandSplit,
+}
+
+impl SpeakerNormalizer {
+    pub fn off() -> Self {
+        Self {
+            correction: None,
+            split: BandSplit::new(),
+        }
+    }
+
+    pub fn new(correction: Correction) -> Self {
+        Self {
+            correction: Some(correction.band_gains()),
+            split: BandSplit::new(),
+        }
+    }
+
+    pub fn is_active(&self) -> bool {
+        self.correction.is_some()
+    }
+
+    pub fn process(&mut self, samples: &mut [f32]) {
+        let Some((low_gain, high_gain)) = self.correction else {
+            return;
+        };
+        for sample in samples {
+            let (low, high) = self.split.split(*sample);
+            *sample = (low * low_gain + high * high_gain).clamp(-1.0, 1.0);
+        }
+    }
+}
+
+/// A one-pole low-pass at [`TILT_SPLIT_HZ`] and its complement, which add
+/// back to the input.
+struct BandSplit {
+    coefficient: f32,
+    low: f32,
+}
+
+impl BandSplit {
+    fn new() -> Self {
+        Self {
+            coefficient: (-2.0 * PI * TILT_SPLIT_HZ / TARGET_SAMPLE_RATE as f32).exp(),
+            low: 0.0,
+        }
+    }
+
+    fn split(&mut self, sample: f32) -> (f32, f32) {
+        self.low = sample + (self.low - sample) * self.coefficient;
+        (self.low, sample - self.low)
+    }
+}
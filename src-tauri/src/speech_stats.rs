@@ -0,0 +1,125 @@
+use serde::{Deserialize, Serialize};
+#[cfg(feature = "desktop")]
+use tauri::AppHandle;
+
+use crate::transcript::Transcript;
+
+/// Sessions kept for the stats card; older ones are dropped.
+pub const MAX_STORED_SESSIONS: usize = 100;
+/// A pause at least this long ends a monologue.
+pub const MONOLOGUE_PAUSE_MS: u64 = 2_000;
+
+#[cfg(feature = "desktop")]
+const STATS_STORE: &str = "stats.json";
+#[cfg(feature = "desktop")]
+const SESSIONS_KEY: &str = "sessions";
+
+/// How one dictation was spoken, measured from its segment timestamps.
+#[derive(Clone, Debug, Deserialize, PartialEq, Serialize)]
+pub struct SpeechStats {
+    /// When recording started, as RFC 3339.
+    pub recorded_at: String,
+    pub duration_ms: u64,
+    /// Time inside speech segments.
+    pub speaking_ms: u64,
+    pub words: usize,
+    /// Words per minute of speaking time, so pauses do not lower the rate.
+    pub words_per_minute: f32,
+    /// Share of the recording spent silent, from 0 to 1.
+    pub pause_ratio: f32,
+    /// The longest stretch of speech without a pause of
+    /// [`MONOLOGUE_PAUSE_MS`] or more.
+    pub longest_monologue_ms: u64,
+}
+
+impl SpeechStats {
+    /// `None` when nothing was said. The recognizer does not time single
+    /// words, so speech is timed by segment: a segment's pauses for breath
+    /// count as speaking.
+    pub fn measure(transcript: &Transcript, duration_ms: u64, recorded_at: String) -> Option<Self> {
+        let spoken: Vec<_> = transcript
+            .segments
+            .iter()
+            .filter(|segment| segment.text.split_whitespace().next().is_some())
+            .collect();
+        let words = spoken
+            .iter()
+            .map(|segment| segment.text.split_whitespace().count())
+            .sum::<usize>();
+        if words == 0 {
+            return None;
+        }
+        let speaking_ms = spoken
+            .iter()
+            .map(|segment| segment.end_ms.saturating_sub(segment.start_ms))
+            .sum::<u64>();
+        let duration_ms = duration_ms.max(speaking_ms);
+        let words_per_minute = if speaking_ms == 0 {
+            0.0
+        } else {
+            words as f32 * 60_000.0 / speaking_ms as f32
+        };
+        let pause_ratio = if duration_ms == 0 {
+            0.0
+        } else {
+            (duration_ms - speaking_ms) as f32 / duration_ms as f32
+        };
+
+        let mut longest_monologue_ms = 0;
+        let mut run: Option<(u64, u64)> = None;
+        for segment in spoken {
+            run = match run {
+                Some((start, end)) if segment.start_ms < end + MONOLOGUE_PAUSE_MS => {
+                    Some((start, end.max(segment.end_ms)))
+                }
+                _ => Some((segment.start_ms, segment.end_ms)),
+            };
+            if let Some((start, end)) = run {
+                longest_monologue_ms = longest_monologue_ms.max(end.saturating_sub(start));
+            }
+        }
+
+        Some(Self {
+            recorded_at,
+            duration_ms,
+            speaking_ms,
+            words,
+            words_per_minute,
+            pause_ratio,
+            longest_monologue_ms,
+        })
+    }
+}
+
+/// Adds `stats` as the newest session, dropping the oldest past
+/// [`MAX_STORED_SESSIONS`].
+pub fn push_session(sessions: &mut Vec<SpeechStats>, stats: SpeechStats) {
+    sessions.push(stats);
+    let excess = sessions.len().saturating_sub(MAX_STORED_SESSIONS);
+    sessions.drain(..excess);
+}
+
+/// Stored sessions, oldest first.
+#[cfg(feature = "desktop")]
+pub fn load_sessions(app: &AppHandle) -> Vec<SpeechStats> {
+    use tauri_plugin_store::StoreExt;
+    app.store(STATS_STORE)
+        .ok()
+        .and_then(|store| store.get(SESSIONS_KEY))
+        .and_then(|value| serde_json::from_value(value).ok())
+        .unwrap_or_default()
+}
+
+#[cfg(feature = "desktop")]
+pub fn record_session(app: &AppHandle, stats: SpeechStats) -> Result<(), String> {
+    use tauri_plugin_store::StoreExt;
+    let mut sessions = load_sessions(app);
+    push_session(&mut sessions, stats);
+    let store = app
+        .store(STATS_STORE)
+        .map_err(|e| format!("Could not open stats store: {e}"))?;
+    store.set(SESSIONS_KEY.to_string(), serde_json::json!(sessions));
+    store
+        .save()
+        .map_err(|e| format!("Could not save speech stats: {e}"))
+}
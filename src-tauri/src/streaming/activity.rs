@@ -0,0 +1,71 @@
+use serde::Serialize;
+
+use crate::vad::{VadConfig, VadEvent, VadSegmenter};
+
+pub const SPEECH_START_EVENT: &str = "speech_start";
+pub const SPEECH_END_EVENT: &str = "speech_end";
+
+/// Where voice activity detection hears the speaker start or stop talking
+/// in a streaming session. Times are milliseconds from the start of the
+/// recording.
+#[derive(Clone, Copy, Debug, PartialEq, Serialize)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum SpeechActivity {
+    Start {
+        at_ms: u64,
+    },
+    /// Sent once the pause after the speech reaches the configured minimum
+    /// silence, or when the recording stops mid-speech.
+    End {
+        start_ms: u64,
+        end_ms: u64,
+    },
+}
+
+impl SpeechActivity {
+    /// The event this is emitted as: `speech_start` or `speech_end`.
+    pub fn event_name(&self) -> &'static str {
+        match self {
+            Self::Start { .. } => SPEECH_START_EVENT,
+            Self::End { .. } => SPEECH_END_EVENT,
+        }
+    }
+
+    fn from_vad(event: VadEvent) -> Option<Self> {
+        match event {
+            VadEvent::SpeechStart { at_ms } => Some(Self::Start { at_ms }),
+            VadEvent::SpeechEnd { start_ms, end_ms } => Some(Self::End { start_ms, end_ms }),
+            VadEvent::Frame { .. } => None,
+        }
+    }
+}
+
+/// Runs voice activity detection over a session's audio as it streams and
+/// keeps only the starts and ends of speech.
+pub struct SpeechActivityTracker {
+    segmenter: VadSegmenter,
+}
+
+impl SpeechActivityTracker {
+    pub fn new(vad: VadConfig) -> Self {
+        Self {
+            segmenter: VadSegmenter::new(vad),
+        }
+    }
+
+    pub fn feed(&mut self, samples: &[f32]) -> Vec<SpeechActivity> {
+        activity(self.segmenter.feed(samples))
+    }
+
+    /// Closes the stream, ending speech still open at its end.
+    pub fn finish(&mut self) -> Vec<SpeechActivity> {
+        activity(self.segmenter.finish())
+    }
+}
+
+fn activity(events: Vec<VadEvent>) -> Vec<SpeechActivity> {
+    events
+        .into_iter()
+        .filter_map(SpeechActivity::from_vad)
+        .collect()
+}
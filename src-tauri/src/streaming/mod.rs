@@ -1,30 +1,80 @@
+pub mod activity;
+pub mod phrase;
 pub mod pipeline;
 
+pub use activity::{SpeechActivity, SpeechActivityTracker, SPEECH_END_EVENT, SPEECH_START_EVENT};
+pub use phrase::PhraseBuffer;
 pub use pipeline::StreamingPipeline;
 
-use serde::Serialize;
+use serde::{Deserialize, Serialize};
+pub use transcript_patch::{PatchError, Transcript, TranscriptPatch};
 
 use crate::asr::AsrError;
 use crate::errors::UserFacing;
+use crate::recording::SessionId;
 
+/// The `transcription_update` event payload: a patch tagged with the
+/// recording it belongs to, so late patches from a finished utterance are not
+/// applied to the next one.
 #[derive(Debug, Clone, Serialize)]
-#[serde(tag = "kind", content = "text", rename_all = "snake_case")]
-pub enum TranscriptionUpdate {
-    Append(String),
-    Replace(String),
+pub struct SessionUpdate {
+    pub session_id: Option<SessionId>,
+    #[serde(flatten)]
+    pub patch: TranscriptPatch,
 }
 
-/// Delivers committed transcription updates to a UI or typing sink.
-pub trait UpdateSink: Fn(TranscriptionUpdate) -> Result<(), String> + Send + 'static {}
+/// How live transcription decodes. `Continuous` runs the streaming decoder on
+/// every chunk as it arrives; `Phrase` waits for voice activity detection to
+/// hear a pause and decodes the phrase in one offline pass, trading latency
+/// for far less CPU.
+#[derive(Clone, Copy, Debug, Default, Deserialize, Eq, PartialEq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum StreamingStrategy {
+    #[default]
+    Continuous,
+    Phrase,
+}
 
-impl<F: Fn(TranscriptionUpdate) -> Result<(), String> + Send + 'static> UpdateSink for F {}
+/// How one recording is transcribed when something outside the settings
+/// picks it, such as a deep link. `Batch` shows no drafts and only runs the
+/// final pass.
+#[derive(Clone, Copy, Debug, Deserialize, Eq, PartialEq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum RecordingMode {
+    Streaming,
+    Phrase,
+    Batch,
+}
+
+impl RecordingMode {
+    pub fn parse(mode: &str) -> Option<Self> {
+        match mode.to_ascii_lowercase().as_str() {
+            "streaming" => Some(Self::Streaming),
+            "phrase" => Some(Self::Phrase),
+            "batch" => Some(Self::Batch),
+            _ => None,
+        }
+    }
+
+    /// The live decoding the mode asks for, or `None` for none.
+    pub fn live_strategy(self) -> Option<StreamingStrategy> {
+        match self {
+            Self::Streaming => Some(StreamingStrategy::Continuous),
+            Self::Phrase => Some(StreamingStrategy::Phrase),
+            Self::Batch => None,
+        }
+    }
+}
+
+/// Delivers transcript patches to a UI or typing sink.
+pub trait UpdateSink: Fn(TranscriptPatch) -> Result<(), String> + Send + 'static {}
+
+impl<F: Fn(TranscriptPatch) -> Result<(), String> + Send + 'static> UpdateSink for F {}
 
 #[derive(thiserror::Error, Debug)]
 pub enum StreamingError {
     #[error("streaming transcription is already running")]
     AlreadyRunning,
-    #[error("{0} lock failed")]
-    LockFailed(&'static str),
     #[error("speech model is not ready")]
     ModelNotReady,
     #[error("speech model is not loaded")]
@@ -46,11 +96,9 @@ impl UserFacing for StreamingError {
             Self::ModelNotReady | Self::ModelNotLoaded => {
                 "The speech model is not ready. Please wait and try again."
             }
-            Self::LockFailed(_)
-            | Self::WorkerStart(_)
-            | Self::Decode(_)
-            | Self::Output(_)
-            | Self::WorkerPanicked => "Streaming transcription failed. Please try recording again.",
+            Self::WorkerStart(_) | Self::Decode(_) | Self::Output(_) | Self::WorkerPanicked => {
+                "Streaming transcription failed. Please try recording again."
+            }
         }
     }
 }
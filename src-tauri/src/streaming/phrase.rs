@@ -0,0 +1,107 @@
+use crate::asr::TARGET_SAMPLE_RATE;
+use crate::vad::{VadConfig, VadEvent, VadSegmenter};
+
+const SAMPLES_PER_MS: usize = TARGET_SAMPLE_RATE as usize / 1_000;
+
+/// Collects streamed audio into phrases for [`StreamingStrategy::Phrase`]:
+/// a phrase is released when voice activity detection hears it end, or once
+/// it runs to `max_phrase_samples` without a pause. Silence between phrases
+/// is dropped apart from the configured padding.
+///
+/// [`StreamingStrategy::Phrase`]: super::StreamingStrategy::Phrase
+pub struct PhraseBuffer {
+    segmenter: VadSegmenter,
+    audio: Vec<f32>,
+    /// Stream position of `audio[0]`.
+    offset: usize,
+    /// Stream position where the open phrase started, if speech is ongoing.
+    phrase_start: Option<usize>,
+    padding: usize,
+    /// Audio kept between phrases: the padding, plus the partial frame the
+    /// segmenter has not analysed yet.
+    lookback: usize,
+    max_phrase_samples: usize,
+}
+
+impl PhraseBuffer {
+    pub fn new(vad: VadConfig, max_phrase_samples: usize) -> Self {
+        Self {
+            segmenter: VadSegmenter::new(vad),
+            audio: Vec::new(),
+            offset: 0,
+            phrase_start: None,
+            padding: vad.padding_samples,
+            lookback: vad.padding_samples + vad.frame_samples,
+            max_phrase_samples: max_phrase_samples.max(1),
+        }
+    }
+
+    /// Buffers `samples` and returns the phrases they completed, oldest first.
+    pub fn feed(&mut self, samples: &[f32]) -> Vec<Vec<f32>> {
+        self.audio.extend_from_slice(samples);
+        let events = self.segmenter.feed(samples);
+        let mut phrases = self.apply(events);
+        if let Some(start) = self.phrase_start {
+            if self.end() - start >= self.max_phrase_samples {
+                phrases.push(self.take(start, self.end()));
+                self.phrase_start = Some(self.end());
+            }
+        }
+        if self.phrase_start.is_none() {
+            let keep_from = self.end().saturating_sub(self.lookback);
+            self.drain_to(keep_from);
+        }
+        phrases
+    }
+
+    /// Closes the stream, returning the phrase still open at its end.
+    pub fn finish(&mut self) -> Vec<Vec<f32>> {
+        let events = self.segmenter.finish();
+        let phrases = self.apply(events);
+        self.audio.clear();
+        self.phrase_start = None;
+        phrases
+    }
+
+    fn apply(&mut self, events: Vec<VadEvent>) -> Vec<Vec<f32>> {
+        let mut phrases = Vec::new();
+        for event in events {
+            match event {
+                VadEvent::SpeechStart { at_ms } => {
+                    self.phrase_start = Some(to_samples(at_ms).max(self.offset));
+                }
+                VadEvent::SpeechEnd { end_ms, .. } => {
+                    let start = self.phrase_start.take().unwrap_or(self.offset);
+                    let end = to_samples(end_ms) + self.padding;
+                    phrases.push(self.take(start, end));
+                }
+                VadEvent::Frame { .. } => {}
+            }
+        }
+        phrases
+    }
+
+    /// Removes and returns stream positions `start - padding .. end`, along
+    /// with everything buffered before them.
+    fn take(&mut self, start: usize, end: usize) -> Vec<f32> {
+        let from = start.saturating_sub(self.padding).max(self.offset) - self.offset;
+        let to = end.clamp(self.offset, self.end()) - self.offset;
+        let phrase = self.audio[from.min(to)..to].to_vec();
+        self.drain_to(self.offset + to);
+        phrase
+    }
+
+    fn drain_to(&mut self, position: usize) {
+        let count = position.saturating_sub(self.offset).min(self.audio.len());
+        self.audio.drain(..count);
+        self.offset += count;
+    }
+
+    fn end(&self) -> usize {
+        self.offset + self.audio.len()
+    }
+}
+
+fn to_samples(ms: u64) -> usize {
+    ms as usize * SAMPLES_PER_MS
+}
@@ -1,16 +1,31 @@
 use std::sync::{mpsc::Receiver, Arc, Mutex, RwLock};
 use std::thread::{self, JoinHandle};
 
-use crate::asr::{AsrModel, STREAM_CHUNK_SAMPLES};
+use crate::asr::{write_model, AsrModel, STREAM_CHUNK_SAMPLES, TARGET_SAMPLE_RATE};
 use crate::audio_processing::AudioFrame;
-use crate::streaming::{StreamingError, TranscriptionUpdate, UpdateSink};
+use crate::streaming::{
+    PhraseBuffer, SpeechActivity, SpeechActivityTracker, StreamingError, StreamingStrategy,
+    TranscriptPatch, UpdateSink,
+};
+use crate::sync::MutexRecover;
+use crate::transcript::PauseMarkers;
+use crate::vad::{PauseDetector, VadConfig};
 
 type Worker = JoinHandle<Result<(), StreamingError>>;
+type ActivitySink = Arc<dyn Fn(SpeechActivity) + Send + Sync>;
+
+/// A phrase longer than this is decoded without waiting for the speaker to
+/// pause, so a monologue still appears every few seconds.
+const MAX_PHRASE_SAMPLES: usize = 10 * TARGET_SAMPLE_RATE as usize;
 
 /// Owns at most one decoding worker; the worker slot is the running/idle state.
+/// The engine holds a single pipeline for the app's lifetime, and each
+/// dictation's worker is joined by `finish` when the recording stops, so
+/// repeated recordings never accumulate decoder threads.
 #[derive(Default)]
 pub struct StreamingPipeline {
     worker: Mutex<Option<Worker>>,
+    on_activity: Option<ActivitySink>,
 }
 
 impl StreamingPipeline {
@@ -18,65 +33,148 @@ impl StreamingPipeline {
         Self::default()
     }
 
+    /// Reports where each session's speech starts and ends to `sink`, from
+    /// the decoder worker as it takes in the audio.
+    pub fn with_activity_sink(
+        mut self,
+        sink: impl Fn(SpeechActivity) + Send + Sync + 'static,
+    ) -> Self {
+        self.on_activity = Some(Arc::new(sink));
+        self
+    }
+
     /// Moves the audio receiver and output sink to one decoder worker. Dropping
     /// every sender closes the stream; `finish` then joins the worker after its flush.
     pub fn start(
         &self,
         audio: Receiver<AudioFrame>,
         model: Arc<RwLock<Option<AsrModel>>>,
+        pause_markers: PauseMarkers,
+        vad: VadConfig,
+        strategy: StreamingStrategy,
         on_update: impl UpdateSink,
     ) -> Result<(), StreamingError> {
-        let mut worker = self
-            .worker
-            .lock()
-            .map_err(|_| StreamingError::LockFailed("streaming worker"))?;
+        let mut worker = self.worker.lock_recover();
+        // A session that ended without `finish` leaves an exited worker in
+        // the slot; reap it rather than refusing every later recording.
+        if let Some(stale) = worker.take_if(|worker| worker.is_finished()) {
+            log::warn!("Reaping streaming worker from an unfinished session");
+            match stale.join() {
+                Ok(Err(error)) => log::warn!("Previous streaming session failed: {error}"),
+                Err(_) => log::warn!("Previous streaming worker panicked"),
+                Ok(Ok(())) => {}
+            }
+        }
         if worker.is_some() {
             return Err(StreamingError::AlreadyRunning);
         }
 
+        let on_activity = self.on_activity.clone();
         *worker = Some(
             thread::Builder::new()
                 .name("streaming-decode".to_string())
-                .spawn(move || Self::run(audio, model, on_update))
+                .spawn(move || {
+                    let pauses = StreamPauses::new(&pause_markers, &vad);
+                    let activity = StreamActivity::new(vad, on_activity);
+                    match strategy {
+                        StreamingStrategy::Continuous => {
+                            Self::run_continuous(audio, model, pauses, activity, on_update)
+                        }
+                        StreamingStrategy::Phrase => {
+                            Self::run_phrases(audio, model, pauses, activity, vad, on_update)
+                        }
+                    }
+                })
                 .map_err(StreamingError::WorkerStart)?,
         );
         Ok(())
     }
 
-    fn run(
+    fn run_continuous(
         audio: Receiver<AudioFrame>,
         model: Arc<RwLock<Option<AsrModel>>>,
+        mut pauses: StreamPauses,
+        mut activity: StreamActivity,
         on_update: impl UpdateSink,
     ) -> Result<(), StreamingError> {
         let mut pending = Vec::with_capacity(STREAM_CHUNK_SAMPLES + 512);
         while let Ok(frame) = audio.recv() {
+            activity.observe(&frame.samples);
             pending.extend_from_slice(&frame.samples);
             while pending.len() >= STREAM_CHUNK_SAMPLES {
+                pauses.observe(&pending[..STREAM_CHUNK_SAMPLES]);
                 let text = Self::with_model(&model, |model| {
                     model.advance_streaming(&pending[..STREAM_CHUNK_SAMPLES])
                 })?;
-                Self::emit(text, &on_update)?;
+                Self::emit(pauses.mark(text), &on_update)?;
                 let remaining = pending.len() - STREAM_CHUNK_SAMPLES;
                 pending.copy_within(STREAM_CHUNK_SAMPLES.., 0);
                 pending.truncate(remaining);
             }
         }
+        activity.finish();
         if !pending.is_empty() {
+            pauses.observe(&pending);
             let text = Self::with_model(&model, |model| model.advance_streaming(&pending))?;
-            Self::emit(text, &on_update)?;
+            Self::emit(pauses.mark(text), &on_update)?;
         }
 
         let text = Self::with_model(&model, AsrModel::finish_streaming)?;
-        Self::emit(text, &on_update)
+        Self::emit(pauses.mark(text), &on_update)
+    }
+
+    /// Decodes each phrase in one offline pass once it ends, rather than
+    /// every chunk as it arrives.
+    fn run_phrases(
+        audio: Receiver<AudioFrame>,
+        model: Arc<RwLock<Option<AsrModel>>>,
+        mut pauses: StreamPauses,
+        mut activity: StreamActivity,
+        vad: VadConfig,
+        on_update: impl UpdateSink,
+    ) -> Result<(), StreamingError> {
+        let mut phrases = PhraseBuffer::new(vad, MAX_PHRASE_SAMPLES.min(vad.max_segment_samples));
+        let mut started = false;
+        while let Ok(frame) = audio.recv() {
+            activity.observe(&frame.samples);
+            pauses.observe(&frame.samples);
+            let ended = phrases.feed(&frame.samples);
+            Self::decode_phrases(&model, ended, &mut pauses, &mut started, &on_update)?;
+        }
+        activity.finish();
+        let ended = phrases.finish();
+        Self::decode_phrases(&model, ended, &mut pauses, &mut started, &on_update)
+    }
+
+    fn decode_phrases(
+        model: &RwLock<Option<AsrModel>>,
+        phrases: Vec<Vec<f32>>,
+        pauses: &mut StreamPauses,
+        started: &mut bool,
+        on_update: &impl UpdateSink,
+    ) -> Result<(), StreamingError> {
+        for phrase in phrases {
+            let text = Self::with_model(model, |model| model.transcribe_segment(&phrase))?;
+            let text = text.trim();
+            if text.is_empty() {
+                continue;
+            }
+            let text = if *started {
+                format!(" {text}")
+            } else {
+                text.to_string()
+            };
+            *started = true;
+            Self::emit(pauses.mark(text), on_update)?;
+        }
+        Ok(())
     }
 
     fn with_model<T>(
         model: &RwLock<Option<AsrModel>>,
         operation: impl FnOnce(&mut AsrModel) -> Result<T, crate::asr::AsrError>,
     ) -> Result<T, StreamingError> {
-        let mut guard = model
-            .write()
-            .map_err(|_| StreamingError::LockFailed("speech model"))?;
+        let mut guard = write_model(model);
         operation(guard.as_mut().ok_or(StreamingError::ModelNotLoaded)?).map_err(Into::into)
     }
 
@@ -84,19 +182,80 @@ impl StreamingPipeline {
         if text.is_empty() {
             return Ok(());
         }
-        on_update(TranscriptionUpdate::Append(text)).map_err(StreamingError::Output)?;
-        Ok(())
+        on_update(TranscriptPatch::Commit { append: text }).map_err(StreamingError::Output)
     }
 
     pub fn finish(&self) -> Result<(), StreamingError> {
-        let worker = self
-            .worker
-            .lock()
-            .map_err(|_| StreamingError::LockFailed("streaming worker"))?
-            .take();
+        let worker = self.worker.lock_recover().take();
         match worker {
             Some(worker) => worker.join().map_err(|_| StreamingError::WorkerPanicked)?,
             None => Ok(()),
         }
     }
 }
+
+/// Puts the pause separator before the first text decoded after a long
+/// pause. The decoder lags the audio slightly, so the break can land a word
+/// late; the final pass places it from the segment timings.
+struct StreamPauses {
+    detector: Option<PauseDetector>,
+    separator: String,
+    pending: bool,
+}
+
+impl StreamPauses {
+    fn new(pause_markers: &PauseMarkers, vad: &VadConfig) -> Self {
+        Self {
+            detector: pause_markers
+                .enabled
+                .then(|| PauseDetector::new(vad, pause_markers.min_pause_ms)),
+            separator: pause_markers.separator(),
+            pending: false,
+        }
+    }
+
+    fn observe(&mut self, samples: &[f32]) {
+        if let Some(detector) = &mut self.detector {
+            self.pending |= detector.observe(samples);
+        }
+    }
+
+    fn mark(&mut self, text: String) -> String {
+        if !self.pending || text.trim().is_empty() {
+            return text;
+        }
+        self.pending = false;
+        format!("{}{}", self.separator, text.trim_start())
+    }
+}
+
+/// Reports where speech starts and ends in the session's audio, for the
+/// recorder's talking indicator. Reported as the worker receives audio, so
+/// a busy decoder delays the reports with the drafts.
+struct StreamActivity {
+    tracker: Option<(SpeechActivityTracker, ActivitySink)>,
+}
+
+impl StreamActivity {
+    fn new(vad: VadConfig, sink: Option<ActivitySink>) -> Self {
+        Self {
+            tracker: sink.map(|sink| (SpeechActivityTracker::new(vad), sink)),
+        }
+    }
+
+    fn observe(&mut self, samples: &[f32]) {
+        if let Some((tracker, sink)) = &mut self.tracker {
+            for activity in tracker.feed(samples) {
+                sink(activity);
+            }
+        }
+    }
+
+    fn finish(&mut self) {
+        if let Some((tracker, sink)) = &mut self.tracker {
+            for activity in tracker.finish() {
+                sink(activity);
+            }
+        }
+    }
+}
@@ -0,0 +1,57 @@
+use std::sync::{
+    LockResult, Mutex, MutexGuard, PoisonError, RwLock, RwLockReadGuard, RwLockWriteGuard,
+};
+
+/// A lock whose holder panicked is still usable: the data is left as the
+/// panicking thread had it, and callers that keep decoder or buffer state
+/// repair it before use. Recovery clears the poison so it is logged once.
+pub trait MutexRecover<T> {
+    fn lock_recover(&self) -> MutexGuard<'_, T>;
+}
+
+pub trait RwLockRecover<T> {
+    fn read_recover(&self) -> RwLockReadGuard<'_, T>;
+
+    fn write_recover(&self) -> RwLockWriteGuard<'_, T> {
+        self.write_repair(|_| {})
+    }
+
+    /// Like [`RwLockRecover::write_recover`], running `repair` on the data
+    /// only when the lock was poisoned.
+    fn write_repair(&self, repair: impl FnOnce(&mut T)) -> RwLockWriteGuard<'_, T>;
+}
+
+impl<T> MutexRecover<T> for Mutex<T> {
+    fn lock_recover(&self) -> MutexGuard<'_, T> {
+        self.lock().unwrap_or_else(|poisoned| {
+            log::warn!("Recovering a lock left poisoned by a panicked thread");
+            self.clear_poison();
+            poisoned.into_inner()
+        })
+    }
+}
+
+impl<T> RwLockRecover<T> for RwLock<T> {
+    fn read_recover(&self) -> RwLockReadGuard<'_, T> {
+        self.read().unwrap_or_else(|poisoned| {
+            log::warn!("Recovering a lock left poisoned by a panicked thread");
+            self.clear_poison();
+            poisoned.into_inner()
+        })
+    }
+
+    fn write_repair(&self, repair: impl FnOnce(&mut T)) -> RwLockWriteGuard<'_, T> {
+        self.write().unwrap_or_else(|poisoned| {
+            log::warn!("Recovering a lock left poisoned by a panicked thread");
+            self.clear_poison();
+            let mut guard = poisoned.into_inner();
+            repair(&mut guard);
+            guard
+        })
+    }
+}
+
+/// Takes the guard back from a poisoned condition variable wait.
+pub fn recover<G>(result: LockResult<G>) -> G {
+    result.unwrap_or_else(PoisonError::into_inner)
+}
@@ -0,0 +1,35 @@
+use serde::{Deserialize, Serialize};
+
+/// What happens when the window focused at recording start has lost focus by
+/// the time its text is typed.
+#[derive(Clone, Copy, Debug, Default, Deserialize, Eq, PartialEq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum TargetLock {
+    /// Type into whatever is focused.
+    #[default]
+    Off,
+    /// Bring the original window back first, stopping if that fails.
+    Refocus,
+    /// Stop typing; the transcript goes to the clipboard instead.
+    Abort,
+}
+
+/// The step before typing a chunk, decided by [`check_target`].
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum TargetCheck {
+    Type,
+    Refocus,
+    Abort,
+}
+
+/// Compares the window locked at recording start with the one focused now.
+/// Either being unknown, as on Wayland or when the query fails, types anyway:
+/// the lock cannot tell a change from a platform without focus queries.
+pub fn check_target(lock: TargetLock, locked: Option<&str>, focused: Option<&str>) -> TargetCheck {
+    match (lock, locked, focused) {
+        (TargetLock::Off, _, _) | (_, None, _) | (_, _, None) => TargetCheck::Type,
+        (_, Some(locked), Some(focused)) if locked == focused => TargetCheck::Type,
+        (TargetLock::Refocus, _, _) => TargetCheck::Refocus,
+        (TargetLock::Abort, _, _) => TargetCheck::Abort,
+    }
+}
@@ -0,0 +1,344 @@
+use serde::{Deserialize, Serialize};
+
+/// Spoken commands interpreted in the final transcript before it is typed.
+/// Streaming output shows the words as spoken; the final pass replaces them.
+#[derive(Clone, Debug, Default, Deserialize, Eq, PartialEq, Serialize)]
+#[serde(default)]
+pub struct TextCommands {
+    /// "spell" followed by letters, NATO code words, or digits joins them
+    /// into one token, as in "spell J O H A N N" for "JOHANN".
+    pub spelling: bool,
+    /// Spoken symbols ("open paren", "arrow") are typed without spaces around
+    /// them, "camel case" and similar join the words after them, and the
+    /// punctuation the model adds is dropped.
+    pub code_mode: bool,
+}
+
+const SPELL_TRIGGER: &str = "spell";
+const CAPITAL_WORDS: [&str; 2] = ["capital", "cap"];
+
+const NATO_ALPHABET: [(&str, char); 30] = [
+    ("alfa", 'a'),
+    ("alpha", 'a'),
+    ("bravo", 'b'),
+    ("charlie", 'c'),
+    ("delta", 'd'),
+    ("echo", 'e'),
+    ("foxtrot", 'f'),
+    ("golf", 'g'),
+    ("hotel", 'h'),
+    ("india", 'i'),
+    ("juliet", 'j'),
+    ("juliett", 'j'),
+    ("kilo", 'k'),
+    ("lima", 'l'),
+    ("mike", 'm'),
+    ("november", 'n'),
+    ("oscar", 'o'),
+    ("papa", 'p'),
+    ("quebec", 'q'),
+    ("romeo", 'r'),
+    ("sierra", 's'),
+    ("tango", 't'),
+    ("uniform", 'u'),
+    ("victor", 'v'),
+    ("whiskey", 'w'),
+    ("whisky", 'w'),
+    ("x-ray", 'x'),
+    ("xray", 'x'),
+    ("yankee", 'y'),
+    ("zulu", 'z'),
+];
+
+/// Spoken symbols that may appear between spelled letters, as in an email
+/// address. They never start or end a spelled token.
+const SPELLED_SYMBOLS: [(&str, char); 5] = [
+    ("at", '@'),
+    ("dot", '.'),
+    ("dash", '-'),
+    ("hyphen", '-'),
+    ("underscore", '_'),
+];
+
+const DIGIT_WORDS: [&str; 10] = [
+    "zero", "one", "two", "three", "four", "five", "six", "seven", "eight", "nine",
+];
+
+/// Phrases code mode types as symbols. Longer phrases are matched first, so
+/// "double equals" wins over "equals".
+const CODE_SYMBOLS: [(&str, &str); 38] = [
+    ("open paren", "("),
+    ("close paren", ")"),
+    ("open bracket", "["),
+    ("close bracket", "]"),
+    ("open brace", "{"),
+    ("close brace", "}"),
+    ("less than", "<"),
+    ("greater than", ">"),
+    ("fat arrow", "=>"),
+    ("arrow", "->"),
+    ("double colon", "::"),
+    ("colon", ":"),
+    ("semicolon", ";"),
+    ("comma", ","),
+    ("dot", "."),
+    ("double equals", "=="),
+    ("not equals", "!="),
+    ("equals", "="),
+    ("plus", "+"),
+    ("minus", "-"),
+    ("dash", "-"),
+    ("star", "*"),
+    ("slash", "/"),
+    ("backslash", "\\"),
+    ("underscore", "_"),
+    ("ampersand", "&"),
+    ("pipe", "|"),
+    ("bang", "!"),
+    ("question mark", "?"),
+    ("hash", "#"),
+    ("dollar", "$"),
+    ("percent", "%"),
+    ("caret", "^"),
+    ("tilde", "~"),
+    ("at sign", "@"),
+    ("single quote", "'"),
+    ("double quote", "\""),
+    ("backtick", "`"),
+];
+
+/// Punctuation the model adds on its own; code mode relies on spoken symbols.
+const MODEL_PUNCTUATION: [char; 6] = ['.', ',', '?', '!', ';', ':'];
+
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+enum IdentifierCase {
+    Camel,
+    Pascal,
+    Snake,
+    Kebab,
+}
+
+const CASE_COMMANDS: [(&str, IdentifierCase); 4] = [
+    ("camel case", IdentifierCase::Camel),
+    ("pascal case", IdentifierCase::Pascal),
+    ("snake case", IdentifierCase::Snake),
+    ("kebab case", IdentifierCase::Kebab),
+];
+
+/// Applies the enabled commands to one stretch of transcript text. Spelling
+/// runs first, so a spelled name can sit inside code.
+pub fn interpret(text: &str, commands: &TextCommands) -> String {
+    let text = if commands.spelling {
+        apply_spelling(text)
+    } else {
+        text.to_string()
+    };
+    if commands.code_mode {
+        apply_code_mode(&text)
+    } else {
+        text
+    }
+}
+
+/// Replaces each "spell" command and the letters after it with the joined
+/// letters. A trigger without any letters after it is left as spoken.
+pub fn apply_spelling(text: &str) -> String {
+    let words = text.split_whitespace().collect::<Vec<_>>();
+    let mut output = Vec::with_capacity(words.len());
+    let mut index = 0;
+    while index < words.len() {
+        if bare(words[index]) == SPELL_TRIGGER {
+            if let Some((token, consumed)) = spelled_token(&words[index + 1..]) {
+                output.push(token);
+                index += 1 + consumed;
+                continue;
+            }
+        }
+        output.push(words[index].to_string());
+        index += 1;
+    }
+    output.join(" ")
+}
+
+/// The word lowercased without surrounding punctuation.
+fn bare(word: &str) -> String {
+    word.trim_matches(|c: char| !c.is_alphanumeric() && c != '-')
+        .to_lowercase()
+}
+
+/// Joins the spelled words at the start of `words`, returning the token and
+/// how many words it used. Punctuation after the last letter, such as a
+/// sentence-ending period, is kept.
+fn spelled_token(words: &[&str]) -> Option<(String, usize)> {
+    let mut token = String::new();
+    let mut used = 0;
+    let mut letters_end = 0;
+    let mut capital = false;
+    for (offset, word) in words.iter().enumerate() {
+        let bare_word = bare(word);
+        if CAPITAL_WORDS.contains(&bare_word.as_str()) {
+            capital = true;
+            continue;
+        }
+        if let Some(symbol) = symbol(&bare_word).filter(|_| used > 0) {
+            token.push(symbol);
+            continue;
+        }
+        let Some(letters) = letters(word) else {
+            break;
+        };
+        for letter in letters {
+            token.push(if capital {
+                letter.to_ascii_uppercase()
+            } else {
+                letter
+            });
+            capital = false;
+        }
+        used = offset + 1;
+        letters_end = token.len();
+    }
+    if used == 0 {
+        return None;
+    }
+    // Symbols and "capital" after the last letter belong to the next words.
+    token.truncate(letters_end);
+    token.push_str(trailing_punctuation(words[used - 1]));
+    Some((token, used))
+}
+
+fn trailing_punctuation(word: &str) -> &str {
+    &word[word.trim_end_matches(['.', ',', '!', '?', ';', ':']).len()..]
+}
+
+fn symbol(bare_word: &str) -> Option<char> {
+    SPELLED_SYMBOLS
+        .iter()
+        .find(|(word, _)| *word == bare_word)
+        .map(|(_, symbol)| *symbol)
+}
+
+/// The characters a spelled word stands for: a single letter or digit, a
+/// hyphenated run of them ("J-O-H-N"), a NATO code word, or a digit word.
+fn letters(word: &str) -> Option<Vec<char>> {
+    let trimmed = word.trim_matches(|c: char| !c.is_alphanumeric());
+    let lower = trimmed.to_lowercase();
+    if let Some((_, letter)) = NATO_ALPHABET.iter().find(|(code, _)| *code == lower) {
+        return Some(vec![*letter]);
+    }
+    if let Some(digit) = DIGIT_WORDS.iter().position(|digit| *digit == lower) {
+        return char::from_digit(digit as u32, 10).map(|digit| vec![digit]);
+    }
+    let parts = trimmed.split('-').collect::<Vec<_>>();
+    if parts.iter().all(|part| part.chars().count() == 1) {
+        let letters = parts
+            .iter()
+            .filter_map(|part| part.chars().next())
+            .collect::<Vec<_>>();
+        if letters.iter().all(|letter| letter.is_alphanumeric()) {
+            return Some(letters);
+        }
+    }
+    None
+}
+
+enum CodePiece {
+    Word(String),
+    Symbol(&'static str),
+}
+
+/// Types spoken symbols and case commands as code. Words are separated by a
+/// space; symbols are joined to whatever is next to them.
+pub fn apply_code_mode(text: &str) -> String {
+    let words = text
+        .split_whitespace()
+        .map(|word| word.trim_end_matches(MODEL_PUNCTUATION))
+        .filter(|word| !word.is_empty())
+        .collect::<Vec<_>>();
+    let mut pieces = Vec::with_capacity(words.len());
+    let mut index = 0;
+    while index < words.len() {
+        if let Some((symbol, length)) = match_phrase(&words[index..], &CODE_SYMBOLS) {
+            pieces.push(CodePiece::Symbol(symbol));
+            index += length;
+        } else if let Some((case, length)) = match_phrase(&words[index..], &CASE_COMMANDS) {
+            index += length;
+            let start = index;
+            while index < words.len() && !starts_command(&words[index..]) {
+                index += 1;
+            }
+            if index > start {
+                pieces.push(CodePiece::Word(join_identifier(&words[start..index], case)));
+            }
+        } else {
+            pieces.push(CodePiece::Word(words[index].to_string()));
+            index += 1;
+        }
+    }
+
+    let mut output = String::new();
+    let mut previous_word = false;
+    for piece in pieces {
+        match piece {
+            CodePiece::Word(word) => {
+                if previous_word {
+                    output.push(' ');
+                }
+                output.push_str(&word);
+                previous_word = true;
+            }
+            CodePiece::Symbol(symbol) => {
+                output.push_str(symbol);
+                previous_word = false;
+            }
+        }
+    }
+    output
+}
+
+fn starts_command(words: &[&str]) -> bool {
+    match_phrase(words, &CODE_SYMBOLS).is_some() || match_phrase(words, &CASE_COMMANDS).is_some()
+}
+
+/// The longest phrase in `table` that `words` starts with, and its length in
+/// words.
+fn match_phrase<T: Copy>(words: &[&str], table: &[(&str, T)]) -> Option<(T, usize)> {
+    table
+        .iter()
+        .filter_map(|(phrase, value)| {
+            let length = phrase.split(' ').count();
+            let matches = words.len() >= length
+                && phrase
+                    .split(' ')
+                    .zip(words)
+                    .all(|(expected, word)| word.eq_ignore_ascii_case(expected));
+            matches.then_some((*value, length))
+        })
+        .max_by_key(|(_, length)| *length)
+}
+
+fn join_identifier(words: &[&str], case: IdentifierCase) -> String {
+    let lower = words.iter().map(|word| word.to_lowercase());
+    match case {
+        IdentifierCase::Snake => lower.collect::<Vec<_>>().join("_"),
+        IdentifierCase::Kebab => lower.collect::<Vec<_>>().join("-"),
+        IdentifierCase::Camel | IdentifierCase::Pascal => lower
+            .enumerate()
+            .map(|(position, word)| {
+                if position == 0 && case == IdentifierCase::Camel {
+                    word
+                } else {
+                    capitalize(&word)
+                }
+            })
+            .collect(),
+    }
+}
+
+fn capitalize(word: &str) -> String {
+    let mut chars = word.chars();
+    match chars.next() {
+        Some(first) => first.to_uppercase().chain(chars).collect(),
+        None => String::new(),
+    }
+}
@@ -0,0 +1,123 @@
+use serde::{Deserialize, Serialize};
+
+pub const MIN_TIME_STRETCH_PERCENT: u32 = 50;
+pub const MAX_TIME_STRETCH_PERCENT: u32 = 100;
+
+/// 30 ms frames at the model's 16 kHz, overlapped by half on output.
+const FRAME: usize = 480;
+const SYNTHESIS_HOP: usize = FRAME / 2;
+/// How far, in samples, a frame may move from its nominal position to line up
+/// with the previous one.
+const TOLERANCE: usize = 160;
+
+/// Slows speech down before the final decode, for fast speakers. Only the
+/// offline pass is stretched; streaming partials hear the audio as recorded.
+#[derive(Clone, Copy, Debug, Deserialize, Eq, PartialEq, Serialize)]
+#[serde(default)]
+pub struct TimeStretch {
+    pub enabled: bool,
+    /// Playback speed as a percentage; 90 plays the speech at 0.9x.
+    pub speed_percent: u32,
+}
+
+impl Default for TimeStretch {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            speed_percent: 90,
+        }
+    }
+}
+
+impl TimeStretch {
+    pub fn clamped(self) -> Self {
+        Self {
+            speed_percent: self
+                .speed_percent
+                .clamp(MIN_TIME_STRETCH_PERCENT, MAX_TIME_STRETCH_PERCENT),
+            ..self
+        }
+    }
+
+    /// The speed to stretch to, or `None` when audio is decoded as recorded.
+    pub fn speed(&self) -> Option<f32> {
+        let percent = self.clamped().speed_percent;
+        (self.enabled && percent < MAX_TIME_STRETCH_PERCENT).then(|| percent as f32 / 100.0)
+    }
+}
+
+/// Changes the tempo of 16 kHz speech without changing its pitch, using WSOLA:
+/// frames are read at `speed` times the output hop, each shifted within a
+/// small tolerance to where it best continues the previous frame, then
+/// overlap-added. The result is about `samples.len() / speed` long.
+pub fn stretch(samples: &[f32], speed: f32) -> Vec<f32> {
+    if !speed.is_finite() || speed <= 0.0 || speed == 1.0 || samples.len() < FRAME {
+        return samples.to_vec();
+    }
+    let output_len = (samples.len() as f32 / speed).round() as usize;
+    let analysis_hop = SYNTHESIS_HOP as f32 * speed;
+    let last_start = samples.len() - FRAME;
+    let window = hann_window();
+    let mut output = vec![0.0; output_len + FRAME];
+    let mut weights = vec![0.0; output_len + FRAME];
+    let mut previous: Option<usize> = None;
+
+    for frame in 0..=output_len / SYNTHESIS_HOP {
+        let nominal = ((frame as f32 * analysis_hop).round() as usize).min(last_start);
+        let start = match previous {
+            Some(previous) => {
+                let natural = (previous + SYNTHESIS_HOP).min(last_start);
+                best_aligned(samples, natural, nominal, last_start)
+            }
+            None => nominal,
+        };
+        let offset = frame * SYNTHESIS_HOP;
+        for (index, weight) in window.iter().enumerate() {
+            output[offset + index] += samples[start + index] * weight;
+            weights[offset + index] += weight;
+        }
+        previous = Some(start);
+    }
+
+    output.truncate(output_len);
+    for (sample, weight) in output.iter_mut().zip(weights) {
+        if weight > f32::EPSILON {
+            *sample /= weight;
+        }
+    }
+    output
+}
+
+/// The frame start near `nominal` that best matches the frame that would
+/// naturally follow the previous one.
+fn best_aligned(samples: &[f32], natural: usize, nominal: usize, last_start: usize) -> usize {
+    let target = &samples[natural..natural + FRAME];
+    let low = nominal.saturating_sub(TOLERANCE);
+    let high = (nominal + TOLERANCE).min(last_start);
+    (low..=high)
+        .map(|start| {
+            let correlation: f32 = samples[start..start + FRAME]
+                .iter()
+                .zip(target)
+                .map(|(sample, target)| sample * target)
+                .sum();
+            (start, correlation)
+        })
+        .fold((nominal, f32::NEG_INFINITY), |best, candidate| {
+            if candidate.1 > best.1 {
+                candidate
+            } else {
+                best
+            }
+        })
+        .0
+}
+
+fn hann_window() -> Vec<f32> {
+    (0..FRAME)
+        .map(|index| {
+            let phase = std::f32::consts::TAU * index as f32 / FRAME as f32;
+            0.5 - 0.5 * phase.cos()
+        })
+        .collect()
+}
@@ -0,0 +1,160 @@
+use std::ops::Range;
+
+use serde::{Deserialize, Serialize};
+
+use crate::asr::TARGET_SAMPLE_RATE;
+
+pub const MIN_PAUSE_MS: u64 = 500;
+pub const MAX_PAUSE_MS: u64 = 60_000;
+pub const DEFAULT_PAUSE_MARKER: &str = "\n\n";
+
+/// Breaks long dictations where the speaker paused. A whitespace-only marker,
+/// such as the default paragraph break, replaces the space between words; any
+/// other marker is set off by a space on each side.
+#[derive(Clone, Debug, Deserialize, Eq, PartialEq, Serialize)]
+#[serde(default)]
+pub struct PauseMarkers {
+    pub enabled: bool,
+    pub min_pause_ms: u64,
+    pub marker: String,
+}
+
+impl Default for PauseMarkers {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            min_pause_ms: 2_000,
+            marker: DEFAULT_PAUSE_MARKER.to_string(),
+        }
+    }
+}
+
+impl PauseMarkers {
+    pub fn clamped(self) -> Self {
+        Self {
+            min_pause_ms: self.min_pause_ms.clamp(MIN_PAUSE_MS, MAX_PAUSE_MS),
+            marker: if self.marker.is_empty() {
+                DEFAULT_PAUSE_MARKER.to_string()
+            } else {
+                self.marker
+            },
+            ..self
+        }
+    }
+
+    /// What goes between the words on either side of a pause.
+    pub fn separator(&self) -> String {
+        let marker = self.marker.trim();
+        if marker.is_empty() {
+            self.marker.clone()
+        } else {
+            format!(" {marker} ")
+        }
+    }
+}
+
+/// A final transcript split into the speech segments it was decoded from.
+/// Speaker and confidence stay empty until a model reports them.
+#[derive(Clone, Debug, Default, Deserialize, PartialEq, Serialize)]
+pub struct Transcript {
+    /// The language prompt the model decoded with, when it takes one.
+    pub language: Option<String>,
+    pub segments: Vec<TranscriptSegment>,
+}
+
+#[derive(Clone, Debug, Deserialize, PartialEq, Serialize)]
+pub struct TranscriptSegment {
+    /// Offsets from the start of the recording.
+    pub start_ms: u64,
+    pub end_ms: u64,
+    pub text: String,
+    pub speaker: Option<String>,
+    pub confidence: Option<f32>,
+}
+
+impl TranscriptSegment {
+    /// The segment decoded from `range` of the recording, or `None` when it
+    /// held no words.
+    pub fn decoded(range: &Range<usize>, text: &str) -> Option<Self> {
+        let text = text.trim();
+        (!text.is_empty()).then(|| Self {
+            start_ms: samples_to_ms(range.start),
+            end_ms: samples_to_ms(range.end),
+            text: text.to_string(),
+            speaker: None,
+            confidence: None,
+        })
+    }
+}
+
+fn samples_to_ms(samples: usize) -> u64 {
+    samples as u64 * 1_000 / u64::from(TARGET_SAMPLE_RATE)
+}
+
+fn ms_to_samples(ms: u64) -> usize {
+    usize::try_from(ms * u64::from(TARGET_SAMPLE_RATE) / 1_000).unwrap_or(usize::MAX)
+}
+
+impl TranscriptSegment {
+    /// The part of the recording's 16 kHz `samples` the segment was decoded
+    /// from.
+    pub fn audio<'a>(&self, samples: &'a [f32]) -> &'a [f32] {
+        let end = ms_to_samples(self.end_ms).min(samples.len());
+        let start = ms_to_samples(self.start_ms).min(end);
+        &samples[start..end]
+    }
+}
+
+impl Transcript {
+    /// Pairs each decoded text with the sample range it came from. Segments
+    /// that decoded to nothing are dropped.
+    pub fn from_segments(
+        language: Option<String>,
+        ranges: &[Range<usize>],
+        texts: Vec<String>,
+    ) -> Self {
+        let segments = ranges
+            .iter()
+            .zip(texts)
+            .filter_map(|(range, text)| TranscriptSegment::decoded(range, &text))
+            .collect();
+        Self { language, segments }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.segments.is_empty()
+    }
+
+    /// The plain text that is typed or delivered, one space between segments.
+    pub fn text(&self) -> String {
+        self.segments
+            .iter()
+            .map(|segment| segment.text.as_str())
+            .collect::<Vec<_>>()
+            .join(" ")
+    }
+
+    /// Like [`Transcript::text`], with the pause separator wherever the gap
+    /// between two segments is at least `min_pause_ms`. Segment edges carry
+    /// the VAD padding, so the gap runs a little shorter than the silence.
+    pub fn text_with_pauses(&self, pauses: &PauseMarkers) -> String {
+        if !pauses.enabled {
+            return self.text();
+        }
+        let separator = pauses.separator();
+        let mut text = String::new();
+        let mut previous_end = None;
+        for segment in &self.segments {
+            match previous_end {
+                Some(end) if segment.start_ms.saturating_sub(end) >= pauses.min_pause_ms => {
+                    text.push_str(&separator)
+                }
+                Some(_) => text.push(' '),
+                None => {}
+            }
+            text.push_str(&segment.text);
+            previous_end = Some(segment.end_ms);
+        }
+        text
+    }
+}
@@ -0,0 +1,108 @@
+use serde::{Deserialize, Serialize};
+
+use crate::errors::UserFacing;
+
+/// Bits a pedal sets while its left, middle, and right pedals are down, as
+/// sent by common transcription pedals such as the Infinity IN-USB.
+pub const PEDAL_LEFT: u8 = 0x01;
+pub const PEDAL_MIDDLE: u8 = 0x02;
+pub const PEDAL_RIGHT: u8 = 0x04;
+
+#[derive(thiserror::Error, Debug)]
+pub enum TriggerError {
+    #[error("listen for mouse buttons: {0}")]
+    MouseListener(String),
+    #[error("no extra mouse button was pressed")]
+    NoButtonPressed,
+    #[error("HID access failed: {0}")]
+    Hid(String),
+}
+
+impl UserFacing for TriggerError {
+    fn user_message(&self) -> &'static str {
+        match self {
+            Self::MouseListener(_) => {
+                "Could not watch mouse buttons. On macOS, allow SilentKeys under Accessibility."
+            }
+            Self::NoButtonPressed => {
+                "No extra mouse button was pressed. Press a side button while detecting."
+            }
+            Self::Hid(_) => {
+                "Could not read USB devices. Check the pedal's connection and permissions."
+            }
+        }
+    }
+}
+
+/// Extra record triggers that act like holding the record shortcut: a mouse
+/// button other than the primary, secondary, and wheel buttons, and a USB
+/// foot pedal.
+#[derive(Clone, Copy, Debug, Default, Deserialize, Eq, PartialEq, Serialize)]
+#[serde(default)]
+pub struct TriggerSettings {
+    /// Platform code of the mouse button, as reported by the detect command.
+    pub mouse_button: Option<u8>,
+    pub pedal: Option<PedalBinding>,
+}
+
+/// Which HID device is the pedal and which of its bits records.
+#[derive(Clone, Copy, Debug, Deserialize, Eq, PartialEq, Serialize)]
+#[serde(default)]
+pub struct PedalBinding {
+    pub vendor_id: u16,
+    pub product_id: u16,
+    /// Byte of the input report that carries the pedal bits.
+    pub report_byte: usize,
+    pub mask: u8,
+}
+
+impl Default for PedalBinding {
+    fn default() -> Self {
+        Self {
+            vendor_id: 0,
+            product_id: 0,
+            report_byte: 0,
+            mask: PEDAL_MIDDLE,
+        }
+    }
+}
+
+impl TriggerSettings {
+    /// Drops a pedal binding that names no device or no pedal.
+    pub fn normalized(self) -> Self {
+        Self {
+            pedal: self
+                .pedal
+                .filter(|pedal| (pedal.vendor_id != 0 || pedal.product_id != 0) && pedal.mask != 0),
+            ..self
+        }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.mouse_button.is_none() && self.pedal.is_none()
+    }
+}
+
+/// Turns the pedal's input reports into press and release edges.
+#[derive(Debug, Default)]
+pub struct PedalState {
+    down: bool,
+}
+
+impl PedalState {
+    /// Returns `Some(true)` when the bound pedal goes down and `Some(false)`
+    /// when it comes up. Reports too short to carry the byte change nothing.
+    pub fn update(&mut self, binding: &PedalBinding, report: &[u8]) -> Option<bool> {
+        let byte = report.get(binding.report_byte)?;
+        let down = byte & binding.mask != 0;
+        if down == self.down {
+            return None;
+        }
+        self.down = down;
+        Some(down)
+    }
+
+    pub fn is_down(&self) -> bool {
+        self.down
+    }
+}
@@ -0,0 +1,133 @@
+use std::io;
+use std::net::{Ipv4Addr, TcpListener};
+
+use serde::{Deserialize, Serialize};
+
+use crate::errors::UserFacing;
+use crate::recording::SessionId;
+use crate::transcript::Transcript;
+use crate::word_timings::{estimated_word_timings, WordTiming};
+
+pub const DEFAULT_TYPED_METADATA_PORT: u16 = 47_615;
+/// Bumped when a [`TypedCommit`] field changes meaning or goes away.
+pub const TYPED_METADATA_VERSION: u32 = 2;
+/// U+2063 INVISIBLE SEPARATOR, typed after each dictation when delimiting is
+/// on. Editors render nothing for it, and it does not occur in dictated text.
+pub const COMMIT_DELIMITER: char = '\u{2063}';
+
+/// A localhost TCP port that publishes each typed dictation with its word
+/// timings, so editor plugins can attach voice metadata to the text they
+/// receive as keystrokes. With `delimiter` on, an invisible separator is
+/// typed after each dictation, marking where one commit's text ends.
+#[derive(Clone, Copy, Debug, Deserialize, Eq, PartialEq, Serialize)]
+pub struct TypedMetadataSettings {
+    pub enabled: bool,
+    pub port: u16,
+    #[serde(default)]
+    pub delimiter: bool,
+}
+
+impl Default for TypedMetadataSettings {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            port: DEFAULT_TYPED_METADATA_PORT,
+            delimiter: false,
+        }
+    }
+}
+
+impl TypedMetadataSettings {
+    /// `text` as it should be typed: followed by [`COMMIT_DELIMITER`] while
+    /// publishing with delimiters on.
+    pub fn delimit(&self, text: String) -> String {
+        if self.enabled && self.delimiter && !text.is_empty() {
+            let mut text = text;
+            text.push(COMMIT_DELIMITER);
+            text
+        } else {
+            text
+        }
+    }
+}
+
+#[derive(thiserror::Error, Debug)]
+pub enum TypedMetadataError {
+    #[error("typed metadata port must not be 0")]
+    InvalidPort,
+    #[error("listen on 127.0.0.1:{port}: {source}")]
+    Bind {
+        port: u16,
+        #[source]
+        source: io::Error,
+    },
+}
+
+impl UserFacing for TypedMetadataError {
+    fn user_message(&self) -> &'static str {
+        match self {
+            Self::InvalidPort => "Choose an editor metadata port between 1 and 65535.",
+            Self::Bind { .. } => {
+                "Could not publish editor metadata. Check that the port is not already in use."
+            }
+        }
+    }
+}
+
+pub fn validate(settings: &TypedMetadataSettings) -> Result<(), TypedMetadataError> {
+    if settings.port == 0 {
+        return Err(TypedMetadataError::InvalidPort);
+    }
+    Ok(())
+}
+
+/// Binds the loopback interface only; anything on this machine may listen.
+pub fn listen(port: u16) -> Result<TcpListener, TypedMetadataError> {
+    TcpListener::bind((Ipv4Addr::LOCALHOST, port))
+        .map_err(|source| TypedMetadataError::Bind { port, source })
+}
+
+/// One typed dictation as published to editor plugins. `sequence` counts
+/// commits since launch, so a plugin can pair the n-th delimited stretch of
+/// text with the n-th message.
+#[derive(Clone, Debug, PartialEq, Serialize)]
+pub struct TypedCommit {
+    pub version: u32,
+    pub sequence: u64,
+    pub session_id: Option<SessionId>,
+    /// The text as typed, without the delimiter.
+    pub text: String,
+    /// Whether [`COMMIT_DELIMITER`] was typed after `text`.
+    pub delimited: bool,
+    pub language: Option<String>,
+    /// Word times interpolated across each speech segment; the model gives
+    /// no word timestamps to align them with.
+    pub estimated_words: Vec<WordTiming>,
+}
+
+impl TypedCommit {
+    pub fn new(
+        sequence: u64,
+        session_id: Option<SessionId>,
+        text: &str,
+        transcript: &Transcript,
+        settings: &TypedMetadataSettings,
+    ) -> Self {
+        Self {
+            version: TYPED_METADATA_VERSION,
+            sequence,
+            session_id,
+            text: text.to_string(),
+            delimited: settings.delimiter,
+            language: transcript.language.clone(),
+            estimated_words: estimated_word_timings(transcript),
+        }
+    }
+
+    /// The message as one line of JSON.
+    pub fn to_line(&self) -> String {
+        let mut line = serde_json::to_string(self).unwrap_or_else(|_| "{}".to_string());
+        line.push('\n');
+        line
+    }
+}
@@ -34,6 +34,8 @@ pub enum AppUpdateError {
     InstallInProgress,
     #[error("speech settings are being changed")]
     SettingsInProgress,
+    #[error("text is being typed")]
+    TypingInProgress,
     #[error("app activity state is unavailable")]
     ActivityState,
 }
@@ -46,6 +48,7 @@ impl UserFacing for AppUpdateError {
             Self::SettingsInProgress => {
                 "Speech settings are being changed. Please try the update again."
             }
+            Self::TypingInProgress => "Text is still being typed. Please try the update again.",
             Self::Initialize(_) | Self::Check(_) => {
                 "Could not check for updates. Check your connection and try again."
             }
@@ -122,6 +125,9 @@ pub async fn install_update(app: AppHandle) -> Result<bool, AppUpdateError> {
         Err(ActivityError::Busy(AppActivity::Configuring)) => {
             return Err(AppUpdateError::SettingsInProgress)
         }
+        Err(ActivityError::Busy(AppActivity::Typing)) => {
+            return Err(AppUpdateError::TypingInProgress)
+        }
         Err(ActivityError::LockFailed) => return Err(AppUpdateError::ActivityState),
     };
 
@@ -0,0 +1,392 @@
+use std::borrow::Cow;
+use std::ops::Range;
+
+use serde::{Deserialize, Serialize};
+
+use crate::asr::TARGET_SAMPLE_RATE;
+
+const SAMPLES_PER_MS: usize = TARGET_SAMPLE_RATE as usize / 1_000;
+
+/// Energy-based voice activity detection over 16 kHz mono audio.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct VadConfig {
+    pub frame_samples: usize,
+    /// RMS level at or above which a frame counts as speech.
+    pub threshold: f32,
+    /// Silence shorter than this stays inside the surrounding segment.
+    pub min_silence_samples: usize,
+    /// Speech kept on both sides of each segment so word edges are not clipped.
+    pub padding_samples: usize,
+    /// Longer speech runs are split so each segment fits one decoder pass.
+    pub max_segment_samples: usize,
+}
+
+impl Default for VadConfig {
+    fn default() -> Self {
+        Self {
+            frame_samples: 30 * SAMPLES_PER_MS,
+            threshold: 0.01,
+            min_silence_samples: 500 * SAMPLES_PER_MS,
+            padding_samples: 150 * SAMPLES_PER_MS,
+            max_segment_samples: 30_000 * SAMPLES_PER_MS,
+        }
+    }
+}
+
+pub const MIN_VAD_THRESHOLD: f32 = 0.001;
+pub const MAX_VAD_THRESHOLD: f32 = 0.2;
+pub const MIN_NOISE_MARGIN_DB: f32 = 3.0;
+pub const MAX_NOISE_MARGIN_DB: f32 = 30.0;
+pub const MIN_INPUT_GAIN: f32 = 0.25;
+pub const MAX_INPUT_GAIN: f32 = 8.0;
+/// Calibration boosts quiet inputs until their noise floor reaches this
+/// level (-60 dBFS); louder inputs are never attenuated.
+const CALIBRATION_NOISE_TARGET: f32 = 0.001;
+/// Share of calibration frames at or below the reported noise floor, so a
+/// single cough or click does not raise it.
+const NOISE_FLOOR_PERCENTILE: f32 = 0.9;
+
+/// Detector and input level settings chosen by calibrating the microphone.
+#[derive(Clone, Copy, Debug, Deserialize, PartialEq, Serialize)]
+#[serde(default)]
+pub struct VadSettings {
+    /// RMS level, after gain, at or above which a frame counts as speech.
+    pub threshold: f32,
+    /// How far above the measured noise floor calibration puts the threshold.
+    pub noise_margin_db: f32,
+    /// Multiplier applied to microphone samples before detection and
+    /// transcription.
+    pub gain: f32,
+}
+
+impl Default for VadSettings {
+    fn default() -> Self {
+        Self {
+            threshold: VadConfig::default().threshold,
+            noise_margin_db: 12.0,
+            gain: 1.0,
+        }
+    }
+}
+
+impl VadSettings {
+    pub fn clamped(self) -> Self {
+        let defaults = Self::default();
+        let finite = |value: f32, default: f32| if value.is_finite() { value } else { default };
+        Self {
+            threshold: finite(self.threshold, defaults.threshold)
+                .clamp(MIN_VAD_THRESHOLD, MAX_VAD_THRESHOLD),
+            noise_margin_db: finite(self.noise_margin_db, defaults.noise_margin_db)
+                .clamp(MIN_NOISE_MARGIN_DB, MAX_NOISE_MARGIN_DB),
+            gain: finite(self.gain, defaults.gain).clamp(MIN_INPUT_GAIN, MAX_INPUT_GAIN),
+        }
+    }
+
+    pub fn config(&self) -> VadConfig {
+        VadConfig {
+            threshold: self.threshold,
+            ..VadConfig::default()
+        }
+    }
+}
+
+/// What a few seconds of ambient noise say about the microphone.
+#[derive(Clone, Copy, Debug, PartialEq, Serialize)]
+pub struct NoiseCalibration {
+    /// Frame RMS that most of the recording stayed under.
+    pub noise_floor: f32,
+    /// Loudest frame RMS, to spot noise bursts during calibration.
+    pub peak: f32,
+    pub recommended: VadSettings,
+}
+
+/// Recommends settings from `samples` of room noise recorded without gain.
+/// Returns `None` for recordings shorter than one frame.
+pub fn calibrate(samples: &[f32], noise_margin_db: f32) -> Option<NoiseCalibration> {
+    let config = VadConfig::default();
+    let mut levels = samples
+        .chunks_exact(config.frame_samples)
+        .map(frame_rms)
+        .collect::<Vec<_>>();
+    if levels.is_empty() {
+        return None;
+    }
+    levels.sort_by(f32::total_cmp);
+    let index = ((levels.len() - 1) as f32 * NOISE_FLOOR_PERCENTILE).round() as usize;
+    let noise_floor = levels[index];
+    let peak = levels[levels.len() - 1];
+
+    let gain = if noise_floor > 0.0 {
+        (CALIBRATION_NOISE_TARGET / noise_floor).clamp(1.0, MAX_INPUT_GAIN)
+    } else {
+        1.0
+    };
+    let margin = 10f32.powf(noise_margin_db / 20.0);
+    let recommended = VadSettings {
+        threshold: noise_floor * gain * margin,
+        noise_margin_db,
+        gain,
+    }
+    .clamped();
+    Some(NoiseCalibration {
+        noise_floor,
+        peak,
+        recommended,
+    })
+}
+
+pub fn frame_rms(frame: &[f32]) -> f32 {
+    if frame.is_empty() {
+        return 0.0;
+    }
+    (frame.iter().map(|sample| sample * sample).sum::<f32>() / frame.len() as f32).sqrt()
+}
+
+/// Returns ordered, non-overlapping sample ranges that contain speech.
+pub fn speech_segments(samples: &[f32], config: &VadConfig) -> Vec<Range<usize>> {
+    let frame_samples = config.frame_samples.max(1);
+    let mut segments = Vec::new();
+    let mut current: Option<Range<usize>> = None;
+    let mut silence = 0;
+
+    for (index, frame) in samples.chunks(frame_samples).enumerate() {
+        let start = index * frame_samples;
+        let end = start + frame.len();
+        if frame_rms(frame) >= config.threshold {
+            silence = 0;
+            match &mut current {
+                Some(segment) => segment.end = end,
+                None => current = Some(start..end),
+            }
+        } else if let Some(segment) = &current {
+            silence += frame.len();
+            if silence >= config.min_silence_samples {
+                segments.push(segment.clone());
+                current = None;
+                silence = 0;
+            }
+        }
+    }
+    segments.extend(current);
+
+    let segments = pad_and_merge(segments, samples.len(), config.padding_samples);
+    split_long(segments, config.max_segment_samples)
+}
+
+/// Keeps only the padded speech segments, dropping leading and trailing
+/// silence and shortening long pauses to the padding on either side. Audio in
+/// which no speech is detected is returned unchanged, so quiet speech below
+/// the threshold still reaches the decoder.
+pub fn trim_silence<'a>(samples: &'a [f32], config: &VadConfig) -> Cow<'a, [f32]> {
+    let segments = speech_segments(samples, config);
+    if segments.is_empty() || (segments.len() == 1 && segments[0] == (0..samples.len())) {
+        return Cow::Borrowed(samples);
+    }
+    Cow::Owned(
+        segments
+            .into_iter()
+            .flat_map(|segment| samples[segment].iter().copied())
+            .collect(),
+    )
+}
+
+/// Follows silence across streamed audio to notice when speech resumes after
+/// a long pause. Only pauses after some speech count, so leading silence is
+/// never reported.
+#[derive(Clone, Debug)]
+pub struct PauseDetector {
+    threshold: f32,
+    frame_samples: usize,
+    min_pause_samples: usize,
+    silent_samples: usize,
+    heard_speech: bool,
+}
+
+impl PauseDetector {
+    pub fn new(config: &VadConfig, min_pause_ms: u64) -> Self {
+        Self {
+            threshold: config.threshold,
+            frame_samples: config.frame_samples.max(1),
+            min_pause_samples: min_pause_ms as usize * SAMPLES_PER_MS,
+            silent_samples: 0,
+            heard_speech: false,
+        }
+    }
+
+    /// Returns whether speech resumed in `samples` after a pause of at least
+    /// the configured length.
+    pub fn observe(&mut self, samples: &[f32]) -> bool {
+        let mut resumed = false;
+        for frame in samples.chunks(self.frame_samples) {
+            if frame_rms(frame) >= self.threshold {
+                resumed |= self.heard_speech && self.silent_samples >= self.min_pause_samples;
+                self.heard_speech = true;
+                self.silent_samples = 0;
+            } else {
+                self.silent_samples += frame.len();
+            }
+        }
+        resumed
+    }
+}
+
+/// What a [`VadSegmenter`] noticed in the audio fed to it. Times are
+/// milliseconds from the first sample fed.
+#[derive(Clone, Debug, PartialEq, Serialize)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum VadEvent {
+    /// One per analysis frame, for level meters.
+    Frame {
+        at_ms: u64,
+        rms: f32,
+        speech: bool,
+    },
+    SpeechStart {
+        at_ms: u64,
+    },
+    /// Closes the segment once the silence after it reaches the configured
+    /// minimum, or when the segmenter is finished.
+    SpeechEnd {
+        start_ms: u64,
+        end_ms: u64,
+    },
+}
+
+/// Incremental form of [`speech_segments`] for audio that arrives in pieces.
+/// Segments are reported without padding or splitting, as soon as they are
+/// known.
+#[derive(Clone, Debug)]
+pub struct VadSegmenter {
+    config: VadConfig,
+    pending: Vec<f32>,
+    /// Samples already analysed, excluding `pending`.
+    position: usize,
+    speech: Option<Range<usize>>,
+    silence: usize,
+}
+
+impl VadSegmenter {
+    pub fn new(config: VadConfig) -> Self {
+        Self {
+            pending: Vec::with_capacity(config.frame_samples.max(1)),
+            config,
+            position: 0,
+            speech: None,
+            silence: 0,
+        }
+    }
+
+    pub fn config(&self) -> &VadConfig {
+        &self.config
+    }
+
+    /// Analyses every complete frame in `samples` plus any left over from
+    /// the previous call.
+    pub fn feed(&mut self, samples: &[f32]) -> Vec<VadEvent> {
+        let frame_samples = self.config.frame_samples.max(1);
+        let mut events = Vec::new();
+        let mut samples = samples;
+        if !self.pending.is_empty() {
+            let needed = (frame_samples - self.pending.len()).min(samples.len());
+            self.pending.extend_from_slice(&samples[..needed]);
+            samples = &samples[needed..];
+            if self.pending.len() < frame_samples {
+                return events;
+            }
+            let mut frame = std::mem::take(&mut self.pending);
+            self.analyse(&frame, &mut events);
+            frame.clear();
+            self.pending = frame;
+        }
+        let mut frames = samples.chunks_exact(frame_samples);
+        for frame in &mut frames {
+            self.analyse(frame, &mut events);
+        }
+        self.pending.extend_from_slice(frames.remainder());
+        events
+    }
+
+    /// Analyses the partial frame left over and closes an open segment.
+    pub fn finish(&mut self) -> Vec<VadEvent> {
+        let mut events = Vec::new();
+        if !self.pending.is_empty() {
+            let frame = std::mem::take(&mut self.pending);
+            self.analyse(&frame, &mut events);
+        }
+        if let Some(speech) = self.speech.take() {
+            events.push(speech_end(&speech));
+        }
+        self.silence = 0;
+        events
+    }
+
+    fn analyse(&mut self, frame: &[f32], events: &mut Vec<VadEvent>) {
+        let start = self.position;
+        let end = start + frame.len();
+        self.position = end;
+        let rms = frame_rms(frame);
+        let speech = rms >= self.config.threshold;
+        events.push(VadEvent::Frame {
+            at_ms: to_ms(start),
+            rms,
+            speech,
+        });
+        if speech {
+            self.silence = 0;
+            match &mut self.speech {
+                Some(segment) => segment.end = end,
+                None => {
+                    events.push(VadEvent::SpeechStart {
+                        at_ms: to_ms(start),
+                    });
+                    self.speech = Some(start..end);
+                }
+            }
+        } else if let Some(segment) = &self.speech {
+            self.silence += frame.len();
+            if self.silence >= self.config.min_silence_samples {
+                events.push(speech_end(segment));
+                self.speech = None;
+                self.silence = 0;
+            }
+        }
+    }
+}
+
+fn speech_end(segment: &Range<usize>) -> VadEvent {
+    VadEvent::SpeechEnd {
+        start_ms: to_ms(segment.start),
+        end_ms: to_ms(segment.end),
+    }
+}
+
+fn to_ms(samples: usize) -> u64 {
+    (samples / SAMPLES_PER_MS) as u64
+}
+
+fn pad_and_merge(segments: Vec<Range<usize>>, len: usize, padding: usize) -> Vec<Range<usize>> {
+    let mut merged: Vec<Range<usize>> = Vec::with_capacity(segments.len());
+    for segment in segments {
+        let padded = segment.start.saturating_sub(padding)..(segment.end + padding).min(len);
+        match merged.last_mut() {
+            Some(previous) if previous.end >= padded.start => previous.end = padded.end,
+            _ => merged.push(padded),
+        }
+    }
+    merged
+}
+
+fn split_long(segments: Vec<Range<usize>>, max_samples: usize) -> Vec<Range<usize>> {
+    if max_samples == 0 {
+        return segments;
+    }
+    segments
+        .into_iter()
+        .flat_map(|segment| {
+            segment
+                .clone()
+                .step_by(max_samples)
+                .map(move |start| start..(start + max_samples).min(segment.end))
+        })
+        .collect()
+}
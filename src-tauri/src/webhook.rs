@@ -0,0 +1,185 @@
+use std::thread;
+use std::time::Duration;
+
+use hmac::{Hmac, Mac};
+use serde::{Deserialize, Serialize};
+use sha2::Sha256;
+use url::Url;
+
+use crate::errors::UserFacing;
+use crate::transcript::TranscriptSegment;
+
+pub const SIGNATURE_HEADER: &str = "X-SilentKeys-Signature";
+pub const TIMESTAMP_HEADER: &str = "X-SilentKeys-Timestamp";
+
+const MAX_ATTEMPTS: u32 = 3;
+const RETRY_BACKOFF: Duration = Duration::from_secs(1);
+const REQUEST_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// Where finished dictations are posted. The signing secret is not saved
+/// here but in the system keychain.
+#[derive(Clone, Debug, Default, Deserialize, Eq, PartialEq, Serialize)]
+pub struct WebhookSettings {
+    pub enabled: bool,
+    pub url: String,
+}
+
+/// What the webhook settings UI reads and writes: the saved settings and the
+/// signing secret from the keychain. An empty secret sends requests unsigned.
+#[derive(Clone, Debug, Default, Deserialize, Eq, PartialEq, Serialize)]
+pub struct WebhookConfig {
+    #[serde(flatten)]
+    pub settings: WebhookSettings,
+    pub secret: String,
+}
+
+#[derive(Clone, Debug, Serialize)]
+pub struct DictationPayload {
+    pub text: String,
+    pub language: Option<String>,
+    /// The timed segments `text` was joined from.
+    pub segments: Vec<TranscriptSegment>,
+    pub audio_duration_ms: u64,
+    /// The settings profile in use, if any.
+    pub profile: Option<String>,
+    /// RFC 3339 time the recording started, derived from its length.
+    pub recorded_at: String,
+    /// RFC 3339 time the final transcript was ready.
+    pub finished_at: String,
+}
+
+#[derive(thiserror::Error, Debug)]
+pub enum WebhookError {
+    #[error("webhook URL must be an http:// or https:// URL with a host: {0}")]
+    InvalidUrl(String),
+    #[error("serialize webhook payload: {0}")]
+    Serialize(#[from] serde_json::Error),
+    #[error("webhook request failed: {0}")]
+    Request(String),
+    #[error("webhook returned HTTP {0}")]
+    Status(u16),
+    #[error("sign webhook payload: {0}")]
+    Signing(String),
+    #[error("keychain: {0}")]
+    Keychain(String),
+}
+
+impl WebhookError {
+    fn is_retryable(&self) -> bool {
+        match self {
+            Self::Request(_) => true,
+            Self::Status(status) => *status == 429 || *status >= 500,
+            Self::InvalidUrl(_) | Self::Serialize(_) | Self::Signing(_) | Self::Keychain(_) => {
+                false
+            }
+        }
+    }
+}
+
+impl UserFacing for WebhookError {
+    fn user_message(&self) -> &'static str {
+        match self {
+            Self::InvalidUrl(_) => {
+                "Enter a valid webhook URL that starts with http:// or https://."
+            }
+            Self::Serialize(_) | Self::Request(_) | Self::Status(_) | Self::Signing(_) => {
+                "Could not send the dictation to the webhook."
+            }
+            Self::Keychain(_) => {
+                "Could not reach the system keychain, which holds the webhook secret."
+            }
+        }
+    }
+}
+
+/// Parses `url` as an absolute `http` or `https` URL with a host, so a typo
+/// is refused when settings are saved rather than when a request is sent.
+pub fn parse_http_url(url: &str) -> Option<Url> {
+    Url::parse(url.trim())
+        .ok()
+        .filter(|url| matches!(url.scheme(), "http" | "https") && url.host().is_some())
+}
+
+pub fn validate_url(url: &str) -> Result<(), WebhookError> {
+    match parse_http_url(url) {
+        Some(_) => Ok(()),
+        None => Err(WebhookError::InvalidUrl(url.trim().to_string())),
+    }
+}
+
+/// Signs `"{timestamp}.{body}"` with HMAC-SHA256 so receivers can reject
+/// replayed payloads.
+pub fn signature(secret: &str, timestamp: &str, body: &[u8]) -> Result<String, WebhookError> {
+    let mut mac = <Hmac<Sha256> as Mac>::new_from_slice(secret.as_bytes())
+        .map_err(|error| WebhookError::Signing(error.to_string()))?;
+    mac.update(timestamp.as_bytes());
+    mac.update(b".");
+    mac.update(body);
+    Ok(format!("sha256={:x}", mac.finalize().into_bytes()))
+}
+
+/// Posts `payload`, retrying transport failures, 429, and 5xx responses with
+/// a linear backoff.
+pub fn send(
+    settings: &WebhookSettings,
+    secret: &str,
+    payload: &DictationPayload,
+) -> Result<(), WebhookError> {
+    validate_url(&settings.url)?;
+    let body = serde_json::to_vec(payload)?;
+    let config = ureq::config::Config::builder()
+        .timeout_global(Some(REQUEST_TIMEOUT))
+        .build();
+    let agent = ureq::Agent::new_with_config(config);
+
+    let mut attempt = 1;
+    loop {
+        match post(&agent, settings, secret, &body) {
+            Ok(()) => return Ok(()),
+            Err(error) if error.is_retryable() && attempt < MAX_ATTEMPTS => {
+                log::warn!("Webhook attempt {attempt}/{MAX_ATTEMPTS} failed: {error}");
+                thread::sleep(RETRY_BACKOFF * attempt);
+                attempt += 1;
+            }
+            Err(error) => return Err(error),
+        }
+    }
+}
+
+fn post(
+    agent: &ureq::Agent,
+    settings: &WebhookSettings,
+    secret: &str,
+    body: &[u8],
+) -> Result<(), WebhookError> {
+    let timestamp = chrono::Utc::now().timestamp().to_string();
+    let mut request = agent
+        .post(settings.url.trim())
+        .header("Content-Type", "application/json")
+        .header(TIMESTAMP_HEADER, &timestamp);
+    if !secret.is_empty() {
+        request = request.header(SIGNATURE_HEADER, &signature(secret, &timestamp, body)?);
+    }
+    match request.send(body) {
+        Ok(_) => Ok(()),
+        Err(ureq::Error::StatusCode(status)) => Err(WebhookError::Status(status)),
+        Err(error) => Err(WebhookError::Request(error.to_string())),
+    }
+}
+
+/// Sends in the background so a slow endpoint never delays typing.
+#[cfg(feature = "desktop")]
+pub(crate) fn dispatch(settings: WebhookSettings, secret: String, payload: DictationPayload) {
+    if !settings.enabled || settings.url.trim().is_empty() {
+        return;
+    }
+    let result = thread::Builder::new()
+        .name("dictation-webhook".to_string())
+        .spawn(move || match send(&settings, &secret, &payload) {
+            Ok(()) => log::info!("Dictation webhook delivered"),
+            Err(error) => log::warn!("Dictation webhook failed: {error}"),
+        });
+    if let Err(error) = result {
+        log::warn!("Could not start dictation webhook worker: {error}");
+    }
+}
@@ -0,0 +1,80 @@
+use serde::Serialize;
+
+use crate::transcript::Transcript;
+
+/// A word with the stretch of the recording it was spoken in.
+#[derive(Clone, Debug, PartialEq, Serialize)]
+pub struct WordTiming {
+    pub word: String,
+    pub start_ms: u64,
+    pub end_ms: u64,
+}
+
+#[derive(Serialize)]
+struct WordTimingsJson<'a> {
+    language: Option<&'a str>,
+    /// Always set: the times are interpolated, not aligned.
+    estimated: bool,
+    words: &'a [WordTiming],
+}
+
+/// Spreads each segment's time across its words by their length. The model
+/// returns text without token or frame times, so these are estimates from
+/// where a word falls in its speech segment, not alignment data.
+pub fn estimated_word_timings(transcript: &Transcript) -> Vec<WordTiming> {
+    let mut timings = Vec::new();
+    for segment in &transcript.segments {
+        let words: Vec<&str> = segment.text.split_whitespace().collect();
+        let total: u64 = words.iter().map(|word| char_count(word)).sum();
+        if total == 0 {
+            continue;
+        }
+        let duration = segment.end_ms.saturating_sub(segment.start_ms);
+        let mut spoken = 0;
+        for word in words {
+            let start_ms = segment.start_ms + duration * spoken / total;
+            spoken += char_count(word);
+            timings.push(WordTiming {
+                word: word.to_string(),
+                start_ms,
+                end_ms: segment.start_ms + duration * spoken / total,
+            });
+        }
+    }
+    timings
+}
+
+/// `{"language": …, "estimated": true, "words": [{"word", "start_ms",
+/// "end_ms"}, …]}`.
+pub fn to_json(language: Option<&str>, words: &[WordTiming]) -> String {
+    serde_json::to_string_pretty(&WordTimingsJson {
+        language,
+        estimated: true,
+        words,
+    })
+    .unwrap_or_else(|_| "{}".to_string())
+}
+
+/// An Audacity label track: one `start<TAB>end<TAB>word` line per word, in
+/// seconds, which **File > Import > Labels** reads.
+pub fn to_audacity_labels(words: &[WordTiming]) -> String {
+    words
+        .iter()
+        .map(|timing| {
+            format!(
+                "{}\t{}\t{}\n",
+                seconds(timing.start_ms),
+                seconds(timing.end_ms),
+                timing.word
+            )
+        })
+        .collect()
+}
+
+fn seconds(ms: u64) -> String {
+    format!("{}.{:03}000", ms / 1_000, ms % 1_000)
+}
+
+fn char_count(word: &str) -> u64 {
+    word.chars().count() as u64
+}
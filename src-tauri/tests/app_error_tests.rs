@@ -0,0 +1,44 @@
+#![cfg(feature = "desktop")]
+
+use serde_json::json;
+use silent_keys_lib::asr::AsrError;
+use silent_keys_lib::desktop::ShortcutError;
+use silent_keys_lib::errors::{AppError, UserFacing};
+use silent_keys_lib::recording::RecordingError;
+use silent_keys_lib::settings::SettingsServiceError;
+use silent_keys_lib::settings_lock::SettingsLockError;
+
+#[test]
+fn commands_fail_with_a_code_and_the_user_message() {
+    let error = AppError::from(RecordingError::AlreadyRecording);
+
+    assert_eq!(
+        serde_json::to_value(&error).unwrap(),
+        json!({
+            "code": "recording",
+            "message": RecordingError::AlreadyRecording.user_message(),
+        })
+    );
+}
+
+#[test]
+fn wrapped_errors_report_the_subsystem_that_failed() {
+    let locked = SettingsServiceError::from(SettingsLockError::Locked);
+
+    assert_eq!(AppError::from(locked).code(), "settings_lock");
+    assert_eq!(
+        AppError::from(SettingsLockError::Locked).code(),
+        "settings_lock"
+    );
+    assert_eq!(AppError::from(AsrError::Cancelled).code(), "speech_model");
+}
+
+#[test]
+fn details_are_logged_but_not_sent() {
+    let error = AppError::from(ShortcutError::Invalid("unknown key Foo".to_string()));
+    let sent = serde_json::to_string(&error).unwrap();
+
+    assert_eq!(error.code(), "shortcut");
+    assert!(error.to_string().contains("Foo"));
+    assert!(!sent.contains("Foo"));
+}
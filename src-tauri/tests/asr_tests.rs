@@ -1,6 +1,5 @@
 use silent_keys_lib::asr::{language_candidates_for_tests, language_options_for_tests, AsrError};
 use silent_keys_lib::errors::UserFacing;
-use silent_keys_lib::settings::{Settings, DEFAULT_ASR_LANGUAGE};
 
 #[test]
 fn system_locale_candidates_fall_back_from_region_to_language() {
@@ -28,8 +27,11 @@ fn model_language_options_use_the_first_declared_code_per_prompt() {
     assert_eq!(languages, ["en-GB", "en-US", "hi-IN"]);
 }
 
+#[cfg(feature = "desktop")]
 #[test]
 fn english_us_is_the_default_language() {
+    use silent_keys_lib::settings::{Settings, DEFAULT_ASR_LANGUAGE};
+
     assert_eq!(Settings::default().asr_language, DEFAULT_ASR_LANGUAGE);
 }
 
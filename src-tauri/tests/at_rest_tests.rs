@@ -0,0 +1,71 @@
+mod common;
+
+use common::temp_dir;
+use silent_keys_lib::at_rest::{
+    is_sealed, open, read_to_string, seal, write, AtRestError, AtRestKey, SEALED_MAGIC,
+};
+
+fn key() -> AtRestKey {
+    AtRestKey::derive(b"0123456789abcdef0123456789abcdef")
+}
+
+#[test]
+fn sealed_bytes_open_to_the_original() {
+    let plaintext = b"{\"at\":\"2026-01-02T03:04:05\",\"text\":\"hello\"}\n";
+    let sealed = seal(&key(), plaintext).unwrap();
+
+    assert!(is_sealed(&sealed));
+    assert!(!sealed
+        .windows(plaintext.len())
+        .any(|window| window == plaintext));
+    assert_eq!(open(Some(&key()), sealed).unwrap(), plaintext);
+}
+
+#[test]
+fn each_seal_uses_a_fresh_nonce() {
+    assert_ne!(
+        seal(&key(), b"same").unwrap(),
+        seal(&key(), b"same").unwrap()
+    );
+}
+
+#[test]
+fn plain_files_are_read_as_they_are() {
+    let plaintext = b"RIFF....WAVEfmt ".to_vec();
+    assert!(!is_sealed(&plaintext));
+    assert_eq!(open(None, plaintext.clone()).unwrap(), plaintext);
+    assert_eq!(open(Some(&key()), plaintext.clone()).unwrap(), plaintext);
+}
+
+#[test]
+fn sealed_files_need_the_right_key() {
+    let sealed = seal(&key(), b"secret").unwrap();
+    let other = AtRestKey::derive(b"another secret");
+
+    assert_eq!(open(None, sealed.clone()), Err(AtRestError::NoKey));
+    assert_eq!(open(Some(&other), sealed), Err(AtRestError::Tampered));
+}
+
+#[test]
+fn damaged_files_are_rejected() {
+    let mut sealed = seal(&key(), b"secret").unwrap();
+    let last = sealed.len() - 1;
+    sealed[last] ^= 1;
+    assert_eq!(open(Some(&key()), sealed), Err(AtRestError::Tampered));
+
+    let mut truncated = SEALED_MAGIC.to_vec();
+    truncated.extend_from_slice(&[0; 10]);
+    assert_eq!(open(Some(&key()), truncated), Err(AtRestError::Truncated));
+}
+
+#[test]
+fn writes_replace_the_file_without_leaving_a_temporary_one() {
+    let dir = temp_dir("silentkeys_at_rest");
+    let path = dir.join("history.jsonl");
+    write(&path, "first\n").unwrap();
+    write(&path, "second\n").unwrap();
+
+    assert_eq!(read_to_string(&path).unwrap(), "second\n");
+    assert_eq!(std::fs::read_dir(&dir).unwrap().count(), 1);
+    std::fs::remove_dir_all(dir).unwrap();
+}
@@ -0,0 +1,191 @@
+use std::io::Cursor;
+use std::path::PathBuf;
+
+use silent_keys_lib::audio_file::{
+    decode_wav, encode_float_wav, encode_wav, read_wav, AudioFileError, WavEncoding, WavExport,
+    NORMALIZE_PEAK,
+};
+use silent_keys_lib::vad::VadConfig;
+
+fn wav_bytes<S: hound::Sample + Copy>(spec: hound::WavSpec, samples: &[S]) -> Vec<u8> {
+    let mut bytes = Cursor::new(Vec::new());
+    let mut writer = hound::WavWriter::new(&mut bytes, spec).expect("WAV writer should open");
+    for sample in samples {
+        writer.write_sample(*sample).expect("sample should write");
+    }
+    writer.finalize().expect("WAV should finalize");
+    bytes.into_inner()
+}
+
+fn spec(channels: u16, bits: u16, format: hound::SampleFormat) -> hound::WavSpec {
+    hound::WavSpec {
+        channels,
+        sample_rate: 44_100,
+        bits_per_sample: bits,
+        sample_format: format,
+    }
+}
+
+fn assert_close(actual: &[f32], expected: &[f32]) {
+    assert_eq!(actual.len(), expected.len());
+    for (actual, expected) in actual.iter().zip(expected) {
+        assert!((actual - expected).abs() < 1e-3, "{actual} vs {expected}");
+    }
+}
+
+#[test]
+fn decodes_integer_pcm_at_every_width() {
+    let expected = [0.0, 0.5, -0.5, -1.0];
+
+    let wav = decode_wav(&wav_bytes(
+        spec(1, 16, hound::SampleFormat::Int),
+        &[0i16, 16_384, -16_384, -32_768],
+    ))
+    .expect("16-bit WAV should decode");
+    assert_eq!(wav.sample_rate, 44_100);
+    assert_close(&wav.samples, &expected);
+
+    let wav = decode_wav(&wav_bytes(
+        spec(1, 24, hound::SampleFormat::Int),
+        &[0i32, 4_194_304, -4_194_304, -8_388_608],
+    ))
+    .expect("24-bit WAV should decode");
+    assert_close(&wav.samples, &expected);
+
+    let wav = decode_wav(&wav_bytes(
+        spec(1, 8, hound::SampleFormat::Int),
+        &[0i8, 64, -64, -128],
+    ))
+    .expect("8-bit WAV should decode");
+    assert_close(&wav.samples, &expected);
+}
+
+#[test]
+fn mixes_float_stereo_down_to_mono() {
+    let wav = decode_wav(&wav_bytes(
+        spec(2, 32, hound::SampleFormat::Float),
+        &[0.5f32, 0.25, -1.0, 1.0],
+    ))
+    .expect("float WAV should decode");
+
+    assert_close(&wav.samples, &[0.375, 0.0]);
+}
+
+#[test]
+fn rejects_what_it_cannot_decode() {
+    assert!(matches!(
+        decode_wav(b"ID3\x03 not a wav at all"),
+        Err(AudioFileError::NotWav(_))
+    ));
+
+    let mut truncated = wav_bytes(spec(1, 16, hound::SampleFormat::Int), &[0i16; 4]);
+    truncated.truncate(36);
+    assert!(matches!(
+        decode_wav(&truncated),
+        Err(AudioFileError::NotWav(_))
+    ));
+
+    let mut alaw = wav_bytes(spec(1, 16, hound::SampleFormat::Int), &[0i16; 4]);
+    // Format tag 6 is A-law.
+    alaw[20] = 6;
+    assert!(matches!(
+        decode_wav(&alaw),
+        Err(AudioFileError::Unsupported {
+            format: 6,
+            bits: 16
+        })
+    ));
+
+    let empty = wav_bytes::<i16>(spec(1, 16, hound::SampleFormat::Int), &[]);
+    assert!(matches!(decode_wav(&empty), Err(AudioFileError::Empty)));
+}
+
+#[test]
+fn reads_the_sample_recording_at_model_rate() {
+    let samples = read_wav(&PathBuf::from("tests/samples/jfk.wav")).expect("sample should read");
+    let reference = hound::WavReader::open("tests/samples/jfk.wav")
+        .expect("sample should open")
+        .duration();
+
+    assert_eq!(samples.len(), reference as usize);
+}
+
+#[test]
+fn missing_files_are_read_errors() {
+    assert!(matches!(
+        read_wav(&std::env::temp_dir().join("silentkeys-missing.wav")),
+        Err(AudioFileError::Read { .. })
+    ));
+}
+
+#[test]
+fn encoded_clips_decode_to_the_same_audio() {
+    let samples = [0.0, 0.5, -0.5, 1.5, -1.0];
+    let wav = decode_wav(&encode_wav(&samples, 16_000)).expect("clip should decode");
+
+    assert_eq!(wav.sample_rate, 16_000);
+    assert_close(&wav.samples, &[0.0, 0.5, -0.5, 1.0, -1.0]);
+}
+
+#[test]
+fn float_clips_keep_samples_exactly() {
+    let samples = [0.0, 0.123_456, -0.5, 1.5];
+    let wav = decode_wav(&encode_float_wav(&samples, 16_000)).expect("clip should decode");
+
+    assert_eq!(wav.sample_rate, 16_000);
+    assert_eq!(wav.samples, samples);
+    assert!(encode_float_wav(&samples, 16_000).len() > encode_wav(&samples, 16_000).len());
+}
+
+#[test]
+fn exports_trim_edge_silence_and_normalize_the_peak() {
+    let vad = VadConfig::default();
+    // Whole 30 ms detector frames, so the speech starts on a frame edge.
+    let silence = vec![0.0; 14_400];
+    let speech = vec![0.25; 9_600];
+    let samples = [silence.as_slice(), &speech, &silence].concat();
+    let export = WavExport {
+        trim_silence: true,
+        normalize: true,
+        encoding: WavEncoding::Pcm16,
+    };
+
+    let (trimmed, audio) = export.prepare(&samples, &vad);
+
+    assert_eq!(trimmed, 14_400 - vad.padding_samples);
+    assert_eq!(audio.len(), 9_600 + 2 * vad.padding_samples);
+    let peak = audio
+        .iter()
+        .fold(0.0_f32, |peak, sample| peak.max(sample.abs()));
+    assert!((peak - NORMALIZE_PEAK).abs() < 1e-6);
+}
+
+#[test]
+fn default_exports_keep_the_audio_as_recorded() {
+    let samples = [vec![0.0; 16_000], vec![0.25; 8_000]].concat();
+    let export = WavExport::default();
+
+    assert_eq!(
+        export.prepare(&samples, &VadConfig::default()),
+        (0, samples.clone())
+    );
+    assert_eq!(
+        export.encode(&samples, 16_000),
+        encode_wav(&samples, 16_000)
+    );
+}
+
+#[test]
+fn silent_clips_are_neither_trimmed_nor_amplified() {
+    let samples = vec![0.0; 4_000];
+    let export = WavExport {
+        trim_silence: true,
+        normalize: true,
+        encoding: WavEncoding::Float32,
+    };
+
+    assert_eq!(
+        export.prepare(&samples, &VadConfig::default()),
+        (0, samples.clone())
+    );
+}
@@ -1,4 +1,6 @@
-use silent_keys_lib::audio_processing::AudioProcessor;
+use silent_keys_lib::audio_processing::{
+    AudioProcessor, ProcessingStage, ResamplerQuality, PROCESS_CHUNK_SIZE,
+};
 
 #[test]
 fn matching_sample_rate_preserves_all_samples() {
@@ -41,6 +43,39 @@ fn resampling_one_second_preserves_duration() {
     );
 }
 
+#[test]
+fn fast_resampler_preserves_duration() {
+    let input: Vec<f32> = (0..44_100)
+        .map(|index| ((index as f32 / 44_100.0) * std::f32::consts::TAU * 440.0).sin())
+        .collect();
+    let mut output = Vec::new();
+    let mut processor = AudioProcessor::with_quality(44_100, 16_000, ResamplerQuality::Fast)
+        .expect("processor should initialize");
+
+    for chunk in input.chunks(441) {
+        processor
+            .process(chunk, |frame| output.extend(frame.samples))
+            .expect("processing should succeed");
+    }
+    processor
+        .flush(|frame| output.extend(frame.samples))
+        .expect("flush should succeed");
+
+    assert!(output.iter().all(|sample| sample.is_finite()));
+    assert!(
+        (15_520..=16_480).contains(&output.len()),
+        "expected about one second at 16 kHz, got {} samples",
+        output.len()
+    );
+}
+
+#[test]
+fn automatic_resampler_quality_resolves_to_a_concrete_choice() {
+    assert_ne!(ResamplerQuality::Auto.resolve(), ResamplerQuality::Auto);
+    assert_eq!(ResamplerQuality::Fast.resolve(), ResamplerQuality::Fast);
+    assert_eq!(ResamplerQuality::High.resolve(), ResamplerQuality::High);
+}
+
 #[test]
 fn two_minute_resampling_preserves_duration() {
     const SECONDS: usize = 120;
@@ -92,3 +127,34 @@ fn twenty_minute_passthrough_drops_no_samples() {
 
     assert_eq!(emitted, TOTAL_SAMPLES);
 }
+
+#[test]
+fn mono_source_at_the_target_rate_passes_through_without_holding_samples() {
+    let mut processor = AudioProcessor::for_source(16_000, 1, 16_000, ResamplerQuality::High)
+        .expect("processor should initialize");
+    assert!(processor.is_bypassed());
+    assert_eq!(processor.stage(), None);
+
+    let input: Vec<f32> = (0..1_001).map(|index| index as f32 / 1_001.0).collect();
+    let mut output = Vec::new();
+    processor
+        .process(&input, |frame| {
+            assert!(frame.samples.len() <= PROCESS_CHUNK_SIZE);
+            output.extend(frame.samples);
+        })
+        .expect("processing should succeed");
+
+    assert_eq!(output, input);
+}
+
+#[test]
+fn other_sources_are_rechunked_or_resampled() {
+    let stereo = AudioProcessor::for_source(16_000, 2, 16_000, ResamplerQuality::High)
+        .expect("processor should initialize");
+    assert_eq!(stereo.stage(), Some(ProcessingStage::Rechunk));
+
+    let mono = AudioProcessor::for_source(48_000, 1, 16_000, ResamplerQuality::Fast)
+        .expect("processor should initialize");
+    assert_eq!(mono.stage(), Some(ProcessingStage::Resample));
+    assert!(!mono.is_bypassed());
+}
@@ -0,0 +1,57 @@
+use std::path::Path;
+
+use silent_keys_lib::autostart::{
+    desktop_entry, launch_agent_plist, run_key_value, AutostartSettings, AUTOSTART_ARG,
+};
+
+#[test]
+fn only_login_launches_start_hidden() {
+    let settings = AutostartSettings {
+        enabled: true,
+        start_in_tray: true,
+    };
+
+    assert!(settings.starts_hidden(&["silent-keys", AUTOSTART_ARG]));
+    assert!(!settings.starts_hidden(&["silent-keys"]));
+    assert!(!settings.starts_hidden(&[AUTOSTART_ARG]));
+    assert!(!AutostartSettings {
+        start_in_tray: false,
+        ..settings
+    }
+    .starts_hidden(&["silent-keys", AUTOSTART_ARG]));
+    assert!(!AutostartSettings {
+        enabled: false,
+        ..settings
+    }
+    .starts_hidden(&["silent-keys", AUTOSTART_ARG]));
+}
+
+#[test]
+fn launch_agent_escapes_the_executable_path() {
+    let plist = launch_agent_plist(
+        "com.kti.silent-keys",
+        Path::new("/Applications/Keys & Co.app/Contents/MacOS/silent-keys"),
+    );
+
+    assert!(plist.contains("<string>com.kti.silent-keys</string>"));
+    assert!(plist
+        .contains("<string>/Applications/Keys &amp; Co.app/Contents/MacOS/silent-keys</string>"));
+    assert!(plist.contains(&format!("<string>{AUTOSTART_ARG}</string>")));
+    assert!(plist.contains("<key>RunAtLoad</key>\n    <true/>"));
+}
+
+#[test]
+fn desktop_entry_quotes_reserved_characters() {
+    let entry = desktop_entry("SilentKeys", Path::new("/opt/Silent Keys/$bin"));
+
+    assert!(entry.starts_with("[Desktop Entry]\n"));
+    assert!(entry.contains("Exec=\"/opt/Silent Keys/\\\\$bin\" --autostart\n"));
+}
+
+#[test]
+fn run_key_value_quotes_the_executable() {
+    assert_eq!(
+        run_key_value(Path::new(r"C:\Program Files\SilentKeys\silent-keys.exe")),
+        r#""C:\Program Files\SilentKeys\silent-keys.exe" --autostart"#
+    );
+}
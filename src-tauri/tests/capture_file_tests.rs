@@ -0,0 +1,86 @@
+mod common;
+
+use chrono::NaiveDate;
+use common::temp_dir;
+use silent_keys_lib::at_rest::{self, AtRestKey};
+use silent_keys_lib::capture_file::{append_entry, check_writable, CaptureFileError};
+
+fn at(hour: u32, minute: u32) -> chrono::NaiveDateTime {
+    NaiveDate::from_ymd_opt(2026, 3, 4)
+        .unwrap()
+        .and_hms_opt(hour, minute, 0)
+        .unwrap()
+}
+
+#[test]
+fn capture_creates_file_and_separates_entries() {
+    let dir = temp_dir("capture_append");
+    let note = dir.join("daily.md");
+    let path = note.to_str().unwrap();
+
+    append_entry(path, " first thought ", at(9, 5)).expect("first entry");
+    append_entry(path, "second thought", at(17, 30)).expect("second entry");
+
+    assert_eq!(
+        std::fs::read_to_string(&note).unwrap(),
+        "## 2026-03-04 09:05\n\nfirst thought\n\n## 2026-03-04 17:30\n\nsecond thought\n"
+    );
+    let _ = std::fs::remove_dir_all(dir);
+}
+
+#[test]
+fn capture_starts_heading_on_its_own_line() {
+    let dir = temp_dir("capture_existing");
+    let note = dir.join("daily.md");
+    std::fs::write(&note, "# Today").unwrap();
+
+    append_entry(note.to_str().unwrap(), "entry", at(8, 0)).expect("entry");
+
+    assert_eq!(
+        std::fs::read_to_string(&note).unwrap(),
+        "# Today\n\n## 2026-03-04 08:00\n\nentry\n"
+    );
+    let _ = std::fs::remove_dir_all(dir);
+}
+
+#[test]
+fn capture_reports_unusable_paths() {
+    assert!(matches!(
+        check_writable("notes/daily.md"),
+        Err(CaptureFileError::InvalidPath(_))
+    ));
+
+    let dir = temp_dir("capture_missing_folder");
+    let missing = dir.join("missing").join("daily.md");
+    assert!(matches!(
+        check_writable(missing.to_str().unwrap()),
+        Err(CaptureFileError::Write { .. })
+    ));
+    let _ = std::fs::remove_dir_all(dir);
+}
+
+#[test]
+fn encryption_never_rewrites_a_plain_note() {
+    let dir = temp_dir("capture_plain_note");
+    let note = dir.join("daily.md");
+    std::fs::write(&note, "# My notes\n").unwrap();
+    #[cfg(unix)]
+    let inode = std::os::unix::fs::MetadataExt::ino(&std::fs::metadata(&note).unwrap());
+
+    at_rest::configure(Some(AtRestKey::derive(b"capture test secret")), true);
+    append_entry(note.to_str().unwrap(), "kept plain", at(8, 0)).expect("entry");
+
+    let written = std::fs::read(&note).unwrap();
+    assert!(!at_rest::is_sealed(&written));
+    assert_eq!(
+        String::from_utf8(written).unwrap(),
+        "# My notes\n\n## 2026-03-04 08:00\n\nkept plain\n"
+    );
+    // Appended in place rather than replaced by a rewritten copy.
+    #[cfg(unix)]
+    assert_eq!(
+        std::os::unix::fs::MetadataExt::ino(&std::fs::metadata(&note).unwrap()),
+        inode
+    );
+    let _ = std::fs::remove_dir_all(dir);
+}
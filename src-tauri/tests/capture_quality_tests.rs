@@ -0,0 +1,98 @@
+mod common;
+
+use common::tone;
+use silent_keys_lib::recording::{analyze_capture, QualityFlag, SessionId};
+
+const SAMPLE_RATE: u32 = 16_000;
+
+/// Deterministic white noise, which carries every frequency up to 8 kHz.
+fn noise(seconds: f32, amplitude: f32) -> Vec<f32> {
+    let mut state = 0x2545_f491_u32;
+    (0..(SAMPLE_RATE as f32 * seconds) as usize)
+        .map(|_| {
+            state ^= state << 13;
+            state ^= state >> 17;
+            state ^= state << 5;
+            (state as f32 / u32::MAX as f32 * 2.0 - 1.0) * amplitude
+        })
+        .collect()
+}
+
+fn session() -> SessionId {
+    SessionId::from_raw_for_tests(7)
+}
+
+#[test]
+fn clean_wideband_capture_is_good() {
+    let quality = analyze_capture(session(), &noise(2.0, 0.2), 48_000);
+
+    assert_eq!(quality.flag, QualityFlag::Good);
+    assert!(quality.suggestions.is_empty());
+    assert_eq!(quality.clipped_frame_ratio, 0.0);
+    assert!(quality.dc_offset.abs() < 0.01);
+    assert_eq!(quality.bandwidth_hz, Some(8_000));
+}
+
+#[test]
+fn clipping_is_reported_with_the_share_of_frames() {
+    let mut samples = noise(2.0, 0.2);
+    for sample in &mut samples[..8_000] {
+        *sample = (*sample * 10.0).clamp(-1.0, 1.0);
+    }
+    let quality = analyze_capture(session(), &samples, 48_000);
+
+    assert_eq!(quality.flag, QualityFlag::Poor);
+    assert!((quality.clipped_frame_ratio - 0.25).abs() < 0.01);
+    assert!(quality.suggestions[0].starts_with("Input clipped in 25% of frames."));
+}
+
+#[test]
+fn dc_offset_is_reported() {
+    let samples: Vec<f32> = noise(1.0, 0.1).iter().map(|sample| sample + 0.05).collect();
+    let quality = analyze_capture(session(), &samples, 48_000);
+
+    assert_eq!(quality.flag, QualityFlag::Poor);
+    assert!((quality.dc_offset - 0.05).abs() < 0.005);
+    assert!(quality.suggestions[0].contains("DC offset of +0.05"));
+}
+
+#[test]
+fn narrowband_input_is_reported() {
+    let samples: Vec<f32> = tone(300.0, 32_000, 0.3)
+        .iter()
+        .zip(tone(3_000.0, 32_000, 0.05))
+        .map(|(low, high)| low + high)
+        .collect();
+    let quality = analyze_capture(session(), &samples, 48_000);
+
+    assert_eq!(quality.flag, QualityFlag::Poor);
+    assert!(quality.bandwidth_hz.is_some_and(|hz| hz < 5_500));
+    assert!(quality.suggestions[0].contains("like a phone call"));
+}
+
+#[test]
+fn low_device_rate_is_reported_instead_of_bandwidth() {
+    let quality = analyze_capture(session(), &tone(300.0, 16_000, 0.3), 8_000);
+
+    assert_eq!(quality.suggestions.len(), 1);
+    assert!(quality.suggestions[0].starts_with("The input device records at 8 kHz."));
+}
+
+#[test]
+fn silence_has_no_bandwidth_and_a_stable_wire_shape() {
+    let quality = analyze_capture(session(), &[0.0; 16_000], 16_000);
+
+    assert_eq!(quality.bandwidth_hz, None);
+    assert_eq!(
+        serde_json::to_value(&quality).expect("capture quality should serialize"),
+        serde_json::json!({
+            "session_id": 7,
+            "flag": "good",
+            "device_sample_rate": 16_000,
+            "clipped_frame_ratio": 0.0,
+            "dc_offset": 0.0,
+            "bandwidth_hz": null,
+            "suggestions": [],
+        })
+    );
+}
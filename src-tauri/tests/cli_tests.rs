@@ -0,0 +1,288 @@
+use std::path::{Path, PathBuf};
+
+use silent_keys_lib::autostart::AUTOSTART_ARG;
+use silent_keys_lib::cli::{
+    output_path, parse_cli, parse_deep_link, transcript_path, CliCommand, CliError, MAX_CLI_ARGS,
+};
+use silent_keys_lib::streaming::{RecordingMode, StreamingStrategy};
+
+fn cwd() -> PathBuf {
+    std::env::temp_dir()
+}
+
+#[test]
+fn parses_recording_commands() {
+    let commands = parse_cli(
+        &[
+            "silentkeys",
+            "--start-recording",
+            "--stop-recording",
+            "--toggle-recording",
+        ],
+        &cwd(),
+        true,
+    );
+
+    assert_eq!(
+        commands,
+        Ok(vec![
+            CliCommand::StartRecording,
+            CliCommand::StopRecording,
+            CliCommand::ToggleRecording,
+        ])
+    );
+}
+
+#[test]
+fn a_plain_launch_has_no_commands() {
+    assert_eq!(parse_cli(&["silentkeys"], &cwd(), true), Ok(vec![]));
+    assert_eq!(
+        parse_cli(&["silentkeys", AUTOSTART_ARG, "-psn_0_12345"], &cwd(), true),
+        Ok(vec![])
+    );
+}
+
+#[test]
+fn transcribe_file_resolves_relative_paths_against_the_caller() {
+    let cwd = cwd();
+    let absolute = cwd.join("meeting.WAV");
+
+    assert_eq!(
+        parse_cli(
+            &["silentkeys", "--transcribe-file", "notes/memo.wav"],
+            &cwd,
+            true
+        ),
+        Ok(vec![CliCommand::TranscribeFile(cwd.join("notes/memo.wav"))])
+    );
+    assert_eq!(
+        parse_cli(
+            &[
+                "silentkeys".to_string(),
+                format!("--transcribe-file={}", absolute.display()),
+            ],
+            Path::new("relative"),
+            true
+        ),
+        Ok(vec![CliCommand::TranscribeFile(absolute)])
+    );
+    assert_eq!(
+        parse_cli(
+            &["silentkeys", "--transcribe-file", "memo.wav"],
+            Path::new(""),
+            true
+        ),
+        Err(CliError::RelativePath(PathBuf::from("memo.wav")))
+    );
+}
+
+#[test]
+fn export_word_timings_takes_a_wav_path_like_transcribe_file() {
+    let cwd = cwd();
+
+    assert_eq!(
+        parse_cli(
+            &[
+                "silentkeys",
+                "--export-word-timings",
+                "talk.wav",
+                "--export-word-timings=clips/intro.wav",
+            ],
+            &cwd,
+            true
+        ),
+        Ok(vec![
+            CliCommand::ExportWordTimings(cwd.join("talk.wav")),
+            CliCommand::ExportWordTimings(cwd.join("clips/intro.wav")),
+        ])
+    );
+    assert_eq!(
+        parse_cli(&["silentkeys", "--export-word-timings="], &cwd, true),
+        Err(CliError::MissingPath("--export-word-timings"))
+    );
+    assert_eq!(
+        output_path(Path::new("/clips/intro.wav"), "words.json", 2),
+        PathBuf::from("/clips/intro (2).words.json")
+    );
+}
+
+#[test]
+fn transcribe_file_needs_a_wav_path() {
+    for args in [
+        &["silentkeys", "--transcribe-file"][..],
+        &["silentkeys", "--transcribe-file", "--start-recording"],
+        &["silentkeys", "--transcribe-file="],
+    ] {
+        assert_eq!(
+            parse_cli(args, &cwd(), true),
+            Err(CliError::MissingPath("--transcribe-file")),
+            "{args:?}"
+        );
+    }
+    assert_eq!(
+        parse_cli(
+            &["silentkeys", "--transcribe-file", "/etc/passwd"],
+            &cwd(),
+            true
+        ),
+        Err(CliError::NotWav(PathBuf::from("/etc/passwd")))
+    );
+}
+
+#[test]
+fn rejects_the_whole_line_on_anything_unknown() {
+    assert_eq!(
+        parse_cli(
+            &["silentkeys", "--start-recording", "--srart"],
+            &cwd(),
+            true
+        ),
+        Err(CliError::UnknownArgument("--srart".to_string()))
+    );
+    assert_eq!(
+        parse_cli(
+            &["silentkeys", "--start-recording", "silentkeys://rekord"],
+            &cwd(),
+            true
+        ),
+        Err(CliError::UnknownLink("silentkeys://rekord".to_string()))
+    );
+    assert_eq!(
+        parse_cli(&["silentkeys", "otherapp://record"], &cwd(), true),
+        Err(CliError::UnknownArgument("otherapp://record".to_string()))
+    );
+}
+
+#[test]
+fn deep_links_start_recordings_under_a_profile_and_mode() {
+    assert_eq!(
+        parse_cli(
+            &[
+                "silentkeys",
+                "silentkeys://record?mode=streaming&profile=code"
+            ],
+            &cwd(),
+            true
+        ),
+        Ok(vec![
+            CliCommand::SwitchProfile("code".to_string()),
+            CliCommand::StartRecordingIn(RecordingMode::Streaming),
+        ])
+    );
+    assert_eq!(
+        parse_deep_link("SilentKeys://toggle?profile=Meeting%20notes&mode=Batch"),
+        Ok(vec![
+            CliCommand::SwitchProfile("Meeting notes".to_string()),
+            CliCommand::ToggleRecordingIn(RecordingMode::Batch),
+        ])
+    );
+    assert_eq!(
+        parse_deep_link("silentkeys:record?mode=phrase"),
+        Ok(vec![CliCommand::StartRecordingIn(RecordingMode::Phrase)])
+    );
+    assert_eq!(
+        parse_deep_link("silentkeys://stop/"),
+        Ok(vec![CliCommand::StopRecording])
+    );
+    assert_eq!(
+        parse_deep_link("silentkeys://record?profile="),
+        Ok(vec![CliCommand::StartRecording])
+    );
+}
+
+#[test]
+fn deep_links_on_the_command_line_need_the_setting() {
+    assert_eq!(
+        parse_cli(
+            &["silentkeys", "--start-recording", "silentkeys://stop"],
+            &cwd(),
+            false
+        ),
+        Err(CliError::DeepLinksDisabled)
+    );
+    assert_eq!(
+        parse_cli(&["silentkeys", "--start-recording"], &cwd(), false),
+        Ok(vec![CliCommand::StartRecording])
+    );
+}
+
+#[test]
+fn deep_links_reject_what_they_do_not_understand() {
+    assert_eq!(
+        parse_deep_link("silentkeys://record?mode=fast"),
+        Err(CliError::UnknownMode("fast".to_string()))
+    );
+    assert_eq!(
+        parse_deep_link("silentkeys://record?language=de"),
+        Err(CliError::UnknownParameter("language".to_string()))
+    );
+    assert_eq!(
+        parse_deep_link("silentkeys://stop?mode=batch"),
+        Err(CliError::UnknownParameter("mode".to_string()))
+    );
+    for link in [
+        "silentkeys://record/now",
+        "silentkeys://delete",
+        "https://record",
+    ] {
+        assert_eq!(
+            parse_deep_link(link),
+            Err(CliError::UnknownLink(link.to_string())),
+            "{link}"
+        );
+    }
+    let long = format!("silentkeys://record?profile={}", "a".repeat(5_000));
+    assert_eq!(parse_deep_link(&long), Err(CliError::ArgumentTooLong));
+}
+
+#[test]
+fn recording_modes_pick_the_live_decoding() {
+    assert_eq!(
+        RecordingMode::Streaming.live_strategy(),
+        Some(StreamingStrategy::Continuous)
+    );
+    assert_eq!(
+        RecordingMode::Phrase.live_strategy(),
+        Some(StreamingStrategy::Phrase)
+    );
+    assert_eq!(RecordingMode::Batch.live_strategy(), None);
+}
+
+#[test]
+fn bounds_forwarded_command_lines() {
+    let mut args = vec!["silentkeys".to_string()];
+    args.extend(std::iter::repeat_n(
+        "--toggle-recording".to_string(),
+        MAX_CLI_ARGS,
+    ));
+    assert_eq!(
+        parse_cli(&args, &cwd(), true).map(|commands| commands.len()),
+        Ok(MAX_CLI_ARGS)
+    );
+
+    args.push("--toggle-recording".to_string());
+    assert_eq!(
+        parse_cli(&args, &cwd(), true),
+        Err(CliError::TooManyArguments)
+    );
+
+    let long = format!("{}.wav", "a".repeat(5_000));
+    assert_eq!(
+        parse_cli(&["silentkeys", "--transcribe-file", &long], &cwd(), true),
+        Err(CliError::ArgumentTooLong)
+    );
+}
+
+#[test]
+fn transcripts_sit_beside_the_audio_without_overwriting() {
+    let audio = Path::new("/recordings/standup.wav");
+
+    assert_eq!(
+        transcript_path(audio, 1),
+        PathBuf::from("/recordings/standup.txt")
+    );
+    assert_eq!(
+        transcript_path(audio, 3),
+        PathBuf::from("/recordings/standup (3).txt")
+    );
+}
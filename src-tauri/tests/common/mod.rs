@@ -0,0 +1,29 @@
+//! Helpers shared by the integration tests. Each test binary uses only some
+//! of them.
+#![allow(dead_code)]
+
+use std::path::PathBuf;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+/// A sine tone at 16 kHz, `samples` long and peaking at `amplitude`. 220 Hz
+/// at 0.5 reads as speech.
+pub fn tone(frequency: f32, samples: usize, amplitude: f32) -> Vec<f32> {
+    (0..samples)
+        .map(|index| {
+            amplitude * ((index as f32 / 16_000.0) * std::f32::consts::TAU * frequency).sin()
+        })
+        .collect()
+}
+
+/// A fresh directory under the system temp dir, unique to this run.
+pub fn temp_dir(name: &str) -> PathBuf {
+    let dir = std::env::temp_dir().join(format!(
+        "{name}_{}",
+        SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .unwrap()
+            .as_nanos()
+    ));
+    std::fs::create_dir_all(&dir).expect("temp dir should be creatable");
+    dir
+}
@@ -0,0 +1,232 @@
+use std::io::{Read, Write};
+use std::time::{Duration, Instant};
+
+use silent_keys_lib::companion::{
+    client_pairing, client_session, encode_server_message, from_hex, hello_proof, new_device,
+    parse_client_message, to_hex, validate, Challenge, ClientMessage, CompanionError,
+    CompanionSettings, CompanionStatus, PairingCode, SealedReader, SealedWriter, ServerMessage,
+};
+use silent_keys_lib::streaming::TranscriptPatch;
+
+#[test]
+fn handshake_lines_parse_into_client_messages() {
+    assert_eq!(
+        parse_client_message(r#"{"type":"pair","device_name":"Phone","pake":"ab","proof":"cd"}"#)
+            .unwrap(),
+        ClientMessage::Pair {
+            device_name: "Phone".to_string(),
+            pake: "ab".to_string(),
+            proof: "cd".to_string(),
+        }
+    );
+    assert_eq!(
+        parse_client_message("{\"type\":\"hello\",\"device_id\":\"ab\",\"proof\":\"cd\"}\n")
+            .unwrap(),
+        ClientMessage::Hello {
+            device_id: "ab".to_string(),
+            proof: "cd".to_string(),
+        }
+    );
+    assert!(matches!(
+        parse_client_message(r#"{"type":"hello","device_id":"ab","token":"cd"}"#),
+        Err(CompanionError::InvalidMessage(_))
+    ));
+}
+
+#[test]
+fn server_messages_are_newline_terminated_json() {
+    assert_eq!(
+        encode_server_message(&ServerMessage::Ready),
+        "{\"type\":\"ready\"}\n"
+    );
+    assert_eq!(
+        encode_server_message(&ServerMessage::Transcript {
+            update: TranscriptPatch::Commit {
+                append: "hello".to_string(),
+            },
+        }),
+        "{\"type\":\"transcript\",\"update\":{\"kind\":\"commit\",\"append\":\"hello\"}}\n"
+    );
+}
+
+#[test]
+fn pairing_proves_the_code_and_hands_over_the_key_sealed() {
+    let mut challenge = Challenge::generate(Some("123456")).unwrap();
+    let ServerMessage::Challenge {
+        nonce,
+        pake: Some(desktop_message),
+    } = challenge.message()
+    else {
+        panic!("expected a pairing challenge");
+    };
+    let nonce = from_hex(&nonce).unwrap();
+
+    let (client_message, client_key) = client_pairing("123456", &nonce, &desktop_message).unwrap();
+    let proof = client_key.proof("Phone").unwrap();
+    let (code, desktop_key) = challenge.pairing_key(&client_message).unwrap();
+    assert_eq!(code, "123456");
+    assert!(desktop_key.proves("Phone", &proof));
+    assert!(!desktop_key.proves("Laptop", &proof));
+    // The exchange runs once per challenge.
+    assert!(matches!(
+        challenge.pairing_key(&client_message),
+        Err(CompanionError::PairingRejected)
+    ));
+
+    let (_, device_key) = new_device("Phone", "2026-01-01T00:00:00+00:00").unwrap();
+    let sealed = desktop_key.seal_device_key(&device_key).unwrap();
+    assert!(!sealed.contains(&to_hex(&device_key)));
+    assert_eq!(client_key.open_device_key(&sealed).unwrap(), device_key);
+}
+
+#[test]
+fn a_wrong_code_agrees_on_no_key() {
+    let mut challenge = Challenge::generate(Some("123456")).unwrap();
+    let ServerMessage::Challenge {
+        nonce,
+        pake: Some(desktop_message),
+    } = challenge.message()
+    else {
+        panic!("expected a pairing challenge");
+    };
+    let nonce = from_hex(&nonce).unwrap();
+
+    let (client_message, client_key) = client_pairing("654321", &nonce, &desktop_message).unwrap();
+    let (_, desktop_key) = challenge.pairing_key(&client_message).unwrap();
+    assert!(!desktop_key.proves("Phone", &client_key.proof("Phone").unwrap()));
+    let (_, device_key) = new_device("Phone", "2026-01-01T00:00:00+00:00").unwrap();
+    let sealed = desktop_key.seal_device_key(&device_key).unwrap();
+    assert!(client_key.open_device_key(&sealed).is_err());
+
+    // Without an outstanding code there is nothing to pair against.
+    let mut idle = Challenge::generate(None).unwrap();
+    assert!(matches!(
+        idle.message(),
+        ServerMessage::Challenge { pake: None, .. }
+    ));
+    assert!(matches!(
+        idle.pairing_key(&client_message),
+        Err(CompanionError::PairingRejected)
+    ));
+}
+
+#[test]
+fn paired_devices_authenticate_only_with_their_key() {
+    let (device, key) = new_device("  Phone  ", "2026-01-01T00:00:00+00:00").unwrap();
+    let (other, other_key) = new_device("", "2026-01-01T00:00:00+00:00").unwrap();
+    assert_eq!(device.name, "Phone");
+    assert_eq!(other.name, "Companion device");
+
+    let devices = vec![device.clone(), other.clone()];
+    let challenge = Challenge::generate(None).unwrap();
+    let proof = hello_proof(&key, challenge.nonce(), &device.id).unwrap();
+    assert_eq!(
+        challenge
+            .authenticate(&devices, &device.id, &key, &proof)
+            .unwrap()
+            .id,
+        device.id
+    );
+    let wrong_key = hello_proof(&other_key, challenge.nonce(), &device.id).unwrap();
+    assert!(matches!(
+        challenge.authenticate(&devices, &device.id, &key, &wrong_key),
+        Err(CompanionError::Unauthorized)
+    ));
+    // A proof recorded on one connection does not answer another's nonce.
+    let replayed = Challenge::generate(None).unwrap();
+    assert!(replayed
+        .authenticate(&devices, &device.id, &key, &proof)
+        .is_err());
+    assert!(challenge
+        .authenticate(&devices, "unknown", &key, &proof)
+        .is_err());
+}
+
+#[test]
+fn sessions_seal_both_directions_and_reject_tampering() {
+    let (_, key) = new_device("Phone", "2026-01-01T00:00:00+00:00").unwrap();
+    let challenge = Challenge::generate(None).unwrap();
+    let (server_opener, server_sealer) = challenge.server_session(&key);
+    let (client_opener, client_sealer) = client_session(&key, challenge.nonce());
+
+    let mut sent = Vec::new();
+    let mut writer = SealedWriter::new(&mut sent, server_sealer);
+    writer
+        .write_all(encode_server_message(&ServerMessage::Ready).as_bytes())
+        .unwrap();
+    writer.write_all(b"second").unwrap();
+    assert!(!sent.windows(5).any(|window| window == b"ready"));
+    let mut received = String::new();
+    SealedReader::new(sent.as_slice(), client_opener)
+        .read_to_string(&mut received)
+        .unwrap();
+    assert_eq!(received, "{\"type\":\"ready\"}\nsecond");
+
+    let mut audio = Vec::new();
+    let frame = vec![7_u8; 70_000];
+    SealedWriter::new(&mut audio, client_sealer)
+        .write_all(&frame)
+        .unwrap();
+    let mut tampered = audio.clone();
+    let last = tampered.len() - 1;
+    tampered[last] ^= 1;
+    let mut opened = Vec::new();
+    SealedReader::new(audio.as_slice(), server_opener)
+        .read_to_end(&mut opened)
+        .unwrap();
+    assert_eq!(opened, frame);
+
+    let (server_opener, _) = challenge.server_session(&key);
+    assert!(SealedReader::new(tampered.as_slice(), server_opener)
+        .read_to_end(&mut Vec::new())
+        .is_err());
+}
+
+#[test]
+fn pairing_codes_expire_and_limit_attempts() {
+    let now = Instant::now();
+    let generated = PairingCode::generate(now).unwrap();
+    assert_eq!(generated.code().len(), 6);
+    assert!(generated.code().chars().all(|c| c.is_ascii_digit()));
+
+    let mut code = PairingCode::new("123456".to_string(), now);
+    assert!(code.check(|code| code == "123456", now).is_ok());
+    assert!(code
+        .check(|_| true, now + Duration::from_secs(121))
+        .is_err());
+
+    for _ in 0..5 {
+        assert!(matches!(
+            code.check(|_| false, now),
+            Err(CompanionError::PairingRejected)
+        ));
+    }
+    assert!(!code.is_usable(now));
+    assert!(code.check(|_| true, now).is_err());
+}
+
+#[test]
+fn status_lists_devices_and_port_zero_is_rejected() {
+    let (device, _) = new_device("Tablet", "2026-01-01T00:00:00+00:00").unwrap();
+    let settings = CompanionSettings {
+        enabled: true,
+        devices: vec![device],
+        ..CompanionSettings::default()
+    };
+    let status = serde_json::to_value(CompanionStatus::from(&settings)).unwrap();
+    assert_eq!(status["port"], 47_614);
+    assert_eq!(status["devices"][0]["name"], "Tablet");
+    assert!(serde_json::to_string(&settings)
+        .unwrap()
+        .find("key")
+        .is_none());
+
+    assert!(validate(&settings).is_ok());
+    assert!(matches!(
+        validate(&CompanionSettings {
+            port: 0,
+            ..settings
+        }),
+        Err(CompanionError::InvalidPort)
+    ));
+}
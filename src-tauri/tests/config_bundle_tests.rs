@@ -0,0 +1,114 @@
+#![cfg(feature = "desktop")]
+
+use silent_keys_lib::config_bundle::{
+    export_bundle, parse_bundle, resolve_bundle, ConfigBundleError, CONFIG_FORMAT, CONFIG_VERSION,
+};
+use silent_keys_lib::settings::Settings;
+
+fn configured() -> Settings {
+    let mut settings = Settings {
+        model_path: Some("/models/here".to_string()),
+        streaming_enabled: true,
+        asr_language: "fr-FR".to_string(),
+        ..Settings::default()
+    };
+    settings.webhook.enabled = true;
+    settings.webhook.url = "https://example.com/hook".to_string();
+    settings.companion.enabled = true;
+    settings.lookback.enabled = true;
+    settings
+}
+
+#[test]
+fn export_leaves_out_local_settings_and_secrets() {
+    let bundle = export_bundle(
+        &configured(),
+        Some("Alt+X".to_string()),
+        "2026-01-01T00:00:00Z",
+    );
+
+    assert_eq!(bundle.format, CONFIG_FORMAT);
+    assert_eq!(bundle.version, CONFIG_VERSION);
+    assert_eq!(bundle.record_shortcut.as_deref(), Some("Alt+X"));
+    assert!(!bundle.settings.contains_key("model_path"));
+    assert!(!bundle.settings.contains_key("companion"));
+    assert!(!bundle.settings.contains_key("lookback"));
+    assert!(!bundle.settings.contains_key("onboarding_completed"));
+    assert_eq!(bundle.settings["streaming_enabled"], true);
+    assert!(bundle.settings["webhook"].get("secret").is_none());
+    assert!(bundle.settings["llm_handoff"].get("api_key").is_none());
+}
+
+#[test]
+fn import_round_trips_and_keeps_local_settings() {
+    let source = configured();
+    let bundle = export_bundle(&source, Some(" Alt+X ".to_string()), "2026-01-01T00:00:00Z");
+    let json = serde_json::to_string(&bundle).unwrap();
+
+    let target = Settings {
+        model_path: Some("/other/models".to_string()),
+        ..Settings::default()
+    };
+    let imported = resolve_bundle(parse_bundle(&json).unwrap(), &target).unwrap();
+
+    assert_eq!(imported.record_shortcut.as_deref(), Some("Alt+X"));
+    assert_eq!(imported.settings.model_path, target.model_path);
+    assert_eq!(imported.settings.companion, target.companion);
+    assert_eq!(imported.settings.lookback, target.lookback);
+    assert_eq!(imported.settings.webhook.url, source.webhook.url);
+    assert_eq!(imported.settings.asr_language, "fr-FR");
+    assert!(imported.settings.streaming_enabled);
+}
+
+#[test]
+fn missing_settings_fall_back_to_defaults_and_unknown_ones_are_ignored() {
+    let json = format!(
+        r#"{{"format":"{CONFIG_FORMAT}","version":1,"exported_at":"","settings":{{"speak_transcripts":true,"hotwords":["x"]}}}}"#
+    );
+
+    let imported = resolve_bundle(parse_bundle(&json).unwrap(), &configured()).unwrap();
+
+    assert!(imported.settings.speak_transcripts);
+    assert!(!imported.settings.streaming_enabled);
+    assert!(!imported.settings.dictation_toggle_shortcut);
+    assert_eq!(imported.record_shortcut, None);
+}
+
+#[test]
+fn foreign_newer_and_invalid_bundles_are_rejected() {
+    assert!(matches!(
+        parse_bundle("not json"),
+        Err(ConfigBundleError::Parse(_))
+    ));
+    assert!(matches!(
+        parse_bundle(r#"{"format":"other","version":1}"#),
+        Err(ConfigBundleError::NotABundle)
+    ));
+    assert!(matches!(
+        parse_bundle(&format!(r#"{{"format":"{CONFIG_FORMAT}","version":0}}"#)),
+        Err(ConfigBundleError::NotABundle)
+    ));
+    assert!(matches!(
+        parse_bundle(&format!(
+            r#"{{"format":"{CONFIG_FORMAT}","version":{},"exported_at":"","settings":{{}}}}"#,
+            CONFIG_VERSION + 1
+        )),
+        Err(ConfigBundleError::UnsupportedVersion(version)) if version == CONFIG_VERSION + 1
+    ));
+
+    let wrong_type = format!(
+        r#"{{"format":"{CONFIG_FORMAT}","version":1,"exported_at":"","settings":{{"typing_pace":"fast"}}}}"#
+    );
+    assert!(matches!(
+        resolve_bundle(parse_bundle(&wrong_type).unwrap(), &Settings::default()),
+        Err(ConfigBundleError::InvalidSetting { key, .. }) if key == "typing_pace"
+    ));
+
+    let bad_port = format!(
+        r#"{{"format":"{CONFIG_FORMAT}","version":1,"exported_at":"","settings":{{"network_input":{{"enabled":true,"port":0}}}}}}"#
+    );
+    assert!(matches!(
+        resolve_bundle(parse_bundle(&bad_port).unwrap(), &Settings::default()),
+        Err(ConfigBundleError::InvalidSetting { key, .. }) if key == "network_input"
+    ));
+}
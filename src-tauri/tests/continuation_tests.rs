@@ -0,0 +1,105 @@
+use std::time::{Duration, Instant};
+
+use silent_keys_lib::continuation::{
+    continues_sentence, lowercase_sentence_start, ContinuationSettings, RecentTyping,
+    CONTEXT_CHARS, MAX_CONTINUATION_WINDOW_SECS, MIN_CONTINUATION_WINDOW_SECS,
+};
+
+#[test]
+fn recent_typing_is_kept_per_app_within_the_window() {
+    let start = Instant::now();
+    let window = Duration::from_secs(30);
+    let mut recent = RecentTyping::default();
+    recent.record("Mail", "Thanks for the notes", start);
+    recent.record("Terminal", "cargo test", start);
+
+    assert_eq!(
+        recent.context("Mail", start + Duration::from_secs(10), window),
+        Some("Thanks for the notes")
+    );
+    assert_eq!(
+        recent.context("Terminal", start, window),
+        Some("cargo test")
+    );
+    assert_eq!(recent.context("Notes", start, window), None);
+    assert_eq!(
+        recent.context("Mail", start + Duration::from_secs(31), window),
+        None
+    );
+}
+
+#[test]
+fn recent_typing_keeps_only_the_tail() {
+    let now = Instant::now();
+    let mut recent = RecentTyping::default();
+    let typed = "é".repeat(CONTEXT_CHARS + 8) + " end";
+    recent.record("Mail", &typed, now);
+
+    let tail = recent
+        .context("Mail", now, Duration::from_secs(1))
+        .expect("tail should be kept");
+    assert_eq!(tail.chars().count(), CONTEXT_CHARS);
+    assert!(tail.ends_with(" end"));
+}
+
+#[test]
+fn empty_dictations_keep_the_previous_context() {
+    let now = Instant::now();
+    let mut recent = RecentTyping::default();
+    recent.record("Mail", "and then", now);
+    recent.record("Mail", "", now);
+
+    assert_eq!(
+        recent.context("Mail", now, Duration::from_secs(1)),
+        Some("and then")
+    );
+}
+
+#[test]
+fn sentence_ends_start_fresh() {
+    assert!(continues_sentence("I was thinking"));
+    assert!(continues_sentence("first, "));
+    assert!(!continues_sentence("Done."));
+    assert!(!continues_sentence("Really?\" "));
+    assert!(!continues_sentence("Heading\n"));
+    assert!(!continues_sentence(""));
+}
+
+#[test]
+fn continuation_lowercases_the_capitalized_first_word() {
+    assert_eq!(
+        lowercase_sentence_start("And then we left."),
+        "and then we left."
+    );
+    assert_eq!(lowercase_sentence_start("  So, yes"), "  so, yes");
+    assert_eq!(lowercase_sentence_start("I think so"), "I think so");
+    assert_eq!(lowercase_sentence_start("I'm here"), "I'm here");
+    assert_eq!(lowercase_sentence_start("NASA called"), "NASA called");
+    assert_eq!(lowercase_sentence_start("already lower"), "already lower");
+    assert_eq!(lowercase_sentence_start(""), "");
+}
+
+#[test]
+fn continuation_window_is_clamped_and_off_by_default() {
+    let settings = ContinuationSettings::default();
+    assert!(!settings.enabled);
+    assert_eq!(settings.window(), None);
+
+    let short = ContinuationSettings {
+        enabled: true,
+        window_secs: 0,
+    }
+    .clamped();
+    assert_eq!(short.window_secs, MIN_CONTINUATION_WINDOW_SECS);
+    assert_eq!(
+        short.window(),
+        Some(Duration::from_secs(MIN_CONTINUATION_WINDOW_SECS))
+    );
+
+    let long = ContinuationSettings {
+        enabled: true,
+        window_secs: u64::MAX,
+    }
+    .clamped();
+    assert_eq!(long.window_secs, MAX_CONTINUATION_WINDOW_SECS);
+}
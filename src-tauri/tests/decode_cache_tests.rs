@@ -0,0 +1,126 @@
+mod common;
+
+use std::path::Path;
+
+use common::temp_dir;
+use silent_keys_lib::decode_cache::{
+    audio_fingerprint, decode_context, decode_key, DecodeCache, DecodeCacheSettings,
+    MAX_MAX_ENTRIES, MIN_MAX_ENTRIES,
+};
+use silent_keys_lib::transcript::{Transcript, TranscriptSegment};
+use silent_keys_lib::vad::VadConfig;
+
+fn transcript(text: &str) -> Transcript {
+    Transcript {
+        language: Some("en".to_string()),
+        segments: vec![TranscriptSegment {
+            start_ms: 0,
+            end_ms: 1_000,
+            text: text.to_string(),
+            speaker: None,
+            confidence: Some(0.5),
+        }],
+    }
+}
+
+#[test]
+fn identical_audio_has_the_same_fingerprint() {
+    let samples = vec![0.1, -0.2, 0.3];
+    assert_eq!(
+        audio_fingerprint(&samples),
+        audio_fingerprint(&[0.1, -0.2, 0.3])
+    );
+    assert_ne!(
+        audio_fingerprint(&samples),
+        audio_fingerprint(&[0.1, -0.2, 0.30001])
+    );
+    assert_ne!(
+        audio_fingerprint(&samples),
+        audio_fingerprint(&samples[..2])
+    );
+    assert_eq!(audio_fingerprint(&[]).len(), 64);
+}
+
+#[test]
+fn keys_change_with_anything_that_changes_the_decode() {
+    let root = Path::new("/models");
+    let vad = VadConfig::default();
+    let fingerprint = audio_fingerprint(&[0.25; 160]);
+    let base = decode_key(&fingerprint, &decode_context(root, "en", &vad, None));
+
+    assert_eq!(
+        base,
+        decode_key(&fingerprint, &decode_context(root, "en", &vad, None))
+    );
+    for context in [
+        decode_context(root, "de", &vad, None),
+        decode_context(root, "en", &vad, Some(0.9)),
+        decode_context(Path::new("/other"), "en", &vad, None),
+    ] {
+        assert_ne!(base, decode_key(&fingerprint, &context));
+    }
+}
+
+#[test]
+fn lookups_return_stored_transcripts() {
+    let mut cache = DecodeCache::default();
+    assert_eq!(cache.get("a"), None);
+
+    cache.insert("a".to_string(), transcript("first"), 4);
+    cache.insert("a".to_string(), transcript("again"), 4);
+
+    assert_eq!(cache.len(), 1);
+    assert_eq!(cache.get("a"), Some(transcript("again")));
+}
+
+#[test]
+fn the_least_recently_used_decode_is_dropped_first() {
+    let mut cache = DecodeCache::default();
+    cache.insert("a".to_string(), transcript("a"), 2);
+    cache.insert("b".to_string(), transcript("b"), 2);
+    assert!(cache.get("a").is_some());
+
+    cache.insert("c".to_string(), transcript("c"), 2);
+
+    assert_eq!(cache.get("b"), None);
+    assert!(cache.get("a").is_some());
+    assert!(cache.get("c").is_some());
+}
+
+#[test]
+fn the_cache_survives_a_round_trip_through_disk() {
+    let path = temp_dir("decode_cache").join("decode_cache.json");
+    let mut cache = DecodeCache::default();
+    cache.insert("a".to_string(), transcript("kept"), 8);
+
+    cache.save(&path).expect("cache should save");
+
+    assert_eq!(DecodeCache::load(&path), cache);
+    let _ = std::fs::remove_dir_all(path.parent().unwrap());
+}
+
+#[test]
+fn missing_or_damaged_caches_load_empty() {
+    let dir = temp_dir("decode_cache_damaged");
+    let path = dir.join("decode_cache.json");
+    assert!(DecodeCache::load(&path).is_empty());
+
+    std::fs::write(&path, "{ not json").unwrap();
+    assert!(DecodeCache::load(&path).is_empty());
+    let _ = std::fs::remove_dir_all(dir);
+}
+
+#[test]
+fn cache_size_is_clamped() {
+    let tiny = DecodeCacheSettings {
+        enabled: true,
+        max_entries: 0,
+    };
+    assert_eq!(tiny.clamped().max_entries, MIN_MAX_ENTRIES);
+    let huge = DecodeCacheSettings {
+        enabled: true,
+        max_entries: usize::MAX,
+    };
+    assert_eq!(huge.clamped().max_entries, MAX_MAX_ENTRIES);
+    assert!(!DecodeCacheSettings::default().enabled);
+}
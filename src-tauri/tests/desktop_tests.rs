@@ -1,3 +1,5 @@
+#![cfg(feature = "desktop")]
+
 use silent_keys_lib::desktop::*;
 
 #[test]
@@ -24,7 +26,7 @@ fn parse_shortcut_with_modifier() {
 fn parse_invalid_shortcut() {
     let result = parse_shortcut_str("InvalidShortcut");
     assert!(result.is_err());
-    assert!(result.unwrap_err().contains("Invalid"));
+    assert!(result.unwrap_err().to_string().contains("Invalid"));
 }
 
 #[test]
@@ -39,3 +41,85 @@ fn default_record_shortcut_returns_string() {
     assert!(!shortcut.is_empty());
     assert!(shortcut.contains("Alt") || shortcut.contains("Key"));
 }
+
+#[test]
+fn dictation_toggle_shortcut_does_not_collide_with_record_shortcut() {
+    assert_ne!(dictation_toggle_shortcut(), default_shortcut());
+    assert!(dictation_toggle_shortcut().into_string().contains("KeyZ"));
+}
+
+#[test]
+fn llm_handoff_shortcut_is_distinct_from_other_shortcuts() {
+    assert_ne!(llm_handoff_shortcut(), default_shortcut());
+    assert_ne!(llm_handoff_shortcut(), dictation_toggle_shortcut());
+}
+
+#[test]
+fn announcement_text_is_passed_as_a_final_argument() {
+    let text = r#"say "hi"; rm -rf ~ & echo $HOME"#;
+    for command in announce_commands(text) {
+        assert_eq!(command.args.last().map(String::as_str), Some(text));
+        assert!(command.args[..command.args.len() - 1]
+            .iter()
+            .all(|arg| !arg.contains(text)));
+    }
+}
+
+#[test]
+fn speech_text_is_written_to_stdin_only() {
+    let text = r#"say "hi"; rm -rf ~ & echo $HOME"#;
+    for command in speech_commands(text) {
+        assert_eq!(command.input.as_deref(), Some(text));
+        assert!(command.args.iter().all(|arg| !arg.contains(text)));
+    }
+}
+
+#[test]
+fn captured_chord_becomes_a_parseable_shortcut() {
+    use silent_keys_lib::key_suppression::{MODIFIER_ALT, MODIFIER_SHIFT};
+    use tauri_plugin_global_shortcut::Code;
+
+    let shortcut = chord_shortcut(Code::KeyZ, MODIFIER_ALT | MODIFIER_SHIFT)
+        .unwrap()
+        .into_string();
+    assert!(shortcut.to_lowercase().contains("alt"));
+    assert!(shortcut.to_lowercase().contains("shift"));
+    assert!(shortcut.contains("KeyZ"));
+    assert!(parse_shortcut_str(&shortcut).is_ok());
+}
+
+#[test]
+fn captured_typing_key_needs_a_modifier_but_a_function_key_does_not() {
+    use tauri_plugin_global_shortcut::Code;
+
+    assert!(matches!(
+        chord_shortcut(Code::KeyZ, 0),
+        Err(ShortcutError::NeedsModifier)
+    ));
+    assert!(chord_shortcut(Code::F13, 0).is_ok());
+}
+
+#[test]
+fn own_fixed_shortcuts_are_reported_as_likely_conflicts() {
+    assert!(likely_conflicts(&dictation_toggle_shortcut())
+        .iter()
+        .any(|owner| owner.contains("SilentKeys")));
+}
+
+#[test]
+fn fallback_candidates_keep_the_key_close_and_skip_known_owners() {
+    let taken = default_shortcut();
+    let candidates = fallback_candidates(&taken);
+
+    assert!(!candidates.is_empty());
+    assert!(!candidates.contains(&taken));
+    assert!(!candidates.contains(&dictation_toggle_shortcut()));
+    assert!(!candidates.contains(&llm_handoff_shortcut()));
+    assert!(candidates
+        .iter()
+        .all(|candidate| likely_conflicts(candidate).is_empty()));
+    assert_eq!(
+        candidates[0],
+        parse_shortcut_str("Control+Alt+KeyZ").unwrap()
+    );
+}
@@ -0,0 +1,141 @@
+mod common;
+
+use chrono::NaiveDate;
+use common::temp_dir;
+use silent_keys_lib::capture_file::append_entry;
+use silent_keys_lib::digest::{
+    digest_file_name, export_digest, parse_date, parse_entries, render_digest, DigestError,
+};
+
+fn date(day: u32) -> NaiveDate {
+    NaiveDate::from_ymd_opt(2026, 3, day).unwrap()
+}
+
+const CAPTURE: &str = "\
+# Journal
+
+## 2026-03-02 09:15
+
+Morning standup notes.
+
+## 2026-03-02 09:40
+
+Second thought
+over two lines.
+
+## Not a timestamp
+
+Ignored.
+
+## 2026-03-03 18:05
+
+Evening walk.
+
+## 2026-03-09 08:00
+
+Outside the range.
+";
+
+#[test]
+fn entries_are_split_at_timestamp_headings() {
+    let entries = parse_entries(CAPTURE);
+
+    assert_eq!(entries.len(), 4);
+    assert_eq!(entries[0].at, date(2).and_hms_opt(9, 15, 0).unwrap());
+    assert_eq!(entries[0].text, "Morning standup notes.");
+    assert_eq!(entries[1].text, "Second thought\nover two lines.");
+    assert_eq!(entries[2].text, "Evening walk.");
+}
+
+#[test]
+fn digest_groups_entries_by_day_and_hour_within_the_range() {
+    let digest = render_digest(&parse_entries(CAPTURE), date(1), date(7));
+
+    assert_eq!(
+        digest,
+        "# Dictation digest, 2026-03-01 to 2026-03-07\n\
+         \n## Monday, 2026-03-02\n\
+         \n### 09:00\n\
+         \n**09:15** Morning standup notes.\n\
+         \n**09:40** Second thought\nover two lines.\n\
+         \n## Tuesday, 2026-03-03\n\
+         \n### 18:00\n\
+         \n**18:05** Evening walk.\n"
+    );
+}
+
+#[test]
+fn titled_entries_show_their_title_in_the_digest() {
+    let entries = parse_entries("## 2026-03-02 09:15 Weekly sync\n\nDates agreed.\n");
+
+    assert_eq!(entries[0].title.as_deref(), Some("Weekly sync"));
+    assert!(render_digest(&entries, date(2), date(2))
+        .contains("\n**09:15 Weekly sync** Dates agreed.\n"));
+}
+
+#[test]
+fn empty_range_says_so() {
+    let digest = render_digest(&parse_entries(CAPTURE), date(20), date(20));
+
+    assert_eq!(
+        digest,
+        "# Dictation digest for 2026-03-20\n\nNo dictations in this range.\n"
+    );
+}
+
+#[test]
+fn dates_must_be_iso_and_ordered() {
+    assert_eq!(parse_date(" 2026-03-02 ").unwrap(), date(2));
+    assert!(matches!(
+        parse_date("03/02/2026"),
+        Err(DigestError::InvalidDate(_))
+    ));
+    assert!(matches!(
+        export_digest("/tmp/capture.md", "2026-03-05", "2026-03-01", None),
+        Err(DigestError::InvalidRange { .. })
+    ));
+    assert!(matches!(
+        export_digest("", "2026-03-01", "2026-03-05", None),
+        Err(DigestError::NoCaptureFile)
+    ));
+}
+
+#[test]
+fn digest_is_written_into_the_notes_folder() {
+    let dir = temp_dir("digest_notes");
+    let capture = dir.join("capture.md");
+    let capture = capture.to_str().unwrap();
+    append_entry(
+        capture,
+        "First entry",
+        date(2).and_hms_opt(7, 30, 0).unwrap(),
+    )
+    .unwrap();
+    let notes = dir.join("notes");
+    std::fs::create_dir(&notes).unwrap();
+
+    let export =
+        export_digest(capture, "2026-03-02", "2026-03-08", notes.to_str()).expect("export digest");
+
+    let expected_path = notes.join(digest_file_name(date(2), date(8)));
+    assert_eq!(export.path, Some(expected_path.display().to_string()));
+    assert_eq!(
+        std::fs::read_to_string(expected_path).unwrap(),
+        export.markdown
+    );
+    assert!(export.markdown.contains("**07:30** First entry"));
+    let _ = std::fs::remove_dir_all(dir);
+}
+
+#[test]
+fn missing_capture_file_yields_an_empty_digest() {
+    let dir = temp_dir("digest_missing");
+    let capture = dir.join("missing.md");
+
+    let export = export_digest(capture.to_str().unwrap(), "2026-03-02", "2026-03-02", None)
+        .expect("export digest");
+
+    assert!(export.markdown.ends_with("No dictations in this range.\n"));
+    assert_eq!(export.path, None);
+    let _ = std::fs::remove_dir_all(dir);
+}
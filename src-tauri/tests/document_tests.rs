@@ -0,0 +1,58 @@
+use silent_keys_lib::document::{append_dictation, plain_text};
+
+fn assemble(dictations: &[&str]) -> String {
+    let mut document = String::new();
+    for dictation in dictations {
+        append_dictation(&mut document, dictation);
+    }
+    document
+}
+
+#[test]
+fn successive_dictations_continue_the_paragraph() {
+    assert_eq!(
+        assemble(&["First thought.", "Second thought."]),
+        "First thought. Second thought."
+    );
+}
+
+#[test]
+fn new_paragraph_and_new_line_break_the_text() {
+    assert_eq!(
+        assemble(&["One. New paragraph. Two.", "New line, three."]),
+        "One.\n\nTwo.\nthree."
+    );
+}
+
+#[test]
+fn new_heading_takes_the_rest_of_the_sentence() {
+    assert_eq!(
+        assemble(&["New heading weekly sync. We agreed on dates."]),
+        "# weekly sync\n\nWe agreed on dates."
+    );
+    assert_eq!(
+        assemble(&["Intro text.", "New subheading, Risks", "None so far."]),
+        "Intro text.\n\n## Risks\n\nNone so far."
+    );
+}
+
+#[test]
+fn the_word_new_alone_is_dictated_text() {
+    assert_eq!(
+        assemble(&["A new idea and a new plan."]),
+        "A new idea and a new plan."
+    );
+}
+
+#[test]
+fn commands_at_the_start_add_no_blank_lines() {
+    assert_eq!(assemble(&["New paragraph. New line. Hello."]), "Hello.");
+}
+
+#[test]
+fn plain_text_drops_heading_markers() {
+    assert_eq!(
+        plain_text("# Title\n\nBody.\n\n## Part\n\nMore.\n\n"),
+        "Title\n\nBody.\n\nPart\n\nMore."
+    );
+}
@@ -2,9 +2,11 @@ use std::path::PathBuf;
 use std::sync::{Arc, Mutex, OnceLock, RwLock};
 use std::time::{Duration, Instant};
 
-use silent_keys_lib::asr::{fallback_model_root, resolve_model_dir, AsrModel};
+use silent_keys_lib::asr::{fallback_model_root, resolve_model_dir, AsrModel, SessionPool};
 use silent_keys_lib::audio_processing::AudioFrame;
-use silent_keys_lib::streaming::StreamingPipeline;
+use silent_keys_lib::streaming::{StreamingPipeline, StreamingStrategy, Transcript};
+use silent_keys_lib::transcript::PauseMarkers;
+use silent_keys_lib::vad::VadConfig;
 
 fn get_wav_path() -> PathBuf {
     PathBuf::from("tests/samples/jfk.wav")
@@ -79,6 +81,31 @@ fn test_exact_match_non_streaming() {
     assert_eq!(actual, expected, "Non-streaming transcription mismatch");
 }
 
+#[test]
+#[ignore = "requires the downloaded Nemotron model"]
+fn test_exact_match_session_pool() {
+    let _guard = model_test_lock();
+    let model_root = std::env::var("SILENT_KEYS_MODEL_ROOT")
+        .map(PathBuf::from)
+        .unwrap_or_else(|_| fallback_model_root());
+    let model_dir = resolve_model_dir(&model_root).expect("Model dir not resolved");
+
+    let pool = SessionPool::new(&model_dir, "en-US", 2).expect("Failed to load session pool");
+    let samples = load_samples(&get_wav_path());
+
+    let started = Instant::now();
+    let transcript = pool
+        .transcribe(&samples, &VadConfig::default())
+        .expect("Transcription failed");
+    report_performance("session pool", samples.len(), started.elapsed());
+
+    assert_eq!(
+        normalize_text(&transcript.text()),
+        normalize_text(&load_ground_truth()),
+        "Session pool transcription mismatch"
+    );
+}
+
 #[test]
 #[ignore = "requires the downloaded Nemotron model"]
 fn test_exact_match_streaming() {
@@ -96,20 +123,21 @@ fn test_exact_match_streaming() {
     let pipeline = Arc::new(StreamingPipeline::new());
     let (tx, rx) = std::sync::mpsc::channel();
 
-    let accumulated_text = Arc::new(Mutex::new(String::new()));
+    let accumulated_text = Arc::new(Mutex::new(Transcript::default()));
     let acc_clone = accumulated_text.clone();
 
     pipeline
-        .start(rx, model_arc.clone(), move |update| {
-            let mut guard = acc_clone.lock().unwrap();
-            match update {
-                silent_keys_lib::streaming::TranscriptionUpdate::Append(text) => {
-                    guard.push_str(&text)
-                }
-                silent_keys_lib::streaming::TranscriptionUpdate::Replace(text) => *guard = text,
-            }
-            Ok(())
-        })
+        .start(
+            rx,
+            model_arc.clone(),
+            PauseMarkers::default(),
+            VadConfig::default(),
+            StreamingStrategy::Continuous,
+            move |update| {
+                let mut guard = acc_clone.lock().unwrap();
+                guard.apply(&update).map_err(|error| error.to_string())
+            },
+        )
         .expect("streaming pipeline should start");
 
     let samples = load_samples(&get_wav_path());
@@ -136,7 +164,7 @@ fn test_exact_match_streaming() {
 
         {
             let accumulated = accumulated_text.lock().unwrap();
-            let current = normalize_text(&accumulated);
+            let current = normalize_text(accumulated.text());
             if !current.is_empty() && current == expected_norm {
                 log::info!("Match found!");
                 break;
@@ -147,7 +175,7 @@ fn test_exact_match_streaming() {
 
     pipeline.finish().expect("pipeline should finish");
 
-    let text = accumulated_text.lock().unwrap().clone();
+    let text = accumulated_text.lock().unwrap().text().to_string();
     let normalized = normalize_text(&text);
     report_performance("streaming partials", samples.len(), start.elapsed());
 
@@ -0,0 +1,129 @@
+use std::sync::{Arc, Mutex};
+
+use silent_keys_lib::extensions::{
+    Extension, ExtensionError, Extensions, FinalTranscript, RecordingStart,
+};
+use silent_keys_lib::recording::SessionId;
+use silent_keys_lib::streaming::TranscriptPatch;
+use silent_keys_lib::transcript::Transcript;
+
+type Calls = Arc<Mutex<Vec<String>>>;
+
+struct Recorder {
+    name: &'static str,
+    calls: Calls,
+}
+
+impl Extension for Recorder {
+    fn name(&self) -> &'static str {
+        self.name
+    }
+
+    fn on_recording_start(&self, start: &RecordingStart) {
+        self.record(format!("start {} {}", start.session_id, start.streaming));
+    }
+
+    fn on_patch(&self, session_id: SessionId, patch: &TranscriptPatch) {
+        if let TranscriptPatch::Commit { append } = patch {
+            self.record(format!("commit {session_id} {append}"));
+        }
+    }
+
+    fn on_final_transcript(&self, transcript: &FinalTranscript<'_>) {
+        self.record(format!("final {}", transcript.text));
+    }
+}
+
+impl Recorder {
+    fn record(&self, call: String) {
+        self.calls
+            .lock()
+            .unwrap()
+            .push(format!("{}: {call}", self.name));
+    }
+}
+
+struct Panics;
+
+impl Extension for Panics {
+    fn name(&self) -> &'static str {
+        "panics"
+    }
+
+    fn on_recording_start(&self, _start: &RecordingStart) {
+        panic!("extension failure");
+    }
+}
+
+fn recorder(name: &'static str, calls: &Calls) -> Box<dyn Extension> {
+    Box::new(Recorder {
+        name,
+        calls: calls.clone(),
+    })
+}
+
+#[test]
+fn hooks_run_in_registration_order() {
+    let calls = Calls::default();
+    let mut extensions = Extensions::new();
+    extensions.register(recorder("first", &calls)).unwrap();
+    extensions.register(recorder("second", &calls)).unwrap();
+    let session_id = SessionId::from_raw_for_tests(7);
+    let transcript = Transcript::default();
+
+    extensions.recording_started(&RecordingStart {
+        session_id,
+        streaming: true,
+    });
+    extensions.patch(
+        session_id,
+        &TranscriptPatch::Commit {
+            append: "Hello".to_string(),
+        },
+    );
+    extensions.final_transcript(&FinalTranscript {
+        session_id: Some(session_id),
+        text: "Hello.",
+        transcript: &transcript,
+    });
+
+    assert_eq!(
+        *calls.lock().unwrap(),
+        [
+            "first: start 7 true",
+            "second: start 7 true",
+            "first: commit 7 Hello",
+            "second: commit 7 Hello",
+            "first: final Hello.",
+            "second: final Hello.",
+        ]
+    );
+}
+
+#[test]
+fn names_are_unique() {
+    let calls = Calls::default();
+    let mut extensions = Extensions::new();
+    extensions.register(recorder("sink", &calls)).unwrap();
+
+    assert_eq!(
+        extensions.register(recorder("sink", &calls)),
+        Err(ExtensionError::DuplicateExtension("sink".to_string()))
+    );
+    assert_eq!(extensions.names(), ["sink"]);
+}
+
+#[test]
+fn a_panicking_hook_does_not_stop_the_others() {
+    let calls = Calls::default();
+    let mut extensions = Extensions::new();
+    extensions.register(Box::new(Panics)).unwrap();
+    extensions.register(recorder("after", &calls)).unwrap();
+
+    extensions.recording_started(&RecordingStart {
+        session_id: SessionId::from_raw_for_tests(1),
+        streaming: false,
+    });
+
+    assert_eq!(*calls.lock().unwrap(), ["after: start 1 false"]);
+}
@@ -0,0 +1,95 @@
+use silent_keys_lib::audio_processing::ResamplerQuality;
+use silent_keys_lib::hardware::{parse_meminfo, HardwareProfile, ModelPrecision, SimdSupport};
+
+const GIB: u64 = 1 << 30;
+
+fn profile(cores: usize, simd: SimdSupport, memory_gib: Option<u64>) -> HardwareProfile {
+    HardwareProfile {
+        cores,
+        simd,
+        memory_bytes: memory_gib.map(|gib| gib * GIB),
+        gpu: Some(false),
+    }
+}
+
+#[test]
+fn capable_machines_stream_with_the_sinc_resampler() {
+    let recommendation = profile(8, SimdSupport::Avx2, Some(16)).recommend();
+
+    assert!(recommendation.streaming_enabled);
+    assert_eq!(recommendation.resampler_quality, ResamplerQuality::High);
+    assert_eq!(recommendation.precision, ModelPrecision::Int8);
+}
+
+#[test]
+fn small_or_unvectorized_machines_stay_light() {
+    for machine in [
+        profile(2, SimdSupport::Neon, Some(8)),
+        profile(8, SimdSupport::None, Some(32)),
+    ] {
+        let recommendation = machine.recommend();
+        assert!(!recommendation.streaming_enabled, "{machine:?}");
+        assert_eq!(recommendation.resampler_quality, ResamplerQuality::Fast);
+        assert_eq!(recommendation.precision, ModelPrecision::Int8);
+    }
+}
+
+#[test]
+fn low_memory_skips_streaming_but_unknown_memory_does_not() {
+    assert!(
+        !profile(4, SimdSupport::Neon, Some(4))
+            .recommend()
+            .streaming_enabled
+    );
+    assert!(
+        profile(4, SimdSupport::Neon, None)
+            .recommend()
+            .streaming_enabled
+    );
+}
+
+#[test]
+fn full_precision_needs_memory_cores_and_acceleration() {
+    assert_eq!(
+        profile(16, SimdSupport::Avx512, Some(32))
+            .recommend()
+            .precision,
+        ModelPrecision::Fp32
+    );
+    let with_gpu = HardwareProfile {
+        gpu: Some(true),
+        ..profile(8, SimdSupport::Neon, Some(16))
+    };
+    assert_eq!(with_gpu.recommend().precision, ModelPrecision::Fp32);
+    assert_eq!(
+        profile(16, SimdSupport::Avx512, None).recommend().precision,
+        ModelPrecision::Int8
+    );
+}
+
+#[test]
+fn meminfo_total_is_read_in_bytes() {
+    let meminfo = "MemTotal:       16318480 kB\nMemFree:         1234567 kB\n";
+
+    assert_eq!(parse_meminfo(meminfo), Some(16_318_480 * 1024));
+    assert_eq!(parse_meminfo("MemFree: 12 kB\n"), None);
+    assert_eq!(parse_meminfo("MemTotal: lots kB\n"), None);
+}
+
+#[test]
+fn detections_use_snake_case_on_the_wire() {
+    let profile = profile(4, SimdSupport::Avx512, Some(8));
+
+    assert_eq!(
+        serde_json::to_value(profile.recommend()).unwrap(),
+        serde_json::json!({
+            "precision": "int8",
+            "streaming_enabled": true,
+            "resampler_quality": "high",
+        })
+    );
+    assert_eq!(
+        serde_json::to_value(profile).unwrap()["simd"],
+        serde_json::json!("avx512")
+    );
+}
@@ -0,0 +1,360 @@
+mod common;
+
+use chrono::NaiveDate;
+use common::temp_dir;
+use silent_keys_lib::audio_file::{WavEncoding, WavExport};
+use silent_keys_lib::capture_file::{
+    append_exported_utterance_entry, append_titled_entry, append_utterance_entry, entry_title,
+    format_offset, parse_heading,
+};
+use silent_keys_lib::history::{
+    entry_clips, list_entries, read_history, read_history_deduplicated, rename_entry,
+    rename_history_entry, replace_utterance, set_history_utterance, set_history_utterances,
+    utterance_clip, HistoryError, HistoryUtterance,
+};
+use silent_keys_lib::transcript::{Transcript, TranscriptSegment};
+use silent_keys_lib::vad::VadConfig;
+
+const CAPTURE: &str = "\
+# Journal
+
+## 2026-03-02 09:15 Weekly sync
+
+Dates agreed.
+Owners next.
+
+## Not a timestamp
+
+Ignored.
+
+## 2026-03-02 09:40
+
+Second thought.
+";
+
+#[test]
+fn spoken_title_names_the_entry_and_is_left_out() {
+    assert_eq!(
+        entry_title("Title: weekly sync. We agreed on dates."),
+        ("Weekly sync".to_string(), "We agreed on dates.")
+    );
+    assert_eq!(
+        entry_title("title weekly sync"),
+        ("Weekly sync".to_string(), "title weekly sync")
+    );
+}
+
+#[test]
+fn first_sentence_names_the_entry_otherwise() {
+    assert_eq!(
+        entry_title("Call the bank. Then lunch."),
+        ("Call the bank".to_string(), "Call the bank. Then lunch.")
+    );
+    let (title, _) = entry_title("one two three four five six seven eight nine ten.");
+    assert_eq!(title, "one two three four five six seven eight…");
+}
+
+#[test]
+fn headings_carry_an_optional_title() {
+    let at = NaiveDate::from_ymd_opt(2026, 3, 2)
+        .unwrap()
+        .and_hms_opt(9, 15, 0)
+        .unwrap();
+
+    assert_eq!(
+        parse_heading("2026-03-02 09:15 Weekly sync"),
+        Some((at, Some("Weekly sync")))
+    );
+    assert_eq!(parse_heading("2026-03-02 09:15"), Some((at, None)));
+    assert_eq!(parse_heading("2026-03-02 09:15x"), None);
+    assert_eq!(parse_heading("Not a timestamp"), None);
+}
+
+#[test]
+fn history_lists_timestamped_entries_with_previews() {
+    let entries = list_entries(CAPTURE);
+
+    assert_eq!(entries.len(), 2);
+    assert_eq!(entries[0].index, 0);
+    assert_eq!(entries[0].at, "2026-03-02 09:15");
+    assert_eq!(entries[0].title.as_deref(), Some("Weekly sync"));
+    assert_eq!(entries[0].preview, "Dates agreed. Owners next.");
+    assert_eq!(entries[1].title, None);
+    assert_eq!(entries[1].preview, "Second thought.");
+}
+
+#[test]
+fn renaming_rewrites_only_the_chosen_heading() {
+    let renamed = rename_entry(CAPTURE, 1, "Follow-up\nnotes").expect("entry 1 exists");
+
+    assert_eq!(
+        renamed,
+        CAPTURE.replace(
+            "## 2026-03-02 09:40\n",
+            "## 2026-03-02 09:40 Follow-up notes\n"
+        )
+    );
+    let cleared = rename_entry(CAPTURE, 0, " ").expect("entry 0 exists");
+    assert!(cleared.contains("## 2026-03-02 09:15\n"));
+    assert_eq!(rename_entry(CAPTURE, 2, "Missing"), None);
+}
+
+#[test]
+fn renaming_updates_the_capture_file() {
+    let dir = temp_dir("history_rename");
+    let note = dir.join("daily.md");
+    let path = note.to_str().unwrap();
+    let at = NaiveDate::from_ymd_opt(2026, 3, 4)
+        .unwrap()
+        .and_hms_opt(8, 0, 0)
+        .unwrap();
+
+    append_titled_entry(path, Some("Standup"), "Notes.", at).expect("entry");
+    rename_history_entry(path, 0, "Planning").expect("rename");
+
+    let history = read_history(path).expect("history");
+    assert_eq!(history[0].title.as_deref(), Some("Planning"));
+    assert!(matches!(
+        rename_history_entry(path, 3, "Nope"),
+        Err(HistoryError::NotFound(3))
+    ));
+    assert!(matches!(read_history(""), Err(HistoryError::NoCaptureFile)));
+    let _ = std::fs::remove_dir_all(dir);
+}
+
+const SPLIT: &str = "\
+## 2026-03-02 09:15 Notes
+
+Plain entry.
+
+## 2026-03-02 10:00 Standup
+
+- [00:00](<daily audio/1.wav>) Yesterday I fixed the build.
+- [01:05](<daily audio/2.wav>) Today the release.
+- [01:40](<../secrets.wav>) Edited by hand.
+";
+
+fn segment(start_ms: u64, end_ms: u64, text: &str) -> TranscriptSegment {
+    TranscriptSegment {
+        start_ms,
+        end_ms,
+        text: text.to_string(),
+        speaker: None,
+        confidence: None,
+    }
+}
+
+#[test]
+fn offsets_show_hours_only_when_needed() {
+    assert_eq!(format_offset(65_400), "01:05");
+    assert_eq!(format_offset(3_725_000), "1:02:05");
+}
+
+#[test]
+fn split_entries_list_their_utterances() {
+    let entries = list_entries(SPLIT);
+
+    assert!(entries[0].utterances.is_empty());
+    assert_eq!(
+        entries[1].utterances[1],
+        HistoryUtterance {
+            index: 1,
+            at: "01:05".to_string(),
+            text: "Today the release.".to_string(),
+        }
+    );
+    assert_eq!(
+        entries[1].preview,
+        "Yesterday I fixed the build. Today the release. Edited by hand."
+    );
+}
+
+#[test]
+fn replacing_an_utterance_keeps_its_offset_and_audio() {
+    let replaced = replace_utterance(SPLIT, 1, 1, "Today the\nrelease notes.").expect("exists");
+
+    assert_eq!(
+        replaced,
+        SPLIT.replace("Today the release.", "Today the release notes.")
+    );
+    assert_eq!(replace_utterance(SPLIT, 0, 0, "Nope"), None);
+    assert_eq!(replace_utterance(SPLIT, 1, 3, "Nope"), None);
+}
+
+#[test]
+fn split_entries_keep_each_utterance_audio_beside_the_file() {
+    let dir = temp_dir("history_split");
+    let note = dir.join("daily.md");
+    let path = note.to_str().unwrap();
+    let at = NaiveDate::from_ymd_opt(2026, 3, 4)
+        .unwrap()
+        .and_hms_milli_opt(8, 0, 5, 120)
+        .unwrap();
+    let transcript = Transcript {
+        language: None,
+        segments: vec![segment(0, 500, "First."), segment(1_000, 1_250, "Second.")],
+    };
+    let samples = vec![0.25; 16_000 * 2];
+
+    append_utterance_entry(path, Some("Standup"), &transcript, &samples, at).expect("entry");
+
+    let contents = std::fs::read_to_string(&note).unwrap();
+    assert_eq!(
+        contents,
+        "## 2026-03-04 08:00 Standup\n\n\
+         - [00:00](<daily audio/20260304-080005120-1.wav>) First.\n\
+         - [00:01](<daily audio/20260304-080005120-2.wav>) Second.\n"
+    );
+    let clip = utterance_clip(path, 0, 1).expect("clip");
+    assert_eq!(
+        clip,
+        dir.join("daily audio").join("20260304-080005120-2.wav")
+    );
+    let audio = silent_keys_lib::audio_file::read_wav(&clip).expect("clip should read");
+    assert_eq!(audio.len(), 4_000);
+
+    set_history_utterance(path, 0, 1, "Second, again.").expect("update");
+    assert_eq!(
+        read_history(path).unwrap()[0].utterances[1].text,
+        "Second, again."
+    );
+    assert!(matches!(
+        utterance_clip(path, 0, 2),
+        Err(HistoryError::UtteranceNotFound {
+            entry: 0,
+            utterance: 2
+        })
+    ));
+    let _ = std::fs::remove_dir_all(dir);
+}
+
+#[test]
+fn exported_clips_are_trimmed_and_their_lines_follow() {
+    let dir = temp_dir("history_export");
+    let note = dir.join("daily.md");
+    let path = note.to_str().unwrap();
+    let at = NaiveDate::from_ymd_opt(2026, 3, 4)
+        .unwrap()
+        .and_hms_opt(8, 0, 0)
+        .unwrap();
+    let transcript = Transcript {
+        language: None,
+        segments: vec![segment(0, 4_000, "Late start.")],
+    };
+    // Three seconds of silence, then 0.6 s of speech.
+    let samples = [vec![0.0; 48_000], vec![0.25; 9_600], vec![0.0; 6_400]].concat();
+    let export = WavExport {
+        trim_silence: true,
+        normalize: false,
+        encoding: WavEncoding::Float32,
+    };
+    let vad = VadConfig::default();
+
+    append_exported_utterance_entry(path, None, &transcript, &samples, &export, &vad, at)
+        .expect("entry");
+
+    let contents = std::fs::read_to_string(&note).unwrap();
+    assert!(contents.contains("- [00:02](<daily audio/"), "{contents}");
+    let clip = utterance_clip(path, 0, 0).expect("clip");
+    let audio = silent_keys_lib::audio_file::read_wav(&clip).expect("clip should read");
+    assert_eq!(audio.len(), 9_600 + 2 * vad.padding_samples);
+    assert_eq!(audio[vad.padding_samples], 0.25);
+    let _ = std::fs::remove_dir_all(dir);
+}
+
+#[test]
+fn identical_recordings_point_at_the_first_entry() {
+    let dir = temp_dir("history_duplicates");
+    let note = dir.join("daily.md");
+    let path = note.to_str().unwrap();
+    let transcript = Transcript {
+        language: None,
+        segments: vec![segment(0, 500, "Hello.")],
+    };
+    let day = NaiveDate::from_ymd_opt(2026, 3, 4).unwrap();
+    for (minute, level) in [(0, 0.25), (1, 0.5), (2, 0.25)] {
+        let at = day.and_hms_opt(8, minute, 0).unwrap();
+        append_utterance_entry(path, None, &transcript, &vec![level; 16_000], at).expect("entry");
+    }
+    std::fs::OpenOptions::new()
+        .append(true)
+        .open(&note)
+        .and_then(|mut file| {
+            std::io::Write::write_all(&mut file, b"\n## 2026-03-04 09:00\n\nTyped.\n")
+        })
+        .unwrap();
+
+    let entries = read_history_deduplicated(path).expect("history");
+
+    let duplicates: Vec<_> = entries.iter().map(|entry| entry.duplicate_of).collect();
+    assert_eq!(duplicates, [None, None, Some(0), None]);
+    assert!(read_history(path)
+        .unwrap()
+        .iter()
+        .all(|entry| entry.duplicate_of.is_none()));
+    let _ = std::fs::remove_dir_all(dir);
+}
+
+#[test]
+fn utterance_audio_outside_the_folder_is_refused() {
+    let dir = temp_dir("history_clip_escape");
+    let note = dir.join("daily.md");
+    std::fs::write(&note, SPLIT).unwrap();
+
+    assert!(matches!(
+        utterance_clip(note.to_str().unwrap(), 1, 2),
+        Err(HistoryError::InvalidClip(_))
+    ));
+    let _ = std::fs::remove_dir_all(dir);
+}
+
+#[test]
+fn keeping_a_retranscription_rewrites_every_utterance() {
+    let dir = temp_dir("history_retranscribe");
+    let note = dir.join("daily.md");
+    let path = note.to_str().unwrap();
+    let edited = "- [01:40](<../secrets.wav>) Edited by hand.\n";
+    std::fs::write(&note, SPLIT.replace(edited, "")).unwrap();
+
+    let clips = entry_clips(path, 1).expect("clips");
+    assert_eq!(
+        clips,
+        vec![
+            (
+                "Yesterday I fixed the build.".to_string(),
+                dir.join("daily audio").join("1.wav")
+            ),
+            (
+                "Today the release.".to_string(),
+                dir.join("daily audio").join("2.wav")
+            ),
+        ]
+    );
+    assert!(matches!(
+        entry_clips(path, 0),
+        Err(HistoryError::NoRecording(0))
+    ));
+
+    let texts = [
+        "Yesterday I fixed the builds.".to_string(),
+        "Today, the release.".to_string(),
+    ];
+    set_history_utterances(path, 1, &texts).expect("update");
+    assert_eq!(
+        std::fs::read_to_string(&note).unwrap(),
+        SPLIT
+            .replace(edited, "")
+            .replace("the build.", "the builds.")
+            .replace("Today the", "Today, the")
+    );
+    assert!(matches!(
+        set_history_utterances(path, 1, &texts[..1]),
+        Err(HistoryError::UtteranceNotFound { entry: 1, .. })
+    ));
+    std::fs::write(&note, SPLIT).unwrap();
+    assert!(matches!(
+        entry_clips(path, 1),
+        Err(HistoryError::InvalidClip(_))
+    ));
+    let _ = std::fs::remove_dir_all(dir);
+}
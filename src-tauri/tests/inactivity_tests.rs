@@ -0,0 +1,75 @@
+use std::time::{Duration, Instant};
+
+use silent_keys_lib::inactivity::{InactivityAction, InactivityTimeout};
+
+fn timeout() -> InactivityTimeout {
+    InactivityTimeout {
+        enabled: true,
+        minutes: 2,
+        grace_secs: 30,
+    }
+}
+
+#[test]
+fn silence_warns_once_then_finalizes_after_the_grace() {
+    let start = Instant::now();
+    let mut watch = timeout().watch(start).expect("enabled");
+
+    assert_eq!(
+        watch.poll(start + Duration::from_secs(119)),
+        InactivityAction::None
+    );
+    assert_eq!(
+        watch.poll(start + Duration::from_secs(120)),
+        InactivityAction::Warn { remaining_secs: 30 }
+    );
+    assert_eq!(
+        watch.poll(start + Duration::from_secs(130)),
+        InactivityAction::None
+    );
+    assert_eq!(
+        watch.poll(start + Duration::from_secs(150)),
+        InactivityAction::Finalize
+    );
+}
+
+#[test]
+fn speech_or_keep_going_withdraws_the_warning() {
+    let start = Instant::now();
+    let mut watch = timeout().watch(start).expect("enabled");
+    let warned_at = start + Duration::from_secs(125);
+    assert!(matches!(
+        watch.poll(warned_at),
+        InactivityAction::Warn { .. }
+    ));
+
+    watch.keep_going(warned_at);
+    assert_eq!(
+        watch.poll(warned_at + Duration::from_secs(1)),
+        InactivityAction::Resume
+    );
+    assert_eq!(
+        watch.poll(warned_at + Duration::from_secs(60)),
+        InactivityAction::None
+    );
+
+    watch.heard_speech(warned_at + Duration::from_secs(100));
+    assert_eq!(
+        watch.poll(warned_at + Duration::from_secs(200)),
+        InactivityAction::None
+    );
+}
+
+#[test]
+fn disabled_rule_watches_nothing_and_limits_are_clamped() {
+    assert!(InactivityTimeout::default().watch(Instant::now()).is_none());
+
+    let clamped = InactivityTimeout {
+        enabled: true,
+        minutes: 0,
+        grace_secs: 10_000,
+    }
+    .clamped();
+    assert_eq!(clamped.minutes, 1);
+    assert_eq!(clamped.grace_secs, 300);
+}
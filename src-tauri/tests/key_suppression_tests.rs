@@ -0,0 +1,94 @@
+use silent_keys_lib::key_suppression::{
+    HookDecision, KeySuppressor, MODIFIER_ALT, MODIFIER_CONTROL, MODIFIER_META,
+};
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+enum Key {
+    Z,
+    X,
+    F13,
+}
+
+fn alt_z() -> KeySuppressor<Key> {
+    let mut suppressor = KeySuppressor::new();
+    suppressor.bind(Some((Key::Z, MODIFIER_ALT)));
+    suppressor
+}
+
+#[test]
+fn swallows_the_chord_key_from_press_to_release() {
+    let mut suppressor = alt_z();
+
+    assert_eq!(
+        suppressor.key(Key::Z, true, MODIFIER_ALT),
+        HookDecision::Press { mask: true }
+    );
+    assert!(suppressor.is_down());
+    assert_eq!(
+        suppressor.key(Key::Z, true, MODIFIER_ALT),
+        HookDecision::Swallow
+    );
+    // Letting go of Alt first still ends push-to-talk on the key's release.
+    assert_eq!(suppressor.key(Key::Z, false, 0), HookDecision::Release);
+    assert!(!suppressor.is_down());
+    assert_eq!(suppressor.key(Key::Z, false, 0), HookDecision::Pass);
+}
+
+#[test]
+fn other_keys_and_modifier_combinations_reach_the_app() {
+    let mut suppressor = alt_z();
+
+    assert_eq!(suppressor.key(Key::Z, true, 0), HookDecision::Pass);
+    assert_eq!(suppressor.key(Key::Z, false, 0), HookDecision::Pass);
+    assert_eq!(
+        suppressor.key(Key::Z, true, MODIFIER_ALT | MODIFIER_CONTROL),
+        HookDecision::Pass
+    );
+    assert_eq!(
+        suppressor.key(Key::X, true, MODIFIER_ALT),
+        HookDecision::Pass
+    );
+    assert!(!suppressor.is_down());
+}
+
+#[test]
+fn only_alt_and_windows_key_chords_need_a_mask() {
+    let mut suppressor = KeySuppressor::new();
+    suppressor.bind(Some((Key::F13, 0)));
+    assert_eq!(
+        suppressor.key(Key::F13, true, 0),
+        HookDecision::Press { mask: false }
+    );
+
+    suppressor.bind(Some((Key::Z, MODIFIER_META)));
+    assert_eq!(
+        suppressor.key(Key::Z, true, MODIFIER_META),
+        HookDecision::Press { mask: true }
+    );
+
+    suppressor.bind(Some((Key::Z, MODIFIER_CONTROL)));
+    assert_eq!(
+        suppressor.key(Key::Z, true, MODIFIER_CONTROL),
+        HookDecision::Press { mask: false }
+    );
+}
+
+#[test]
+fn unbinding_passes_everything_and_forgets_a_held_key() {
+    let mut suppressor = alt_z();
+    suppressor.key(Key::Z, true, MODIFIER_ALT);
+
+    suppressor.bind(Some((Key::Z, MODIFIER_ALT)));
+    assert!(
+        suppressor.is_down(),
+        "rebinding the same chord keeps it held"
+    );
+
+    suppressor.bind(None);
+    assert!(!suppressor.is_down());
+    assert_eq!(suppressor.key(Key::Z, false, 0), HookDecision::Pass);
+    assert_eq!(
+        suppressor.key(Key::Z, true, MODIFIER_ALT),
+        HookDecision::Pass
+    );
+}
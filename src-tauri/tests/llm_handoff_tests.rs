@@ -0,0 +1,78 @@
+use serde_json::json;
+use silent_keys_lib::llm_handoff::{
+    chat_completions_url, request_body, response_text, rewrite, LlmHandoffError,
+    LlmHandoffSettings, PromptTemplate,
+};
+
+#[test]
+fn chat_completions_url_accepts_base_or_full_url() {
+    assert_eq!(
+        chat_completions_url("http://localhost:11434/v1/").unwrap(),
+        "http://localhost:11434/v1/chat/completions"
+    );
+    assert_eq!(
+        chat_completions_url("https://llm.local/v1/chat/completions").unwrap(),
+        "https://llm.local/v1/chat/completions"
+    );
+    assert_eq!(
+        chat_completions_url("http://host/v1?key=x").unwrap(),
+        "http://host/v1/chat/completions?key=x"
+    );
+    assert_eq!(
+        chat_completions_url("HTTP://host").unwrap(),
+        "http://host/chat/completions"
+    );
+    assert!(matches!(
+        chat_completions_url("localhost:11434"),
+        Err(LlmHandoffError::InvalidEndpoint(_))
+    ));
+    assert!(matches!(
+        chat_completions_url("http://exa mple.com/v1"),
+        Err(LlmHandoffError::InvalidEndpoint(_))
+    ));
+}
+
+#[test]
+fn request_body_sends_template_as_system_prompt() {
+    let settings = LlmHandoffSettings {
+        model: " llama3 ".to_string(),
+        template: PromptTemplate::Summarize,
+        ..LlmHandoffSettings::default()
+    };
+
+    let body = request_body(&settings, "raw transcript");
+
+    assert_eq!(body["model"], "llama3");
+    assert_eq!(body["stream"], false);
+    assert_eq!(body["messages"][0]["role"], "system");
+    assert_eq!(
+        body["messages"][0]["content"],
+        PromptTemplate::Summarize.instruction()
+    );
+    assert_eq!(body["messages"][1]["content"], "raw transcript");
+}
+
+#[test]
+fn response_text_reads_first_choice() {
+    let response = json!({
+        "choices": [{ "message": { "role": "assistant", "content": "  Formal text.\n" } }]
+    });
+    assert_eq!(response_text(&response).unwrap(), "Formal text.");
+
+    let empty = json!({ "choices": [{ "message": { "content": "   " } }] });
+    assert!(matches!(
+        response_text(&empty),
+        Err(LlmHandoffError::EmptyResponse)
+    ));
+}
+
+#[test]
+fn rewrite_never_contacts_endpoint_unless_enabled() {
+    let settings = LlmHandoffSettings::default();
+
+    assert!(!settings.enabled);
+    assert!(matches!(
+        rewrite(&settings, "", "hello"),
+        Err(LlmHandoffError::Disabled)
+    ));
+}
@@ -0,0 +1,59 @@
+use silent_keys_lib::recording::{
+    Lookback, LookbackSettings, RollingBuffer, MAX_BUFFER_SECS, MIN_BUFFER_SECS,
+    MIN_TRANSCRIBE_SECS,
+};
+
+#[test]
+fn the_buffer_keeps_only_the_newest_samples() {
+    let mut buffer = RollingBuffer::new(4);
+    assert!(buffer.is_empty());
+
+    buffer.push(&[1.0, 2.0, 3.0]);
+    buffer.push(&[4.0, 5.0]);
+
+    assert_eq!(buffer.len(), 4);
+    assert_eq!(buffer.latest(10), vec![2.0, 3.0, 4.0, 5.0]);
+    assert_eq!(buffer.latest(2), vec![4.0, 5.0]);
+}
+
+#[test]
+fn a_push_longer_than_the_buffer_keeps_its_tail() {
+    let mut buffer = RollingBuffer::new(3);
+    buffer.push(&[0.5]);
+
+    buffer.push(&[1.0, 2.0, 3.0, 4.0, 5.0]);
+
+    assert_eq!(buffer.latest(3), vec![3.0, 4.0, 5.0]);
+}
+
+#[test]
+fn lookback_spans_are_clamped_to_the_buffer() {
+    let settings = LookbackSettings {
+        enabled: true,
+        buffer_secs: 1,
+        transcribe_secs: 900,
+    }
+    .clamped();
+    assert_eq!(settings.buffer_secs, MIN_BUFFER_SECS);
+    assert_eq!(settings.transcribe_secs, MIN_BUFFER_SECS);
+
+    let settings = LookbackSettings {
+        enabled: true,
+        buffer_secs: u32::MAX,
+        transcribe_secs: 0,
+    }
+    .clamped();
+    assert_eq!(settings.buffer_secs, MAX_BUFFER_SECS);
+    assert_eq!(settings.transcribe_secs, MIN_TRANSCRIBE_SECS);
+    assert_eq!(
+        settings.transcribe_samples(),
+        MIN_TRANSCRIBE_SECS as usize * 16_000
+    );
+}
+
+#[test]
+fn lookback_is_off_and_silent_until_enabled() {
+    assert!(!LookbackSettings::default().enabled);
+    assert!(!Lookback::global().is_listening());
+    assert!(Lookback::global().latest(16_000).is_empty());
+}
@@ -0,0 +1,42 @@
+use silent_keys_lib::recording::{
+    analyze_mic_test, level_dbfs, peak_preview, MIC_TEST_PREVIEW_BUCKETS, SILENCE_DBFS,
+};
+
+#[test]
+fn levels_are_reported_in_decibels_below_full_scale() {
+    assert_eq!(level_dbfs(1.0), 0.0);
+    assert!((level_dbfs(0.1) + 20.0).abs() < 1e-4);
+    assert_eq!(level_dbfs(0.0), SILENCE_DBFS);
+    assert_eq!(level_dbfs(1e-9), SILENCE_DBFS);
+}
+
+#[test]
+fn the_preview_keeps_each_slice_peak() {
+    let samples = [0.1, -0.5, 0.2, 0.0, -0.3, 0.25];
+
+    assert_eq!(peak_preview(&samples, 3), vec![0.5, 0.2, 0.3]);
+    assert_eq!(peak_preview(&samples, 100).len(), samples.len());
+    assert!(peak_preview(&[], 10).is_empty());
+}
+
+#[test]
+fn a_spoken_take_is_measured_and_previewed() {
+    let samples: Vec<f32> = (0..48_000)
+        .map(|index| 0.5 * (index as f32 * 0.05).sin())
+        .collect();
+
+    let test = analyze_mic_test(&samples, 3_000, None);
+
+    assert!((test.peak_dbfs + 6.02).abs() < 0.1);
+    assert!((test.rms_dbfs + 9.03).abs() < 0.1);
+    assert_eq!(test.preview.len(), MIC_TEST_PREVIEW_BUCKETS);
+    assert!(!test.too_quiet);
+}
+
+#[test]
+fn a_muted_microphone_is_flagged_as_too_quiet() {
+    let test = analyze_mic_test(&[0.001; 16_000], 1_000, None);
+
+    assert!(test.too_quiet);
+    assert_eq!(test.duration_ms, 1_000);
+}
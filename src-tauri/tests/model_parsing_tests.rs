@@ -0,0 +1,92 @@
+mod common;
+
+use common::temp_dir;
+use proptest::prelude::*;
+use silent_keys_lib::asr::{
+    check_model_files_for_tests, parse_model_config_for_tests,
+    verification_receipt_matches_for_tests, AsrError,
+};
+use silent_keys_lib::errors::UserFacing;
+
+const ASSETS: &[(&str, u64, &str)] = &[("config.json", 2, "hash")];
+
+#[test]
+fn config_with_prompts_lists_one_language_per_prompt() {
+    let config = br#"{"prompt_dictionary": {"auto": 0, "en-US": 1, "en": 1, "de-DE": 2}}"#;
+
+    let options = parse_model_config_for_tests(config).expect("config should parse");
+
+    assert_eq!(options, vec!["de-DE".to_string(), "en-US".to_string()]);
+}
+
+#[test]
+fn malformed_configs_are_typed_errors() {
+    assert!(matches!(
+        parse_model_config_for_tests(b"{\"prompt_dictionary\": [1, 2]}"),
+        Err(AsrError::Config { .. })
+    ));
+    assert!(matches!(
+        parse_model_config_for_tests(b"{}"),
+        Err(AsrError::Config { .. })
+    ));
+    assert!(matches!(
+        parse_model_config_for_tests(br#"{"prompt_dictionary": {"en-US": 70000}}"#),
+        Err(AsrError::Config { .. })
+    ));
+    assert!(matches!(
+        parse_model_config_for_tests(br#"{"prompt_dictionary": {"": 1}}"#),
+        Err(AsrError::Corrupted { .. })
+    ));
+}
+
+#[test]
+fn missing_and_empty_model_files_report_corruption() {
+    let dir = temp_dir("asr_corrupted_snapshot");
+
+    let missing = check_model_files_for_tests(&dir).expect_err("empty snapshot is corrupt");
+    assert!(matches!(missing, AsrError::Corrupted { .. }));
+    assert!(missing.to_string().contains("missing"));
+
+    std::fs::write(dir.join("config.json"), b"").expect("write should succeed");
+    let empty = check_model_files_for_tests(&dir).expect_err("empty file is corrupt");
+    assert!(empty.to_string().contains("config.json"));
+    assert!(empty.user_message().contains("Download the model again"));
+
+    let _ = std::fs::remove_dir_all(&dir);
+}
+
+proptest! {
+    #![proptest_config(ProptestConfig::with_cases(256))]
+
+    #[test]
+    fn arbitrary_config_bytes_never_panic(data in proptest::collection::vec(any::<u8>(), 0..512)) {
+        let _ = parse_model_config_for_tests(&data);
+    }
+
+    #[test]
+    fn well_formed_dictionaries_always_parse(
+        entries in proptest::collection::btree_map("[a-z]{2}(-[A-Z]{2})?", any::<u16>(), 0..16)
+    ) {
+        let config = serde_json::json!({ "prompt_dictionary": entries });
+        let data = serde_json::to_vec(&config).unwrap();
+
+        let options = parse_model_config_for_tests(&data).expect("valid config should parse");
+
+        prop_assert!(options.len() <= entries.len());
+        prop_assert!(options.iter().all(|language| entries.contains_key(language)));
+    }
+
+    #[test]
+    fn corrupted_verification_receipts_only_miss_the_cache(
+        data in proptest::collection::vec(any::<u8>(), 0..256)
+    ) {
+        let dir = temp_dir("asr_receipt_fuzz");
+        std::fs::write(dir.join("config.json"), b"{}").unwrap();
+        std::fs::write(dir.join(".silentkeys-verified.json"), &data).unwrap();
+
+        let matches = verification_receipt_matches_for_tests(&dir, "revision", ASSETS);
+
+        let _ = std::fs::remove_dir_all(&dir);
+        prop_assert_eq!(matches, Ok(false));
+    }
+}
@@ -0,0 +1,101 @@
+mod common;
+
+use common::temp_dir;
+use silent_keys_lib::asr::{
+    cancel_model_download, discard_model_download, resolve_model_dir, AsrError, ModelLoadStage,
+};
+use silent_keys_lib::errors::UserFacing;
+
+#[test]
+fn skipped_download_stops_before_fetching() {
+    let root = temp_dir("asr_skipped_download");
+
+    cancel_model_download();
+    let error = resolve_model_dir(&root).expect_err("a skipped load should not resolve");
+
+    assert!(matches!(error, AsrError::Cancelled), "got {error:?}");
+    assert!(error.user_message().contains("Load it"));
+    let snapshot = std::fs::read_dir(root.join("snapshots"))
+        .expect("the snapshot layout should exist")
+        .next()
+        .expect("a snapshot directory should exist")
+        .expect("snapshot entry should be readable")
+        .path();
+    assert!(
+        std::fs::read_dir(snapshot)
+            .expect("snapshot should be readable")
+            .next()
+            .is_none(),
+        "nothing should be fetched"
+    );
+
+    let _ = std::fs::remove_dir_all(root);
+}
+
+#[test]
+fn cancelled_download_discards_partial_files_but_keeps_others() {
+    let root = temp_dir("asr_cancelled_download");
+
+    discard_model_download();
+    let error = resolve_model_dir(&root).expect_err("a cancelled load should not resolve");
+    assert!(matches!(error, AsrError::Cancelled), "got {error:?}");
+    let snapshot = std::fs::read_dir(root.join("snapshots"))
+        .expect("the snapshot layout should exist")
+        .next()
+        .expect("a snapshot directory should exist")
+        .expect("snapshot entry should be readable")
+        .path();
+    let partial = snapshot.join("encoder.onnx.download");
+    let unrelated = snapshot.join("notes.txt");
+    std::fs::write(&partial, b"partial").expect("partial download should be written");
+    std::fs::write(&unrelated, b"keep").expect("unrelated file should be written");
+
+    let error = resolve_model_dir(&root).expect_err("a cancelled load should not resolve");
+
+    assert!(matches!(error, AsrError::Cancelled), "got {error:?}");
+    assert!(!partial.exists(), "the partial download should be deleted");
+    assert!(unrelated.exists());
+
+    let _ = std::fs::remove_dir_all(root);
+}
+
+#[test]
+fn downloading_stage_is_reported_in_snake_case() {
+    assert_eq!(
+        serde_json::to_value(ModelLoadStage::Downloading).unwrap(),
+        serde_json::json!("downloading")
+    );
+}
+
+#[test]
+fn reload_stages_are_reported_in_snake_case() {
+    assert_eq!(
+        serde_json::to_value(ModelLoadStage::WaitingForDictation).unwrap(),
+        serde_json::json!("waiting_for_dictation")
+    );
+    assert_eq!(
+        serde_json::to_value(ModelLoadStage::Unloading).unwrap(),
+        serde_json::json!("unloading")
+    );
+    assert_eq!(
+        serde_json::to_value(ModelLoadStage::Failed).unwrap(),
+        serde_json::json!("failed")
+    );
+}
+
+#[cfg(feature = "desktop")]
+#[test]
+fn prewarm_defaults_to_launch_and_reads_stored_modes() {
+    use silent_keys_lib::settings::{ModelPrewarm, Settings};
+
+    assert_eq!(Settings::default().model_prewarm, ModelPrewarm::OnLaunch);
+    assert_eq!(
+        serde_json::from_value::<ModelPrewarm>(serde_json::json!("on_first_use")).unwrap(),
+        ModelPrewarm::OnFirstUse
+    );
+    assert_eq!(
+        serde_json::from_value::<ModelPrewarm>(serde_json::json!("manual")).unwrap(),
+        ModelPrewarm::Manual
+    );
+    assert!(serde_json::from_value::<ModelPrewarm>(serde_json::json!("later")).is_err());
+}
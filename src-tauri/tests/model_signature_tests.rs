@@ -0,0 +1,72 @@
+use ort::ErrorCode;
+use parakeet_rs::Error;
+use silent_keys_lib::asr::{
+    is_shape_error, read_signature, GraphSignature, ModelSignature, TensorSignature,
+};
+
+#[test]
+fn shape_mismatches_are_told_apart_from_other_decode_errors() {
+    let rejected = Error::Ort(ort::Error::new_with_code(
+        ErrorCode::InvalidArgument,
+        "Invalid rank for input: encoder_outputs Got: 2 Expected: 3",
+    ));
+    assert!(is_shape_error(&rejected), "{rejected}");
+    for error in [
+        Error::Ort(ort::Error::new_with_code(
+            ErrorCode::NoSuchFile,
+            "Load model from decoder_joint.onnx failed",
+        )),
+        Error::Model(
+            "Failed to reshape encoder output: ShapeError/IncompatibleShape: incompatible shapes"
+                .to_string(),
+        ),
+        Error::Model("Failed to acquire model lock: poisoned lock".to_string()),
+        Error::Config("encoder dimension missing from config.json".to_string()),
+        Error::Tokenizer("Failed to open tokenizer.model: incompatible rank".to_string()),
+        Error::Io(std::io::Error::from(std::io::ErrorKind::NotFound)),
+    ] {
+        assert!(!is_shape_error(&error), "{error}");
+    }
+}
+
+#[test]
+fn missing_graphs_are_listed_with_why_they_could_not_be_read() {
+    let dir = std::env::temp_dir().join("model_signature_missing_graphs");
+
+    let signature = read_signature(&dir);
+
+    let files: Vec<_> = signature
+        .graphs
+        .iter()
+        .map(|graph| graph.file.as_str())
+        .collect();
+    assert_eq!(files, ["decoder_joint.onnx", "encoder.onnx"]);
+    assert!(signature
+        .graphs
+        .iter()
+        .all(|graph| graph.error.is_some() && graph.inputs.is_empty()));
+}
+
+#[test]
+fn the_report_lists_each_tensor_under_its_graph() {
+    let signature = ModelSignature {
+        graphs: vec![GraphSignature {
+            file: "decoder_joint.onnx".to_string(),
+            inputs: vec![TensorSignature {
+                name: "targets".to_string(),
+                dtype: "Tensor<i32>(-1, -1)".to_string(),
+            }],
+            outputs: vec![TensorSignature {
+                name: "outputs".to_string(),
+                dtype: "Tensor<f32>(-1, -1, -1, 1025)".to_string(),
+            }],
+            error: None,
+        }],
+    };
+
+    let report = signature.to_string();
+
+    assert!(report.starts_with("Model "));
+    assert!(report.contains("decoder_joint.onnx:\n  in  targets: Tensor<i32>(-1, -1)\n"));
+    assert!(report.contains("  out outputs: Tensor<f32>(-1, -1, -1, 1025)\n"));
+}
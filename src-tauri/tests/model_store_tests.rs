@@ -2,9 +2,9 @@ use std::path::PathBuf;
 use std::time::{Duration, SystemTime, UNIX_EPOCH};
 
 use silent_keys_lib::asr::{
-    fallback_model_root, invalid_model_files_for_tests, model_file_matches_for_tests,
-    resolve_model_dir, verification_receipt_matches_for_tests,
-    write_verification_receipt_for_tests,
+    fallback_model_root, import_model_archive, invalid_model_files_for_tests,
+    model_file_matches_for_tests, resolve_model_dir, verification_receipt_matches_for_tests,
+    write_verification_receipt_for_tests, ModelStoreLock,
 };
 
 #[test]
@@ -129,6 +129,87 @@ fn verification_receipt_tracks_snapshot_metadata_and_manifest() {
     let _ = std::fs::remove_dir_all(temp_dir);
 }
 
+#[test]
+fn model_archive_import_rejects_mismatched_assets() {
+    let temp_dir = std::env::temp_dir().join(format!(
+        "asr_archive_import_{}",
+        SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .expect("system clock should be after the Unix epoch")
+            .as_nanos()
+    ));
+    std::fs::create_dir_all(&temp_dir).expect("temp dir should be creatable");
+    let archive_path = temp_dir.join("model.tar");
+
+    let mut builder = tar::Builder::new(
+        std::fs::File::create(&archive_path).expect("archive should be creatable"),
+    );
+    let contents = b"not the pinned config";
+    let mut header = tar::Header::new_gnu();
+    header.set_size(contents.len() as u64);
+    header.set_mode(0o644);
+    header.set_cksum();
+    builder
+        .append_data(&mut header, "model/config.json", &contents[..])
+        .expect("archive entry should be writable");
+    builder.finish().expect("archive should be finalized");
+    drop(builder);
+
+    let root = temp_dir.join("root");
+    let error = import_model_archive(&archive_path, &root)
+        .expect_err("a mismatched asset should be rejected");
+    assert!(error.to_string().contains("config.json"));
+    assert!(
+        invalid_model_files_for_tests(&resolve_snapshot(&root))
+            .expect("validation should succeed")
+            .contains(&"config.json".to_string()),
+        "the rejected asset should not be installed"
+    );
+
+    let _ = std::fs::remove_dir_all(temp_dir);
+}
+
+fn resolve_snapshot(root: &std::path::Path) -> PathBuf {
+    let snapshots = root.join("snapshots");
+    std::fs::read_dir(&snapshots)
+        .expect("import should create the snapshot layout")
+        .next()
+        .expect("a snapshot directory should exist")
+        .expect("snapshot entry should be readable")
+        .path()
+}
+
+#[test]
+fn model_store_lock_blocks_until_released() {
+    let root = std::env::temp_dir().join(format!(
+        "asr_store_lock_{}",
+        SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .expect("system clock should be after the Unix epoch")
+            .as_nanos()
+    ));
+    let held = ModelStoreLock::acquire(&root).expect("lock should be acquired");
+
+    let (tx, rx) = std::sync::mpsc::channel();
+    let waiter_root = root.clone();
+    let waiter = std::thread::spawn(move || {
+        let lock = ModelStoreLock::acquire(&waiter_root).expect("lock should be acquired");
+        tx.send(()).expect("receiver should be alive");
+        drop(lock);
+    });
+
+    assert!(
+        rx.recv_timeout(Duration::from_millis(200)).is_err(),
+        "a second holder must wait for the first"
+    );
+    drop(held);
+    rx.recv_timeout(Duration::from_secs(5))
+        .expect("the waiter should acquire the released lock");
+    waiter.join().expect("waiter should finish");
+
+    let _ = std::fs::remove_dir_all(root);
+}
+
 #[test]
 #[ignore = "requires network access; repairs the local model cache in place"]
 fn corrupt_cached_asset_is_repaired() {
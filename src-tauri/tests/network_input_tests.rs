@@ -0,0 +1,99 @@
+use std::io::Cursor;
+use std::sync::mpsc;
+
+use silent_keys_lib::audio_processing::ResamplerQuality;
+use silent_keys_lib::network_input::{
+    decode_pcm16, read_frame, validate, NetworkInputError, NetworkInputSettings, MAX_FRAME_BYTES,
+};
+use silent_keys_lib::recording::{AudioInput, Recorder};
+
+fn frame(samples: &[i16]) -> Vec<u8> {
+    let mut bytes = ((samples.len() * 2) as u32).to_le_bytes().to_vec();
+    for sample in samples {
+        bytes.extend_from_slice(&sample.to_le_bytes());
+    }
+    bytes
+}
+
+#[test]
+fn pcm16_frames_decode_to_normalized_samples() {
+    assert_eq!(
+        decode_pcm16(&[0x00, 0x80, 0x00, 0x00, 0x00, 0x40]),
+        vec![-1.0, 0.0, 0.5]
+    );
+
+    let mut stream = frame(&[i16::MIN, 0, 16_384]);
+    stream.extend(frame(&[0]));
+    let mut reader = Cursor::new(stream);
+
+    assert_eq!(read_frame(&mut reader).unwrap(), Some(vec![-1.0, 0.0, 0.5]));
+    assert_eq!(read_frame(&mut reader).unwrap(), Some(vec![0.0]));
+    assert_eq!(read_frame(&mut reader).unwrap(), None);
+}
+
+#[test]
+fn zero_length_frame_ends_the_dictation() {
+    let mut stream = frame(&[]);
+    stream.extend(frame(&[1, 2]));
+
+    assert_eq!(read_frame(&mut Cursor::new(stream)).unwrap(), None);
+}
+
+#[test]
+fn malformed_frames_are_rejected() {
+    let too_large = ((MAX_FRAME_BYTES + 2) as u32).to_le_bytes();
+    assert!(matches!(
+        read_frame(&mut Cursor::new(too_large)),
+        Err(NetworkInputError::FrameTooLarge(_))
+    ));
+
+    let mut odd = 3_u32.to_le_bytes().to_vec();
+    odd.extend([0, 0, 0]);
+    assert!(matches!(
+        read_frame(&mut Cursor::new(odd)),
+        Err(NetworkInputError::OddFrameLength(3))
+    ));
+
+    let mut truncated = frame(&[1, 2, 3]);
+    truncated.truncate(6);
+    assert!(matches!(
+        read_frame(&mut Cursor::new(truncated)),
+        Err(NetworkInputError::Read(_))
+    ));
+}
+
+#[test]
+fn network_input_requires_a_port() {
+    assert!(validate(&NetworkInputSettings::default()).is_ok());
+    assert!(matches!(
+        validate(&NetworkInputSettings {
+            enabled: true,
+            port: 0
+        }),
+        Err(NetworkInputError::InvalidPort)
+    ));
+}
+
+#[test]
+fn recorder_captures_network_audio_without_a_microphone() {
+    let recorder = Recorder::global();
+    let (frames_tx, frames_rx) = mpsc::channel();
+    let reservation = recorder
+        .reserve()
+        .expect("reservation should succeed")
+        .with_input(AudioInput::Network(frames_rx));
+    recorder
+        .start(reservation, None, ResamplerQuality::Auto)
+        .expect("network capture should start");
+
+    let samples: Vec<f32> = (0..1_000).map(|index| index as f32 / 1_000.0).collect();
+    frames_tx.send(samples[..700].to_vec()).unwrap();
+    frames_tx.send(samples[700..].to_vec()).unwrap();
+    drop(frames_tx);
+
+    let audio = recorder.stop().expect("network capture should stop");
+    assert_eq!(audio.samples(), samples.as_slice());
+    assert_eq!(audio.health().device_sample_rate, 16_000);
+    assert!(!audio.health().resampling);
+    assert!(audio.health().processing_chain.is_empty());
+}
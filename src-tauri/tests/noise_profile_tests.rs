@@ -0,0 +1,146 @@
+mod common;
+
+use common::tone;
+use silent_keys_lib::noise_profile::{
+    closest_profile, noise_similarity, noise_spectrum, NoiseProfile, NoiseProfileError,
+    NoiseSelection, NoiseSuppression, SpectralSubtractor, MAX_NOISE_PROFILES, SPECTRUM_BINS,
+};
+
+/// Deterministic white-ish noise.
+fn noise(len: usize, amplitude: f32) -> Vec<f32> {
+    let mut state = 0x2545_f491_u32;
+    (0..len)
+        .map(|_| {
+            state ^= state << 13;
+            state ^= state >> 17;
+            state ^= state << 5;
+            (state as f32 / u32::MAX as f32 * 2.0 - 1.0) * amplitude
+        })
+        .collect()
+}
+
+fn rms(samples: &[f32]) -> f32 {
+    (samples.iter().map(|sample| sample * sample).sum::<f32>() / samples.len() as f32).sqrt()
+}
+
+/// Runs `input` through in uneven chunks, as the capture thread does.
+fn subtract(subtractor: &mut SpectralSubtractor, input: &[f32]) -> Vec<f32> {
+    let mut output = Vec::new();
+    for chunk in input.chunks(333) {
+        output.extend(subtractor.process(chunk));
+    }
+    output.extend(subtractor.finish());
+    output
+}
+
+fn profile(name: &str, samples: &[f32]) -> NoiseProfile {
+    NoiseProfile::capture(name, samples).expect("profile")
+}
+
+#[test]
+fn nothing_to_subtract_returns_the_input() {
+    let input = tone(440.0, 5_000, 0.5);
+    let output = subtract(&mut SpectralSubtractor::new(&[0.0; SPECTRUM_BINS]), &input);
+
+    assert_eq!(output.len(), input.len());
+    for (output, input) in output.iter().zip(&input) {
+        assert!((output - input).abs() < 1e-3, "{output} != {input}");
+    }
+}
+
+#[test]
+fn subtraction_quiets_the_profiled_noise() {
+    let room = noise(16_000, 0.1);
+    let profile = profile("Fan", &room);
+    let output = subtract(&mut SpectralSubtractor::new(&profile.spectrum), &room);
+
+    assert_eq!(output.len(), room.len());
+    assert!(rms(&output) < rms(&room) * 0.3);
+}
+
+#[test]
+fn auto_picks_the_matching_profile() {
+    let hiss = noise(8_000, 0.05);
+    let hum = tone(60.0, 8_000, 0.5);
+    let profiles = vec![profile("Car", &hum), profile("Office", &hiss)];
+    let heard = noise_spectrum(&noise(8_000, 0.2)).expect("spectrum");
+
+    assert!(noise_similarity(&profiles[1].spectrum, &heard) > 0.9);
+    assert_eq!(
+        closest_profile(&profiles, &heard).map(|profile| profile.name.as_str()),
+        Some("Office")
+    );
+
+    let suppression = NoiseSuppression {
+        profiles,
+        selection: NoiseSelection::Auto,
+    };
+    let mut reducer = suppression.reducer();
+    let mut recording = noise(16_000, 0.2);
+    let length = recording.len();
+    reducer.process(&mut recording);
+    let total = recording.len() + reducer.finish().len();
+
+    assert_eq!(reducer.profile(), Some("Office"));
+    assert_eq!(total, length);
+}
+
+#[test]
+fn profiles_are_named_and_bounded() {
+    assert_eq!(
+        NoiseProfile::capture(" ", &noise(1_000, 0.1)),
+        Err(NoiseProfileError::EmptyName)
+    );
+    assert_eq!(
+        NoiseProfile::capture("Car", &noise(100, 0.1)),
+        Err(NoiseProfileError::TooShort)
+    );
+
+    let mut suppression = NoiseSuppression::default();
+    for index in 0..MAX_NOISE_PROFILES {
+        suppression
+            .save_profile(profile(&format!("Room {index}"), &noise(1_000, 0.1)))
+            .unwrap();
+    }
+    assert_eq!(
+        suppression.save_profile(profile("One more", &noise(1_000, 0.1))),
+        Err(NoiseProfileError::TooManyProfiles)
+    );
+    suppression
+        .save_profile(profile("Room 0", &noise(1_000, 0.2)))
+        .expect("replacing keeps the count");
+
+    suppression.select(NoiseSelection::Profile("Room 1".to_string()));
+    suppression.delete_profile("Room 1").unwrap();
+    assert_eq!(suppression.selection, NoiseSelection::Off);
+    assert_eq!(
+        suppression.delete_profile("Room 1"),
+        Err(NoiseProfileError::NotFound("Room 1".to_string()))
+    );
+}
+
+#[test]
+fn normalizing_drops_broken_profiles_and_stale_selections() {
+    let suppression = NoiseSuppression {
+        profiles: vec![
+            NoiseProfile {
+                name: " Car ".to_string(),
+                spectrum: vec![0.1; SPECTRUM_BINS],
+            },
+            NoiseProfile {
+                name: "Car".to_string(),
+                spectrum: vec![0.2; SPECTRUM_BINS],
+            },
+            NoiseProfile {
+                name: "Short".to_string(),
+                spectrum: vec![0.1; 3],
+            },
+        ],
+        selection: NoiseSelection::Profile("Short".to_string()),
+    }
+    .normalized();
+
+    assert_eq!(suppression.info().profiles, ["Car"]);
+    assert_eq!(suppression.profiles[0].spectrum[0], 0.1);
+    assert_eq!(suppression.selection, NoiseSelection::Off);
+}
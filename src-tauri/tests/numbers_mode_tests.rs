@@ -0,0 +1,70 @@
+use silent_keys_lib::numbers_mode::constrain_to_digits;
+
+#[test]
+fn spoken_digits_become_digits() {
+    assert_eq!(
+        constrain_to_digits("One five five, zero one two."),
+        "155012"
+    );
+    assert_eq!(constrain_to_digits("nought three eight nine"), "0389");
+}
+
+#[test]
+fn ordinary_words_that_sound_like_digits_are_dropped() {
+    assert_eq!(constrain_to_digits("Go to room four for the keys"), "4");
+    assert_eq!(constrain_to_digits("Oh, I ate on the way"), "");
+}
+
+#[test]
+fn written_digits_keep_their_separators() {
+    assert_eq!(constrain_to_digits("+1 (555) 010-4477."), "+1(555)010-4477");
+    assert_eq!(constrain_to_digits("Call 1,500 now"), "1500");
+}
+
+#[test]
+fn spoken_separators_become_symbols() {
+    assert_eq!(
+        constrain_to_digits("plus four four dash seven seven dot one"),
+        "+44-77.1"
+    );
+}
+
+#[test]
+fn tens_take_the_next_digit() {
+    assert_eq!(constrain_to_digits("twenty-one thirty zero four"), "213004");
+    assert_eq!(constrain_to_digits("thirty oh four"), "304");
+    assert_eq!(constrain_to_digits("nineteen eighty"), "1980");
+}
+
+#[test]
+fn repeat_words_repeat_the_next_digit() {
+    assert_eq!(
+        constrain_to_digits("double five triple zero seven"),
+        "550007"
+    );
+}
+
+#[test]
+fn scale_words_multiply_the_number_before_them() {
+    assert_eq!(constrain_to_digits("two thousand twenty"), "2020");
+    assert_eq!(constrain_to_digits("two thousand and twenty four"), "2024");
+    assert_eq!(constrain_to_digits("nineteen hundred ninety nine"), "1999");
+    assert_eq!(
+        constrain_to_digits("three hundred twenty thousand five hundred"),
+        "320500"
+    );
+    assert_eq!(constrain_to_digits("a hundred"), "100");
+}
+
+#[test]
+fn scale_words_leave_digit_by_digit_numbers_alone() {
+    assert_eq!(constrain_to_digits("five five five two hundred"), "555200");
+    assert_eq!(constrain_to_digits("one hundred five five"), "1055");
+    assert_eq!(constrain_to_digits("two thousand dash seven"), "2000-7");
+}
+
+#[test]
+fn words_that_are_not_numbers_are_dropped() {
+    assert_eq!(constrain_to_digits("My number is seven, I think."), "7");
+    assert_eq!(constrain_to_digits("Hello there."), "");
+}
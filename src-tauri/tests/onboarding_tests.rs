@@ -0,0 +1,89 @@
+use silent_keys_lib::onboarding::{Onboarding, OnboardingEvent, OnboardingStep};
+
+#[test]
+fn setup_starts_at_the_download_unless_the_model_is_loaded_or_setup_is_done() {
+    assert_eq!(
+        Onboarding::new(false, false).step,
+        OnboardingStep::ModelDownload
+    );
+    assert_eq!(
+        Onboarding::new(false, true).step,
+        OnboardingStep::MicPermission
+    );
+    assert!(Onboarding::new(true, false).is_done());
+}
+
+#[test]
+fn each_passed_step_leads_to_the_next_until_the_sample_is_heard() {
+    let mut onboarding = Onboarding::new(false, false);
+
+    for (event, step) in [
+        (OnboardingEvent::ModelReady, OnboardingStep::MicPermission),
+        (OnboardingEvent::MicrophoneOpened, OnboardingStep::MicTest),
+        (
+            OnboardingEvent::MicTestPassed,
+            OnboardingStep::ShortcutCapture,
+        ),
+        (
+            OnboardingEvent::ShortcutConfirmed,
+            OnboardingStep::SampleDictation,
+        ),
+        (
+            OnboardingEvent::SampleDictated(" hello there ".to_string()),
+            OnboardingStep::Done,
+        ),
+    ] {
+        assert!(onboarding.apply(event));
+        assert_eq!(onboarding.step, step);
+    }
+    assert_eq!(onboarding.sample_text.as_deref(), Some("hello there"));
+}
+
+#[test]
+fn outcomes_of_other_steps_are_ignored() {
+    let mut onboarding = Onboarding::new(false, false);
+
+    assert!(!onboarding.apply(OnboardingEvent::MicTestPassed));
+    assert!(!onboarding.apply(OnboardingEvent::SampleDictated("hi".to_string())));
+    assert_eq!(onboarding.step, OnboardingStep::ModelDownload);
+
+    onboarding.apply(OnboardingEvent::SkipStep);
+    assert!(!onboarding.apply(OnboardingEvent::ModelReady));
+    assert_eq!(onboarding.step, OnboardingStep::MicPermission);
+}
+
+#[test]
+fn a_failed_step_keeps_its_problem_until_it_passes_or_is_skipped() {
+    let mut onboarding = Onboarding::new(false, true);
+
+    onboarding.apply(OnboardingEvent::MicrophoneBlocked("denied".to_string()));
+    assert_eq!(onboarding.step, OnboardingStep::MicPermission);
+    assert_eq!(onboarding.problem.as_deref(), Some("denied"));
+
+    onboarding.apply(OnboardingEvent::MicrophoneOpened);
+    assert_eq!(onboarding.step, OnboardingStep::MicTest);
+    assert_eq!(onboarding.problem, None);
+
+    onboarding.apply(OnboardingEvent::MicTestFailed("quiet".to_string()));
+    onboarding.apply(OnboardingEvent::SkipStep);
+    assert_eq!(onboarding.step, OnboardingStep::ShortcutCapture);
+    assert_eq!(onboarding.problem, None);
+}
+
+#[test]
+fn a_silent_sample_asks_again_and_finishing_ends_setup_from_any_step() {
+    let mut onboarding = Onboarding::new(false, true);
+    for _ in 0..3 {
+        onboarding.apply(OnboardingEvent::SkipStep);
+    }
+    assert_eq!(onboarding.step, OnboardingStep::SampleDictation);
+
+    assert!(onboarding.apply(OnboardingEvent::SampleDictated("  ".to_string())));
+    assert_eq!(onboarding.step, OnboardingStep::SampleDictation);
+    assert!(onboarding.problem.is_some());
+
+    assert!(onboarding.apply(OnboardingEvent::Finish));
+    assert!(onboarding.is_done());
+    assert_eq!(onboarding.sample_text, None);
+    assert!(!onboarding.apply(OnboardingEvent::SkipStep));
+}
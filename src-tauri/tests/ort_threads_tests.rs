@@ -0,0 +1,71 @@
+use std::time::Duration;
+
+use silent_keys_lib::asr::{
+    tune_threads, tuning_candidates, OrtThreads, ThreadTiming, MAX_ORT_THREADS,
+};
+
+fn threads(intra_threads: usize, inter_threads: usize) -> OrtThreads {
+    OrtThreads {
+        intra_threads,
+        inter_threads,
+    }
+}
+
+#[test]
+fn automatic_counts_leave_cores_free_and_run_one_inter_thread() {
+    assert_eq!(OrtThreads::default().resolved(8), threads(4, 1));
+    assert_eq!(OrtThreads::default().resolved(1), threads(1, 1));
+    assert_eq!(OrtThreads::default().resolved(64), threads(8, 1));
+    assert_eq!(threads(6, 0).resolved(8), threads(6, 1));
+    assert_eq!(threads(0, 2).resolved(8), threads(4, 2));
+    assert_eq!(
+        threads(100, 100).resolved(8),
+        threads(MAX_ORT_THREADS, MAX_ORT_THREADS)
+    );
+}
+
+#[test]
+fn candidates_cover_a_quarter_half_and_all_cores_without_repeats() {
+    assert_eq!(
+        tuning_candidates(8),
+        vec![threads(2, 1), threads(4, 1), threads(8, 1), threads(4, 2)]
+    );
+    assert_eq!(tuning_candidates(1), vec![threads(1, 1), threads(1, 2)]);
+    assert!(tuning_candidates(128)
+        .iter()
+        .all(|candidate| candidate.intra_threads <= MAX_ORT_THREADS));
+}
+
+#[test]
+fn the_fastest_candidate_wins_and_ties_keep_fewer_threads() {
+    let candidates = tuning_candidates(8);
+    let tuning = tune_threads(&candidates, |candidate| {
+        Ok::<_, ()>(Duration::from_millis(match candidate.intra_threads {
+            2 => 900,
+            _ => 400,
+        }))
+    })
+    .unwrap();
+
+    assert_eq!(tuning.fastest, threads(4, 1));
+    assert_eq!(
+        tuning.timings[0],
+        ThreadTiming {
+            threads: threads(2, 1),
+            decode_ms: 900
+        }
+    );
+    assert_eq!(tuning.timings.len(), candidates.len());
+}
+
+#[test]
+fn a_failed_load_stops_tuning() {
+    let mut measured = 0;
+    let result = tune_threads(&tuning_candidates(8), |_| {
+        measured += 1;
+        Err::<Duration, _>("model missing")
+    });
+
+    assert_eq!(result, Err("model missing"));
+    assert_eq!(measured, 1);
+}
@@ -0,0 +1,43 @@
+use chrono::NaiveDate;
+use silent_keys_lib::output_template::{render, validate, OutputTemplates};
+
+fn now() -> chrono::NaiveDateTime {
+    NaiveDate::from_ymd_opt(2026, 3, 4)
+        .unwrap()
+        .and_hms_opt(9, 5, 0)
+        .unwrap()
+}
+
+#[test]
+fn template_expands_text_date_and_time() {
+    assert_eq!(
+        render("- [ ] {{text}} ({{date}} {{ time }})", "call Sam", now()),
+        "- [ ] call Sam (2026-03-04 09:05)"
+    );
+}
+
+#[test]
+fn empty_template_types_transcript_unchanged() {
+    assert_eq!(render("  ", "as spoken", now()), "as spoken");
+}
+
+#[test]
+fn transcript_braces_and_unknown_variables_are_not_expanded() {
+    assert_eq!(
+        render("{{app}}: {{text}} {{", "say {{date}}", now()),
+        "{{app}}: say {{date}} {{"
+    );
+}
+
+#[test]
+fn templates_must_include_the_transcript() {
+    assert!(validate("").is_ok());
+    assert!(validate("> {{ text }}").is_ok());
+    assert!(validate("{{date}}").is_err());
+
+    let templates = OutputTemplates {
+        record: "{{text}}".to_string(),
+        llm_handoff: "summary".to_string(),
+    };
+    assert!(templates.validate().is_err());
+}
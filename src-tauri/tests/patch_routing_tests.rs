@@ -0,0 +1,213 @@
+use std::sync::{Arc, Mutex};
+
+use silent_keys_lib::patch_routing::{
+    PatchDestinations, PatchRouter, PatchRouting, PatchSource, WINDOWED_DRAFT_MIN_CHARS,
+};
+use silent_keys_lib::recording::SessionId;
+use silent_keys_lib::streaming::TranscriptPatch;
+
+type Log = Arc<Mutex<Vec<String>>>;
+
+fn describe(patch: &TranscriptPatch) -> String {
+    match patch {
+        TranscriptPatch::Draft { full_text, .. } => format!("draft {full_text}"),
+        TranscriptPatch::DraftTail { keep, tail, .. } => format!("draft tail {keep} {tail}"),
+        TranscriptPatch::Commit { append } => format!("commit {append}"),
+        TranscriptPatch::Replace { range, text } => {
+            format!("replace {}..{} {text}", range.start, range.end)
+        }
+    }
+}
+
+fn commit(text: &str) -> TranscriptPatch {
+    TranscriptPatch::Commit {
+        append: text.to_string(),
+    }
+}
+
+/// A router whose typing, window, and mirror sinks write to separate logs.
+fn recording_router(destinations: PatchDestinations) -> (PatchRouter, Log, Log, Log) {
+    let (typed, shown, mirrored) = (Log::default(), Log::default(), Log::default());
+    let (typing, window, mirror) = (typed.clone(), shown.clone(), mirrored.clone());
+    let router = PatchRouter::new(Some(SessionId::from_raw_for_tests(7)), destinations)
+        .with_typing(move |patch, _| {
+            typing.lock().unwrap().push(describe(patch));
+            Ok(())
+        })
+        .with_window(move |update| {
+            assert_eq!(update.session_id, Some(SessionId::from_raw_for_tests(7)));
+            window.lock().unwrap().push(describe(&update.patch));
+            Ok(())
+        })
+        .with_mirror(move |patch| mirror.lock().unwrap().push(describe(&patch)));
+    (router, typed, shown, mirrored)
+}
+
+fn entries(log: &Log) -> Vec<String> {
+    log.lock().unwrap().clone()
+}
+
+#[test]
+fn shortcut_dictations_type_and_show_while_the_window_only_shows_by_default() {
+    let routing = PatchRouting::default();
+
+    assert_eq!(
+        routing.destinations(PatchSource::Shortcut),
+        PatchDestinations::Both
+    );
+    assert_eq!(
+        routing.destinations(PatchSource::App),
+        PatchDestinations::Window
+    );
+}
+
+#[test]
+fn both_types_commits_and_shows_every_patch() {
+    let (router, typed, shown, _) = recording_router(PatchDestinations::Both);
+
+    router.live(commit("hello")).unwrap();
+    router
+        .live(TranscriptPatch::Draft {
+            full_text: "hello thair".into(),
+        })
+        .unwrap();
+    router.finish("hello there".into()).unwrap();
+
+    assert_eq!(
+        entries(&typed),
+        ["commit hello", "replace 0..5 hello there"]
+    );
+    assert_eq!(
+        entries(&shown),
+        [
+            "commit hello",
+            "draft hello thair",
+            "replace 0..5 hello there"
+        ]
+    );
+}
+
+#[test]
+fn windowed_drafts_reach_only_the_window() {
+    let (router, _, shown, mirrored) = recording_router(PatchDestinations::Both);
+    let router = router.with_windowed_drafts(true);
+    let long = format!(
+        "{} more",
+        "word ".repeat(WINDOWED_DRAFT_MIN_CHARS as usize / 5)
+    );
+
+    router.live(commit("word ")).unwrap();
+    router
+        .live(TranscriptPatch::Draft {
+            full_text: long.clone(),
+        })
+        .unwrap();
+
+    assert_eq!(
+        entries(&shown),
+        [
+            "commit word ".to_string(),
+            format!("draft tail 5 {}", &long[5..])
+        ]
+    );
+    assert_eq!(entries(&mirrored)[1], format!("draft {long}"));
+}
+
+#[test]
+fn window_only_never_types() {
+    let (router, typed, shown, _) = recording_router(PatchDestinations::Window);
+
+    router.live(commit("hello")).unwrap();
+    router.finish("hello".into()).unwrap();
+
+    assert!(!router.types());
+    assert!(entries(&typed).is_empty());
+    assert_eq!(entries(&shown), ["commit hello", "replace 0..5 hello"]);
+}
+
+#[test]
+fn type_only_leaves_the_window_blank() {
+    let (router, typed, shown, _) = recording_router(PatchDestinations::Type);
+
+    router.live(commit("hello")).unwrap();
+    router.finish("hello".into()).unwrap();
+
+    assert_eq!(entries(&typed), ["commit hello", "replace 0..5 hello"]);
+    assert!(entries(&shown).is_empty());
+}
+
+#[test]
+fn neither_still_feeds_the_mirror() {
+    let (router, typed, shown, mirrored) = recording_router(PatchDestinations::Neither);
+
+    router.live(commit("hello")).unwrap();
+    router.finish("hello".into()).unwrap();
+
+    assert!(entries(&typed).is_empty());
+    assert!(entries(&shown).is_empty());
+    assert_eq!(entries(&mirrored), ["commit hello", "replace 0..5 hello"]);
+}
+
+#[test]
+fn live_typing_can_wait_for_the_final_text() {
+    let (router, typed, shown, _) = recording_router(PatchDestinations::Both);
+    let router = router.with_live_typing(false);
+
+    router.live(commit("hello")).unwrap();
+    router.finish("Hello.".into()).unwrap();
+
+    assert_eq!(entries(&typed), ["replace 0..5 Hello."]);
+    assert_eq!(entries(&shown), ["commit hello", "replace 0..5 Hello."]);
+}
+
+#[test]
+fn clones_share_the_transcript_the_final_text_replaces() {
+    let typed = Log::default();
+    let typing = typed.clone();
+    let router =
+        PatchRouter::new(None, PatchDestinations::Type).with_typing(move |patch, transcript| {
+            if matches!(patch, TranscriptPatch::Replace { .. }) {
+                typing
+                    .lock()
+                    .unwrap()
+                    .push(transcript.committed().to_string());
+            }
+            Ok(())
+        });
+    let live = router.clone();
+
+    live.live(commit("héllo ")).unwrap();
+    live.live(commit("wörld")).unwrap();
+    router.finish("Hello world.".into()).unwrap();
+
+    assert_eq!(entries(&typed), ["Hello world."]);
+}
+
+#[test]
+fn window_failures_do_not_interrupt_typing() {
+    let typed = Log::default();
+    let typing = typed.clone();
+    let router = PatchRouter::new(None, PatchDestinations::Both)
+        .with_window(|_| Err("window closed".to_string()))
+        .with_typing(move |patch, _| {
+            typing.lock().unwrap().push(describe(patch));
+            Ok(())
+        });
+
+    router.finish("hello".into()).unwrap();
+    assert_eq!(entries(&typed), ["replace 0..0 hello"]);
+
+    let failing = PatchRouter::new(None, PatchDestinations::Type)
+        .with_typing(|_, _| Err("keyboard unavailable".to_string()));
+    assert!(failing.finish("hello".into()).is_err());
+}
+
+#[test]
+fn routing_reads_partial_settings() {
+    let routing: PatchRouting =
+        serde_json::from_value(serde_json::json!({ "app": "both" })).unwrap();
+
+    assert_eq!(routing.app, PatchDestinations::Both);
+    assert_eq!(routing.shortcut, PatchDestinations::Both);
+    assert!(serde_json::from_value::<PatchRouting>(serde_json::json!({ "app": "ui" })).is_err());
+}
@@ -0,0 +1,77 @@
+mod common;
+
+use common::tone;
+use silent_keys_lib::streaming::{PhraseBuffer, StreamingStrategy};
+use silent_keys_lib::vad::VadConfig;
+
+const SECOND: usize = 16_000;
+
+fn feed_all(buffer: &mut PhraseBuffer, samples: &[f32]) -> Vec<Vec<f32>> {
+    samples
+        .chunks(1_000)
+        .flat_map(|chunk| buffer.feed(chunk))
+        .collect()
+}
+
+#[test]
+fn phrases_are_released_at_each_pause_with_padding() {
+    let config = VadConfig::default();
+    let mut samples = vec![0.0; 14_400];
+    samples.extend(tone(220.0, 14_400, 0.5));
+    samples.extend(vec![0.0; 28_800]);
+    samples.extend(tone(220.0, 9_600, 0.5));
+    samples.extend(vec![0.0; 14_400]);
+    let mut buffer = PhraseBuffer::new(config, 10 * SECOND);
+
+    let phrases = feed_all(&mut buffer, &samples);
+
+    let padding = config.padding_samples;
+    assert_eq!(phrases.len(), 2);
+    assert!(phrases[0] == samples[14_400 - padding..28_800 + padding]);
+    assert!(phrases[1] == samples[57_600 - padding..67_200 + padding]);
+    assert!(buffer.finish().is_empty());
+}
+
+#[test]
+fn long_speech_is_cut_at_the_phrase_limit() {
+    let samples = tone(220.0, 25 * SECOND, 0.5);
+    let mut buffer = PhraseBuffer::new(VadConfig::default(), 10 * SECOND);
+
+    let mut phrases = feed_all(&mut buffer, &samples);
+    assert_eq!(phrases.len(), 2);
+    phrases.extend(buffer.finish());
+
+    assert_eq!(phrases.len(), 3);
+    assert!(phrases.concat() == samples);
+}
+
+#[test]
+fn speech_still_open_at_the_end_is_released_by_finish() {
+    let mut samples = vec![0.0; 14_400];
+    samples.extend(tone(220.0, 9_600, 0.5));
+    let mut buffer = PhraseBuffer::new(VadConfig::default(), 10 * SECOND);
+
+    assert!(feed_all(&mut buffer, &samples).is_empty());
+    let phrases = buffer.finish();
+
+    let padding = VadConfig::default().padding_samples;
+    assert_eq!(phrases.len(), 1);
+    assert!(phrases[0] == samples[14_400 - padding..]);
+}
+
+#[test]
+fn silence_yields_no_phrases() {
+    let mut buffer = PhraseBuffer::new(VadConfig::default(), 10 * SECOND);
+
+    assert!(feed_all(&mut buffer, &vec![0.0; 5 * SECOND]).is_empty());
+    assert!(buffer.finish().is_empty());
+}
+
+#[test]
+fn strategies_use_snake_case_and_default_to_continuous() {
+    assert_eq!(StreamingStrategy::default(), StreamingStrategy::Continuous);
+    assert_eq!(
+        serde_json::from_value::<StreamingStrategy>(serde_json::json!("phrase")).unwrap(),
+        StreamingStrategy::Phrase
+    );
+}
@@ -0,0 +1,30 @@
+use std::time::{Duration, Instant};
+
+use silent_keys_lib::recording::{PlaybackGate, PLAYBACK_ECHO_TAIL};
+
+#[test]
+fn capture_is_muted_during_playback_and_its_echo_tail() {
+    let gate = PlaybackGate::new(PLAYBACK_ECHO_TAIL);
+    assert!(!gate.is_muted_at(Instant::now()));
+
+    let playback = gate.begin();
+    assert!(gate.is_muted_at(Instant::now()));
+    drop(playback);
+
+    let ended = Instant::now();
+    assert!(gate.is_muted_at(ended));
+    assert!(!gate.is_muted_at(ended + PLAYBACK_ECHO_TAIL + Duration::from_millis(1)));
+}
+
+#[test]
+fn overlapping_playback_keeps_capture_muted_until_the_last_ends() {
+    let gate = PlaybackGate::new(Duration::ZERO);
+    let first = gate.begin();
+    let second = gate.begin();
+
+    drop(first);
+    assert!(gate.is_muted_at(Instant::now() + Duration::from_secs(1)));
+
+    drop(second);
+    assert!(!gate.is_muted_at(Instant::now() + Duration::from_secs(1)));
+}
@@ -0,0 +1,165 @@
+use std::collections::BTreeMap;
+
+use silent_keys_lib::post_process::{
+    PostProcessError, PostProcessStage, PostProcessing, PostProcessor, StageContext,
+    CODE_MODE_STAGE, SPELLING_STAGE,
+};
+use silent_keys_lib::text_commands::{interpret, TextCommands};
+
+/// A stand-in for a community language pack's number formatting.
+struct GermanNumbers;
+
+impl PostProcessStage for GermanNumbers {
+    fn name(&self) -> &'static str {
+        "de_numbers"
+    }
+
+    fn languages(&self) -> &'static [&'static str] {
+        &["de"]
+    }
+
+    fn apply(&self, text: &str, _: &StageContext<'_>) -> String {
+        text.replace("zwei", "2")
+    }
+}
+
+struct Shout;
+
+impl PostProcessStage for Shout {
+    fn name(&self) -> &'static str {
+        "shout"
+    }
+
+    fn apply(&self, text: &str, _: &StageContext<'_>) -> String {
+        text.to_uppercase()
+    }
+}
+
+fn with_pack() -> PostProcessor {
+    let mut processor = PostProcessor::builtin();
+    processor
+        .register(Box::new(GermanNumbers))
+        .expect("stage should register");
+    processor
+        .register(Box::new(Shout))
+        .expect("stage should register");
+    processor
+}
+
+fn selection(entries: &[(&str, &[&str])]) -> PostProcessing {
+    PostProcessing {
+        languages: entries
+            .iter()
+            .map(|(language, stages)| {
+                (
+                    language.to_string(),
+                    stages.iter().map(|stage| stage.to_string()).collect(),
+                )
+            })
+            .collect(),
+    }
+}
+
+#[test]
+fn builtin_stages_match_the_text_commands() {
+    let commands = TextCommands {
+        spelling: true,
+        code_mode: true,
+    };
+    let context = StageContext {
+        language: Some("en-US"),
+        text_commands: &commands,
+    };
+    let text = "call spell F O O open paren close paren";
+
+    assert_eq!(
+        PostProcessor::builtin().run(text, &context, &PostProcessing::default()),
+        interpret(text, &commands)
+    );
+    assert_eq!(
+        PostProcessor::builtin().plan(Some("en-US"), &PostProcessing::default()),
+        vec![SPELLING_STAGE, CODE_MODE_STAGE]
+    );
+}
+
+#[test]
+fn language_stages_only_run_for_their_language() {
+    let processor = with_pack();
+    let default = PostProcessing::default();
+
+    assert_eq!(
+        processor.plan(Some("de-AT"), &default),
+        vec![SPELLING_STAGE, CODE_MODE_STAGE, "de_numbers", "shout"]
+    );
+    assert_eq!(
+        processor.plan(Some("en-US"), &default),
+        vec![SPELLING_STAGE, CODE_MODE_STAGE, "shout"]
+    );
+    assert_eq!(
+        processor.plan(None, &default),
+        vec![SPELLING_STAGE, CODE_MODE_STAGE, "shout"]
+    );
+}
+
+#[test]
+fn selection_picks_stages_and_order_per_language() {
+    let processor = with_pack();
+    let commands = TextCommands::default();
+    let post_processing = selection(&[("de", &["shout", "de_numbers"]), ("de-ch", &[])]);
+
+    assert_eq!(
+        processor.plan(Some("de-DE"), &post_processing),
+        vec!["shout", "de_numbers"]
+    );
+    assert!(processor.plan(Some("de-CH"), &post_processing).is_empty());
+    let context = StageContext {
+        language: Some("de-DE"),
+        text_commands: &commands,
+    };
+    // Shouting first leaves "ZWEI" for the number stage to miss.
+    assert_eq!(
+        processor.run("zwei Katzen", &context, &post_processing),
+        "ZWEI KATZEN"
+    );
+}
+
+#[test]
+fn unknown_and_duplicate_stages_are_rejected() {
+    let mut processor = with_pack();
+
+    assert_eq!(
+        processor.validate(&selection(&[("fr", &["shout", "fr_numbers"])])),
+        Err(PostProcessError::UnknownStage("fr_numbers".to_string()))
+    );
+    assert_eq!(
+        processor.validate(&selection(&[("fr", &["shout"])])),
+        Ok(())
+    );
+    assert_eq!(
+        processor.register(Box::new(Shout)),
+        Err(PostProcessError::DuplicateStage("shout".to_string()))
+    );
+    // An unknown stage saved before its pack was removed is skipped.
+    assert_eq!(
+        processor.plan(Some("fr"), &selection(&[("fr", &["gone", "shout"])])),
+        vec!["shout"]
+    );
+}
+
+#[test]
+fn normalized_cleans_language_codes_and_stage_names() {
+    let post_processing = PostProcessing {
+        languages: BTreeMap::from([
+            (
+                " DE-at ".to_string(),
+                vec![" Shout ".to_string(), " ".to_string()],
+            ),
+            ("  ".to_string(), vec!["shout".to_string()]),
+        ]),
+    }
+    .normalized();
+
+    assert_eq!(post_processing, selection(&[("de-at", &["shout"])]));
+    let missing: PostProcessing = serde_json::from_value(serde_json::json!({})).unwrap();
+    assert_eq!(missing, PostProcessing::default());
+}
@@ -0,0 +1,48 @@
+use silent_keys_lib::power::{
+    battery_statuses_on_battery, pmset_on_battery, win32_battery_on_battery, PowerSaverMode,
+};
+
+#[test]
+fn auto_follows_the_battery_and_overrides_ignore_it() {
+    assert!(PowerSaverMode::Auto.active(|| true));
+    assert!(!PowerSaverMode::Auto.active(|| false));
+    assert!(PowerSaverMode::On.active(|| false));
+    assert!(!PowerSaverMode::Off.active(|| panic!("override should not query the battery")));
+}
+
+#[test]
+fn pmset_reports_the_power_source() {
+    let battery =
+        "Now drawing from 'Battery Power'\n -InternalBattery-0 (id=1)\t84%; discharging\n";
+    let plugged = "Now drawing from 'AC Power'\n -InternalBattery-0 (id=1)\t100%; charged\n";
+
+    assert_eq!(pmset_on_battery(battery), Some(true));
+    assert_eq!(pmset_on_battery(plugged), Some(false));
+    assert_eq!(pmset_on_battery(""), None);
+}
+
+#[test]
+fn any_discharging_battery_means_unplugged() {
+    assert!(battery_statuses_on_battery(["Full\n", "Discharging\n"]));
+    assert!(!battery_statuses_on_battery([
+        "Charging\n",
+        "Not charging\n"
+    ]));
+    assert!(!battery_statuses_on_battery(std::iter::empty()));
+}
+
+#[test]
+fn win32_battery_status_one_is_discharging() {
+    assert_eq!(win32_battery_on_battery("1\r\n"), Some(true));
+    assert_eq!(win32_battery_on_battery("2\r\n"), Some(false));
+    assert_eq!(win32_battery_on_battery(""), None);
+}
+
+#[test]
+fn modes_use_snake_case_and_default_to_auto() {
+    assert_eq!(PowerSaverMode::default(), PowerSaverMode::Auto);
+    assert_eq!(
+        serde_json::to_value(PowerSaverMode::Off).unwrap(),
+        serde_json::json!("off")
+    );
+}
@@ -0,0 +1,148 @@
+#![cfg(feature = "desktop")]
+
+use silent_keys_lib::profiles::{switch, ProfileError, ProfileList, ProfileSettings, Profiles};
+use silent_keys_lib::settings::{Settings, TypingPace};
+use silent_keys_lib::text_commands::TextCommands;
+use silent_keys_lib::time_stretch::TimeStretch;
+
+fn settings_with(language: &str, streaming_enabled: bool) -> Settings {
+    Settings {
+        asr_language: language.to_string(),
+        streaming_enabled,
+        ..Settings::default()
+    }
+}
+
+#[test]
+fn first_profile_becomes_active_and_names_are_unique() {
+    let current = settings_with("en-US", true);
+    let mut profiles = Profiles::default();
+
+    profiles.create("  Work ", &current).unwrap();
+    profiles.create("Personal", &current).unwrap();
+
+    assert_eq!(profiles.active.as_deref(), Some("Work"));
+    assert_eq!(
+        profiles.create("work", &current),
+        Err(ProfileError::Duplicate("work".to_string()))
+    );
+    assert_eq!(
+        profiles.create("   ", &current),
+        Err(ProfileError::EmptyName)
+    );
+    assert_eq!(
+        profiles.create(&"x".repeat(41), &current),
+        Err(ProfileError::NameTooLong)
+    );
+    let list = ProfileList::from(&profiles);
+    assert_eq!(list.names, ["Work", "Personal"]);
+}
+
+#[test]
+fn deleting_the_active_profile_leaves_no_profile_active() {
+    let current = Settings::default();
+    let mut profiles = Profiles::default();
+    profiles.create("Work", &current).unwrap();
+    profiles.create("Personal", &current).unwrap();
+
+    profiles.delete("WORK").unwrap();
+
+    assert_eq!(profiles.active, None);
+    assert_eq!(profiles.names(), ["Personal"]);
+    assert_eq!(
+        profiles.delete("Work"),
+        Err(ProfileError::NotFound("Work".to_string()))
+    );
+    profiles.create("Travel", &current).unwrap();
+    assert_eq!(profiles.active.as_deref(), Some("Travel"));
+}
+
+#[test]
+fn switch_saves_the_active_profile_and_applies_the_chosen_one() {
+    let mut current = settings_with("en-US", true);
+    let work = current.clone();
+    current.profiles.create("Work", &work).unwrap();
+    let personal = Settings {
+        typing_pace: TypingPace {
+            chunk_chars: 4,
+            chunk_delay_ms: 20,
+            ..TypingPace::default()
+        },
+        ..settings_with("de-DE", false)
+    };
+    current.profiles.create("Personal", &personal).unwrap();
+    current.asr_language = "fr-FR".to_string();
+    current.webhook.url = "https://example.com/hook".to_string();
+
+    let next = switch(&current, "personal").unwrap();
+
+    assert_eq!(next.profiles.active.as_deref(), Some("Personal"));
+    assert_eq!(next.asr_language, "de-DE");
+    assert!(!next.streaming_enabled);
+    assert_eq!(next.typing_pace, personal.typing_pace);
+    assert_eq!(next.webhook.url, "https://example.com/hook");
+
+    let back = switch(&next, "Work").unwrap();
+    assert_eq!(back.asr_language, "fr-FR");
+    assert!(back.streaming_enabled);
+    assert_eq!(
+        switch(&back, "Travel"),
+        Err(ProfileError::NotFound("Travel".to_string()))
+    );
+}
+
+#[test]
+fn code_mode_follows_the_profile() {
+    let mut current = Settings::default();
+    let plain = current.clone();
+    current.profiles.create("Prose", &plain).unwrap();
+    let code = Settings {
+        text_commands: TextCommands {
+            code_mode: true,
+            ..TextCommands::default()
+        },
+        ..Settings::default()
+    };
+    current.profiles.create("Code", &code).unwrap();
+
+    let coding = switch(&current, "Code").unwrap();
+    assert!(coding.text_commands.code_mode);
+    let writing = switch(&coding, "Prose").unwrap();
+    assert!(!writing.text_commands.code_mode);
+}
+
+#[test]
+fn time_stretch_follows_the_profile() {
+    let mut current = Settings::default();
+    let plain = current.clone();
+    current.profiles.create("Normal", &plain).unwrap();
+    let fast = Settings {
+        time_stretch: TimeStretch {
+            enabled: true,
+            speed_percent: 85,
+        },
+        ..Settings::default()
+    };
+    current.profiles.create("Fast", &fast).unwrap();
+
+    let slowed = switch(&current, "Fast").unwrap();
+    assert_eq!(slowed.time_stretch.speed(), Some(0.85));
+    let normal = switch(&slowed, "Normal").unwrap();
+    assert_eq!(normal.time_stretch.speed(), None);
+}
+
+#[test]
+fn profiles_saved_before_text_commands_load_with_them_off() {
+    let saved = serde_json::json!({
+        "asr_language": "en-US",
+        "streaming_enabled": false,
+        "typing_pace": { "chunk_chars": 0, "chunk_delay_ms": 0 },
+        "output_templates": { "record": "", "llm_handoff": "" },
+        "capture_file": { "enabled": false, "path": "" }
+    });
+
+    let settings: ProfileSettings = serde_json::from_value(saved).unwrap();
+
+    assert_eq!(settings.text_commands, TextCommands::default());
+    assert_eq!(settings.time_stretch, TimeStretch::default());
+}
@@ -0,0 +1,71 @@
+use silent_keys_lib::quiet_hours::{QuietHours, QuietRange};
+
+const MONDAY: u8 = 0;
+const TUESDAY: u8 = 1;
+const SUNDAY: u8 = 6;
+
+fn quiet_hours(ranges: Vec<QuietRange>) -> QuietHours {
+    QuietHours {
+        enabled: true,
+        ranges,
+    }
+}
+
+#[test]
+fn daytime_range_covers_only_listed_days() {
+    let schedule = quiet_hours(vec![QuietRange {
+        days: vec![MONDAY],
+        start_minute: 9 * 60,
+        end_minute: 17 * 60,
+    }]);
+
+    assert!(schedule.is_quiet(MONDAY, 9 * 60));
+    assert!(!schedule.is_quiet(MONDAY, 17 * 60));
+    assert!(!schedule.is_quiet(TUESDAY, 12 * 60));
+}
+
+#[test]
+fn overnight_range_continues_into_the_next_day() {
+    let schedule = quiet_hours(vec![QuietRange {
+        days: vec![SUNDAY],
+        start_minute: 22 * 60,
+        end_minute: 7 * 60,
+    }]);
+
+    assert!(schedule.is_quiet(SUNDAY, 23 * 60));
+    assert!(schedule.is_quiet(MONDAY, 6 * 60 + 59));
+    assert!(!schedule.is_quiet(MONDAY, 7 * 60));
+    assert!(!schedule.is_quiet(SUNDAY, 6 * 60));
+}
+
+#[test]
+fn disabled_schedule_is_never_quiet() {
+    let mut schedule = quiet_hours(vec![QuietRange {
+        days: vec![MONDAY],
+        start_minute: 0,
+        end_minute: 0,
+    }]);
+    assert!(schedule.is_quiet(MONDAY, 12 * 60));
+
+    schedule.enabled = false;
+    assert!(!schedule.is_quiet(MONDAY, 12 * 60));
+}
+
+#[test]
+fn normalization_drops_invalid_days_and_clamps_minutes() {
+    let schedule = quiet_hours(vec![QuietRange {
+        days: vec![9, TUESDAY, MONDAY, TUESDAY],
+        start_minute: 5_000,
+        end_minute: 60,
+    }])
+    .normalized();
+
+    assert_eq!(
+        schedule.ranges,
+        vec![QuietRange {
+            days: vec![MONDAY, TUESDAY],
+            start_minute: 24 * 60 - 1,
+            end_minute: 60,
+        }]
+    );
+}
@@ -0,0 +1,51 @@
+use log::Level;
+use silent_keys_lib::recent_logs::{parse_level, LogEntry, RecentLogs, RecentLogsError};
+
+fn entry(message: &str) -> LogEntry {
+    LogEntry {
+        timestamp: "2026-03-04 09:05:00.000".to_string(),
+        level: String::new(),
+        target: "silent_keys_lib".to_string(),
+        message: message.to_string(),
+    }
+}
+
+fn messages(entries: &[LogEntry]) -> Vec<&str> {
+    entries.iter().map(|entry| entry.message.as_str()).collect()
+}
+
+#[test]
+fn ring_drops_the_oldest_records_at_capacity() {
+    let logs = RecentLogs::new(3);
+    for message in ["one", "two", "three", "four"] {
+        logs.push(Level::Info, entry(message));
+    }
+
+    assert_eq!(
+        messages(&logs.recent(Level::Trace, 10)),
+        ["two", "three", "four"]
+    );
+}
+
+#[test]
+fn recent_filters_by_severity_and_keeps_the_newest() {
+    let logs = RecentLogs::new(10);
+    logs.push(Level::Error, entry("failed"));
+    logs.push(Level::Debug, entry("detail"));
+    logs.push(Level::Warn, entry("slow"));
+    logs.push(Level::Info, entry("loaded"));
+
+    assert_eq!(messages(&logs.recent(Level::Warn, 10)), ["failed", "slow"]);
+    assert_eq!(messages(&logs.recent(Level::Trace, 2)), ["slow", "loaded"]);
+    assert!(logs.recent(Level::Info, 0).is_empty());
+}
+
+#[test]
+fn levels_parse_case_insensitively() {
+    assert_eq!(parse_level("WARN"), Ok(Level::Warn));
+    assert_eq!(parse_level(" debug "), Ok(Level::Debug));
+    assert_eq!(
+        parse_level("verbose"),
+        Err(RecentLogsError::UnknownLevel("verbose".to_string()))
+    );
+}
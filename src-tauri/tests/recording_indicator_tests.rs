@@ -0,0 +1,40 @@
+#![cfg(feature = "desktop")]
+
+use silent_keys_lib::desktop::badge_rgba;
+use silent_keys_lib::settings::{RecordingIndicator, Settings};
+
+fn pixel(rgba: &[u8], width: u32, x: u32, y: u32) -> &[u8] {
+    let offset = ((y * width + x) * 4) as usize;
+    &rgba[offset..offset + 4]
+}
+
+#[test]
+fn badge_marks_the_lower_right_corner_only() {
+    let icon = vec![0x10; 16 * 16 * 4];
+
+    let badged = badge_rgba(&icon, 16, 16);
+
+    assert_eq!(badged.len(), icon.len());
+    assert_eq!(pixel(&badged, 16, 13, 13), [0xE5, 0x39, 0x35, 0xFF]);
+    assert_eq!(pixel(&badged, 16, 2, 2), [0x10; 4]);
+    assert_eq!(pixel(&badged, 16, 13, 2), [0x10; 4]);
+}
+
+#[test]
+fn badge_tolerates_a_short_buffer() {
+    let badged = badge_rgba(&[0; 8], 4, 4);
+
+    assert_eq!(badged, vec![0; 8]);
+}
+
+#[test]
+fn recording_indicator_pulses_by_default() {
+    assert_eq!(
+        Settings::default().recording_indicator,
+        RecordingIndicator::Pulse
+    );
+    assert_eq!(
+        serde_json::to_value(RecordingIndicator::Steady).unwrap(),
+        serde_json::json!("steady")
+    );
+}
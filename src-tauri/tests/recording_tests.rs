@@ -1,7 +1,19 @@
+#[cfg(feature = "desktop")]
 use cpal::Sample;
+use silent_keys_lib::audio_processing::{ProcessingStage, ResamplerQuality};
 use silent_keys_lib::errors::UserFacing;
-use silent_keys_lib::recording::{Recorder, RecordingError};
+use std::sync::mpsc;
+use std::thread;
+use std::time::Duration;
 
+use silent_keys_lib::recording::{
+    downmix, fixed_buffer_frames, set_queued_start_sink, AudioHealth, AudioInput,
+    InputDeviceConfig, InputDeviceSettings, Recorder, RecordingError, SessionId, WaveformBucketer,
+    MAX_BUFFER_FRAMES, MIN_DEVICE_SAMPLE_RATE,
+};
+use silent_keys_lib::streaming::{SessionUpdate, TranscriptPatch};
+
+#[cfg(feature = "desktop")]
 #[test]
 fn i8_normalization() {
     assert!((Sample::to_sample::<f32>(i8::MAX) - 0.9921875).abs() < f32::EPSILON);
@@ -9,6 +21,7 @@ fn i8_normalization() {
     assert!((Sample::to_sample::<f32>(-64i8) - (-0.5)).abs() < f32::EPSILON);
 }
 
+#[cfg(feature = "desktop")]
 #[test]
 fn i16_normalization() {
     let val = Sample::to_sample::<f32>(i16::MAX);
@@ -16,6 +29,7 @@ fn i16_normalization() {
     assert!((Sample::to_sample::<f32>(0i16)).abs() < f32::EPSILON);
 }
 
+#[cfg(feature = "desktop")]
 #[test]
 fn i32_normalization() {
     let val = Sample::to_sample::<f32>(i32::MAX);
@@ -23,6 +37,7 @@ fn i32_normalization() {
     assert!((Sample::to_sample::<f32>(0i32)).abs() < f32::EPSILON);
 }
 
+#[cfg(feature = "desktop")]
 #[test]
 fn f32_passthrough() {
     assert!((Sample::to_sample::<f32>(0.5f32) - 0.5).abs() < f32::EPSILON);
@@ -34,6 +49,9 @@ fn recording_error_user_messages() {
     assert!(!RecordingError::AlreadyRecording.user_message().is_empty());
     assert!(!RecordingError::NotRecording.user_message().is_empty());
     assert!(!RecordingError::NoInputDevice.user_message().is_empty());
+    assert!(!RecordingError::InputDeviceNotFound("Cable".to_string())
+        .user_message()
+        .is_empty());
     assert!(!RecordingError::NoAudioCaptured.user_message().is_empty());
     assert!(!RecordingError::Device("test".to_string())
         .user_message()
@@ -70,6 +88,197 @@ fn recording_reservation_is_visible_and_exclusive() {
         Err(RecordingError::AlreadyRecording)
     ));
 
+    let first_session = reservation.session_id();
     drop(reservation);
     assert!(!recorder.is_recording());
+
+    let next = recorder.reserve().expect("reservation should succeed");
+    assert_ne!(next.session_id(), first_session);
+
+    let (queued_tx, queued_rx) = mpsc::channel();
+    assert!(set_queued_start_sink(move |reservation| {
+        let _ = queued_tx.send(reservation);
+    }));
+    assert!(matches!(recorder.reserve_or_queue(), Ok(None)));
+    assert!(recorder.has_queued_start());
+    assert!(recorder.cancel_queued_start());
+    assert!(!recorder.cancel_queued_start());
+    assert!(!recorder.has_queued_start());
+
+    assert!(matches!(recorder.reserve_or_queue(), Ok(None)));
+    let finished = next.session_id();
+    drop(next);
+    let queued = queued_rx
+        .try_recv()
+        .expect("the queued recording should take over the recorder");
+    assert!(!recorder.has_queued_start());
+    assert!(recorder.is_recording());
+    assert_ne!(queued.session_id(), finished);
+
+    drop(queued);
+    assert!(!recorder.is_recording());
+    assert!(queued_rx.try_recv().is_err());
+
+    let measurement = recorder
+        .reserve_measurement()
+        .expect("measurement should reserve");
+    assert!(matches!(
+        recorder.reserve_or_queue(),
+        Err(RecordingError::AlreadyRecording)
+    ));
+    let (audio_tx, audio_rx) = mpsc::channel();
+    let measuring = thread::spawn(move || {
+        Recorder::global().measure(
+            measurement.with_input(AudioInput::Network(audio_rx)),
+            Duration::from_millis(100),
+            ResamplerQuality::default(),
+        )
+    });
+    assert!(matches!(recorder.stop(), Err(RecordingError::NotRecording)));
+    audio_tx.send(vec![0.1; 1_600]).unwrap();
+    let audio = measuring
+        .join()
+        .unwrap()
+        .expect("the measurement should end once its length has arrived");
+    assert_eq!(audio.samples().len(), 1_600);
+    drop(audio);
+    assert!(!recorder.is_recording());
+    assert!(queued_rx.try_recv().is_err());
+}
+
+#[test]
+fn transcription_updates_carry_their_session_id() {
+    let update = SessionUpdate {
+        session_id: Some(SessionId::from_raw_for_tests(7)),
+        patch: TranscriptPatch::Commit {
+            append: "hello".to_string(),
+        },
+    };
+
+    assert_eq!(
+        serde_json::to_value(update).unwrap(),
+        serde_json::json!({ "session_id": 7, "kind": "commit", "append": "hello" })
+    );
+}
+
+#[test]
+fn audio_health_reports_degradation_with_a_stable_wire_shape() {
+    let healthy = AudioHealth {
+        device_sample_rate: 48_000,
+        resampling: true,
+        overrun_samples: 0,
+        dropped_streaming_samples: 0,
+        processing_chain: vec![ProcessingStage::Downmix, ProcessingStage::Resample],
+    };
+    assert!(!healthy.is_degraded());
+    assert!(AudioHealth {
+        overrun_samples: 12,
+        ..healthy.clone()
+    }
+    .is_degraded());
+
+    assert_eq!(
+        serde_json::to_value(&healthy).expect("audio health should serialize"),
+        serde_json::json!({
+            "device_sample_rate": 48_000,
+            "resampling": true,
+            "overrun_samples": 0,
+            "dropped_streaming_samples": 0,
+            "processing_chain": ["downmix", "resample"],
+        })
+    );
+}
+
+#[test]
+fn channel_mask_selects_channels_to_mix() {
+    let all = InputDeviceConfig::default();
+    assert_eq!(all.mixed_channels(2), vec![0, 1]);
+
+    let front_pair = InputDeviceConfig {
+        channel_mask: 0b11,
+        ..InputDeviceConfig::default()
+    };
+    assert_eq!(front_pair.mixed_channels(8), vec![0, 1]);
+
+    let missing = InputDeviceConfig {
+        channel_mask: 1 << 6,
+        ..InputDeviceConfig::default()
+    };
+    assert_eq!(missing.mixed_channels(2), vec![0, 1]);
+}
+
+#[test]
+fn downmix_averages_only_selected_channels() {
+    let frame = [0.5, 0.25, 0.0, 0.0, 0.0, 0.0, 0.0, 0.0];
+    assert!((downmix(&frame, &[0, 1]) - 0.375).abs() < f32::EPSILON);
+    assert!((downmix(&frame, &[0]) - 0.5).abs() < f32::EPSILON);
+    assert!((downmix(&frame[..2], &[0, 5]) - 0.5).abs() < f32::EPSILON);
+    assert_eq!(downmix(&frame, &[]), 0.0);
+}
+
+#[test]
+fn buffer_request_stays_within_the_driver_range() {
+    assert_eq!(fixed_buffer_frames(64, Some((128, 4096))), 128);
+    assert_eq!(fixed_buffer_frames(8192, Some((128, 4096))), 4096);
+    assert_eq!(fixed_buffer_frames(512, Some((128, 4096))), 512);
+    assert_eq!(fixed_buffer_frames(512, None), 512);
+}
+
+#[test]
+fn input_device_settings_drop_empty_overrides_and_clamp() {
+    let settings = InputDeviceSettings {
+        device: Some(" ".to_string()),
+        configs: [
+            ("Idle".to_string(), InputDeviceConfig::default()),
+            (
+                "Cable".to_string(),
+                InputDeviceConfig {
+                    sample_rate: Some(1),
+                    channel_mask: 0b11,
+                    buffer_frames: Some(u32::MAX),
+                },
+            ),
+        ]
+        .into_iter()
+        .collect(),
+    }
+    .normalized();
+
+    assert_eq!(settings.device, None);
+    assert_eq!(settings.configs.len(), 1);
+    let cable = settings.config_for("Cable");
+    assert_eq!(cable.sample_rate, Some(MIN_DEVICE_SAMPLE_RATE));
+    assert_eq!(cable.buffer_frames, Some(MAX_BUFFER_FRAMES));
+    assert_eq!(settings.config_for("Idle"), InputDeviceConfig::default());
+}
+
+#[test]
+fn waveform_frames_arrive_at_a_steady_rate_however_audio_is_chunked() {
+    let mut bucketer = WaveformBucketer::new(30, 4);
+    let samples = vec![0.25; 16_000];
+
+    let frames = samples
+        .chunks(700)
+        .flat_map(|chunk| bucketer.feed(chunk))
+        .collect::<Vec<_>>();
+
+    assert_eq!(frames.len(), 30);
+    assert_eq!(frames[0].at_ms, 33);
+    assert_eq!(frames[29].at_ms, 999);
+    assert!(frames.iter().all(|frame| frame.peaks == vec![0.25; 4]));
+}
+
+#[test]
+fn waveform_buckets_keep_the_peak_of_their_slice() {
+    let mut bucketer = WaveformBucketer::new(1_000, 4);
+    let mut samples = vec![0.0; 16];
+    samples[1] = -0.5;
+    samples[6] = 0.25;
+    samples[15] = 1.0;
+
+    let frames = bucketer.feed(&samples);
+
+    assert_eq!(frames.len(), 1);
+    assert_eq!(frames[0].peaks, vec![0.5, 0.25, 0.0, 1.0]);
+    assert!(bucketer.feed(&[0.5; 15]).is_empty());
 }
@@ -0,0 +1,60 @@
+use silent_keys_lib::retranscribe::{word_diff, DiffWord, RetranscribeConfig, Retranscription};
+
+fn same(text: &str) -> DiffWord {
+    DiffWord::Same(text.to_string())
+}
+
+fn removed(text: &str) -> DiffWord {
+    DiffWord::Removed(text.to_string())
+}
+
+fn added(text: &str) -> DiffWord {
+    DiffWord::Added(text.to_string())
+}
+
+#[test]
+fn changed_words_are_removed_then_added() {
+    assert_eq!(
+        word_diff("ship the  build today", "ship a build today please"),
+        vec![
+            same("ship"),
+            removed("the"),
+            added("a"),
+            same("build"),
+            same("today"),
+            added("please"),
+        ]
+    );
+    assert_eq!(word_diff("", ""), Vec::new());
+    assert_eq!(word_diff("gone", ""), vec![removed("gone")]);
+}
+
+#[test]
+fn retranscriptions_compare_utterance_by_utterance() {
+    let retranscription = Retranscription::new(
+        vec!["one two".to_string(), "three".to_string()],
+        vec!["one".to_string(), "two three".to_string()],
+    );
+
+    assert_eq!(
+        retranscription.diff,
+        vec![same("one"), removed("two"), added("two"), same("three")]
+    );
+}
+
+#[test]
+fn unset_options_keep_the_current_settings() {
+    let config: RetranscribeConfig = serde_json::from_str(r#"{"raw":true}"#).expect("config");
+
+    assert_eq!(
+        config,
+        RetranscribeConfig {
+            raw: true,
+            ..RetranscribeConfig::default()
+        }
+    );
+    assert_eq!(
+        serde_json::to_value(added("word")).unwrap(),
+        serde_json::json!({ "kind": "added", "text": "word" })
+    );
+}
@@ -0,0 +1,93 @@
+use std::time::Instant;
+
+use silent_keys_lib::settings_lock::{
+    SettingsLock, SettingsLockError, SettingsLockStatus, UnlockSession, MAX_FAILED_ATTEMPTS,
+    RETRY_DELAY, UNLOCK_DURATION,
+};
+
+fn lock() -> SettingsLock {
+    SettingsLock::with_salt("family pc", b"0011223344556677").unwrap()
+}
+
+#[test]
+fn only_a_salted_hash_of_the_passphrase_is_kept() {
+    let lock = lock();
+    let stored = serde_json::to_string(&lock).unwrap();
+
+    assert!(lock.is_enabled());
+    assert!(!stored.contains("family pc"));
+    assert!(stored.contains("$argon2id$"));
+    assert!(lock.verify("family pc"));
+    assert!(!lock.verify("family PC"));
+    assert_ne!(
+        SettingsLock::with_salt("family pc", b"8899aabbccddeeff").unwrap(),
+        lock
+    );
+    assert_eq!(
+        SettingsLock::with_salt("abc", b"0011223344556677"),
+        Err(SettingsLockError::PassphraseTooShort)
+    );
+    assert!(!SettingsLock::default().verify(""));
+}
+
+#[test]
+fn changes_need_the_lock_open_until_it_expires() {
+    let lock = lock();
+    let mut session = UnlockSession::new();
+    let now = Instant::now();
+
+    assert_eq!(session.check(&SettingsLock::default(), now), Ok(()));
+    assert_eq!(session.check(&lock, now), Err(SettingsLockError::Locked));
+
+    session.unlock(&lock, "family pc", now).unwrap();
+    assert_eq!(session.check(&lock, now), Ok(()));
+    assert_eq!(
+        session.status(&lock, now),
+        SettingsLockStatus {
+            enabled: true,
+            unlocked: true
+        }
+    );
+    assert_eq!(
+        session.check(&lock, now + UNLOCK_DURATION),
+        Err(SettingsLockError::Locked)
+    );
+
+    session.unlock(&lock, "family pc", now).unwrap();
+    session.relock();
+    assert_eq!(session.check(&lock, now), Err(SettingsLockError::Locked));
+}
+
+#[test]
+fn repeated_wrong_passphrases_pause_unlocking() {
+    let lock = lock();
+    let mut session = UnlockSession::new();
+    let now = Instant::now();
+
+    for _ in 0..MAX_FAILED_ATTEMPTS {
+        assert_eq!(
+            session.unlock(&lock, "guess", now),
+            Err(SettingsLockError::WrongPassphrase)
+        );
+    }
+    assert_eq!(
+        session.unlock(&lock, "family pc", now),
+        Err(SettingsLockError::TooManyAttempts(RETRY_DELAY))
+    );
+    assert!(!session.is_unlocked(now));
+
+    session
+        .unlock(&lock, "family pc", now + RETRY_DELAY)
+        .unwrap();
+    assert!(session.is_unlocked(now + RETRY_DELAY));
+}
+
+#[test]
+fn unlocking_without_a_lock_is_refused() {
+    let mut session = UnlockSession::new();
+
+    assert_eq!(
+        session.unlock(&SettingsLock::default(), "anything", Instant::now()),
+        Err(SettingsLockError::NotEnabled)
+    );
+}
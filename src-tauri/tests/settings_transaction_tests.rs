@@ -1,6 +1,9 @@
+#![cfg(feature = "desktop")]
+
 use silent_keys_lib::settings::{
-    reset_settings_transaction, set_asr_language_transaction, EngineReadiness, Settings,
-    SettingsAction, SettingsTransactionBackend, TransactionFailure,
+    import_settings_transaction, reset_settings_transaction, set_asr_language_transaction,
+    switch_profile_transaction, EngineReadiness, Settings, SettingsAction,
+    SettingsTransactionBackend, TransactionFailure,
 };
 
 struct FakeSettingsBackend {
@@ -18,6 +21,7 @@ impl FakeSettingsBackend {
                 model_path: Some("/models/custom".to_string()),
                 streaming_enabled: true,
                 asr_language: "en-US".to_string(),
+                ..Settings::default()
             },
             readiness: EngineReadiness::Ready,
             shortcut: Some("Alt+X".to_string()),
@@ -211,3 +215,67 @@ fn reset_waits_for_loading_model_before_mutating_settings() {
     assert_eq!(backend.settings, previous_settings);
     assert!(backend.calls.is_empty());
 }
+
+#[test]
+fn import_applies_settings_shortcut_and_language() {
+    let mut backend = FakeSettingsBackend::new();
+    let imported = Settings {
+        asr_language: "de-DE".to_string(),
+        announce_transcripts: true,
+        ..Settings::default()
+    };
+
+    import_settings_transaction(&mut backend, &imported, Some("Ctrl+Shift+D"))
+        .expect("import should succeed");
+
+    assert_eq!(backend.settings, imported);
+    assert_eq!(backend.shortcut.as_deref(), Some("Ctrl+Shift+D"));
+    assert_eq!(
+        backend.calls,
+        [
+            SettingsAction::ValidateImportedSpeechLanguage,
+            SettingsAction::ImportRecordShortcut,
+            SettingsAction::PersistImportedSettings,
+            SettingsAction::ApplyImportedSpeechLanguage,
+        ]
+    );
+}
+
+#[test]
+fn import_without_shortcut_keeps_it_and_rolls_back_on_apply_failure() {
+    let mut backend = FakeSettingsBackend::new();
+    let previous_settings = backend.settings.clone();
+    backend.fail(SettingsAction::ApplyImportedSpeechLanguage, "apply failed");
+
+    let error = import_settings_transaction(&mut backend, &Settings::default(), None)
+        .expect_err("language apply failure should be returned");
+
+    assert_eq!(error, TransactionFailure::Operation("apply failed"));
+    assert_eq!(backend.settings, previous_settings);
+    assert_eq!(backend.shortcut.as_deref(), Some("Alt+X"));
+    assert!(!backend
+        .calls
+        .contains(&SettingsAction::ImportRecordShortcut));
+}
+
+#[test]
+fn profile_switch_keeps_the_shortcut_and_applies_the_profile_language() {
+    let mut backend = FakeSettingsBackend::new();
+    let next = Settings {
+        asr_language: "de-DE".to_string(),
+        ..backend.settings.clone()
+    };
+
+    switch_profile_transaction(&mut backend, &next).expect("switch should succeed");
+
+    assert_eq!(backend.settings, next);
+    assert_eq!(backend.shortcut.as_deref(), Some("Alt+X"));
+    assert_eq!(
+        backend.calls,
+        [
+            SettingsAction::ValidateProfileSpeechLanguage,
+            SettingsAction::PersistProfileSettings,
+            SettingsAction::ApplyProfileSpeechLanguage,
+        ]
+    );
+}
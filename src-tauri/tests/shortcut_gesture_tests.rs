@@ -0,0 +1,218 @@
+use std::time::{Duration, Instant};
+
+use silent_keys_lib::shortcut_gesture::{
+    GestureAction, GestureInput, GestureMachine, GestureMode, ShortcutGestureSettings,
+    MAX_GESTURE_WINDOW_MS, MIN_GESTURE_WINDOW_MS,
+};
+
+const WINDOW: Duration = Duration::from_millis(400);
+
+fn ms(start: Instant, offset: u64) -> Instant {
+    start + Duration::from_millis(offset)
+}
+
+#[test]
+fn hold_mode_maps_presses_straight_through() {
+    let start = Instant::now();
+    let mut machine = GestureMachine::new(GestureMode::Hold, WINDOW);
+
+    assert_eq!(
+        machine.handle(GestureInput::Pressed, start),
+        GestureAction::Start
+    );
+    assert_eq!(
+        machine.handle(GestureInput::Released, ms(start, 50)),
+        GestureAction::Stop
+    );
+    assert_eq!(machine.deadline(), None);
+}
+
+#[test]
+fn double_tap_holding_is_push_to_talk() {
+    let start = Instant::now();
+    let mut machine = GestureMachine::new(GestureMode::DoubleTap, WINDOW);
+
+    assert_eq!(
+        machine.handle(GestureInput::Pressed, start),
+        GestureAction::Start
+    );
+    assert_eq!(
+        machine.handle(GestureInput::Released, ms(start, 2_000)),
+        GestureAction::Stop
+    );
+    assert_eq!(machine.deadline(), None);
+}
+
+#[test]
+fn double_tap_latches_until_the_next_tap() {
+    let start = Instant::now();
+    let mut machine = GestureMachine::new(GestureMode::DoubleTap, WINDOW);
+
+    assert_eq!(
+        machine.handle(GestureInput::Pressed, start),
+        GestureAction::Start
+    );
+    assert_eq!(
+        machine.handle(GestureInput::Released, ms(start, 100)),
+        GestureAction::None
+    );
+    assert_eq!(machine.deadline(), Some(ms(start, 500)));
+    assert_eq!(
+        machine.handle(GestureInput::Pressed, ms(start, 250)),
+        GestureAction::None
+    );
+    assert_eq!(
+        machine.handle(GestureInput::Released, ms(start, 300)),
+        GestureAction::None
+    );
+    // A stale deadline from the first tap changes nothing once latched.
+    assert_eq!(
+        machine.handle(GestureInput::Expired, ms(start, 500)),
+        GestureAction::None
+    );
+
+    assert_eq!(
+        machine.handle(GestureInput::Pressed, ms(start, 9_000)),
+        GestureAction::Stop
+    );
+    assert_eq!(
+        machine.handle(GestureInput::Released, ms(start, 9_100)),
+        GestureAction::None
+    );
+    assert_eq!(
+        machine.handle(GestureInput::Pressed, ms(start, 10_000)),
+        GestureAction::Start
+    );
+}
+
+#[test]
+fn a_single_tap_stops_when_no_second_tap_follows() {
+    let start = Instant::now();
+    let mut machine = GestureMachine::new(GestureMode::DoubleTap, WINDOW);
+    machine.handle(GestureInput::Pressed, start);
+    machine.handle(GestureInput::Released, ms(start, 100));
+
+    assert_eq!(
+        machine.handle(GestureInput::Expired, ms(start, 200)),
+        GestureAction::None
+    );
+    assert_eq!(
+        machine.handle(GestureInput::Expired, ms(start, 500)),
+        GestureAction::Stop
+    );
+    assert_eq!(machine.deadline(), None);
+}
+
+#[test]
+fn a_latch_stopped_elsewhere_is_forgotten() {
+    let start = Instant::now();
+    let mut machine = GestureMachine::new(GestureMode::DoubleTap, WINDOW);
+    machine.handle(GestureInput::Pressed, start);
+    machine.handle(GestureInput::Released, ms(start, 100));
+    machine.handle(GestureInput::Pressed, ms(start, 200));
+    machine.handle(GestureInput::Released, ms(start, 250));
+
+    machine.sync(false);
+
+    assert_eq!(
+        machine.handle(GestureInput::Pressed, ms(start, 5_000)),
+        GestureAction::Start
+    );
+}
+
+#[test]
+fn a_start_queued_behind_a_transcription_still_latches() {
+    let start = Instant::now();
+    let mut machine = GestureMachine::new(GestureMode::DoubleTap, WINDOW);
+
+    // The first tap queues its recording; a queued start syncs as recording.
+    assert_eq!(
+        machine.handle(GestureInput::Pressed, start),
+        GestureAction::Start
+    );
+    machine.handle(GestureInput::Released, ms(start, 100));
+    machine.sync(true);
+    assert_eq!(
+        machine.handle(GestureInput::Pressed, ms(start, 200)),
+        GestureAction::None
+    );
+    machine.handle(GestureInput::Released, ms(start, 250));
+    machine.sync(true);
+
+    assert_eq!(
+        machine.handle(GestureInput::Pressed, ms(start, 5_000)),
+        GestureAction::Stop
+    );
+}
+
+#[test]
+fn chord_toggles_only_when_the_follow_up_comes_in_time() {
+    let start = Instant::now();
+    let mut machine = GestureMachine::new(GestureMode::Chord, WINDOW);
+
+    assert_eq!(
+        machine.handle(GestureInput::Pressed, start),
+        GestureAction::ArmFollowUp
+    );
+    assert_eq!(
+        machine.handle(GestureInput::Released, ms(start, 50)),
+        GestureAction::None
+    );
+    assert_eq!(
+        machine.handle(GestureInput::FollowUp, ms(start, 200)),
+        GestureAction::Toggle
+    );
+    assert_eq!(
+        machine.handle(GestureInput::FollowUp, ms(start, 250)),
+        GestureAction::None
+    );
+
+    machine.handle(GestureInput::Pressed, ms(start, 1_000));
+    assert_eq!(
+        machine.handle(GestureInput::Expired, ms(start, 1_400)),
+        GestureAction::DisarmFollowUp
+    );
+    assert_eq!(
+        machine.handle(GestureInput::FollowUp, ms(start, 1_450)),
+        GestureAction::None
+    );
+}
+
+#[test]
+fn reconfiguring_drops_a_gesture_in_progress() {
+    let start = Instant::now();
+    let mut machine = GestureMachine::new(GestureMode::Chord, WINDOW);
+    machine.handle(GestureInput::Pressed, start);
+
+    machine.configure(GestureMode::Chord, WINDOW);
+    assert_eq!(machine.deadline(), Some(ms(start, 400)));
+
+    machine.configure(GestureMode::Chord, Duration::from_millis(800));
+    assert_eq!(machine.deadline(), None);
+}
+
+#[test]
+fn gesture_settings_clamp_the_window_and_trim_the_follow_up() {
+    let settings = ShortcutGestureSettings {
+        mode: GestureMode::Chord,
+        window_ms: 10,
+        follow_up: "  KeyD ".to_string(),
+    }
+    .clamped();
+
+    assert_eq!(settings.window_ms, MIN_GESTURE_WINDOW_MS);
+    assert_eq!(settings.follow_up, "KeyD");
+    assert_eq!(
+        ShortcutGestureSettings {
+            window_ms: u64::MAX,
+            ..ShortcutGestureSettings::default()
+        }
+        .clamped()
+        .window_ms,
+        MAX_GESTURE_WINDOW_MS
+    );
+    assert_eq!(
+        serde_json::to_value(GestureMode::DoubleTap).unwrap(),
+        "double_tap"
+    );
+}
@@ -0,0 +1,119 @@
+use std::f32::consts::PI;
+
+use silent_keys_lib::speaker_adaptation::{
+    measure_voice, Correction, SpeakerAdaptation, SpeakerNormalizer, VoiceProfile,
+    MAX_TILT_CORRECTION_DB, MIN_UTTERANCES, TARGET_LEVEL_DBFS, TARGET_TILT_DB,
+};
+
+const RATE: f32 = 16_000.0;
+
+/// One second of `(frequency, amplitude)` tones mixed together.
+fn voice(tones: &[(f32, f32)]) -> Vec<f32> {
+    (0..RATE as usize)
+        .map(|index| {
+            let t = index as f32 / RATE;
+            tones
+                .iter()
+                .map(|(frequency, amplitude)| amplitude * (2.0 * PI * frequency * t).sin())
+                .sum()
+        })
+        .collect()
+}
+
+fn learned(samples: &[f32]) -> VoiceProfile {
+    let stats = measure_voice(samples).unwrap();
+    let mut voice = VoiceProfile::default();
+    for _ in 0..MIN_UTTERANCES {
+        voice.observe(stats);
+    }
+    voice
+}
+
+#[test]
+fn measures_level_and_tilt_of_speech() {
+    let bass = measure_voice(&voice(&[(200.0, 0.1)])).unwrap();
+    let treble = measure_voice(&voice(&[(3_000.0, 0.1)])).unwrap();
+
+    // A sine's RMS is its amplitude over the square root of two.
+    assert!((bass.level_db - -23.0).abs() < 0.5, "{bass:?}");
+    assert!((treble.level_db - -23.0).abs() < 0.5, "{treble:?}");
+    assert!(bass.tilt_db < TARGET_TILT_DB && treble.tilt_db > TARGET_TILT_DB);
+}
+
+#[test]
+fn pauses_do_not_count_toward_the_level() {
+    let mut samples = vec![0.0; 16_000];
+    samples.extend(voice(&[(200.0, 0.1)]));
+    let with_pause = measure_voice(&samples).unwrap();
+    assert!((with_pause.level_db - -23.0).abs() < 0.5, "{with_pause:?}");
+
+    assert_eq!(measure_voice(&vec![0.0; 32_000]), None);
+    assert_eq!(measure_voice(&voice(&[(200.0, 0.1)])[..4_000]), None);
+}
+
+#[test]
+fn corrects_only_after_enough_utterances() {
+    let stats = measure_voice(&voice(&[(200.0, 0.01)])).unwrap();
+    let mut adaptation = SpeakerAdaptation {
+        enabled: true,
+        ..Default::default()
+    };
+    for _ in 1..MIN_UTTERANCES {
+        adaptation.voice.observe(stats);
+        assert_eq!(adaptation.correction(), None);
+    }
+    adaptation.voice.observe(stats);
+    let correction = adaptation.correction().unwrap();
+    let tilt_db = (TARGET_TILT_DB - stats.tilt_db).min(MAX_TILT_CORRECTION_DB);
+    assert!(tilt_db > 0.0 && (correction.tilt_db - tilt_db).abs() < 0.01);
+    // The bass cut is made up for on top of the level difference.
+    assert!(correction.gain_db > TARGET_LEVEL_DBFS - stats.level_db);
+
+    adaptation.enabled = false;
+    assert_eq!(adaptation.correction(), None);
+    assert_eq!(adaptation.info().correction, Some(correction));
+}
+
+#[test]
+fn brings_a_quiet_voice_to_the_target_level() {
+    let quiet = voice(&[(150.0, 0.01), (2_500.0, 0.001)]);
+    let correction = learned(&quiet).correction().unwrap();
+    let mut normalized = quiet.clone();
+    SpeakerNormalizer::new(correction).process(&mut normalized);
+
+    let before = measure_voice(&quiet).unwrap();
+    let after = measure_voice(&normalized).unwrap();
+    assert!(
+        (after.level_db - TARGET_LEVEL_DBFS).abs() < 2.0,
+        "{after:?}"
+    );
+    assert!(
+        (after.tilt_db - TARGET_TILT_DB).abs() < (before.tilt_db - TARGET_TILT_DB).abs(),
+        "{before:?} -> {after:?}"
+    );
+}
+
+#[test]
+fn undo_restores_the_audio_before_correction() {
+    let original = voice(&[(150.0, 0.02), (2_500.0, 0.004)]);
+    let correction = Correction {
+        gain_db: 8.0,
+        tilt_db: 5.0,
+    };
+    let mut normalized = original.clone();
+    SpeakerNormalizer::new(correction).process(&mut normalized);
+
+    let restored = correction.undo(&normalized);
+    for (restored, original) in restored.iter().zip(&original) {
+        assert!((restored - original).abs() < 1e-4);
+    }
+}
+
+#[test]
+fn normalizer_off_passes_audio_through() {
+    let original = voice(&[(200.0, 0.1)]);
+    let mut samples = original.clone();
+    SpeakerNormalizer::off().process(&mut samples);
+    assert_eq!(samples, original);
+    assert!(SpeakerAdaptation::default().correction().is_none());
+}
@@ -0,0 +1,79 @@
+mod common;
+
+use common::tone;
+use silent_keys_lib::streaming::{
+    SpeechActivity, SpeechActivityTracker, SPEECH_END_EVENT, SPEECH_START_EVENT,
+};
+use silent_keys_lib::vad::VadConfig;
+
+fn feed_all(tracker: &mut SpeechActivityTracker, samples: &[f32]) -> Vec<SpeechActivity> {
+    samples
+        .chunks(1_000)
+        .flat_map(|chunk| tracker.feed(chunk))
+        .collect()
+}
+
+#[test]
+fn reports_where_speech_starts_and_ends() {
+    let mut samples = vec![0.0; 14_400];
+    samples.extend(tone(220.0, 14_400, 0.5));
+    samples.extend(vec![0.0; 28_800]);
+    let mut tracker = SpeechActivityTracker::new(VadConfig::default());
+
+    let activity = feed_all(&mut tracker, &samples);
+
+    assert_eq!(
+        activity,
+        vec![
+            SpeechActivity::Start { at_ms: 900 },
+            SpeechActivity::End {
+                start_ms: 900,
+                end_ms: 1_800
+            },
+        ]
+    );
+    assert!(tracker.finish().is_empty());
+}
+
+#[test]
+fn speech_open_when_the_recording_stops_is_ended_by_finish() {
+    let mut samples = vec![0.0; 14_400];
+    samples.extend(tone(220.0, 9_600, 0.5));
+    let mut tracker = SpeechActivityTracker::new(VadConfig::default());
+
+    assert_eq!(
+        feed_all(&mut tracker, &samples),
+        vec![SpeechActivity::Start { at_ms: 900 }]
+    );
+    assert_eq!(
+        tracker.finish(),
+        vec![SpeechActivity::End {
+            start_ms: 900,
+            end_ms: 1_500
+        }]
+    );
+}
+
+#[test]
+fn silence_reports_nothing() {
+    let mut tracker = SpeechActivityTracker::new(VadConfig::default());
+
+    assert!(feed_all(&mut tracker, &vec![0.0; 32_000]).is_empty());
+    assert!(tracker.finish().is_empty());
+}
+
+#[test]
+fn activity_is_emitted_as_speech_start_and_speech_end() {
+    let start = SpeechActivity::Start { at_ms: 250 };
+    let end = SpeechActivity::End {
+        start_ms: 250,
+        end_ms: 900,
+    };
+
+    assert_eq!(start.event_name(), SPEECH_START_EVENT);
+    assert_eq!(end.event_name(), SPEECH_END_EVENT);
+    assert_eq!(
+        serde_json::to_value(end).unwrap(),
+        serde_json::json!({ "kind": "end", "start_ms": 250, "end_ms": 900 })
+    );
+}
@@ -0,0 +1,74 @@
+use silent_keys_lib::speech_stats::{push_session, SpeechStats, MAX_STORED_SESSIONS};
+use silent_keys_lib::transcript::{Transcript, TranscriptSegment};
+
+fn segment(start_ms: u64, end_ms: u64, text: &str) -> TranscriptSegment {
+    TranscriptSegment {
+        start_ms,
+        end_ms,
+        text: text.to_string(),
+        speaker: None,
+        confidence: None,
+    }
+}
+
+fn transcript(segments: Vec<TranscriptSegment>) -> Transcript {
+    Transcript {
+        language: None,
+        segments,
+    }
+}
+
+#[test]
+fn rate_and_pauses_come_from_segment_timing() {
+    let transcript = transcript(vec![
+        segment(
+            1_000,
+            7_000,
+            "one two three four five six seven eight nine ten",
+        ),
+        segment(8_000, 14_000, "eleven twelve thirteen fourteen fifteen"),
+        segment(14_500, 15_000, " "),
+        segment(20_000, 23_000, "sixteen seventeen eighteen nineteen twenty"),
+    ]);
+
+    let stats = SpeechStats::measure(&transcript, 30_000, "2026-03-04T08:00:00Z".to_string())
+        .expect("speech");
+
+    assert_eq!(stats.words, 20);
+    assert_eq!(stats.speaking_ms, 15_000);
+    assert_eq!(stats.words_per_minute, 80.0);
+    assert_eq!(stats.pause_ratio, 0.5);
+    assert_eq!(stats.longest_monologue_ms, 13_000);
+}
+
+#[test]
+fn silence_has_no_stats() {
+    assert_eq!(
+        SpeechStats::measure(&transcript(vec![segment(0, 500, "")]), 1_000, String::new()),
+        None
+    );
+}
+
+#[test]
+fn stored_sessions_keep_the_newest() {
+    let stats = SpeechStats::measure(
+        &transcript(vec![segment(0, 1_000, "hello")]),
+        1_000,
+        String::new(),
+    )
+    .expect("speech");
+    let mut sessions = Vec::new();
+    for index in 0..=MAX_STORED_SESSIONS {
+        push_session(
+            &mut sessions,
+            SpeechStats {
+                words: index,
+                ..stats.clone()
+            },
+        );
+    }
+
+    assert_eq!(sessions.len(), MAX_STORED_SESSIONS);
+    assert_eq!(sessions[0].words, 1);
+    assert_eq!(sessions[MAX_STORED_SESSIONS - 1].words, MAX_STORED_SESSIONS);
+}
@@ -0,0 +1,42 @@
+use std::sync::{Mutex, RwLock};
+use std::thread;
+
+use silent_keys_lib::sync::{MutexRecover, RwLockRecover};
+
+/// Runs `hold` on another thread, which panics while `hold`'s guard is
+/// still alive.
+fn panic_while<T: Sync>(lock: &T, hold: impl Fn(&T) + Send + Sync) {
+    thread::scope(|scope| {
+        let result = scope.spawn(|| hold(lock)).join();
+        assert!(result.is_err());
+    });
+}
+
+#[test]
+fn a_poisoned_mutex_keeps_its_data_and_is_cleared() {
+    let buffer = Mutex::new(vec![1.0_f32]);
+    panic_while(&buffer, |buffer| {
+        let mut guard = buffer.lock().unwrap();
+        guard.push(2.0);
+        panic!("capture thread failed");
+    });
+    assert!(buffer.is_poisoned());
+
+    assert_eq!(*buffer.lock_recover(), vec![1.0, 2.0]);
+    assert!(!buffer.is_poisoned());
+}
+
+#[test]
+fn write_repair_runs_only_after_a_panic() {
+    let model = RwLock::new(3);
+    *model.write_repair(|value| *value = 0) += 1;
+    assert_eq!(*model.read_recover(), 4);
+
+    panic_while(&model, |model| {
+        let _guard = model.write().unwrap();
+        panic!("decode failed");
+    });
+
+    assert_eq!(*model.write_repair(|value| *value = 0), 0);
+    assert!(!model.is_poisoned());
+}
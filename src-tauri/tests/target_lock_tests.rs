@@ -0,0 +1,51 @@
+use silent_keys_lib::target_lock::{check_target, TargetCheck, TargetLock};
+
+#[test]
+fn the_locked_window_keeps_typing() {
+    for lock in [TargetLock::Off, TargetLock::Refocus, TargetLock::Abort] {
+        assert_eq!(
+            check_target(lock, Some("4194307"), Some("4194307")),
+            TargetCheck::Type
+        );
+    }
+}
+
+#[test]
+fn a_focus_change_refocuses_or_aborts() {
+    assert_eq!(
+        check_target(TargetLock::Refocus, Some("Mail"), Some("Slack")),
+        TargetCheck::Refocus
+    );
+    assert_eq!(
+        check_target(TargetLock::Abort, Some("Mail"), Some("Slack")),
+        TargetCheck::Abort
+    );
+    assert_eq!(
+        check_target(TargetLock::Off, Some("Mail"), Some("Slack")),
+        TargetCheck::Type
+    );
+}
+
+#[test]
+fn unknown_windows_type_anyway() {
+    assert_eq!(
+        check_target(TargetLock::Abort, None, Some("Slack")),
+        TargetCheck::Type
+    );
+    assert_eq!(
+        check_target(TargetLock::Abort, Some("Mail"), None),
+        TargetCheck::Type
+    );
+}
+
+#[test]
+fn the_lock_is_saved_by_name() {
+    assert_eq!(
+        serde_json::to_value(TargetLock::Refocus).unwrap(),
+        serde_json::json!("refocus")
+    );
+    assert_eq!(
+        serde_json::from_value::<TargetLock>(serde_json::json!("abort")).unwrap(),
+        TargetLock::Abort
+    );
+}
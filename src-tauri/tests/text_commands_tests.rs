@@ -0,0 +1,98 @@
+use silent_keys_lib::text_commands::{apply_code_mode, apply_spelling, interpret, TextCommands};
+
+#[test]
+fn spelled_letters_join_into_one_word() {
+    assert_eq!(
+        apply_spelling("My name is spell J O H A N N."),
+        "My name is JOHANN."
+    );
+    assert_eq!(apply_spelling("Spell: J-O-H-N, please"), "JOHN, please");
+}
+
+#[test]
+fn nato_words_digits_and_capitals_are_spelled() {
+    assert_eq!(
+        apply_spelling("the code is spell capital kilo alpha nine two ok"),
+        "the code is Ka92 ok"
+    );
+    assert_eq!(apply_spelling("spell x-ray Yankee zulu"), "xyz");
+}
+
+#[test]
+fn symbols_join_letters_but_never_end_a_word() {
+    assert_eq!(
+        apply_spelling("mail spell j d at x dot y today"),
+        "mail jd@x.y today"
+    );
+    assert_eq!(apply_spelling("spell A B at noon"), "AB at noon");
+}
+
+#[test]
+fn spell_without_letters_is_left_as_spoken() {
+    assert_eq!(
+        apply_spelling("how do you spell that word"),
+        "how do you spell that word"
+    );
+    assert_eq!(apply_spelling("spell"), "spell");
+}
+
+#[test]
+fn disabled_commands_leave_text_unchanged() {
+    let text = "spell J O H N";
+
+    assert_eq!(interpret(text, &TextCommands::default()), text);
+    assert_eq!(
+        interpret(
+            text,
+            &TextCommands {
+                spelling: true,
+                ..TextCommands::default()
+            }
+        ),
+        "JOHN"
+    );
+}
+
+#[test]
+fn code_mode_types_symbols_without_spaces() {
+    assert_eq!(
+        apply_code_mode("Print open paren x comma y close paren semicolon."),
+        "Print(x,y);"
+    );
+    assert_eq!(
+        apply_code_mode("self arrow value double equals none"),
+        "self->value==none"
+    );
+    assert_eq!(apply_code_mode("std double colon io"), "std::io");
+}
+
+#[test]
+fn case_commands_join_words_up_to_the_next_symbol() {
+    assert_eq!(
+        apply_code_mode("Camel case get user name, open paren close paren."),
+        "getUserName()"
+    );
+    assert_eq!(
+        apply_code_mode("snake case Max Retry Count"),
+        "max_retry_count"
+    );
+    assert_eq!(
+        apply_code_mode("class pascal case http client open brace"),
+        "class HttpClient{"
+    );
+    assert_eq!(apply_code_mode("kebab case main menu"), "main-menu");
+    assert_eq!(apply_code_mode("camel case"), "");
+}
+
+#[test]
+fn spelling_runs_before_code_mode() {
+    let commands = TextCommands {
+        spelling: true,
+        code_mode: true,
+    };
+
+    assert_eq!(
+        interpret("let spell x y equals five.", &commands),
+        "let xy=five"
+    );
+}
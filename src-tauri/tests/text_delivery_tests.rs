@@ -1,7 +1,12 @@
+#![cfg(feature = "desktop")]
+
 use silent_keys_lib::desktop::{
-    append_for_tests, deliver_for_tests, plan_final_delivery, FinalDelivery,
+    append_for_tests, deliver_for_tests, needs_leading_space, plan_final_delivery,
+    rate_limit_delay, reached_window, typing_chunks, untyped_remainder, DeliveryProgress,
+    FinalDelivery,
 };
-use silent_keys_lib::streaming::TranscriptionUpdate;
+use silent_keys_lib::streaming::TranscriptPatch;
+use std::time::Duration;
 
 #[test]
 fn final_delivery_appends_only_a_true_suffix() {
@@ -88,17 +93,130 @@ fn empty_final_transcript_clears_divergent_streamed_text() {
 
 #[test]
 fn transcription_updates_have_stable_wire_shapes() {
-    let append = serde_json::to_value(TranscriptionUpdate::Append("hello".to_string()))
-        .expect("append update should serialize");
-    let replace = serde_json::to_value(TranscriptionUpdate::Replace("world".to_string()))
-        .expect("replace update should serialize");
+    let draft = serde_json::to_value(TranscriptPatch::Draft {
+        full_text: "hello wor".to_string(),
+    })
+    .expect("draft patch should serialize");
+    let commit = serde_json::to_value(TranscriptPatch::Commit {
+        append: "hello".to_string(),
+    })
+    .expect("commit patch should serialize");
+    let replace = serde_json::to_value(TranscriptPatch::Replace {
+        range: 0..5,
+        text: "world".to_string(),
+    })
+    .expect("replace patch should serialize");
 
     assert_eq!(
-        append,
-        serde_json::json!({ "kind": "append", "text": "hello" })
+        draft,
+        serde_json::json!({ "kind": "draft", "full_text": "hello wor" })
+    );
+    assert_eq!(
+        commit,
+        serde_json::json!({ "kind": "commit", "append": "hello" })
     );
     assert_eq!(
         replace,
-        serde_json::json!({ "kind": "replace", "text": "world" })
+        serde_json::json!({ "kind": "replace", "range": { "start": 0, "end": 5 }, "text": "world" })
+    );
+}
+
+#[test]
+fn typing_chunks_split_on_character_boundaries() {
+    assert_eq!(typing_chunks("héllo wörld", 4), vec!["héll", "o wö", "rld"]);
+    assert_eq!(typing_chunks("hello", 0), vec!["hello"]);
+    assert_eq!(typing_chunks("hi", 8), vec!["hi"]);
+    assert!(typing_chunks("", 4).is_empty());
+}
+
+#[test]
+fn rate_limit_waits_until_typed_characters_fit_the_rate() {
+    assert_eq!(
+        rate_limit_delay(50, 100, Duration::from_millis(200)),
+        Duration::from_millis(300)
+    );
+    assert_eq!(
+        rate_limit_delay(50, 100, Duration::from_secs(1)),
+        Duration::ZERO
+    );
+    assert_eq!(rate_limit_delay(0, 100, Duration::ZERO), Duration::ZERO);
+}
+
+#[test]
+fn zero_rate_is_unlimited() {
+    assert_eq!(rate_limit_delay(10_000, 0, Duration::ZERO), Duration::ZERO);
+}
+
+#[test]
+fn leading_space_follows_a_word_or_punctuation() {
+    assert!(needs_leading_space(Some('d'), "next sentence"));
+    assert!(needs_leading_space(Some('.'), "Next sentence"));
+    assert!(needs_leading_space(Some(','), " and then"));
+}
+
+#[test]
+fn no_leading_space_at_field_start_after_whitespace_or_openers() {
+    assert!(!needs_leading_space(None, "hello"));
+    assert!(!needs_leading_space(Some(' '), "hello"));
+    assert!(!needs_leading_space(Some('\n'), "hello"));
+    assert!(!needs_leading_space(Some('('), "hello"));
+    assert!(!needs_leading_space(Some('"'), "hello"));
+}
+
+#[test]
+fn no_leading_space_before_punctuation_or_empty_text() {
+    assert!(!needs_leading_space(Some('d'), ", and then"));
+    assert!(!needs_leading_space(Some('d'), "."));
+    assert!(!needs_leading_space(Some('d'), "   "));
+}
+
+#[test]
+fn a_stopped_delivery_leaves_only_its_untyped_end_to_paste() {
+    let appended = reached_window(
+        "hello",
+        &FinalDelivery::Append(" brave new world".to_string()),
+        DeliveryProgress {
+            deleted: 0,
+            typed: 6,
+        },
+    );
+    assert_eq!(appended, "hello brave");
+    assert_eq!(
+        untyped_remainder(&appended, "hello brave new world"),
+        " new world"
+    );
+
+    let replaced = reached_window(
+        "hello word",
+        &FinalDelivery::Replace {
+            previous_chars: 4,
+            text: "world".to_string(),
+        },
+        DeliveryProgress {
+            deleted: 4,
+            typed: 2,
+        },
+    );
+    assert_eq!(replaced, "hello wo");
+    assert_eq!(untyped_remainder(&replaced, "hello world"), "rld");
+}
+
+#[test]
+fn a_correction_stopped_while_deleting_leaves_all_of_the_text_to_paste() {
+    let partly_deleted = reached_window(
+        "hello word",
+        &FinalDelivery::Replace {
+            previous_chars: 4,
+            text: "planet".to_string(),
+        },
+        DeliveryProgress {
+            deleted: 2,
+            typed: 0,
+        },
+    );
+    assert_eq!(partly_deleted, "hello wo");
+    assert_eq!(
+        untyped_remainder(&partly_deleted, "hello planet"),
+        "hello planet"
     );
 }
@@ -0,0 +1,67 @@
+mod common;
+
+use common::tone;
+use silent_keys_lib::time_stretch::{stretch, TimeStretch};
+
+const SAMPLE_RATE: f32 = 16_000.0;
+
+/// Estimates the dominant frequency from zero crossings.
+fn frequency(samples: &[f32]) -> f32 {
+    let crossings = samples
+        .windows(2)
+        .filter(|pair| (pair[0] < 0.0) != (pair[1] < 0.0))
+        .count();
+    crossings as f32 / 2.0 / (samples.len() as f32 / SAMPLE_RATE)
+}
+
+#[test]
+fn stretching_is_off_unless_enabled_below_full_speed() {
+    assert_eq!(TimeStretch::default().speed(), None);
+    let enabled = TimeStretch {
+        enabled: true,
+        speed_percent: 90,
+    };
+    assert_eq!(enabled.speed(), Some(0.9));
+    let full_speed = TimeStretch {
+        speed_percent: 100,
+        ..enabled
+    };
+    assert_eq!(full_speed.speed(), None);
+}
+
+#[test]
+fn speed_is_clamped_to_the_supported_range() {
+    let slow = TimeStretch {
+        enabled: true,
+        speed_percent: 10,
+    };
+    assert_eq!(slow.clamped().speed_percent, 50);
+    assert_eq!(slow.speed(), Some(0.5));
+    let fast = TimeStretch {
+        enabled: true,
+        speed_percent: 150,
+    };
+    assert_eq!(fast.clamped().speed_percent, 100);
+}
+
+#[test]
+fn slowing_down_lengthens_audio_without_lowering_pitch() {
+    let samples = tone(220.0, 32_000, 0.5);
+    let stretched = stretch(&samples, 0.9);
+
+    let expected = (samples.len() as f32 / 0.9).round() as usize;
+    assert_eq!(stretched.len(), expected);
+    let pitch = frequency(&stretched[800..stretched.len() - 800]);
+    assert!((pitch - 220.0).abs() < 5.0, "pitch moved to {pitch} Hz");
+    let peak = stretched
+        .iter()
+        .fold(0.0f32, |peak, sample| peak.max(sample.abs()));
+    assert!((0.4..=0.55).contains(&peak), "level changed to {peak}");
+}
+
+#[test]
+fn full_speed_and_short_audio_pass_through() {
+    let samples = tone(440.0, 8_000, 0.5);
+    assert_eq!(stretch(&samples, 1.0), samples);
+    assert_eq!(stretch(&samples[..100], 0.8), &samples[..100]);
+}
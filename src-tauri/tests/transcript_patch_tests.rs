@@ -0,0 +1,150 @@
+use silent_keys_lib::streaming::{PatchError, Transcript, TranscriptPatch};
+
+fn commit(text: &str) -> TranscriptPatch {
+    TranscriptPatch::Commit {
+        append: text.to_string(),
+    }
+}
+
+fn replace(range: std::ops::Range<u32>, text: &str) -> TranscriptPatch {
+    TranscriptPatch::Replace {
+        range,
+        text: text.to_string(),
+    }
+}
+
+#[test]
+fn commits_append_and_clear_the_draft() {
+    let mut transcript = Transcript::default();
+
+    transcript.apply(&commit("hello")).unwrap();
+    transcript
+        .apply(&TranscriptPatch::Draft {
+            full_text: "hello wor".to_string(),
+        })
+        .unwrap();
+    assert_eq!(transcript.text(), "hello wor");
+    assert_eq!(transcript.committed(), "hello");
+
+    transcript.apply(&commit(" world")).unwrap();
+    assert_eq!(transcript.text(), "hello world");
+}
+
+#[test]
+fn replacements_count_characters_not_bytes() {
+    let mut transcript = Transcript::default();
+    transcript.apply(&commit("café crème")).unwrap();
+
+    transcript.apply(&replace(5..10, "au lait")).unwrap();
+    assert_eq!(transcript.text(), "café au lait");
+
+    transcript.apply(&replace(3..4, "e")).unwrap();
+    assert_eq!(transcript.text(), "cafe au lait");
+}
+
+#[test]
+fn replace_all_covers_the_committed_text_only() {
+    let mut transcript = Transcript::default();
+    transcript.apply(&commit("héllo")).unwrap();
+    transcript
+        .apply(&TranscriptPatch::Draft {
+            full_text: "héllo there".to_string(),
+        })
+        .unwrap();
+
+    let patch = transcript.replace_all("Hello there.".to_string());
+    assert_eq!(patch, replace(0..5, "Hello there."));
+
+    transcript.apply(&patch).unwrap();
+    assert_eq!(transcript.text(), "Hello there.");
+}
+
+#[test]
+fn out_of_range_replacements_leave_the_transcript_alone() {
+    let mut transcript = Transcript::default();
+    transcript.apply(&commit("hello")).unwrap();
+
+    assert_eq!(
+        transcript.apply(&replace(2..9, "")),
+        Err(PatchError::OutOfRange {
+            start: 2,
+            end: 9,
+            len: 5
+        })
+    );
+    #[allow(clippy::reversed_empty_ranges)]
+    let backwards = replace(4..1, "");
+    assert!(transcript.apply(&backwards).is_err());
+    assert_eq!(transcript.text(), "hello");
+}
+
+#[test]
+fn patches_round_trip_through_json() {
+    let patches = [
+        TranscriptPatch::Draft {
+            full_text: "hi".to_string(),
+        },
+        TranscriptPatch::DraftTail {
+            keep: 2,
+            tail: " there".to_string(),
+        },
+        commit("hi"),
+        replace(0..2, "Hi."),
+    ];
+
+    for patch in patches {
+        let json = serde_json::to_string(&patch).unwrap();
+        assert_eq!(
+            serde_json::from_str::<TranscriptPatch>(&json).unwrap(),
+            patch
+        );
+    }
+}
+
+#[test]
+fn long_drafts_window_to_the_words_that_changed() {
+    let mut sender = Transcript::default();
+    let mut receiver = Transcript::default();
+    for patch in [
+        commit("one two "),
+        TranscriptPatch::Draft {
+            full_text: "one two three fo".to_string(),
+        },
+    ] {
+        receiver.apply(&sender.windowed(patch.clone(), 0)).unwrap();
+        sender.apply(&patch).unwrap();
+    }
+
+    let draft = TranscriptPatch::Draft {
+        full_text: "one two three four five".to_string(),
+    };
+    let windowed = sender.windowed(draft.clone(), 10);
+    assert_eq!(
+        windowed,
+        TranscriptPatch::DraftTail {
+            keep: 14,
+            tail: "four five".to_string(),
+        }
+    );
+    assert_eq!(sender.windowed(draft.clone(), 100), draft);
+
+    receiver.apply(&windowed).unwrap();
+    sender.apply(&draft).unwrap();
+    assert_eq!(receiver.text(), sender.text());
+}
+
+#[test]
+fn windowed_drafts_cannot_keep_more_than_is_shown() {
+    let mut transcript = Transcript::default();
+    transcript.apply(&commit("héllo")).unwrap();
+    let patch = TranscriptPatch::DraftTail {
+        keep: 9,
+        tail: "x".to_string(),
+    };
+
+    assert_eq!(
+        transcript.apply(&patch),
+        Err(PatchError::KeepOutOfRange { keep: 9, len: 5 })
+    );
+    assert_eq!(transcript.text(), "héllo");
+}
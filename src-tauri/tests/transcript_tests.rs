@@ -0,0 +1,141 @@
+use silent_keys_lib::transcript::{PauseMarkers, Transcript, TranscriptSegment, MIN_PAUSE_MS};
+
+#[test]
+fn segments_carry_millisecond_offsets_from_sample_ranges() {
+    let transcript = Transcript::from_segments(
+        Some("en-US".to_string()),
+        &[0..8_000, 16_000..40_000],
+        vec!["hello".to_string(), "world".to_string()],
+    );
+
+    assert_eq!(transcript.language.as_deref(), Some("en-US"));
+    assert_eq!(
+        transcript.segments,
+        vec![
+            TranscriptSegment {
+                start_ms: 0,
+                end_ms: 500,
+                text: "hello".to_string(),
+                speaker: None,
+                confidence: None,
+            },
+            TranscriptSegment {
+                start_ms: 1_000,
+                end_ms: 2_500,
+                text: "world".to_string(),
+                speaker: None,
+                confidence: None,
+            },
+        ]
+    );
+}
+
+#[test]
+fn blank_segments_are_dropped_and_text_is_trimmed() {
+    let transcript = Transcript::from_segments(
+        None,
+        &[0..1_600, 1_600..3_200, 3_200..4_800],
+        vec![" one ".to_string(), "  ".to_string(), "two".to_string()],
+    );
+
+    assert_eq!(transcript.segments.len(), 2);
+    assert_eq!(transcript.segments[0].text, "one");
+    assert_eq!(transcript.segments[1].start_ms, 200);
+    assert_eq!(transcript.text(), "one two");
+}
+
+#[test]
+fn a_decoded_segment_matches_its_place_in_the_transcript() {
+    let ranges = [0..1_600, 1_600..3_200, 3_200..4_800];
+    let texts = [" one ", "  ", "two"];
+    let transcript = Transcript::from_segments(
+        None,
+        &ranges,
+        texts.iter().map(|text| text.to_string()).collect(),
+    );
+
+    let decoded: Vec<_> = ranges
+        .iter()
+        .zip(texts)
+        .filter_map(|(range, text)| TranscriptSegment::decoded(range, text))
+        .collect();
+
+    assert_eq!(decoded, transcript.segments);
+    assert_eq!(TranscriptSegment::decoded(&(0..1_600), " \n"), None);
+}
+
+#[test]
+fn transcript_without_speech_is_empty() {
+    let transcript =
+        Transcript::from_segments(None, std::slice::from_ref(&(0..1_600)), vec![String::new()]);
+
+    assert!(transcript.is_empty());
+    assert_eq!(transcript.text(), "");
+}
+
+#[test]
+fn transcript_serializes_segments_for_the_frontend() {
+    let transcript = Transcript::from_segments(
+        None,
+        std::slice::from_ref(&(0..16_000)),
+        vec!["hi".to_string()],
+    );
+    let json = serde_json::to_value(&transcript).expect("serialize transcript");
+
+    assert_eq!(json["language"], serde_json::Value::Null);
+    assert_eq!(json["segments"][0]["end_ms"], 1_000);
+    assert_eq!(json["segments"][0]["text"], "hi");
+}
+
+fn paused_transcript() -> Transcript {
+    Transcript::from_segments(
+        None,
+        &[0..16_000, 20_800..32_000, 72_000..80_000],
+        vec!["one".to_string(), "two".to_string(), "three".to_string()],
+    )
+}
+
+#[test]
+fn pause_markers_break_text_at_long_gaps_only() {
+    let pauses = PauseMarkers {
+        enabled: true,
+        min_pause_ms: 2_000,
+        ..PauseMarkers::default()
+    };
+
+    assert_eq!(
+        paused_transcript().text_with_pauses(&pauses),
+        "one two\n\nthree"
+    );
+}
+
+#[test]
+fn visible_pause_markers_are_spaced_and_disabled_markers_are_ignored() {
+    let pauses = PauseMarkers {
+        enabled: true,
+        min_pause_ms: 2_000,
+        marker: "[pause]".to_string(),
+    };
+
+    assert_eq!(
+        paused_transcript().text_with_pauses(&pauses),
+        "one two [pause] three"
+    );
+    assert_eq!(
+        paused_transcript().text_with_pauses(&PauseMarkers::default()),
+        "one two three"
+    );
+}
+
+#[test]
+fn pause_markers_are_clamped_and_default_to_a_paragraph_break() {
+    let pauses = PauseMarkers {
+        enabled: true,
+        min_pause_ms: 0,
+        marker: String::new(),
+    }
+    .clamped();
+
+    assert_eq!(pauses.min_pause_ms, MIN_PAUSE_MS);
+    assert_eq!(pauses.separator(), "\n\n");
+}
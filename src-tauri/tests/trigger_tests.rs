@@ -0,0 +1,97 @@
+use silent_keys_lib::errors::UserFacing;
+use silent_keys_lib::trigger::{
+    PedalBinding, PedalState, TriggerError, TriggerSettings, PEDAL_LEFT, PEDAL_MIDDLE,
+};
+
+fn binding(report_byte: usize, mask: u8) -> PedalBinding {
+    PedalBinding {
+        vendor_id: 0x05f3,
+        product_id: 0x00ff,
+        report_byte,
+        mask,
+    }
+}
+
+#[test]
+fn pedal_reports_become_press_and_release_edges() {
+    let binding = binding(0, PEDAL_MIDDLE);
+    let mut state = PedalState::default();
+
+    assert_eq!(state.update(&binding, &[0x00]), None);
+    assert_eq!(state.update(&binding, &[PEDAL_MIDDLE]), Some(true));
+    assert_eq!(state.update(&binding, &[PEDAL_MIDDLE]), None);
+    assert!(state.is_down());
+    assert_eq!(state.update(&binding, &[0x00]), Some(false));
+    assert!(!state.is_down());
+}
+
+#[test]
+fn other_pedals_do_not_trigger_the_bound_one() {
+    let binding = binding(0, PEDAL_MIDDLE);
+    let mut state = PedalState::default();
+
+    assert_eq!(state.update(&binding, &[PEDAL_LEFT]), None);
+    assert_eq!(
+        state.update(&binding, &[PEDAL_LEFT | PEDAL_MIDDLE]),
+        Some(true)
+    );
+    // Releasing another pedal while the bound one stays down is no edge.
+    assert_eq!(state.update(&binding, &[PEDAL_MIDDLE]), None);
+}
+
+#[test]
+fn short_reports_leave_the_pedal_state_alone() {
+    let binding = binding(2, PEDAL_LEFT);
+    let mut state = PedalState::default();
+
+    assert_eq!(
+        state.update(&binding, &[0x00, PEDAL_LEFT, PEDAL_LEFT]),
+        Some(true)
+    );
+    assert_eq!(state.update(&binding, &[]), None);
+    assert_eq!(state.update(&binding, &[0x00]), None);
+    assert!(state.is_down());
+}
+
+#[test]
+fn normalized_drops_pedals_without_a_device_or_mask() {
+    let unnamed = TriggerSettings {
+        mouse_button: Some(4),
+        pedal: Some(PedalBinding::default()),
+    }
+    .normalized();
+    assert_eq!(unnamed.mouse_button, Some(4));
+    assert_eq!(unnamed.pedal, None);
+
+    let unmasked = TriggerSettings {
+        mouse_button: None,
+        pedal: Some(binding(0, 0)),
+    }
+    .normalized();
+    assert!(unmasked.is_empty());
+
+    let kept = TriggerSettings {
+        mouse_button: None,
+        pedal: Some(binding(0, PEDAL_MIDDLE)),
+    };
+    assert_eq!(kept.normalized(), kept);
+}
+
+#[test]
+fn missing_trigger_fields_deserialize_to_defaults() {
+    let settings: TriggerSettings =
+        serde_json::from_value(serde_json::json!({ "pedal": { "vendor_id": 1 } })).unwrap();
+
+    assert_eq!(settings.mouse_button, None);
+    assert_eq!(settings.pedal.map(|pedal| pedal.mask), Some(PEDAL_MIDDLE));
+}
+
+#[test]
+fn trigger_errors_explain_what_to_do() {
+    assert!(TriggerError::MouseListener("denied".to_string())
+        .user_message()
+        .contains("Accessibility"));
+    assert!(TriggerError::NoButtonPressed
+        .user_message()
+        .contains("side button"));
+}
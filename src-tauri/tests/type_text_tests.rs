@@ -0,0 +1,103 @@
+#![cfg(feature = "desktop")]
+
+use std::time::Duration;
+
+use silent_keys_lib::continuation::ContinuationSettings;
+use silent_keys_lib::desktop::{TypeTextError, TypeTextOptions};
+use silent_keys_lib::profiles::ProfileError;
+use silent_keys_lib::settings::{Settings, TypingPace};
+
+fn pace(chunk_chars: usize) -> TypingPace {
+    TypingPace {
+        chunk_chars,
+        chunk_delay_ms: 20,
+        ..TypingPace::default()
+    }
+}
+
+#[test]
+fn unset_options_follow_the_dictation_settings() {
+    let settings = Settings {
+        typing_pace: pace(8),
+        caret_spacing: true,
+        continuation: ContinuationSettings {
+            enabled: true,
+            window_secs: 45,
+        },
+        ..Settings::default()
+    };
+
+    let plan = TypeTextOptions::default().plan(&settings).unwrap();
+
+    assert_eq!(plan.pace, pace(8));
+    assert!(plan.caret_spacing);
+    assert_eq!(plan.continuation_window, Some(Duration::from_secs(45)));
+}
+
+#[test]
+fn options_override_spacing_and_continuation() {
+    let settings = Settings {
+        continuation: ContinuationSettings {
+            enabled: false,
+            window_secs: 45,
+        },
+        ..Settings::default()
+    };
+    let options = TypeTextOptions {
+        spacing: Some(true),
+        continuation: Some(true),
+        profile: None,
+    };
+
+    let plan = options.plan(&settings).unwrap();
+
+    assert!(plan.caret_spacing);
+    assert_eq!(plan.continuation_window, Some(Duration::from_secs(45)));
+    let off = TypeTextOptions {
+        continuation: Some(false),
+        ..options
+    };
+    assert_eq!(off.plan(&settings).unwrap().continuation_window, None);
+}
+
+#[test]
+fn a_named_profile_supplies_its_saved_pace() {
+    let mut settings = Settings {
+        typing_pace: pace(8),
+        ..Settings::default()
+    };
+    let work = settings.clone();
+    settings.profiles.create("Work", &work).unwrap();
+    settings.typing_pace = pace(2);
+    let remote = settings.clone();
+    settings.profiles.create("Remote", &remote).unwrap();
+    settings.typing_pace = pace(16);
+    let with_profile = |name: &str| TypeTextOptions {
+        profile: Some(name.to_string()),
+        ..TypeTextOptions::default()
+    };
+
+    assert_eq!(
+        with_profile("Remote").plan(&settings).unwrap().pace,
+        pace(2)
+    );
+    assert_eq!(with_profile("work").plan(&settings).unwrap().pace, pace(16));
+    assert!(matches!(
+        with_profile("Gaming").plan(&settings),
+        Err(TypeTextError::Profile(ProfileError::NotFound(name))) if name == "Gaming"
+    ));
+}
+
+#[test]
+fn options_read_partial_json() {
+    let options: TypeTextOptions =
+        serde_json::from_value(serde_json::json!({ "spacing": false })).unwrap();
+
+    assert_eq!(
+        options,
+        TypeTextOptions {
+            spacing: Some(false),
+            ..TypeTextOptions::default()
+        }
+    );
+}
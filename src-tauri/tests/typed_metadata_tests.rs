@@ -0,0 +1,86 @@
+use silent_keys_lib::transcript::{Transcript, TranscriptSegment};
+use silent_keys_lib::typed_metadata::{
+    validate, TypedCommit, TypedMetadataError, TypedMetadataSettings, COMMIT_DELIMITER,
+    TYPED_METADATA_VERSION,
+};
+
+fn transcript() -> Transcript {
+    Transcript {
+        language: Some("en".to_string()),
+        segments: vec![TranscriptSegment {
+            start_ms: 0,
+            end_ms: 1_000,
+            text: "Hello world".to_string(),
+            speaker: None,
+            confidence: None,
+        }],
+    }
+}
+
+fn publishing(delimiter: bool) -> TypedMetadataSettings {
+    TypedMetadataSettings {
+        enabled: true,
+        delimiter,
+        ..TypedMetadataSettings::default()
+    }
+}
+
+#[test]
+fn delimiter_is_typed_only_while_publishing_with_delimiters() {
+    assert_eq!(
+        publishing(true).delimit("Hello world".to_string()),
+        format!("Hello world{COMMIT_DELIMITER}")
+    );
+    assert_eq!(publishing(false).delimit("Hello".to_string()), "Hello");
+    assert_eq!(publishing(true).delimit(String::new()), "");
+
+    let disabled = TypedMetadataSettings {
+        enabled: false,
+        ..publishing(true)
+    };
+    assert_eq!(disabled.delimit("Hello".to_string()), "Hello");
+}
+
+#[test]
+fn zero_port_is_rejected() {
+    assert!(validate(&TypedMetadataSettings::default()).is_ok());
+    assert!(matches!(
+        validate(&TypedMetadataSettings {
+            port: 0,
+            ..TypedMetadataSettings::default()
+        }),
+        Err(TypedMetadataError::InvalidPort)
+    ));
+}
+
+#[test]
+fn settings_saved_before_delimiters_load_without_them() {
+    let settings: TypedMetadataSettings =
+        serde_json::from_str(r#"{"enabled":true,"port":47615}"#).unwrap();
+
+    assert!(!settings.delimiter);
+}
+
+#[test]
+fn commit_is_one_json_line_with_estimated_word_timings() {
+    let commit = TypedCommit::new(7, None, "Hello world", &transcript(), &publishing(true));
+    let line = commit.to_line();
+
+    assert!(line.ends_with('\n'));
+    assert_eq!(line.matches('\n').count(), 1);
+
+    let value: serde_json::Value = serde_json::from_str(&line).unwrap();
+    assert_eq!(value["version"], TYPED_METADATA_VERSION);
+    assert_eq!(value["sequence"], 7);
+    assert_eq!(value["session_id"], serde_json::Value::Null);
+    assert_eq!(value["text"], "Hello world");
+    assert_eq!(value["delimited"], true);
+    assert_eq!(value["language"], "en");
+    assert_eq!(
+        value["estimated_words"],
+        serde_json::json!([
+            {"word": "Hello", "start_ms": 0, "end_ms": 500},
+            {"word": "world", "start_ms": 500, "end_ms": 1000},
+        ])
+    );
+}
@@ -1,3 +1,5 @@
+#![cfg(feature = "desktop")]
+
 use silent_keys_lib::errors::UserFacing;
 use silent_keys_lib::updater::{begin_exclusive_update_for_tests, AppUpdateError};
 
@@ -0,0 +1,273 @@
+mod common;
+
+use std::borrow::Cow;
+use std::sync::{Arc, Mutex};
+
+use common::tone;
+use silent_keys_lib::recording::VadMonitor;
+use silent_keys_lib::vad::{
+    calibrate, speech_segments, trim_silence, PauseDetector, VadConfig, VadEvent, VadSegmenter,
+    VadSettings, MAX_VAD_THRESHOLD,
+};
+
+#[test]
+fn silence_produces_no_segments() {
+    assert!(speech_segments(&[0.0; 48_000], &VadConfig::default()).is_empty());
+}
+
+#[test]
+fn long_pauses_split_speech_into_ordered_padded_segments() {
+    let config = VadConfig::default();
+    let mut samples = vec![0.0; 14_400];
+    samples.extend(tone(220.0, 14_400, 0.5));
+    samples.extend(vec![0.0; 28_800]);
+    samples.extend(tone(220.0, 9_600, 0.5));
+    samples.extend(vec![0.0; 14_400]);
+
+    let segments = speech_segments(&samples, &config);
+
+    let padding = config.padding_samples;
+    assert_eq!(
+        segments,
+        [
+            14_400 - padding..28_800 + padding,
+            57_600 - padding..67_200 + padding
+        ]
+    );
+}
+
+#[test]
+fn short_pauses_stay_inside_one_segment() {
+    let mut samples = tone(220.0, 16_000, 0.5);
+    samples.extend(vec![0.0; 3_200]);
+    samples.extend(tone(220.0, 16_000, 0.5));
+
+    let segments = speech_segments(&samples, &VadConfig::default());
+
+    assert_eq!(segments.len(), 1);
+    assert_eq!(segments[0], 0..samples.len());
+}
+
+#[test]
+fn long_speech_is_split_at_the_segment_limit() {
+    let config = VadConfig {
+        max_segment_samples: 16_000,
+        ..VadConfig::default()
+    };
+
+    let segments = speech_segments(&tone(220.0, 40_000, 0.5), &config);
+
+    assert_eq!(segments, [0..16_000, 16_000..32_000, 32_000..40_000]);
+}
+
+#[test]
+fn trim_silence_keeps_padded_speech_only() {
+    let config = VadConfig::default();
+    let mut samples = vec![0.0; 14_400];
+    samples.extend(tone(220.0, 9_600, 0.5));
+    samples.extend(vec![0.0; 28_800]);
+    samples.extend(tone(220.0, 9_600, 0.5));
+    samples.extend(vec![0.0; 14_400]);
+
+    let trimmed = trim_silence(&samples, &config);
+
+    assert_eq!(trimmed.len(), 2 * 9_600 + 4 * config.padding_samples);
+    assert!(matches!(trimmed, Cow::Owned(_)));
+}
+
+#[test]
+fn trim_silence_leaves_silence_and_continuous_speech_untouched() {
+    let silence = vec![0.0; 16_000];
+    let speech = tone(220.0, 16_000, 0.5);
+
+    assert!(matches!(
+        trim_silence(&silence, &VadConfig::default()),
+        Cow::Borrowed(borrowed) if borrowed.len() == silence.len()
+    ));
+    assert!(matches!(
+        trim_silence(&speech, &VadConfig::default()),
+        Cow::Borrowed(_)
+    ));
+}
+
+#[test]
+fn pause_detector_reports_speech_resuming_after_a_long_pause() {
+    let mut detector = PauseDetector::new(&VadConfig::default(), 1_000);
+
+    assert!(!detector.observe(&vec![0.0; 32_000]));
+    assert!(!detector.observe(&tone(220.0, 9_600, 0.5)));
+    assert!(!detector.observe(&vec![0.0; 9_600]));
+    assert!(!detector.observe(&tone(220.0, 9_600, 0.5)));
+    assert!(!detector.observe(&vec![0.0; 16_320]));
+    assert!(detector.observe(&tone(220.0, 9_600, 0.5)));
+    assert!(!detector.observe(&tone(220.0, 9_600, 0.5)));
+}
+
+fn boundaries(events: &[VadEvent]) -> Vec<VadEvent> {
+    events
+        .iter()
+        .filter(|event| !matches!(event, VadEvent::Frame { .. }))
+        .cloned()
+        .collect()
+}
+
+#[test]
+fn segmenter_reports_segments_as_audio_arrives_in_odd_chunks() {
+    let mut samples = vec![0.0; 14_400];
+    samples.extend(tone(220.0, 14_400, 0.5));
+    samples.extend(vec![0.0; 28_800]);
+    samples.extend(tone(220.0, 9_600, 0.5));
+    samples.extend(vec![0.0; 14_400]);
+
+    let mut segmenter = VadSegmenter::new(VadConfig::default());
+    let mut events = Vec::new();
+    for chunk in samples.chunks(1_000) {
+        events.extend(segmenter.feed(chunk));
+    }
+    events.extend(segmenter.finish());
+
+    assert_eq!(
+        boundaries(&events),
+        [
+            VadEvent::SpeechStart { at_ms: 900 },
+            VadEvent::SpeechEnd {
+                start_ms: 900,
+                end_ms: 1_800
+            },
+            VadEvent::SpeechStart { at_ms: 3_600 },
+            VadEvent::SpeechEnd {
+                start_ms: 3_600,
+                end_ms: 4_200
+            },
+        ]
+    );
+    let frames = events
+        .iter()
+        .filter(|event| matches!(event, VadEvent::Frame { .. }))
+        .count();
+    assert_eq!(frames, samples.len() / VadConfig::default().frame_samples);
+}
+
+#[test]
+fn finishing_closes_open_speech_and_flushes_the_partial_frame() {
+    let mut segmenter = VadSegmenter::new(VadConfig::default());
+    let events = segmenter.feed(&tone(220.0, 4_900, 0.5));
+    assert_eq!(boundaries(&events), [VadEvent::SpeechStart { at_ms: 0 }]);
+
+    let events = segmenter.finish();
+    assert_eq!(
+        events.last(),
+        Some(&VadEvent::SpeechEnd {
+            start_ms: 0,
+            end_ms: 306
+        })
+    );
+    assert!(segmenter.finish().is_empty());
+}
+
+#[test]
+fn vad_events_have_stable_wire_shapes() {
+    let frame = serde_json::to_value(VadEvent::Frame {
+        at_ms: 30,
+        rms: 0.5,
+        speech: true,
+    })
+    .expect("frame event should serialize");
+    assert_eq!(
+        frame,
+        serde_json::json!({ "kind": "frame", "at_ms": 30, "rms": 0.5, "speech": true })
+    );
+    let end = serde_json::to_value(VadEvent::SpeechEnd {
+        start_ms: 900,
+        end_ms: 1_800,
+    })
+    .expect("speech end should serialize");
+    assert_eq!(
+        end,
+        serde_json::json!({ "kind": "speech_end", "start_ms": 900, "end_ms": 1_800 })
+    );
+}
+
+#[test]
+fn vad_monitor_delivers_only_while_subscribed() {
+    let monitor = VadMonitor::new();
+    let received = Arc::new(Mutex::new(Vec::new()));
+    let sink = received.clone();
+    assert!(monitor.set_sink(move |events: &[VadEvent]| {
+        sink.lock().unwrap().extend_from_slice(events);
+    }));
+    assert!(!monitor.set_sink(|_: &[VadEvent]| {}));
+    let event = VadEvent::SpeechStart { at_ms: 0 };
+
+    monitor.publish(std::slice::from_ref(&event));
+    assert!(!monitor.is_active());
+
+    monitor.subscribe();
+    monitor.publish(std::slice::from_ref(&event));
+    monitor.unsubscribe();
+    monitor.unsubscribe();
+    monitor.publish(std::slice::from_ref(&event));
+
+    assert!(!monitor.is_active());
+    assert_eq!(*received.lock().unwrap(), [event]);
+}
+
+#[test]
+fn vad_monitor_counts_speech_frames_without_subscribers() {
+    let monitor = VadMonitor::new();
+    let frame = |speech| VadEvent::Frame {
+        at_ms: 0,
+        rms: 0.1,
+        speech,
+    };
+
+    monitor.publish(&[frame(false), frame(true), frame(true)]);
+    monitor.publish(&[frame(false)]);
+
+    assert_eq!(monitor.speech_frames(), 2);
+}
+
+#[test]
+fn calibration_boosts_a_quiet_microphone_above_its_noise_floor() {
+    let calibration = calibrate(&[0.0005; 16_000], 12.0).expect("one second of noise");
+
+    assert!((calibration.noise_floor - 0.0005).abs() < 1e-6);
+    assert!((calibration.recommended.gain - 2.0).abs() < 1e-3);
+    let expected = 0.001 * 10f32.powf(12.0 / 20.0);
+    assert!((calibration.recommended.threshold - expected).abs() < 1e-5);
+}
+
+#[test]
+fn calibration_ignores_short_bursts_and_clamps_loud_rooms() {
+    let mut samples = vec![0.5; 16_000];
+    samples[..480].fill(0.9);
+    let calibration = calibrate(&samples, 30.0).expect("one second of noise");
+
+    assert!((calibration.noise_floor - 0.5).abs() < 1e-3);
+    assert!((calibration.peak - 0.9).abs() < 1e-3);
+    assert_eq!(calibration.recommended.gain, 1.0);
+    assert_eq!(calibration.recommended.threshold, MAX_VAD_THRESHOLD);
+}
+
+#[test]
+fn calibration_needs_at_least_one_frame() {
+    assert!(calibrate(&[], 12.0).is_none());
+    assert!(calibrate(&[0.0; 100], 12.0).is_none());
+    let silent = calibrate(&[0.0; 16_000], 12.0).expect("one second of silence");
+    assert_eq!(silent.recommended.gain, 1.0);
+}
+
+#[test]
+fn vad_settings_clamp_and_replace_non_finite_values() {
+    let settings = VadSettings {
+        threshold: f32::NAN,
+        noise_margin_db: 100.0,
+        gain: 0.0,
+    }
+    .clamped();
+
+    assert_eq!(settings.threshold, VadSettings::default().threshold);
+    assert_eq!(settings.noise_margin_db, 30.0);
+    assert_eq!(settings.gain, 0.25);
+    assert_eq!(settings.config().threshold, settings.threshold);
+}
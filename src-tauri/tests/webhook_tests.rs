@@ -0,0 +1,116 @@
+use std::io::{BufRead, BufReader, Read, Write};
+use std::net::TcpListener;
+use std::thread;
+
+use silent_keys_lib::webhook::{
+    send, signature, validate_url, DictationPayload, WebhookError, WebhookSettings,
+    SIGNATURE_HEADER,
+};
+
+fn payload() -> DictationPayload {
+    DictationPayload {
+        text: "hello world".to_string(),
+        language: Some("en-US".to_string()),
+        segments: Vec::new(),
+        audio_duration_ms: 1_500,
+        profile: Some("Work".to_string()),
+        recorded_at: "2026-01-01T09:00:00+00:00".to_string(),
+        finished_at: "2026-01-01T09:00:02+00:00".to_string(),
+    }
+}
+
+/// Answers one request per status and returns each request's header lines.
+fn serve(statuses: Vec<u16>) -> (String, thread::JoinHandle<Vec<Vec<String>>>) {
+    let listener = TcpListener::bind("127.0.0.1:0").expect("bind test server");
+    let url = format!("http://{}/hook", listener.local_addr().unwrap());
+    let handle = thread::spawn(move || {
+        statuses
+            .into_iter()
+            .map(|status| {
+                let (stream, _) = listener.accept().expect("accept webhook request");
+                let mut reader = BufReader::new(stream);
+                let mut headers = Vec::new();
+                loop {
+                    let mut line = String::new();
+                    reader.read_line(&mut line).expect("read header");
+                    let line = line.trim_end().to_string();
+                    if line.is_empty() {
+                        break;
+                    }
+                    headers.push(line);
+                }
+                let length = headers
+                    .iter()
+                    .find_map(|line| {
+                        let (name, value) = line.split_once(':')?;
+                        if name.eq_ignore_ascii_case("content-length") {
+                            value.trim().parse::<usize>().ok()
+                        } else {
+                            None
+                        }
+                    })
+                    .unwrap_or(0);
+                let mut body = vec![0; length];
+                reader.read_exact(&mut body).expect("read body");
+                let mut stream = reader.into_inner();
+                write!(
+                    stream,
+                    "HTTP/1.1 {status} Test\r\nContent-Length: 0\r\nConnection: close\r\n\r\n"
+                )
+                .expect("write response");
+                headers
+            })
+            .collect()
+    });
+    (url, handle)
+}
+
+#[test]
+fn signature_covers_timestamp_and_body() {
+    assert_eq!(
+        signature("secret", "1700000000", br#"{"text":"hi"}"#).unwrap(),
+        "sha256=3ad1ab8e3e2036926574b48bb349a6926291abb28309d44d18066d4f51de2112"
+    );
+}
+
+#[test]
+fn webhook_url_requires_http_scheme_and_host() {
+    assert!(validate_url("https://example.com/hook").is_ok());
+    assert!(validate_url(" http://localhost:8080 ").is_ok());
+    assert!(validate_url("ftp://example.com").is_err());
+    assert!(validate_url("https://").is_err());
+    assert!(validate_url("example.com").is_err());
+    assert!(validate_url("HTTPS://Example.com/hook").is_ok());
+    assert!(validate_url("https://exa mple.com").is_err());
+    assert!(validate_url("https://?x").is_err());
+    assert!(validate_url("mailto:someone@example.com").is_err());
+}
+
+#[test]
+fn webhook_retries_server_errors_and_signs_requests() {
+    let (url, server) = serve(vec![503, 200]);
+    let settings = WebhookSettings { enabled: true, url };
+
+    send(&settings, "secret", &payload()).expect("webhook delivered after retry");
+
+    let requests = server.join().expect("test server");
+    assert_eq!(requests.len(), 2);
+    let signature_prefix = format!("{}: sha256=", SIGNATURE_HEADER.to_ascii_lowercase());
+    assert!(requests.iter().all(|headers| headers
+        .iter()
+        .any(|line| line.to_ascii_lowercase().starts_with(&signature_prefix))));
+}
+
+#[test]
+fn webhook_does_not_retry_client_errors() {
+    let (url, server) = serve(vec![400]);
+    let settings = WebhookSettings { enabled: true, url };
+
+    let error = send(&settings, "", &payload()).expect_err("client error is final");
+
+    assert!(matches!(error, WebhookError::Status(400)));
+    let requests = server.join().expect("test server");
+    assert!(requests[0].iter().all(|line| !line
+        .to_ascii_lowercase()
+        .starts_with("x-silentkeys-signature")));
+}
@@ -3,9 +3,12 @@ use std::time::Instant;
 
 use serde::Deserialize;
 use silent_keys_lib::asr::{fallback_model_root, resolve_model_dir, AsrModel};
+use silent_keys_lib::time_stretch::{stretch, TimeStretch};
 use unicode_segmentation::UnicodeSegmentation;
 
 const CORPUS_MANIFEST_ENV: &str = "SILENT_KEYS_CORPUS_MANIFEST";
+/// Speed percentage the time-stretch comparison slows the corpus to.
+const TIME_STRETCH_PERCENT_ENV: &str = "SILENT_KEYS_TIME_STRETCH_PERCENT";
 
 #[derive(Deserialize)]
 struct Corpus {
@@ -124,29 +127,36 @@ fn newest_versioned_manifest_is_selected() {
     assert_eq!(newest_manifest_name(names.into_iter()), Some("v12.json"));
 }
 
-#[test]
-#[ignore = "requires the downloaded Nemotron model"]
-fn corpus_word_error_rate_stays_within_ceiling() {
+fn load_corpus() -> Corpus {
     let manifest_path = corpus_manifest_path();
     let manifest = std::fs::read_to_string(&manifest_path).expect("corpus manifest should exist");
     let corpus: Corpus = serde_json::from_str(&manifest).expect("corpus manifest should parse");
     assert!(!corpus.clips.is_empty(), "corpus must contain clips");
-    let project_root = Path::new(env!("CARGO_MANIFEST_DIR"));
+    corpus
+}
 
+fn load_model(corpus: &Corpus) -> AsrModel {
     let model_root = std::env::var("SILENT_KEYS_MODEL_ROOT")
         .map(PathBuf::from)
         .unwrap_or_else(|_| fallback_model_root());
     let model_dir = resolve_model_dir(&model_root).expect("model dir should resolve");
-    let mut model =
-        AsrModel::new(&model_dir, &corpus.clips[0].language).expect("model should load");
+    AsrModel::new(&model_dir, &corpus.clips[0].language).expect("model should load")
+}
 
+/// Decodes every clip, slowed to `speed` when given, and returns the word
+/// errors and reference words over the corpus.
+fn corpus_errors(model: &mut AsrModel, corpus: &Corpus, speed: Option<f32>) -> (usize, usize) {
+    let project_root = Path::new(env!("CARGO_MANIFEST_DIR"));
     let mut total_errors = 0usize;
     let mut total_words = 0usize;
     for clip in &corpus.clips {
         model
             .set_language(&clip.language)
             .expect("corpus clip language must be supported by the model");
-        let samples = load_samples(&project_root.join(&clip.audio));
+        let mut samples = load_samples(&project_root.join(&clip.audio));
+        if let Some(speed) = speed {
+            samples = stretch(&samples, speed);
+        }
         let reference_text = std::fs::read_to_string(project_root.join(&clip.transcript))
             .expect("corpus transcript should exist");
 
@@ -177,7 +187,16 @@ fn corpus_word_error_rate_stays_within_ceiling() {
             elapsed.as_secs_f64() / audio_seconds
         );
     }
+    (total_errors, total_words)
+}
+
+#[test]
+#[ignore = "requires the downloaded Nemotron model"]
+fn corpus_word_error_rate_stays_within_ceiling() {
+    let corpus = load_corpus();
+    let mut model = load_model(&corpus);
 
+    let (total_errors, total_words) = corpus_errors(&mut model, &corpus, None);
     let corpus_wer = total_errors as f64 / total_words as f64;
     eprintln!(
         "corpus v{}: WER {:.4} ({total_errors}/{total_words} words over {} clips)",
@@ -192,3 +211,39 @@ fn corpus_word_error_rate_stays_within_ceiling() {
         corpus.max_wer
     );
 }
+
+/// Reports whether slowing the corpus down changes its word error rate. Point
+/// `SILENT_KEYS_CORPUS_MANIFEST` at a manifest of your own recordings to check
+/// a setting for your voice before enabling it in a profile.
+#[test]
+#[ignore = "requires the downloaded Nemotron model"]
+fn time_stretch_word_error_rate_comparison() {
+    let corpus = load_corpus();
+    let mut model = load_model(&corpus);
+    let time_stretch = TimeStretch {
+        enabled: true,
+        speed_percent: std::env::var(TIME_STRETCH_PERCENT_ENV)
+            .ok()
+            .and_then(|percent| percent.parse().ok())
+            .unwrap_or(TimeStretch::default().speed_percent),
+    }
+    .clamped();
+
+    let (plain_errors, words) = corpus_errors(&mut model, &corpus, None);
+    let (stretched_errors, _) = corpus_errors(&mut model, &corpus, time_stretch.speed());
+
+    let plain_wer = plain_errors as f64 / words as f64;
+    let stretched_wer = stretched_errors as f64 / words as f64;
+    eprintln!(
+        "corpus v{}: WER {plain_wer:.4} as recorded, {stretched_wer:.4} at {}% speed ({})",
+        corpus.corpus_version,
+        time_stretch.speed_percent,
+        if stretched_errors < plain_errors {
+            "improved"
+        } else if stretched_errors == plain_errors {
+            "unchanged"
+        } else {
+            "worse"
+        }
+    );
+}
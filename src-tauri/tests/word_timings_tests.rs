@@ -0,0 +1,75 @@
+use silent_keys_lib::transcript::{Transcript, TranscriptSegment};
+use silent_keys_lib::word_timings::{
+    estimated_word_timings, to_audacity_labels, to_json, WordTiming,
+};
+
+fn segment(start_ms: u64, end_ms: u64, text: &str) -> TranscriptSegment {
+    TranscriptSegment {
+        start_ms,
+        end_ms,
+        text: text.to_string(),
+        speaker: None,
+        confidence: None,
+    }
+}
+
+fn timing(word: &str, start_ms: u64, end_ms: u64) -> WordTiming {
+    WordTiming {
+        word: word.to_string(),
+        start_ms,
+        end_ms,
+    }
+}
+
+#[test]
+fn words_share_their_segment_by_length() {
+    let transcript = Transcript {
+        language: Some("en".to_string()),
+        segments: vec![
+            segment(1_000, 2_000, "Hi there"),
+            segment(3_000, 3_600, " café  now "),
+        ],
+    };
+
+    assert_eq!(
+        estimated_word_timings(&transcript),
+        [
+            timing("Hi", 1_000, 1_285),
+            timing("there", 1_285, 2_000),
+            timing("café", 3_000, 3_342),
+            timing("now", 3_342, 3_600),
+        ]
+    );
+}
+
+#[test]
+fn empty_segments_have_no_words() {
+    let transcript = Transcript {
+        language: None,
+        segments: vec![segment(0, 500, "  ")],
+    };
+
+    assert!(estimated_word_timings(&transcript).is_empty());
+}
+
+#[test]
+fn exports_json_and_audacity_labels() {
+    let words = [timing("Hello", 250, 1_005), timing("world", 1_005, 61_000)];
+
+    let json: serde_json::Value = serde_json::from_str(&to_json(Some("en"), &words)).unwrap();
+    assert_eq!(
+        json,
+        serde_json::json!({
+            "language": "en",
+            "estimated": true,
+            "words": [
+                {"word": "Hello", "start_ms": 250, "end_ms": 1005},
+                {"word": "world", "start_ms": 1005, "end_ms": 61000},
+            ]
+        })
+    );
+    assert_eq!(
+        to_audacity_labels(&words),
+        "0.250000\t1.005000\tHello\n1.005000\t61.000000\tworld\n"
+    );
+}
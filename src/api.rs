@@ -1,4 +1,6 @@
 use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+pub use transcript_patch::{Transcript, TranscriptPatch};
 use wasm_bindgen::closure::Closure;
 use wasm_bindgen::prelude::*;
 
@@ -12,6 +14,20 @@ extern "C" {
         event: &str,
         handler: &Closure<dyn FnMut(JsValue)>,
     ) -> Result<JsValue, JsValue>;
+
+    #[wasm_bindgen(js_namespace = ["navigator", "clipboard"], js_name = writeText, catch)]
+    async fn write_clipboard_text(text: &str) -> Result<JsValue, JsValue>;
+
+    #[wasm_bindgen(js_namespace = ["window", "__TAURI__", "window"], js_name = getCurrentWindow)]
+    fn current_window() -> JsValue;
+}
+
+/// The label of the window this UI runs in, such as `main` or `document`.
+pub fn current_window_label() -> String {
+    js_sys::Reflect::get(&current_window(), &"label".into())
+        .ok()
+        .and_then(|label| label.as_string())
+        .unwrap_or_default()
 }
 
 #[derive(Serialize, Deserialize)]
@@ -26,6 +42,8 @@ pub struct ModelDownloadProgressDto {
     pub downloaded_bytes: u64,
     pub total_bytes: u64,
     pub done: bool,
+    #[serde(default)]
+    pub paused: bool,
 }
 
 #[derive(Deserialize, Debug)]
@@ -37,6 +55,54 @@ pub enum EngineStateDto {
     Failed(String),
 }
 
+#[derive(Clone, Copy, Deserialize, Debug)]
+#[serde(rename_all = "snake_case")]
+pub enum ModelLoadStageDto {
+    WaitingForDictation,
+    Unloading,
+    VerifyingFiles,
+    Downloading,
+    ReadingConfig,
+    LoadingSessions,
+    ConfiguringLanguage,
+    WarmingUp,
+    Ready,
+    Failed,
+}
+
+#[derive(Deserialize, Debug)]
+pub struct ModelStatusDto {
+    pub stage: ModelLoadStageDto,
+    pub elapsed_ms: u64,
+}
+
+#[derive(Deserialize, Debug)]
+pub struct ModelNotReadyDto {
+    pub state: EngineStateDto,
+    pub progress: Option<ModelDownloadProgressDto>,
+    pub buffering: bool,
+}
+
+#[derive(Clone, Debug, Default, Deserialize, PartialEq)]
+pub struct OnboardingDto {
+    pub step: String,
+    pub problem: Option<String>,
+    pub sample_text: Option<String>,
+}
+
+#[derive(Clone, Deserialize, Debug)]
+pub struct TranscriptSegmentDto {
+    pub text: String,
+}
+
+#[derive(Deserialize, Debug)]
+pub struct FileTranscriptionProgressDto {
+    pub path: String,
+    pub completed: usize,
+    pub total: usize,
+    pub segment: Option<TranscriptSegmentDto>,
+}
+
 #[derive(Clone, Deserialize, Debug)]
 pub struct AppUpdateInfoDto {
     pub current_version: String,
@@ -45,11 +111,550 @@ pub struct AppUpdateInfoDto {
     pub body: Option<String>,
 }
 
-#[derive(Deserialize)]
+#[derive(Clone, Debug, Deserialize)]
+pub struct HardwareProfileDto {
+    pub cores: usize,
+    pub simd: String,
+    pub memory_bytes: Option<u64>,
+    pub gpu: Option<bool>,
+}
+
+#[derive(Clone, Debug, Deserialize)]
+pub struct HardwareRecommendationDto {
+    pub precision: String,
+    pub streaming_enabled: bool,
+    pub resampler_quality: String,
+}
+
+#[derive(Clone, Debug, Deserialize)]
+pub struct HardwareDetectionDto {
+    pub profile: HardwareProfileDto,
+    pub recommendation: HardwareRecommendationDto,
+}
+
+#[derive(Clone, Copy, Debug, Default, Deserialize, PartialEq, Serialize)]
+pub struct OrtThreadsDto {
+    pub intra_threads: usize,
+    pub inter_threads: usize,
+}
+
+#[derive(Clone, Debug, Deserialize)]
+pub struct ThreadTimingDto {
+    pub threads: OrtThreadsDto,
+    pub decode_ms: u64,
+}
+
+#[derive(Clone, Debug, Deserialize)]
+pub struct ThreadTuningDto {
+    pub fastest: OrtThreadsDto,
+    pub timings: Vec<ThreadTimingDto>,
+}
+
+#[derive(Clone, Deserialize, Debug)]
+pub struct AudioHealthDto {
+    pub device_sample_rate: u32,
+    pub overrun_samples: usize,
+    pub dropped_streaming_samples: usize,
+    #[serde(default)]
+    pub processing_chain: Vec<String>,
+}
+
+#[derive(Clone, Deserialize, Debug)]
+pub struct CaptureQualityDto {
+    pub flag: String,
+    pub suggestions: Vec<String>,
+}
+
+#[derive(Clone, Debug, Deserialize, PartialEq)]
+pub struct SpeechStatsDto {
+    pub recorded_at: String,
+    pub duration_ms: u64,
+    pub speaking_ms: u64,
+    pub words: usize,
+    pub words_per_minute: f32,
+    pub pause_ratio: f32,
+    pub longest_monologue_ms: u64,
+}
+
+#[derive(Clone, Debug, Deserialize, PartialEq)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum VadEventDto {
+    Frame { at_ms: u64, rms: f32, speech: bool },
+    SpeechStart { at_ms: u64 },
+    SpeechEnd { start_ms: u64, end_ms: u64 },
+}
+
+#[derive(Clone, Debug, Deserialize, PartialEq)]
+pub struct WaveformFrameDto {
+    pub at_ms: u64,
+    pub peaks: Vec<f32>,
+}
+
+#[derive(Clone, Copy, Debug, Default, Deserialize, PartialEq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum TargetLockDto {
+    #[default]
+    Off,
+    Refocus,
+    Abort,
+}
+
+impl TargetLockDto {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::Off => "off",
+            Self::Refocus => "refocus",
+            Self::Abort => "abort",
+        }
+    }
+
+    pub fn from_value(value: &str) -> Self {
+        match value {
+            "refocus" => Self::Refocus,
+            "abort" => Self::Abort,
+            _ => Self::Off,
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, Deserialize, PartialEq, Serialize)]
+pub struct TypingPaceDto {
+    pub chunk_chars: usize,
+    pub chunk_delay_ms: u64,
+    pub max_chars_per_sec: u32,
+    pub pause_for_modifiers: bool,
+    #[serde(default)]
+    pub target_lock: TargetLockDto,
+}
+
+impl Default for TypingPaceDto {
+    fn default() -> Self {
+        Self {
+            chunk_chars: 0,
+            chunk_delay_ms: 0,
+            max_chars_per_sec: 0,
+            pause_for_modifiers: true,
+            target_lock: TargetLockDto::Off,
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, Default, Deserialize, PartialEq, Serialize)]
+pub struct InputDeviceConfigDto {
+    pub sample_rate: Option<u32>,
+    pub channel_mask: u64,
+    pub buffer_frames: Option<u32>,
+}
+
+#[derive(Clone, Debug, Default, Deserialize, PartialEq, Serialize)]
+pub struct InputDeviceDto {
+    pub device: Option<String>,
+    pub configs: BTreeMap<String, InputDeviceConfigDto>,
+}
+
+#[derive(Clone, Debug, Default, Deserialize, PartialEq, Serialize)]
+pub struct ShortcutGestureDto {
+    pub mode: String,
+    pub window_ms: u64,
+    pub follow_up: String,
+}
+
+#[derive(Clone, Copy, Debug, Default, Deserialize, PartialEq, Serialize)]
+pub struct PedalBindingDto {
+    pub vendor_id: u16,
+    pub product_id: u16,
+    pub report_byte: usize,
+    pub mask: u8,
+}
+
+#[derive(Clone, Copy, Debug, Default, Deserialize, PartialEq, Serialize)]
+pub struct TriggerSettingsDto {
+    pub mouse_button: Option<u8>,
+    pub pedal: Option<PedalBindingDto>,
+}
+
+#[derive(Clone, Debug, Deserialize, PartialEq)]
+pub struct HidDeviceDto {
+    pub vendor_id: u16,
+    pub product_id: u16,
+    pub name: String,
+}
+
+#[derive(Clone, Copy, Debug, Default, Deserialize, PartialEq, Serialize)]
+pub struct VadSettingsDto {
+    pub threshold: f32,
+    pub noise_margin_db: f32,
+    pub gain: f32,
+}
+
+#[derive(Clone, Debug, Deserialize)]
+pub struct MicrophoneTestDto {
+    pub duration_ms: u64,
+    pub peak_dbfs: f32,
+    pub rms_dbfs: f32,
+    pub preview: Vec<f32>,
+    pub too_quiet: bool,
+    pub quality: Option<CaptureQualityDto>,
+}
+
+#[derive(Clone, Copy, Debug, Deserialize, PartialEq)]
+pub struct NoiseCalibrationDto {
+    pub noise_floor: f32,
+    pub peak: f32,
+    pub recommended: VadSettingsDto,
+}
+
+#[derive(Clone, Debug, Default, Deserialize, PartialEq, Serialize)]
+#[serde(tag = "mode", content = "profile", rename_all = "snake_case")]
+pub enum NoiseSelectionDto {
+    #[default]
+    Off,
+    Profile(String),
+    Auto,
+}
+
+#[derive(Clone, Debug, Default, Deserialize, PartialEq)]
+pub struct NoiseSuppressionDto {
+    pub profiles: Vec<String>,
+    pub selection: NoiseSelectionDto,
+}
+
+#[derive(Clone, Copy, Debug, Default, Deserialize, PartialEq)]
+pub struct VoiceProfileDto {
+    pub utterances: u32,
+    pub level_db: f32,
+    pub tilt_db: f32,
+}
+
+#[derive(Clone, Copy, Debug, Deserialize, PartialEq)]
+pub struct VoiceCorrectionDto {
+    pub gain_db: f32,
+    pub tilt_db: f32,
+}
+
+#[derive(Clone, Copy, Debug, Default, Deserialize, PartialEq)]
+pub struct SpeakerAdaptationDto {
+    pub enabled: bool,
+    pub voice: VoiceProfileDto,
+    pub correction: Option<VoiceCorrectionDto>,
+}
+
+#[derive(Clone, Debug, Default, Deserialize, PartialEq, Serialize)]
+pub struct ContinuationDto {
+    pub enabled: bool,
+    pub window_secs: u64,
+}
+
+#[derive(Clone, Debug, Default, Deserialize, PartialEq, Serialize)]
+pub struct InactivityDto {
+    pub enabled: bool,
+    pub minutes: u64,
+    pub grace_secs: u64,
+}
+
+#[derive(Clone, Copy, Debug, Deserialize, PartialEq)]
+pub struct InactivityWarningDto {
+    pub remaining_secs: u64,
+}
+
+#[derive(Clone, Debug, Default, Deserialize, PartialEq, Serialize)]
+pub struct PauseMarkersDto {
+    pub enabled: bool,
+    pub min_pause_ms: u64,
+    pub marker: String,
+}
+
+#[derive(Clone, Debug, Deserialize, PartialEq, Serialize)]
+pub struct TimeStretchDto {
+    pub enabled: bool,
+    pub speed_percent: u32,
+}
+
+#[derive(Clone, Debug, Deserialize, PartialEq, Serialize)]
+pub struct DecodeCacheDto {
+    pub enabled: bool,
+    pub max_entries: usize,
+}
+
+impl Default for DecodeCacheDto {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            max_entries: 256,
+        }
+    }
+}
+
+#[derive(Clone, Debug, Deserialize, PartialEq, Serialize)]
+pub struct LookbackDto {
+    pub enabled: bool,
+    pub buffer_secs: u32,
+    pub transcribe_secs: u32,
+}
+
+impl Default for LookbackDto {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            buffer_secs: 120,
+            transcribe_secs: 30,
+        }
+    }
+}
+
+impl Default for TimeStretchDto {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            speed_percent: 90,
+        }
+    }
+}
+
+#[derive(Clone, Debug, Deserialize, PartialEq, Serialize)]
+pub struct PatchRoutingDto {
+    pub shortcut: String,
+    pub app: String,
+}
+
+impl Default for PatchRoutingDto {
+    fn default() -> Self {
+        Self {
+            shortcut: "both".to_string(),
+            app: "window".to_string(),
+        }
+    }
+}
+
+#[derive(Clone, Debug, Default, Deserialize, PartialEq, Serialize)]
+pub struct TextCommandsDto {
+    pub spelling: bool,
+    pub code_mode: bool,
+}
+
+#[derive(Clone, Debug, Default, Deserialize, PartialEq, Serialize)]
+pub struct PostProcessingDto {
+    pub languages: BTreeMap<String, Vec<String>>,
+}
+
+#[derive(Clone, Debug, Deserialize, PartialEq)]
+pub struct PostProcessStageDto {
+    pub name: String,
+    pub languages: Vec<String>,
+}
+
+#[derive(Clone, Debug, Deserialize, PartialEq, Serialize)]
+pub struct QuietRangeDto {
+    pub days: Vec<u8>,
+    pub start_minute: u16,
+    pub end_minute: u16,
+}
+
+#[derive(Clone, Debug, Default, Deserialize, PartialEq, Serialize)]
+pub struct QuietHoursDto {
+    pub enabled: bool,
+    pub ranges: Vec<QuietRangeDto>,
+}
+
+#[derive(Clone, Debug, Default, Deserialize, PartialEq, Serialize)]
+pub struct WebhookDto {
+    pub enabled: bool,
+    pub url: String,
+    pub secret: String,
+}
+
+#[derive(Clone, Debug, Default, Deserialize, PartialEq, Serialize)]
+pub struct LlmHandoffDto {
+    pub enabled: bool,
+    pub endpoint: String,
+    pub model: String,
+    pub api_key: String,
+    pub template: String,
+}
+
+#[derive(Clone, Debug, Default, Deserialize, PartialEq, Serialize)]
+pub struct OutputTemplatesDto {
+    pub record: String,
+    pub llm_handoff: String,
+}
+
+#[derive(Clone, Debug, Default, Deserialize, PartialEq, Serialize)]
+pub struct CaptureFileDto {
+    pub enabled: bool,
+    pub path: String,
+    pub name_entries: bool,
+    pub split_utterances: bool,
+    #[serde(default)]
+    pub clip_export: WavExportDto,
+}
+
+#[derive(Clone, Copy, Debug, Default, Deserialize, PartialEq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum WavEncodingDto {
+    #[default]
+    Pcm16,
+    Float32,
+}
+
+impl WavEncodingDto {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::Pcm16 => "pcm16",
+            Self::Float32 => "float32",
+        }
+    }
+
+    pub fn from_value(value: &str) -> Self {
+        match value {
+            "float32" => Self::Float32,
+            _ => Self::Pcm16,
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, Default, Deserialize, PartialEq, Serialize)]
+pub struct WavExportDto {
+    pub trim_silence: bool,
+    pub normalize: bool,
+    pub encoding: WavEncodingDto,
+}
+
+#[derive(Clone, Debug, Default, Deserialize, PartialEq, Serialize)]
+pub struct NetworkInputDto {
+    pub enabled: bool,
+    pub port: u16,
+}
+
+#[derive(Clone, Debug, Default, Deserialize, PartialEq, Serialize)]
+pub struct TypedMetadataDto {
+    pub enabled: bool,
+    pub port: u16,
+    pub delimiter: bool,
+}
+
+#[derive(Clone, Debug, Default, Deserialize, PartialEq)]
+pub struct DigestExportDto {
+    pub markdown: String,
+    pub path: Option<String>,
+}
+
+#[derive(Clone, Debug, Deserialize, PartialEq)]
+pub struct HistoryEntryDto {
+    pub index: usize,
+    pub at: String,
+    pub title: Option<String>,
+    pub preview: String,
+    #[serde(default)]
+    pub utterances: Vec<HistoryUtteranceDto>,
+    #[serde(default)]
+    pub duplicate_of: Option<usize>,
+}
+
+#[derive(Clone, Debug, Deserialize, PartialEq)]
+pub struct HistoryUtteranceDto {
+    pub index: usize,
+    pub at: String,
+    pub text: String,
+}
+
+#[derive(Clone, Debug, Default, PartialEq, Serialize)]
+pub struct RetranscribeConfigDto {
+    pub language: Option<String>,
+    pub time_stretch: Option<TimeStretchDto>,
+    pub raw: bool,
+}
+
+#[derive(Clone, Debug, Deserialize, PartialEq)]
 #[serde(tag = "kind", content = "text", rename_all = "snake_case")]
-pub enum TranscriptionUpdateDto {
-    Append(String),
-    Replace(String),
+pub enum DiffWordDto {
+    Same(String),
+    Removed(String),
+    Added(String),
+}
+
+#[derive(Clone, Debug, Deserialize, PartialEq)]
+pub struct RetranscriptionDto {
+    pub original: Vec<String>,
+    pub retranscribed: Vec<String>,
+    pub diff: Vec<DiffWordDto>,
+}
+
+#[derive(Clone, Debug, Deserialize, PartialEq)]
+pub struct LogEntryDto {
+    pub timestamp: String,
+    pub level: String,
+    pub target: String,
+    pub message: String,
+}
+
+#[derive(Clone, Copy, Debug, Default, Deserialize, PartialEq)]
+pub struct SettingsLockDto {
+    pub enabled: bool,
+    pub unlocked: bool,
+}
+
+#[derive(Clone, Debug, Default, Deserialize, PartialEq, Serialize)]
+pub struct AutostartDto {
+    pub enabled: bool,
+    pub start_in_tray: bool,
+}
+
+#[derive(Clone, Debug, Default, Deserialize, PartialEq)]
+pub struct ProfileListDto {
+    pub active: Option<String>,
+    pub names: Vec<String>,
+}
+
+#[derive(Clone, Debug, Default, Deserialize, PartialEq)]
+pub struct CompanionDto {
+    pub enabled: bool,
+    pub port: u16,
+    pub devices: Vec<CompanionDeviceDto>,
+}
+
+#[derive(Clone, Debug, Deserialize, PartialEq)]
+pub struct CompanionDeviceDto {
+    pub id: String,
+    pub name: String,
+    pub paired_at: String,
+}
+
+#[derive(Clone, Debug, Deserialize)]
+pub struct PairingInfoDto {
+    pub code: String,
+    pub expires_in_secs: u64,
+}
+
+#[derive(Deserialize)]
+pub struct SessionUpdateDto {
+    pub session_id: Option<u64>,
+    #[serde(flatten)]
+    pub patch: TranscriptPatch,
+}
+
+#[derive(Clone, Copy, Debug, Deserialize, PartialEq)]
+pub struct PatchProtocolDto {
+    pub windowed_drafts: bool,
+    pub windowed_draft_min_chars: u32,
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct NegotiatePatchProtocolArgs {
+    windowed_drafts: bool,
+}
+
+#[derive(Clone, Debug, Deserialize, PartialEq)]
+pub struct ShortcutConflictDto {
+    pub shortcut: String,
+    pub likely_owners: Vec<String>,
+    pub suggestions: Vec<String>,
+}
+
+#[derive(Deserialize)]
+pub struct DictationErrorDto {
+    pub session_id: Option<u64>,
+    pub message: String,
 }
 
 #[derive(Serialize)]
@@ -58,99 +663,1416 @@ struct SetModelPathArgs {
 }
 
 #[derive(Serialize)]
-struct SetStreamingArgs {
-    enabled: bool,
+struct SetTypingPaceArgs {
+    pace: TypingPaceDto,
 }
 
 #[derive(Serialize)]
-struct SetAsrLanguageArgs {
-    language: String,
+struct SetOrtThreadsArgs {
+    threads: OrtThreadsDto,
 }
 
-async fn invoke_no_args(cmd: &str) -> Result<JsValue, String> {
-    invoke(cmd, JsValue::NULL).await.map_err(extract_error)
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct SetInputDeviceArgs {
+    input_device: InputDeviceDto,
 }
 
-pub async fn fetch_engine_state() -> Result<EngineStateDto, String> {
-    let value = invoke_no_args("engine_state").await?;
-    serde_wasm_bindgen::from_value(value).map_err(|err| err.to_string())
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct SetShortcutGestureArgs {
+    gesture: ShortcutGestureDto,
 }
 
-pub async fn retry_model_download_cmd() -> Result<(), String> {
-    invoke_no_args("retry_model_download").await.map(|_| ())
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct SetTriggersArgs {
+    triggers: TriggerSettingsDto,
 }
 
-pub async fn start_recording_cmd() -> Result<(), String> {
-    invoke_no_args("start_recording").await.map(|_| ())
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct SetVadSettingsArgs {
+    vad: VadSettingsDto,
 }
 
-pub async fn stop_recording_cmd() -> Result<(), String> {
-    invoke_no_args("stop_recording").await.map(|_| ())
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct SetContinuationArgs {
+    continuation: ContinuationDto,
 }
 
-pub async fn fetch_current_shortcut() -> Result<Option<String>, String> {
-    let value = invoke_no_args("get_record_shortcut").await?;
-    Ok(value.as_string())
+#[derive(Serialize)]
+struct SetInactivityArgs {
+    inactivity: InactivityDto,
 }
 
-pub async fn fetch_model_path() -> Result<String, String> {
-    let value = invoke_no_args("get_model_path").await?;
-    value
-        .as_string()
-        .ok_or_else(|| "Invalid response format".to_string())
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct SetPauseMarkersArgs {
+    pause_markers: PauseMarkersDto,
 }
 
-pub async fn fetch_default_shortcut() -> Result<Option<String>, String> {
-    let value = invoke_no_args("default_record_shortcut").await?;
-    Ok(value.as_string())
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct SetTimeStretchArgs {
+    time_stretch: TimeStretchDto,
 }
 
-pub async fn save_model_path(path: String) -> Result<(), String> {
-    let args =
-        serde_wasm_bindgen::to_value(&SetModelPathArgs { path }).map_err(|err| err.to_string())?;
-    invoke("set_model_path", args)
-        .await
-        .map(|_| ())
-        .map_err(extract_error)
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct SetDecodeCacheArgs {
+    decode_cache: DecodeCacheDto,
 }
 
-pub async fn pick_model_folder_cmd() -> Result<Option<String>, String> {
-    let value = invoke_no_args("pick_model_folder").await?;
-    if value.is_null() {
-        return Ok(None);
-    }
-    value
-        .as_string()
-        .ok_or_else(|| "Invalid response".to_string())
-        .map(Some)
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct SetLookbackArgs {
+    lookback: LookbackDto,
 }
 
-pub async fn reset_settings_cmd() -> Result<(), String> {
-    invoke_no_args("reset_settings").await.map(|_| ())
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct SetPatchRoutingArgs {
+    routing: PatchRoutingDto,
 }
 
-pub async fn save_shortcut(shortcut: &str) -> Result<String, String> {
-    let args = serde_wasm_bindgen::to_value(&UpdateShortcutArgs { shortcut })
-        .map_err(|err| err.to_string())?;
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct SetTextCommandsArgs {
+    text_commands: TextCommandsDto,
+}
 
-    let value = invoke("update_record_shortcut", args)
-        .await
-        .map_err(extract_error)?;
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct SetPostProcessingArgs {
+    post_processing: PostProcessingDto,
+}
 
-    value
-        .as_string()
-        .ok_or_else(|| "Shortcut saved, but response was empty".to_string())
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct SetQuietHoursArgs {
+    quiet_hours: QuietHoursDto,
 }
 
-pub async fn fetch_streaming_enabled() -> Result<bool, String> {
-    let value = invoke_no_args("get_use_streaming").await?;
-    Ok(value.as_bool().unwrap_or(false))
+#[derive(Serialize)]
+struct SetWebhookArgs {
+    webhook: WebhookDto,
 }
 
-pub async fn save_streaming_enabled(enabled: bool) -> Result<(), String> {
-    let args = serde_wasm_bindgen::to_value(&SetStreamingArgs { enabled })
-        .map_err(|err| err.to_string())?;
-    invoke("set_use_streaming", args)
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct SetLlmHandoffArgs {
+    llm_handoff: LlmHandoffDto,
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct SetOutputTemplatesArgs {
+    output_templates: OutputTemplatesDto,
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct SetCaptureFileArgs {
+    capture_file: CaptureFileDto,
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct SetNetworkInputArgs {
+    network_input: NetworkInputDto,
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct SetTypedMetadataArgs {
+    typed_metadata: TypedMetadataDto,
+}
+
+#[derive(Serialize)]
+struct RecentLogsArgs {
+    level: String,
+    limit: usize,
+}
+
+/// What to do with the open document; see the backend's `DocumentFinish`.
+#[derive(Clone, Debug, Serialize)]
+#[serde(tag = "action", rename_all = "snake_case")]
+pub enum DocumentFinishDto {
+    Type,
+    Save { path: String },
+    Discard,
+}
+
+#[derive(Serialize)]
+struct SetDocumentTextArgs {
+    text: String,
+}
+
+#[derive(Serialize)]
+struct FinishDocumentArgs {
+    finish: DocumentFinishDto,
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct ExportDigestArgs {
+    from: String,
+    to: String,
+    notes_dir: Option<String>,
+}
+
+#[derive(Serialize)]
+struct RenameHistoryEntryArgs {
+    index: usize,
+    title: String,
+}
+
+#[derive(Serialize)]
+struct RetranscribeUtteranceArgs {
+    index: usize,
+    utterance: usize,
+}
+
+#[derive(Serialize)]
+struct RetranscribeEntryArgs {
+    index: usize,
+    config: RetranscribeConfigDto,
+}
+
+#[derive(Serialize)]
+struct SetHistoryUtterancesArgs {
+    index: usize,
+    texts: Vec<String>,
+}
+
+#[derive(Serialize)]
+struct SetAutostartArgs {
+    autostart: AutostartDto,
+}
+
+#[derive(Serialize)]
+struct PassphraseArgs {
+    passphrase: String,
+}
+
+#[derive(Serialize)]
+struct ProfileNameArgs {
+    name: String,
+}
+
+#[derive(Serialize)]
+struct SetNoiseSelectionArgs {
+    selection: NoiseSelectionDto,
+}
+
+#[derive(Serialize)]
+struct SetCompanionArgs {
+    enabled: bool,
+    port: u16,
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct RevokeCompanionDeviceArgs {
+    device_id: String,
+}
+
+#[derive(Serialize)]
+struct SetEnabledArgs {
+    enabled: bool,
+}
+
+#[derive(Serialize)]
+struct SetResamplerQualityArgs {
+    quality: String,
+}
+
+#[derive(Serialize)]
+struct SetModelPrewarmArgs {
+    prewarm: String,
+}
+
+#[derive(Serialize)]
+struct SetStreamingStrategyArgs {
+    strategy: String,
+}
+
+#[derive(Serialize)]
+struct SetPowerSaverArgs {
+    mode: String,
+}
+
+#[derive(Serialize)]
+struct SetRecordingIndicatorArgs {
+    indicator: String,
+}
+
+#[derive(Serialize)]
+struct SetAsrLanguageArgs {
+    language: String,
+}
+
+async fn invoke_no_args(cmd: &str) -> Result<JsValue, String> {
+    invoke(cmd, JsValue::NULL).await.map_err(extract_error)
+}
+
+pub async fn fetch_engine_state() -> Result<EngineStateDto, String> {
+    let value = invoke_no_args("engine_state").await?;
+    serde_wasm_bindgen::from_value(value).map_err(|err| err.to_string())
+}
+
+pub async fn fetch_speech_stats() -> Result<Vec<SpeechStatsDto>, String> {
+    let value = invoke_no_args("list_speech_stats").await?;
+    serde_wasm_bindgen::from_value(value).map_err(|err| err.to_string())
+}
+
+pub async fn fetch_capture_quality() -> Result<Option<CaptureQualityDto>, String> {
+    let value = invoke_no_args("capture_quality").await?;
+    if value.is_null() || value.is_undefined() {
+        return Ok(None);
+    }
+    serde_wasm_bindgen::from_value(value).map_err(|err| err.to_string())
+}
+
+pub async fn fetch_audio_health() -> Result<Option<AudioHealthDto>, String> {
+    let value = invoke_no_args("audio_health").await?;
+    if value.is_null() || value.is_undefined() {
+        return Ok(None);
+    }
+    serde_wasm_bindgen::from_value(value).map_err(|err| err.to_string())
+}
+
+/// Tells the backend this window applies windowed drafts.
+pub async fn negotiate_patch_protocol() -> Result<PatchProtocolDto, String> {
+    let args = serde_wasm_bindgen::to_value(&NegotiatePatchProtocolArgs {
+        windowed_drafts: true,
+    })
+    .map_err(|err| err.to_string())?;
+    let value = invoke("negotiate_patch_protocol", args)
+        .await
+        .map_err(extract_error)?;
+    serde_wasm_bindgen::from_value(value).map_err(|err| err.to_string())
+}
+
+pub async fn fetch_is_recording() -> Result<bool, String> {
+    let value = invoke_no_args("is_recording").await?;
+    Ok(value.as_bool().unwrap_or(false))
+}
+
+pub async fn subscribe_vad_events() -> Result<(), String> {
+    invoke_no_args("subscribe_vad_events").await.map(|_| ())
+}
+
+pub async fn retry_model_download_cmd() -> Result<(), String> {
+    invoke_no_args("retry_model_download").await.map(|_| ())
+}
+
+pub async fn skip_model_load_cmd() -> Result<(), String> {
+    invoke_no_args("skip_model_load").await.map(|_| ())
+}
+
+pub async fn pause_download_cmd() -> Result<(), String> {
+    invoke_no_args("pause_download").await.map(|_| ())
+}
+
+pub async fn resume_download_cmd() -> Result<(), String> {
+    invoke_no_args("resume_download").await.map(|_| ())
+}
+
+pub async fn cancel_download_cmd() -> Result<(), String> {
+    invoke_no_args("cancel_download").await.map(|_| ())
+}
+
+pub async fn start_recording_cmd() -> Result<(), String> {
+    invoke_no_args("start_recording").await.map(|_| ())
+}
+
+pub async fn stop_recording_cmd() -> Result<(), String> {
+    invoke_no_args("stop_recording").await.map(|_| ())
+}
+
+pub async fn fetch_current_shortcut() -> Result<Option<String>, String> {
+    let value = invoke_no_args("get_record_shortcut").await?;
+    Ok(value.as_string())
+}
+
+pub async fn fetch_model_path() -> Result<String, String> {
+    let value = invoke_no_args("get_model_path").await?;
+    value
+        .as_string()
+        .ok_or_else(|| "Invalid response format".to_string())
+}
+
+pub async fn fetch_default_shortcut() -> Result<Option<String>, String> {
+    let value = invoke_no_args("default_record_shortcut").await?;
+    Ok(value.as_string())
+}
+
+pub async fn save_model_path(path: String) -> Result<(), String> {
+    let args =
+        serde_wasm_bindgen::to_value(&SetModelPathArgs { path }).map_err(|err| err.to_string())?;
+    invoke("set_model_path", args)
+        .await
+        .map(|_| ())
+        .map_err(extract_error)
+}
+
+pub async fn pick_model_folder_cmd() -> Result<Option<String>, String> {
+    let value = invoke_no_args("pick_model_folder").await?;
+    if value.is_null() {
+        return Ok(None);
+    }
+    value
+        .as_string()
+        .ok_or_else(|| "Invalid response".to_string())
+        .map(Some)
+}
+
+pub async fn pick_model_archive_cmd() -> Result<Option<String>, String> {
+    let value = invoke_no_args("pick_model_archive").await?;
+    if value.is_null() {
+        return Ok(None);
+    }
+    value
+        .as_string()
+        .ok_or_else(|| "Invalid response".to_string())
+        .map(Some)
+}
+
+pub async fn import_model_archive(path: String) -> Result<(), String> {
+    let args =
+        serde_wasm_bindgen::to_value(&SetModelPathArgs { path }).map_err(|err| err.to_string())?;
+    invoke("import_model_archive", args)
+        .await
+        .map(|_| ())
+        .map_err(extract_error)
+}
+
+pub async fn pick_audio_file_cmd() -> Result<Option<String>, String> {
+    let value = invoke_no_args("pick_audio_file").await?;
+    if value.is_null() {
+        return Ok(None);
+    }
+    value
+        .as_string()
+        .ok_or_else(|| "Invalid response".to_string())
+        .map(Some)
+}
+
+pub async fn transcribe_audio_file(path: String) -> Result<String, String> {
+    let args =
+        serde_wasm_bindgen::to_value(&SetModelPathArgs { path }).map_err(|err| err.to_string())?;
+    let value = invoke("transcribe_audio_file", args)
+        .await
+        .map_err(extract_error)?;
+    serde_wasm_bindgen::from_value(value).map_err(|err| err.to_string())
+}
+
+pub async fn cancel_file_transcription_cmd() -> Result<(), String> {
+    invoke_no_args("cancel_file_transcription")
+        .await
+        .map(|_| ())
+}
+
+pub async fn reset_settings_cmd() -> Result<(), String> {
+    invoke_no_args("reset_settings").await.map(|_| ())
+}
+
+pub async fn save_shortcut(shortcut: &str) -> Result<String, String> {
+    let args = serde_wasm_bindgen::to_value(&UpdateShortcutArgs { shortcut })
+        .map_err(|err| err.to_string())?;
+
+    let value = invoke("update_record_shortcut", args)
+        .await
+        .map_err(extract_error)?;
+
+    value
+        .as_string()
+        .ok_or_else(|| "Shortcut saved, but response was empty".to_string())
+}
+
+pub async fn fetch_shortcut_conflict() -> Result<Option<ShortcutConflictDto>, String> {
+    let value = invoke_no_args("get_shortcut_conflict").await?;
+    serde_wasm_bindgen::from_value(value).map_err(|err| err.to_string())
+}
+
+pub async fn begin_shortcut_capture_cmd() -> Result<Option<String>, String> {
+    let value = invoke_no_args("begin_shortcut_capture").await?;
+    serde_wasm_bindgen::from_value(value).map_err(|err| err.to_string())
+}
+
+pub async fn fetch_streaming_enabled() -> Result<bool, String> {
+    let value = invoke_no_args("get_use_streaming").await?;
+    Ok(value.as_bool().unwrap_or(false))
+}
+
+pub async fn save_streaming_enabled(enabled: bool) -> Result<(), String> {
+    let args =
+        serde_wasm_bindgen::to_value(&SetEnabledArgs { enabled }).map_err(|err| err.to_string())?;
+    invoke("set_use_streaming", args)
+        .await
+        .map(|_| ())
+        .map_err(extract_error)
+}
+
+pub async fn fetch_streaming_strategy() -> Result<String, String> {
+    let value = invoke_no_args("get_streaming_strategy").await?;
+    value
+        .as_string()
+        .ok_or_else(|| "Streaming strategy response was invalid".to_string())
+}
+
+pub async fn save_streaming_strategy(strategy: String) -> Result<(), String> {
+    let args = serde_wasm_bindgen::to_value(&SetStreamingStrategyArgs { strategy })
+        .map_err(|err| err.to_string())?;
+    invoke("set_streaming_strategy", args)
+        .await
+        .map(|_| ())
+        .map_err(extract_error)
+}
+
+pub async fn fetch_resampler_quality() -> Result<String, String> {
+    let value = invoke_no_args("get_resampler_quality").await?;
+    value
+        .as_string()
+        .ok_or_else(|| "Resampler quality response was invalid".to_string())
+}
+
+pub async fn save_resampler_quality(quality: String) -> Result<(), String> {
+    let args = serde_wasm_bindgen::to_value(&SetResamplerQualityArgs { quality })
+        .map_err(|err| err.to_string())?;
+    invoke("set_resampler_quality", args)
+        .await
+        .map(|_| ())
+        .map_err(extract_error)
+}
+
+pub async fn list_input_devices() -> Result<Vec<String>, String> {
+    let value = invoke_no_args("list_input_devices").await?;
+    serde_wasm_bindgen::from_value(value).map_err(|err| err.to_string())
+}
+
+pub async fn fetch_input_device() -> Result<InputDeviceDto, String> {
+    let value = invoke_no_args("get_input_device").await?;
+    serde_wasm_bindgen::from_value(value).map_err(|err| err.to_string())
+}
+
+pub async fn save_input_device(input_device: InputDeviceDto) -> Result<(), String> {
+    let args = serde_wasm_bindgen::to_value(&SetInputDeviceArgs { input_device })
+        .map_err(|err| err.to_string())?;
+    invoke("set_input_device", args)
+        .await
+        .map(|_| ())
+        .map_err(extract_error)
+}
+
+pub async fn fetch_vad_settings() -> Result<VadSettingsDto, String> {
+    let value = invoke_no_args("get_vad_settings").await?;
+    serde_wasm_bindgen::from_value(value).map_err(|err| err.to_string())
+}
+
+pub async fn save_vad_settings(vad: VadSettingsDto) -> Result<(), String> {
+    let args =
+        serde_wasm_bindgen::to_value(&SetVadSettingsArgs { vad }).map_err(|err| err.to_string())?;
+    invoke("set_vad_settings", args)
+        .await
+        .map(|_| ())
+        .map_err(extract_error)
+}
+
+pub async fn calibrate_microphone() -> Result<NoiseCalibrationDto, String> {
+    let value = invoke_no_args("calibrate_microphone").await?;
+    serde_wasm_bindgen::from_value(value).map_err(|err| err.to_string())
+}
+
+pub async fn test_microphone_cmd() -> Result<MicrophoneTestDto, String> {
+    let value = invoke_no_args("test_microphone").await?;
+    serde_wasm_bindgen::from_value(value).map_err(|err| err.to_string())
+}
+
+pub async fn fetch_onboarding() -> Result<OnboardingDto, String> {
+    let value = invoke_no_args("get_onboarding").await?;
+    serde_wasm_bindgen::from_value(value).map_err(|err| err.to_string())
+}
+
+pub async fn check_onboarding_microphone_cmd() -> Result<(), String> {
+    invoke_no_args("check_onboarding_microphone")
+        .await
+        .map(|_| ())
+}
+
+pub async fn test_onboarding_microphone_cmd() -> Result<Option<MicrophoneTestDto>, String> {
+    let value = invoke_no_args("test_onboarding_microphone").await?;
+    serde_wasm_bindgen::from_value(value).map_err(|err| err.to_string())
+}
+
+pub async fn confirm_onboarding_shortcut_cmd() -> Result<(), String> {
+    invoke_no_args("confirm_onboarding_shortcut")
+        .await
+        .map(|_| ())
+}
+
+pub async fn skip_onboarding_step_cmd() -> Result<(), String> {
+    invoke_no_args("skip_onboarding_step").await.map(|_| ())
+}
+
+pub async fn finish_onboarding_cmd() -> Result<(), String> {
+    invoke_no_args("finish_onboarding").await.map(|_| ())
+}
+
+pub async fn restart_onboarding_cmd() -> Result<OnboardingDto, String> {
+    let value = invoke_no_args("restart_onboarding").await?;
+    serde_wasm_bindgen::from_value(value).map_err(|err| err.to_string())
+}
+
+pub async fn play_microphone_test_cmd() -> Result<(), String> {
+    invoke_no_args("play_microphone_test").await.map(|_| ())
+}
+
+pub async fn fetch_noise_suppression() -> Result<NoiseSuppressionDto, String> {
+    let value = invoke_no_args("get_noise_suppression").await?;
+    serde_wasm_bindgen::from_value(value).map_err(|err| err.to_string())
+}
+
+pub async fn save_noise_selection(
+    selection: NoiseSelectionDto,
+) -> Result<NoiseSuppressionDto, String> {
+    let args = serde_wasm_bindgen::to_value(&SetNoiseSelectionArgs { selection })
+        .map_err(|err| err.to_string())?;
+    let value = invoke("set_noise_selection", args)
+        .await
+        .map_err(extract_error)?;
+    serde_wasm_bindgen::from_value(value).map_err(|err| err.to_string())
+}
+
+async fn invoke_with_noise_profile_name(
+    cmd: &str,
+    name: String,
+) -> Result<NoiseSuppressionDto, String> {
+    let args =
+        serde_wasm_bindgen::to_value(&ProfileNameArgs { name }).map_err(|err| err.to_string())?;
+    let value = invoke(cmd, args).await.map_err(extract_error)?;
+    serde_wasm_bindgen::from_value(value).map_err(|err| err.to_string())
+}
+
+pub async fn capture_noise_profile(name: String) -> Result<NoiseSuppressionDto, String> {
+    invoke_with_noise_profile_name("capture_noise_profile", name).await
+}
+
+pub async fn delete_noise_profile(name: String) -> Result<NoiseSuppressionDto, String> {
+    invoke_with_noise_profile_name("delete_noise_profile", name).await
+}
+
+pub async fn fetch_speaker_adaptation() -> Result<SpeakerAdaptationDto, String> {
+    let value = invoke_no_args("get_speaker_adaptation").await?;
+    serde_wasm_bindgen::from_value(value).map_err(|err| err.to_string())
+}
+
+pub async fn save_speaker_adaptation(enabled: bool) -> Result<SpeakerAdaptationDto, String> {
+    let args =
+        serde_wasm_bindgen::to_value(&SetEnabledArgs { enabled }).map_err(|err| err.to_string())?;
+    let value = invoke("set_speaker_adaptation", args)
+        .await
+        .map_err(extract_error)?;
+    serde_wasm_bindgen::from_value(value).map_err(|err| err.to_string())
+}
+
+pub async fn reset_voice_profile() -> Result<SpeakerAdaptationDto, String> {
+    let value = invoke_no_args("reset_voice_profile").await?;
+    serde_wasm_bindgen::from_value(value).map_err(|err| err.to_string())
+}
+
+pub async fn fetch_shortcut_gesture() -> Result<ShortcutGestureDto, String> {
+    let value = invoke_no_args("get_shortcut_gesture").await?;
+    serde_wasm_bindgen::from_value(value).map_err(|err| err.to_string())
+}
+
+pub async fn save_shortcut_gesture(gesture: ShortcutGestureDto) -> Result<(), String> {
+    let args = serde_wasm_bindgen::to_value(&SetShortcutGestureArgs { gesture })
+        .map_err(|err| err.to_string())?;
+    invoke("set_shortcut_gesture", args)
+        .await
+        .map(|_| ())
+        .map_err(extract_error)
+}
+
+pub async fn fetch_triggers() -> Result<TriggerSettingsDto, String> {
+    let value = invoke_no_args("get_triggers").await?;
+    serde_wasm_bindgen::from_value(value).map_err(|err| err.to_string())
+}
+
+pub async fn save_triggers(triggers: TriggerSettingsDto) -> Result<(), String> {
+    let args = serde_wasm_bindgen::to_value(&SetTriggersArgs { triggers })
+        .map_err(|err| err.to_string())?;
+    invoke("set_triggers", args)
+        .await
+        .map(|_| ())
+        .map_err(extract_error)
+}
+
+pub async fn fetch_suppress_record_key() -> Result<bool, String> {
+    let value = invoke_no_args("get_suppress_record_key").await?;
+    Ok(value.as_bool().unwrap_or(false))
+}
+
+pub async fn save_suppress_record_key(enabled: bool) -> Result<(), String> {
+    let args =
+        serde_wasm_bindgen::to_value(&SetEnabledArgs { enabled }).map_err(|err| err.to_string())?;
+    invoke("set_suppress_record_key", args)
+        .await
+        .map(|_| ())
+        .map_err(extract_error)
+}
+
+pub async fn detect_mouse_button() -> Result<u8, String> {
+    let value = invoke_no_args("detect_mouse_button").await?;
+    serde_wasm_bindgen::from_value(value).map_err(|err| err.to_string())
+}
+
+pub async fn list_hid_devices() -> Result<Vec<HidDeviceDto>, String> {
+    let value = invoke_no_args("list_hid_devices").await?;
+    serde_wasm_bindgen::from_value(value).map_err(|err| err.to_string())
+}
+
+pub async fn fetch_live_waveform() -> Result<bool, String> {
+    let value = invoke_no_args("get_live_waveform").await?;
+    Ok(value.as_bool().unwrap_or(false))
+}
+
+pub async fn save_live_waveform(enabled: bool) -> Result<(), String> {
+    let args =
+        serde_wasm_bindgen::to_value(&SetEnabledArgs { enabled }).map_err(|err| err.to_string())?;
+    invoke("set_live_waveform", args)
+        .await
+        .map(|_| ())
+        .map_err(extract_error)
+}
+
+pub async fn fetch_caret_spacing() -> Result<bool, String> {
+    let value = invoke_no_args("get_caret_spacing").await?;
+    Ok(value.as_bool().unwrap_or(false))
+}
+
+pub async fn save_caret_spacing(enabled: bool) -> Result<(), String> {
+    let args =
+        serde_wasm_bindgen::to_value(&SetEnabledArgs { enabled }).map_err(|err| err.to_string())?;
+    invoke("set_caret_spacing", args)
+        .await
+        .map(|_| ())
+        .map_err(extract_error)
+}
+
+pub async fn fetch_continuation() -> Result<ContinuationDto, String> {
+    let value = invoke_no_args("get_continuation").await?;
+    serde_wasm_bindgen::from_value(value).map_err(|err| err.to_string())
+}
+
+pub async fn save_continuation(continuation: ContinuationDto) -> Result<(), String> {
+    let args = serde_wasm_bindgen::to_value(&SetContinuationArgs { continuation })
+        .map_err(|err| err.to_string())?;
+    invoke("set_continuation", args)
+        .await
+        .map(|_| ())
+        .map_err(extract_error)
+}
+
+pub async fn fetch_inactivity() -> Result<InactivityDto, String> {
+    let value = invoke_no_args("get_inactivity").await?;
+    serde_wasm_bindgen::from_value(value).map_err(|err| err.to_string())
+}
+
+pub async fn save_inactivity(inactivity: InactivityDto) -> Result<(), String> {
+    let args = serde_wasm_bindgen::to_value(&SetInactivityArgs { inactivity })
+        .map_err(|err| err.to_string())?;
+    invoke("set_inactivity", args)
+        .await
+        .map(|_| ())
+        .map_err(extract_error)
+}
+
+pub async fn keep_recording() -> Result<bool, String> {
+    let value = invoke_no_args("keep_recording").await?;
+    Ok(value.as_bool().unwrap_or(false))
+}
+
+pub async fn fetch_encrypt_at_rest() -> Result<bool, String> {
+    let value = invoke_no_args("get_encrypt_at_rest").await?;
+    Ok(value.as_bool().unwrap_or(false))
+}
+
+pub async fn save_encrypt_at_rest(enabled: bool) -> Result<(), String> {
+    let args =
+        serde_wasm_bindgen::to_value(&SetEnabledArgs { enabled }).map_err(|err| err.to_string())?;
+    invoke("set_encrypt_at_rest", args)
+        .await
+        .map(|_| ())
+        .map_err(extract_error)
+}
+
+pub async fn fetch_deep_links_enabled() -> Result<bool, String> {
+    let value = invoke_no_args("get_deep_links_enabled").await?;
+    Ok(value.as_bool().unwrap_or(false))
+}
+
+pub async fn save_deep_links_enabled(enabled: bool) -> Result<(), String> {
+    let args =
+        serde_wasm_bindgen::to_value(&SetEnabledArgs { enabled }).map_err(|err| err.to_string())?;
+    invoke("set_deep_links_enabled", args)
+        .await
+        .map(|_| ())
+        .map_err(extract_error)
+}
+
+pub async fn fetch_announce_transcripts() -> Result<bool, String> {
+    let value = invoke_no_args("get_announce_transcripts").await?;
+    Ok(value.as_bool().unwrap_or(false))
+}
+
+pub async fn save_announce_transcripts(enabled: bool) -> Result<(), String> {
+    let args =
+        serde_wasm_bindgen::to_value(&SetEnabledArgs { enabled }).map_err(|err| err.to_string())?;
+    invoke("set_announce_transcripts", args)
+        .await
+        .map(|_| ())
+        .map_err(extract_error)
+}
+
+pub async fn fetch_speak_transcripts() -> Result<bool, String> {
+    let value = invoke_no_args("get_speak_transcripts").await?;
+    Ok(value.as_bool().unwrap_or(false))
+}
+
+pub async fn save_speak_transcripts(enabled: bool) -> Result<(), String> {
+    let args =
+        serde_wasm_bindgen::to_value(&SetEnabledArgs { enabled }).map_err(|err| err.to_string())?;
+    invoke("set_speak_transcripts", args)
+        .await
+        .map(|_| ())
+        .map_err(extract_error)
+}
+
+pub async fn fetch_numbers_mode() -> Result<bool, String> {
+    let value = invoke_no_args("get_numbers_mode").await?;
+    Ok(value.as_bool().unwrap_or(false))
+}
+
+pub async fn save_numbers_mode(enabled: bool) -> Result<(), String> {
+    let args =
+        serde_wasm_bindgen::to_value(&SetEnabledArgs { enabled }).map_err(|err| err.to_string())?;
+    invoke("set_numbers_mode", args)
+        .await
+        .map(|_| ())
+        .map_err(extract_error)
+}
+
+pub async fn fetch_buffer_until_model_ready() -> Result<bool, String> {
+    let value = invoke_no_args("get_buffer_until_model_ready").await?;
+    Ok(value.as_bool().unwrap_or(false))
+}
+
+pub async fn save_buffer_until_model_ready(enabled: bool) -> Result<(), String> {
+    let args =
+        serde_wasm_bindgen::to_value(&SetEnabledArgs { enabled }).map_err(|err| err.to_string())?;
+    invoke("set_buffer_until_model_ready", args)
+        .await
+        .map(|_| ())
+        .map_err(extract_error)
+}
+
+pub async fn fetch_hardware_detection() -> Result<Option<HardwareDetectionDto>, String> {
+    let value = invoke_no_args("get_hardware_detection").await?;
+    if value.is_null() || value.is_undefined() {
+        return Ok(None);
+    }
+    serde_wasm_bindgen::from_value(value).map_err(|err| err.to_string())
+}
+
+pub async fn detect_hardware_cmd() -> Result<HardwareDetectionDto, String> {
+    let value = invoke_no_args("detect_hardware").await?;
+    serde_wasm_bindgen::from_value(value).map_err(|err| err.to_string())
+}
+
+pub async fn fetch_ort_threads() -> Result<OrtThreadsDto, String> {
+    let value = invoke_no_args("get_ort_threads").await?;
+    serde_wasm_bindgen::from_value(value).map_err(|err| err.to_string())
+}
+
+pub async fn save_ort_threads(threads: OrtThreadsDto) -> Result<(), String> {
+    let args = serde_wasm_bindgen::to_value(&SetOrtThreadsArgs { threads })
+        .map_err(|err| err.to_string())?;
+    invoke("set_ort_threads", args)
+        .await
+        .map(|_| ())
+        .map_err(extract_error)
+}
+
+pub async fn tune_ort_threads_cmd() -> Result<ThreadTuningDto, String> {
+    let value = invoke_no_args("tune_ort_threads").await?;
+    serde_wasm_bindgen::from_value(value).map_err(|err| err.to_string())
+}
+
+pub async fn fetch_model_signature_report() -> Result<String, String> {
+    let value = invoke_no_args("model_signature_report").await?;
+    value
+        .as_string()
+        .ok_or_else(|| "Model report response was invalid".to_string())
+}
+
+pub async fn fetch_model_prewarm() -> Result<String, String> {
+    let value = invoke_no_args("get_model_prewarm").await?;
+    value
+        .as_string()
+        .ok_or_else(|| "Model prewarm response was invalid".to_string())
+}
+
+pub async fn save_model_prewarm(prewarm: String) -> Result<(), String> {
+    let args = serde_wasm_bindgen::to_value(&SetModelPrewarmArgs { prewarm })
+        .map_err(|err| err.to_string())?;
+    invoke("set_model_prewarm", args)
+        .await
+        .map(|_| ())
+        .map_err(extract_error)
+}
+
+pub async fn fetch_power_saver() -> Result<String, String> {
+    let value = invoke_no_args("get_power_saver").await?;
+    value
+        .as_string()
+        .ok_or_else(|| "Battery saver response was invalid".to_string())
+}
+
+pub async fn save_power_saver(mode: String) -> Result<(), String> {
+    let args =
+        serde_wasm_bindgen::to_value(&SetPowerSaverArgs { mode }).map_err(|err| err.to_string())?;
+    invoke("set_power_saver", args)
+        .await
+        .map(|_| ())
+        .map_err(extract_error)
+}
+
+pub async fn fetch_recording_indicator() -> Result<String, String> {
+    let value = invoke_no_args("get_recording_indicator").await?;
+    value
+        .as_string()
+        .ok_or_else(|| "Recording indicator response was invalid".to_string())
+}
+
+pub async fn save_recording_indicator(indicator: String) -> Result<(), String> {
+    let args = serde_wasm_bindgen::to_value(&SetRecordingIndicatorArgs { indicator })
+        .map_err(|err| err.to_string())?;
+    invoke("set_recording_indicator", args)
+        .await
+        .map(|_| ())
+        .map_err(extract_error)
+}
+
+pub async fn fetch_dictation_enabled() -> Result<bool, String> {
+    let value = invoke_no_args("get_dictation_enabled").await?;
+    Ok(value.as_bool().unwrap_or(true))
+}
+
+pub async fn save_dictation_enabled(enabled: bool) -> Result<(), String> {
+    let args =
+        serde_wasm_bindgen::to_value(&SetEnabledArgs { enabled }).map_err(|err| err.to_string())?;
+    invoke("set_dictation_enabled", args)
+        .await
+        .map(|_| ())
+        .map_err(extract_error)
+}
+
+pub async fn fetch_dictation_toggle_shortcut() -> Result<bool, String> {
+    let value = invoke_no_args("get_dictation_toggle_shortcut").await?;
+    Ok(value.as_bool().unwrap_or(false))
+}
+
+pub async fn save_dictation_toggle_shortcut(enabled: bool) -> Result<(), String> {
+    let args =
+        serde_wasm_bindgen::to_value(&SetEnabledArgs { enabled }).map_err(|err| err.to_string())?;
+    invoke("set_dictation_toggle_shortcut", args)
+        .await
+        .map(|_| ())
+        .map_err(extract_error)
+}
+
+pub async fn fetch_quiet_hours() -> Result<QuietHoursDto, String> {
+    let value = invoke_no_args("get_quiet_hours").await?;
+    serde_wasm_bindgen::from_value(value).map_err(|err| err.to_string())
+}
+
+pub async fn save_quiet_hours(quiet_hours: QuietHoursDto) -> Result<(), String> {
+    let args = serde_wasm_bindgen::to_value(&SetQuietHoursArgs { quiet_hours })
+        .map_err(|err| err.to_string())?;
+    invoke("set_quiet_hours", args)
+        .await
+        .map(|_| ())
+        .map_err(extract_error)
+}
+
+pub async fn fetch_webhook() -> Result<WebhookDto, String> {
+    let value = invoke_no_args("get_webhook").await?;
+    serde_wasm_bindgen::from_value(value).map_err(|err| err.to_string())
+}
+
+pub async fn save_webhook(webhook: WebhookDto) -> Result<(), String> {
+    let args =
+        serde_wasm_bindgen::to_value(&SetWebhookArgs { webhook }).map_err(|err| err.to_string())?;
+    invoke("set_webhook", args)
+        .await
+        .map(|_| ())
+        .map_err(extract_error)
+}
+
+pub async fn fetch_llm_handoff() -> Result<LlmHandoffDto, String> {
+    let value = invoke_no_args("get_llm_handoff").await?;
+    serde_wasm_bindgen::from_value(value).map_err(|err| err.to_string())
+}
+
+pub async fn save_llm_handoff(llm_handoff: LlmHandoffDto) -> Result<(), String> {
+    let args = serde_wasm_bindgen::to_value(&SetLlmHandoffArgs { llm_handoff })
+        .map_err(|err| err.to_string())?;
+    invoke("set_llm_handoff", args)
+        .await
+        .map(|_| ())
+        .map_err(extract_error)
+}
+
+pub async fn list_post_process_stages() -> Result<Vec<PostProcessStageDto>, String> {
+    let value = invoke_no_args("list_post_process_stages").await?;
+    serde_wasm_bindgen::from_value(value).map_err(|err| err.to_string())
+}
+
+pub async fn fetch_post_processing() -> Result<PostProcessingDto, String> {
+    let value = invoke_no_args("get_post_processing").await?;
+    serde_wasm_bindgen::from_value(value).map_err(|err| err.to_string())
+}
+
+pub async fn save_post_processing(post_processing: PostProcessingDto) -> Result<(), String> {
+    let args = serde_wasm_bindgen::to_value(&SetPostProcessingArgs { post_processing })
+        .map_err(|err| err.to_string())?;
+    invoke("set_post_processing", args)
+        .await
+        .map(|_| ())
+        .map_err(extract_error)
+}
+
+pub async fn fetch_output_templates() -> Result<OutputTemplatesDto, String> {
+    let value = invoke_no_args("get_output_templates").await?;
+    serde_wasm_bindgen::from_value(value).map_err(|err| err.to_string())
+}
+
+pub async fn save_output_templates(output_templates: OutputTemplatesDto) -> Result<(), String> {
+    let args = serde_wasm_bindgen::to_value(&SetOutputTemplatesArgs { output_templates })
+        .map_err(|err| err.to_string())?;
+    invoke("set_output_templates", args)
+        .await
+        .map(|_| ())
+        .map_err(extract_error)
+}
+
+pub async fn fetch_capture_file() -> Result<CaptureFileDto, String> {
+    let value = invoke_no_args("get_capture_file").await?;
+    serde_wasm_bindgen::from_value(value).map_err(|err| err.to_string())
+}
+
+pub async fn save_capture_file(capture_file: CaptureFileDto) -> Result<(), String> {
+    let args = serde_wasm_bindgen::to_value(&SetCaptureFileArgs { capture_file })
+        .map_err(|err| err.to_string())?;
+    invoke("set_capture_file", args)
+        .await
+        .map(|_| ())
+        .map_err(extract_error)
+}
+
+pub async fn fetch_network_input() -> Result<NetworkInputDto, String> {
+    let value = invoke_no_args("get_network_input").await?;
+    serde_wasm_bindgen::from_value(value).map_err(|err| err.to_string())
+}
+
+pub async fn save_network_input(network_input: NetworkInputDto) -> Result<(), String> {
+    let args = serde_wasm_bindgen::to_value(&SetNetworkInputArgs { network_input })
+        .map_err(|err| err.to_string())?;
+    invoke("set_network_input", args)
+        .await
+        .map(|_| ())
+        .map_err(extract_error)
+}
+
+pub async fn fetch_typed_metadata() -> Result<TypedMetadataDto, String> {
+    let value = invoke_no_args("get_typed_metadata").await?;
+    serde_wasm_bindgen::from_value(value).map_err(|err| err.to_string())
+}
+
+pub async fn save_typed_metadata(typed_metadata: TypedMetadataDto) -> Result<(), String> {
+    let args = serde_wasm_bindgen::to_value(&SetTypedMetadataArgs { typed_metadata })
+        .map_err(|err| err.to_string())?;
+    invoke("set_typed_metadata", args)
+        .await
+        .map(|_| ())
+        .map_err(extract_error)
+}
+
+pub async fn fetch_recent_logs(level: String, limit: usize) -> Result<Vec<LogEntryDto>, String> {
+    let args = serde_wasm_bindgen::to_value(&RecentLogsArgs { level, limit })
+        .map_err(|err| err.to_string())?;
+    let value = invoke("get_recent_logs", args)
+        .await
+        .map_err(extract_error)?;
+    serde_wasm_bindgen::from_value(value).map_err(|err| err.to_string())
+}
+
+pub async fn copy_to_clipboard(text: &str) -> Result<(), String> {
+    write_clipboard_text(text)
+        .await
+        .map(|_| ())
+        .map_err(extract_error)
+}
+
+pub async fn fetch_settings_lock() -> Result<SettingsLockDto, String> {
+    let value = invoke_no_args("get_settings_lock").await?;
+    serde_wasm_bindgen::from_value(value).map_err(|err| err.to_string())
+}
+
+async fn invoke_with_passphrase(cmd: &str, passphrase: String) -> Result<(), String> {
+    let args = serde_wasm_bindgen::to_value(&PassphraseArgs { passphrase })
+        .map_err(|err| err.to_string())?;
+    invoke(cmd, args).await.map(|_| ()).map_err(extract_error)
+}
+
+pub async fn save_settings_lock(passphrase: String) -> Result<(), String> {
+    invoke_with_passphrase("set_settings_lock", passphrase).await
+}
+
+pub async fn remove_settings_lock(passphrase: String) -> Result<(), String> {
+    invoke_with_passphrase("remove_settings_lock", passphrase).await
+}
+
+pub async fn unlock_settings(passphrase: String) -> Result<(), String> {
+    invoke_with_passphrase("unlock_settings", passphrase).await
+}
+
+pub async fn lock_settings() -> Result<(), String> {
+    invoke_no_args("lock_settings").await.map(|_| ())
+}
+
+pub async fn fetch_autostart() -> Result<AutostartDto, String> {
+    let value = invoke_no_args("get_autostart").await?;
+    serde_wasm_bindgen::from_value(value).map_err(|err| err.to_string())
+}
+
+pub async fn save_autostart(autostart: AutostartDto) -> Result<(), String> {
+    let args = serde_wasm_bindgen::to_value(&SetAutostartArgs { autostart })
+        .map_err(|err| err.to_string())?;
+    invoke("set_autostart", args)
+        .await
+        .map(|_| ())
+        .map_err(extract_error)
+}
+
+pub async fn pick_config_export_file_cmd() -> Result<Option<String>, String> {
+    let value = invoke_no_args("pick_config_export_file").await?;
+    Ok(value.as_string())
+}
+
+pub async fn pick_config_import_file_cmd() -> Result<Option<String>, String> {
+    let value = invoke_no_args("pick_config_import_file").await?;
+    Ok(value.as_string())
+}
+
+pub async fn export_config(path: String) -> Result<(), String> {
+    let args =
+        serde_wasm_bindgen::to_value(&SetModelPathArgs { path }).map_err(|err| err.to_string())?;
+    invoke("export_config", args)
+        .await
+        .map(|_| ())
+        .map_err(extract_error)
+}
+
+pub async fn import_config(path: String) -> Result<(), String> {
+    let args =
+        serde_wasm_bindgen::to_value(&SetModelPathArgs { path }).map_err(|err| err.to_string())?;
+    invoke("import_config", args)
+        .await
+        .map(|_| ())
+        .map_err(extract_error)
+}
+
+pub async fn fetch_profiles() -> Result<ProfileListDto, String> {
+    let value = invoke_no_args("get_profiles").await?;
+    serde_wasm_bindgen::from_value(value).map_err(|err| err.to_string())
+}
+
+async fn invoke_with_profile_name(cmd: &str, name: String) -> Result<(), String> {
+    let args =
+        serde_wasm_bindgen::to_value(&ProfileNameArgs { name }).map_err(|err| err.to_string())?;
+    invoke(cmd, args).await.map(|_| ()).map_err(extract_error)
+}
+
+pub async fn create_profile(name: String) -> Result<(), String> {
+    invoke_with_profile_name("create_profile", name).await
+}
+
+pub async fn delete_profile(name: String) -> Result<(), String> {
+    invoke_with_profile_name("delete_profile", name).await
+}
+
+pub async fn switch_profile(name: String) -> Result<(), String> {
+    invoke_with_profile_name("switch_profile", name).await
+}
+
+pub async fn fetch_companion() -> Result<CompanionDto, String> {
+    let value = invoke_no_args("get_companion").await?;
+    serde_wasm_bindgen::from_value(value).map_err(|err| err.to_string())
+}
+
+pub async fn save_companion(enabled: bool, port: u16) -> Result<(), String> {
+    let args = serde_wasm_bindgen::to_value(&SetCompanionArgs { enabled, port })
+        .map_err(|err| err.to_string())?;
+    invoke("set_companion", args)
+        .await
+        .map(|_| ())
+        .map_err(extract_error)
+}
+
+pub async fn start_companion_pairing() -> Result<PairingInfoDto, String> {
+    let value = invoke_no_args("start_companion_pairing").await?;
+    serde_wasm_bindgen::from_value(value).map_err(|err| err.to_string())
+}
+
+pub async fn revoke_companion_device(device_id: String) -> Result<(), String> {
+    let args = serde_wasm_bindgen::to_value(&RevokeCompanionDeviceArgs { device_id })
+        .map_err(|err| err.to_string())?;
+    invoke("revoke_companion_device", args)
+        .await
+        .map(|_| ())
+        .map_err(extract_error)
+}
+
+pub async fn pick_capture_file_cmd() -> Result<Option<String>, String> {
+    let value = invoke_no_args("pick_capture_file").await?;
+    if value.is_null() {
+        return Ok(None);
+    }
+    value
+        .as_string()
+        .ok_or_else(|| "Invalid response".to_string())
+        .map(Some)
+}
+
+pub async fn pick_digest_folder_cmd() -> Result<Option<String>, String> {
+    let value = invoke_no_args("pick_digest_folder").await?;
+    if value.is_null() {
+        return Ok(None);
+    }
+    value
+        .as_string()
+        .ok_or_else(|| "Invalid response".to_string())
+        .map(Some)
+}
+
+pub async fn open_document_cmd() -> Result<(), String> {
+    invoke_no_args("open_document").await.map(|_| ())
+}
+
+pub async fn fetch_document() -> Result<Option<String>, String> {
+    let value = invoke_no_args("get_document").await?;
+    serde_wasm_bindgen::from_value(value).map_err(|err| err.to_string())
+}
+
+pub async fn save_document_text(text: String) -> Result<(), String> {
+    let args = serde_wasm_bindgen::to_value(&SetDocumentTextArgs { text })
+        .map_err(|err| err.to_string())?;
+    invoke("set_document_text", args)
+        .await
+        .map(|_| ())
+        .map_err(extract_error)
+}
+
+pub async fn pick_document_file_cmd() -> Result<Option<String>, String> {
+    let value = invoke_no_args("pick_document_file").await?;
+    if value.is_null() {
+        return Ok(None);
+    }
+    value
+        .as_string()
+        .ok_or_else(|| "Invalid response".to_string())
+        .map(Some)
+}
+
+pub async fn finish_document(finish: DocumentFinishDto) -> Result<(), String> {
+    let args = serde_wasm_bindgen::to_value(&FinishDocumentArgs { finish })
+        .map_err(|err| err.to_string())?;
+    invoke("finish_document", args)
+        .await
+        .map(|_| ())
+        .map_err(extract_error)
+}
+
+pub async fn export_digest(
+    from: String,
+    to: String,
+    notes_dir: Option<String>,
+) -> Result<DigestExportDto, String> {
+    let args = serde_wasm_bindgen::to_value(&ExportDigestArgs {
+        from,
+        to,
+        notes_dir,
+    })
+    .map_err(|err| err.to_string())?;
+    let value = invoke("export_digest", args).await.map_err(extract_error)?;
+    serde_wasm_bindgen::from_value(value).map_err(|err| err.to_string())
+}
+
+pub async fn fetch_history_entries() -> Result<Vec<HistoryEntryDto>, String> {
+    let value = invoke_no_args("list_history_entries").await?;
+    serde_wasm_bindgen::from_value(value).map_err(|err| err.to_string())
+}
+
+pub async fn rename_history_entry(index: usize, title: String) -> Result<(), String> {
+    let args = serde_wasm_bindgen::to_value(&RenameHistoryEntryArgs { index, title })
+        .map_err(|err| err.to_string())?;
+    invoke("rename_history_entry", args)
+        .await
+        .map(|_| ())
+        .map_err(extract_error)
+}
+
+pub async fn retranscribe_history_utterance(
+    index: usize,
+    utterance: usize,
+) -> Result<String, String> {
+    let args = serde_wasm_bindgen::to_value(&RetranscribeUtteranceArgs { index, utterance })
+        .map_err(|err| err.to_string())?;
+    let value = invoke("retranscribe_history_utterance", args)
+        .await
+        .map_err(extract_error)?;
+    serde_wasm_bindgen::from_value(value).map_err(|err| err.to_string())
+}
+
+pub async fn retranscribe_history_entry(
+    index: usize,
+    config: RetranscribeConfigDto,
+) -> Result<RetranscriptionDto, String> {
+    let args = serde_wasm_bindgen::to_value(&RetranscribeEntryArgs { index, config })
+        .map_err(|err| err.to_string())?;
+    let value = invoke("retranscribe_history_entry", args)
+        .await
+        .map_err(extract_error)?;
+    serde_wasm_bindgen::from_value(value).map_err(|err| err.to_string())
+}
+
+pub async fn save_history_utterances(index: usize, texts: Vec<String>) -> Result<(), String> {
+    let args = serde_wasm_bindgen::to_value(&SetHistoryUtterancesArgs { index, texts })
+        .map_err(|err| err.to_string())?;
+    invoke("set_history_utterances", args)
+        .await
+        .map(|_| ())
+        .map_err(extract_error)
+}
+
+pub async fn fetch_quiet_hours_active() -> Result<bool, String> {
+    let value = invoke_no_args("get_quiet_hours_active").await?;
+    Ok(value.as_bool().unwrap_or(false))
+}
+
+pub async fn fetch_typing_pace() -> Result<TypingPaceDto, String> {
+    let value = invoke_no_args("get_typing_pace").await?;
+    serde_wasm_bindgen::from_value(value).map_err(|err| err.to_string())
+}
+
+pub async fn save_typing_pace(pace: TypingPaceDto) -> Result<(), String> {
+    let args =
+        serde_wasm_bindgen::to_value(&SetTypingPaceArgs { pace }).map_err(|err| err.to_string())?;
+    invoke("set_typing_pace", args)
+        .await
+        .map(|_| ())
+        .map_err(extract_error)
+}
+
+pub async fn fetch_pause_markers() -> Result<PauseMarkersDto, String> {
+    let value = invoke_no_args("get_pause_markers").await?;
+    serde_wasm_bindgen::from_value(value).map_err(|err| err.to_string())
+}
+
+pub async fn save_pause_markers(pause_markers: PauseMarkersDto) -> Result<(), String> {
+    let args = serde_wasm_bindgen::to_value(&SetPauseMarkersArgs { pause_markers })
+        .map_err(|err| err.to_string())?;
+    invoke("set_pause_markers", args)
+        .await
+        .map(|_| ())
+        .map_err(extract_error)
+}
+
+pub async fn fetch_time_stretch() -> Result<TimeStretchDto, String> {
+    let value = invoke_no_args("get_time_stretch").await?;
+    serde_wasm_bindgen::from_value(value).map_err(|err| err.to_string())
+}
+
+pub async fn save_time_stretch(time_stretch: TimeStretchDto) -> Result<(), String> {
+    let args = serde_wasm_bindgen::to_value(&SetTimeStretchArgs { time_stretch })
+        .map_err(|err| err.to_string())?;
+    invoke("set_time_stretch", args)
+        .await
+        .map(|_| ())
+        .map_err(extract_error)
+}
+
+pub async fn fetch_decode_cache() -> Result<DecodeCacheDto, String> {
+    let value = invoke_no_args("get_decode_cache").await?;
+    serde_wasm_bindgen::from_value(value).map_err(|err| err.to_string())
+}
+
+pub async fn save_decode_cache(decode_cache: DecodeCacheDto) -> Result<(), String> {
+    let args = serde_wasm_bindgen::to_value(&SetDecodeCacheArgs { decode_cache })
+        .map_err(|err| err.to_string())?;
+    invoke("set_decode_cache", args)
+        .await
+        .map(|_| ())
+        .map_err(extract_error)
+}
+
+pub async fn clear_decode_cache() -> Result<(), String> {
+    invoke_no_args("clear_decode_cache").await.map(|_| ())
+}
+
+pub async fn fetch_lookback() -> Result<LookbackDto, String> {
+    let value = invoke_no_args("get_lookback").await?;
+    serde_wasm_bindgen::from_value(value).map_err(|err| err.to_string())
+}
+
+pub async fn save_lookback(lookback: LookbackDto) -> Result<(), String> {
+    let args = serde_wasm_bindgen::to_value(&SetLookbackArgs { lookback })
+        .map_err(|err| err.to_string())?;
+    invoke("set_lookback", args)
+        .await
+        .map(|_| ())
+        .map_err(extract_error)
+}
+
+pub async fn fetch_patch_routing() -> Result<PatchRoutingDto, String> {
+    let value = invoke_no_args("get_patch_routing").await?;
+    serde_wasm_bindgen::from_value(value).map_err(|err| err.to_string())
+}
+
+pub async fn save_patch_routing(routing: PatchRoutingDto) -> Result<(), String> {
+    let args = serde_wasm_bindgen::to_value(&SetPatchRoutingArgs { routing })
+        .map_err(|err| err.to_string())?;
+    invoke("set_patch_routing", args)
+        .await
+        .map(|_| ())
+        .map_err(extract_error)
+}
+
+pub async fn fetch_text_commands() -> Result<TextCommandsDto, String> {
+    let value = invoke_no_args("get_text_commands").await?;
+    serde_wasm_bindgen::from_value(value).map_err(|err| err.to_string())
+}
+
+pub async fn save_text_commands(text_commands: TextCommandsDto) -> Result<(), String> {
+    let args = serde_wasm_bindgen::to_value(&SetTextCommandsArgs { text_commands })
+        .map_err(|err| err.to_string())?;
+    invoke("set_text_commands", args)
         .await
         .map(|_| ())
         .map_err(extract_error)
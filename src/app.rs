@@ -1,6 +1,8 @@
 use crate::api::*;
+use crate::components::onboarding::OnboardingCard;
 use crate::components::recorder::RecorderSection;
 use crate::components::settings::SettingsSection;
+use crate::components::speech_stats::SpeechStatsCard;
 use leptos::prelude::*;
 use leptos::task::spawn_local;
 use wasm_bindgen::closure::Closure;
@@ -8,29 +10,58 @@ use wasm_bindgen::prelude::*;
 
 fn download_status_text(progress: &ModelDownloadProgressDto) -> String {
     let index = progress.file_index.min(progress.file_count);
+    let action = if progress.paused {
+        "Download paused"
+    } else {
+        "Downloading"
+    };
     if progress.total_bytes > 0 {
         format!(
-            "Downloading ({:.1}/{:.1} MB, {}/{})",
+            "{action} ({:.1}/{:.1} MB, {}/{})",
             progress.downloaded_bytes as f64 / 1e6,
             progress.total_bytes as f64 / 1e6,
             index,
             progress.file_count
         )
+    } else if progress.paused {
+        format!(
+            "Download paused before file {}/{}",
+            index, progress.file_count
+        )
     } else {
         format!("Downloading file {}/{}...", index, progress.file_count)
     }
 }
 
+/// Within this many pixels of the bottom, the transcript follows new text.
+const FOLLOW_SLACK_PX: i32 = 24;
+
+fn follows_tail(body: &leptos::web_sys::HtmlDivElement) -> bool {
+    body.scroll_height() - body.scroll_top() - body.client_height() <= FOLLOW_SLACK_PX
+}
+
 #[derive(Clone, Copy)]
 struct ModelView {
     set_status: WriteSignal<String>,
     set_model_ready: WriteSignal<bool>,
     set_model_error: WriteSignal<Option<String>>,
+    set_model_unloaded: WriteSignal<bool>,
+    set_model_skippable: WriteSignal<bool>,
+    /// Whether the running download is paused, or `None` when nothing is
+    /// downloading.
+    set_download_paused: WriteSignal<Option<bool>>,
     set_language_options: WriteSignal<Vec<String>>,
 }
 
 impl ModelView {
     fn apply_state(self, state: EngineStateDto) {
+        self.set_model_unloaded
+            .set(matches!(state, EngineStateDto::Unloaded));
+        self.set_model_skippable
+            .set(matches!(state, EngineStateDto::Loading));
+        if !matches!(state, EngineStateDto::Loading) {
+            self.set_download_paused.set(None);
+        }
         match state {
             EngineStateDto::Loaded => {
                 self.set_model_ready.set(true);
@@ -47,15 +78,74 @@ impl ModelView {
                 self.set_model_error.set(Some(error));
                 self.set_status.set("Model download failed.".to_string());
             }
-            EngineStateDto::Loading | EngineStateDto::Unloaded => {
+            EngineStateDto::Loading => {
                 self.set_model_ready.set(false);
                 self.set_model_error.set(None);
                 self.set_status.set("Preparing speech model...".to_string());
             }
+            EngineStateDto::Unloaded => {
+                self.set_model_ready.set(false);
+                self.set_model_error.set(None);
+                self.set_status
+                    .set("Speech model not loaded. Load it when you are ready.".to_string());
+            }
+        }
+    }
+
+    fn apply_not_ready(self, notice: ModelNotReadyDto) {
+        let detail = match (&notice.state, &notice.progress) {
+            (_, Some(progress)) => download_status_text(progress),
+            (EngineStateDto::Failed(_), None) => "Model download failed".to_string(),
+            (EngineStateDto::Unloaded, None) => "Model not loaded".to_string(),
+            _ => "Loading speech model".to_string(),
+        };
+        let action = if notice.buffering {
+            "recording will be transcribed once it is ready"
+        } else if matches!(notice.state, EngineStateDto::Unloaded) {
+            "load it from the recorder first"
+        } else {
+            "try again when it is ready"
+        };
+        self.set_status
+            .set(format!("Speech model not ready ({detail}); {action}."));
+    }
+
+    fn apply_status(self, status: ModelStatusDto) {
+        // Past the file checks and download, the load can no longer stop.
+        self.set_model_skippable.set(matches!(
+            status.stage,
+            ModelLoadStageDto::VerifyingFiles | ModelLoadStageDto::Downloading
+        ));
+        if !matches!(status.stage, ModelLoadStageDto::Downloading) {
+            self.set_download_paused.set(None);
         }
+        let step = match status.stage {
+            ModelLoadStageDto::WaitingForDictation => "waiting for the current dictation",
+            ModelLoadStageDto::Unloading => "releasing the previous model",
+            ModelLoadStageDto::VerifyingFiles => "checking model files",
+            ModelLoadStageDto::Downloading => "downloading model files",
+            ModelLoadStageDto::ReadingConfig => "reading model configuration",
+            ModelLoadStageDto::LoadingSessions => "loading encoder, decoder and preprocessor",
+            ModelLoadStageDto::ConfiguringLanguage => "configuring language",
+            ModelLoadStageDto::WarmingUp => "warming up",
+            ModelLoadStageDto::Ready => return,
+            ModelLoadStageDto::Failed => {
+                self.set_status.set(
+                    "Could not reload the speech model: the previous load stopped responding."
+                        .to_string(),
+                );
+                return;
+            }
+        };
+        self.set_status.set(format!(
+            "Preparing speech model: {step} ({:.1}s)...",
+            status.elapsed_ms as f64 / 1000.0
+        ));
     }
 
     fn apply_progress(self, progress: ModelDownloadProgressDto) {
+        self.set_download_paused
+            .set((!progress.done).then_some(progress.paused));
         if !progress.done {
             self.set_status.set(download_status_text(&progress));
         }
@@ -104,31 +194,147 @@ fn start_model_event_listeners(model_view: ModelView) {
         }
         callback.forget();
     });
+
+    spawn_local(async move {
+        let callback = Closure::wrap(Box::new(move |event: JsValue| {
+            let Ok(payload) = js_sys::Reflect::get(&event, &"payload".into()) else {
+                return;
+            };
+            match serde_wasm_bindgen::from_value::<ModelNotReadyDto>(payload) {
+                Ok(notice) => model_view.apply_not_ready(notice),
+                Err(error) => {
+                    leptos::logging::error!("Failed to parse model readiness notice: {:?}", error)
+                }
+            }
+        }) as Box<dyn FnMut(JsValue)>);
+
+        if let Err(error) = listen("model_not_ready", &callback).await {
+            leptos::logging::error!("Failed to listen for model readiness: {:?}", error);
+        }
+        callback.forget();
+    });
+
+    spawn_local(async move {
+        let callback = Closure::wrap(Box::new(move |event: JsValue| {
+            let Ok(payload) = js_sys::Reflect::get(&event, &"payload".into()) else {
+                return;
+            };
+            match serde_wasm_bindgen::from_value::<ModelStatusDto>(payload) {
+                Ok(status) => model_view.apply_status(status),
+                Err(error) => {
+                    leptos::logging::error!("Failed to parse model status: {:?}", error)
+                }
+            }
+        }) as Box<dyn FnMut(JsValue)>);
+
+        if let Err(error) = listen("model_status", &callback).await {
+            leptos::logging::error!("Failed to listen for model status: {:?}", error);
+        }
+        callback.forget();
+    });
+}
+
+/// Updates from a session older than the one on screen arrive late from a
+/// finished utterance and must not overwrite the current transcript.
+fn is_older_session(incoming: Option<u64>, current: Option<u64>) -> bool {
+    matches!((incoming, current), (Some(incoming), Some(current)) if incoming < current)
 }
 
 #[component]
 pub fn App() -> impl IntoView {
-    let (transcription, set_transcription) = signal(String::new());
+    let (transcription, set_transcription) = signal(Transcript::default());
+    let transcript_body = NodeRef::<leptos::html::Div>::new();
+    let (transcript_session, set_transcript_session) = signal::<Option<u64>>(None);
     let (transcribing, set_transcribing) = signal(false);
     let (is_recording, set_is_recording) = signal(false);
     let (status, set_status) = signal("Preparing speech model...".to_string());
     let (model_ready, set_model_ready) = signal(false);
     let (model_error, set_model_error) = signal::<Option<String>>(None);
+    let (model_unloaded, set_model_unloaded) = signal(false);
+    let (model_skippable, set_model_skippable) = signal(false);
+    let (download_paused, set_download_paused) = signal::<Option<bool>>(None);
     let (shortcut, set_shortcut) = signal(String::new());
+    let (shortcut_gesture, set_shortcut_gesture) = signal(ShortcutGestureDto::default());
+    let (triggers, set_triggers) = signal(TriggerSettingsDto::default());
+    let (suppress_record_key, set_suppress_record_key) = signal(false);
     let (streaming_enabled, set_streaming_enabled) = signal(false);
+    let (streaming_strategy, set_streaming_strategy) = signal("continuous".to_string());
     let (model_path, set_model_path) = signal(String::new());
     let (asr_language, set_asr_language) = signal("en-US".to_string());
+    let (resampler_quality, set_resampler_quality) = signal("auto".to_string());
+    let (input_device, set_input_device) = signal(InputDeviceDto::default());
+    let (vad_settings, set_vad_settings) = signal(VadSettingsDto::default());
+    let (noise_suppression, set_noise_suppression) = signal(NoiseSuppressionDto::default());
+    let (speaker_adaptation, set_speaker_adaptation) = signal(SpeakerAdaptationDto::default());
+    let (live_waveform, set_live_waveform) = signal(false);
+    let (buffer_until_ready, set_buffer_until_ready) = signal(false);
+    let (model_prewarm, set_model_prewarm) = signal("on_launch".to_string());
+    let (power_saver, set_power_saver) = signal("auto".to_string());
+    let (recording_indicator, set_recording_indicator) = signal("pulse".to_string());
+    let (typing_pace, set_typing_pace) = signal(TypingPaceDto::default());
+    let (caret_spacing, set_caret_spacing) = signal(false);
+    let (continuation, set_continuation) = signal(ContinuationDto::default());
+    let (inactivity, set_inactivity) = signal(InactivityDto::default());
+    let (pause_markers, set_pause_markers) = signal(PauseMarkersDto::default());
+    let (time_stretch, set_time_stretch) = signal(TimeStretchDto::default());
+    let (patch_routing, set_patch_routing) = signal(PatchRoutingDto::default());
+    let (text_commands, set_text_commands) = signal(TextCommandsDto::default());
+    let (post_processing, set_post_processing) = signal(PostProcessingDto::default());
+    let (dictation_enabled, set_dictation_enabled) = signal(true);
+    let (dictation_toggle_shortcut, set_dictation_toggle_shortcut) = signal(false);
+    let (quiet_hours, set_quiet_hours) = signal(QuietHoursDto::default());
+    let (webhook, set_webhook) = signal(WebhookDto::default());
+    let (llm_handoff, set_llm_handoff) = signal(LlmHandoffDto::default());
+    let (numbers_mode, set_numbers_mode) = signal(false);
+    let (output_templates, set_output_templates) = signal(OutputTemplatesDto::default());
+    let (capture_file, set_capture_file) = signal(CaptureFileDto::default());
+    let (announce_transcripts, set_announce_transcripts) = signal(false);
+    let (speak_transcripts, set_speak_transcripts) = signal(false);
+    let (network_input, set_network_input) = signal(NetworkInputDto::default());
+    let (typed_metadata, set_typed_metadata) = signal(TypedMetadataDto::default());
+    let (companion, set_companion) = signal(CompanionDto::default());
+    let (profiles, set_profiles) = signal(ProfileListDto::default());
+    let (autostart, set_autostart) = signal(AutostartDto::default());
+    let (settings_lock, set_settings_lock) = signal(SettingsLockDto::default());
+    let (announcement, set_announcement) = signal(String::new());
     let (language_options, set_language_options) = signal(Vec::<String>::new());
+    let (audio_health, set_audio_health) = signal::<Option<AudioHealthDto>>(None);
+    let (capture_quality, set_capture_quality) = signal::<Option<CaptureQualityDto>>(None);
+    let (inactivity_warning, set_inactivity_warning) = signal::<Option<u64>>(None);
+    let (speech_stats, set_speech_stats) = signal(Vec::<SpeechStatsDto>::new());
+    let (voice_level, set_voice_level) = signal::<Option<(f32, bool)>>(None);
+    // Whether the streaming session last heard speech start or end.
+    let (speaking, set_speaking) = signal::<Option<bool>>(None);
+    let (waveform, set_waveform) = signal(Vec::<f32>::new());
 
     spawn_local(async move {
         let callback = Closure::wrap(Box::new(move |event: JsValue| {
             if let Ok(payload) = js_sys::Reflect::get(&event, &"payload".into()) {
-                match serde_wasm_bindgen::from_value::<TranscriptionUpdateDto>(payload) {
-                    Ok(update) => {
-                        set_transcription.update(|current| match update {
-                            TranscriptionUpdateDto::Append(text) => current.push_str(&text),
-                            TranscriptionUpdateDto::Replace(text) => *current = text,
+                match serde_wasm_bindgen::from_value::<SessionUpdateDto>(payload) {
+                    Ok(SessionUpdateDto { session_id, patch }) => {
+                        let current_session = transcript_session.get_untracked();
+                        if is_older_session(session_id, current_session) {
+                            return;
+                        }
+                        if session_id != current_session {
+                            set_transcript_session.set(session_id);
+                            set_transcription.set(Transcript::default());
+                        }
+                        let follow = transcript_body
+                            .get_untracked()
+                            .is_some_and(|body| follows_tail(&body));
+                        set_transcription.update(|current| {
+                            if let Err(e) = current.apply(&patch) {
+                                leptos::logging::error!("Failed to apply transcript patch: {}", e);
+                            }
                         });
+                        if follow {
+                            request_animation_frame(move || {
+                                if let Some(body) = transcript_body.get_untracked() {
+                                    body.set_scroll_top(body.scroll_height());
+                                }
+                            });
+                        }
                     }
                     Err(e) => {
                         leptos::logging::error!("Failed to parse transcription update: {:?}", e);
@@ -139,6 +345,10 @@ pub fn App() -> impl IntoView {
             }
         }) as Box<dyn FnMut(JsValue)>);
 
+        // Long sessions get drafts as tails of the shown text.
+        if let Err(e) = negotiate_patch_protocol().await {
+            leptos::logging::error!("Failed to negotiate the patch protocol: {}", e);
+        }
         match listen("transcription_update", &callback).await {
             Ok(_) => leptos::logging::log!("Listening for transcription_update"),
             Err(e) => leptos::logging::error!("Failed to listen for transcription_update: {:?}", e),
@@ -147,12 +357,74 @@ pub fn App() -> impl IntoView {
     });
 
     spawn_local(async move {
-        let callback = Closure::wrap(Box::new(move |event: JsValue| {
-            if let Some(message) = js_sys::Reflect::get(&event, &"payload".into())
+        let speech_start = Closure::wrap(Box::new(move |_event: JsValue| {
+            set_speaking.set(Some(true));
+        }) as Box<dyn FnMut(JsValue)>);
+        let speech_end = Closure::wrap(Box::new(move |_event: JsValue| {
+            set_speaking.set(Some(false));
+        }) as Box<dyn FnMut(JsValue)>);
+
+        if let Err(e) = listen("speech_start", &speech_start).await {
+            leptos::logging::error!("Failed to listen for speech_start: {:?}", e);
+        }
+        if let Err(e) = listen("speech_end", &speech_end).await {
+            leptos::logging::error!("Failed to listen for speech_end: {:?}", e);
+        }
+        speech_start.forget();
+        speech_end.forget();
+    });
+
+    spawn_local(async move {
+        let started = Closure::wrap(Box::new(move |event: JsValue| {
+            set_is_recording.set(true);
+            set_waveform.set(Vec::new());
+            set_speaking.set(None);
+            let session_id = js_sys::Reflect::get(&event, &"payload".into())
                 .ok()
-                .and_then(|payload| payload.as_string())
-            {
-                set_status.set(message);
+                .and_then(|payload| payload.as_f64())
+                .map(|id| id as u64);
+            if session_id.is_some() && session_id != transcript_session.get_untracked() {
+                set_transcript_session.set(session_id);
+                set_transcription.set(Transcript::default());
+            }
+        }) as Box<dyn FnMut(JsValue)>);
+        let stopped = Closure::wrap(Box::new(move |_event: JsValue| {
+            set_is_recording.set(false);
+            set_inactivity_warning.set(None);
+            set_speaking.set(None);
+        }) as Box<dyn FnMut(JsValue)>);
+
+        if let Err(e) = listen("recording_started", &started).await {
+            leptos::logging::error!("Failed to listen for recording_started: {:?}", e);
+        }
+        if let Err(e) = listen("recording_stopped", &stopped).await {
+            leptos::logging::error!("Failed to listen for recording_stopped: {:?}", e);
+        }
+        started.forget();
+        stopped.forget();
+
+        if let Ok(recording) = fetch_is_recording().await {
+            set_is_recording.set(recording);
+        }
+    });
+
+    spawn_local(async move {
+        let callback = Closure::wrap(Box::new(move |event: JsValue| {
+            let Ok(payload) = js_sys::Reflect::get(&event, &"payload".into()) else {
+                return;
+            };
+            match serde_wasm_bindgen::from_value::<DictationErrorDto>(payload) {
+                Ok(error) => {
+                    if let Some(session_id) = error.session_id {
+                        leptos::logging::warn!(
+                            "Dictation {} failed: {}",
+                            session_id,
+                            error.message
+                        );
+                    }
+                    set_status.set(error.message);
+                }
+                Err(e) => leptos::logging::error!("Failed to parse dictation error: {:?}", e),
             }
         }) as Box<dyn FnMut(JsValue)>);
 
@@ -162,10 +434,152 @@ pub fn App() -> impl IntoView {
         callback.forget();
     });
 
+    spawn_local(async move {
+        let warning = Closure::wrap(Box::new(move |event: JsValue| {
+            let Ok(payload) = js_sys::Reflect::get(&event, &"payload".into()) else {
+                return;
+            };
+            match serde_wasm_bindgen::from_value::<InactivityWarningDto>(payload) {
+                Ok(warning) => set_inactivity_warning.set(Some(warning.remaining_secs)),
+                Err(e) => leptos::logging::error!("Failed to parse inactivity warning: {:?}", e),
+            }
+        }) as Box<dyn FnMut(JsValue)>);
+        let resumed = Closure::wrap(Box::new(move |_event: JsValue| {
+            set_inactivity_warning.set(None);
+        }) as Box<dyn FnMut(JsValue)>);
+
+        if let Err(e) = listen("inactivity_warning", &warning).await {
+            leptos::logging::error!("Failed to listen for inactivity_warning: {:?}", e);
+        }
+        if let Err(e) = listen("inactivity_resumed", &resumed).await {
+            leptos::logging::error!("Failed to listen for inactivity_resumed: {:?}", e);
+        }
+        warning.forget();
+        resumed.forget();
+    });
+
+    spawn_local(async move {
+        let callback = Closure::wrap(Box::new(move |event: JsValue| {
+            let Ok(payload) = js_sys::Reflect::get(&event, &"payload".into()) else {
+                return;
+            };
+            match serde_wasm_bindgen::from_value::<AudioHealthDto>(payload) {
+                Ok(health) => set_audio_health.set(Some(health)),
+                Err(error) => leptos::logging::error!("Failed to parse audio health: {:?}", error),
+            }
+        }) as Box<dyn FnMut(JsValue)>);
+
+        if let Err(e) = listen("audio_health", &callback).await {
+            leptos::logging::error!("Failed to listen for audio_health: {:?}", e);
+        }
+        callback.forget();
+
+        if let Ok(health) = fetch_audio_health().await {
+            set_audio_health.set(health);
+        }
+    });
+
+    spawn_local(async move {
+        let callback = Closure::wrap(Box::new(move |event: JsValue| {
+            let Ok(payload) = js_sys::Reflect::get(&event, &"payload".into()) else {
+                return;
+            };
+            match serde_wasm_bindgen::from_value::<CaptureQualityDto>(payload) {
+                Ok(quality) => set_capture_quality.set(Some(quality)),
+                Err(error) => {
+                    leptos::logging::error!("Failed to parse capture quality: {:?}", error)
+                }
+            }
+        }) as Box<dyn FnMut(JsValue)>);
+
+        if let Err(e) = listen("capture_quality", &callback).await {
+            leptos::logging::error!("Failed to listen for capture_quality: {:?}", e);
+        }
+        callback.forget();
+
+        if let Ok(quality) = fetch_capture_quality().await {
+            set_capture_quality.set(quality);
+        }
+    });
+
+    spawn_local(async move {
+        let callback = Closure::wrap(Box::new(move |event: JsValue| {
+            let Ok(payload) = js_sys::Reflect::get(&event, &"payload".into()) else {
+                return;
+            };
+            match serde_wasm_bindgen::from_value::<SpeechStatsDto>(payload) {
+                Ok(stats) => set_speech_stats.update(|sessions| sessions.push(stats)),
+                Err(error) => {
+                    leptos::logging::error!("Failed to parse speech stats: {:?}", error)
+                }
+            }
+        }) as Box<dyn FnMut(JsValue)>);
+
+        if let Err(e) = listen("speech_stats", &callback).await {
+            leptos::logging::error!("Failed to listen for speech_stats: {:?}", e);
+        }
+        callback.forget();
+
+        if let Ok(sessions) = fetch_speech_stats().await {
+            set_speech_stats.set(sessions);
+        }
+    });
+
+    spawn_local(async move {
+        let callback = Closure::wrap(Box::new(move |event: JsValue| {
+            let Ok(payload) = js_sys::Reflect::get(&event, &"payload".into()) else {
+                return;
+            };
+            match serde_wasm_bindgen::from_value::<Vec<VadEventDto>>(payload) {
+                Ok(events) => {
+                    let level = events.iter().rev().find_map(|event| match event {
+                        VadEventDto::Frame { rms, speech, .. } => Some((*rms, *speech)),
+                        _ => None,
+                    });
+                    if level.is_some() {
+                        set_voice_level.set(level);
+                    }
+                }
+                Err(error) => {
+                    leptos::logging::error!("Failed to parse voice activity: {:?}", error)
+                }
+            }
+        }) as Box<dyn FnMut(JsValue)>);
+
+        if let Err(e) = listen("vad_events", &callback).await {
+            leptos::logging::error!("Failed to listen for vad_events: {:?}", e);
+        }
+        callback.forget();
+
+        if let Err(e) = subscribe_vad_events().await {
+            leptos::logging::error!("Failed to subscribe to voice activity: {:?}", e);
+        }
+    });
+
+    spawn_local(async move {
+        let callback = Closure::wrap(Box::new(move |event: JsValue| {
+            let Ok(payload) = js_sys::Reflect::get(&event, &"payload".into()) else {
+                return;
+            };
+            match serde_wasm_bindgen::from_value::<WaveformFrameDto>(payload) {
+                Ok(frame) => set_waveform.set(frame.peaks),
+                Err(error) => leptos::logging::error!("Failed to parse waveform: {:?}", error),
+            }
+        }) as Box<dyn FnMut(JsValue)>);
+
+        if let Err(e) = listen("waveform", &callback).await {
+            leptos::logging::error!("Failed to listen for waveform: {:?}", e);
+        }
+        callback.forget();
+    });
+
     start_model_event_listeners(ModelView {
         set_status,
         set_model_ready,
         set_model_error,
+        set_model_unloaded,
+        set_model_skippable,
+        set_download_paused,
         set_language_options,
     });
 
@@ -176,6 +590,15 @@ pub fn App() -> impl IntoView {
         } else if let Ok(Some(s)) = fetch_default_shortcut().await {
             set_shortcut.set(s);
         }
+        if let Ok(gesture) = fetch_shortcut_gesture().await {
+            set_shortcut_gesture.set(gesture);
+        }
+        if let Ok(saved) = fetch_triggers().await {
+            set_triggers.set(saved);
+        }
+        if let Ok(enabled) = fetch_suppress_record_key().await {
+            set_suppress_record_key.set(enabled);
+        }
 
         if let Ok(path) = fetch_model_path().await {
             set_model_path.set(path);
@@ -183,39 +606,304 @@ pub fn App() -> impl IntoView {
         if let Ok(enabled) = fetch_streaming_enabled().await {
             set_streaming_enabled.set(enabled);
         }
+        if let Ok(strategy) = fetch_streaming_strategy().await {
+            set_streaming_strategy.set(strategy);
+        }
         if let Ok(language) = fetch_asr_language().await {
             set_asr_language.set(language);
         }
+        if let Ok(quality) = fetch_resampler_quality().await {
+            set_resampler_quality.set(quality);
+        }
+        if let Ok(settings) = fetch_input_device().await {
+            set_input_device.set(settings);
+        }
+        if let Ok(settings) = fetch_vad_settings().await {
+            set_vad_settings.set(settings);
+        }
+        if let Ok(settings) = fetch_noise_suppression().await {
+            set_noise_suppression.set(settings);
+        }
+        if let Ok(settings) = fetch_speaker_adaptation().await {
+            set_speaker_adaptation.set(settings);
+        }
+        if let Ok(enabled) = fetch_live_waveform().await {
+            set_live_waveform.set(enabled);
+        }
+        if let Ok(enabled) = fetch_buffer_until_model_ready().await {
+            set_buffer_until_ready.set(enabled);
+        }
+        if let Ok(prewarm) = fetch_model_prewarm().await {
+            set_model_prewarm.set(prewarm);
+        }
+        if let Ok(mode) = fetch_power_saver().await {
+            set_power_saver.set(mode);
+        }
+        if let Ok(indicator) = fetch_recording_indicator().await {
+            set_recording_indicator.set(indicator);
+        }
+        if let Ok(pace) = fetch_typing_pace().await {
+            set_typing_pace.set(pace);
+        }
+        if let Ok(enabled) = fetch_caret_spacing().await {
+            set_caret_spacing.set(enabled);
+        }
+        if let Ok(settings) = fetch_continuation().await {
+            set_continuation.set(settings);
+        }
+        if let Ok(settings) = fetch_inactivity().await {
+            set_inactivity.set(settings);
+        }
+        if let Ok(settings) = fetch_pause_markers().await {
+            set_pause_markers.set(settings);
+        }
+        if let Ok(settings) = fetch_time_stretch().await {
+            set_time_stretch.set(settings);
+        }
+        if let Ok(routing) = fetch_patch_routing().await {
+            set_patch_routing.set(routing);
+        }
+        if let Ok(settings) = fetch_text_commands().await {
+            set_text_commands.set(settings);
+        }
+        if let Ok(selection) = fetch_post_processing().await {
+            set_post_processing.set(selection);
+        }
+        if let Ok(enabled) = fetch_dictation_enabled().await {
+            set_dictation_enabled.set(enabled);
+        }
+        if let Ok(enabled) = fetch_dictation_toggle_shortcut().await {
+            set_dictation_toggle_shortcut.set(enabled);
+        }
+        if let Ok(schedule) = fetch_quiet_hours().await {
+            set_quiet_hours.set(schedule);
+        }
+        if let Ok(settings) = fetch_webhook().await {
+            set_webhook.set(settings);
+        }
+        if let Ok(settings) = fetch_llm_handoff().await {
+            set_llm_handoff.set(settings);
+        }
+        if let Ok(enabled) = fetch_numbers_mode().await {
+            set_numbers_mode.set(enabled);
+        }
+        if let Ok(templates) = fetch_output_templates().await {
+            set_output_templates.set(templates);
+        }
+        if let Ok(settings) = fetch_capture_file().await {
+            set_capture_file.set(settings);
+        }
+        if let Ok(enabled) = fetch_announce_transcripts().await {
+            set_announce_transcripts.set(enabled);
+        }
+        if let Ok(enabled) = fetch_speak_transcripts().await {
+            set_speak_transcripts.set(enabled);
+        }
+        if let Ok(settings) = fetch_network_input().await {
+            set_network_input.set(settings);
+        }
+        if let Ok(settings) = fetch_typed_metadata().await {
+            set_typed_metadata.set(settings);
+        }
+        if let Ok(settings) = fetch_companion().await {
+            set_companion.set(settings);
+        }
+        if let Ok(list) = fetch_profiles().await {
+            set_profiles.set(list);
+        }
+        if let Ok(settings) = fetch_autostart().await {
+            set_autostart.set(settings);
+        }
+        if let Ok(lock) = fetch_settings_lock().await {
+            set_settings_lock.set(lock);
+        }
+        if let Ok(true) = fetch_quiet_hours_active().await {
+            set_status.set("Quiet hours active; the record shortcut is paused.".to_string());
+        }
+    });
+
+    spawn_local(async move {
+        let callback = Closure::wrap(Box::new(move |event: JsValue| {
+            if let Some(active) = js_sys::Reflect::get(&event, &"payload".into())
+                .ok()
+                .and_then(|payload| payload.as_bool())
+            {
+                set_status.set(if active {
+                    "Quiet hours active; the record shortcut is paused.".to_string()
+                } else {
+                    "Quiet hours ended.".to_string()
+                });
+            }
+        }) as Box<dyn FnMut(JsValue)>);
+
+        if let Err(e) = listen("quiet_hours_active", &callback).await {
+            leptos::logging::error!("Failed to listen for quiet_hours_active: {:?}", e);
+        }
+        callback.forget();
+    });
+
+    spawn_local(async move {
+        let callback = Closure::wrap(Box::new(move |_event: JsValue| {
+            set_status.set("Companion device paired.".to_string());
+            spawn_local(async move {
+                if let Ok(settings) = fetch_companion().await {
+                    set_companion.set(settings);
+                }
+            });
+        }) as Box<dyn FnMut(JsValue)>);
+
+        if let Err(e) = listen("companion_paired", &callback).await {
+            leptos::logging::error!("Failed to listen for companion_paired: {:?}", e);
+        }
+        callback.forget();
+    });
+
+    spawn_local(async move {
+        let callback = Closure::wrap(Box::new(move |event: JsValue| {
+            if let Some(name) = js_sys::Reflect::get(&event, &"payload".into())
+                .ok()
+                .and_then(|payload| payload.as_string())
+            {
+                set_status.set(format!("Switched to profile {}.", name));
+            }
+            spawn_local(async move {
+                if let Ok(list) = fetch_profiles().await {
+                    set_profiles.set(list);
+                }
+                if let Ok(language) = fetch_asr_language().await {
+                    set_asr_language.set(language);
+                }
+                if let Ok(enabled) = fetch_streaming_enabled().await {
+                    set_streaming_enabled.set(enabled);
+                }
+                if let Ok(pace) = fetch_typing_pace().await {
+                    set_typing_pace.set(pace);
+                }
+                if let Ok(templates) = fetch_output_templates().await {
+                    set_output_templates.set(templates);
+                }
+                if let Ok(settings) = fetch_capture_file().await {
+                    set_capture_file.set(settings);
+                }
+                if let Ok(settings) = fetch_text_commands().await {
+                    set_text_commands.set(settings);
+                }
+                if let Ok(settings) = fetch_time_stretch().await {
+                    set_time_stretch.set(settings);
+                }
+                if let Ok(settings) = fetch_speaker_adaptation().await {
+                    set_speaker_adaptation.set(settings);
+                }
+            });
+        }) as Box<dyn FnMut(JsValue)>);
+
+        if let Err(e) = listen("profile_changed", &callback).await {
+            leptos::logging::error!("Failed to listen for profile_changed: {:?}", e);
+        }
+        callback.forget();
+    });
+
+    spawn_local(async move {
+        let callback = Closure::wrap(Box::new(move |event: JsValue| {
+            if let Some(enabled) = js_sys::Reflect::get(&event, &"payload".into())
+                .ok()
+                .and_then(|payload| payload.as_bool())
+            {
+                set_dictation_enabled.set(enabled);
+                set_status.set(if enabled {
+                    "SilentKeys enabled.".to_string()
+                } else {
+                    "SilentKeys disabled; the record shortcut is inactive.".to_string()
+                });
+            }
+        }) as Box<dyn FnMut(JsValue)>);
+
+        if let Err(e) = listen("dictation_enabled", &callback).await {
+            leptos::logging::error!("Failed to listen for dictation_enabled: {:?}", e);
+        }
+        callback.forget();
+    });
+
+    // Windows screen readers are reached through the webview's live region.
+    spawn_local(async move {
+        let callback = Closure::wrap(Box::new(move |event: JsValue| {
+            if let Some(text) = js_sys::Reflect::get(&event, &"payload".into())
+                .ok()
+                .and_then(|payload| payload.as_string())
+            {
+                set_announcement.set(text);
+            }
+        }) as Box<dyn FnMut(JsValue)>);
+
+        if let Err(e) = listen("screen_reader_announcement", &callback).await {
+            leptos::logging::error!("Failed to listen for screen_reader_announcement: {:?}", e);
+        }
+        callback.forget();
     });
 
     view! {
         <main class="shell">
+            <div class="visually-hidden" role="status" aria-live="assertive">
+                {move || announcement.get()}
+            </div>
             <header class="hero">
                 <p class="eyebrow">"Local-first capture"</p>
                 <h1>"SilentKeys"</h1>
             </header>
 
+            <OnboardingCard shortcut />
+
             <RecorderSection
                 is_recording transcribing status set_status model_ready
-                set_model_error model_error set_transcription
-                set_is_recording set_transcribing
+                set_model_error model_error model_unloaded model_skippable download_paused
+                set_transcription
+                set_transcribing audio_health capture_quality voice_level speaking waveform
+                inactivity_warning set_inactivity_warning
             />
 
             <section class="grid">
                 <div class="card">
                     <div class="card-header"><p class="eyebrow">"Transcript"</p></div>
-                    <div class="transcription-body">
+                    <div class="transcription-body" node_ref=transcript_body>
                         <p class="result-text">
-                            {move || if transcription.get().is_empty() { "Your transcription will appear here.".to_string() } else { transcription.get() }}
+                            {move || transcription.with(|transcript| if transcript.is_empty() { "Your transcription will appear here.".to_string() } else { transcript.text().to_string() })}
                         </p>
                     </div>
                 </div>
 
+                <SpeechStatsCard speech_stats />
+
                 <div class="card settings-card">
                     <div class="card-header"><p class="eyebrow">"Settings"</p></div>
                     <SettingsSection
                         model_path set_model_path streaming_enabled set_streaming_enabled
-                        shortcut set_shortcut asr_language set_asr_language language_options
+                        streaming_strategy set_streaming_strategy
+                        shortcut set_shortcut shortcut_gesture set_shortcut_gesture triggers set_triggers
+                        suppress_record_key set_suppress_record_key
+                        asr_language set_asr_language language_options
+                        resampler_quality set_resampler_quality input_device set_input_device
+                        vad_settings set_vad_settings noise_suppression set_noise_suppression
+                        speaker_adaptation set_speaker_adaptation
+                        live_waveform set_live_waveform
+                        buffer_until_ready set_buffer_until_ready model_prewarm set_model_prewarm
+                        power_saver set_power_saver recording_indicator set_recording_indicator
+                        typing_pace set_typing_pace
+                        caret_spacing set_caret_spacing continuation set_continuation
+                        inactivity set_inactivity
+                        pause_markers set_pause_markers time_stretch set_time_stretch
+                        text_commands set_text_commands post_processing set_post_processing
+                        dictation_enabled set_dictation_enabled
+                        dictation_toggle_shortcut set_dictation_toggle_shortcut
+                        quiet_hours set_quiet_hours
+                        webhook set_webhook llm_handoff set_llm_handoff
+                        numbers_mode set_numbers_mode
+                        output_templates set_output_templates capture_file set_capture_file
+                        patch_routing set_patch_routing
+                        announce_transcripts set_announce_transcripts
+                        speak_transcripts set_speak_transcripts network_input set_network_input
+                        typed_metadata set_typed_metadata
+                        companion set_companion profiles set_profiles autostart set_autostart
+                        settings_lock set_settings_lock
                         is_recording transcribing set_status
                     />
                 </div>
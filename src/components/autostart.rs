@@ -0,0 +1,67 @@
+use crate::api::*;
+use leptos::prelude::*;
+use leptos::task::spawn_local;
+
+#[component]
+pub fn AutostartRow(
+    autostart: ReadSignal<AutostartDto>,
+    set_autostart: WriteSignal<AutostartDto>,
+) -> impl IntoView {
+    let (autostart_status, set_autostart_status) = signal(String::new());
+
+    let save = move |settings: AutostartDto| {
+        set_autostart.set(settings.clone());
+        spawn_local(async move {
+            match save_autostart(settings).await {
+                Ok(_) => set_autostart_status.set(String::new()),
+                Err(err) => {
+                    if let Ok(saved) = fetch_autostart().await {
+                        set_autostart.set(saved);
+                    }
+                    set_autostart_status.set(format!("Failed to save start at login: {}", err))
+                }
+            }
+        });
+    };
+
+    view! {
+        <div class="settings-row">
+            <div class="settings-label">
+                <span class="settings-title">"Start at Login"</span>
+                <span class="settings-hint">"Launch SilentKeys when you sign in"</span>
+                <p class="settings-status">{ move || autostart_status.get() }</p>
+            </div>
+            <button
+                class="toggle"
+                class:active=move || autostart.get().enabled
+                on:click=move |_| {
+                    let mut settings = autostart.get();
+                    settings.enabled = !settings.enabled;
+                    save(settings);
+                }
+            >
+                <div class="toggle-track"><div class="toggle-thumb"></div></div>
+            </button>
+        </div>
+        <div class="settings-row">
+            <div class="settings-label">
+                <span class="settings-title">"Start in Tray"</span>
+                <span class="settings-hint">
+                    "Keep this window hidden when launched at login; open it from the tray"
+                </span>
+            </div>
+            <button
+                class="toggle"
+                class:active=move || autostart.get().start_in_tray
+                disabled=move || !autostart.get().enabled
+                on:click=move |_| {
+                    let mut settings = autostart.get();
+                    settings.start_in_tray = !settings.start_in_tray;
+                    save(settings);
+                }
+            >
+                <div class="toggle-track"><div class="toggle-thumb"></div></div>
+            </button>
+        </div>
+    }
+}
@@ -0,0 +1,162 @@
+use crate::api::*;
+use crate::components::settings::{input_value, select_value};
+use leptos::prelude::*;
+use leptos::task::spawn_local;
+
+#[component]
+pub fn CaptureFileRow(
+    capture_file: ReadSignal<CaptureFileDto>,
+    set_capture_file: WriteSignal<CaptureFileDto>,
+) -> impl IntoView {
+    let (capture_status, set_capture_status) = signal(String::new());
+
+    let browse_action = move |_| {
+        spawn_local(async move {
+            match pick_capture_file_cmd().await {
+                Ok(Some(path)) => set_capture_file.update(|settings| settings.path = path),
+                Ok(None) => {}
+                Err(err) => set_capture_status.set(format!("Failed to pick file: {}", err)),
+            }
+        });
+    };
+
+    let save_action = move |_| {
+        let settings = capture_file.get();
+        spawn_local(async move {
+            match save_capture_file(settings).await {
+                Ok(_) => {
+                    if let Ok(saved) = fetch_capture_file().await {
+                        set_capture_file.set(saved);
+                    }
+                    set_capture_status.set("Capture file saved.".to_string());
+                }
+                Err(err) => set_capture_status.set(format!("Failed to save capture file: {}", err)),
+            }
+        });
+    };
+
+    view! {
+        <div class="settings-row">
+            <div class="settings-label">
+                <span class="settings-title">"Capture to File"</span>
+                <span class="settings-hint">
+                    "Append shortcut dictations to a Markdown file under a timestamp instead of typing"
+                </span>
+                <p class="settings-status">{ move || capture_status.get() }</p>
+            </div>
+            <button
+                class="toggle"
+                class:active=move || capture_file.get().enabled
+                on:click=move |_| set_capture_file.update(|settings| settings.enabled = !settings.enabled)
+            >
+                <div class="toggle-track"><div class="toggle-thumb"></div></div>
+            </button>
+        </div>
+        <div class="settings-row">
+            <div class="settings-label">
+                <span class="settings-title">"Name Entries"</span>
+                <span class="settings-hint">
+                    "Title each entry from a spoken \"title: ...\" phrase or its first sentence"
+                </span>
+            </div>
+            <button
+                class="toggle"
+                class:active=move || capture_file.get().name_entries
+                on:click=move |_| {
+                    set_capture_file.update(|settings| settings.name_entries = !settings.name_entries)
+                }
+            >
+                <div class="toggle-track"><div class="toggle-thumb"></div></div>
+            </button>
+        </div>
+        <div class="settings-row">
+            <div class="settings-label">
+                <span class="settings-title">"Split Utterances"</span>
+                <span class="settings-hint">
+                    "Write each utterance on its own timestamped line and keep its audio beside the file, so one can be transcribed again from the history"
+                </span>
+            </div>
+            <button
+                class="toggle"
+                class:active=move || capture_file.get().split_utterances
+                on:click=move |_| {
+                    set_capture_file
+                        .update(|settings| settings.split_utterances = !settings.split_utterances)
+                }
+            >
+                <div class="toggle-track"><div class="toggle-thumb"></div></div>
+            </button>
+        </div>
+        <div class="settings-row">
+            <div class="settings-label">
+                <span class="settings-title">"Trim Clip Silence"</span>
+                <span class="settings-hint">
+                    "Cut the silence before and after the speech in each utterance's audio"
+                </span>
+            </div>
+            <button
+                class="toggle"
+                class:active=move || capture_file.get().clip_export.trim_silence
+                on:click=move |_| {
+                    set_capture_file.update(|settings| {
+                        settings.clip_export.trim_silence = !settings.clip_export.trim_silence
+                    })
+                }
+            >
+                <div class="toggle-track"><div class="toggle-thumb"></div></div>
+            </button>
+        </div>
+        <div class="settings-row">
+            <div class="settings-label">
+                <span class="settings-title">"Normalize Clips"</span>
+                <span class="settings-hint">
+                    "Scale each utterance's audio so its loudest peak sits just below full scale"
+                </span>
+            </div>
+            <button
+                class="toggle"
+                class:active=move || capture_file.get().clip_export.normalize
+                on:click=move |_| {
+                    set_capture_file.update(|settings| {
+                        settings.clip_export.normalize = !settings.clip_export.normalize
+                    })
+                }
+            >
+                <div class="toggle-track"><div class="toggle-thumb"></div></div>
+            </button>
+        </div>
+        <div class="settings-row">
+            <div class="settings-label">
+                <span class="settings-title">"Clip Format"</span>
+                <span class="settings-hint">
+                    "16-bit PCM is half the size; 32-bit float keeps the audio exactly as the model heard it"
+                </span>
+            </div>
+            <select
+                class="settings-input settings-select"
+                prop:value=move || capture_file.get().clip_export.encoding.as_str()
+                on:change=move |event| {
+                    let encoding = WavEncodingDto::from_value(&select_value(&event));
+                    set_capture_file.update(|settings| settings.clip_export.encoding = encoding);
+                }
+            >
+                <option value="pcm16">"16-bit PCM"</option>
+                <option value="float32">"32-bit float"</option>
+            </select>
+        </div>
+        <div class="settings-input-group">
+            <input
+                type="text"
+                class="settings-input"
+                placeholder="/path/to/daily-note.md"
+                prop:value=move || capture_file.get().path
+                on:input=move |event| {
+                    let path = input_value(&event);
+                    set_capture_file.update(|settings| settings.path = path);
+                }
+            />
+            <button class="ghost compact" on:click=browse_action>"Browse"</button>
+            <button class="ghost compact" on:click=save_action>"Save"</button>
+        </div>
+    }
+}
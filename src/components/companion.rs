@@ -0,0 +1,121 @@
+use crate::api::*;
+use crate::components::settings::input_value;
+use leptos::prelude::*;
+use leptos::task::spawn_local;
+
+#[component]
+pub fn CompanionRow(
+    companion: ReadSignal<CompanionDto>,
+    set_companion: WriteSignal<CompanionDto>,
+) -> impl IntoView {
+    let (companion_status, set_companion_status) = signal(String::new());
+
+    let refresh = move || {
+        spawn_local(async move {
+            if let Ok(saved) = fetch_companion().await {
+                set_companion.set(saved);
+            }
+        });
+    };
+
+    let save_action = move |_| {
+        let settings = companion.get();
+        spawn_local(async move {
+            match save_companion(settings.enabled, settings.port).await {
+                Ok(_) => {
+                    if let Ok(saved) = fetch_companion().await {
+                        set_companion.set(saved);
+                    }
+                    set_companion_status.set("Companion devices saved.".to_string());
+                }
+                Err(err) => {
+                    set_companion_status.set(format!("Failed to save companion devices: {}", err))
+                }
+            }
+        });
+    };
+
+    let pair_action = move |_| {
+        spawn_local(async move {
+            match start_companion_pairing().await {
+                Ok(info) => set_companion_status.set(format!(
+                    "Enter pairing code {} on the companion device within {} seconds.",
+                    info.code, info.expires_in_secs
+                )),
+                Err(err) => set_companion_status.set(format!("Failed to start pairing: {}", err)),
+            }
+        });
+    };
+
+    let device_view = move |device: CompanionDeviceDto| {
+        let device_id = device.id.clone();
+        view! {
+            <div class="settings-input-group">
+                <span class="settings-hint">{format!("{} (paired {})", device.name, device.paired_at)}</span>
+                <button
+                    class="ghost compact"
+                    on:click=move |_| {
+                        let device_id = device_id.clone();
+                        spawn_local(async move {
+                            match revoke_companion_device(device_id).await {
+                                Ok(_) => {
+                                    set_companion_status.set("Companion device revoked.".to_string());
+                                    refresh();
+                                }
+                                Err(err) => set_companion_status
+                                    .set(format!("Failed to revoke companion device: {}", err)),
+                            }
+                        });
+                    }
+                >
+                    "Revoke"
+                </button>
+            </div>
+        }
+    };
+
+    view! {
+        <div class="settings-row">
+            <div class="settings-label">
+                <span class="settings-title">"Companion Devices"</span>
+                <span class="settings-hint">
+                    "Dictate from a paired phone or tablet on your local network"
+                </span>
+                <p class="settings-status">{ move || companion_status.get() }</p>
+            </div>
+            <button
+                class="toggle"
+                class:active=move || companion.get().enabled
+                on:click=move |_| set_companion.update(|settings| settings.enabled = !settings.enabled)
+            >
+                <div class="toggle-track"><div class="toggle-thumb"></div></div>
+            </button>
+        </div>
+        <div class="settings-input-group">
+            <input
+                type="number"
+                min="1"
+                max="65535"
+                class="settings-input"
+                prop:value=move || companion.get().port.to_string()
+                on:input=move |event| {
+                    let port = input_value(&event).parse().unwrap_or(0);
+                    set_companion.update(|settings| settings.port = port);
+                }
+            />
+            <button class="ghost compact" on:click=save_action>"Save"</button>
+            <button
+                class="ghost compact"
+                disabled=move || !companion.get().enabled
+                on:click=pair_action
+            >
+                "Pair Device"
+            </button>
+        </div>
+        <For
+            each=move || companion.get().devices
+            key=|device| device.id.clone()
+            children=device_view
+        />
+    }
+}
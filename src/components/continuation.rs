@@ -0,0 +1,64 @@
+use crate::api::*;
+use crate::components::settings::input_value;
+use leptos::prelude::*;
+use leptos::task::spawn_local;
+
+#[component]
+pub fn ContinuationRow(
+    continuation: ReadSignal<ContinuationDto>,
+    set_continuation: WriteSignal<ContinuationDto>,
+) -> impl IntoView {
+    let (continuation_status, set_continuation_status) = signal(String::new());
+
+    let save_action = move |_| {
+        let settings = continuation.get();
+        spawn_local(async move {
+            match save_continuation(settings).await {
+                Ok(_) => {
+                    if let Ok(saved) = fetch_continuation().await {
+                        set_continuation.set(saved);
+                    }
+                    set_continuation_status.set("Sentence continuation saved.".to_string());
+                }
+                Err(err) => set_continuation_status
+                    .set(format!("Failed to save sentence continuation: {}", err)),
+            }
+        });
+    };
+
+    view! {
+        <div class="settings-row">
+            <div class="settings-label">
+                <span class="settings-title">"Sentence Continuation"</span>
+                <span class="settings-hint">
+                    "Continue the last sentence typed into the same app within this many seconds instead of starting a new one"
+                </span>
+                <p class="settings-status">{ move || continuation_status.get() }</p>
+            </div>
+            <button
+                class="toggle"
+                class:active=move || continuation.get().enabled
+                on:click=move |_| set_continuation.update(|settings| settings.enabled = !settings.enabled)
+            >
+                <div class="toggle-track"><div class="toggle-thumb"></div></div>
+            </button>
+        </div>
+        <div class="settings-input-group">
+            <input
+                type="number"
+                class="settings-input"
+                min="5"
+                max="600"
+                step="5"
+                aria-label="Continuation window in seconds"
+                prop:value=move || continuation.get().window_secs.to_string()
+                on:input=move |event| {
+                    if let Ok(window_secs) = input_value(&event).parse::<u64>() {
+                        set_continuation.update(|settings| settings.window_secs = window_secs);
+                    }
+                }
+            />
+            <button class="ghost compact" on:click=save_action>"Save"</button>
+        </div>
+    }
+}
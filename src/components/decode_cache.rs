@@ -0,0 +1,58 @@
+use crate::api::*;
+use leptos::prelude::*;
+use leptos::task::spawn_local;
+
+#[component]
+pub fn DecodeCacheRow() -> impl IntoView {
+    let (decode_cache, set_decode_cache) = signal(DecodeCacheDto::default());
+    let (cache_status, set_cache_status) = signal(String::new());
+
+    spawn_local(async move {
+        if let Ok(saved) = fetch_decode_cache().await {
+            set_decode_cache.set(saved);
+        }
+    });
+
+    let toggle_action = move |_| {
+        let mut next = decode_cache.get();
+        next.enabled = !next.enabled;
+        set_decode_cache.set(next.clone());
+        spawn_local(async move {
+            if let Err(err) = save_decode_cache(next).await {
+                set_cache_status.set(format!("Failed to set decode cache: {}", err));
+            }
+        });
+    };
+
+    let clear_action = move |_| {
+        spawn_local(async move {
+            match clear_decode_cache().await {
+                Ok(()) => set_cache_status.set("Decode cache cleared.".to_string()),
+                Err(err) => set_cache_status.set(format!("Failed to clear decode cache: {}", err)),
+            }
+        });
+    };
+
+    view! {
+        <div class="settings-row">
+            <div class="settings-label">
+                <span class="settings-title">"Decode Cache"</span>
+                <span class="settings-hint">
+                    {move || format!(
+                        "Remembers the transcripts of the last {} transcribed files and clips, so unchanged audio returns instantly. History marks entries with identical recordings",
+                        decode_cache.get().max_entries
+                    )}
+                </span>
+                <p class="settings-status">{ move || cache_status.get() }</p>
+            </div>
+            <button class="ghost compact" on:click=clear_action>"Clear Cache"</button>
+            <button
+                class="toggle"
+                class:active=move || decode_cache.get().enabled
+                on:click=toggle_action
+            >
+                <div class="toggle-track"><div class="toggle-thumb"></div></div>
+            </button>
+        </div>
+    }
+}
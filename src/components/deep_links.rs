@@ -0,0 +1,44 @@
+use crate::api::*;
+use leptos::prelude::*;
+use leptos::task::spawn_local;
+
+#[component]
+pub fn DeepLinksRow() -> impl IntoView {
+    let (enabled, set_enabled) = signal(false);
+    let (status, set_status) = signal(String::new());
+
+    spawn_local(async move {
+        if let Ok(saved) = fetch_deep_links_enabled().await {
+            set_enabled.set(saved);
+        }
+    });
+
+    let toggle_action = move |_| {
+        let requested = !enabled.get();
+        set_enabled.set(requested);
+        spawn_local(async move {
+            match save_deep_links_enabled(requested).await {
+                Ok(_) => set_status.set(String::new()),
+                Err(err) => {
+                    set_enabled.set(!requested);
+                    set_status.set(format!("Failed to set links from other apps: {}", err));
+                }
+            }
+        });
+    };
+
+    view! {
+        <div class="settings-row">
+            <div class="settings-label">
+                <span class="settings-title">"Links From Other Apps"</span>
+                <span class="settings-hint">
+                    "Lets other apps and launchers start and stop recordings with silentkeys:// links. Any app or web page can open one while this is on"
+                </span>
+                <p class="settings-status">{ move || status.get() }</p>
+            </div>
+            <button class="toggle" class:active=move || enabled.get() on:click=toggle_action>
+                <div class="toggle-track"><div class="toggle-thumb"></div></div>
+            </button>
+        </div>
+    }
+}
@@ -0,0 +1,92 @@
+use crate::api::*;
+use crate::components::settings::input_value;
+use leptos::prelude::*;
+use leptos::task::spawn_local;
+use wasm_bindgen::JsValue;
+
+const DAY_MS: f64 = 24.0 * 60.0 * 60.0 * 1_000.0;
+
+/// The local calendar date `days_ago` days before today, as YYYY-MM-DD.
+fn local_date(days_ago: u32) -> String {
+    let date = js_sys::Date::new(&JsValue::from_f64(
+        js_sys::Date::now() - f64::from(days_ago) * DAY_MS,
+    ));
+    format!(
+        "{:04}-{:02}-{:02}",
+        date.get_full_year(),
+        date.get_month() + 1,
+        date.get_date()
+    )
+}
+
+#[component]
+pub fn DigestRow() -> impl IntoView {
+    let (from, set_from) = signal(local_date(6));
+    let (to, set_to) = signal(local_date(0));
+    let (digest_status, set_digest_status) = signal(String::new());
+
+    let copy_action = move |_| {
+        let (from, to) = (from.get(), to.get());
+        spawn_local(async move {
+            let result = match export_digest(from, to, None).await {
+                Ok(digest) => copy_to_clipboard(&digest.markdown).await,
+                Err(err) => Err(err),
+            };
+            match result {
+                Ok(_) => set_digest_status.set("Digest copied to the clipboard.".to_string()),
+                Err(err) => set_digest_status.set(format!("Failed to copy digest: {}", err)),
+            }
+        });
+    };
+
+    let save_action = move |_| {
+        let (from, to) = (from.get(), to.get());
+        spawn_local(async move {
+            let folder = match pick_digest_folder_cmd().await {
+                Ok(Some(folder)) => folder,
+                Ok(None) => return,
+                Err(err) => {
+                    set_digest_status.set(format!("Failed to pick folder: {}", err));
+                    return;
+                }
+            };
+            match export_digest(from, to, Some(folder)).await {
+                Ok(digest) => set_digest_status.set(format!(
+                    "Digest saved to {}.",
+                    digest.path.unwrap_or_default()
+                )),
+                Err(err) => set_digest_status.set(format!("Failed to save digest: {}", err)),
+            }
+        });
+    };
+
+    view! {
+        <div class="settings-row">
+            <div class="settings-label">
+                <span class="settings-title">"Dictation Digest"</span>
+                <span class="settings-hint">
+                    "Roll capture file entries between two dates into one Markdown note, grouped by day and hour"
+                </span>
+                <p class="settings-status">{ move || digest_status.get() }</p>
+            </div>
+        </div>
+        <div class="settings-input-group">
+            <input
+                type="date"
+                class="settings-input"
+                aria-label="Digest start date"
+                prop:value=move || from.get()
+                on:input=move |event| set_from.set(input_value(&event))
+            />
+            <input
+                type="date"
+                class="settings-input"
+                aria-label="Digest end date"
+                prop:value=move || to.get()
+                on:input=move |event| set_to.set(input_value(&event))
+            />
+            <button class="ghost compact" on:click=copy_action>"Copy"</button>
+            <button class="ghost compact" on:click=save_action>"Save to Folder"</button>
+        </div>
+    }
+}
@@ -0,0 +1,121 @@
+use crate::api::*;
+use leptos::prelude::*;
+use leptos::task::spawn_local;
+use wasm_bindgen::prelude::*;
+
+#[component]
+pub fn DocumentRow() -> impl IntoView {
+    let (document_status, set_document_status) = signal(String::new());
+
+    let open_action = move |_| {
+        spawn_local(async move {
+            if let Err(err) = open_document_cmd().await {
+                set_document_status.set(format!("Failed to open document: {}", err));
+            }
+        });
+    };
+
+    view! {
+        <div class="settings-row">
+            <div class="settings-label">
+                <span class="settings-title">"Document Mode"</span>
+                <span class="settings-hint">
+                    "Collect dictations in an editor instead of typing them, then type or save the whole document when you finish"
+                </span>
+                <p class="settings-status">{ move || document_status.get() }</p>
+            </div>
+            <button class="ghost compact" on:click=open_action>"Open Document"</button>
+        </div>
+    }
+}
+
+/// The document window: the assembled document as editable Markdown, kept in
+/// step with dictations as they are added.
+#[component]
+pub fn DocumentEditor() -> impl IntoView {
+    let (text, set_text) = signal(String::new());
+    let (status, set_status) = signal(String::new());
+    let (finishing, set_finishing) = signal(false);
+
+    spawn_local(async move {
+        if let Ok(Some(document)) = fetch_document().await {
+            set_text.set(document);
+        }
+    });
+
+    spawn_local(async move {
+        let callback = Closure::wrap(Box::new(move |event: JsValue| {
+            if let Some(document) = js_sys::Reflect::get(&event, &"payload".into())
+                .ok()
+                .and_then(|payload| payload.as_string())
+            {
+                set_text.set(document);
+            }
+        }) as Box<dyn FnMut(JsValue)>);
+
+        if let Err(error) = listen("document_updated", &callback).await {
+            leptos::logging::error!("Failed to listen for document updates: {:?}", error);
+        }
+        callback.forget();
+    });
+
+    let edit_action = move |event: leptos::ev::Event| {
+        let document = event_target_value(&event);
+        set_text.set(document.clone());
+        spawn_local(async move {
+            if let Err(err) = save_document_text(document).await {
+                set_status.set(format!("Failed to keep your edit: {}", err));
+            }
+        });
+    };
+
+    let finish = move |action: DocumentFinishDto| {
+        set_finishing.set(true);
+        spawn_local(async move {
+            match finish_document(action).await {
+                Ok(()) => set_text.set(String::new()),
+                Err(err) => set_status.set(format!("Failed to finish document: {}", err)),
+            }
+            set_finishing.set(false);
+        });
+    };
+
+    let save_action = move |_| {
+        spawn_local(async move {
+            match pick_document_file_cmd().await {
+                Ok(Some(path)) => finish(DocumentFinishDto::Save { path }),
+                Ok(None) => {}
+                Err(err) => set_status.set(format!("Failed to pick file: {}", err)),
+            }
+        });
+    };
+
+    view! {
+        <main class="shell">
+            <section class="card">
+                <div class="card-header"><p class="eyebrow">"Document"</p></div>
+                <p class="settings-hint">
+                    "Dictate as usual. Say \"new paragraph\", \"new line\", \"new heading\", or \"new subheading\" to shape the text."
+                </p>
+                <textarea
+                    class="settings-input document-editor"
+                    aria-label="Document"
+                    prop:value=move || text.get()
+                    on:input=edit_action
+                ></textarea>
+                <p class="settings-status">{ move || status.get() }</p>
+                <div class="settings-input-group">
+                    <button disabled=move || finishing.get() on:click=move |_| finish(DocumentFinishDto::Type)>
+                        "Type into App"
+                    </button>
+                    <button class="ghost compact" disabled=move || finishing.get() on:click=save_action>
+                        "Save as Markdown"
+                    </button>
+                    <button class="danger compact" disabled=move || finishing.get() on:click=move |_| finish(DocumentFinishDto::Discard)>
+                        "Discard"
+                    </button>
+                </div>
+            </section>
+        </main>
+    }
+}
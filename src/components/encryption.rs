@@ -0,0 +1,49 @@
+use crate::api::*;
+use leptos::prelude::*;
+use leptos::task::spawn_local;
+
+#[component]
+pub fn EncryptionRow() -> impl IntoView {
+    let (enabled, set_enabled) = signal(false);
+    let (encryption_status, set_encryption_status) = signal(String::new());
+
+    let refresh = move || {
+        spawn_local(async move {
+            if let Ok(saved) = fetch_encrypt_at_rest().await {
+                set_enabled.set(saved);
+            }
+        });
+    };
+    refresh();
+
+    let toggle_action = move |_| {
+        let requested = !enabled.get();
+        set_enabled.set(requested);
+        spawn_local(async move {
+            match save_encrypt_at_rest(requested).await {
+                Ok(_) if requested => set_encryption_status
+                    .set("Saved clips are encrypted from their next write; the capture file stays plain Markdown.".to_string()),
+                Ok(_) => set_encryption_status.set(
+                    "New writes are stored unencrypted. Encrypted files stay readable.".to_string(),
+                ),
+                Err(err) => set_encryption_status.set(format!("Failed to set encryption: {}", err)),
+            }
+            refresh();
+        });
+    };
+
+    view! {
+        <div class="settings-row">
+            <div class="settings-label">
+                <span class="settings-title">"Encrypt History"</span>
+                <span class="settings-hint">
+                    "Encrypts saved clips and the decode cache with a key kept in the system keychain. The capture file is your note, so it stays plain Markdown"
+                </span>
+                <p class="settings-status">{ move || encryption_status.get() }</p>
+            </div>
+            <button class="toggle" class:active=move || enabled.get() on:click=toggle_action>
+                <div class="toggle-track"><div class="toggle-thumb"></div></div>
+            </button>
+        </div>
+    }
+}
@@ -0,0 +1,121 @@
+use crate::api::*;
+use leptos::prelude::*;
+use leptos::task::spawn_local;
+use wasm_bindgen::prelude::*;
+
+/// Transcribes a picked WAV file, showing each segment as it is decoded so a
+/// long file's transcript builds up and the wrong file can be cancelled early.
+#[component]
+pub fn FileTranscriptionRow(is_recording: ReadSignal<bool>) -> impl IntoView {
+    let (path, set_path) = signal(None::<String>);
+    let (segments, set_segments) = signal(Vec::<String>::new());
+    let (progress, set_progress) = signal(None::<(usize, usize)>);
+    let (transcript, set_transcript) = signal(String::new());
+    let (status, set_status) = signal(String::new());
+
+    spawn_local(async move {
+        let callback = Closure::wrap(Box::new(move |event: JsValue| {
+            let Ok(payload) = js_sys::Reflect::get(&event, &"payload".into()) else {
+                return;
+            };
+            let update =
+                match serde_wasm_bindgen::from_value::<FileTranscriptionProgressDto>(payload) {
+                    Ok(update) => update,
+                    Err(error) => {
+                        leptos::logging::error!("Failed to parse file progress: {:?}", error);
+                        return;
+                    }
+                };
+            if path.get_untracked().as_deref() != Some(update.path.as_str()) {
+                return;
+            }
+            set_progress.set(Some((update.completed, update.total)));
+            if let Some(segment) = update.segment {
+                set_segments.update(|segments| segments.push(segment.text));
+            }
+        }) as Box<dyn FnMut(JsValue)>);
+
+        if let Err(error) = listen("file_transcription_progress", &callback).await {
+            leptos::logging::error!("Failed to listen for file progress: {:?}", error);
+        }
+        callback.forget();
+    });
+
+    let transcribe_action = move |_| {
+        spawn_local(async move {
+            let picked = match pick_audio_file_cmd().await {
+                Ok(Some(picked)) => picked,
+                Ok(None) => return,
+                Err(err) => {
+                    set_status.set(format!("Failed to pick file: {}", err));
+                    return;
+                }
+            };
+            set_segments.set(Vec::new());
+            set_progress.set(None);
+            set_transcript.set(String::new());
+            set_status.set(String::new());
+            set_path.set(Some(picked.clone()));
+            match transcribe_audio_file(picked).await {
+                Ok(text) => set_transcript.set(text),
+                Err(err) => set_status.set(format!("Transcription stopped: {}", err)),
+            }
+            set_path.set(None);
+        });
+    };
+
+    let cancel_action = move |_| {
+        spawn_local(async move {
+            if let Err(err) = cancel_file_transcription_cmd().await {
+                set_status.set(format!("Failed to cancel: {}", err));
+            }
+        });
+    };
+
+    let running = move || path.get().is_some();
+    let shown_text = move || {
+        let finished = transcript.get();
+        if finished.is_empty() {
+            segments.get().join(" ")
+        } else {
+            finished
+        }
+    };
+
+    view! {
+        <div class="settings-row">
+            <div class="settings-label">
+                <span class="settings-title">"Transcribe File"</span>
+                <span class="settings-hint">
+                    "Transcribe a WAV recording with your dictation settings. The transcript appears segment by segment as it is decoded."
+                </span>
+                <Show when=move || progress.get().is_some()>
+                    <p class="settings-status">
+                        { move || progress.get().map(|(completed, total)| format!("Segment {completed} of {total}")) }
+                    </p>
+                </Show>
+                <Show when=move || !shown_text().is_empty()>
+                    <textarea
+                        class="settings-input file-transcript"
+                        aria-label="File transcript"
+                        readonly
+                        prop:value=shown_text
+                    ></textarea>
+                </Show>
+                <p class="settings-status">{ move || status.get() }</p>
+            </div>
+            <div class="settings-input-group">
+                <button
+                    class="ghost compact"
+                    disabled=move || running() || is_recording.get()
+                    on:click=transcribe_action
+                >
+                    "Choose WAV"
+                </button>
+                <Show when=running>
+                    <button class="danger compact" on:click=cancel_action>"Cancel"</button>
+                </Show>
+            </div>
+        </div>
+    }
+}
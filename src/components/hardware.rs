@@ -0,0 +1,106 @@
+use crate::api::*;
+use leptos::prelude::*;
+use leptos::task::spawn_local;
+
+const GIB: f64 = 1_073_741_824.0;
+
+fn simd_label(simd: &str) -> &str {
+    match simd {
+        "avx2" => "AVX2",
+        "avx512" => "AVX-512",
+        "neon" => "NEON",
+        _ => "no SIMD",
+    }
+}
+
+fn hardware_summary(detection: &HardwareDetectionDto) -> String {
+    let profile = &detection.profile;
+    let mut parts = vec![
+        format!("{} cores", profile.cores),
+        simd_label(&profile.simd).to_string(),
+    ];
+    if let Some(bytes) = profile.memory_bytes {
+        parts.push(format!("{:.0} GB memory", bytes as f64 / GIB));
+    }
+    match profile.gpu {
+        Some(true) => parts.push("GPU".to_string()),
+        Some(false) => parts.push("no GPU".to_string()),
+        None => {}
+    }
+    parts.join(", ")
+}
+
+fn recommendation_summary(recommendation: &HardwareRecommendationDto) -> String {
+    let streaming = if recommendation.streaming_enabled {
+        "streaming on"
+    } else {
+        "streaming off"
+    };
+    let model = if recommendation.precision == "fp32" {
+        "int8 model (full precision would suit this machine but is not published)"
+    } else {
+        "int8 model"
+    };
+    format!(
+        "Recommended: {}, {} resampler, {}",
+        streaming, recommendation.resampler_quality, model
+    )
+}
+
+#[component]
+pub fn HardwareRow(
+    set_streaming_enabled: WriteSignal<bool>,
+    set_resampler_quality: WriteSignal<String>,
+    is_recording: ReadSignal<bool>,
+) -> impl IntoView {
+    let (detection, set_detection) = signal::<Option<HardwareDetectionDto>>(None);
+    let (hardware_status, set_hardware_status) = signal(String::new());
+    let (detecting, set_detecting) = signal(false);
+
+    spawn_local(async move {
+        if let Ok(saved) = fetch_hardware_detection().await {
+            set_detection.set(saved);
+        }
+    });
+
+    let detect_action = move |_| {
+        set_detecting.set(true);
+        spawn_local(async move {
+            match detect_hardware_cmd().await {
+                Ok(detected) => {
+                    set_streaming_enabled.set(detected.recommendation.streaming_enabled);
+                    set_resampler_quality.set(detected.recommendation.resampler_quality.clone());
+                    set_detection.set(Some(detected));
+                    set_hardware_status.set("Recommended settings applied.".to_string());
+                }
+                Err(err) => set_hardware_status.set(format!("Failed to detect hardware: {}", err)),
+            }
+            set_detecting.set(false);
+        });
+    };
+
+    view! {
+        <div class="settings-row">
+            <div class="settings-label">
+                <span class="settings-title">"Hardware"</span>
+                <span class="settings-hint">
+                    {move || match detection.get() {
+                        Some(detection) => hardware_summary(&detection),
+                        None => "Not detected yet".to_string(),
+                    }}
+                </span>
+                <span class="settings-hint">
+                    {move || detection.get().map(|detection| recommendation_summary(&detection.recommendation))}
+                </span>
+                <p class="settings-status">{ move || hardware_status.get() }</p>
+            </div>
+            <button
+                class="ghost compact"
+                disabled=move || detecting.get() || is_recording.get()
+                on:click=detect_action
+            >
+                "Re-run Detection"
+            </button>
+        </div>
+    }
+}
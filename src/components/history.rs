@@ -0,0 +1,240 @@
+use crate::api::*;
+use crate::components::settings::input_value;
+use leptos::prelude::*;
+use leptos::task::spawn_local;
+
+/// The capture file's entries, newest first, with their titles editable.
+#[component]
+pub fn HistoryRow() -> impl IntoView {
+    let (entries, set_entries) = signal(Vec::<HistoryEntryDto>::new());
+    let (history_status, set_history_status) = signal(String::new());
+
+    let refresh = move || {
+        spawn_local(async move {
+            match fetch_history_entries().await {
+                Ok(mut loaded) => {
+                    loaded.reverse();
+                    if loaded.is_empty() {
+                        set_history_status.set("No entries in the capture file yet.".to_string());
+                    }
+                    set_entries.set(loaded);
+                }
+                Err(err) => set_history_status.set(format!("Failed to load history: {}", err)),
+            }
+        });
+    };
+
+    let utterance_view = move |index: usize, utterance: HistoryUtteranceDto| {
+        let (text, set_text) = signal(utterance.text);
+        let number = utterance.index;
+        view! {
+            <div class="settings-input-group">
+                <span class="settings-hint">{utterance.at}</span>
+                <span class="settings-hint">{ move || text.get() }</span>
+                <button
+                    class="ghost compact"
+                    on:click=move |_| {
+                        set_history_status.set("Transcribing utterance...".to_string());
+                        spawn_local(async move {
+                            match retranscribe_history_utterance(index, number).await {
+                                Ok(updated) => {
+                                    set_text.set(updated);
+                                    set_history_status.set("Utterance transcribed again.".to_string());
+                                }
+                                Err(err) => set_history_status
+                                    .set(format!("Failed to transcribe utterance: {}", err)),
+                            }
+                        });
+                    }
+                >
+                    "Re-transcribe"
+                </button>
+            </div>
+        }
+    };
+
+    let retry_view = move |index: usize| {
+        let (language, set_language) = signal(String::new());
+        let (speed, set_speed) = signal(String::new());
+        let (raw, set_raw) = signal(false);
+        let (retry, set_retry) = signal(None::<RetranscriptionDto>);
+
+        let compare = move |_| {
+            let speed = speed.get();
+            let config = RetranscribeConfigDto {
+                language: Some(language.get().trim().to_string()).filter(|code| !code.is_empty()),
+                time_stretch: speed.trim().parse::<u32>().ok().map(|speed_percent| {
+                    TimeStretchDto {
+                        enabled: true,
+                        speed_percent,
+                    }
+                }),
+                raw: raw.get(),
+            };
+            set_history_status.set("Transcribing entry again...".to_string());
+            spawn_local(async move {
+                match retranscribe_history_entry(index, config).await {
+                    Ok(result) => {
+                        set_history_status.set("Compare the transcripts below.".to_string());
+                        set_retry.set(Some(result));
+                    }
+                    Err(err) => {
+                        set_history_status.set(format!("Failed to transcribe entry: {}", err))
+                    }
+                }
+            });
+        };
+
+        let keep = move |_| {
+            let Some(result) = retry.get() else {
+                return;
+            };
+            spawn_local(async move {
+                match save_history_utterances(index, result.retranscribed).await {
+                    Ok(_) => {
+                        set_history_status.set("Entry updated.".to_string());
+                        set_retry.set(None);
+                        refresh();
+                    }
+                    Err(err) => set_history_status.set(format!("Failed to update entry: {}", err)),
+                }
+            });
+        };
+
+        view! {
+            <div class="settings-input-group">
+                <input
+                    type="text"
+                    class="settings-input"
+                    placeholder="Language"
+                    aria-label="Language to transcribe with"
+                    prop:value=move || language.get()
+                    on:input=move |event| set_language.set(input_value(&event))
+                />
+                <input
+                    type="number"
+                    class="settings-input"
+                    min="50"
+                    max="100"
+                    step="5"
+                    placeholder="Speed %"
+                    aria-label="Playback speed in percent"
+                    prop:value=move || speed.get()
+                    on:input=move |event| set_speed.set(input_value(&event))
+                />
+                <button
+                    class="toggle"
+                    class:active=move || raw.get()
+                    title="Skip post-processing"
+                    on:click=move |_| set_raw.update(|raw| *raw = !*raw)
+                >
+                    <div class="toggle-track"><div class="toggle-thumb"></div></div>
+                </button>
+                <button class="ghost compact" on:click=compare>"Compare"</button>
+            </div>
+            {move || {
+                retry
+                    .get()
+                    .map(|result| {
+                        view! {
+                            <div class="history-diff">
+                                <p class="history-diff-column">{diff_words(&result.diff, false)}</p>
+                                <p class="history-diff-column">{diff_words(&result.diff, true)}</p>
+                            </div>
+                            <div class="settings-input-group">
+                                <button class="ghost compact" on:click=keep>"Keep New"</button>
+                                <button class="ghost compact" on:click=move |_| set_retry.set(None)>
+                                    "Discard"
+                                </button>
+                            </div>
+                        }
+                    })
+            }}
+        }
+    };
+
+    let entry_view = move |entry: HistoryEntryDto| {
+        let (title, set_title) = signal(entry.title.clone().unwrap_or_default());
+        let index = entry.index;
+        let duplicate = entry.duplicate_of.map(|original| {
+            let at = entries.with_untracked(|all| {
+                all.iter()
+                    .find(|other| other.index == original)
+                    .map(|other| other.at.clone())
+                    .unwrap_or_default()
+            });
+            view! { <p class="settings-hint">{format!("Same recording as the entry from {at}.")}</p> }
+        });
+        let retry = (!entry.utterances.is_empty()).then(|| retry_view(index));
+        let utterances = entry
+            .utterances
+            .into_iter()
+            .map(|utterance| utterance_view(index, utterance))
+            .collect_view();
+        view! {
+            <div class="settings-input-group">
+                <span class="settings-hint">{entry.at.clone()}</span>
+                <input
+                    type="text"
+                    class="settings-input"
+                    placeholder=entry.preview.clone()
+                    prop:value=move || title.get()
+                    on:input=move |event| set_title.set(input_value(&event))
+                />
+                <button
+                    class="ghost compact"
+                    on:click=move |_| {
+                        let title = title.get();
+                        spawn_local(async move {
+                            match rename_history_entry(index, title).await {
+                                Ok(_) => {
+                                    set_history_status.set("Entry renamed.".to_string());
+                                    refresh();
+                                }
+                                Err(err) => set_history_status
+                                    .set(format!("Failed to rename entry: {}", err)),
+                            }
+                        });
+                    }
+                >
+                    "Rename"
+                </button>
+            </div>
+            {duplicate}
+            {utterances}
+            {retry}
+        }
+    };
+
+    view! {
+        <div class="settings-row">
+            <div class="settings-label">
+                <span class="settings-title">"History"</span>
+                <span class="settings-hint">
+                    "Browse capture file entries and rename them; a blank title leaves only the timestamp"
+                </span>
+                <p class="settings-status">{ move || history_status.get() }</p>
+            </div>
+            <button class="ghost compact" on:click=move |_| refresh()>"Load"</button>
+        </div>
+        <For
+            each=move || entries.get()
+            key=|entry| (entry.index, entry.at.clone(), entry.title.clone())
+            children=entry_view
+        />
+    }
+}
+
+/// One side of a comparison: the original keeps removed words, the new
+/// transcript keeps added ones, each marked.
+fn diff_words(diff: &[DiffWordDto], new: bool) -> impl IntoView {
+    diff.iter()
+        .filter_map(|word| match word {
+            DiffWordDto::Same(text) => Some((text.clone(), "")),
+            DiffWordDto::Removed(text) if !new => Some((text.clone(), "diff-removed")),
+            DiffWordDto::Added(text) if new => Some((text.clone(), "diff-added")),
+            _ => None,
+        })
+        .map(|(text, class)| view! { <span class=class>{text}</span>" " })
+        .collect_view()
+}
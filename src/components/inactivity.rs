@@ -0,0 +1,79 @@
+use crate::api::*;
+use crate::components::settings::input_value;
+use leptos::prelude::*;
+use leptos::task::spawn_local;
+
+#[component]
+pub fn InactivityRow(
+    inactivity: ReadSignal<InactivityDto>,
+    set_inactivity: WriteSignal<InactivityDto>,
+) -> impl IntoView {
+    let (inactivity_status, set_inactivity_status) = signal(String::new());
+
+    let save_action = move |_| {
+        let settings = inactivity.get();
+        spawn_local(async move {
+            match save_inactivity(settings).await {
+                Ok(_) => {
+                    if let Ok(saved) = fetch_inactivity().await {
+                        set_inactivity.set(saved);
+                    }
+                    set_inactivity_status.set("Inactivity timeout saved.".to_string());
+                }
+                Err(err) => {
+                    set_inactivity_status.set(format!("Failed to save inactivity timeout: {}", err))
+                }
+            }
+        });
+    };
+
+    view! {
+        <div class="settings-row">
+            <div class="settings-label">
+                <span class="settings-title">"Inactivity Timeout"</span>
+                <span class="settings-hint">
+                    "Warn after this many minutes without speech in a latched recording, then finalize it unless you keep going within the grace seconds"
+                </span>
+                <p class="settings-status">{ move || inactivity_status.get() }</p>
+            </div>
+            <button
+                class="toggle"
+                class:active=move || inactivity.get().enabled
+                on:click=move |_| set_inactivity.update(|settings| settings.enabled = !settings.enabled)
+            >
+                <div class="toggle-track"><div class="toggle-thumb"></div></div>
+            </button>
+        </div>
+        <div class="settings-input-group">
+            <input
+                type="number"
+                class="settings-input"
+                min="1"
+                max="120"
+                step="1"
+                aria-label="Minutes without speech"
+                prop:value=move || inactivity.get().minutes.to_string()
+                on:input=move |event| {
+                    if let Ok(minutes) = input_value(&event).parse::<u64>() {
+                        set_inactivity.update(|settings| settings.minutes = minutes);
+                    }
+                }
+            />
+            <input
+                type="number"
+                class="settings-input"
+                min="10"
+                max="300"
+                step="5"
+                aria-label="Grace seconds before finalizing"
+                prop:value=move || inactivity.get().grace_secs.to_string()
+                on:input=move |event| {
+                    if let Ok(grace_secs) = input_value(&event).parse::<u64>() {
+                        set_inactivity.update(|settings| settings.grace_secs = grace_secs);
+                    }
+                }
+            />
+            <button class="ghost compact" on:click=save_action>"Save"</button>
+        </div>
+    }
+}
@@ -0,0 +1,164 @@
+use crate::api::*;
+use crate::components::settings::{input_value, select_value};
+use leptos::prelude::*;
+use leptos::task::spawn_local;
+
+/// Channels are shown 1-based, as audio interfaces label them.
+fn channels_text(mask: u64) -> String {
+    (0..u64::BITS)
+        .filter(|channel| mask & (1 << channel) != 0)
+        .map(|channel| (channel + 1).to_string())
+        .collect::<Vec<_>>()
+        .join(", ")
+}
+
+fn parse_channels(text: &str) -> u64 {
+    text.split(|c: char| c == ',' || c.is_whitespace())
+        .filter_map(|channel| channel.parse::<u32>().ok())
+        .filter(|channel| (1..=u64::BITS).contains(channel))
+        .fold(0, |mask, channel| mask | 1 << (channel - 1))
+}
+
+fn parse_optional(text: &str) -> Option<u32> {
+    text.trim().parse().ok().filter(|value| *value > 0)
+}
+
+fn optional_text(value: Option<u32>) -> String {
+    value.map(|value| value.to_string()).unwrap_or_default()
+}
+
+#[component]
+pub fn InputDeviceRow(
+    input_device: ReadSignal<InputDeviceDto>,
+    set_input_device: WriteSignal<InputDeviceDto>,
+    is_recording: ReadSignal<bool>,
+) -> impl IntoView {
+    let (devices, set_devices) = signal(Vec::<String>::new());
+    let (device_status, set_device_status) = signal(String::new());
+
+    let refresh_devices = move || {
+        spawn_local(async move {
+            match list_input_devices().await {
+                Ok(names) => set_devices.set(names),
+                Err(err) => set_device_status.set(format!("Failed to list input devices: {}", err)),
+            }
+        });
+    };
+    refresh_devices();
+
+    let selected_config = move || {
+        let settings = input_device.get();
+        settings
+            .device
+            .as_ref()
+            .and_then(|device| settings.configs.get(device).copied())
+            .unwrap_or_default()
+    };
+    let update_config = move |change: &dyn Fn(&mut InputDeviceConfigDto)| {
+        set_input_device.update(|settings| {
+            if let Some(device) = settings.device.clone() {
+                change(settings.configs.entry(device).or_default());
+            }
+        });
+    };
+
+    let save_action = move |_| {
+        let settings = input_device.get();
+        spawn_local(async move {
+            match save_input_device(settings).await {
+                Ok(_) => {
+                    if let Ok(saved) = fetch_input_device().await {
+                        set_input_device.set(saved);
+                    }
+                    set_device_status.set("Input device saved.".to_string());
+                }
+                Err(err) => set_device_status.set(format!("Failed to save input device: {}", err)),
+            }
+        });
+    };
+
+    view! {
+        <div class="settings-row">
+            <div class="settings-label">
+                <span class="settings-title">"Input Device"</span>
+                <span class="settings-hint">
+                    "Record from a specific device; optionally request a sample rate, pick the channels to mix (such as 1, 2), and set a buffer size in frames"
+                </span>
+                <p class="settings-status">{ move || device_status.get() }</p>
+            </div>
+            <select
+                class="settings-input settings-select"
+                prop:value=move || input_device.get().device.unwrap_or_default()
+                disabled=move || is_recording.get()
+                on:focus=move |_| refresh_devices()
+                on:change=move |event| {
+                    let device = select_value(&event);
+                    set_input_device.update(|settings| {
+                        settings.device = (!device.is_empty()).then_some(device);
+                    });
+                }
+            >
+                <option value="">"System default"</option>
+                <For
+                    each=move || {
+                        let mut names = devices.get();
+                        if let Some(selected) = input_device.get().device {
+                            if !names.contains(&selected) {
+                                names.push(selected);
+                            }
+                        }
+                        names
+                    }
+                    key=|name| name.clone()
+                    children=move |name| {
+                        let label = name.clone();
+                        view! { <option value=name>{label}</option> }
+                    }
+                />
+            </select>
+        </div>
+        <div class="settings-input-group">
+            <input
+                type="number"
+                class="settings-input"
+                min="8000"
+                max="384000"
+                placeholder="Default rate"
+                aria-label="Sample rate in Hz"
+                disabled=move || input_device.get().device.is_none()
+                prop:value=move || optional_text(selected_config().sample_rate)
+                on:input=move |event| {
+                    let sample_rate = parse_optional(&input_value(&event));
+                    update_config(&|config| config.sample_rate = sample_rate);
+                }
+            />
+            <input
+                type="text"
+                class="settings-input"
+                placeholder="All channels"
+                aria-label="Channels to mix"
+                disabled=move || input_device.get().device.is_none()
+                prop:value=move || channels_text(selected_config().channel_mask)
+                on:change=move |event| {
+                    let channel_mask = parse_channels(&input_value(&event));
+                    update_config(&|config| config.channel_mask = channel_mask);
+                }
+            />
+            <input
+                type="number"
+                class="settings-input"
+                min="32"
+                max="16384"
+                placeholder="Default buffer"
+                aria-label="Buffer size in frames"
+                disabled=move || input_device.get().device.is_none()
+                prop:value=move || optional_text(selected_config().buffer_frames)
+                on:input=move |event| {
+                    let buffer_frames = parse_optional(&input_value(&event));
+                    update_config(&|config| config.buffer_frames = buffer_frames);
+                }
+            />
+            <button class="ghost compact" on:click=save_action>"Save"</button>
+        </div>
+    }
+}
@@ -0,0 +1,90 @@
+use crate::api::*;
+use crate::components::settings::{input_value, select_value};
+use leptos::prelude::*;
+use leptos::task::spawn_local;
+
+#[component]
+pub fn LlmHandoffRow(
+    llm_handoff: ReadSignal<LlmHandoffDto>,
+    set_llm_handoff: WriteSignal<LlmHandoffDto>,
+) -> impl IntoView {
+    let (handoff_status, set_handoff_status) = signal(String::new());
+
+    let save_action = move |_| {
+        let settings = llm_handoff.get();
+        spawn_local(async move {
+            match save_llm_handoff(settings).await {
+                Ok(_) => {
+                    if let Ok(saved) = fetch_llm_handoff().await {
+                        set_llm_handoff.set(saved);
+                    }
+                    set_handoff_status.set("LLM handoff saved.".to_string());
+                }
+                Err(err) => set_handoff_status.set(format!("Failed to save LLM handoff: {}", err)),
+            }
+        });
+    };
+
+    view! {
+        <div class="settings-row">
+            <div class="settings-label">
+                <span class="settings-title">"LLM Handoff"</span>
+                <span class="settings-hint">
+                    "Alt+X records and types a local model's rewrite; off sends nothing"
+                </span>
+                <p class="settings-status">{ move || handoff_status.get() }</p>
+            </div>
+            <button
+                class="toggle"
+                class:active=move || llm_handoff.get().enabled
+                on:click=move |_| set_llm_handoff.update(|settings| settings.enabled = !settings.enabled)
+            >
+                <div class="toggle-track"><div class="toggle-thumb"></div></div>
+            </button>
+        </div>
+        <div class="settings-input-group">
+            <input
+                type="url"
+                class="settings-input"
+                placeholder="http://localhost:11434/v1"
+                prop:value=move || llm_handoff.get().endpoint
+                on:input=move |event| {
+                    let endpoint = input_value(&event);
+                    set_llm_handoff.update(|settings| settings.endpoint = endpoint);
+                }
+            />
+            <input
+                type="text"
+                class="settings-input"
+                placeholder="Model"
+                prop:value=move || llm_handoff.get().model
+                on:input=move |event| {
+                    let model = input_value(&event);
+                    set_llm_handoff.update(|settings| settings.model = model);
+                }
+            />
+            <input
+                type="password"
+                class="settings-input"
+                placeholder="API key (optional)"
+                prop:value=move || llm_handoff.get().api_key
+                on:input=move |event| {
+                    let api_key = input_value(&event);
+                    set_llm_handoff.update(|settings| settings.api_key = api_key);
+                }
+            />
+            <select
+                class="settings-input settings-select"
+                prop:value=move || llm_handoff.get().template
+                on:change=move |event| {
+                    let template = select_value(&event);
+                    set_llm_handoff.update(|settings| settings.template = template);
+                }
+            >
+                <option value="rewrite_formally">"Rewrite formally"</option>
+                <option value="summarize">"Summarize"</option>
+            </select>
+            <button class="ghost compact" on:click=save_action>"Save"</button>
+        </div>
+    }
+}
@@ -0,0 +1,105 @@
+use crate::api::*;
+use crate::components::settings::select_value;
+use leptos::prelude::*;
+use leptos::task::spawn_local;
+
+const LOG_VIEW_LIMIT: usize = 200;
+
+fn format_entry(entry: &LogEntryDto) -> String {
+    format!(
+        "{} {:<5} {}: {}",
+        entry.timestamp,
+        entry.level.to_uppercase(),
+        entry.target,
+        entry.message
+    )
+}
+
+#[component]
+pub fn LogViewer() -> impl IntoView {
+    let (level, set_level) = signal("info".to_string());
+    let (entries, set_entries) = signal(Vec::<LogEntryDto>::new());
+    let (log_status, set_log_status) = signal(String::new());
+
+    let refresh = move || {
+        let level = level.get_untracked();
+        spawn_local(async move {
+            match fetch_recent_logs(level, LOG_VIEW_LIMIT).await {
+                Ok(recent) => {
+                    set_log_status.set(format!("{} recent entries.", recent.len()));
+                    set_entries.set(recent);
+                }
+                Err(err) => set_log_status.set(format!("Failed to load logs: {}", err)),
+            }
+        });
+    };
+
+    refresh();
+
+    let copy_action = move |_| {
+        let text = entries
+            .get()
+            .iter()
+            .map(format_entry)
+            .collect::<Vec<_>>()
+            .join("\n");
+        spawn_local(async move {
+            match copy_to_clipboard(&text).await {
+                Ok(_) => set_log_status.set("Logs copied to the clipboard.".to_string()),
+                Err(err) => set_log_status.set(format!("Failed to copy logs: {}", err)),
+            }
+        });
+    };
+
+    let report_action = move |_| {
+        set_log_status.set("Reading the speech model...".to_string());
+        spawn_local(async move {
+            let copied = match fetch_model_signature_report().await {
+                Ok(report) => copy_to_clipboard(&report).await,
+                Err(err) => Err(err),
+            };
+            match copied {
+                Ok(_) => set_log_status.set("Model report copied to the clipboard.".to_string()),
+                Err(err) => set_log_status.set(format!("Failed to copy model report: {}", err)),
+            }
+        });
+    };
+
+    view! {
+        <div class="settings-row">
+            <div class="settings-label">
+                <span class="settings-title">"Recent Logs"</span>
+                <span class="settings-hint">"Paste these into bug reports; they stay on this device"</span>
+                <p class="settings-status">{ move || log_status.get() }</p>
+            </div>
+            <div class="settings-input-group">
+                <select
+                    class="settings-input settings-select"
+                    prop:value=move || level.get()
+                    on:change=move |event| {
+                        set_level.set(select_value(&event));
+                        refresh();
+                    }
+                >
+                    <option value="error">"Errors"</option>
+                    <option value="warn">"Warnings"</option>
+                    <option value="info">"Info"</option>
+                    <option value="debug">"Debug"</option>
+                    <option value="trace">"Trace"</option>
+                </select>
+                <button class="ghost compact" on:click=move |_| refresh()>"Refresh"</button>
+                <button
+                    class="ghost compact"
+                    disabled=move || entries.get().is_empty()
+                    on:click=copy_action
+                >
+                    "Copy"
+                </button>
+                <button class="ghost compact" on:click=report_action>"Copy Model Report"</button>
+            </div>
+        </div>
+        <pre class="log-view">
+            { move || entries.get().iter().map(format_entry).collect::<Vec<_>>().join("\n") }
+        </pre>
+    }
+}
@@ -0,0 +1,94 @@
+use crate::api::*;
+use crate::components::settings::input_value;
+use leptos::prelude::*;
+use leptos::task::spawn_local;
+
+#[component]
+pub fn LookbackRow() -> impl IntoView {
+    let (lookback, set_lookback) = signal(LookbackDto::default());
+    let (lookback_status, set_lookback_status) = signal(String::new());
+
+    spawn_local(async move {
+        if let Ok(saved) = fetch_lookback().await {
+            set_lookback.set(saved);
+        }
+    });
+
+    let save = move |next: LookbackDto| {
+        spawn_local(async move {
+            match save_lookback(next).await {
+                Ok(_) => {
+                    if let Ok(saved) = fetch_lookback().await {
+                        set_lookback.set(saved);
+                    }
+                    set_lookback_status.set("Lookback saved.".to_string());
+                }
+                Err(err) => set_lookback_status.set(format!("Failed to save lookback: {}", err)),
+            }
+        });
+    };
+
+    let toggle_action = move |_| {
+        let mut next = lookback.get();
+        next.enabled = !next.enabled;
+        set_lookback.set(next.clone());
+        save(next);
+    };
+
+    view! {
+        <div class="settings-row">
+            <div class="settings-label">
+                <span class="settings-title">"Lookback"</span>
+                <span class="settings-hint">
+                    {move || {
+                        let lookback = lookback.get();
+                        format!(
+                            "Keep listening into a {} second buffer in memory; Alt+Shift+X transcribes the last {} seconds of it. The microphone stays open while this is on",
+                            lookback.buffer_secs,
+                            lookback.transcribe_secs
+                        )
+                    }}
+                </span>
+                <p class="settings-status">{ move || lookback_status.get() }</p>
+            </div>
+            <button
+                class="toggle"
+                class:active=move || lookback.get().enabled
+                on:click=toggle_action
+            >
+                <div class="toggle-track"><div class="toggle-thumb"></div></div>
+            </button>
+        </div>
+        <div class="settings-input-group">
+            <input
+                type="number"
+                class="settings-input"
+                min="10"
+                max="600"
+                step="10"
+                aria-label="Seconds kept in the lookback buffer"
+                prop:value=move || lookback.get().buffer_secs.to_string()
+                on:input=move |event| {
+                    if let Ok(buffer_secs) = input_value(&event).parse::<u32>() {
+                        set_lookback.update(|settings| settings.buffer_secs = buffer_secs);
+                    }
+                }
+            />
+            <input
+                type="number"
+                class="settings-input"
+                min="5"
+                max="600"
+                step="5"
+                aria-label="Seconds the lookback shortcut transcribes"
+                prop:value=move || lookback.get().transcribe_secs.to_string()
+                on:input=move |event| {
+                    if let Ok(transcribe_secs) = input_value(&event).parse::<u32>() {
+                        set_lookback.update(|settings| settings.transcribe_secs = transcribe_secs);
+                    }
+                }
+            />
+            <button class="ghost compact" on:click=move |_| save(lookback.get())>"Save"</button>
+        </div>
+    }
+}
@@ -0,0 +1,103 @@
+use crate::api::*;
+use crate::components::recorder::level_percent;
+use leptos::prelude::*;
+use leptos::task::spawn_local;
+
+pub fn levels_text(test: &MicrophoneTestDto) -> String {
+    let mut text = format!(
+        "Peak {:.0} dBFS, average {:.0} dBFS over {:.1} s.",
+        test.peak_dbfs,
+        test.rms_dbfs,
+        test.duration_ms as f32 / 1000.0
+    );
+    if test.too_quiet {
+        text.push_str(" Barely any sound reached the app; check the input device and gain.");
+    }
+    text
+}
+
+#[component]
+pub fn MicrophoneTestRow(is_recording: ReadSignal<bool>) -> impl IntoView {
+    let (test_status, set_test_status) = signal(String::new());
+    let (busy, set_busy) = signal(false);
+    let (test, set_test) = signal::<Option<MicrophoneTestDto>>(None);
+
+    let play = move || {
+        spawn_local(async move {
+            set_busy.set(true);
+            set_test_status.set("Playing back the 16 kHz take...".to_string());
+            match play_microphone_test_cmd().await {
+                Ok(()) => {
+                    if let Some(result) = test.get_untracked() {
+                        set_test_status.set(levels_text(&result));
+                    }
+                }
+                Err(err) => set_test_status.set(format!("Playback failed: {}", err)),
+            }
+            set_busy.set(false);
+        });
+    };
+
+    let test_action = move |_| {
+        set_busy.set(true);
+        set_test.set(None);
+        set_test_status
+            .set("Recording 3 seconds. Speak as you would when dictating...".to_string());
+        spawn_local(async move {
+            match test_microphone_cmd().await {
+                Ok(result) => {
+                    set_test_status.set(levels_text(&result));
+                    set_test.set(Some(result));
+                    play();
+                }
+                Err(err) => {
+                    set_test_status.set(format!("Microphone test failed: {}", err));
+                    set_busy.set(false);
+                }
+            }
+        });
+    };
+
+    view! {
+        <div class="settings-row">
+            <div class="settings-label">
+                <span class="settings-title">"Microphone Test"</span>
+                <span class="settings-hint">
+                    "Records 3 seconds with your input settings, then plays back the 16 kHz audio the speech model hears"
+                </span>
+                <p class="settings-status">{ move || test_status.get() }</p>
+                {move || test.get().and_then(|result| result.quality).map(|quality| view! {
+                    <ul class="settings-hint">
+                        {quality.suggestions.into_iter().map(|suggestion| view! {
+                            <li>{suggestion}</li>
+                        }).collect_view()}
+                    </ul>
+                })}
+                {move || test.get().map(|result| view! {
+                    <div class="waveform" aria-hidden="true">
+                        {result.preview.into_iter().map(|peak| view! {
+                            <div
+                                class="waveform-bar"
+                                style:height=format!("{:.0}%", level_percent(peak).max(4.0))
+                            ></div>
+                        }).collect_view()}
+                    </div>
+                })}
+            </div>
+            <button
+                class="ghost compact"
+                disabled=move || busy.get() || is_recording.get()
+                on:click=test_action
+            >
+                { move || if busy.get() { "Testing..." } else { "Test Microphone" } }
+            </button>
+            <button
+                class="ghost compact"
+                disabled=move || busy.get() || is_recording.get() || test.get().is_none()
+                on:click=move |_| play()
+            >
+                "Play Again"
+            </button>
+        </div>
+    }
+}
@@ -1,2 +1,41 @@
+pub mod autostart;
+pub mod capture_file;
+pub mod companion;
+pub mod continuation;
+pub mod decode_cache;
+pub mod deep_links;
+pub mod digest;
+pub mod document;
+pub mod encryption;
+pub mod file_transcription;
+pub mod hardware;
+pub mod history;
+pub mod inactivity;
+pub mod input_device;
+pub mod llm_handoff;
+pub mod logs;
+pub mod lookback;
+pub mod microphone_test;
+pub mod network_input;
+pub mod noise_profiles;
+pub mod onboarding;
+pub mod output_templates;
+pub mod patch_routing;
+pub mod pause_markers;
+pub mod post_processing;
+pub mod profiles;
+pub mod quiet_hours;
 pub mod recorder;
 pub mod settings;
+pub mod settings_lock;
+pub mod shortcut_conflict;
+pub mod shortcut_gesture;
+pub mod speaker_adaptation;
+pub mod speech_stats;
+pub mod speech_threads;
+pub mod text_commands;
+pub mod time_stretch;
+pub mod triggers;
+pub mod typed_metadata;
+pub mod vad_settings;
+pub mod webhook;
@@ -0,0 +1,62 @@
+use crate::api::*;
+use crate::components::settings::input_value;
+use leptos::prelude::*;
+use leptos::task::spawn_local;
+
+#[component]
+pub fn NetworkInputRow(
+    network_input: ReadSignal<NetworkInputDto>,
+    set_network_input: WriteSignal<NetworkInputDto>,
+) -> impl IntoView {
+    let (network_status, set_network_status) = signal(String::new());
+
+    let save_action = move |_| {
+        let settings = network_input.get();
+        spawn_local(async move {
+            match save_network_input(settings).await {
+                Ok(_) => {
+                    if let Ok(saved) = fetch_network_input().await {
+                        set_network_input.set(saved);
+                    }
+                    set_network_status.set("Network audio input saved.".to_string());
+                }
+                Err(err) => {
+                    set_network_status.set(format!("Failed to save network audio input: {}", err))
+                }
+            }
+        });
+    };
+
+    view! {
+        <div class="settings-row">
+            <div class="settings-label">
+                <span class="settings-title">"Network Audio Input"</span>
+                <span class="settings-hint">
+                    "Dictate from another device by streaming 16 kHz PCM to this port on localhost"
+                </span>
+                <p class="settings-status">{ move || network_status.get() }</p>
+            </div>
+            <button
+                class="toggle"
+                class:active=move || network_input.get().enabled
+                on:click=move |_| set_network_input.update(|settings| settings.enabled = !settings.enabled)
+            >
+                <div class="toggle-track"><div class="toggle-thumb"></div></div>
+            </button>
+        </div>
+        <div class="settings-input-group">
+            <input
+                type="number"
+                min="1"
+                max="65535"
+                class="settings-input"
+                prop:value=move || network_input.get().port.to_string()
+                on:input=move |event| {
+                    let port = input_value(&event).parse().unwrap_or(0);
+                    set_network_input.update(|settings| settings.port = port);
+                }
+            />
+            <button class="ghost compact" on:click=save_action>"Save"</button>
+        </div>
+    }
+}
@@ -0,0 +1,142 @@
+use crate::api::*;
+use crate::components::settings::{input_value, select_value};
+use leptos::prelude::*;
+use leptos::task::spawn_local;
+
+const PROFILE_PREFIX: &str = "profile:";
+
+fn selection_value(selection: &NoiseSelectionDto) -> String {
+    match selection {
+        NoiseSelectionDto::Off => "off".to_string(),
+        NoiseSelectionDto::Auto => "auto".to_string(),
+        NoiseSelectionDto::Profile(name) => format!("{PROFILE_PREFIX}{name}"),
+    }
+}
+
+fn parse_selection(value: &str) -> NoiseSelectionDto {
+    match value.strip_prefix(PROFILE_PREFIX) {
+        Some(name) => NoiseSelectionDto::Profile(name.to_string()),
+        None if value == "auto" => NoiseSelectionDto::Auto,
+        None => NoiseSelectionDto::Off,
+    }
+}
+
+#[component]
+pub fn NoiseProfilesRow(
+    noise_suppression: ReadSignal<NoiseSuppressionDto>,
+    set_noise_suppression: WriteSignal<NoiseSuppressionDto>,
+    is_recording: ReadSignal<bool>,
+) -> impl IntoView {
+    let (noise_status, set_noise_status) = signal(String::new());
+    let (new_name, set_new_name) = signal(String::new());
+    let (capturing, set_capturing) = signal(false);
+
+    let select_action = move |event: leptos::ev::Event| {
+        let selection = parse_selection(&select_value(&event));
+        spawn_local(async move {
+            match save_noise_selection(selection).await {
+                Ok(saved) => {
+                    set_noise_suppression.set(saved);
+                    set_noise_status.set("Noise suppression saved.".to_string());
+                }
+                Err(err) => {
+                    if let Ok(current) = fetch_noise_suppression().await {
+                        set_noise_suppression.set(current);
+                    }
+                    set_noise_status.set(format!("Failed to save noise suppression: {}", err));
+                }
+            }
+        });
+    };
+
+    let capture_action = move |_| {
+        let name = new_name.get();
+        set_capturing.set(true);
+        set_noise_status.set("Recording the room's noise. Stay quiet for 3 seconds...".to_string());
+        spawn_local(async move {
+            match capture_noise_profile(name).await {
+                Ok(saved) => {
+                    set_noise_suppression.set(saved);
+                    set_new_name.set(String::new());
+                    set_noise_status.set("Noise profile saved.".to_string());
+                }
+                Err(err) => set_noise_status.set(format!("Capture failed: {}", err)),
+            }
+            set_capturing.set(false);
+        });
+    };
+
+    let delete_action = move |name: String| {
+        spawn_local(async move {
+            match delete_noise_profile(name).await {
+                Ok(saved) => {
+                    set_noise_suppression.set(saved);
+                    set_noise_status.set("Noise profile deleted.".to_string());
+                }
+                Err(err) => {
+                    set_noise_status.set(format!("Failed to delete noise profile: {}", err))
+                }
+            }
+        });
+    };
+
+    view! {
+        <div class="settings-row">
+            <div class="settings-label">
+                <span class="settings-title">"Noise Suppression"</span>
+                <span class="settings-hint">
+                    "Subtract a recorded room noise, such as a fan or air conditioner, from the microphone. Automatic picks the profile that matches the first moments of each recording"
+                </span>
+                <p class="settings-status">{ move || noise_status.get() }</p>
+            </div>
+            <select
+                class="settings-input settings-select"
+                prop:value=move || selection_value(&noise_suppression.get().selection)
+                disabled=move || is_recording.get()
+                on:change=select_action
+            >
+                <option value="off">"Off"</option>
+                <option value="auto">"Automatic"</option>
+                { move || noise_suppression.get().profiles.into_iter().map(|name| {
+                    let value = format!("{PROFILE_PREFIX}{name}");
+                    view! { <option value=value>{ name }</option> }
+                }).collect_view() }
+            </select>
+        </div>
+        <div class="settings-input-group">
+            <input
+                type="text"
+                class="settings-input"
+                maxlength="40"
+                placeholder="Profile name, e.g. Office fan"
+                aria-label="Noise profile name"
+                prop:value=move || new_name.get()
+                on:input=move |event| set_new_name.set(input_value(&event))
+            />
+            <button
+                class="ghost compact"
+                disabled=move || {
+                    capturing.get() || is_recording.get() || new_name.get().trim().is_empty()
+                }
+                on:click=capture_action
+            >
+                { move || if capturing.get() { "Listening..." } else { "Capture" } }
+            </button>
+        </div>
+        { move || noise_suppression.get().profiles.into_iter().map(|name| {
+            let label = name.clone();
+            view! {
+                <div class="settings-input-group">
+                    <span class="settings-hint">{ label }</span>
+                    <button
+                        class="ghost compact"
+                        disabled=move || is_recording.get()
+                        on:click=move |_| delete_action(name.clone())
+                    >
+                        "Delete"
+                    </button>
+                </div>
+            }
+        }).collect_view() }
+    }
+}
@@ -0,0 +1,164 @@
+use crate::api::*;
+use crate::components::microphone_test::levels_text;
+use leptos::prelude::*;
+use leptos::task::spawn_local;
+use wasm_bindgen::prelude::*;
+
+const STEPS: [(&str, &str); 5] = [
+    ("model_download", "Speech model"),
+    ("mic_permission", "Microphone access"),
+    ("mic_test", "Microphone test"),
+    ("shortcut_capture", "Shortcut"),
+    ("sample_dictation", "First dictation"),
+];
+
+fn step_hint(step: &str, shortcut: &str) -> String {
+    match step {
+        "model_download" => {
+            "The speech model is downloading and loading. This step passes on its own once it is ready."
+                .to_string()
+        }
+        "mic_permission" => {
+            "SilentKeys needs your microphone. Allow access when your system asks.".to_string()
+        }
+        "mic_test" => "Record three seconds, speaking as you would when dictating.".to_string(),
+        "shortcut_capture" => format!(
+            "Hold {shortcut} to dictate. Keep it, or change it under Settings first."
+        ),
+        "sample_dictation" => format!(
+            "Hold {shortcut}, or press Start recording, and say a short sentence."
+        ),
+        _ => String::new(),
+    }
+}
+
+/// Walks a new install through setup, one step at a time, following the
+/// backend's `onboarding_state`. Hidden once setup is done or skipped.
+#[component]
+pub fn OnboardingCard(shortcut: ReadSignal<String>) -> impl IntoView {
+    let (onboarding, set_onboarding) = signal(OnboardingDto {
+        step: "done".to_string(),
+        ..OnboardingDto::default()
+    });
+    let (busy, set_busy) = signal(false);
+    let (mic_levels, set_mic_levels) = signal(String::new());
+
+    spawn_local(async move {
+        let callback = Closure::wrap(Box::new(move |event: JsValue| {
+            let Ok(payload) = js_sys::Reflect::get(&event, &"payload".into()) else {
+                return;
+            };
+            match serde_wasm_bindgen::from_value::<OnboardingDto>(payload) {
+                Ok(state) => set_onboarding.set(state),
+                Err(error) => leptos::logging::error!("Failed to parse onboarding: {:?}", error),
+            }
+        }) as Box<dyn FnMut(JsValue)>);
+
+        if let Err(error) = listen("onboarding_state", &callback).await {
+            leptos::logging::error!("Failed to listen for onboarding: {:?}", error);
+        }
+        callback.forget();
+
+        if let Ok(state) = fetch_onboarding().await {
+            set_onboarding.set(state);
+        }
+    });
+
+    // Runs the current step's action, or "skip" or "finish".
+    let run = move |action: String| {
+        set_busy.set(true);
+        spawn_local(async move {
+            let result = match action.as_str() {
+                "mic_permission" => check_onboarding_microphone_cmd().await,
+                "mic_test" => test_onboarding_microphone_cmd().await.map(|test| {
+                    set_mic_levels.set(test.as_ref().map(levels_text).unwrap_or_default());
+                }),
+                "shortcut_capture" => confirm_onboarding_shortcut_cmd().await,
+                "skip" => skip_onboarding_step_cmd().await,
+                _ => finish_onboarding_cmd().await,
+            };
+            if let Err(err) = result {
+                leptos::logging::error!("Onboarding action failed: {}", err);
+            }
+            set_busy.set(false);
+        });
+    };
+
+    let step = move || onboarding.with(|state| state.step.clone());
+    let action_label = move || match step().as_str() {
+        "mic_permission" => Some("Allow Microphone"),
+        "mic_test" => Some("Test Microphone"),
+        "shortcut_capture" => Some("Keep Shortcut"),
+        _ => None,
+    };
+
+    view! {
+        <Show when=move || step() != "done">
+            <section class="card onboarding-card">
+                <div class="card-header">
+                    <div>
+                        <p class="eyebrow">"Getting started"</p>
+                        <h2>"Set up SilentKeys"</h2>
+                    </div>
+                </div>
+                <ol class="onboarding-steps">
+                    {STEPS.iter().map(|(key, label)| view! {
+                        <li class:current=move || step() == *key>{*label}</li>
+                    }).collect_view()}
+                </ol>
+                <p class="settings-hint">{move || step_hint(&step(), &shortcut.get())}</p>
+                <Show when=move || step() == "mic_test" && !mic_levels.get().is_empty()>
+                    <p class="settings-status">{move || mic_levels.get()}</p>
+                </Show>
+                <Show when=move || onboarding.with(|state| state.problem.is_some())>
+                    <p class="settings-status">
+                        {move || onboarding.with(|state| state.problem.clone().unwrap_or_default())}
+                    </p>
+                </Show>
+                <div class="settings-input-group">
+                    <Show when=move || action_label().is_some()>
+                        <button
+                            disabled=move || busy.get()
+                            on:click=move |_| run(step())
+                        >
+                            {move || action_label().unwrap_or_default()}
+                        </button>
+                    </Show>
+                    <button class="ghost compact" disabled=move || busy.get() on:click=move |_| run("skip".to_string())>
+                        "Skip Step"
+                    </button>
+                    <button class="ghost compact" disabled=move || busy.get() on:click=move |_| run("finish".to_string())>
+                        "Skip Setup"
+                    </button>
+                </div>
+            </section>
+        </Show>
+    }
+}
+
+#[component]
+pub fn OnboardingRow() -> impl IntoView {
+    let (status, set_status) = signal(String::new());
+
+    let restart_action = move |_| {
+        spawn_local(async move {
+            match restart_onboarding_cmd().await {
+                Ok(_) => set_status.set("Setup restarted at the top of the window.".to_string()),
+                Err(err) => set_status.set(format!("Failed to restart setup: {}", err)),
+            }
+        });
+    };
+
+    view! {
+        <div class="settings-row">
+            <div class="settings-label">
+                <span class="settings-title">"Setup Guide"</span>
+                <span class="settings-hint">
+                    "Walk through the first-run steps again: model, microphone, shortcut, and a first dictation"
+                </span>
+                <p class="settings-status">{ move || status.get() }</p>
+            </div>
+            <button class="ghost compact" on:click=restart_action>"Run Again"</button>
+        </div>
+    }
+}
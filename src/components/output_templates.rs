@@ -0,0 +1,64 @@
+use crate::api::*;
+use crate::components::settings::input_value;
+use leptos::prelude::*;
+use leptos::task::spawn_local;
+
+#[component]
+pub fn OutputTemplatesRow(
+    output_templates: ReadSignal<OutputTemplatesDto>,
+    set_output_templates: WriteSignal<OutputTemplatesDto>,
+) -> impl IntoView {
+    let (templates_status, set_templates_status) = signal(String::new());
+
+    let save_action = move |_| {
+        let templates = output_templates.get();
+        spawn_local(async move {
+            match save_output_templates(templates).await {
+                Ok(_) => {
+                    if let Ok(saved) = fetch_output_templates().await {
+                        set_output_templates.set(saved);
+                    }
+                    set_templates_status.set("Output templates saved.".to_string());
+                }
+                Err(err) => {
+                    set_templates_status.set(format!("Failed to save output templates: {}", err))
+                }
+            }
+        });
+    };
+
+    view! {
+        <div class="settings-row">
+            <div class="settings-label">
+                <span class="settings-title">"Output Templates"</span>
+                <span class="settings-hint">
+                    "Wrap the transcript, e.g. - [ ] {{text}} ({{date}}); {{time}} is also available. Empty types it as is"
+                </span>
+                <p class="settings-status">{ move || templates_status.get() }</p>
+            </div>
+            <div class="settings-input-group">
+                <input
+                    type="text"
+                    class="settings-input"
+                    placeholder="Record shortcut"
+                    prop:value=move || output_templates.get().record
+                    on:input=move |event| {
+                        let template = input_value(&event);
+                        set_output_templates.update(|templates| templates.record = template);
+                    }
+                />
+                <input
+                    type="text"
+                    class="settings-input"
+                    placeholder="LLM handoff (Alt+X)"
+                    prop:value=move || output_templates.get().llm_handoff
+                    on:input=move |event| {
+                        let template = input_value(&event);
+                        set_output_templates.update(|templates| templates.llm_handoff = template);
+                    }
+                />
+                <button class="ghost compact" on:click=save_action>"Save"</button>
+            </div>
+        </div>
+    }
+}
@@ -0,0 +1,77 @@
+use crate::api::*;
+use crate::components::settings::select_value;
+use leptos::prelude::*;
+use leptos::task::spawn_local;
+
+#[component]
+fn DestinationOptions() -> impl IntoView {
+    view! {
+        <option value="both">"Type and show here"</option>
+        <option value="type">"Type only"</option>
+        <option value="window">"Show here only"</option>
+        <option value="neither">"Neither"</option>
+    }
+}
+
+#[component]
+pub fn PatchRoutingRow(
+    patch_routing: ReadSignal<PatchRoutingDto>,
+    set_patch_routing: WriteSignal<PatchRoutingDto>,
+) -> impl IntoView {
+    let (routing_status, set_routing_status) = signal(String::new());
+
+    let persist = move |update: fn(&mut PatchRoutingDto, String), value: String| {
+        let previous = patch_routing.get_untracked();
+        let mut routing = previous.clone();
+        update(&mut routing, value);
+        if routing == previous {
+            return;
+        }
+        set_patch_routing.set(routing.clone());
+        spawn_local(async move {
+            match save_patch_routing(routing).await {
+                Ok(_) => set_routing_status.set(String::new()),
+                Err(err) => {
+                    set_patch_routing.set(previous);
+                    set_routing_status.set(format!("Failed to save transcript output: {}", err));
+                }
+            }
+        });
+    };
+
+    view! {
+        <div class="settings-row">
+            <div class="settings-label">
+                <span class="settings-title">"Transcript Output"</span>
+                <span class="settings-hint">
+                    "Where dictation goes: typed into the focused app, shown in this window, or both"
+                </span>
+                <p class="settings-status">{ move || routing_status.get() }</p>
+            </div>
+        </div>
+        <div class="settings-input-group">
+            <span class="settings-hint">"Shortcut"</span>
+            <select
+                class="settings-input settings-select"
+                aria-label="Shortcut dictation output"
+                prop:value=move || patch_routing.get().shortcut
+                on:change=move |event| {
+                    persist(|routing, value| routing.shortcut = value, select_value(&event))
+                }
+            >
+                <DestinationOptions />
+            </select>
+            <span class="settings-hint">"Record button"</span>
+            <select
+                class="settings-input settings-select"
+                aria-label="Record button output"
+                prop:value=move || patch_routing.get().app
+                on:change=move |event| {
+                    persist(|routing, value| routing.app = value, select_value(&event))
+                }
+            >
+                <DestinationOptions />
+            </select>
+        </div>
+    }
+}
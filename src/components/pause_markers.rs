@@ -0,0 +1,83 @@
+use crate::api::*;
+use crate::components::settings::input_value;
+use leptos::prelude::*;
+use leptos::task::spawn_local;
+
+/// Paragraph breaks are stored as newlines, which a single-line input cannot
+/// show, so they appear as an empty field and an empty field saves as one.
+const PARAGRAPH_BREAK: &str = "\n\n";
+
+#[component]
+pub fn PauseMarkersRow(
+    pause_markers: ReadSignal<PauseMarkersDto>,
+    set_pause_markers: WriteSignal<PauseMarkersDto>,
+) -> impl IntoView {
+    let (pause_status, set_pause_status) = signal(String::new());
+
+    let save_action = move |_| {
+        let settings = pause_markers.get();
+        spawn_local(async move {
+            match save_pause_markers(settings).await {
+                Ok(_) => {
+                    if let Ok(saved) = fetch_pause_markers().await {
+                        set_pause_markers.set(saved);
+                    }
+                    set_pause_status.set("Pause markers saved.".to_string());
+                }
+                Err(err) => set_pause_status.set(format!("Failed to save pause markers: {}", err)),
+            }
+        });
+    };
+
+    view! {
+        <div class="settings-row">
+            <div class="settings-label">
+                <span class="settings-title">"Pause Markers"</span>
+                <span class="settings-hint">
+                    "Break the transcript where you pause for at least this many seconds; leave the marker empty for a new paragraph"
+                </span>
+                <p class="settings-status">{ move || pause_status.get() }</p>
+            </div>
+            <button
+                class="toggle"
+                class:active=move || pause_markers.get().enabled
+                on:click=move |_| set_pause_markers.update(|settings| settings.enabled = !settings.enabled)
+            >
+                <div class="toggle-track"><div class="toggle-thumb"></div></div>
+            </button>
+        </div>
+        <div class="settings-input-group">
+            <input
+                type="number"
+                class="settings-input"
+                min="0.5"
+                max="60"
+                step="0.5"
+                aria-label="Minimum pause in seconds"
+                prop:value=move || (pause_markers.get().min_pause_ms as f64 / 1_000.0).to_string()
+                on:input=move |event| {
+                    if let Ok(seconds) = input_value(&event).parse::<f64>() {
+                        let min_pause_ms = (seconds.max(0.0) * 1_000.0).round() as u64;
+                        set_pause_markers.update(|settings| settings.min_pause_ms = min_pause_ms);
+                    }
+                }
+            />
+            <input
+                type="text"
+                class="settings-input"
+                placeholder="New paragraph"
+                aria-label="Pause marker"
+                prop:value=move || {
+                    let marker = pause_markers.get().marker;
+                    if marker.trim().is_empty() { String::new() } else { marker }
+                }
+                on:input=move |event| {
+                    let marker = input_value(&event);
+                    let marker = if marker.is_empty() { PARAGRAPH_BREAK.to_string() } else { marker };
+                    set_pause_markers.update(|settings| settings.marker = marker);
+                }
+            />
+            <button class="ghost compact" on:click=save_action>"Save"</button>
+        </div>
+    }
+}
@@ -0,0 +1,121 @@
+use std::collections::BTreeMap;
+
+use crate::api::*;
+use crate::components::settings::input_value;
+use leptos::prelude::*;
+use leptos::task::spawn_local;
+
+/// Writes the selection as `de: spelling, code_mode; en-gb: spelling`.
+fn format_selection(post_processing: &PostProcessingDto) -> String {
+    post_processing
+        .languages
+        .iter()
+        .map(|(language, stages)| format!("{}: {}", language, stages.join(", ")))
+        .collect::<Vec<_>>()
+        .join("; ")
+}
+
+fn parse_selection(text: &str) -> Result<PostProcessingDto, String> {
+    let mut languages = BTreeMap::new();
+    for entry in text
+        .split(';')
+        .map(str::trim)
+        .filter(|entry| !entry.is_empty())
+    {
+        let (language, stages) = entry
+            .split_once(':')
+            .ok_or_else(|| format!("\"{}\" needs a language code and a colon", entry))?;
+        let stages = stages
+            .split(',')
+            .map(str::trim)
+            .filter(|stage| !stage.is_empty())
+            .map(str::to_string)
+            .collect();
+        languages.insert(language.trim().to_string(), stages);
+    }
+    Ok(PostProcessingDto { languages })
+}
+
+#[component]
+pub fn PostProcessingRow(
+    post_processing: ReadSignal<PostProcessingDto>,
+    set_post_processing: WriteSignal<PostProcessingDto>,
+) -> impl IntoView {
+    // Unsaved edits; until there are some the saved selection is shown.
+    let (draft, set_draft) = signal(None::<String>);
+    let (stages, set_stages) = signal(Vec::<PostProcessStageDto>::new());
+    let (stages_status, set_stages_status) = signal(String::new());
+
+    spawn_local(async move {
+        if let Ok(available) = list_post_process_stages().await {
+            set_stages.set(available);
+        }
+    });
+
+    let available = move || {
+        stages
+            .get()
+            .into_iter()
+            .map(|stage| {
+                if stage.languages.is_empty() {
+                    stage.name
+                } else {
+                    format!("{} ({})", stage.name, stage.languages.join(", "))
+                }
+            })
+            .collect::<Vec<_>>()
+            .join(", ")
+    };
+
+    let save_action = move |_| {
+        let text = draft
+            .get()
+            .unwrap_or_else(|| format_selection(&post_processing.get()));
+        let selection = match parse_selection(&text) {
+            Ok(selection) => selection,
+            Err(err) => {
+                set_stages_status.set(err);
+                return;
+            }
+        };
+        spawn_local(async move {
+            match save_post_processing(selection).await {
+                Ok(_) => {
+                    if let Ok(saved) = fetch_post_processing().await {
+                        set_post_processing.set(saved);
+                    }
+                    set_draft.set(None);
+                    set_stages_status.set("Post-processing stages saved.".to_string());
+                }
+                Err(err) => {
+                    set_stages_status.set(format!("Failed to save post-processing stages: {}", err))
+                }
+            }
+        });
+    };
+
+    view! {
+        <div class="settings-row">
+            <div class="settings-label">
+                <span class="settings-title">"Post-processing Stages"</span>
+                <span class="settings-hint">
+                    "Stages to run per language, in order, e.g. de: spelling, code_mode; en-gb: spelling. Other languages run every stage written for them"
+                </span>
+                <span class="settings-hint">{ move || format!("Available: {}", available()) }</span>
+                <p class="settings-status">{ move || stages_status.get() }</p>
+            </div>
+            <div class="settings-input-group">
+                <input
+                    type="text"
+                    class="settings-input"
+                    placeholder="language: stage, stage; ..."
+                    prop:value=move || {
+                        draft.get().unwrap_or_else(|| format_selection(&post_processing.get()))
+                    }
+                    on:input=move |event| set_draft.set(Some(input_value(&event)))
+                />
+                <button class="ghost compact" on:click=save_action>"Save"</button>
+            </div>
+        </div>
+    }
+}
@@ -0,0 +1,112 @@
+use crate::api::*;
+use crate::components::settings::{input_value, select_value};
+use leptos::prelude::*;
+use leptos::task::spawn_local;
+
+#[component]
+pub fn ProfilesRow(
+    profiles: ReadSignal<ProfileListDto>,
+    set_profiles: WriteSignal<ProfileListDto>,
+) -> impl IntoView {
+    let (profile_status, set_profile_status) = signal(String::new());
+    let (new_name, set_new_name) = signal(String::new());
+
+    let refresh = move || {
+        spawn_local(async move {
+            if let Ok(list) = fetch_profiles().await {
+                set_profiles.set(list);
+            }
+        });
+    };
+
+    // The window reloads the profile settings on the `profile_changed` event,
+    // which also covers switches made from the tray.
+    let switch_action = move |event: leptos::ev::Event| {
+        let name = select_value(&event);
+        if name.is_empty() || profiles.get_untracked().active.as_deref() == Some(name.as_str()) {
+            return;
+        }
+        set_profile_status.set("Switching...".to_string());
+        spawn_local(async move {
+            match switch_profile(name.clone()).await {
+                Ok(_) => set_profile_status.set(format!("Switched to {}.", name)),
+                Err(err) => {
+                    refresh();
+                    set_profile_status.set(format!("Failed to switch profile: {}", err))
+                }
+            }
+        });
+    };
+
+    let create_action = move |_| {
+        let name = new_name.get();
+        spawn_local(async move {
+            match create_profile(name.clone()).await {
+                Ok(_) => {
+                    set_new_name.set(String::new());
+                    refresh();
+                    set_profile_status.set(format!("Saved current settings as {}.", name.trim()));
+                }
+                Err(err) => set_profile_status.set(format!("Failed to create profile: {}", err)),
+            }
+        });
+    };
+
+    let delete_action = move |_| {
+        let Some(name) = profiles.get().active else {
+            return;
+        };
+        spawn_local(async move {
+            match delete_profile(name.clone()).await {
+                Ok(_) => {
+                    refresh();
+                    set_profile_status.set(format!("Deleted {}.", name));
+                }
+                Err(err) => set_profile_status.set(format!("Failed to delete profile: {}", err)),
+            }
+        });
+    };
+
+    view! {
+        <div class="settings-row">
+            <div class="settings-label">
+                <span class="settings-title">"Profile"</span>
+                <span class="settings-hint">
+                    "Language, streaming, typing pace, output templates, and capture file per profile"
+                </span>
+                <p class="settings-status">{ move || profile_status.get() }</p>
+            </div>
+            <select
+                class="settings-input settings-select"
+                prop:value=move || profiles.get().active.unwrap_or_default()
+                disabled=move || profiles.get().names.is_empty()
+                on:change=switch_action
+            >
+                <For
+                    each=move || profiles.get().names
+                    key=|name| name.clone()
+                    children=move |name| {
+                        view! { <option value=name.clone()>{name.clone()}</option> }
+                    }
+                />
+            </select>
+        </div>
+        <div class="settings-input-group">
+            <input
+                type="text"
+                class="settings-input"
+                placeholder="New profile name"
+                prop:value=move || new_name.get()
+                on:input=move |event| set_new_name.set(input_value(&event))
+            />
+            <button class="ghost compact" on:click=create_action>"Save As Profile"</button>
+            <button
+                class="ghost compact"
+                disabled=move || profiles.get().active.is_none()
+                on:click=delete_action
+            >
+                "Delete Current"
+            </button>
+        </div>
+    }
+}
@@ -0,0 +1,153 @@
+use crate::api::*;
+use leptos::prelude::*;
+use leptos::task::spawn_local;
+use leptos::web_sys::HtmlInputElement;
+use wasm_bindgen::JsCast;
+
+const DAY_LABELS: [&str; 7] = ["M", "T", "W", "T", "F", "S", "S"];
+
+fn time_value(event: &leptos::ev::Event) -> Option<u16> {
+    let value = event
+        .target()
+        .and_then(|target| target.dyn_into::<HtmlInputElement>().ok())
+        .map(|input| input.value())?;
+    let (hours, minutes) = value.split_once(':')?;
+    Some(hours.parse::<u16>().ok()? * 60 + minutes.parse::<u16>().ok()?)
+}
+
+fn format_time(minute: u16) -> String {
+    format!("{:02}:{:02}", minute / 60, minute % 60)
+}
+
+fn default_range() -> QuietRangeDto {
+    QuietRangeDto {
+        days: (0..7).collect(),
+        start_minute: 22 * 60,
+        end_minute: 7 * 60,
+    }
+}
+
+#[component]
+pub fn QuietHoursRow(
+    quiet_hours: ReadSignal<QuietHoursDto>,
+    set_quiet_hours: WriteSignal<QuietHoursDto>,
+) -> impl IntoView {
+    let (quiet_status, set_quiet_status) = signal(String::new());
+
+    let save_action = move |_| {
+        let schedule = quiet_hours.get();
+        spawn_local(async move {
+            match save_quiet_hours(schedule).await {
+                Ok(_) => {
+                    if let Ok(saved) = fetch_quiet_hours().await {
+                        set_quiet_hours.set(saved);
+                    }
+                    set_quiet_status.set("Quiet hours saved.".to_string());
+                }
+                Err(err) => set_quiet_status.set(format!("Failed to save quiet hours: {}", err)),
+            }
+        });
+    };
+
+    let range_view = move |index: usize| {
+        let range = move || quiet_hours.get().ranges.get(index).cloned();
+        let update_range = move |apply: &dyn Fn(&mut QuietRangeDto)| {
+            set_quiet_hours.update(|schedule| {
+                if let Some(range) = schedule.ranges.get_mut(index) {
+                    apply(range);
+                }
+            });
+        };
+        view! {
+            <div class="settings-input-group">
+                {(0..7u8)
+                    .map(|day| {
+                        view! {
+                            <button
+                                class="ghost compact"
+                                class:active=move || {
+                                    range().is_some_and(|range| range.days.contains(&day))
+                                }
+                                on:click=move |_| {
+                                    update_range(&|range| {
+                                        if let Some(position) = range.days.iter().position(|d| *d == day) {
+                                            range.days.remove(position);
+                                        } else {
+                                            range.days.push(day);
+                                            range.days.sort_unstable();
+                                        }
+                                    })
+                                }
+                            >
+                                {DAY_LABELS[day as usize]}
+                            </button>
+                        }
+                    })
+                    .collect_view()}
+                <input
+                    type="time"
+                    class="settings-input"
+                    prop:value=move || range().map(|range| format_time(range.start_minute)).unwrap_or_default()
+                    on:change=move |event| {
+                        if let Some(minute) = time_value(&event) {
+                            update_range(&|range| range.start_minute = minute);
+                        }
+                    }
+                />
+                <input
+                    type="time"
+                    class="settings-input"
+                    prop:value=move || range().map(|range| format_time(range.end_minute)).unwrap_or_default()
+                    on:change=move |event| {
+                        if let Some(minute) = time_value(&event) {
+                            update_range(&|range| range.end_minute = minute);
+                        }
+                    }
+                />
+                <button
+                    class="ghost compact"
+                    on:click=move |_| {
+                        set_quiet_hours.update(|schedule| {
+                            if index < schedule.ranges.len() {
+                                schedule.ranges.remove(index);
+                            }
+                        })
+                    }
+                >
+                    "Remove"
+                </button>
+            </div>
+        }
+    };
+
+    view! {
+        <div class="settings-row">
+            <div class="settings-label">
+                <span class="settings-title">"Quiet Hours"</span>
+                <span class="settings-hint">"The record shortcut is inactive during these times"</span>
+                <p class="settings-status">{ move || quiet_status.get() }</p>
+            </div>
+            <button
+                class="toggle"
+                class:active=move || quiet_hours.get().enabled
+                on:click=move |_| set_quiet_hours.update(|schedule| schedule.enabled = !schedule.enabled)
+            >
+                <div class="toggle-track"><div class="toggle-thumb"></div></div>
+            </button>
+        </div>
+        <For
+            each=move || 0..quiet_hours.get().ranges.len()
+            key=|index| *index
+            children=range_view
+        />
+        <div class="settings-input-group">
+            <button
+                class="ghost compact"
+                on:click=move |_| set_quiet_hours.update(|schedule| schedule.ranges.push(default_range()))
+            >
+                "Add Range"
+            </button>
+            <button class="ghost compact" on:click=save_action>"Save"</button>
+        </div>
+    }
+}
@@ -2,10 +2,40 @@ use crate::api::*;
 use leptos::prelude::*;
 use leptos::task::spawn_local;
 
+fn audio_health_text(health: &AudioHealthDto) -> Option<String> {
+    if health.overrun_samples == 0 && health.dropped_streaming_samples == 0 {
+        return None;
+    }
+    let chain = if health.processing_chain.is_empty() {
+        "passed through".to_string()
+    } else {
+        health.processing_chain.join(" → ").replace('_', " ")
+    };
+    let mut text = format!("Last capture: {} Hz ({chain})", health.device_sample_rate);
+    if health.overrun_samples > 0 {
+        text.push_str(&format!(
+            ", {} samples lost to overruns",
+            health.overrun_samples
+        ));
+    }
+    if health.dropped_streaming_samples > 0 {
+        text.push_str(&format!(
+            ", {} samples missed by live transcription",
+            health.dropped_streaming_samples
+        ));
+    }
+    Some(text)
+}
+
+/// Width of the level meter for a frame RMS, on a -60 to 0 dBFS scale.
+pub(crate) fn level_percent(rms: f32) -> f32 {
+    let db = 20.0 * rms.max(1e-6).log10();
+    ((db + 60.0) / 60.0 * 100.0).clamp(0.0, 100.0)
+}
+
 #[component]
 pub fn RecorderSection(
     is_recording: ReadSignal<bool>,
-    set_is_recording: WriteSignal<bool>,
     transcribing: ReadSignal<bool>,
     set_transcribing: WriteSignal<bool>,
     status: ReadSignal<String>,
@@ -13,8 +43,27 @@ pub fn RecorderSection(
     model_ready: ReadSignal<bool>,
     set_model_error: WriteSignal<Option<String>>,
     model_error: ReadSignal<Option<String>>,
-    set_transcription: WriteSignal<String>,
+    model_unloaded: ReadSignal<bool>,
+    model_skippable: ReadSignal<bool>,
+    download_paused: ReadSignal<Option<bool>>,
+    set_transcription: WriteSignal<Transcript>,
+    audio_health: ReadSignal<Option<AudioHealthDto>>,
+    capture_quality: ReadSignal<Option<CaptureQualityDto>>,
+    voice_level: ReadSignal<Option<(f32, bool)>>,
+    speaking: ReadSignal<Option<bool>>,
+    waveform: ReadSignal<Vec<f32>>,
+    inactivity_warning: ReadSignal<Option<u64>>,
+    set_inactivity_warning: WriteSignal<Option<u64>>,
 ) -> impl IntoView {
+    let keep_going = move |_| {
+        spawn_local(async move {
+            match keep_recording().await {
+                Ok(_) => set_inactivity_warning.set(None),
+                Err(err) => set_status.set(format!("Could not keep recording: {}", err)),
+            }
+        });
+    };
+
     let toggle_recording = move |_| {
         if !model_ready.get() {
             if model_error.get().is_some() {
@@ -28,6 +77,13 @@ pub fn RecorderSection(
                         }
                     }
                 });
+            } else if model_unloaded.get() {
+                set_status.set("Loading speech model...".to_string());
+                spawn_local(async move {
+                    if let Err(err) = retry_model_download_cmd().await {
+                        set_status.set(format!("Speech model not loaded: {}", err));
+                    }
+                });
             }
             return;
         }
@@ -38,15 +94,11 @@ pub fn RecorderSection(
 
         if !is_recording.get() {
             set_status.set("Starting recording...".to_string());
-            set_is_recording.set(true);
-            set_transcription.set(String::new());
+            set_transcription.set(Transcript::default());
             spawn_local(async move {
                 match start_recording_cmd().await {
                     Ok(_) => set_status.set("Recording... tap to stop.".to_string()),
-                    Err(msg) => {
-                        set_is_recording.set(false);
-                        set_status.set(format!("Could not start recording: {}", msg));
-                    }
+                    Err(msg) => set_status.set(format!("Could not start recording: {}", msg)),
                 }
             });
             return;
@@ -57,19 +109,43 @@ pub fn RecorderSection(
             set_transcribing.set(true);
 
             match stop_recording_cmd().await {
-                Ok(()) => {
-                    set_is_recording.set(false);
-                    set_status.set("Finished.".to_string());
-                }
-                Err(err) => {
-                    set_is_recording.set(false);
-                    set_status.set(format!("Could not stop recording: {}", err));
-                }
+                Ok(()) => set_status.set("Finished.".to_string()),
+                Err(err) => set_status.set(format!("Could not stop recording: {}", err)),
             }
             set_transcribing.set(false);
         });
     };
 
+    let skip_load = move |_| {
+        spawn_local(async move {
+            if let Err(err) = skip_model_load_cmd().await {
+                set_status.set(format!("Could not skip model loading: {}", err));
+            }
+        });
+    };
+
+    let toggle_download_pause = move |_| {
+        let paused = download_paused.get_untracked().unwrap_or(false);
+        spawn_local(async move {
+            let result = if paused {
+                resume_download_cmd().await
+            } else {
+                pause_download_cmd().await
+            };
+            if let Err(err) = result {
+                set_status.set(format!("Could not change the model download: {}", err));
+            }
+        });
+    };
+
+    let cancel_download = move |_| {
+        spawn_local(async move {
+            if let Err(err) = cancel_download_cmd().await {
+                set_status.set(format!("Could not cancel the model download: {}", err));
+            }
+        });
+    };
+
     view! {
         <section class="card control-card">
             <div class="card-header">
@@ -80,14 +156,21 @@ pub fn RecorderSection(
                 <span class="pill"
                     class:live=move || is_recording.get()
                     class:glow=move || transcribing.get()
-                    class:idle=move || model_ready.get() && !is_recording.get() && !transcribing.get()
-                    class:preparing=move || !model_ready.get() && model_error.get().is_none()
+                    class:idle=move || {
+                        (model_ready.get() || model_unloaded.get())
+                            && !is_recording.get()
+                            && !transcribing.get()
+                    }
+                    class:preparing=move || {
+                        !model_ready.get() && !model_unloaded.get() && model_error.get().is_none()
+                    }
                     class:unavailable=move || !model_ready.get() && model_error.get().is_some()
                 >
                     {move || {
                         if is_recording.get() { "Listening" }
                         else if transcribing.get() { "Transcribing" }
                         else if !model_ready.get() && model_error.get().is_some() { "Unavailable" }
+                        else if model_unloaded.get() { "Not loaded" }
                         else if !model_ready.get() { "Preparing" }
                         else { "Idle" }
                     }}
@@ -96,18 +179,83 @@ pub fn RecorderSection(
             <div class="control-row">
                 <button
                     on:click=toggle_recording
-                    disabled=move || transcribing.get() || (!model_ready.get() && model_error.get().is_none())
+                    disabled=move || {
+                        transcribing.get()
+                            || (!model_ready.get()
+                                && !model_unloaded.get()
+                                && model_error.get().is_none())
+                    }
                 >
                     {move || {
                         if !model_ready.get() {
-                            if model_error.get().is_some() { "Retry model download" } else { "Preparing..." }
+                            if model_error.get().is_some() { "Retry model download" }
+                            else if model_unloaded.get() { "Load speech model" }
+                            else { "Preparing..." }
                         } else if transcribing.get() { "Working..." }
                         else if is_recording.get() { "Finish recording" }
                         else { "Start recording" }
                     }}
                 </button>
+                {move || model_skippable.get().then(|| view! {
+                    <button class="ghost compact" on:click=skip_load>"Skip for now"</button>
+                })}
+                {move || download_paused.get().map(|paused| view! {
+                    <button class="ghost compact" on:click=toggle_download_pause>
+                        {if paused { "Resume download" } else { "Pause download" }}
+                    </button>
+                    <button class="ghost compact" on:click=cancel_download>"Cancel download"</button>
+                })}
                 <div class="status-container">
                     <p class="inline-status">{ move || status.get() }</p>
+                    {move || speaking.get().filter(|_| is_recording.get()).map(|speaking| view! {
+                        <p class="speech-state" class:speaking=speaking>
+                            {if speaking { "Hearing speech" } else { "Pause" }}
+                        </p>
+                    })}
+                    {move || is_recording.get().then(|| {
+                        let level = move || voice_level.get().unwrap_or_default();
+                        view! {
+                            <div class="vad-meter" class:speech=move || level().1 aria-hidden="true">
+                                <div
+                                    class="vad-level"
+                                    style:width=move || format!("{:.0}%", level_percent(level().0))
+                                ></div>
+                            </div>
+                        }
+                    })}
+                    {move || (is_recording.get() && !waveform.get().is_empty()).then(|| view! {
+                        <div class="waveform" aria-hidden="true">
+                            {move || waveform.get().into_iter().map(|peak| view! {
+                                <div
+                                    class="waveform-bar"
+                                    style:height=format!("{:.0}%", level_percent(peak).max(4.0))
+                                ></div>
+                            }).collect_view()}
+                        </div>
+                    })}
+                    {move || inactivity_warning.get().map(|remaining_secs| view! {
+                        <div class="inactivity-warning" role="alert">
+                            <p class="settings-hint">
+                                {format!("No speech heard. Finishing in {remaining_secs} seconds.")}
+                            </p>
+                            <button class="ghost compact" on:click=keep_going>"Keep Going"</button>
+                        </div>
+                    })}
+                    {move || audio_health.get().as_ref().and_then(audio_health_text).map(|text| view! {
+                        <p class="settings-hint">{text}</p>
+                    })}
+                    {move || {
+                        capture_quality
+                            .get()
+                            .filter(|quality| quality.flag == "poor")
+                            .map(|quality| {
+                                quality
+                                    .suggestions
+                                    .into_iter()
+                                    .map(|suggestion| view! { <p class="settings-hint">{suggestion}</p> })
+                                    .collect_view()
+                            })
+                    }}
                     {move || model_error.get().map(|err| view! {
                         <div class="error-details">
                             <p class="error-msg">{err}</p>
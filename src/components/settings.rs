@@ -1,10 +1,48 @@
 use crate::api::*;
+use crate::components::autostart::AutostartRow;
+use crate::components::capture_file::CaptureFileRow;
+use crate::components::companion::CompanionRow;
+use crate::components::continuation::ContinuationRow;
+use crate::components::decode_cache::DecodeCacheRow;
+use crate::components::deep_links::DeepLinksRow;
+use crate::components::digest::DigestRow;
+use crate::components::document::DocumentRow;
+use crate::components::encryption::EncryptionRow;
+use crate::components::file_transcription::FileTranscriptionRow;
+use crate::components::hardware::HardwareRow;
+use crate::components::history::HistoryRow;
+use crate::components::inactivity::InactivityRow;
+use crate::components::input_device::InputDeviceRow;
+use crate::components::llm_handoff::LlmHandoffRow;
+use crate::components::logs::LogViewer;
+use crate::components::lookback::LookbackRow;
+use crate::components::microphone_test::MicrophoneTestRow;
+use crate::components::network_input::NetworkInputRow;
+use crate::components::noise_profiles::NoiseProfilesRow;
+use crate::components::onboarding::OnboardingRow;
+use crate::components::output_templates::OutputTemplatesRow;
+use crate::components::patch_routing::PatchRoutingRow;
+use crate::components::pause_markers::PauseMarkersRow;
+use crate::components::post_processing::PostProcessingRow;
+use crate::components::profiles::ProfilesRow;
+use crate::components::quiet_hours::QuietHoursRow;
+use crate::components::settings_lock::SettingsLockRow;
+use crate::components::shortcut_conflict::ShortcutConflictNotice;
+use crate::components::shortcut_gesture::ShortcutGestureRow;
+use crate::components::speaker_adaptation::SpeakerAdaptationRow;
+use crate::components::speech_threads::SpeechThreadsRow;
+use crate::components::text_commands::TextCommandsRow;
+use crate::components::time_stretch::TimeStretchRow;
+use crate::components::triggers::TriggerRow;
+use crate::components::typed_metadata::TypedMetadataRow;
+use crate::components::vad_settings::VadSettingsRow;
+use crate::components::webhook::WebhookRow;
 use leptos::prelude::*;
 use leptos::task::spawn_local;
 use leptos::web_sys::{HtmlInputElement, HtmlSelectElement};
 use wasm_bindgen::JsCast;
 
-fn input_value(event: &leptos::ev::Event) -> String {
+pub(crate) fn input_value(event: &leptos::ev::Event) -> String {
     event
         .target()
         .and_then(|target| target.dyn_into::<HtmlInputElement>().ok())
@@ -12,7 +50,7 @@ fn input_value(event: &leptos::ev::Event) -> String {
         .unwrap_or_default()
 }
 
-fn select_value(event: &leptos::ev::Event) -> String {
+pub(crate) fn select_value(event: &leptos::ev::Event) -> String {
     event
         .target()
         .and_then(|target| target.dyn_into::<HtmlSelectElement>().ok())
@@ -53,17 +91,97 @@ pub fn SettingsSection(
     set_model_path: WriteSignal<String>,
     streaming_enabled: ReadSignal<bool>,
     set_streaming_enabled: WriteSignal<bool>,
+    streaming_strategy: ReadSignal<String>,
+    set_streaming_strategy: WriteSignal<String>,
     shortcut: ReadSignal<String>,
     set_shortcut: WriteSignal<String>,
+    shortcut_gesture: ReadSignal<ShortcutGestureDto>,
+    set_shortcut_gesture: WriteSignal<ShortcutGestureDto>,
+    triggers: ReadSignal<TriggerSettingsDto>,
+    set_triggers: WriteSignal<TriggerSettingsDto>,
+    suppress_record_key: ReadSignal<bool>,
+    set_suppress_record_key: WriteSignal<bool>,
     asr_language: ReadSignal<String>,
     set_asr_language: WriteSignal<String>,
     language_options: ReadSignal<Vec<String>>,
+    resampler_quality: ReadSignal<String>,
+    set_resampler_quality: WriteSignal<String>,
+    input_device: ReadSignal<InputDeviceDto>,
+    set_input_device: WriteSignal<InputDeviceDto>,
+    vad_settings: ReadSignal<VadSettingsDto>,
+    set_vad_settings: WriteSignal<VadSettingsDto>,
+    noise_suppression: ReadSignal<NoiseSuppressionDto>,
+    set_noise_suppression: WriteSignal<NoiseSuppressionDto>,
+    speaker_adaptation: ReadSignal<SpeakerAdaptationDto>,
+    set_speaker_adaptation: WriteSignal<SpeakerAdaptationDto>,
+    live_waveform: ReadSignal<bool>,
+    set_live_waveform: WriteSignal<bool>,
+    buffer_until_ready: ReadSignal<bool>,
+    set_buffer_until_ready: WriteSignal<bool>,
+    model_prewarm: ReadSignal<String>,
+    set_model_prewarm: WriteSignal<String>,
+    power_saver: ReadSignal<String>,
+    set_power_saver: WriteSignal<String>,
+    recording_indicator: ReadSignal<String>,
+    set_recording_indicator: WriteSignal<String>,
+    typing_pace: ReadSignal<TypingPaceDto>,
+    set_typing_pace: WriteSignal<TypingPaceDto>,
+    caret_spacing: ReadSignal<bool>,
+    set_caret_spacing: WriteSignal<bool>,
+    continuation: ReadSignal<ContinuationDto>,
+    set_continuation: WriteSignal<ContinuationDto>,
+    inactivity: ReadSignal<InactivityDto>,
+    set_inactivity: WriteSignal<InactivityDto>,
+    pause_markers: ReadSignal<PauseMarkersDto>,
+    set_pause_markers: WriteSignal<PauseMarkersDto>,
+    time_stretch: ReadSignal<TimeStretchDto>,
+    set_time_stretch: WriteSignal<TimeStretchDto>,
+    patch_routing: ReadSignal<PatchRoutingDto>,
+    set_patch_routing: WriteSignal<PatchRoutingDto>,
+    text_commands: ReadSignal<TextCommandsDto>,
+    set_text_commands: WriteSignal<TextCommandsDto>,
+    post_processing: ReadSignal<PostProcessingDto>,
+    set_post_processing: WriteSignal<PostProcessingDto>,
+    dictation_enabled: ReadSignal<bool>,
+    set_dictation_enabled: WriteSignal<bool>,
+    dictation_toggle_shortcut: ReadSignal<bool>,
+    set_dictation_toggle_shortcut: WriteSignal<bool>,
+    quiet_hours: ReadSignal<QuietHoursDto>,
+    set_quiet_hours: WriteSignal<QuietHoursDto>,
+    webhook: ReadSignal<WebhookDto>,
+    set_webhook: WriteSignal<WebhookDto>,
+    llm_handoff: ReadSignal<LlmHandoffDto>,
+    set_llm_handoff: WriteSignal<LlmHandoffDto>,
+    numbers_mode: ReadSignal<bool>,
+    set_numbers_mode: WriteSignal<bool>,
+    output_templates: ReadSignal<OutputTemplatesDto>,
+    set_output_templates: WriteSignal<OutputTemplatesDto>,
+    capture_file: ReadSignal<CaptureFileDto>,
+    set_capture_file: WriteSignal<CaptureFileDto>,
+    announce_transcripts: ReadSignal<bool>,
+    set_announce_transcripts: WriteSignal<bool>,
+    speak_transcripts: ReadSignal<bool>,
+    set_speak_transcripts: WriteSignal<bool>,
+    network_input: ReadSignal<NetworkInputDto>,
+    set_network_input: WriteSignal<NetworkInputDto>,
+    typed_metadata: ReadSignal<TypedMetadataDto>,
+    set_typed_metadata: WriteSignal<TypedMetadataDto>,
+    companion: ReadSignal<CompanionDto>,
+    set_companion: WriteSignal<CompanionDto>,
+    profiles: ReadSignal<ProfileListDto>,
+    set_profiles: WriteSignal<ProfileListDto>,
+    autostart: ReadSignal<AutostartDto>,
+    set_autostart: WriteSignal<AutostartDto>,
+    settings_lock: ReadSignal<SettingsLockDto>,
+    set_settings_lock: WriteSignal<SettingsLockDto>,
     is_recording: ReadSignal<bool>,
     transcribing: ReadSignal<bool>,
     set_status: WriteSignal<String>,
 ) -> impl IntoView {
     let (shortcut_status, set_shortcut_status) = signal(String::new());
+    let (capturing_shortcut, set_capturing_shortcut) = signal(false);
     let (language_status, set_language_status) = signal(String::new());
+    let (typing_pace_status, set_typing_pace_status) = signal(String::new());
     let (update_status, set_update_status) = signal("Checking for updates...".to_string());
     let (available_update, set_available_update) = signal::<Option<AppUpdateInfoDto>>(None);
 
@@ -78,7 +196,8 @@ pub fn SettingsSection(
                 Ok(Some(path)) => match save_model_path(path.clone()).await {
                     Ok(_) => {
                         set_model_path.set(path);
-                        set_status.set("Model path updated.".to_string());
+                        set_status
+                            .set("Model path updated. Reloading the speech model...".to_string());
                     }
                     Err(e) => set_status.set(format!("Failed to save path: {}", e)),
                 },
@@ -88,6 +207,22 @@ pub fn SettingsSection(
         });
     };
 
+    let import_archive_action = move |_| {
+        spawn_local(async move {
+            match pick_model_archive_cmd().await {
+                Ok(Some(path)) => {
+                    set_status.set("Importing model archive...".to_string());
+                    match import_model_archive(path).await {
+                        Ok(_) => set_status.set("Model archive imported.".to_string()),
+                        Err(e) => set_status.set(format!("Model import failed: {}", e)),
+                    }
+                }
+                Ok(None) => {}
+                Err(e) => set_status.set(format!("Failed to pick archive: {}", e)),
+            }
+        });
+    };
+
     let save_shortcut_action = move |_| {
         let val = shortcut.get();
         set_shortcut_status.set("Saving shortcut...".to_string());
@@ -99,22 +234,207 @@ pub fn SettingsSection(
         });
     };
 
+    let capture_shortcut_action = move |_| {
+        set_capturing_shortcut.set(true);
+        set_shortcut_status.set("Press the new shortcut... Esc cancels".to_string());
+        spawn_local(async move {
+            match begin_shortcut_capture_cmd().await {
+                Ok(Some(captured)) => {
+                    set_shortcut.set(captured.clone());
+                    set_shortcut_status.set(format!("Captured {}. Save to use it.", captured));
+                }
+                Ok(None) => set_shortcut_status.set(String::new()),
+                Err(err) => set_shortcut_status.set(format!("Failed to capture shortcut: {}", err)),
+            }
+            set_capturing_shortcut.set(false);
+        });
+    };
+
+    let save_typing_pace_action = move |_| {
+        let pace = typing_pace.get();
+        spawn_local(async move {
+            match save_typing_pace(pace).await {
+                Ok(_) => {
+                    if let Ok(saved) = fetch_typing_pace().await {
+                        set_typing_pace.set(saved);
+                    }
+                    set_typing_pace_status.set("Typing pace saved.".to_string());
+                }
+                Err(err) => {
+                    set_typing_pace_status.set(format!("Failed to save typing pace: {}", err))
+                }
+            }
+        });
+    };
+
+    let toggle_modifier_pause_action = move |_| {
+        let previous = typing_pace.get();
+        let pace = TypingPaceDto {
+            pause_for_modifiers: !previous.pause_for_modifiers,
+            ..previous
+        };
+        set_typing_pace.set(pace);
+        spawn_local(async move {
+            if let Err(err) = save_typing_pace(pace).await {
+                set_typing_pace.set(previous);
+                set_typing_pace_status.set(format!("Failed to save typing pace: {}", err));
+            }
+        });
+    };
+
+    let change_target_lock_action = move |event: leptos::ev::Event| {
+        let previous = typing_pace.get_untracked();
+        let pace = TypingPaceDto {
+            target_lock: TargetLockDto::from_value(&select_value(&event)),
+            ..previous
+        };
+        set_typing_pace.set(pace);
+        spawn_local(async move {
+            if let Err(err) = save_typing_pace(pace).await {
+                set_typing_pace.set(previous);
+                set_typing_pace_status.set(format!("Failed to save typing pace: {}", err));
+            }
+        });
+    };
+
+    // Reset and import replace every setting, so each control is refreshed.
+    let reload_settings = move || async move {
+        if let Ok(path) = fetch_model_path().await {
+            set_model_path.set(path);
+        }
+        if let Ok(enabled) = fetch_streaming_enabled().await {
+            set_streaming_enabled.set(enabled);
+        }
+        if let Ok(strategy) = fetch_streaming_strategy().await {
+            set_streaming_strategy.set(strategy);
+        }
+        if let Ok(language) = fetch_asr_language().await {
+            set_asr_language.set(language);
+        }
+        if let Ok(quality) = fetch_resampler_quality().await {
+            set_resampler_quality.set(quality);
+        }
+        if let Ok(settings) = fetch_input_device().await {
+            set_input_device.set(settings);
+        }
+        if let Ok(settings) = fetch_vad_settings().await {
+            set_vad_settings.set(settings);
+        }
+        if let Ok(settings) = fetch_noise_suppression().await {
+            set_noise_suppression.set(settings);
+        }
+        if let Ok(settings) = fetch_speaker_adaptation().await {
+            set_speaker_adaptation.set(settings);
+        }
+        if let Ok(enabled) = fetch_live_waveform().await {
+            set_live_waveform.set(enabled);
+        }
+        if let Ok(enabled) = fetch_buffer_until_model_ready().await {
+            set_buffer_until_ready.set(enabled);
+        }
+        if let Ok(prewarm) = fetch_model_prewarm().await {
+            set_model_prewarm.set(prewarm);
+        }
+        if let Ok(mode) = fetch_power_saver().await {
+            set_power_saver.set(mode);
+        }
+        if let Ok(indicator) = fetch_recording_indicator().await {
+            set_recording_indicator.set(indicator);
+        }
+        if let Ok(pace) = fetch_typing_pace().await {
+            set_typing_pace.set(pace);
+        }
+        if let Ok(enabled) = fetch_caret_spacing().await {
+            set_caret_spacing.set(enabled);
+        }
+        if let Ok(settings) = fetch_continuation().await {
+            set_continuation.set(settings);
+        }
+        if let Ok(settings) = fetch_inactivity().await {
+            set_inactivity.set(settings);
+        }
+        if let Ok(settings) = fetch_pause_markers().await {
+            set_pause_markers.set(settings);
+        }
+        if let Ok(settings) = fetch_time_stretch().await {
+            set_time_stretch.set(settings);
+        }
+        if let Ok(routing) = fetch_patch_routing().await {
+            set_patch_routing.set(routing);
+        }
+        if let Ok(settings) = fetch_text_commands().await {
+            set_text_commands.set(settings);
+        }
+        if let Ok(selection) = fetch_post_processing().await {
+            set_post_processing.set(selection);
+        }
+        if let Ok(enabled) = fetch_dictation_enabled().await {
+            set_dictation_enabled.set(enabled);
+        }
+        if let Ok(enabled) = fetch_dictation_toggle_shortcut().await {
+            set_dictation_toggle_shortcut.set(enabled);
+        }
+        if let Ok(schedule) = fetch_quiet_hours().await {
+            set_quiet_hours.set(schedule);
+        }
+        if let Ok(settings) = fetch_webhook().await {
+            set_webhook.set(settings);
+        }
+        if let Ok(settings) = fetch_llm_handoff().await {
+            set_llm_handoff.set(settings);
+        }
+        if let Ok(enabled) = fetch_numbers_mode().await {
+            set_numbers_mode.set(enabled);
+        }
+        if let Ok(templates) = fetch_output_templates().await {
+            set_output_templates.set(templates);
+        }
+        if let Ok(settings) = fetch_capture_file().await {
+            set_capture_file.set(settings);
+        }
+        if let Ok(enabled) = fetch_announce_transcripts().await {
+            set_announce_transcripts.set(enabled);
+        }
+        if let Ok(enabled) = fetch_speak_transcripts().await {
+            set_speak_transcripts.set(enabled);
+        }
+        if let Ok(settings) = fetch_network_input().await {
+            set_network_input.set(settings);
+        }
+        if let Ok(settings) = fetch_typed_metadata().await {
+            set_typed_metadata.set(settings);
+        }
+        if let Ok(settings) = fetch_companion().await {
+            set_companion.set(settings);
+        }
+        if let Ok(list) = fetch_profiles().await {
+            set_profiles.set(list);
+        }
+        if let Ok(settings) = fetch_autostart().await {
+            set_autostart.set(settings);
+        }
+        if let Ok(lock) = fetch_settings_lock().await {
+            set_settings_lock.set(lock);
+        }
+        if let Ok(Some(s)) = fetch_current_shortcut().await {
+            set_shortcut.set(s);
+        }
+        if let Ok(gesture) = fetch_shortcut_gesture().await {
+            set_shortcut_gesture.set(gesture);
+        }
+        if let Ok(saved) = fetch_triggers().await {
+            set_triggers.set(saved);
+        }
+        if let Ok(enabled) = fetch_suppress_record_key().await {
+            set_suppress_record_key.set(enabled);
+        }
+    };
+
     let reset_settings_action = move |_| {
         spawn_local(async move {
             match reset_settings_cmd().await {
                 Ok(_) => {
-                    if let Ok(path) = fetch_model_path().await {
-                        set_model_path.set(path);
-                    }
-                    if let Ok(Some(s)) = fetch_default_shortcut().await {
-                        set_shortcut.set(s);
-                    }
-                    if let Ok(enabled) = fetch_streaming_enabled().await {
-                        set_streaming_enabled.set(enabled);
-                    }
-                    if let Ok(language) = fetch_asr_language().await {
-                        set_asr_language.set(language);
-                    }
+                    reload_settings().await;
                     set_status.set("Settings reset.".to_string());
                 }
                 Err(e) => set_status.set(format!("Reset failed: {}", e)),
@@ -122,6 +442,35 @@ pub fn SettingsSection(
         });
     };
 
+    let export_config_action = move |_| {
+        spawn_local(async move {
+            match pick_config_export_file_cmd().await {
+                Ok(Some(path)) => match export_config(path).await {
+                    Ok(_) => set_status.set("Settings exported.".to_string()),
+                    Err(e) => set_status.set(format!("Export failed: {}", e)),
+                },
+                Ok(None) => {}
+                Err(e) => set_status.set(format!("Failed to pick file: {}", e)),
+            }
+        });
+    };
+
+    let import_config_action = move |_| {
+        spawn_local(async move {
+            match pick_config_import_file_cmd().await {
+                Ok(Some(path)) => match import_config(path).await {
+                    Ok(_) => {
+                        reload_settings().await;
+                        set_status.set("Settings imported.".to_string());
+                    }
+                    Err(e) => set_status.set(format!("Import failed: {}", e)),
+                },
+                Ok(None) => {}
+                Err(e) => set_status.set(format!("Failed to pick file: {}", e)),
+            }
+        });
+    };
+
     let change_language_action = move |event: leptos::ev::Event| {
         let language = select_value(&event);
         let previous = asr_language.get_untracked();
@@ -141,6 +490,81 @@ pub fn SettingsSection(
         });
     };
 
+    let change_resampler_action = move |event: leptos::ev::Event| {
+        let quality = select_value(&event);
+        let previous = resampler_quality.get_untracked();
+        if quality == previous {
+            return;
+        }
+        set_resampler_quality.set(quality.clone());
+        spawn_local(async move {
+            if let Err(error) = save_resampler_quality(quality).await {
+                set_resampler_quality.set(previous);
+                set_status.set(format!("Failed to save resampler quality: {}", error));
+            }
+        });
+    };
+
+    let change_strategy_action = move |event: leptos::ev::Event| {
+        let strategy = select_value(&event);
+        let previous = streaming_strategy.get_untracked();
+        if strategy == previous {
+            return;
+        }
+        set_streaming_strategy.set(strategy.clone());
+        spawn_local(async move {
+            if let Err(error) = save_streaming_strategy(strategy).await {
+                set_streaming_strategy.set(previous);
+                set_status.set(format!("Failed to save streaming strategy: {}", error));
+            }
+        });
+    };
+
+    let change_prewarm_action = move |event: leptos::ev::Event| {
+        let prewarm = select_value(&event);
+        let previous = model_prewarm.get_untracked();
+        if prewarm == previous {
+            return;
+        }
+        set_model_prewarm.set(prewarm.clone());
+        spawn_local(async move {
+            if let Err(error) = save_model_prewarm(prewarm).await {
+                set_model_prewarm.set(previous);
+                set_status.set(format!("Failed to save model loading: {}", error));
+            }
+        });
+    };
+
+    let change_power_saver_action = move |event: leptos::ev::Event| {
+        let mode = select_value(&event);
+        let previous = power_saver.get_untracked();
+        if mode == previous {
+            return;
+        }
+        set_power_saver.set(mode.clone());
+        spawn_local(async move {
+            if let Err(error) = save_power_saver(mode).await {
+                set_power_saver.set(previous);
+                set_status.set(format!("Failed to save battery saver: {}", error));
+            }
+        });
+    };
+
+    let change_recording_indicator_action = move |event: leptos::ev::Event| {
+        let indicator = select_value(&event);
+        let previous = recording_indicator.get_untracked();
+        if indicator == previous {
+            return;
+        }
+        set_recording_indicator.set(indicator.clone());
+        spawn_local(async move {
+            if let Err(error) = save_recording_indicator(indicator).await {
+                set_recording_indicator.set(previous);
+                set_status.set(format!("Failed to save recording indicator: {}", error));
+            }
+        });
+    };
+
     let check_update_action = move |_| {
         set_update_status.set("Checking for updates...".to_string());
         spawn_local(refresh_update_status(
@@ -179,6 +603,7 @@ pub fn SettingsSection(
 
     view! {
         <div class="settings-section">
+            <ProfilesRow profiles set_profiles />
             <div class="settings-row">
                 <div class="settings-label">
                     <span class="settings-title">"Speech Language"</span>
@@ -210,6 +635,49 @@ pub fn SettingsSection(
                     />
                 </select>
             </div>
+            <div class="settings-row">
+                <div class="settings-label">
+                    <span class="settings-title">"Dictation Enabled"</span>
+                    <span class="settings-hint">"Off ignores the record shortcut until turned back on"</span>
+                </div>
+                <button
+                    class="toggle"
+                    class:active=move || dictation_enabled.get()
+                    on:click=move |_| {
+                        let new_val = !dictation_enabled.get();
+                        spawn_local(async move {
+                            match save_dictation_enabled(new_val).await {
+                                Ok(_) => set_dictation_enabled.set(new_val),
+                                Err(e) => set_status.set(format!("Failed to change dictation state: {}", e)),
+                            }
+                        });
+                    }
+                >
+                    <div class="toggle-track"><div class="toggle-thumb"></div></div>
+                </button>
+            </div>
+            <div class="settings-row">
+                <div class="settings-label">
+                    <span class="settings-title">"Dictation Toggle Shortcut"</span>
+                    <span class="settings-hint">"Alt+Shift+Z turns dictation on and off from any app"</span>
+                </div>
+                <button
+                    class="toggle"
+                    class:active=move || dictation_toggle_shortcut.get()
+                    on:click=move |_| {
+                        let new_val = !dictation_toggle_shortcut.get();
+                        spawn_local(async move {
+                            match save_dictation_toggle_shortcut(new_val).await {
+                                Ok(_) => set_dictation_toggle_shortcut.set(new_val),
+                                Err(e) => set_status.set(format!("Failed to change dictation toggle shortcut: {}", e)),
+                            }
+                        });
+                    }
+                >
+                    <div class="toggle-track"><div class="toggle-thumb"></div></div>
+                </button>
+            </div>
+            <QuietHoursRow quiet_hours set_quiet_hours />
             <div class="settings-row">
                 <div class="settings-label">
                     <span class="settings-title">"Streaming Mode"</span>
@@ -227,34 +695,346 @@ pub fn SettingsSection(
                     <div class="toggle-track"><div class="toggle-thumb"></div></div>
                 </button>
             </div>
+            <div class="settings-row">
+                <div class="settings-label">
+                    <span class="settings-title">"Streaming Strategy"</span>
+                    <span class="settings-hint">"Per phrase waits for a pause, then transcribes the phrase at once: slower to appear, much lighter on the CPU"</span>
+                </div>
+                <select
+                    class="settings-input settings-select"
+                    disabled=move || !streaming_enabled.get()
+                    prop:value=move || streaming_strategy.get()
+                    on:change=change_strategy_action
+                >
+                    <option value="continuous">"Continuous"</option>
+                    <option value="phrase">"Per phrase"</option>
+                </select>
+            </div>
+            <div class="settings-row">
+                <div class="settings-label">
+                    <span class="settings-title">"Load Speech Model"</span>
+                    <span class="settings-hint">"When to load the model; loading can be skipped from the recorder"</span>
+                </div>
+                <select
+                    class="settings-input settings-select"
+                    prop:value=move || model_prewarm.get()
+                    on:change=change_prewarm_action
+                >
+                    <option value="on_launch">"On launch"</option>
+                    <option value="on_first_use">"On first use"</option>
+                    <option value="manual">"Manually"</option>
+                </select>
+            </div>
+            <div class="settings-row">
+                <div class="settings-label">
+                    <span class="settings-title">"Battery Saver"</span>
+                    <span class="settings-hint">"Transcribe once per phrase, skip the live waveform, and load the model on first use"</span>
+                </div>
+                <select
+                    class="settings-input settings-select"
+                    prop:value=move || power_saver.get()
+                    on:change=change_power_saver_action
+                >
+                    <option value="auto">"When on battery"</option>
+                    <option value="on">"Always"</option>
+                    <option value="off">"Never"</option>
+                </select>
+            </div>
+            <div class="settings-row">
+                <div class="settings-label">
+                    <span class="settings-title">"Record While Model Loads"</span>
+                    <span class="settings-hint">"Transcribe shortcut recordings once the model is ready"</span>
+                </div>
+                <button
+                    class="toggle"
+                    class:active=move || buffer_until_ready.get()
+                    on:click=move |_| {
+                        let new_val = !buffer_until_ready.get();
+                        set_buffer_until_ready.set(new_val);
+                        spawn_local(async move { let _ = save_buffer_until_model_ready(new_val).await; });
+                    }
+                >
+                    <div class="toggle-track"><div class="toggle-thumb"></div></div>
+                </button>
+            </div>
+            <div class="settings-row">
+                <div class="settings-label">
+                    <span class="settings-title">"Resampler Quality"</span>
+                    <span class="settings-hint">"Fast uses less CPU on older machines"</span>
+                </div>
+                <select
+                    class="settings-input settings-select"
+                    prop:value=move || resampler_quality.get()
+                    disabled=move || is_recording.get() || transcribing.get()
+                    on:change=change_resampler_action
+                >
+                    <option value="auto">"Automatic"</option>
+                    <option value="fast">"Fast"</option>
+                    <option value="high">"High quality"</option>
+                </select>
+            </div>
+            <HardwareRow set_streaming_enabled set_resampler_quality is_recording />
+            <SpeechThreadsRow is_recording />
+            <InputDeviceRow input_device set_input_device is_recording />
+            <MicrophoneTestRow is_recording />
+            <LookbackRow />
+            <VadSettingsRow vad_settings set_vad_settings is_recording />
+            <NoiseProfilesRow noise_suppression set_noise_suppression is_recording />
+            <SpeakerAdaptationRow speaker_adaptation set_speaker_adaptation />
+            <div class="settings-row">
+                <div class="settings-label">
+                    <span class="settings-title">"Live Waveform"</span>
+                    <span class="settings-hint">"Draw the microphone waveform on the recorder card while recording"</span>
+                </div>
+                <button
+                    class="toggle"
+                    class:active=move || live_waveform.get()
+                    on:click=move |_| {
+                        let new_val = !live_waveform.get();
+                        set_live_waveform.set(new_val);
+                        spawn_local(async move { let _ = save_live_waveform(new_val).await; });
+                    }
+                >
+                    <div class="toggle-track"><div class="toggle-thumb"></div></div>
+                </button>
+            </div>
+            <div class="settings-row">
+                <div class="settings-label">
+                    <span class="settings-title">"Recording Indicator"</span>
+                    <span class="settings-hint">"Mark the tray icon while recording. macOS also shows its own microphone dot."</span>
+                </div>
+                <select
+                    class="settings-input settings-select"
+                    prop:value=move || recording_indicator.get()
+                    on:change=change_recording_indicator_action
+                >
+                    <option value="pulse">"Pulsing"</option>
+                    <option value="steady">"Steady"</option>
+                    <option value="off">"Off"</option>
+                </select>
+            </div>
+            <div class="settings-row">
+                <div class="settings-label">
+                    <span class="settings-title">"Typing Pace"</span>
+                    <span class="settings-hint">"Characters per chunk (0 types at once), delay between chunks in ms, and maximum characters per second (0 is unlimited)"</span>
+                    <p class="settings-status">{ move || typing_pace_status.get() }</p>
+                </div>
+                <div class="settings-input-group">
+                    <input
+                        type="number"
+                        min="0"
+                        class="settings-input"
+                        prop:value=move || typing_pace.get().chunk_chars.to_string()
+                        on:input=move |event| {
+                            let chunk_chars = input_value(&event).parse().unwrap_or(0);
+                            set_typing_pace.update(|pace| pace.chunk_chars = chunk_chars);
+                        }
+                    />
+                    <input
+                        type="number"
+                        min="0"
+                        max="1000"
+                        class="settings-input"
+                        prop:value=move || typing_pace.get().chunk_delay_ms.to_string()
+                        on:input=move |event| {
+                            let chunk_delay_ms = input_value(&event).parse().unwrap_or(0);
+                            set_typing_pace.update(|pace| pace.chunk_delay_ms = chunk_delay_ms);
+                        }
+                    />
+                    <input
+                        type="number"
+                        min="0"
+                        max="2000"
+                        class="settings-input"
+                        prop:value=move || typing_pace.get().max_chars_per_sec.to_string()
+                        on:input=move |event| {
+                            let max_chars_per_sec = input_value(&event).parse().unwrap_or(0);
+                            set_typing_pace.update(|pace| pace.max_chars_per_sec = max_chars_per_sec);
+                        }
+                    />
+                    <button class="ghost compact" on:click=save_typing_pace_action>"Save"</button>
+                </div>
+            </div>
+            <div class="settings-row">
+                <div class="settings-label">
+                    <span class="settings-title">"Pause Typing for Modifier Keys"</span>
+                    <span class="settings-hint">"Hold typed output while Shift, Control, Alt, or the system key is held down"</span>
+                </div>
+                <button
+                    class="toggle"
+                    class:active=move || typing_pace.get().pause_for_modifiers
+                    on:click=toggle_modifier_pause_action
+                >
+                    <div class="toggle-track"><div class="toggle-thumb"></div></div>
+                </button>
+            </div>
+            <div class="settings-row">
+                <div class="settings-label">
+                    <span class="settings-title">"Typing Target Lock"</span>
+                    <span class="settings-hint">"When another window is focused while a dictation is typed, bring back the window the recording started in, or stop and copy the text to the clipboard"</span>
+                </div>
+                <select
+                    class="settings-input settings-select"
+                    prop:value=move || typing_pace.get().target_lock.as_str()
+                    on:change=change_target_lock_action
+                >
+                    <option value="off">"Off"</option>
+                    <option value="refocus">"Refocus"</option>
+                    <option value="abort">"Stop and copy"</option>
+                </select>
+            </div>
+            <div class="settings-row">
+                <div class="settings-label">
+                    <span class="settings-title">"Context-Aware Spacing"</span>
+                    <span class="settings-hint">"Add a space when dictating right after a word in the focused field"</span>
+                </div>
+                <button
+                    class="toggle"
+                    class:active=move || caret_spacing.get()
+                    on:click=move |_| {
+                        let new_val = !caret_spacing.get();
+                        set_caret_spacing.set(new_val);
+                        spawn_local(async move { let _ = save_caret_spacing(new_val).await; });
+                    }
+                >
+                    <div class="toggle-track"><div class="toggle-thumb"></div></div>
+                </button>
+            </div>
+            <ContinuationRow continuation set_continuation />
+            <PauseMarkersRow pause_markers set_pause_markers />
+            <TimeStretchRow time_stretch set_time_stretch />
+            <TextCommandsRow text_commands set_text_commands />
+            <PostProcessingRow post_processing set_post_processing />
+            <WebhookRow webhook set_webhook />
+            <LlmHandoffRow llm_handoff set_llm_handoff />
+            <div class="settings-row">
+                <div class="settings-label">
+                    <span class="settings-title">"Numbers Mode"</span>
+                    <span class="settings-hint">
+                        "Hold Alt+N to dictate phone numbers and IDs; only digits and separators are typed"
+                    </span>
+                </div>
+                <button
+                    class="toggle"
+                    class:active=move || numbers_mode.get()
+                    on:click=move |_| {
+                        let new_val = !numbers_mode.get();
+                        set_numbers_mode.set(new_val);
+                        spawn_local(async move { let _ = save_numbers_mode(new_val).await; });
+                    }
+                >
+                    <div class="toggle-track"><div class="toggle-thumb"></div></div>
+                </button>
+            </div>
+            <OutputTemplatesRow output_templates set_output_templates />
+            <CaptureFileRow capture_file set_capture_file />
+            <EncryptionRow />
+            <DeepLinksRow />
+            <DecodeCacheRow />
+            <PatchRoutingRow patch_routing set_patch_routing />
+            <HistoryRow />
+            <DigestRow />
+            <DocumentRow />
+            <FileTranscriptionRow is_recording />
+            <NetworkInputRow network_input set_network_input />
+            <TypedMetadataRow typed_metadata set_typed_metadata />
+            <CompanionRow companion set_companion />
+            <AutostartRow autostart set_autostart />
+            <SettingsLockRow settings_lock set_settings_lock />
+            <OnboardingRow />
+            <div class="settings-row">
+                <div class="settings-label">
+                    <span class="settings-title">"Announce Transcripts"</span>
+                    <span class="settings-hint">"Read shortcut dictations back through your screen reader"</span>
+                </div>
+                <button
+                    class="toggle"
+                    class:active=move || announce_transcripts.get()
+                    on:click=move |_| {
+                        let new_val = !announce_transcripts.get();
+                        set_announce_transcripts.set(new_val);
+                        spawn_local(async move { let _ = save_announce_transcripts(new_val).await; });
+                    }
+                >
+                    <div class="toggle-track"><div class="toggle-thumb"></div></div>
+                </button>
+            </div>
+            <div class="settings-row">
+                <div class="settings-label">
+                    <span class="settings-title">"Read Back Transcripts"</span>
+                    <span class="settings-hint">"Speak shortcut dictations aloud with the system voice"</span>
+                </div>
+                <button
+                    class="toggle"
+                    class:active=move || speak_transcripts.get()
+                    on:click=move |_| {
+                        let new_val = !speak_transcripts.get();
+                        set_speak_transcripts.set(new_val);
+                        spawn_local(async move { let _ = save_speak_transcripts(new_val).await; });
+                    }
+                >
+                    <div class="toggle-track"><div class="toggle-thumb"></div></div>
+                </button>
+            </div>
             <div class="settings-row">
                 <div class="settings-label">
                     <span class="settings-title">"Shortcut"</span>
                     <p class="settings-status">{ move || shortcut_status.get() }</p>
+                    <ShortcutConflictNotice set_shortcut set_shortcut_status />
                 </div>
                 <div class="settings-input-group">
                     <input
                         type="text"
                         class="settings-input"
-                        value=move || shortcut.get()
+                        prop:value=move || shortcut.get()
                         disabled=move || is_recording.get() || transcribing.get()
                         on:input=move |event| set_shortcut.set(input_value(&event))
                     />
                     <button
                         class="ghost compact"
-                        disabled=move || is_recording.get() || transcribing.get()
+                        disabled=move || is_recording.get() || transcribing.get() || capturing_shortcut.get()
+                        on:click=capture_shortcut_action
+                    >
+                        "Record"
+                    </button>
+                    <button
+                        class="ghost compact"
+                        disabled=move || is_recording.get() || transcribing.get() || capturing_shortcut.get()
                         on:click=save_shortcut_action
                     >
                         "Save"
                     </button>
                 </div>
             </div>
+            <div class="settings-row">
+                <div class="settings-label">
+                    <span class="settings-title">"Swallow Shortcut Key"</span>
+                    <span class="settings-hint">"Keep the record shortcut's key from reaching the focused app, for a dedicated push-to-talk key (Windows)"</span>
+                </div>
+                <button
+                    class="toggle"
+                    class:active=move || suppress_record_key.get()
+                    on:click=move |_| {
+                        let new_val = !suppress_record_key.get();
+                        set_suppress_record_key.set(new_val);
+                        spawn_local(async move { let _ = save_suppress_record_key(new_val).await; });
+                    }
+                >
+                    <div class="toggle-track"><div class="toggle-thumb"></div></div>
+                </button>
+            </div>
+            <ShortcutGestureRow shortcut_gesture set_shortcut_gesture />
+            <InactivityRow inactivity set_inactivity />
+            <TriggerRow triggers set_triggers />
             <div class="settings-row">
                 <div class="settings-label">
                     <span class="settings-title">"Model Location"</span>
                     <code class="path-code">{ move || model_path.get() }</code>
                 </div>
-                <button class="ghost compact" on:click=change_path_action>"Change"</button>
+                <div class="settings-input-group">
+                    <button class="ghost compact" on:click=change_path_action>"Change"</button>
+                    <button class="ghost compact" on:click=import_archive_action>"Import"</button>
+                </div>
             </div>
             <div class="settings-row">
                 <div class="settings-label">
@@ -277,8 +1057,11 @@ pub fn SettingsSection(
                     </button>
                 </div>
             </div>
+            <LogViewer />
             <div class="settings-divider"></div>
             <div class="settings-footer">
+                <button class="ghost compact" on:click=export_config_action>"Export Settings"</button>
+                <button class="ghost compact" on:click=import_config_action>"Import Settings"</button>
                 <button class="danger compact" on:click=reset_settings_action>"Reset All Settings"</button>
             </div>
         </div>
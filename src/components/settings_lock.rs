@@ -0,0 +1,137 @@
+use crate::api::*;
+use crate::components::settings::input_value;
+use leptos::prelude::*;
+use leptos::task::spawn_local;
+
+fn lock_hint(lock: SettingsLockDto) -> &'static str {
+    match (lock.enabled, lock.unlocked) {
+        (false, _) => "Require a passphrase before settings, shortcuts, or the model path can change. Dictation keeps working",
+        (true, false) => "Settings are locked. Enter the passphrase to change them for 10 minutes",
+        (true, true) => "Settings are unlocked for now and lock again after 10 minutes or a restart",
+    }
+}
+
+#[component]
+pub fn SettingsLockRow(
+    settings_lock: ReadSignal<SettingsLockDto>,
+    set_settings_lock: WriteSignal<SettingsLockDto>,
+) -> impl IntoView {
+    let (lock_status, set_lock_status) = signal(String::new());
+    let (passphrase, set_passphrase) = signal(String::new());
+
+    let refresh = move || {
+        spawn_local(async move {
+            if let Ok(lock) = fetch_settings_lock().await {
+                set_settings_lock.set(lock);
+            }
+        });
+    };
+
+    let lock_action = move |_| {
+        let entered = passphrase.get();
+        spawn_local(async move {
+            match save_settings_lock(entered).await {
+                Ok(_) => {
+                    set_passphrase.set(String::new());
+                    set_lock_status.set("Settings locked.".to_string());
+                }
+                Err(err) => set_lock_status.set(format!("Failed to lock settings: {}", err)),
+            }
+            refresh();
+        });
+    };
+
+    let unlock_action = move |_| {
+        let entered = passphrase.get();
+        spawn_local(async move {
+            match unlock_settings(entered).await {
+                Ok(_) => {
+                    set_passphrase.set(String::new());
+                    set_lock_status.set("Settings unlocked.".to_string());
+                }
+                Err(err) => set_lock_status.set(format!("Failed to unlock settings: {}", err)),
+            }
+            refresh();
+        });
+    };
+
+    let relock_action = move |_| {
+        spawn_local(async move {
+            if let Err(err) = lock_settings().await {
+                set_lock_status.set(format!("Failed to lock settings: {}", err));
+            } else {
+                set_lock_status.set("Settings locked.".to_string());
+            }
+            refresh();
+        });
+    };
+
+    let remove_action = move |_| {
+        let entered = passphrase.get();
+        spawn_local(async move {
+            match remove_settings_lock(entered).await {
+                Ok(_) => {
+                    set_passphrase.set(String::new());
+                    set_lock_status.set("Settings lock removed.".to_string());
+                }
+                Err(err) => {
+                    set_lock_status.set(format!("Failed to remove the settings lock: {}", err))
+                }
+            }
+            refresh();
+        });
+    };
+
+    view! {
+        <div class="settings-row">
+            <div class="settings-label">
+                <span class="settings-title">"Settings Lock"</span>
+                <span class="settings-hint">{ move || lock_hint(settings_lock.get()) }</span>
+                <p class="settings-status">{ move || lock_status.get() }</p>
+            </div>
+        </div>
+        <div class="settings-input-group">
+            <input
+                type="password"
+                class="settings-input"
+                placeholder="Passphrase"
+                aria-label="Settings passphrase"
+                prop:value=move || passphrase.get()
+                on:input=move |event| set_passphrase.set(input_value(&event))
+            />
+            {move || (!settings_lock.get().enabled).then(|| view! {
+                <button
+                    class="ghost compact"
+                    disabled=move || passphrase.get().is_empty()
+                    on:click=lock_action
+                >
+                    "Lock"
+                </button>
+            })}
+            {move || {
+                let lock = settings_lock.get();
+                (lock.enabled && !lock.unlocked).then(|| view! {
+                    <button
+                        class="ghost compact"
+                        disabled=move || passphrase.get().is_empty()
+                        on:click=unlock_action
+                    >
+                        "Unlock"
+                    </button>
+                })
+            }}
+            {move || settings_lock.get().unlocked.then(|| view! {
+                <button class="ghost compact" on:click=relock_action>"Lock Now"</button>
+            })}
+            {move || settings_lock.get().enabled.then(|| view! {
+                <button
+                    class="ghost compact"
+                    disabled=move || passphrase.get().is_empty()
+                    on:click=remove_action
+                >
+                    "Remove Lock"
+                </button>
+            })}
+        </div>
+    }
+}
@@ -0,0 +1,98 @@
+use crate::api::*;
+use leptos::prelude::*;
+use leptos::task::spawn_local;
+use wasm_bindgen::prelude::*;
+
+/// Shows why the record shortcut could not be registered, with the free
+/// chords the backend found. Picking one saves it as the shortcut.
+#[component]
+pub fn ShortcutConflictNotice(
+    set_shortcut: WriteSignal<String>,
+    set_shortcut_status: WriteSignal<String>,
+) -> impl IntoView {
+    let (conflict, set_conflict) = signal(None::<ShortcutConflictDto>);
+
+    spawn_local(async move {
+        let callback = Closure::wrap(Box::new(move |event: JsValue| {
+            let Ok(payload) = js_sys::Reflect::get(&event, &"payload".into()) else {
+                return;
+            };
+            match serde_wasm_bindgen::from_value::<Option<ShortcutConflictDto>>(payload) {
+                Ok(found) => set_conflict.set(found),
+                Err(error) => {
+                    leptos::logging::error!("Failed to parse shortcut conflict: {:?}", error)
+                }
+            }
+        }) as Box<dyn FnMut(JsValue)>);
+
+        if let Err(error) = listen("shortcut_conflict", &callback).await {
+            leptos::logging::error!("Failed to listen for shortcut conflicts: {:?}", error);
+        }
+        callback.forget();
+
+        if let Ok(found) = fetch_shortcut_conflict().await {
+            set_conflict.set(found);
+        }
+    });
+
+    let use_suggestion = move |suggestion: String| {
+        set_shortcut.set(suggestion.clone());
+        set_shortcut_status.set("Saving shortcut...".to_string());
+        spawn_local(async move {
+            match save_shortcut(&suggestion).await {
+                Ok(saved) => {
+                    set_conflict.set(None);
+                    set_shortcut_status.set(format!("Shortcut saved: {}", saved));
+                }
+                Err(err) => set_shortcut_status.set(format!("Failed to save shortcut: {}", err)),
+            }
+        });
+    };
+
+    let summary = move || {
+        conflict.with(|conflict| {
+            let Some(conflict) = conflict else {
+                return String::new();
+            };
+            if conflict.likely_owners.is_empty() {
+                format!("{} is taken by another app.", conflict.shortcut)
+            } else {
+                format!(
+                    "{} is taken, likely by {}.",
+                    conflict.shortcut,
+                    conflict.likely_owners.join(" or ")
+                )
+            }
+        })
+    };
+    let suggestions = move || {
+        conflict.with(|conflict| {
+            conflict
+                .as_ref()
+                .map(|conflict| conflict.suggestions.clone())
+                .unwrap_or_default()
+        })
+    };
+
+    view! {
+        <Show when=move || conflict.with(Option::is_some)>
+            <p class="settings-status">{summary}</p>
+            <Show when=move || !suggestions().is_empty()>
+                <div class="settings-input-group">
+                    <span class="settings-hint">"Free instead:"</span>
+                    {move || suggestions().into_iter().map(|suggestion| {
+                        let label = suggestion.clone();
+                        view! {
+                            <button
+                                class="ghost compact"
+                                on:click=move |_| use_suggestion(suggestion.clone())
+                            >
+                                {label}
+                            </button>
+                        }
+                    }).collect_view()}
+                </div>
+            </Show>
+        </Show>
+    }
+}
@@ -0,0 +1,83 @@
+use crate::api::*;
+use crate::components::settings::{input_value, select_value};
+use leptos::prelude::*;
+use leptos::task::spawn_local;
+
+#[component]
+pub fn ShortcutGestureRow(
+    shortcut_gesture: ReadSignal<ShortcutGestureDto>,
+    set_shortcut_gesture: WriteSignal<ShortcutGestureDto>,
+) -> impl IntoView {
+    let (gesture_status, set_gesture_status) = signal(String::new());
+
+    let save_action = move |_| {
+        let gesture = shortcut_gesture.get();
+        spawn_local(async move {
+            match save_shortcut_gesture(gesture).await {
+                Ok(_) => {
+                    if let Ok(saved) = fetch_shortcut_gesture().await {
+                        set_shortcut_gesture.set(saved);
+                    }
+                    set_gesture_status.set("Shortcut gesture saved.".to_string());
+                }
+                Err(err) => {
+                    set_gesture_status.set(format!("Failed to save shortcut gesture: {}", err))
+                }
+            }
+        });
+    };
+
+    view! {
+        <div class="settings-row">
+            <div class="settings-label">
+                <span class="settings-title">"Shortcut Gesture"</span>
+                <span class="settings-hint">
+                    "Hold to record, double-tap to latch recording on (holding still records push-to-talk), or press the shortcut then a follow-up key such as D to start and stop; the window is in ms"
+                </span>
+                <p class="settings-status">{ move || gesture_status.get() }</p>
+            </div>
+            <select
+                class="settings-input settings-select"
+                prop:value=move || shortcut_gesture.get().mode
+                on:change=move |event| {
+                    let mode = select_value(&event);
+                    set_shortcut_gesture.update(|gesture| gesture.mode = mode);
+                }
+            >
+                <option value="hold">"Hold"</option>
+                <option value="double_tap">"Double-tap"</option>
+                <option value="chord">"Chord"</option>
+            </select>
+        </div>
+        <div class="settings-input-group">
+            <input
+                type="number"
+                class="settings-input"
+                min="150"
+                max="3000"
+                step="50"
+                aria-label="Gesture window in milliseconds"
+                disabled=move || shortcut_gesture.get().mode == "hold"
+                prop:value=move || shortcut_gesture.get().window_ms.to_string()
+                on:input=move |event| {
+                    if let Ok(window_ms) = input_value(&event).parse::<u64>() {
+                        set_shortcut_gesture.update(|gesture| gesture.window_ms = window_ms);
+                    }
+                }
+            />
+            <input
+                type="text"
+                class="settings-input"
+                placeholder="Follow-up key"
+                aria-label="Chord follow-up key"
+                disabled=move || shortcut_gesture.get().mode != "chord"
+                prop:value=move || shortcut_gesture.get().follow_up
+                on:input=move |event| {
+                    let follow_up = input_value(&event);
+                    set_shortcut_gesture.update(|gesture| gesture.follow_up = follow_up);
+                }
+            />
+            <button class="ghost compact" on:click=save_action>"Save"</button>
+        </div>
+    }
+}
@@ -0,0 +1,82 @@
+use crate::api::*;
+use leptos::prelude::*;
+use leptos::task::spawn_local;
+
+/// Utterances heard before the learned voice starts correcting audio.
+const MIN_UTTERANCES: u32 = 3;
+
+fn voice_summary(adaptation: &SpeakerAdaptationDto) -> String {
+    let voice = adaptation.voice;
+    match adaptation.correction {
+        Some(correction) => format!(
+            "Learned from {} dictations: speech at {:.0} dBFS, treble {:+.0} dB against bass. Correcting by {:+.0} dB level and {:+.0} dB treble.",
+            voice.utterances, voice.level_db, voice.tilt_db, correction.gain_db, correction.tilt_db
+        ),
+        None => format!(
+            "Learning your voice: {} of {} dictations heard.",
+            voice.utterances.min(MIN_UTTERANCES),
+            MIN_UTTERANCES
+        ),
+    }
+}
+
+#[component]
+pub fn SpeakerAdaptationRow(
+    speaker_adaptation: ReadSignal<SpeakerAdaptationDto>,
+    set_speaker_adaptation: WriteSignal<SpeakerAdaptationDto>,
+) -> impl IntoView {
+    let (adaptation_status, set_adaptation_status) = signal(String::new());
+
+    let toggle_action = move |_| {
+        let enabled = !speaker_adaptation.get().enabled;
+        spawn_local(async move {
+            match save_speaker_adaptation(enabled).await {
+                Ok(saved) => set_speaker_adaptation.set(saved),
+                Err(err) => {
+                    set_adaptation_status.set(format!("Failed to set speaker adaptation: {}", err))
+                }
+            }
+        });
+    };
+
+    let reset_action = move |_| {
+        spawn_local(async move {
+            match reset_voice_profile().await {
+                Ok(saved) => {
+                    set_speaker_adaptation.set(saved);
+                    set_adaptation_status.set("Learned voice cleared.".to_string());
+                }
+                Err(err) => {
+                    set_adaptation_status.set(format!("Failed to reset learned voice: {}", err))
+                }
+            }
+        });
+    };
+
+    view! {
+        <div class="settings-row">
+            <div class="settings-label">
+                <span class="settings-title">"Speaker Adaptation"</span>
+                <span class="settings-hint">
+                    "Learns the level and tone of your voice over dictations and evens them out toward what the speech model was trained on. Helps quiet and bass-heavy voices; each profile learns its own"
+                </span>
+                <span class="settings-hint">{ move || voice_summary(&speaker_adaptation.get()) }</span>
+                <p class="settings-status">{ move || adaptation_status.get() }</p>
+            </div>
+            <button
+                class="ghost compact"
+                disabled=move || speaker_adaptation.get().voice.utterances == 0
+                on:click=reset_action
+            >
+                "Forget Voice"
+            </button>
+            <button
+                class="toggle"
+                class:active=move || speaker_adaptation.get().enabled
+                on:click=toggle_action
+            >
+                <div class="toggle-track"><div class="toggle-thumb"></div></div>
+            </button>
+        </div>
+    }
+}
@@ -0,0 +1,76 @@
+use crate::api::*;
+use leptos::prelude::*;
+
+fn format_span(ms: u64) -> String {
+    let secs = ms / 1_000;
+    format!("{}:{:02}", secs / 60, secs % 60)
+}
+
+fn summary(words: usize, speaking_ms: u64, duration_ms: u64, longest_ms: u64) -> String {
+    let wpm = if speaking_ms == 0 {
+        0.0
+    } else {
+        words as f32 * 60_000.0 / speaking_ms as f32
+    };
+    let pauses = if duration_ms == 0 {
+        0.0
+    } else {
+        duration_ms.saturating_sub(speaking_ms) as f32 / duration_ms as f32
+    };
+    format!(
+        "{:.0} wpm, {:.0}% pauses, longest monologue {}",
+        wpm,
+        pauses * 100.0,
+        format_span(longest_ms)
+    )
+}
+
+/// Speech rate and talk time of the last dictation and of those stored.
+#[component]
+pub fn SpeechStatsCard(speech_stats: ReadSignal<Vec<SpeechStatsDto>>) -> impl IntoView {
+    let last = move || {
+        speech_stats.with(|sessions| {
+            sessions.last().map(|stats| {
+                summary(
+                    stats.words,
+                    stats.speaking_ms,
+                    stats.duration_ms,
+                    stats.longest_monologue_ms,
+                )
+            })
+        })
+    };
+    let overall = move || {
+        speech_stats.with(|sessions| {
+            let words = sessions.iter().map(|stats| stats.words).sum();
+            let speaking_ms = sessions.iter().map(|stats| stats.speaking_ms).sum();
+            let duration_ms = sessions.iter().map(|stats| stats.duration_ms).sum();
+            let longest_ms = sessions
+                .iter()
+                .map(|stats| stats.longest_monologue_ms)
+                .max()
+                .unwrap_or(0);
+            format!(
+                "{} over {} sessions",
+                summary(words, speaking_ms, duration_ms, longest_ms),
+                sessions.len()
+            )
+        })
+    };
+
+    view! {
+        <div class="card">
+            <div class="card-header"><p class="eyebrow">"Speaking"</p></div>
+            <p class="settings-title">
+                {move || {
+                    last().unwrap_or_else(|| {
+                        "Speech rate and talk time appear after a dictation.".to_string()
+                    })
+                }}
+            </p>
+            <p class="settings-hint">
+                {move || (!speech_stats.with(Vec::is_empty)).then(overall)}
+            </p>
+        </div>
+    }
+}
@@ -0,0 +1,132 @@
+use crate::api::*;
+use crate::components::settings::input_value;
+use leptos::prelude::*;
+use leptos::task::spawn_local;
+
+fn threads_label(count: usize) -> String {
+    match count {
+        0 => "auto".to_string(),
+        count => count.to_string(),
+    }
+}
+
+fn tuning_summary(tuning: &ThreadTuningDto) -> String {
+    let timings = tuning
+        .timings
+        .iter()
+        .map(|timing| {
+            format!(
+                "{}/{}: {} ms",
+                timing.threads.intra_threads, timing.threads.inter_threads, timing.decode_ms
+            )
+        })
+        .collect::<Vec<_>>()
+        .join(", ");
+    format!(
+        "Fastest was {} intra and {} inter threads ({}).",
+        tuning.fastest.intra_threads, tuning.fastest.inter_threads, timings
+    )
+}
+
+#[component]
+pub fn SpeechThreadsRow(is_recording: ReadSignal<bool>) -> impl IntoView {
+    let (threads, set_threads) = signal(OrtThreadsDto::default());
+    let (threads_status, set_threads_status) = signal(String::new());
+    let (tuning, set_tuning) = signal(false);
+
+    let refresh = move || {
+        spawn_local(async move {
+            if let Ok(saved) = fetch_ort_threads().await {
+                set_threads.set(saved);
+            }
+        });
+    };
+    refresh();
+
+    let save_action = move |_| {
+        let requested = threads.get();
+        spawn_local(async move {
+            match save_ort_threads(requested).await {
+                Ok(_) => set_threads_status
+                    .set("Speech threads saved. The speech model reloads to use them.".to_string()),
+                Err(err) => {
+                    set_threads_status.set(format!("Failed to save speech threads: {}", err))
+                }
+            }
+            refresh();
+        });
+    };
+
+    let tune_action = move |_| {
+        set_tuning.set(true);
+        set_threads_status.set("Timing thread counts on the sample recording...".to_string());
+        spawn_local(async move {
+            match tune_ort_threads_cmd().await {
+                Ok(result) => set_threads_status.set(tuning_summary(&result)),
+                Err(err) => {
+                    set_threads_status.set(format!("Failed to tune speech threads: {}", err))
+                }
+            }
+            set_tuning.set(false);
+            refresh();
+        });
+    };
+
+    view! {
+        <div class="settings-row">
+            <div class="settings-label">
+                <span class="settings-title">"Speech Threads"</span>
+                <span class="settings-hint">
+                    "Threads within each model step and across independent steps (0 picks from the core count). Auto-Tune times a few on a sample recording and keeps the fastest"
+                </span>
+                <span class="settings-hint">
+                    {move || {
+                        let threads = threads.get();
+                        format!(
+                            "Current: {} intra, {} inter",
+                            threads_label(threads.intra_threads),
+                            threads_label(threads.inter_threads)
+                        )
+                    }}
+                </span>
+                <p class="settings-status">{ move || threads_status.get() }</p>
+            </div>
+        </div>
+        <div class="settings-input-group">
+            <input
+                type="number"
+                min="0"
+                max="16"
+                class="settings-input"
+                aria-label="Intra-op threads"
+                prop:value=move || threads.get().intra_threads.to_string()
+                on:input=move |event| {
+                    let intra_threads = input_value(&event).parse().unwrap_or(0);
+                    set_threads.update(|threads| threads.intra_threads = intra_threads);
+                }
+            />
+            <input
+                type="number"
+                min="0"
+                max="16"
+                class="settings-input"
+                aria-label="Inter-op threads"
+                prop:value=move || threads.get().inter_threads.to_string()
+                on:input=move |event| {
+                    let inter_threads = input_value(&event).parse().unwrap_or(0);
+                    set_threads.update(|threads| threads.inter_threads = inter_threads);
+                }
+            />
+            <button class="ghost compact" disabled=move || tuning.get() on:click=save_action>
+                "Save"
+            </button>
+            <button
+                class="ghost compact"
+                disabled=move || tuning.get() || is_recording.get()
+                on:click=tune_action
+            >
+                {move || if tuning.get() { "Tuning..." } else { "Auto-Tune" }}
+            </button>
+        </div>
+    }
+}
@@ -0,0 +1,68 @@
+use crate::api::*;
+use leptos::prelude::*;
+use leptos::task::spawn_local;
+
+#[component]
+pub fn TextCommandsRow(
+    text_commands: ReadSignal<TextCommandsDto>,
+    set_text_commands: WriteSignal<TextCommandsDto>,
+) -> impl IntoView {
+    let (commands_status, set_commands_status) = signal(String::new());
+
+    let save = move |settings: TextCommandsDto| {
+        set_text_commands.set(settings.clone());
+        spawn_local(async move {
+            match save_text_commands(settings).await {
+                Ok(_) => set_commands_status.set(String::new()),
+                Err(err) => {
+                    if let Ok(saved) = fetch_text_commands().await {
+                        set_text_commands.set(saved);
+                    }
+                    set_commands_status.set(format!("Failed to save text commands: {}", err))
+                }
+            }
+        });
+    };
+
+    view! {
+        <div class="settings-row">
+            <div class="settings-label">
+                <span class="settings-title">"Spelling Mode"</span>
+                <span class="settings-hint">
+                    "Say \"spell\" then letters, digits, or NATO words (\"spell J O H N\") to type them as one word"
+                </span>
+                <p class="settings-status">{ move || commands_status.get() }</p>
+            </div>
+            <button
+                class="toggle"
+                class:active=move || text_commands.get().spelling
+                on:click=move |_| {
+                    let mut settings = text_commands.get();
+                    settings.spelling = !settings.spelling;
+                    save(settings);
+                }
+            >
+                <div class="toggle-track"><div class="toggle-thumb"></div></div>
+            </button>
+        </div>
+        <div class="settings-row">
+            <div class="settings-label">
+                <span class="settings-title">"Code Mode"</span>
+                <span class="settings-hint">
+                    "Type spoken symbols (\"open paren\", \"arrow\") without spaces and join words with \"camel case\" or \"snake case\""
+                </span>
+            </div>
+            <button
+                class="toggle"
+                class:active=move || text_commands.get().code_mode
+                on:click=move |_| {
+                    let mut settings = text_commands.get();
+                    settings.code_mode = !settings.code_mode;
+                    save(settings);
+                }
+            >
+                <div class="toggle-track"><div class="toggle-thumb"></div></div>
+            </button>
+        </div>
+    }
+}
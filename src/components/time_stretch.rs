@@ -0,0 +1,63 @@
+use crate::api::*;
+use crate::components::settings::input_value;
+use leptos::prelude::*;
+use leptos::task::spawn_local;
+
+#[component]
+pub fn TimeStretchRow(
+    time_stretch: ReadSignal<TimeStretchDto>,
+    set_time_stretch: WriteSignal<TimeStretchDto>,
+) -> impl IntoView {
+    let (stretch_status, set_stretch_status) = signal(String::new());
+
+    let save_action = move |_| {
+        let settings = time_stretch.get();
+        spawn_local(async move {
+            match save_time_stretch(settings).await {
+                Ok(_) => {
+                    if let Ok(saved) = fetch_time_stretch().await {
+                        set_time_stretch.set(saved);
+                    }
+                    set_stretch_status.set("Time stretch saved.".to_string());
+                }
+                Err(err) => set_stretch_status.set(format!("Failed to save time stretch: {}", err)),
+            }
+        });
+    };
+
+    view! {
+        <div class="settings-row">
+            <div class="settings-label">
+                <span class="settings-title">"Slow Down Fast Speech"</span>
+                <span class="settings-hint">
+                    "Stretch speech to this percentage of its speed, without changing pitch, before the final transcription; saved per profile"
+                </span>
+                <p class="settings-status">{ move || stretch_status.get() }</p>
+            </div>
+            <button
+                class="toggle"
+                class:active=move || time_stretch.get().enabled
+                on:click=move |_| set_time_stretch.update(|settings| settings.enabled = !settings.enabled)
+            >
+                <div class="toggle-track"><div class="toggle-thumb"></div></div>
+            </button>
+        </div>
+        <div class="settings-input-group">
+            <input
+                type="number"
+                class="settings-input"
+                min="50"
+                max="100"
+                step="5"
+                aria-label="Speech speed in percent"
+                prop:value=move || time_stretch.get().speed_percent.to_string()
+                on:input=move |event| {
+                    if let Ok(speed_percent) = input_value(&event).parse::<u32>() {
+                        set_time_stretch.update(|settings| settings.speed_percent = speed_percent);
+                    }
+                }
+            />
+            <button class="ghost compact" on:click=save_action>"Save"</button>
+        </div>
+    }
+}
@@ -0,0 +1,179 @@
+use crate::api::*;
+use crate::components::settings::select_value;
+use leptos::prelude::*;
+use leptos::task::spawn_local;
+
+fn device_key(vendor_id: u16, product_id: u16) -> String {
+    format!("{:04x}:{:04x}", vendor_id, product_id)
+}
+
+fn parse_device_key(key: &str) -> Option<(u16, u16)> {
+    let (vendor, product) = key.split_once(':')?;
+    Some((
+        u16::from_str_radix(vendor, 16).ok()?,
+        u16::from_str_radix(product, 16).ok()?,
+    ))
+}
+
+#[component]
+pub fn TriggerRow(
+    triggers: ReadSignal<TriggerSettingsDto>,
+    set_triggers: WriteSignal<TriggerSettingsDto>,
+) -> impl IntoView {
+    let (devices, set_devices) = signal(Vec::<HidDeviceDto>::new());
+    let (trigger_status, set_trigger_status) = signal(String::new());
+    let (detecting, set_detecting) = signal(false);
+
+    let refresh_devices = move || {
+        spawn_local(async move {
+            match list_hid_devices().await {
+                Ok(list) => set_devices.set(list),
+                Err(err) => set_trigger_status.set(format!("Failed to list USB devices: {}", err)),
+            }
+        });
+    };
+
+    let persist = move |saved_message: &'static str| {
+        let settings = triggers.get();
+        spawn_local(async move {
+            match save_triggers(settings).await {
+                Ok(_) => {
+                    if let Ok(saved) = fetch_triggers().await {
+                        set_triggers.set(saved);
+                    }
+                    set_trigger_status.set(saved_message.to_string());
+                }
+                Err(err) => set_trigger_status.set(format!("Failed to save triggers: {}", err)),
+            }
+        });
+    };
+
+    let detect_action = move |_| {
+        set_detecting.set(true);
+        set_trigger_status.set("Press the mouse button to use...".to_string());
+        spawn_local(async move {
+            match detect_mouse_button().await {
+                Ok(code) => {
+                    set_triggers.update(|settings| settings.mouse_button = Some(code));
+                    persist("Mouse button saved.");
+                }
+                Err(err) => set_trigger_status.set(err),
+            }
+            set_detecting.set(false);
+        });
+    };
+
+    let clear_mouse_action = move |_| {
+        set_triggers.update(|settings| settings.mouse_button = None);
+        persist("Mouse button cleared.");
+    };
+
+    let save_pedal_action = move |_| persist("Pedal saved.");
+
+    view! {
+        <div class="settings-row">
+            <div class="settings-label">
+                <span class="settings-title">"Record Triggers"</span>
+                <span class="settings-hint">
+                    "Hold a side mouse button or a USB foot pedal to record, like holding the shortcut"
+                </span>
+                <p class="settings-status">{ move || trigger_status.get() }</p>
+            </div>
+            <div class="settings-input-group">
+                <span class="settings-hint">
+                    {move || match triggers.get().mouse_button {
+                        Some(code) => format!("Mouse button {}", code),
+                        None => "No mouse button".to_string(),
+                    }}
+                </span>
+                <button
+                    class="ghost compact"
+                    disabled=move || detecting.get()
+                    on:click=detect_action
+                >
+                    "Detect"
+                </button>
+                <button
+                    class="ghost compact"
+                    disabled=move || triggers.get().mouse_button.is_none()
+                    on:click=clear_mouse_action
+                >
+                    "Clear"
+                </button>
+            </div>
+        </div>
+        <div class="settings-input-group">
+            <select
+                class="settings-input settings-select"
+                aria-label="Foot pedal device"
+                prop:value=move || {
+                    triggers
+                        .get()
+                        .pedal
+                        .map(|pedal| device_key(pedal.vendor_id, pedal.product_id))
+                        .unwrap_or_default()
+                }
+                on:focus=move |_| refresh_devices()
+                on:change=move |event| {
+                    let key = select_value(&event);
+                    set_triggers.update(|settings| {
+                        settings.pedal = parse_device_key(&key).map(|(vendor_id, product_id)| {
+                            let current = settings.pedal.unwrap_or(PedalBindingDto {
+                                mask: 2,
+                                ..PedalBindingDto::default()
+                            });
+                            PedalBindingDto { vendor_id, product_id, ..current }
+                        });
+                    });
+                }
+            >
+                <option value="">"No pedal"</option>
+                <For
+                    each=move || {
+                        let mut list = devices.get();
+                        if let Some(pedal) = triggers.get().pedal {
+                            if !list.iter().any(|device| {
+                                device.vendor_id == pedal.vendor_id
+                                    && device.product_id == pedal.product_id
+                            }) {
+                                list.push(HidDeviceDto {
+                                    vendor_id: pedal.vendor_id,
+                                    product_id: pedal.product_id,
+                                    name: device_key(pedal.vendor_id, pedal.product_id),
+                                });
+                            }
+                        }
+                        list
+                    }
+                    key=|device| (device.vendor_id, device.product_id)
+                    children=move |device| {
+                        let key = device_key(device.vendor_id, device.product_id);
+                        view! { <option value=key>{device.name}</option> }
+                    }
+                />
+            </select>
+            <select
+                class="settings-input settings-select"
+                aria-label="Pedal that records"
+                disabled=move || triggers.get().pedal.is_none()
+                prop:value=move || {
+                    triggers.get().pedal.map(|pedal| pedal.mask).unwrap_or(2).to_string()
+                }
+                on:change=move |event| {
+                    if let Ok(mask) = select_value(&event).parse::<u8>() {
+                        set_triggers.update(|settings| {
+                            if let Some(pedal) = settings.pedal.as_mut() {
+                                pedal.mask = mask;
+                            }
+                        });
+                    }
+                }
+            >
+                <option value="1">"Left pedal"</option>
+                <option value="2">"Middle pedal"</option>
+                <option value="4">"Right pedal"</option>
+            </select>
+            <button class="ghost compact" on:click=save_pedal_action>"Save"</button>
+        </div>
+    }
+}
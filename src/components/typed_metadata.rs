@@ -0,0 +1,77 @@
+use crate::api::*;
+use crate::components::settings::input_value;
+use leptos::prelude::*;
+use leptos::task::spawn_local;
+
+#[component]
+pub fn TypedMetadataRow(
+    typed_metadata: ReadSignal<TypedMetadataDto>,
+    set_typed_metadata: WriteSignal<TypedMetadataDto>,
+) -> impl IntoView {
+    let (metadata_status, set_metadata_status) = signal(String::new());
+
+    let save_action = move |_| {
+        let settings = typed_metadata.get();
+        spawn_local(async move {
+            match save_typed_metadata(settings).await {
+                Ok(_) => {
+                    if let Ok(saved) = fetch_typed_metadata().await {
+                        set_typed_metadata.set(saved);
+                    }
+                    set_metadata_status.set("Editor metadata saved.".to_string());
+                }
+                Err(err) => {
+                    set_metadata_status.set(format!("Failed to save editor metadata: {}", err))
+                }
+            }
+        });
+    };
+
+    view! {
+        <div class="settings-row">
+            <div class="settings-label">
+                <span class="settings-title">"Editor Metadata"</span>
+                <span class="settings-hint">
+                    "Publish each typed dictation with its word timings on this localhost port, for editor plugins"
+                </span>
+                <p class="settings-status">{ move || metadata_status.get() }</p>
+            </div>
+            <button
+                class="toggle"
+                class:active=move || typed_metadata.get().enabled
+                on:click=move |_| set_typed_metadata.update(|settings| settings.enabled = !settings.enabled)
+            >
+                <div class="toggle-track"><div class="toggle-thumb"></div></div>
+            </button>
+        </div>
+        <div class="settings-row">
+            <div class="settings-label">
+                <span class="settings-title">"Mark Dictation Boundaries"</span>
+                <span class="settings-hint">
+                    "Type an invisible separator after each dictation so plugins can tell where it ends"
+                </span>
+            </div>
+            <button
+                class="toggle"
+                class:active=move || typed_metadata.get().delimiter
+                on:click=move |_| set_typed_metadata.update(|settings| settings.delimiter = !settings.delimiter)
+            >
+                <div class="toggle-track"><div class="toggle-thumb"></div></div>
+            </button>
+        </div>
+        <div class="settings-input-group">
+            <input
+                type="number"
+                min="1"
+                max="65535"
+                class="settings-input"
+                prop:value=move || typed_metadata.get().port.to_string()
+                on:input=move |event| {
+                    let port = input_value(&event).parse().unwrap_or(0);
+                    set_typed_metadata.update(|settings| settings.port = port);
+                }
+            />
+            <button class="ghost compact" on:click=save_action>"Save"</button>
+        </div>
+    }
+}
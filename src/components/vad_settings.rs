@@ -0,0 +1,142 @@
+use crate::api::*;
+use crate::components::settings::input_value;
+use leptos::prelude::*;
+use leptos::task::spawn_local;
+
+fn dbfs(rms: f32) -> String {
+    if rms > 0.0 {
+        format!("{:.0} dBFS", 20.0 * rms.log10())
+    } else {
+        "silent".to_string()
+    }
+}
+
+fn calibration_text(calibration: &NoiseCalibrationDto) -> String {
+    format!(
+        "Noise floor {} (peak {}). Recommended threshold {:.4}, gain {:.1}x.",
+        dbfs(calibration.noise_floor),
+        dbfs(calibration.peak),
+        calibration.recommended.threshold,
+        calibration.recommended.gain
+    )
+}
+
+#[component]
+pub fn VadSettingsRow(
+    vad_settings: ReadSignal<VadSettingsDto>,
+    set_vad_settings: WriteSignal<VadSettingsDto>,
+    is_recording: ReadSignal<bool>,
+) -> impl IntoView {
+    let (vad_status, set_vad_status) = signal(String::new());
+    let (calibrating, set_calibrating) = signal(false);
+    let (calibration, set_calibration) = signal::<Option<NoiseCalibrationDto>>(None);
+
+    let save = move |settings: VadSettingsDto| {
+        spawn_local(async move {
+            match save_vad_settings(settings).await {
+                Ok(_) => {
+                    if let Ok(saved) = fetch_vad_settings().await {
+                        set_vad_settings.set(saved);
+                    }
+                    set_vad_status.set("Voice detection saved.".to_string());
+                }
+                Err(err) => set_vad_status.set(format!("Failed to save voice detection: {}", err)),
+            }
+        });
+    };
+
+    let calibrate_action = move |_| {
+        set_calibrating.set(true);
+        set_calibration.set(None);
+        set_vad_status.set("Listening to the room. Stay quiet for 3 seconds...".to_string());
+        spawn_local(async move {
+            match calibrate_microphone().await {
+                Ok(result) => {
+                    set_vad_status.set(calibration_text(&result));
+                    set_calibration.set(Some(result));
+                }
+                Err(err) => set_vad_status.set(format!("Calibration failed: {}", err)),
+            }
+            set_calibrating.set(false);
+        });
+    };
+
+    let apply_action = move |_| {
+        if let Some(result) = calibration.get() {
+            set_vad_settings.set(result.recommended);
+            set_calibration.set(None);
+            save(result.recommended);
+        }
+    };
+
+    view! {
+        <div class="settings-row">
+            <div class="settings-label">
+                <span class="settings-title">"Voice Detection"</span>
+                <span class="settings-hint">
+                    "Calibrate to the room's background noise, or set the speech threshold (RMS), margin above the noise floor (dB), and microphone gain"
+                </span>
+                <p class="settings-status">{ move || vad_status.get() }</p>
+            </div>
+            <button
+                class="ghost compact"
+                disabled=move || calibrating.get() || is_recording.get()
+                on:click=calibrate_action
+            >
+                { move || if calibrating.get() { "Listening..." } else { "Calibrate Microphone" } }
+            </button>
+            <button
+                class="ghost compact"
+                disabled=move || calibration.get().is_none()
+                on:click=apply_action
+            >
+                "Apply"
+            </button>
+        </div>
+        <div class="settings-input-group">
+            <input
+                type="number"
+                class="settings-input"
+                min="0.001"
+                max="0.2"
+                step="0.001"
+                aria-label="Speech threshold"
+                prop:value=move || format!("{:.4}", vad_settings.get().threshold)
+                on:change=move |event| {
+                    if let Ok(threshold) = input_value(&event).parse::<f32>() {
+                        set_vad_settings.update(|settings| settings.threshold = threshold);
+                    }
+                }
+            />
+            <input
+                type="number"
+                class="settings-input"
+                min="3"
+                max="30"
+                step="1"
+                aria-label="Noise margin in decibels"
+                prop:value=move || vad_settings.get().noise_margin_db.to_string()
+                on:change=move |event| {
+                    if let Ok(margin) = input_value(&event).parse::<f32>() {
+                        set_vad_settings.update(|settings| settings.noise_margin_db = margin);
+                    }
+                }
+            />
+            <input
+                type="number"
+                class="settings-input"
+                min="0.25"
+                max="8"
+                step="0.25"
+                aria-label="Microphone gain"
+                prop:value=move || vad_settings.get().gain.to_string()
+                on:change=move |event| {
+                    if let Ok(gain) = input_value(&event).parse::<f32>() {
+                        set_vad_settings.update(|settings| settings.gain = gain);
+                    }
+                }
+            />
+            <button class="ghost compact" on:click=move |_| save(vad_settings.get())>"Save"</button>
+        </div>
+    }
+}
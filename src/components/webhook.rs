@@ -0,0 +1,67 @@
+use crate::api::*;
+use crate::components::settings::input_value;
+use leptos::prelude::*;
+use leptos::task::spawn_local;
+
+#[component]
+pub fn WebhookRow(
+    webhook: ReadSignal<WebhookDto>,
+    set_webhook: WriteSignal<WebhookDto>,
+) -> impl IntoView {
+    let (webhook_status, set_webhook_status) = signal(String::new());
+
+    let save_action = move |_| {
+        let settings = webhook.get();
+        spawn_local(async move {
+            match save_webhook(settings).await {
+                Ok(_) => {
+                    if let Ok(saved) = fetch_webhook().await {
+                        set_webhook.set(saved);
+                    }
+                    set_webhook_status.set("Webhook saved.".to_string());
+                }
+                Err(err) => set_webhook_status.set(format!("Failed to save webhook: {}", err)),
+            }
+        });
+    };
+
+    view! {
+        <div class="settings-row">
+            <div class="settings-label">
+                <span class="settings-title">"Webhook"</span>
+                <span class="settings-hint">"POST each finished dictation as JSON; a secret signs requests"</span>
+                <p class="settings-status">{ move || webhook_status.get() }</p>
+            </div>
+            <button
+                class="toggle"
+                class:active=move || webhook.get().enabled
+                on:click=move |_| set_webhook.update(|settings| settings.enabled = !settings.enabled)
+            >
+                <div class="toggle-track"><div class="toggle-thumb"></div></div>
+            </button>
+        </div>
+        <div class="settings-input-group">
+            <input
+                type="url"
+                class="settings-input"
+                placeholder="https://example.com/dictation"
+                prop:value=move || webhook.get().url
+                on:input=move |event| {
+                    let url = input_value(&event);
+                    set_webhook.update(|settings| settings.url = url);
+                }
+            />
+            <input
+                type="password"
+                class="settings-input"
+                placeholder="Signing secret"
+                prop:value=move || webhook.get().secret
+                on:input=move |event| {
+                    let secret = input_value(&event);
+                    set_webhook.update(|settings| settings.secret = secret);
+                }
+            />
+            <button class="ghost compact" on:click=save_action>"Save"</button>
+        </div>
+    }
+}
@@ -3,10 +3,15 @@ mod app;
 mod components;
 
 use app::*;
+use components::document::DocumentEditor;
 use leptos::prelude::*;
 
 fn main() {
     console_error_panic_hook::set_once();
+    if api::current_window_label() == "document" {
+        mount_to_body(|| view! { <DocumentEditor/> });
+        return;
+    }
     mount_to_body(|| {
         view! {
             <App/>
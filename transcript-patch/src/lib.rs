@@ -0,0 +1,158 @@
+//! The patches that build a dictation's transcript, shared by the backend
+//! that emits them and the UI that applies them so both read the same wire
+//! format and agree on the result.
+//!
+//! Offsets count Unicode scalar values (Rust `char`s), not bytes or UTF-16
+//! units, and are `u32` on every target so a patch means the same thing on
+//! the desktop and in wasm.
+
+use std::ops::Range;
+
+use serde::{Deserialize, Serialize};
+
+#[derive(Clone, Debug, Deserialize, Eq, PartialEq, Serialize)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum TranscriptPatch {
+    /// Provisional text for the whole transcript, shown until the next commit
+    /// or replacement. Drafts are never typed.
+    Draft { full_text: String },
+    /// A draft sent as a window over the text shown now: its first `keep`
+    /// characters followed by `tail`. Only sent to a receiver that asked for
+    /// windowed drafts.
+    DraftTail { keep: u32, tail: String },
+    /// Text appended to the committed transcript.
+    Commit { append: String },
+    /// Replaces `range` of the committed transcript with `text`.
+    Replace { range: Range<u32>, text: String },
+}
+
+#[derive(thiserror::Error, Clone, Debug, Eq, PartialEq)]
+pub enum PatchError {
+    #[error("replacement range {start}..{end} is outside a transcript of {len} characters")]
+    OutOfRange { start: u32, end: u32, len: u32 },
+    #[error("windowed draft keeps {keep} characters of a transcript showing {len}")]
+    KeepOutOfRange { keep: u32, len: u32 },
+}
+
+/// A transcript built from patches: the committed text and, while the
+/// recognizer is unsure, a draft shown in its place.
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct Transcript {
+    committed: String,
+    draft: Option<String>,
+}
+
+impl Transcript {
+    /// The text to show: the draft if there is one, otherwise the committed
+    /// transcript.
+    pub fn text(&self) -> &str {
+        self.draft.as_deref().unwrap_or(&self.committed)
+    }
+
+    pub fn committed(&self) -> &str {
+        &self.committed
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.text().is_empty()
+    }
+
+    /// A patch that replaces the whole committed transcript with `text`.
+    pub fn replace_all(&self, text: String) -> TranscriptPatch {
+        TranscriptPatch::Replace {
+            range: 0..char_len(&self.committed),
+            text,
+        }
+    }
+
+    /// `patch` as the receiver of windowed drafts should get it. A draft of
+    /// at least `min_chars` characters becomes a [`TranscriptPatch::DraftTail`]
+    /// that keeps the words it shares with the text shown now, so only what
+    /// changed crosses the wire. Other patches are returned unchanged.
+    pub fn windowed(&self, patch: TranscriptPatch, min_chars: u32) -> TranscriptPatch {
+        let TranscriptPatch::Draft { full_text } = patch else {
+            return patch;
+        };
+        if char_len(&full_text) < min_chars {
+            return TranscriptPatch::Draft { full_text };
+        }
+        // Keep whole words, so a word the recognizer is revising is resent
+        // whole.
+        let shared = shared_prefix(self.text(), &full_text);
+        let keep = full_text[..shared]
+            .char_indices()
+            .rev()
+            .find(|(_, c)| c.is_whitespace())
+            .map_or(0, |(index, c)| index + c.len_utf8());
+        TranscriptPatch::DraftTail {
+            keep: char_len(&full_text[..keep]),
+            tail: full_text[keep..].to_string(),
+        }
+    }
+
+    /// Applies `patch`. A replacement outside the committed text, or a
+    /// windowed draft keeping more than is shown, leaves the transcript
+    /// unchanged.
+    pub fn apply(&mut self, patch: &TranscriptPatch) -> Result<(), PatchError> {
+        match patch {
+            TranscriptPatch::Draft { full_text } => {
+                self.draft = Some(full_text.clone());
+                return Ok(());
+            }
+            TranscriptPatch::DraftTail { keep, tail } => {
+                let shown = self.text();
+                let kept = byte_offset(shown, *keep).ok_or(PatchError::KeepOutOfRange {
+                    keep: *keep,
+                    len: char_len(shown),
+                })?;
+                let mut draft = shown[..kept].to_string();
+                draft.push_str(tail);
+                self.draft = Some(draft);
+                return Ok(());
+            }
+            TranscriptPatch::Commit { append } => self.committed.push_str(append),
+            TranscriptPatch::Replace { range, text } => {
+                let bytes = byte_range(&self.committed, range)?;
+                self.committed.replace_range(bytes, text);
+            }
+        }
+        self.draft = None;
+        Ok(())
+    }
+}
+
+/// Bytes at the start of `a` and `b` that match, ending on a character
+/// boundary of both.
+fn shared_prefix(a: &str, b: &str) -> usize {
+    a.char_indices()
+        .zip(b.chars())
+        .find(|((_, left), right)| left != right)
+        .map(|((index, _), _)| index)
+        .unwrap_or_else(|| a.len().min(b.len()))
+}
+
+fn byte_offset(text: &str, chars: u32) -> Option<usize> {
+    text.char_indices()
+        .map(|(index, _)| index)
+        .chain(std::iter::once(text.len()))
+        .nth(chars as usize)
+}
+
+fn char_len(text: &str) -> u32 {
+    u32::try_from(text.chars().count()).unwrap_or(u32::MAX)
+}
+
+fn byte_range(text: &str, range: &Range<u32>) -> Result<Range<usize>, PatchError> {
+    let out_of_range = || PatchError::OutOfRange {
+        start: range.start,
+        end: range.end,
+        len: char_len(text),
+    };
+    if range.start > range.end {
+        return Err(out_of_range());
+    }
+    match (byte_offset(text, range.start), byte_offset(text, range.end)) {
+        (Some(start), Some(end)) => Ok(start..end),
+        _ => Err(out_of_range()),
+    }
+}